@@ -4,7 +4,16 @@ import logging
 from tempfile import TemporaryDirectory
 
 from pathlib import Path
-from typing import Any, List, Optional, Union, Callable, TYPE_CHECKING, cast
+from typing import (
+    Any,
+    Callable,
+    Iterator,
+    List,
+    Optional,
+    Union,
+    TYPE_CHECKING,
+    cast,
+)
 from nrel.routee.compass.routee_compass_py import (
     CompassAppWrapper,
 )
@@ -398,6 +407,62 @@ class CompassApp:
             return results[0]
         return results
 
+    def run_streaming(
+        self,
+        queries: List[CompassQuery],
+        config: Optional[Config] = None,
+        chunk_size: int = 1000,
+    ) -> Iterator[Result]:
+        """
+        Run a batch of queries against the CompassApp, yielding each result as it
+        completes. Queries are run ``chunk_size`` at a time, and the next chunk only
+        runs once every result of the previous chunk has been consumed, so at most
+        one chunk of results is held in memory. Stopping iteration early cancels the
+        queries that have not yet run.
+
+        Args:
+            queries: A list of queries to run
+            config: optional configuration
+            chunk_size: number of queries run at a time. Queries within a chunk
+                run in parallel, so this should be at least the configured
+                parallelism. Defaults to 1000.
+
+        Returns:
+            results: An iterator over the results, in the order of the queries
+
+        Example:
+            >>> from nrel.routee.compass import CompassApp
+            >>> app = CompassApp.from_config_file("config.toml")
+            >>> for result in app.run_streaming(queries, chunk_size=100):
+                    write_result(result)
+
+        """
+        queries_str = list(map(json.dumps, queries))
+        config_str = json.dumps(config) if config is not None else None
+
+        for result_json in self._app._run_queries_streaming(
+            queries_str, config_str, chunk_size
+        ):
+            yield json.loads(result_json)
+
+    def describe_models(self) -> dict[str, Any]:
+        """
+        describe the query-time choices of the loaded traversal models, such as
+        the vehicle model names available for the "model_name" query key and the
+        query parameters each vehicle accepts
+
+        Returns:
+            description: a dict with a "traversal_models" list
+
+        Example:
+            >>> from nrel.routee.compass import CompassApp
+            >>> app = CompassApp.from_config_file("config.toml")
+            >>> energy = app.describe_models()["traversal_models"][0]
+            >>> list(energy["vehicles"])
+            ['2012_Ford_Focus', '2017_CHEVROLET_Bolt']
+        """
+        return cast(dict[str, Any], json.loads(self._app._describe_models()))
+
     def graph_edge_origin(self, edge_id: int) -> int:
         """
         get the origin vertex id for some edge
@@ -547,3 +612,53 @@ class CompassApp:
         if single_query and len(results) == 1:
             return results[0]
         return results
+
+    def run_replan(
+        self,
+        request: Union[dict[str, Any], List[dict[str, Any]]],
+        config: Optional[Config] = None,
+    ) -> Union[Result, Results]:
+        """
+        Re-plan a trip that is underway (or multiple trips) to the destination of a previous response
+
+        Args:
+            request: A request or list of requests to run. Each request must have the
+                previous 'response' and the current 'position' of the vehicle, either
+                as an 'edge_id' or as 'x' and 'y' coordinates, and may have observed
+                'state' values such as the current SOC or elapsed trip time.
+            config: optional configuration
+
+        Returns:
+            results: A list of results (or a single result if a single request was passed)
+
+        Example:
+            >>> from nrel.routee.compass import CompassApp
+            >>> app = CompassApp.from_config_file("config.toml")
+            >>> response = app.run({"origin_vertex": 0, "destination_vertex": 2})
+            >>> request = {
+                    "response": response,
+                    "position": {"edge_id": 0},
+                    "state": {"trip_time": 12.5},
+                }
+            >>> result = app.run_replan(request)
+        """
+        if isinstance(request, dict):
+            requests = [request]
+            single_request = True
+        elif isinstance(request, list):
+            requests = request
+            single_request = False
+        else:
+            raise ValueError(
+                f"Request must be a dict or list of dicts, not {type(request)}"
+            )
+
+        requests_str = list(map(json.dumps, requests))
+        config_str = json.dumps(config) if config is not None else None
+
+        results_json: List[str] = self._app._run_replan(requests_str, config_str)
+
+        results: Results = list(map(json.loads, results_json))
+        if single_request and len(results) == 1:
+            return results[0]
+        return results
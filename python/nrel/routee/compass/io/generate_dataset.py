@@ -108,6 +108,7 @@ def generate_compass_dataset(
     afdc_api_key: str = "DEMO_KEY",
     vehicle_models: Optional[List[str]] = None,
     hooks: Optional[List[DatasetHook]] = None,
+    dataset_version: Optional[str] = None,
 ) -> None:
     """
     Processes a graph downloaded via OSMNx, generating the set of input
@@ -142,6 +143,10 @@ def generate_compass_dataset(
         hooks: Optional list of callables that take a ``HookParameters`` object.
             These hooks will be called after the dataset has been generated
             and before the function returns.
+        dataset_version: If provided, a manifest of the generated files and their
+            content hashes is written to ``compass-manifest.json`` with this dataset
+            version, after the hooks have run. See the ``dataset_manifest`` system
+            configuration for verifying it when the app is built.
     Example:
         >>> import osmnx as ox
         >>> g = ox.graph_from_place("Denver, Colorado, USA")
@@ -409,6 +414,13 @@ def generate_compass_dataset(
         for hook in hooks:
             hook(params)
 
+    # WRITE DATASET MANIFEST
+    if dataset_version is not None:
+        from nrel.routee.compass.routee_compass_py import write_dataset_manifest
+
+        write_dataset_manifest(str(output_directory), dataset_version)
+        log.info(f"wrote dataset manifest with version {dataset_version}")
+
 
 def _resolve_required_model_bins(vehicle_models: List[str]) -> set[str]:
     """
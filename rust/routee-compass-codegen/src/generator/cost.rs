@@ -0,0 +1,167 @@
+use std::fs;
+use std::path::Path;
+
+use indoc::formatdoc;
+
+use super::traversal::{builder_template, mod_template, service_template};
+
+/// creates the file contents and writes to the files with template code. the cost model
+/// itself is configured through the `[cost]` section rather than provided by plugins, so
+/// a cost module is a traversal model that accumulates an edge cost, in currency, into a
+/// `trip_{name}` state feature, along with a `cost.toml` snippet that prices that feature.
+pub fn generate_cost_module(
+    pascal_case_name: &str,
+    path: &Path,
+    force: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    super::util::validate_path(path)?;
+    let snake_case_name = super::util::validate_pascal_case_name(pascal_case_name)?;
+    let feature_name = feature_name(&snake_case_name);
+    let module_dir = path.join(&snake_case_name);
+    fs::create_dir_all(&module_dir)?;
+
+    super::util::write_file(
+        module_dir.join("mod.rs").as_path(),
+        mod_template(pascal_case_name, false, false),
+        force,
+    )?;
+    super::util::write_file(
+        module_dir.join("builder.rs").as_path(),
+        builder_template(pascal_case_name),
+        force,
+    )?;
+    super::util::write_file(
+        module_dir.join("service.rs").as_path(),
+        service_template(pascal_case_name),
+        force,
+    )?;
+    super::util::write_file(
+        module_dir.join("model.rs").as_path(),
+        model_template(pascal_case_name, &feature_name),
+        force,
+    )?;
+    super::util::write_file(
+        module_dir.join("cost.toml").as_path(),
+        cost_toml_template(&snake_case_name, &feature_name),
+        force,
+    )?;
+
+    println!(
+        "✓ Generated cost module at {}/{}",
+        path.display(),
+        snake_case_name
+    );
+    println!("  Next steps:");
+    println!("  1. Add 'mod {};' to your lib.rs", snake_case_name);
+    println!("  2. Implement edge_cost in model.rs");
+    println!("  3. Register builder with inventory::submit! in your plugin registration");
+    println!("  4. Add the traversal model and the [cost] entries in cost.toml to your config");
+
+    Ok(())
+}
+
+/// name of the state feature accumulating the cost of a module
+pub fn feature_name(snake_case_name: &str) -> String {
+    format!("trip_{snake_case_name}")
+}
+
+pub fn model_template(pascal_case_name: &str, feature_name: &str) -> String {
+    let model_name = format!("{pascal_case_name}Model");
+    formatdoc!(
+        "
+        use routee_compass_core::{{
+            algorithm::search::SearchTree,
+            model::{{
+                network::{{Edge, Vertex}},
+                state::{{
+                    CustomVariableConfig, InputFeature, StateModel, StateVariable,
+                    StateVariableConfig,
+                }},
+                traversal::{{TraversalModel, TraversalModelError}},
+            }},
+        }};
+
+        /// state feature accumulating the cost of each traversed edge, in currency
+        pub const FEATURE_NAME: &str = \"{feature_name}\";
+
+        pub struct {model_name} {{}}
+
+        impl {model_name} {{
+            pub fn new() -> Self {{
+                Self {{}}
+            }}
+
+            /// the cost, in currency, of traversing an edge
+            fn edge_cost(
+                &self,
+                _edge: &Edge,
+                _state: &[StateVariable],
+                _state_model: &StateModel,
+            ) -> Result<f64, TraversalModelError> {{
+                todo!()
+            }}
+        }}
+
+        impl TraversalModel for {model_name} {{
+            fn name(&self) -> String {{
+                \"{model_name}\".to_string()
+            }}
+
+            fn input_features(&self) -> Vec<InputFeature> {{
+                vec![]
+            }}
+
+            fn output_features(&self) -> Vec<(String, StateVariableConfig)> {{
+                vec![(
+                    String::from(FEATURE_NAME),
+                    StateVariableConfig::Custom {{
+                        custom_type: String::from(\"currency\"),
+                        value: CustomVariableConfig::DEFAULT,
+                        accumulator: true,
+                    }},
+                )]
+            }}
+
+            fn traverse_edge(
+                &self,
+                trajectory: (&Vertex, &Edge, &Vertex),
+                state: &mut Vec<StateVariable>,
+                _tree: &SearchTree,
+                state_model: &StateModel,
+            ) -> Result<(), TraversalModelError> {{
+                let (_, edge, _) = trajectory;
+                let cost = self.edge_cost(edge, state, state_model)?;
+                let trip_cost = state_model.get_custom_f64(state, FEATURE_NAME)?;
+                state_model.set_custom_f64(state, FEATURE_NAME, &(trip_cost + cost))?;
+                Ok(())
+            }}
+
+            fn estimate_traversal(
+                &self,
+                _od: (&Vertex, &Vertex),
+                _state: &mut Vec<StateVariable>,
+                _tree: &SearchTree,
+                _state_model: &StateModel,
+            ) -> Result<(), TraversalModelError> {{
+                // no remaining cost is estimated, which keeps the A* heuristic admissible
+                Ok(())
+            }}
+        }}
+    "
+    )
+}
+
+pub fn cost_toml_template(snake_case_name: &str, feature_name: &str) -> String {
+    formatdoc!(
+        "
+        # add the {snake_case_name} traversal model to search.traversal.models, and these
+        # entries to the cost section, so that the accumulated cost is minimized by the search.
+        # the feature is already in currency, so it is priced with a raw vehicle rate.
+        [cost.vehicle_rates.{feature_name}]
+        type = \"raw\"
+
+        [cost.weights]
+        {feature_name} = 1.0
+    "
+    )
+}
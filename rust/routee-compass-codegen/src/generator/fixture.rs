@@ -0,0 +1,246 @@
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+use indoc::formatdoc;
+
+/// south-west corner of the generated grid, in WGS84 degrees
+const BASE_X: f64 = -105.0;
+const BASE_Y: f64 = 40.0;
+/// spacing between grid vertices in degrees, roughly 1km
+const SPACING: f64 = 0.01;
+/// edge speeds in kph for edges running east-west and north-south
+const HORIZONTAL_SPEED_KPH: f64 = 40.0;
+const VERTICAL_SPEED_KPH: f64 = 60.0;
+
+/// creates an integration test environment at path: a size x size grid network with
+/// vertices, edges, edge geometries and edge speeds, along with a compass TOML file
+/// that runs distance, speed and time traversal models over it.
+pub fn generate_fixture(
+    path: &Path,
+    size: usize,
+    force: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    super::util::validate_path(path)?;
+    write_fixture(path, size, None, force)?;
+
+    println!(
+        "✓ Generated {size}x{size} grid fixture at {}",
+        path.display()
+    );
+    println!("  Next steps:");
+    println!("  1. Load config.toml with CompassApp::try_from in an integration test");
+    println!(
+        "  2. Query between vertex ids 0 and {}, or between coordinates inside the grid",
+        size * size - 1
+    );
+    println!("  3. Add your own models to the [[search.traversal.models]] list");
+
+    Ok(())
+}
+
+/// writes the fixture files to dir. when provided, the extra traversal model is appended
+/// to the list of traversal models in the configuration.
+pub fn write_fixture(
+    dir: &Path,
+    size: usize,
+    extra_traversal_model: Option<&str>,
+    force: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if size < 2 {
+        return Err(format!("grid size must be at least 2, found {size}").into());
+    }
+    fs::create_dir_all(dir)?;
+    let grid = Grid::new(size);
+    super::util::write_file(&dir.join("vertices.csv"), grid.vertices_csv(), force)?;
+    super::util::write_file(&dir.join("edges.csv"), grid.edges_csv(), force)?;
+    super::util::write_file(
+        &dir.join("edge_geometries.txt"),
+        grid.geometries_txt(),
+        force,
+    )?;
+    super::util::write_file(&dir.join("edge_speeds.csv"), grid.speeds_csv(), force)?;
+    super::util::write_file(
+        &dir.join("config.toml"),
+        config_template(extra_traversal_model),
+        force,
+    )?;
+    Ok(())
+}
+
+/// a grid of vertices connected in both directions to their east-west and
+/// north-south neighbors. vertex ids increase by column, then by row.
+struct Grid {
+    vertices: Vec<(f64, f64)>,
+    edges: Vec<(usize, usize, f64)>,
+}
+
+impl Grid {
+    fn new(size: usize) -> Grid {
+        let mut vertices = Vec::with_capacity(size * size);
+        for row in 0..size {
+            for col in 0..size {
+                vertices.push((BASE_X + col as f64 * SPACING, BASE_Y + row as f64 * SPACING));
+            }
+        }
+        let mut edges = vec![];
+        for row in 0..size {
+            for col in 0..size {
+                let src = row * size + col;
+                if col + 1 < size {
+                    edges.push((src, src + 1, HORIZONTAL_SPEED_KPH));
+                    edges.push((src + 1, src, HORIZONTAL_SPEED_KPH));
+                }
+                if row + 1 < size {
+                    edges.push((src, src + size, VERTICAL_SPEED_KPH));
+                    edges.push((src + size, src, VERTICAL_SPEED_KPH));
+                }
+            }
+        }
+        Grid { vertices, edges }
+    }
+
+    fn vertices_csv(&self) -> String {
+        let mut out = String::from("vertex_id,x,y\n");
+        for (id, (x, y)) in self.vertices.iter().enumerate() {
+            let _ = writeln!(out, "{id},{x},{y}");
+        }
+        out
+    }
+
+    fn edges_csv(&self) -> String {
+        let mut out = String::from("edge_id,src_vertex_id,dst_vertex_id,distance\n");
+        for (id, (src, dst, _)) in self.edges.iter().enumerate() {
+            let distance = self.distance_meters(*src, *dst);
+            let _ = writeln!(out, "{id},{src},{dst},{distance:.3}");
+        }
+        out
+    }
+
+    fn geometries_txt(&self) -> String {
+        let mut out = String::new();
+        for (src, dst, _) in self.edges.iter() {
+            let (x1, y1) = self.vertices[*src];
+            let (x2, y2) = self.vertices[*dst];
+            let _ = writeln!(out, "LINESTRING ({x1} {y1}, {x2} {y2})");
+        }
+        out
+    }
+
+    fn speeds_csv(&self) -> String {
+        let mut out = String::new();
+        for (_, _, speed) in self.edges.iter() {
+            let _ = writeln!(out, "{speed:.1}");
+        }
+        out
+    }
+
+    /// equirectangular approximation, accurate enough at the scale of the grid
+    fn distance_meters(&self, src: usize, dst: usize) -> f64 {
+        let (x1, y1) = self.vertices[src];
+        let (x2, y2) = self.vertices[dst];
+        let dx = (x2 - x1) * 111_000.0 * y1.to_radians().cos();
+        let dy = (y2 - y1) * 111_000.0;
+        (dx * dx + dy * dy).sqrt()
+    }
+}
+
+pub fn config_template(extra_traversal_model: Option<&str>) -> String {
+    let extra_model = extra_traversal_model
+        .map(|name| format!("[[search.traversal.models]]\ntype = \"{name}\"\n"))
+        .unwrap_or_default();
+    formatdoc!(
+        r#"
+        [graph]
+        vertex_list_input_file = "vertices.csv"
+        [graph.edge_list]
+        input_file = "edges.csv"
+
+        [search]
+        [search.traversal]
+        type = "combined"
+        [[search.traversal.models]]
+        type = "distance"
+        distance_unit = "miles"
+        [[search.traversal.models]]
+        type = "speed"
+        speed_table_input_file = "edge_speeds.csv"
+        speed_unit = "kph"
+        [[search.traversal.models]]
+        type = "time"
+        time_unit = "minutes"
+        {extra_model}
+        [cost]
+        cost_aggregation = "sum"
+        [cost.weights]
+        trip_time = 1
+        [cost.vehicle_rates.trip_time]
+        type = "raw"
+
+        [mapping]
+        spatial_index_type = "edge"
+        matching_type = ["point", "edge_id", "vertex_id"]
+        [mapping.geometry]
+        type = "from_linestrings"
+        geometry_input_file = "edge_geometries.txt"
+        [mapping.tolerance]
+        distance = 2000.0
+        unit = "meters"
+
+        [plugin]
+        input_plugins = []
+        [[plugin.output_plugins]]
+        type = "traversal"
+        route = "edge_id"
+    "#
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use routee_compass::app::compass::CompassApp;
+
+    #[test]
+    fn test_fixture_runs_queries() {
+        let dir = tempfile::tempdir().unwrap();
+        write_fixture(dir.path(), 3, None, false).unwrap();
+        let app = CompassApp::try_from(dir.path().join("config.toml").as_path())
+            .expect("failed to load the fixture");
+
+        // a query between opposite corners by vertex id, and one by coordinates
+        let mut queries = vec![
+            serde_json::json!({"origin_vertex": 0, "destination_vertex": 8}),
+            serde_json::json!({
+                "origin_x": BASE_X + 0.1 * SPACING,
+                "origin_y": BASE_Y,
+                "destination_x": BASE_X + 2.0 * SPACING,
+                "destination_y": BASE_Y + 1.9 * SPACING
+            }),
+        ];
+        let responses = app.run(&mut queries, None).expect("run failed");
+        assert_eq!(responses.len(), 2);
+        for response in responses.iter() {
+            assert!(response.get("error").is_none(), "query failed: {response}");
+            let path = response["route"]["path"]
+                .as_array()
+                .expect("response has no route path");
+            assert!(!path.is_empty(), "{response}");
+        }
+        let corner_path = responses
+            .iter()
+            .find(|r| r["request"]["origin_vertex"] == 0)
+            .map(|r| r["route"]["path"].as_array().unwrap().len());
+        assert_eq!(
+            corner_path,
+            Some(4),
+            "a corner to corner path crosses 4 edges"
+        );
+    }
+
+    #[test]
+    fn test_fixture_rejects_small_grid() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(write_fixture(dir.path(), 1, None, false).is_err());
+    }
+}
@@ -0,0 +1,145 @@
+use std::fs;
+use std::path::Path;
+
+use indoc::formatdoc;
+
+/// creates the file contents and writes to the files with template code.
+pub fn generate_label_module(
+    pascal_case_name: &str,
+    path: &Path,
+    force: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    super::util::validate_path(path)?;
+    let snake_case_name = super::util::validate_pascal_case_name(pascal_case_name)?;
+    let module_dir = path.join(&snake_case_name);
+    fs::create_dir_all(&module_dir)?;
+
+    super::util::write_file(
+        module_dir.join("mod.rs").as_path(),
+        super::util::mod_rs_template(pascal_case_name, &["builder", "service", "model"]),
+        force,
+    )?;
+    super::util::write_file(
+        module_dir.join("builder.rs").as_path(),
+        builder_template(pascal_case_name),
+        force,
+    )?;
+    super::util::write_file(
+        module_dir.join("service.rs").as_path(),
+        service_template(pascal_case_name),
+        force,
+    )?;
+    super::util::write_file(
+        module_dir.join("model.rs").as_path(),
+        model_template(pascal_case_name),
+        force,
+    )?;
+
+    println!(
+        "✓ Generated LabelModel module at {}/{}",
+        path.display(),
+        snake_case_name
+    );
+    println!("  Next steps:");
+    println!("  1. Add 'mod {};' to your lib.rs", snake_case_name);
+    println!("  2. Implement the trait methods in each file");
+    println!("  3. Register builder with inventory::submit! in your plugin registration via add_label_model");
+
+    Ok(())
+}
+
+pub fn builder_template(pascal_case_name: &str) -> String {
+    let service_name = format!("{pascal_case_name}Service");
+    let builder_name = format!("{pascal_case_name}Builder");
+    formatdoc!("
+        use std::sync::Arc;
+
+        use super::{service_name};
+
+        use routee_compass_core::model::label::{{LabelModelBuilder, LabelModelError, LabelModelService}};
+
+        pub struct {builder_name} {{}}
+
+        impl LabelModelBuilder for {builder_name} {{
+            fn build(
+                &self,
+                _parameters: &serde_json::Value,
+            ) -> Result<Arc<dyn LabelModelService>, LabelModelError> {{
+                let service = {service_name}::new();
+                Ok(Arc::new(service))
+            }}
+        }}
+    ")
+}
+
+pub fn service_template(pascal_case_name: &str) -> String {
+    let service_name = format!("{pascal_case_name}Service");
+    let model_name = format!("{pascal_case_name}Model");
+    formatdoc!(
+        "
+        use std::sync::Arc;
+
+        use super::{model_name};
+
+        use routee_compass_core::model::{{
+            label::{{LabelModel, LabelModelError, LabelModelService}},
+            state::StateModel,
+        }};
+
+        pub struct {service_name} {{}}
+
+        impl LabelModelService for {service_name} {{
+            fn build(
+                &self,
+                _query: &serde_json::Value,
+                _state_model: Arc<StateModel>,
+            ) -> Result<Arc<dyn LabelModel>, LabelModelError> {{
+                let model = {model_name}::new();
+                Ok(Arc::new(model))
+            }}
+        }}
+
+        impl {service_name} {{
+            pub fn new() -> Self {{
+                Self {{}}
+            }}
+        }}
+    "
+    )
+}
+
+pub fn model_template(pascal_case_name: &str) -> String {
+    let model_name = format!("{pascal_case_name}Model");
+    formatdoc!(
+        "
+        use routee_compass_core::model::{{
+            label::{{Label, LabelModel, LabelModelError}},
+            network::VertexId,
+            state::{{StateModel, StateVariable}},
+        }};
+
+        pub struct {model_name} {{}}
+
+        impl {model_name} {{
+            pub fn new() -> Self {{
+                Self {{}}
+            }}
+        }}
+
+        impl LabelModel for {model_name} {{
+            fn label_from_state(
+                &self,
+                _vertex_id: VertexId,
+                _state: &[StateVariable],
+                _state_model: &StateModel,
+            ) -> Result<Label, LabelModelError> {{
+                todo!()
+            }}
+
+            fn compare(&self, _prev: &Label, _next: &Label) -> Result<std::cmp::Ordering, LabelModelError> {{
+                todo!()
+            }}
+        }}
+    "
+    )
+}
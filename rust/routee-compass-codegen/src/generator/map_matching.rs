@@ -0,0 +1,104 @@
+use std::fs;
+use std::path::Path;
+
+use heck::ToSnakeCase;
+use indoc::formatdoc;
+
+/// creates the file contents and writes to the files with template code.
+pub fn generate_map_matching_module(
+    pascal_case_name: &str,
+    path: &Path,
+    force: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    super::util::validate_path(path)?;
+    let snake_case_name = super::util::validate_pascal_case_name(pascal_case_name)?;
+    let module_dir = path.join(&snake_case_name);
+    fs::create_dir_all(&module_dir)?;
+
+    super::util::write_file(
+        module_dir.join("mod.rs").as_path(),
+        super::util::mod_rs_template(pascal_case_name, &["builder", "algorithm"]),
+        force,
+    )?;
+    super::util::write_file(
+        module_dir.join("builder.rs").as_path(),
+        builder_template(pascal_case_name),
+        force,
+    )?;
+    super::util::write_file(
+        module_dir.join("algorithm.rs").as_path(),
+        algorithm_template(pascal_case_name),
+        force,
+    )?;
+
+    println!(
+        "✓ Generated MapMatchingAlgorithm module at {}/{}",
+        path.display(),
+        snake_case_name
+    );
+    println!("  Next steps:");
+    println!("  1. Add 'mod {};' to your lib.rs", snake_case_name);
+    println!("  2. Implement the trait methods in each file");
+    println!("  3. Register builder with inventory::submit! in your plugin registration via add_map_matching_model");
+
+    Ok(())
+}
+
+pub fn builder_template(pascal_case_name: &str) -> String {
+    let builder_name = format!("{pascal_case_name}Builder");
+    let algorithm_name = format!("{pascal_case_name}Algorithm");
+    formatdoc!("
+        use std::sync::Arc;
+
+        use super::{algorithm_name};
+
+        use routee_compass_core::algorithm::map_matching::{{MapMatchingAlgorithm, MapMatchingBuilder, MapMatchingError}};
+
+        pub struct {builder_name} {{}}
+
+        impl MapMatchingBuilder for {builder_name} {{
+            fn build(
+                &self,
+                _parameters: &serde_json::Value,
+            ) -> Result<Arc<dyn MapMatchingAlgorithm>, MapMatchingError> {{
+                let algorithm = {algorithm_name}::new();
+                Ok(Arc::new(algorithm))
+            }}
+        }}
+    ")
+}
+
+pub fn algorithm_template(pascal_case_name: &str) -> String {
+    let algorithm_name = format!("{pascal_case_name}Algorithm");
+    let snake_case_name = pascal_case_name.to_snake_case();
+    formatdoc!(
+        "
+        use routee_compass_core::algorithm::{{
+            map_matching::{{MapMatchingAlgorithm, MapMatchingError, MapMatchingResult, MapMatchingTrace}},
+            search::SearchInstance,
+        }};
+
+        pub struct {algorithm_name} {{}}
+
+        impl {algorithm_name} {{
+            pub fn new() -> Self {{
+                Self {{}}
+            }}
+        }}
+
+        impl MapMatchingAlgorithm for {algorithm_name} {{
+            fn match_trace(
+                &self,
+                _trace: &MapMatchingTrace,
+                _si: &SearchInstance,
+            ) -> Result<MapMatchingResult, MapMatchingError> {{
+                todo!()
+            }}
+
+            fn name(&self) -> &str {{
+                \"{snake_case_name}\"
+            }}
+        }}
+    "
+    )
+}
@@ -1,5 +1,10 @@
 pub mod constraint;
+pub mod cost;
+pub mod fixture;
 pub mod input_plugin;
+pub mod label;
+pub mod map_matching;
+pub mod new_crate;
 pub mod output_plugin;
 pub mod traversal;
 pub mod util;
@@ -0,0 +1,304 @@
+use std::fs;
+use std::path::Path;
+
+use heck::{ToKebabCase, ToUpperCamelCase};
+use indoc::formatdoc;
+
+use super::traversal::{builder_template, mod_template, service_template};
+
+/// RouteE Compass version targeted by generated crates. keep in sync with the
+/// routee-compass and routee-compass-core crate versions.
+pub const ROUTEE_COMPASS_VERSION: &str = "0.18.0";
+const INVENTORY_VERSION: &str = "0.3.21";
+const SERDE_VERSION: &str = "1.0.228";
+const SERDE_JSON_VERSION: &str = "1.0.145";
+
+/// creates a downstream plugin crate at path/name with a Cargo.toml, a lib.rs that
+/// registers a stub traversal model with inventory, and an integration test that
+/// runs a query against a tiny grid fixture.
+pub fn generate_crate(
+    name: &str,
+    path: &Path,
+    force: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    super::util::validate_path(path)?;
+    let lib_name = super::util::validate_crate_name(name)?;
+    let crate_name = name.to_kebab_case();
+    let pascal_case_name = name.to_upper_camel_case();
+    let crate_dir = path.join(&crate_name);
+    let module_dir = crate_dir.join("src").join(&lib_name);
+    let fixture_dir = crate_dir.join("tests").join("fixtures");
+    fs::create_dir_all(&module_dir)?;
+    fs::create_dir_all(&fixture_dir)?;
+
+    super::util::write_file(
+        crate_dir.join("Cargo.toml").as_path(),
+        cargo_toml_template(&crate_name),
+        force,
+    )?;
+    super::util::write_file(
+        crate_dir.join("src").join("lib.rs").as_path(),
+        lib_template(&pascal_case_name, &lib_name),
+        force,
+    )?;
+
+    // the traversal module uses the traversal generator templates, with a model that
+    // has no inputs or outputs so that the generated crate builds and tests out of the box
+    super::util::write_file(
+        module_dir.join("mod.rs").as_path(),
+        mod_template(&pascal_case_name, false, false),
+        force,
+    )?;
+    super::util::write_file(
+        module_dir.join("builder.rs").as_path(),
+        builder_template(&pascal_case_name),
+        force,
+    )?;
+    super::util::write_file(
+        module_dir.join("service.rs").as_path(),
+        service_template(&pascal_case_name),
+        force,
+    )?;
+    super::util::write_file(
+        module_dir.join("model.rs").as_path(),
+        model_template(&pascal_case_name),
+        force,
+    )?;
+
+    super::util::write_file(
+        crate_dir
+            .join("tests")
+            .join(format!("{lib_name}_test.rs"))
+            .as_path(),
+        integration_test_template(&lib_name),
+        force,
+    )?;
+    super::fixture::write_fixture(&fixture_dir, 3, Some(&lib_name), force)?;
+
+    println!("✓ Generated plugin crate at {}", crate_dir.display());
+    println!("  Next steps:");
+    println!(
+        "  1. Implement {pascal_case_name}Model in src/{lib_name}/model.rs and add its features to tests/fixtures/config.toml"
+    );
+    println!("  2. Run 'cargo test' from {}", crate_dir.display());
+    println!("  3. Depend on '{crate_name}' from your application so its builders get registered");
+
+    Ok(())
+}
+
+pub fn cargo_toml_template(crate_name: &str) -> String {
+    formatdoc!(
+        r#"
+        [package]
+        name = "{crate_name}"
+        version = "0.1.0"
+        edition = "2021"
+
+        [dependencies]
+        routee-compass = "{ROUTEE_COMPASS_VERSION}"
+        routee-compass-core = "{ROUTEE_COMPASS_VERSION}"
+        inventory = "{INVENTORY_VERSION}"
+        serde = {{ version = "{SERDE_VERSION}", features = ["derive"] }}
+        serde_json = "{SERDE_JSON_VERSION}"
+    "#
+    )
+}
+
+pub fn lib_template(pascal_case_name: &str, lib_name: &str) -> String {
+    let builder_name = format!("{pascal_case_name}Builder");
+    formatdoc!(
+        r#"
+        mod {lib_name};
+
+        pub use {lib_name}::{{{builder_name}, {pascal_case_name}Model, {pascal_case_name}Service}};
+
+        use routee_compass::app::compass::BuilderRegistration;
+        use std::sync::Arc;
+
+        // registers the builders in this crate with RouteE Compass. any application that
+        // links this crate can then reference them by name in its configuration.
+        inventory::submit! {{
+            BuilderRegistration(|builder| {{
+                builder.add_traversal_model("{lib_name}".to_string(), Arc::new({builder_name} {{}}));
+                Ok(())
+            }})
+        }}
+    "#
+    )
+}
+
+pub fn model_template(pascal_case_name: &str) -> String {
+    let model_name = format!("{pascal_case_name}Model");
+    formatdoc!(
+        r#"
+        use routee_compass_core::{{
+            algorithm::search::SearchTree,
+            model::{{
+                network::{{Edge, Vertex}},
+                state::{{InputFeature, StateModel, StateVariable, StateVariableConfig}},
+                traversal::{{TraversalModel, TraversalModelError}},
+            }},
+        }};
+
+        pub struct {model_name} {{}}
+
+        impl {model_name} {{
+            pub fn new() -> Self {{
+                Self {{}}
+            }}
+        }}
+
+        impl Default for {model_name} {{
+            fn default() -> Self {{
+                Self::new()
+            }}
+        }}
+
+        impl TraversalModel for {model_name} {{
+            fn name(&self) -> String {{
+                "{model_name}".to_string()
+            }}
+
+            fn input_features(&self) -> Vec<InputFeature> {{
+                vec![]
+            }}
+
+            fn output_features(&self) -> Vec<(String, StateVariableConfig)> {{
+                vec![]
+            }}
+
+            fn traverse_edge(
+                &self,
+                _trajectory: (&Vertex, &Edge, &Vertex),
+                _state: &mut Vec<StateVariable>,
+                _tree: &SearchTree,
+                _state_model: &StateModel,
+            ) -> Result<(), TraversalModelError> {{
+                Ok(())
+            }}
+
+            fn estimate_traversal(
+                &self,
+                _od: (&Vertex, &Vertex),
+                _state: &mut Vec<StateVariable>,
+                _tree: &SearchTree,
+                _state_model: &StateModel,
+            ) -> Result<(), TraversalModelError> {{
+                Ok(())
+            }}
+        }}
+    "#
+    )
+}
+
+pub fn integration_test_template(lib_name: &str) -> String {
+    formatdoc!(
+        r#"
+        use routee_compass::app::compass::CompassApp;
+        use std::path::PathBuf;
+
+        // links this crate so that its builders are registered with inventory
+        use {lib_name} as _;
+
+        #[test]
+        fn test_run_query() {{
+            let conf_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                .join("tests")
+                .join("fixtures")
+                .join("config.toml");
+            let app = CompassApp::try_from(conf_file.as_path()).expect("failed to build app");
+            let mut queries = vec![serde_json::json!({{
+                "origin_vertex": 0,
+                "destination_vertex": 8
+            }})];
+            let result = app.run(&mut queries, None).expect("run failed");
+            assert_eq!(result.len(), 1, "expected one result");
+            assert!(result[0].get("error").is_none(), "query failed: {{}}", result[0]);
+            let path = result[0]["route"]["path"]
+                .as_array()
+                .expect("result has no route path");
+            assert!(!path.is_empty(), "route path should not be empty");
+        }}
+    "#
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::path::PathBuf;
+    use std::process::Command;
+
+    /// the rust workspace containing this crate
+    fn workspace_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .expect("codegen crate has a parent directory")
+            .to_path_buf()
+    }
+
+    /// points the routee-compass dependencies of a generated crate at this workspace and
+    /// makes the crate its own workspace, so that it builds against the current sources
+    fn use_workspace_dependencies(crate_dir: &Path) {
+        let cargo_toml_path = crate_dir.join("Cargo.toml");
+        let cargo_toml = fs::read_to_string(&cargo_toml_path).unwrap();
+        let mut patched = cargo_toml.clone();
+        for dependency in ["routee-compass", "routee-compass-core"] {
+            let path = workspace_dir().join(dependency);
+            patched = patched.replace(
+                &format!("{dependency} = \"{ROUTEE_COMPASS_VERSION}\""),
+                &format!(
+                    "{dependency} = {{ path = {:?} }}",
+                    path.display().to_string()
+                ),
+            );
+        }
+        assert_ne!(patched, cargo_toml, "generated Cargo.toml was not patched");
+        fs::write(&cargo_toml_path, format!("{patched}\n[workspace]\n")).unwrap();
+        // resolve the same dependency versions as this workspace, without the network
+        let lock_file = workspace_dir().join("Cargo.lock");
+        if lock_file.exists() {
+            fs::copy(lock_file, crate_dir.join("Cargo.lock")).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_generated_crate_builds_and_runs() {
+        let dir = tempfile::tempdir().unwrap();
+        generate_crate("codegen-test-plugin", dir.path(), false).unwrap();
+        let crate_dir = dir.path().join("codegen-test-plugin");
+        let src_dir = crate_dir.join("src");
+
+        // add a module from each of the other generators to the crate
+        super::super::constraint::generate_constraint_module("DistanceLimit", &src_dir, false)
+            .unwrap();
+        super::super::cost::generate_cost_module("TollCost", &src_dir, false).unwrap();
+        super::super::label::generate_label_module("SocBucket", &src_dir, false).unwrap();
+        super::super::map_matching::generate_map_matching_module("HiddenMarkov", &src_dir, false)
+            .unwrap();
+        let lib_rs = src_dir.join("lib.rs");
+        let lib = fs::read_to_string(&lib_rs).unwrap();
+        let modules = "pub mod distance_limit;\npub mod hidden_markov;\npub mod soc_bucket;\npub mod toll_cost;\n";
+        fs::write(&lib_rs, format!("{modules}{lib}")).unwrap();
+        use_workspace_dependencies(&crate_dir);
+
+        // builds the crate and runs its generated integration test, which loads the
+        // generated fixture with the stub traversal model registered by the crate
+        let cargo = std::env::var("CARGO").unwrap_or_else(|_| String::from("cargo"));
+        let output = Command::new(cargo)
+            .args(["test", "--offline", "--quiet"])
+            .current_dir(&crate_dir)
+            .env(
+                "CARGO_TARGET_DIR",
+                workspace_dir().join("target").join("codegen-test"),
+            )
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "generated crate failed to build or test:\n{}\n{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+}
@@ -1,8 +1,10 @@
 use std::path::Path;
 
 pub fn generate_output_plugin_module(
-    _pascal_case_name: &str,
-    _path: &Path,
+    pascal_case_name: &str,
+    path: &Path,
 ) -> Result<(), Box<dyn std::error::Error>> {
+    super::util::validate_path(path)?;
+    super::util::validate_pascal_case_name(pascal_case_name)?;
     Err("OutputPlugin generation not yet implemented".into())
 }
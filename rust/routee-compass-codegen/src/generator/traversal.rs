@@ -1,7 +1,6 @@
 use std::fs;
 use std::path::Path;
 
-use heck::ToSnakeCase;
 use indoc::formatdoc;
 use serde::{Deserialize, Serialize};
 
@@ -24,12 +23,8 @@ pub fn generate_traversal_module(
     extensions: Option<&TraversalExtensions>,
     force: bool,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let parent_traversal_in_path = path.to_str().map(|p| p.contains("..")).unwrap_or_default();
-    if parent_traversal_in_path {
-        return Err("provided path traverses upward with '..' which is not allowed".into());
-    }
-
-    let snake_case_name = pascal_case_name.to_snake_case();
+    super::util::validate_path(path)?;
+    let snake_case_name = super::util::validate_pascal_case_name(pascal_case_name)?;
     let module_dir = path.join(&snake_case_name);
     fs::create_dir_all(&module_dir)?;
 
@@ -135,28 +130,7 @@ pub fn mod_template(pascal_case_name: &str, typed_config: bool, engine: bool) ->
     if engine {
         entries.push("engine");
     }
-    entries.sort();
-
-    let mut result = String::new();
-
-    // import each file as a module (not pub)
-    for entry in entries.iter() {
-        let mod_row = format!("mod {entry};\n");
-        result.push_str(&mod_row);
-    }
-    result.push('\n');
-
-    // expose each type from each file (pub)
-    for entry in entries.iter() {
-        let mut entry_cap = entry.to_string();
-        if let Some(first_char) = entry_cap.get_mut(0..1) {
-            first_char.make_ascii_uppercase();
-        }
-        let use_row = format!("pub use {entry}::{pascal_case_name}{entry_cap};\n");
-        result.push_str(&use_row);
-    }
-
-    result
+    super::util::mod_rs_template(pascal_case_name, &entries)
 }
 
 pub fn builder_template(pascal_case_name: &str) -> String {
@@ -1,6 +1,96 @@
+use heck::{ToSnakeCase, ToUpperCamelCase};
 use std::fs;
 use std::path::Path;
 
+/// strict and reserved keywords of the Rust language, which cannot be used as
+/// module or type names.
+const RUST_KEYWORDS: [&str; 51] = [
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type",
+    "unsafe", "use", "where", "while", "abstract", "become", "box", "do", "final", "macro",
+    "override", "priv", "typeof", "unsized", "virtual", "yield", "try",
+];
+
+/// validates a PascalCase name used to derive generated type names (e.g., EnergyCost)
+/// and returns its snake_case form, used for the generated module name (e.g., energy_cost).
+pub fn validate_pascal_case_name(name: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let starts_uppercase = name.chars().next().is_some_and(|c| c.is_ascii_uppercase());
+    let alphanumeric = name.chars().all(|c| c.is_ascii_alphanumeric());
+    if !starts_uppercase || !alphanumeric {
+        return Err(format!(
+            "name '{name}' must be PascalCase, starting with an uppercase letter and containing only letters and digits (e.g., '{}')",
+            name.to_upper_camel_case()
+        )
+        .into());
+    }
+    let snake_case_name = name.to_snake_case();
+    if RUST_KEYWORDS.contains(&name) || RUST_KEYWORDS.contains(&snake_case_name.as_str()) {
+        return Err(format!(
+            "name '{name}' produces module '{snake_case_name}' which is a reserved Rust keyword"
+        )
+        .into());
+    }
+    Ok(snake_case_name)
+}
+
+/// validates a crate name (e.g., fancy-robot or fancy_robot) and returns its
+/// snake_case form, used as the library name.
+pub fn validate_crate_name(name: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let starts_alphabetic = name.chars().next().is_some_and(|c| c.is_ascii_alphabetic());
+    let valid_chars = name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !starts_alphabetic || !valid_chars {
+        return Err(format!(
+            "crate name '{name}' must start with a letter and contain only letters, digits, '-' and '_'"
+        )
+        .into());
+    }
+    let lib_name = name.to_snake_case();
+    if RUST_KEYWORDS.contains(&lib_name.as_str()) {
+        return Err(format!("crate name '{name}' is a reserved Rust keyword").into());
+    }
+    Ok(lib_name)
+}
+
+/// rejects output paths that traverse upward with '..'
+pub fn validate_path(path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let parent_traversal_in_path = path.to_str().map(|p| p.contains("..")).unwrap_or_default();
+    if parent_traversal_in_path {
+        return Err("provided path traverses upward with '..' which is not allowed".into());
+    }
+    Ok(())
+}
+
+/// generates a mod.rs file content which declares each entry as a private module
+/// and re-exports the type named by the entry from it, such as `pub use builder::FooBuilder;`.
+pub fn mod_rs_template(pascal_case_name: &str, entries: &[&str]) -> String {
+    let mut entries = entries.to_vec();
+    entries.sort();
+
+    let mut result = String::new();
+
+    // import each file as a module (not pub)
+    for entry in entries.iter() {
+        let mod_row = format!("mod {entry};\n");
+        result.push_str(&mod_row);
+    }
+    result.push('\n');
+
+    // expose each type from each file (pub)
+    for entry in entries.iter() {
+        let mut entry_cap = entry.to_string();
+        if let Some(first_char) = entry_cap.get_mut(0..1) {
+            first_char.make_ascii_uppercase();
+        }
+        let use_row = format!("pub use {entry}::{pascal_case_name}{entry_cap};\n");
+        result.push_str(&use_row);
+    }
+
+    result
+}
+
 /// helper for file writing with overwrite check
 pub fn write_file(
     path: &Path,
@@ -16,3 +106,46 @@ pub fn write_file(
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_validate_pascal_case_name() {
+        assert_eq!(
+            validate_pascal_case_name("EnergyCost").unwrap(),
+            "energy_cost"
+        );
+        assert_eq!(
+            validate_pascal_case_name("Soc2Bucket").unwrap(),
+            "soc2_bucket"
+        );
+        for invalid in [
+            "",
+            "energyCost",
+            "energy_cost",
+            "Energy-Cost",
+            "2Fast",
+            "Self",
+            "Type",
+        ] {
+            assert!(
+                validate_pascal_case_name(invalid).is_err(),
+                "expected '{invalid}' to be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_crate_name() {
+        assert_eq!(validate_crate_name("fancy-robot").unwrap(), "fancy_robot");
+        assert_eq!(validate_crate_name("fancy_robot").unwrap(), "fancy_robot");
+        for invalid in ["", "-robot", "fancy robot", "fancy.robot", "crate"] {
+            assert!(
+                validate_crate_name(invalid).is_err(),
+                "expected '{invalid}' to be rejected"
+            );
+        }
+    }
+}
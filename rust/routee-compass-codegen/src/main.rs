@@ -44,6 +44,19 @@ enum CompassSubcommands {
         name: String,
         /// Parent directory path to where the module should be created (e.g., src)
         path: PathBuf,
+        /// allow the user to force overwriting existing files
+        #[arg(short, long)]
+        force: bool,
+    },
+    /// Generate a new TraversalModel module accumulating a cost feature, with cost config
+    Cost {
+        /// Name of the cost in PascalCase (e.g., TollCost)
+        name: String,
+        /// Parent directory path to where the module should be created (e.g., src)
+        path: PathBuf,
+        /// allow the user to force overwriting existing files
+        #[arg(short, long)]
+        force: bool,
     },
     /// Generate a new InputPlugin module
     InputPlugin {
@@ -59,6 +72,48 @@ enum CompassSubcommands {
         /// Parent directory path to where the module should be created (e.g., src)
         path: PathBuf,
     },
+    /// Generate a new LabelModel module
+    Label {
+        /// Name of the label model in PascalCase (e.g., SocBucket)
+        name: String,
+        /// Parent directory path to where the module should be created (e.g., src)
+        path: PathBuf,
+        /// allow the user to force overwriting existing files
+        #[arg(short, long)]
+        force: bool,
+    },
+    /// Generate a new MapMatchingAlgorithm module
+    MapMatching {
+        /// Name of the map matching algorithm in PascalCase (e.g., HiddenMarkov)
+        name: String,
+        /// Parent directory path to where the module should be created (e.g., src)
+        path: PathBuf,
+        /// allow the user to force overwriting existing files
+        #[arg(short, long)]
+        force: bool,
+    },
+    /// Generate a grid network and compass TOML config for integration tests
+    Fixture {
+        /// Directory path to where the fixture files should be created (e.g., tests/fixtures)
+        path: PathBuf,
+        /// number of vertices along each side of the grid
+        #[arg(long, default_value_t = 5)]
+        size: usize,
+        /// allow the user to force overwriting existing files
+        #[arg(short, long)]
+        force: bool,
+    },
+    /// Generate a new plugin crate with a stub TraversalModel registered via inventory
+    NewCrate {
+        /// Name of the crate (e.g., fancy-robot)
+        name: String,
+        /// Parent directory path to where the crate should be created
+        #[arg(default_value = ".")]
+        path: PathBuf,
+        /// allow the user to force overwriting existing files
+        #[arg(short, long)]
+        force: bool,
+    },
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -80,11 +135,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 force,
             )?;
         }
-        CompassSubcommands::Constraint { name, path } => {
+        CompassSubcommands::Constraint { name, path, force } => {
             routee_compass_codegen::generator::constraint::generate_constraint_module(
-                &name, &path,
+                &name, &path, force,
             )?;
         }
+        CompassSubcommands::Cost { name, path, force } => {
+            routee_compass_codegen::generator::cost::generate_cost_module(&name, &path, force)?;
+        }
         CompassSubcommands::InputPlugin { name, path } => {
             routee_compass_codegen::generator::input_plugin::generate_input_plugin_module(
                 &name, &path,
@@ -95,6 +153,20 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 &name, &path,
             )?;
         }
+        CompassSubcommands::Label { name, path, force } => {
+            routee_compass_codegen::generator::label::generate_label_module(&name, &path, force)?;
+        }
+        CompassSubcommands::MapMatching { name, path, force } => {
+            routee_compass_codegen::generator::map_matching::generate_map_matching_module(
+                &name, &path, force,
+            )?;
+        }
+        CompassSubcommands::Fixture { path, size, force } => {
+            routee_compass_codegen::generator::fixture::generate_fixture(&path, size, force)?;
+        }
+        CompassSubcommands::NewCrate { name, path, force } => {
+            routee_compass_codegen::generator::new_crate::generate_crate(&name, &path, force)?;
+        }
     }
 
     Ok(())
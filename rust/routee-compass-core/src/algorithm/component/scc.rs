@@ -145,6 +145,93 @@ pub fn largest_strongly_connected_component(graph: &Graph) -> Result<Vec<VertexI
     Ok(largest_component)
 }
 
+/// Labels each vertex with the strongly connected component containing it, using an
+/// iterative form of Tarjan's algorithm so that large graphs do not overflow the stack.
+/// Only edges in the graph adjacencies are followed.
+///
+/// # Arguments
+///
+/// * `graph` - A directed graph to find strongly connected components in.
+///
+/// # Returns
+///
+/// For each VertexId, the id of its component. Component ids are ordered by decreasing
+/// number of vertices, so component 0 is the largest.
+pub fn strongly_connected_component_ids(graph: &Graph) -> Vec<usize> {
+    const UNVISITED: usize = usize::MAX;
+    let n_vertices = graph.n_vertices();
+    let mut index = vec![UNVISITED; n_vertices];
+    let mut lowlink = vec![0; n_vertices];
+    let mut on_stack = vec![false; n_vertices];
+    let mut stack: Vec<usize> = vec![];
+    let mut component = vec![UNVISITED; n_vertices];
+    let mut n_components = 0;
+    let mut next_index = 0;
+    // each frame is a vertex and the position of the next out edge to explore
+    let mut call_stack: Vec<(usize, usize)> = vec![];
+
+    for root in 0..n_vertices {
+        if index[root] != UNVISITED {
+            continue;
+        }
+        index[root] = next_index;
+        lowlink[root] = next_index;
+        next_index += 1;
+        stack.push(root);
+        on_stack[root] = true;
+        call_stack.push((root, 0));
+
+        while let Some(frame) = call_stack.last_mut() {
+            let v = frame.0;
+            match graph.adj[v].get_index(frame.1) {
+                Some((_, dst)) => {
+                    frame.1 += 1;
+                    let w = dst.0;
+                    if index[w] == UNVISITED {
+                        index[w] = next_index;
+                        lowlink[w] = next_index;
+                        next_index += 1;
+                        stack.push(w);
+                        on_stack[w] = true;
+                        call_stack.push((w, 0));
+                    } else if on_stack[w] {
+                        lowlink[v] = lowlink[v].min(index[w]);
+                    }
+                }
+                None => {
+                    call_stack.pop();
+                    if let Some((parent, _)) = call_stack.last() {
+                        lowlink[*parent] = lowlink[*parent].min(lowlink[v]);
+                    }
+                    if lowlink[v] == index[v] {
+                        while let Some(w) = stack.pop() {
+                            on_stack[w] = false;
+                            component[w] = n_components;
+                            if w == v {
+                                break;
+                            }
+                        }
+                        n_components += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    // renumber components from largest to smallest
+    let mut sizes = vec![0; n_components];
+    for c in component.iter() {
+        sizes[*c] += 1;
+    }
+    let mut order: Vec<usize> = (0..n_components).collect();
+    order.sort_by_key(|c| (std::cmp::Reverse(sizes[*c]), *c));
+    let mut rank = vec![0; n_components];
+    for (r, c) in order.into_iter().enumerate() {
+        rank[c] = r;
+    }
+    component.into_iter().map(|c| rank[c]).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use indexmap::IndexMap;
@@ -197,6 +284,8 @@ mod tests {
             edge_lists: vec![EdgeList(edges.into_boxed_slice())],
             adj: adj.into_boxed_slice(),
             rev: rev.into_boxed_slice(),
+            reverse_edges: None,
+            components: None,
         }
     }
 
@@ -211,6 +300,13 @@ mod tests {
         assert!(component.contains(&VertexId(3)));
     }
 
+    #[test]
+    fn test_strongly_connected_component_ids() {
+        let graph = build_mock_graph();
+        let ids = strongly_connected_component_ids(&graph);
+        assert_eq!(ids, vec![0, 0, 0, 0, 1]);
+    }
+
     #[test]
     fn test_all_strongly_connected_components() {
         let graph = build_mock_graph();
@@ -6,12 +6,32 @@ use serde::{Deserialize, Serialize};
 pub struct MapMatchingTrace {
     /// Ordered sequence of GPS points in the trace
     pub points: Vec<MapMatchingPoint>,
+    /// Seed for any randomness used while matching this trace. When absent,
+    /// stochastic algorithms draw a seed from the operating system.
+    #[serde(default)]
+    pub seed: Option<u64>,
 }
 
 impl MapMatchingTrace {
     /// Creates a new trace from a vector of points.
     pub fn new(points: Vec<MapMatchingPoint>) -> Self {
-        Self { points }
+        Self { points, seed: None }
+    }
+
+    /// Sets the seed used by stochastic algorithms when matching this trace.
+    pub fn with_seed(mut self, seed: Option<u64>) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Derives a seed for this trace from a global seed and the trace coordinates,
+    /// so that each trace receives its own random stream that does not depend on
+    /// batch order or thread count.
+    pub fn derive_seed(&self, seed: u64) -> u64 {
+        self.points.iter().fold(splitmix64(seed), |acc, p| {
+            let xy = ((p.coord.x().to_bits() as u64) << 32) | p.coord.y().to_bits() as u64;
+            splitmix64(acc ^ xy)
+        })
     }
 
     /// Returns the number of points in the trace.
@@ -25,6 +45,14 @@ impl MapMatchingTrace {
     }
 }
 
+/// the SplitMix64 finalizer, used to mix seed material
+fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9e3779b97f4a7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
 /// A single GPS point in a trace.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MapMatchingPoint {
@@ -61,4 +89,13 @@ mod tests {
         assert!(trace.is_empty());
         assert_eq!(trace.len(), 0);
     }
+
+    #[test]
+    fn test_derive_seed() {
+        let a = MapMatchingTrace::new(vec![MapMatchingPoint::new(point!(x: -105.0, y: 40.0))]);
+        let b = MapMatchingTrace::new(vec![MapMatchingPoint::new(point!(x: -105.1, y: 40.0))]);
+        assert_eq!(a.derive_seed(42), a.clone().derive_seed(42));
+        assert_ne!(a.derive_seed(42), a.derive_seed(43));
+        assert_ne!(a.derive_seed(42), b.derive_seed(42));
+    }
 }
@@ -7,6 +7,8 @@ use crate::algorithm::map_matching::map_matching_trace::MapMatchingTrace;
 use crate::algorithm::map_matching::model::lcss::trajectory_segment;
 use crate::algorithm::search::SearchInstance;
 use crate::model::unit::DistanceUnit;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 use serde::{Deserialize, Serialize};
 use uom::si::f64::Length;
 
@@ -33,7 +35,8 @@ pub struct LcssConfig {
 /// - `distance_epsilon`: The distance epsilon to use for matching (default: 50.0 meters)
 /// - `similarity_cutoff`: The similarity cutoff to use for stopping the algorithm (default: 0.9)
 /// - `cutting_threshold`: The distance threshold to use for computing cutting points (default: 10.0 meters)
-/// - `random_cuts`: The number of random cuts to add at each iteration (default: 0)
+/// - `random_cuts`: The number of random cuts to add at each iteration (default: 0).
+///   Cuts are drawn from a generator seeded by the trace seed, if provided.
 /// - `distance_threshold`: The distance threshold above which no match is made (default: 10000.0)
 #[derive(Debug, Clone)]
 pub struct LcssMapMatching {
@@ -75,13 +78,6 @@ impl MapMatchingAlgorithm for LcssMapMatching {
             return Err(MapMatchingError::EmptyTrace);
         }
 
-        // LCSS map matching requires an edge-oriented spatial index
-        if !si.map_model.spatial_index.is_edge_oriented() {
-            return Err(MapMatchingError::InternalError(
-                "LCSS map matching requires an edge-oriented spatial index.".to_string(),
-            ));
-        }
-
         let stationary_indices = lcss_ops::find_stationary_points(trace);
         let skip_indices: std::collections::HashSet<_> = stationary_indices
             .iter()
@@ -96,12 +92,16 @@ impl MapMatchingAlgorithm for LcssMapMatching {
             .map(|(_, p)| p.clone())
             .collect();
         let sub_trace = MapMatchingTrace::new(sub_trace_points);
+        let mut rng = match trace.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => rand::make_rng(),
+        };
 
         let initial_path = lcss_ops::new_path_for_trace(&sub_trace, si)?;
         let mut initial_segment = TrajectorySegment::new(sub_trace.clone(), initial_path);
 
         initial_segment.score_and_match(self, si)?;
-        initial_segment.compute_cutting_points(self);
+        initial_segment.compute_cutting_points(self, &mut rng);
 
         let mut scheme = initial_segment.split_segment(si)?;
 
@@ -111,7 +111,7 @@ impl MapMatchingAlgorithm for LcssMapMatching {
 
             for mut segment in scheme.clone() {
                 segment.score_and_match(self, si)?;
-                segment.compute_cutting_points(self);
+                segment.compute_cutting_points(self, &mut rng);
 
                 if segment.score >= self.similarity_cutoff {
                     next_scheme.push(segment);
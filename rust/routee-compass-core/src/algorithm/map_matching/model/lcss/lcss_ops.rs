@@ -2,11 +2,10 @@ use crate::algorithm::map_matching::map_matching_error::MapMatchingError;
 use crate::algorithm::map_matching::map_matching_result::PointMatch;
 use crate::algorithm::map_matching::map_matching_trace::MapMatchingTrace;
 use crate::algorithm::search::a_star::run_vertex_oriented;
-use crate::algorithm::search::{Direction, SearchError, SearchInstance};
-use crate::model::map::NearestSearchResult;
+use crate::algorithm::search::{Direction, SearchError, SearchInstance, SearchTreeStorage};
+use crate::model::map::{spatial_index_ops, NearestSearchResult, SpatialIndexType};
 use crate::model::network::{EdgeId, EdgeListId, VertexId};
 use crate::util::geo::haversine;
-use geo::ClosestPoint;
 use uom::si::f64::Length;
 use uom::si::length::meter;
 
@@ -32,17 +31,10 @@ pub(crate) fn compute_distance_to_edge(
     edge_id: &EdgeId,
     si: &SearchInstance,
 ) -> Length {
-    if let Ok(linestring) = si.map_model.get_linestring(edge_list_id, edge_id) {
-        match linestring.closest_point(point) {
-            geo::Closest::SinglePoint(p) | geo::Closest::Intersection(p) => {
-                haversine::haversine_distance(point.x(), point.y(), p.x(), p.y())
-                    .unwrap_or_else(|_| Length::new::<meter>(f64::INFINITY))
-            }
-            geo::Closest::Indeterminate => Length::new::<meter>(f64::INFINITY),
-        }
-    } else {
-        Length::new::<meter>(f64::INFINITY)
-    }
+    si.map_model
+        .get_linestring(edge_list_id, edge_id)
+        .and_then(|linestring| spatial_index_ops::linestring_distance(linestring, point))
+        .unwrap_or_else(|_| Length::new::<meter>(f64::INFINITY))
 }
 
 /// Finds the closest vertex (source or destination) of an edge to a given point.
@@ -116,7 +108,15 @@ pub(crate) fn run_shortest_path(
     end: VertexId,
     si: &SearchInstance,
 ) -> Result<Vec<(EdgeListId, EdgeId)>, MapMatchingError> {
-    match run_vertex_oriented(start, Some(end), &Direction::Forward, true, si) {
+    match run_vertex_oriented(
+        start,
+        Some(end),
+        &Direction::Forward,
+        true,
+        None,
+        SearchTreeStorage::Nodes,
+        si,
+    ) {
         Ok(search_result) => match search_result.tree.backtrack(end) {
             Ok(path) => {
                 let edge_ids = path
@@ -127,7 +127,9 @@ pub(crate) fn run_shortest_path(
             }
             Err(e) => Err(MapMatchingError::SearchTreeError(e)),
         },
-        Err(SearchError::NoPathExistsBetweenVertices(_, _, _)) => Ok(Vec::new()),
+        Err(SearchError::NoPathExistsBetweenVertices(_, _, _) | SearchError::NoPathExists(_)) => {
+            Ok(Vec::new())
+        }
         Err(e) => Err(MapMatchingError::SearchError(e)),
     }
 }
@@ -141,7 +143,7 @@ pub(crate) fn find_candidates(
 ) -> Result<Vec<(EdgeListId, EdgeId, Length)>, MapMatchingError> {
     let nearest_iter = si
         .map_model
-        .spatial_index
+        .get_spatial_index(&SpatialIndexType::EdgeOriented)
         .nearest_graph_id_iter(point)
         .take(k);
 
@@ -159,7 +161,7 @@ pub(crate) fn find_candidates(
     if candidates.is_empty() {
         let nearest = si
             .map_model
-            .spatial_index
+            .get_spatial_index(&SpatialIndexType::EdgeOriented)
             .nearest_graph_id(point)
             .map_err(|e| {
                 MapMatchingError::InternalError(format!("spatial index query failed: {}", e))
@@ -4,6 +4,7 @@ use crate::algorithm::map_matching::map_matching_trace::MapMatchingTrace;
 use crate::algorithm::search::SearchInstance;
 use crate::model::network::{EdgeId, EdgeListId};
 use itertools::Itertools;
+use rand::{Rng, RngExt};
 use uom::si::f64::Length;
 use uom::si::length::meter;
 
@@ -168,7 +169,7 @@ impl TrajectorySegment {
     ///
     /// # Arguments
     /// * `lcss` - The LCSS configuration containing epsilon and threshold parameters.
-    pub(crate) fn compute_cutting_points(&mut self, lcss: &LcssMapMatching) {
+    pub(crate) fn compute_cutting_points(&mut self, lcss: &LcssMapMatching, rng: &mut impl Rng) {
         let mut cutting_points = Vec::new();
 
         let no_match = self
@@ -205,10 +206,17 @@ impl TrajectorySegment {
             }
         }
 
-        let compressed_points = compress(cutting_points);
+        let mut compressed_points = compress(cutting_points);
 
-        // Filter out start/end and points directly adjacent to them since it doesn't make sense to split there
+        // Add random cuts, which can help the matcher escape a poor local split
         let n = self.trace.len();
+        if n > 0 {
+            for _ in 0..lcss.random_cuts {
+                compressed_points.push(rng.random_range(0..n));
+            }
+        }
+
+        // Filter out start/end and points directly adjacent to them since it doesn't make sense to split there
         self.cutting_points = compressed_points
             .into_iter()
             .unique()
@@ -1,68 +1,189 @@
 use crate::algorithm::search::a_star::frontier_instance::FrontierInstance;
 use crate::algorithm::search::Direction;
 use crate::algorithm::search::EdgeTraversal;
+use crate::algorithm::search::NoPathDiagnostics;
 use crate::algorithm::search::SearchError;
 use crate::algorithm::search::SearchInstance;
 use crate::algorithm::search::SearchResult;
 use crate::algorithm::search::SearchTree;
+use crate::algorithm::search::SearchTreeBudget;
+use crate::algorithm::search::SearchTreeStorage;
+use crate::algorithm::search::SettledLabel;
 use crate::model::cost::TraversalCost;
 use crate::model::label::Label;
 use crate::model::network::EdgeListId;
 use crate::model::network::{EdgeId, VertexId};
 use crate::model::state::StateVariable;
-use crate::model::unit::Cost;
 use crate::model::unit::ReverseCost;
+use crate::model::unit::{AsF64, Cost};
 use crate::util::priority_queue::InternalPriorityQueue;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::time::Instant;
 
 /// run an A* Search over the given directed graph model. traverses links
 /// from the source, via the provided direction, to the target. uses the
 /// provided traversal model for state updates and link costs. estimates
 /// the distance to the destination (the a* heuristic) using the provided
-/// cost estimate function.
+/// cost estimate function. if a memory budget is provided, it is applied
+/// to the search tree as the search runs, and the tree stores the result
+/// states of its branches as specified by the tree storage.
 pub fn run_vertex_oriented(
     source: VertexId,
     target: Option<VertexId>,
     direction: &Direction,
     a_star: bool,
+    budget: Option<&SearchTreeBudget>,
+    storage: SearchTreeStorage,
     si: &SearchInstance,
 ) -> Result<SearchResult, SearchError> {
     log::debug!(
         "sssp::run_vertex_oriented: source: {source}, target: {target:?}, direction: {direction:?}, astar: {a_star}"
     );
-    if target == Some(source) {
-        let initial_state = si.state_model.initial_state(None)?;
+    run_search(
+        &[source],
+        target.as_slice(),
+        None,
+        direction,
+        a_star,
+        budget,
+        storage,
+        si,
+    )
+}
+
+/// run a search that starts from every source vertex at once, as if from a virtual
+/// super-source connected to each of them at zero cost, and ends once the cheapest
+/// reachable target vertex is settled. this lets map matching retain several candidate
+/// origins and destinations and leaves it to the search to pick the best pair. the
+/// A* cost estimate, if used, is the smallest estimate to any target.
+pub fn run_vertex_oriented_candidates(
+    sources: &[VertexId],
+    targets: &[VertexId],
+    direction: &Direction,
+    a_star: bool,
+    budget: Option<&SearchTreeBudget>,
+    storage: SearchTreeStorage,
+    si: &SearchInstance,
+) -> Result<SearchResult, SearchError> {
+    log::debug!(
+        "sssp::run_vertex_oriented_candidates: sources: {}, targets: {}, direction: {direction:?}, astar: {a_star}",
+        sources.len(),
+        targets.len()
+    );
+    if sources.is_empty() {
+        return Err(SearchError::BuildError(String::from(
+            "search requires at least one source vertex",
+        )));
+    }
+    run_search(
+        sources, targets, None, direction, a_star, budget, storage, si,
+    )
+}
+
+/// run a one-to-many Dijkstra search from the source that terminates once every
+/// target has been settled, or the frontier is exhausted. the resulting tree can be
+/// backtracked to find the shortest path to each reachable target. no cost estimate
+/// is used, as there is no single destination to guide the search toward.
+pub fn run_vertex_oriented_many(
+    source: VertexId,
+    targets: &[VertexId],
+    direction: &Direction,
+    budget: Option<&SearchTreeBudget>,
+    storage: SearchTreeStorage,
+    si: &SearchInstance,
+) -> Result<SearchResult, SearchError> {
+    log::debug!(
+        "sssp::run_vertex_oriented_many: source: {source}, targets: {}, direction: {direction:?}",
+        targets.len()
+    );
+    let remaining = targets
+        .iter()
+        .filter(|t| **t != source)
+        .copied()
+        .collect::<HashSet<_>>();
+    if remaining.is_empty() {
+        return run_search(
+            &[source],
+            &[source],
+            None,
+            direction,
+            false,
+            budget,
+            storage,
+            si,
+        );
+    }
+    run_search(
+        &[source],
+        &[],
+        Some(remaining),
+        direction,
+        false,
+        budget,
+        storage,
+        si,
+    )
+}
+
+/// the search loop shared by the one-to-one, one-to-many and candidate searches. each
+/// source is a root of the search tree. the search ends once any of the targets has been
+/// settled, or, if a set of remaining targets is provided, once all of them have been settled.
+#[allow(clippy::too_many_arguments)]
+fn run_search(
+    sources: &[VertexId],
+    targets: &[VertexId],
+    mut remaining_targets: Option<HashSet<VertexId>>,
+    direction: &Direction,
+    a_star: bool,
+    budget: Option<&SearchTreeBudget>,
+    storage: SearchTreeStorage,
+    si: &SearchInstance,
+) -> Result<SearchResult, SearchError> {
+    let source = sources[0];
+    if let Some(source) = sources.iter().find(|s| targets.contains(s)) {
+        let initial_state = si.initial_state()?;
         let initial_label =
             si.label_model
-                .label_from_state(source, &initial_state, &si.state_model)?;
-        let tree = SearchTree::with_root(initial_label, *direction);
+                .label_from_state(*source, &initial_state, &si.state_model)?;
+        let mut tree = SearchTree::with_storage(*direction, storage);
+        tree.set_root(initial_label);
         return Ok(SearchResult::completed(tree, 0));
     }
+    // a single target is handled when popping the frontier, several targets by the search loop
+    let target = match targets {
+        [target] => Some(*target),
+        _ => None,
+    };
 
     // context for the search (graph, search functions, frontier priority queue)
     let mut frontier: InternalPriorityQueue<Label, ReverseCost> = InternalPriorityQueue::default();
     let mut traversal_costs: HashMap<Label, Cost> = HashMap::new();
-    let mut solution = SearchTree::new(*direction);
-
-    // setup initial search state
-    let initial_state = si.state_model.initial_state(None)?;
-    let inital_label = si
-        .label_model
-        .label_from_state(source, &initial_state, &si.state_model)?;
-    traversal_costs.insert(inital_label.clone(), Cost::ZERO);
-    let origin_cost = match (target, a_star) {
-        (Some(target), true) => {
-            let cost_est = estimate_traversal_cost(source, target, &initial_state, &solution, si)?;
-            cost_est.objective_cost
+    let mut solution = SearchTree::with_storage(*direction, storage);
+
+    // setup initial search state. with several sources, each is added to the tree as a root
+    let initial_state = si.initial_state()?;
+    for source in sources.iter() {
+        let inital_label =
+            si.label_model
+                .label_from_state(*source, &initial_state, &si.state_model)?;
+        if sources.len() > 1 {
+            solution.add_root(inital_label.clone());
         }
-        _ => Cost::ZERO,
-    };
-    frontier.push(inital_label, origin_cost.into());
+        traversal_costs.insert(inital_label.clone(), Cost::ZERO);
+        let origin_cost = match a_star {
+            true => estimate_min_traversal_cost(*source, targets, &initial_state, &solution, si)?,
+            false => Cost::ZERO,
+        };
+        frontier.push(inital_label, origin_cost.into());
+    }
 
     let start_time = Instant::now();
     let mut iterations = 0;
+    let mut max_frontier_size = frontier.len();
+    let mut peak_tree_size = solution.len();
+    let mut constraint_rejections = 0;
+    let mut settled: Vec<SettledLabel> = vec![];
 
     loop {
         // terminate the search if a termination condition was met.
@@ -70,7 +191,19 @@ pub fn run_vertex_oriented(
             si.termination_model
                 .continue_or_explain(&start_time, &solution, iterations)
         {
-            return Ok(SearchResult::terminated(solution, iterations, explanation));
+            let result = SearchResult::terminated(solution, iterations, explanation)
+                .with_max_frontier_size(max_frontier_size)
+                .with_peak_tree_size(peak_tree_size)
+                .with_settled(settled);
+            return Ok(result);
+        }
+
+        // enforce the search tree memory budget, which may prune the tree
+        if let Some(budget) = budget {
+            let pruned = budget.apply(&mut solution, &traversal_costs, iterations)?;
+            if pruned > 0 {
+                log::debug!("search tree memory budget exceeded, pruned {pruned} labels");
+            }
         }
 
         // grab the frontier assets, or break if there is nothing to pop
@@ -80,11 +213,51 @@ pub fn run_vertex_oriented(
             target,
             &solution,
             &initial_state,
-        )? {
-            None => break,
-            Some(f) => f,
+        ) {
+            Ok(None) if targets.len() > 1 => {
+                let diagnostics = NoPathDiagnostics::new(
+                    source,
+                    targets[0],
+                    &solution,
+                    &si.graph,
+                    constraint_rejections,
+                );
+                return Err(SearchError::NoPathExists(Box::new(diagnostics)));
+            }
+            Ok(None) => break,
+            Ok(Some(f)) => f,
+            Err(SearchError::NoPathExistsBetweenVertices(src, dst, _)) => {
+                let diagnostics =
+                    NoPathDiagnostics::new(src, dst, &solution, &si.graph, constraint_rejections);
+                return Err(SearchError::NoPathExists(Box::new(diagnostics)));
+            }
+            Err(e) => return Err(e),
         };
 
+        if si.record_settled {
+            settled.push(SettledLabel {
+                vertex_id: *f.prev_label.vertex_id(),
+                cost: traversal_costs
+                    .get(&f.prev_label)
+                    .copied()
+                    .unwrap_or(Cost::ZERO),
+                priority: f.priority,
+            });
+        }
+
+        // a search with several targets ends once the first of them has been settled
+        if targets.len() > 1 && targets.contains(f.prev_label.vertex_id()) {
+            break;
+        }
+
+        // a one-to-many search ends once every target has been settled
+        if let Some(remaining) = remaining_targets.as_mut() {
+            remaining.remove(f.prev_label.vertex_id());
+            if remaining.is_empty() {
+                break;
+            }
+        }
+
         let prev_gscore = traversal_costs
             .get(&f.prev_label)
             .unwrap_or(&Cost::INFINITY)
@@ -93,6 +266,13 @@ pub fn run_vertex_oriented(
         // visit all neighbors of this source vertex
         let incident_edge_iterator = direction.get_incident_edges(f.prev_label.vertex_id(), si);
         for (edge_list_id, edge_id) in incident_edge_iterator {
+            if si
+                .corridor
+                .as_ref()
+                .is_some_and(|c| !c.contains(edge_list_id, edge_id))
+            {
+                continue;
+            }
             let e = si.graph.get_edge(edge_list_id, edge_id)?;
 
             let terminal_vertex_id = direction.terminal_vertex_id(e);
@@ -111,16 +291,28 @@ pub fn run_vertex_oriented(
                 si.get_constraint_model(edge_list_id)?.valid_frontier(
                     e,
                     previous_edge,
+                    &solution,
+                    direction,
                     &f.prev_state,
                     &si.state_model,
                 )?
             };
             if !valid_frontier {
+                constraint_rejections += 1;
                 continue;
             }
 
             let next_edge = (*edge_list_id, *edge_id);
             let et = EdgeTraversal::new(next_edge, &solution, &f.prev_state, si)?;
+            let valid_traversal = si.get_constraint_model(edge_list_id)?.valid_traversal(
+                e,
+                &et.result_state,
+                &si.state_model,
+            )?;
+            if !valid_traversal {
+                constraint_rejections += 1;
+                continue;
+            }
             let key_label = si.label_model.label_from_state(
                 key_vertex_id,
                 &et.result_state,
@@ -128,6 +320,12 @@ pub fn run_vertex_oriented(
             )?;
 
             let tentative_gscore = prev_gscore + et.cost.objective_cost;
+            if si
+                .max_cost
+                .is_some_and(|max_cost| tentative_gscore > max_cost)
+            {
+                continue;
+            }
 
             let existing_gscore = traversal_costs
                 .get(&key_label)
@@ -139,29 +337,28 @@ pub fn run_vertex_oriented(
                 traversal_costs.insert(key_label.clone(), tentative_gscore);
                 solution.insert(
                     terminal_label,
-                    et.clone(),
+                    et,
                     key_label.clone(),
                     si.label_model.clone(),
                 )?;
 
-                let dst_h_cost = match (target, a_star) {
-                    (Some(target), true) => {
-                        let cost_est = estimate_traversal_cost(
-                            key_vertex_id,
-                            target,
-                            &initial_state,
-                            &solution,
-                            si,
-                        )?;
-                        cost_est.objective_cost
-                    }
-                    _ => Cost::ZERO,
+                let dst_h_cost = match a_star {
+                    true => estimate_min_traversal_cost(
+                        key_vertex_id,
+                        targets,
+                        &initial_state,
+                        &solution,
+                        si,
+                    )?,
+                    false => Cost::ZERO,
                 };
 
                 let f_score_value = tentative_gscore + dst_h_cost;
                 frontier.push_increase(key_label, f_score_value.into());
             }
         }
+        max_frontier_size = max_frontier_size.max(frontier.len());
+        peak_tree_size = peak_tree_size.max(solution.len());
         iterations += 1;
     }
     log::debug!(
@@ -170,7 +367,10 @@ pub fn run_vertex_oriented(
         solution.len()
     );
 
-    let result = SearchResult::completed(solution, iterations);
+    let result = SearchResult::completed(solution, iterations)
+        .with_max_frontier_size(max_frontier_size)
+        .with_peak_tree_size(peak_tree_size)
+        .with_settled(settled);
     Ok(result)
 }
 
@@ -183,6 +383,8 @@ pub fn run_edge_oriented(
     target: Option<(EdgeListId, EdgeId)>,
     direction: &Direction,
     a_star: bool,
+    budget: Option<&SearchTreeBudget>,
+    storage: SearchTreeStorage,
     si: &SearchInstance,
 ) -> Result<SearchResult, SearchError> {
     // For now, convert to vertex-oriented search and use compatibility layer
@@ -190,25 +392,56 @@ pub fn run_edge_oriented(
     let e1_dst = si.graph.dst_vertex_id(&source.0, &source.1)?;
 
     match target {
-        None => run_vertex_oriented(e1_dst, None, direction, a_star, si),
+        None => run_vertex_oriented(e1_dst, None, direction, a_star, budget, storage, si),
         Some(target_edge) => {
             let e2_src = si.graph.src_vertex_id(&target_edge.0, &target_edge.1)?;
             let _e2_dst = si.graph.dst_vertex_id(&target_edge.0, &target_edge.1)?;
 
             if source == target_edge {
-                let initial_state = si.state_model.initial_state(None)?;
+                let initial_state = si.initial_state()?;
                 let initial_label =
                     si.label_model
                         .label_from_state(e1_dst, &initial_state, &si.state_model)?;
-                let tree = SearchTree::with_root(initial_label, *direction);
+                let mut tree = SearchTree::with_storage(*direction, storage);
+                tree.set_root(initial_label);
                 Ok(SearchResult::completed(tree, 0))
             } else {
-                run_vertex_oriented(e1_dst, Some(e2_src), direction, a_star, si)
+                run_vertex_oriented(e1_dst, Some(e2_src), direction, a_star, budget, storage, si)
             }
         }
     }
 }
 
+/// the smallest estimated objective cost from a vertex to any of the targets, which is
+/// zero if there are no targets. uses the search instance heuristic when one is
+/// configured, and otherwise the traversal model estimates. the estimate is scaled
+/// by the heuristic weight of the search instance for weighted A*.
+fn estimate_min_traversal_cost(
+    src: VertexId,
+    targets: &[VertexId],
+    state: &[StateVariable],
+    tree: &SearchTree,
+    si: &SearchInstance,
+) -> Result<Cost, SearchError> {
+    let mut min_cost: Option<Cost> = None;
+    for target in targets.iter() {
+        let cost_est = match &si.heuristic {
+            Some(heuristic) => heuristic.estimate_cost(src, *target, state, si)?,
+            None => estimate_traversal_cost(src, *target, state, tree, si)?.objective_cost,
+        };
+        if min_cost.is_none_or(|c| cost_est < c) {
+            min_cost = Some(cost_est);
+        }
+    }
+    match min_cost {
+        Some(cost) if si.heuristic_weight != 1.0 => {
+            Ok(Cost::new(cost.as_f64() * si.heuristic_weight))
+        }
+        Some(cost) => Ok(cost),
+        None => Ok(Cost::ZERO),
+    }
+}
+
 /// approximates the traversal state delta between two vertices and uses
 /// the result to compute a cost estimate.
 pub fn estimate_traversal_cost(
@@ -235,6 +468,7 @@ pub fn estimate_traversal_cost(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::algorithm::search::Corridor;
     use crate::model::constraint::default::no_restriction::NoRestriction;
     use crate::model::cost::CostAggregation;
     use crate::model::cost::CostModel;
@@ -256,6 +490,7 @@ mod tests {
     use rayon::prelude::*;
     use std::sync::Arc;
     use uom::si::f64::Length;
+    use uom::ConstZero;
 
     fn build_square_graph() -> Graph {
         use uom::si::length::kilometer;
@@ -294,6 +529,8 @@ mod tests {
             edge_lists: vec![EdgeList(edges.into_boxed_slice())],
             adj: adj.into_boxed_slice(),
             rev: rev.into_boxed_slice(),
+            reverse_edges: None,
+            components: None,
         }
     }
 
@@ -345,6 +582,8 @@ mod tests {
             edge_lists: vec![EdgeList(edges.into_boxed_slice())],
             adj: adj.into_boxed_slice(),
             rev: rev.into_boxed_slice(),
+            reverse_edges: None,
+            components: None,
         }
     }
 
@@ -384,6 +623,12 @@ mod tests {
             termination_model: Arc::new(TerminationModel::IterationsLimit { limit: 20 }),
             label_model: Arc::new(VertexLabelModel {}),
             default_edge_list: None,
+            heuristic: None,
+            heuristic_weight: 1.0,
+            record_settled: false,
+            max_cost: None,
+            corridor: None,
+            initial_state: None,
         }
     }
 
@@ -399,6 +644,8 @@ mod tests {
             Some(query_destination),
             &Direction::Forward,
             true,
+            None,
+            SearchTreeStorage::Nodes,
             &si,
         )
         .expect("failure running search for A* test");
@@ -407,11 +654,198 @@ mod tests {
             .tree
             .backtrack(query_destination)
             .expect("failure creating search result");
-        for (route_edge, expected_edge) in route.into_iter().zip(optimal_route.into_iter()) {
+        for (route_edge, expected_edge) in route.into_iter().zip(optimal_route) {
             assert_eq!(route_edge.edge_id, expected_edge);
         }
     }
 
+    #[test]
+    fn test_arena_storage() {
+        let graph = Arc::new(build_astar_graph());
+        let si = build_search_instance(graph.clone());
+        let search = |storage| {
+            run_vertex_oriented(
+                VertexId(0),
+                None,
+                &Direction::Forward,
+                false,
+                None,
+                storage,
+                &si,
+            )
+            .expect("failure running search for arena test")
+        };
+        let nodes = search(SearchTreeStorage::Nodes);
+        let arena = search(SearchTreeStorage::Arena);
+        assert_eq!(arena.tree.storage(), SearchTreeStorage::Arena);
+        assert_eq!(arena.tree.len(), nodes.tree.len());
+        for label in nodes.tree.labels() {
+            assert_eq!(arena.tree.get_state(label), nodes.tree.get_state(label));
+        }
+        let route_states = |tree: &SearchTree| {
+            tree.backtrack(VertexId(5))
+                .expect("failure backtracking arena test tree")
+                .into_iter()
+                .map(|et| (et.edge_id, et.result_state))
+                .collect::<Vec<_>>()
+        };
+        let expected = route_states(&nodes.tree);
+        assert!(!expected.is_empty());
+        assert!(expected.iter().all(|(_, state)| !state.is_empty()));
+        assert_eq!(route_states(&arena.tree), expected);
+    }
+
+    #[test]
+    fn test_no_path_diagnostics() {
+        // vertex 5 has no out-edges, so the search settles only the origin
+        let graph = Arc::new(build_astar_graph());
+        let si = build_search_instance(graph.clone());
+        let result = run_vertex_oriented(
+            VertexId(5),
+            Some(VertexId(0)),
+            &Direction::Forward,
+            true,
+            None,
+            SearchTreeStorage::Nodes,
+            &si,
+        );
+        match result {
+            Err(SearchError::NoPathExists(diagnostics)) => {
+                assert_eq!(diagnostics.source, VertexId(5));
+                assert_eq!(diagnostics.target, VertexId(0));
+                assert_eq!(diagnostics.settled_vertices, 1);
+                assert_eq!(diagnostics.nearest_settled_vertex, Some(VertexId(5)));
+                let distance = diagnostics.nearest_settled_distance_meters.unwrap();
+                assert!((distance - 2224.0).abs() < 10.0, "found {distance}");
+                assert_eq!(diagnostics.source_component, None);
+                assert!(!diagnostics.pruned_by_constraints);
+            }
+            Err(other) => panic!("expected NoPathExists, found {other}"),
+            Ok(_) => panic!("expected NoPathExists, found a path"),
+        }
+    }
+
+    #[test]
+    fn test_candidates() {
+        // origin candidates 2 and 4 and destination candidates 1 and 5: the cheapest
+        // pair is 4 -> 5 over a single edge, while 2 -> 1 would take two edges
+        let graph = Arc::new(build_astar_graph());
+        let si = build_search_instance(graph.clone());
+        let sources = [VertexId(2), VertexId(4)];
+        let targets = [VertexId(1), VertexId(5)];
+        let result = run_vertex_oriented_candidates(
+            &sources,
+            &targets,
+            &Direction::Forward,
+            true,
+            None,
+            SearchTreeStorage::Nodes,
+            &si,
+        )
+        .expect("failure running candidate search");
+        let route = result
+            .tree
+            .backtrack(VertexId(5))
+            .expect("failure backtracking candidate tree")
+            .into_iter()
+            .map(|et| et.edge_id)
+            .collect::<Vec<_>>();
+        assert_eq!(route, vec![EdgeId(8)]);
+        assert!(result.tree.get_min_cost_label(VertexId(1)).is_none());
+
+        // vertex 5 has no out-edges, so no candidate pair is connected
+        let result = run_vertex_oriented_candidates(
+            &[VertexId(5)],
+            &[VertexId(1), VertexId(2)],
+            &Direction::Forward,
+            true,
+            None,
+            SearchTreeStorage::Nodes,
+            &si,
+        );
+        assert!(matches!(result, Err(SearchError::NoPathExists(_))));
+    }
+
+    #[test]
+    fn test_one_to_many() {
+        let graph = Arc::new(build_astar_graph());
+        let si = build_search_instance(graph.clone());
+        let targets = [VertexId(5), VertexId(1), VertexId(2)];
+        let result = run_vertex_oriented_many(
+            VertexId(0),
+            &targets,
+            &Direction::Forward,
+            None,
+            SearchTreeStorage::Nodes,
+            &si,
+        )
+        .expect("failure running one-to-many search");
+        let expected = [vec![EdgeId(6), EdgeId(8)], vec![EdgeId(0)], vec![EdgeId(2)]];
+        for (target, expected_route) in targets.iter().zip(expected) {
+            let route = result
+                .tree
+                .backtrack(*target)
+                .expect("failure backtracking one-to-many tree")
+                .into_iter()
+                .map(|et| et.edge_id)
+                .collect::<Vec<_>>();
+            assert_eq!(route, expected_route);
+        }
+
+        // a reverse search shares the tree across origins that route to a common destination
+        let result = run_vertex_oriented_many(
+            VertexId(5),
+            &[VertexId(1)],
+            &Direction::Reverse,
+            None,
+            SearchTreeStorage::Nodes,
+            &si,
+        )
+        .expect("failure running many-to-one search");
+        let route = result
+            .tree
+            .backtrack(VertexId(1))
+            .expect("failure backtracking many-to-one tree")
+            .into_iter()
+            .map(|et| et.edge_id)
+            .collect::<Vec<_>>();
+        assert_eq!(route, vec![EdgeId(1), EdgeId(6), EdgeId(8)]);
+    }
+
+    #[test]
+    fn test_corridor() {
+        // a zero-width network corridor around edge 0 only admits edges between
+        // vertices 0 and 1, so the search must take the long direct edge instead
+        // of the shorter route through vertices 3 and 2
+        let graph = Arc::new(build_square_graph());
+        let corridor = Corridor::network(&[(EdgeListId(0), EdgeId(0))], Length::ZERO, &graph)
+            .expect("failure building corridor");
+        assert_eq!(corridor.len(), 2);
+        let si = SearchInstance {
+            corridor: Some(Arc::new(corridor)),
+            ..build_search_instance(graph.clone())
+        };
+        let result = run_vertex_oriented(
+            VertexId(0),
+            Some(VertexId(1)),
+            &Direction::Forward,
+            true,
+            None,
+            SearchTreeStorage::Nodes,
+            &si,
+        )
+        .expect("failure running corridor search");
+        let route = result
+            .tree
+            .backtrack(VertexId(1))
+            .expect("failure backtracking corridor tree")
+            .into_iter()
+            .map(|et| et.edge_id)
+            .collect::<Vec<_>>();
+        assert_eq!(route, vec![EdgeId(0)]);
+        assert!(result.tree.get_min_cost_label(VertexId(3)).is_none());
+    }
+
     #[test]
     fn test_square_graph() {
         // simple box world that exists in a non-euclidean plane that stretches
@@ -467,8 +901,16 @@ mod tests {
             .clone()
             .into_par_iter()
             .map(|(o, d, _expected)| {
-                run_vertex_oriented(o, Some(d), &Direction::Forward, false, &si)
-                    .map(|search_result| search_result.tree)
+                run_vertex_oriented(
+                    o,
+                    Some(d),
+                    &Direction::Forward,
+                    false,
+                    None,
+                    SearchTreeStorage::Nodes,
+                    &si,
+                )
+                .map(|search_result| search_result.tree)
             })
             .collect();
 
@@ -1,7 +1,8 @@
 use itertools::Itertools;
 
-use crate::algorithm::search::{
-    edge_traversal::EdgeTraversal, search_error::SearchError, SearchInstance,
+use crate::{
+    algorithm::search::{edge_traversal::EdgeTraversal, search_error::SearchError, SearchInstance},
+    model::unit::{AsF64, Cost},
 };
 
 /// identifies routes that have loops by checking if any two edges share
@@ -24,3 +25,50 @@ pub fn route_contains_loop(
         .collect::<Result<Vec<_>, _>>()?;
     Ok(src_vertices.iter().unique().collect_vec().len() < src_vertices.len())
 }
+
+/// confirms a weighted A* heuristic weight is a finite number no less than 1.
+///
+/// # Arguments
+/// * `weight` - the weight applied to the A* cost estimate
+///
+/// # Returns
+///
+/// the weight, or an error if it is invalid
+pub fn validate_heuristic_weight(weight: f64) -> Result<f64, SearchError> {
+    if weight.is_finite() && weight >= 1.0 {
+        Ok(weight)
+    } else {
+        Err(SearchError::BuildError(format!(
+            "heuristic_weight must be a number no less than 1, found {weight}"
+        )))
+    }
+}
+
+/// with a heuristic that never overestimates, weighted A* finds a route that costs no more
+/// than `weight` times the optimal route. the optimal cost is then at least
+/// `route_cost / weight`, which bounds how much cheaper it could be than this route.
+///
+/// # Arguments
+/// * `route_cost` - objective cost of the route found by weighted A*
+/// * `weight`     - the weight applied to the A* cost estimate
+///
+/// # Returns
+///
+/// the largest possible difference between the route cost and the optimal cost
+pub fn optimality_gap_bound(route_cost: Cost, weight: f64) -> Cost {
+    Cost::new(route_cost.as_f64() * (1.0 - 1.0 / weight))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_heuristic_weight() {
+        assert_eq!(validate_heuristic_weight(1.5).unwrap(), 1.5);
+        assert!(validate_heuristic_weight(0.5).is_err());
+        assert!(validate_heuristic_weight(f64::NAN).is_err());
+        assert_eq!(optimality_gap_bound(Cost::new(100.0), 1.0), Cost::ZERO);
+        assert_eq!(optimality_gap_bound(Cost::new(100.0), 2.0), Cost::new(50.0));
+    }
+}
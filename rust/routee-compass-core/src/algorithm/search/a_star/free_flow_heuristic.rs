@@ -0,0 +1,205 @@
+use crate::{
+    algorithm::search::{SearchError, SearchInstance},
+    model::{
+        network::{EdgeListId, Graph, VertexId},
+        state::StateVariable,
+        traversal::default::fieldname,
+        unit::{Cost, SpeedUnit},
+    },
+    util::{
+        fs::{read_decoders, read_utils},
+        geo::haversine,
+    },
+};
+use kdam::Bar;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use uom::{
+    si::f64::{Length, Time, Velocity},
+    ConstZero,
+};
+
+/// configures a heuristic that replaces the traversal model cost estimates used by A*.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum HeuristicConfig {
+    /// estimates the remaining cost as the travel time at the fastest straight-line
+    /// speed found in a free-flow speed table.
+    FreeFlow {
+        /// file with one free-flow speed per edge, in edge id order
+        speed_table_input_file: String,
+        /// unit of the speeds in the speed table
+        speed_unit: SpeedUnit,
+        /// edge list that the speed table describes, 0 by default
+        edge_list_id: Option<usize>,
+    },
+}
+
+/// a lower bound on the remaining travel time of a search, built from the minimum
+/// travel time of each edge at free-flow speeds.
+///
+/// the travel time of each edge is divided into the straight-line distance between its
+/// endpoints, and the fastest of these straight-line speeds bounds every path. since edge
+/// geometries are never shorter than the straight line between their endpoints, this is
+/// no faster (and so no looser) than the maximum speed in the table. the bound only holds
+/// for routes on the edge list of the speed table, and for searches where no edge is
+/// faster than its free-flow speed.
+#[derive(Debug, Clone, Copy)]
+pub struct FreeFlowHeuristic {
+    pub max_straight_line_speed: Velocity,
+}
+
+impl FreeFlowHeuristic {
+    /// finds the fastest straight-line speed over the edges of an edge list.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - the road network
+    /// * `edge_list_id` - the edge list with the edges described by `speeds`
+    /// * `speeds` - free-flow speed of each edge, by edge id
+    pub fn new(
+        graph: &Graph,
+        edge_list_id: EdgeListId,
+        speeds: &[Velocity],
+    ) -> Result<FreeFlowHeuristic, SearchError> {
+        let edge_list = graph.get_edge_list(&edge_list_id)?;
+        if edge_list.len() != speeds.len() {
+            return Err(SearchError::BuildError(format!(
+                "free flow heuristic speed table has {} entries but edge list {edge_list_id} has {} edges",
+                speeds.len(),
+                edge_list.len()
+            )));
+        }
+        let mut max_speed = Velocity::ZERO;
+        for edge in edge_list.edges() {
+            let speed = speeds[edge.edge_id.as_usize()];
+            if speed <= Velocity::ZERO || edge.distance <= Length::ZERO {
+                continue;
+            }
+            let min_time: Time = edge.distance / speed;
+            let src = graph.get_vertex(&edge.src_vertex_id)?;
+            let dst = graph.get_vertex(&edge.dst_vertex_id)?;
+            let straight_line = haversine::coord_distance(&src.coordinate, &dst.coordinate)
+                .map_err(SearchError::BuildError)?;
+            let straight_line_speed: Velocity = straight_line / min_time;
+            if straight_line_speed > max_speed {
+                max_speed = straight_line_speed;
+            }
+        }
+        if max_speed <= Velocity::ZERO {
+            return Err(SearchError::BuildError(format!(
+                "free flow heuristic found no edge in edge list {edge_list_id} with a positive speed between distinct vertices"
+            )));
+        }
+        Ok(FreeFlowHeuristic {
+            max_straight_line_speed: max_speed,
+        })
+    }
+
+    /// builds the heuristic from configuration, reading the speed table from disk.
+    pub fn from_config(
+        config: &HeuristicConfig,
+        graph: &Graph,
+    ) -> Result<FreeFlowHeuristic, SearchError> {
+        match config {
+            HeuristicConfig::FreeFlow {
+                speed_table_input_file,
+                speed_unit,
+                edge_list_id,
+            } => {
+                let speeds: Vec<Velocity> = read_utils::read_raw_file(
+                    Path::new(speed_table_input_file),
+                    read_decoders::f64,
+                    Some(Bar::builder().desc("free flow speeds")),
+                    None,
+                )
+                .map_err(|e| {
+                    SearchError::BuildError(format!(
+                        "cannot read free flow speed table {speed_table_input_file}: {e}"
+                    ))
+                })?
+                .iter()
+                .map(|s| speed_unit.to_uom(*s))
+                .collect();
+                FreeFlowHeuristic::new(graph, EdgeListId(edge_list_id.unwrap_or_default()), &speeds)
+            }
+        }
+    }
+
+    /// estimates the cost of travel between two vertices as the time to cover the
+    /// straight-line distance between them at the fastest straight-line speed, priced
+    /// with the weights and rates of the active cost model. state variables other than
+    /// time are reset, so they do not contribute to the estimate.
+    pub fn estimate_cost(
+        &self,
+        src: VertexId,
+        dst: VertexId,
+        state: &[StateVariable],
+        si: &SearchInstance,
+    ) -> Result<Cost, SearchError> {
+        let src = si.graph.get_vertex(&src)?;
+        let dst = si.graph.get_vertex(&dst)?;
+        let distance = haversine::coord_distance(&src.coordinate, &dst.coordinate)
+            .map_err(SearchError::InternalError)?;
+        let time: Time = distance / self.max_straight_line_speed;
+
+        let mut dst_state = si.state_model.initial_state(Some(state))?;
+        let edge_time = String::from(fieldname::EDGE_TIME);
+        let trip_time = String::from(fieldname::TRIP_TIME);
+        if si.state_model.contains_key(&edge_time) {
+            si.state_model.set_time(&mut dst_state, &edge_time, &time)?;
+        }
+        if si.state_model.contains_key(&trip_time) {
+            si.state_model.add_time(&mut dst_state, &trip_time, &time)?;
+        }
+        let cost = si.cost_model.estimate_cost(&dst_state, &si.state_model)?;
+        Ok(cost.objective_cost)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::network::{Edge, EdgeList, Vertex};
+    use indexmap::IndexMap;
+    use uom::si::{length::meter, velocity::meter_per_second};
+
+    #[test]
+    fn test_straight_line_speed() {
+        // two edges spanning about 1000 meters each, where edge 1 has a winding geometry
+        let vertices = vec![
+            Vertex::new(0, 0.0, 0.0),
+            Vertex::new(1, 0.0089932, 0.0),
+            Vertex::new(2, 0.0089932, 0.0089932),
+        ];
+        let edges = vec![
+            Edge::new(0, 0, 0, 1, Length::new::<meter>(1000.0)),
+            Edge::new(0, 1, 1, 2, Length::new::<meter>(4000.0)),
+        ];
+        let mut adj = vec![IndexMap::new(); vertices.len()];
+        let mut rev = vec![IndexMap::new(); vertices.len()];
+        for edge in &edges {
+            adj[edge.src_vertex_id.0].insert((EdgeListId(0), edge.edge_id), edge.dst_vertex_id);
+            rev[edge.dst_vertex_id.0].insert((EdgeListId(0), edge.edge_id), edge.src_vertex_id);
+        }
+        let graph = Graph {
+            vertices: vertices.into_boxed_slice(),
+            edge_lists: vec![EdgeList(edges.into_boxed_slice())],
+            adj: adj.into_boxed_slice(),
+            rev: rev.into_boxed_slice(),
+            reverse_edges: None,
+            components: None,
+        };
+        let speeds = vec![
+            Velocity::new::<meter_per_second>(10.0),
+            Velocity::new::<meter_per_second>(30.0),
+        ];
+        let heuristic = FreeFlowHeuristic::new(&graph, EdgeListId(0), &speeds).unwrap();
+        // edge 1 has the fastest speed, but covers only 1000 meters of straight line in 133 seconds
+        let speed = heuristic.max_straight_line_speed.get::<meter_per_second>();
+        assert!((speed - 10.0).abs() < 0.1, "expected 10 m/s, found {speed}");
+
+        let mismatched = FreeFlowHeuristic::new(&graph, EdgeListId(0), &speeds[0..1]);
+        assert!(mismatched.is_err());
+    }
+}
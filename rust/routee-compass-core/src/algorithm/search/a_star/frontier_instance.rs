@@ -4,7 +4,7 @@ use crate::{
         label::Label,
         network::{EdgeId, EdgeListId, VertexId},
         state::StateVariable,
-        unit::ReverseCost,
+        unit::{Cost, ReverseCost},
     },
     util::priority_queue::InternalPriorityQueue,
 };
@@ -13,6 +13,8 @@ pub struct FrontierInstance {
     pub prev_label: Label,
     pub prev_edge: Option<(EdgeListId, EdgeId)>,
     pub prev_state: Vec<StateVariable>,
+    /// frontier priority of the label when it was popped
+    pub priority: Cost,
 }
 
 impl FrontierInstance {
@@ -55,28 +57,28 @@ impl FrontierInstance {
                 (Some((prev_label, _)), Some(target_v)) if prev_label.vertex_id() == &target_v => {
                     return Ok(None)
                 }
-                (Some((prev_label, _)), _) => {
+                (Some((prev_label, priority)), _) => {
                     let node_opt = solution.get(&prev_label);
                     if node_opt.is_none() && !solution.is_empty() {
                         // this label was pruned from the search tree while it was in the frontier.
                         // skip it and continue to the next label in the frontier.
                         continue;
                     }
-                    let prev_edge_traversal_opt = node_opt.and_then(|n| n.incoming_edge()).cloned();
+                    let prev_edge = node_opt
+                        .and_then(|n| n.incoming_edge())
+                        .map(|et| (et.edge_list_id, et.edge_id));
 
                     // grab the current state from the solution, or get initial state if we are at the search root
-                    let prev_edge = prev_edge_traversal_opt
-                        .as_ref()
-                        .map(|et| (et.edge_list_id, et.edge_id));
-                    let prev_state = match prev_edge_traversal_opt.as_ref() {
+                    let prev_state = match solution.get_state(&prev_label) {
                         None => initial_state.to_vec(),
-                        Some(et) => et.result_state.clone(),
+                        Some(state) => state.to_vec(),
                     };
 
                     let result = FrontierInstance {
                         prev_label,
                         prev_edge,
                         prev_state,
+                        priority: priority.0,
                     };
 
                     return Ok(Some(result));
@@ -139,6 +141,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::needless_update)]
     fn test_pop_new_skips_pruned_label() {
         let mut frontier = InternalPriorityQueue::default();
         let l1 = Label::Vertex(VertexId(1));
@@ -201,6 +204,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::needless_update)]
     fn test_pop_new_skips_pruned_state_label() {
         let mut frontier = InternalPriorityQueue::default();
         let v1 = VertexId(1);
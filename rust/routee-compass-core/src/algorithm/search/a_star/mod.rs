@@ -1,5 +1,10 @@
 mod a_star_algorithm;
 pub mod a_star_ops;
+mod free_flow_heuristic;
 mod frontier_instance;
 
-pub use a_star_algorithm::{run_edge_oriented, run_vertex_oriented};
+pub use a_star_algorithm::{
+    run_edge_oriented, run_vertex_oriented, run_vertex_oriented_candidates,
+    run_vertex_oriented_many,
+};
+pub use free_flow_heuristic::{FreeFlowHeuristic, HeuristicConfig};
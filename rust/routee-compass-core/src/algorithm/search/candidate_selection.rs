@@ -0,0 +1,10 @@
+/// identifies the origin and destination candidates used by the route found by
+/// [`super::SearchAlgorithm::run_candidates`], as indices into the candidate lists.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CandidateSelection {
+    /// the origin candidate the route starts from. None when the search has no destination,
+    /// as the resulting tree grows from every origin candidate.
+    pub origin: Option<usize>,
+    /// the destination candidate the route ends at, if the search has a destination
+    pub destination: Option<usize>,
+}
@@ -0,0 +1,255 @@
+use super::SearchError;
+use crate::model::{
+    map::MapModel,
+    network::{EdgeId, EdgeListId, Graph, VertexId},
+    unit::DistanceUnit,
+};
+use crate::util::geo::haversine;
+use geo::{Coord, LineString, Point};
+use ordered_float::OrderedFloat;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use uom::{si::f64::Length, ConstZero};
+
+/// restricts a search to the edges within a corridor around a reference path, such as
+/// for a fast re-optimization of a route after a small change in the network. edges
+/// outside of the corridor are never added to the search frontier.
+#[derive(Clone, Debug, Default)]
+pub struct Corridor {
+    edges: HashSet<(EdgeListId, EdgeId)>,
+}
+
+/// how the width of a corridor is measured from the reference path
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CorridorType {
+    /// edges within the width of the reference path, found with the map model
+    /// spatial index
+    #[default]
+    Spatial,
+    /// edges whose endpoints can be reached from the reference path by traveling at
+    /// most the width along the network, in either direction
+    Network,
+}
+
+/// the `corridor` query key
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct CorridorQuery {
+    #[serde(default, rename = "type")]
+    pub corridor_type: CorridorType,
+    /// edge ids of the reference path
+    pub edges: Option<Vec<EdgeId>>,
+    /// edge list of the reference path edges. defaults to 0.
+    pub edge_list_id: Option<EdgeListId>,
+    /// WGS84 `[x, y]` coordinates of the reference path, used by spatial corridors
+    /// instead of the reference path edges
+    pub path: Option<Vec<[f32; 2]>>,
+    pub width: f64,
+    /// unit of the width. defaults to miles.
+    pub distance_unit: Option<DistanceUnit>,
+}
+
+impl Corridor {
+    pub const QUERY_KEY: &'static str = "corridor";
+
+    /// builds the corridor of a query with a `corridor` key
+    ///
+    /// # Returns
+    ///
+    /// the corridor, or None if the query has no corridor
+    pub fn from_query(
+        query: &serde_json::Value,
+        graph: &Graph,
+        map_model: &MapModel,
+    ) -> Result<Option<Corridor>, SearchError> {
+        let Some(value) = query.get(Corridor::QUERY_KEY) else {
+            return Ok(None);
+        };
+        let corridor_query: CorridorQuery = serde_json::from_value(value.clone())
+            .map_err(|e| SearchError::BuildError(format!("invalid query 'corridor': {e}")))?;
+        if !(corridor_query.width.is_finite() && corridor_query.width >= 0.0) {
+            return Err(SearchError::BuildError(format!(
+                "query corridor 'width' must be a non-negative number, found {}",
+                corridor_query.width
+            )));
+        }
+        let width = corridor_query
+            .distance_unit
+            .unwrap_or_default()
+            .to_uom(corridor_query.width);
+        let edge_list_id = corridor_query.edge_list_id.unwrap_or_default();
+        let reference = corridor_query.edges.as_ref().map(|edges| {
+            edges
+                .iter()
+                .map(|edge_id| (edge_list_id, *edge_id))
+                .collect::<Vec<_>>()
+        });
+        let corridor = match (
+            corridor_query.corridor_type,
+            reference,
+            &corridor_query.path,
+        ) {
+            (CorridorType::Spatial, Some(reference), None) => {
+                let linestrings = reference
+                    .iter()
+                    .map(|(el, e)| map_model.get_linestring(el, e).cloned())
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| SearchError::BuildError(format!("invalid corridor: {e}")))?;
+                let mut corridor = Corridor::spatial(&linestrings, width, map_model)?;
+                corridor.edges.extend(reference);
+                corridor
+            }
+            (CorridorType::Spatial, None, Some(path)) => {
+                let linestring = path
+                    .iter()
+                    .map(|[x, y]| Coord { x: *x, y: *y })
+                    .collect::<LineString<f32>>();
+                Corridor::spatial(&[linestring], width, map_model)?
+            }
+            (CorridorType::Network, Some(reference), None) => {
+                Corridor::network(&reference, width, graph)?
+            }
+            (CorridorType::Network, _, Some(_)) => {
+                return Err(SearchError::BuildError(String::from(
+                    "query corridor of type 'network' requires 'edges' instead of 'path'",
+                )))
+            }
+            _ => {
+                return Err(SearchError::BuildError(String::from(
+                    "query corridor requires exactly one of 'edges' or 'path'",
+                )))
+            }
+        };
+        log::debug!("corridor with {} edges", corridor.len());
+        Ok(Some(corridor))
+    }
+
+    /// finds the edges within a distance of any of the linestrings, using the map model
+    /// spatial index. each linestring is sampled at intervals of half the width, so an
+    /// edge may be found slightly beyond the width between samples.
+    pub fn spatial(
+        linestrings: &[LineString<f32>],
+        width: Length,
+        map_model: &MapModel,
+    ) -> Result<Corridor, SearchError> {
+        let width_meters = width.get::<uom::si::length::meter>();
+        let step = (width_meters / 2.0).max(1.0);
+        let mut edges = HashSet::new();
+        for linestring in linestrings.iter() {
+            for point in sample_points(linestring, step)? {
+                let candidates = map_model
+                    .edges_within(&point, width)
+                    .map_err(|e| SearchError::BuildError(format!("invalid corridor: {e}")))?;
+                edges.extend(candidates.iter().map(|c| (c.edge_list_id, c.edge_id)));
+            }
+        }
+        Ok(Corridor { edges })
+    }
+
+    /// finds the edges whose endpoints are within a network distance of the vertices of
+    /// the reference path, traveling along edges in either direction.
+    pub fn network(
+        reference: &[(EdgeListId, EdgeId)],
+        width: Length,
+        graph: &Graph,
+    ) -> Result<Corridor, SearchError> {
+        let mut distances: HashMap<VertexId, Length> = HashMap::new();
+        let mut frontier = BinaryHeap::new();
+        for (edge_list_id, edge_id) in reference.iter() {
+            let edge = graph.get_edge(edge_list_id, edge_id)?;
+            for vertex_id in [edge.src_vertex_id, edge.dst_vertex_id] {
+                distances.insert(vertex_id, Length::ZERO);
+                frontier.push(Reverse((OrderedFloat(0.0), vertex_id.0)));
+            }
+        }
+        while let Some(Reverse((OrderedFloat(meters), vertex_id))) = frontier.pop() {
+            let vertex_id = VertexId(vertex_id);
+            let distance = Length::new::<uom::si::length::meter>(meters);
+            if distances.get(&vertex_id).is_some_and(|d| *d < distance) {
+                continue;
+            }
+            let incident = graph
+                .out_edges_iter(&vertex_id)
+                .chain(graph.in_edges_iter(&vertex_id));
+            for (edge_list_id, edge_id) in incident {
+                let edge = graph.get_edge(edge_list_id, edge_id)?;
+                let next = match edge.src_vertex_id == vertex_id {
+                    true => edge.dst_vertex_id,
+                    false => edge.src_vertex_id,
+                };
+                let next_distance = distance + edge.distance;
+                let improves = distances.get(&next).is_none_or(|d| next_distance < *d);
+                if next_distance <= width && improves {
+                    distances.insert(next, next_distance);
+                    frontier.push(Reverse((
+                        OrderedFloat(next_distance.get::<uom::si::length::meter>()),
+                        next.0,
+                    )));
+                }
+            }
+        }
+        let mut edges: HashSet<(EdgeListId, EdgeId)> = reference.iter().copied().collect();
+        for vertex_id in distances.keys() {
+            for (edge_list_id, edge_id) in graph.out_edges(vertex_id) {
+                let edge = graph.get_edge(&edge_list_id, &edge_id)?;
+                if distances.contains_key(&edge.dst_vertex_id) {
+                    edges.insert((edge_list_id, edge_id));
+                }
+            }
+        }
+        Ok(Corridor { edges })
+    }
+
+    /// true if the edge is in the corridor
+    pub fn contains(&self, edge_list_id: &EdgeListId, edge_id: &EdgeId) -> bool {
+        self.edges.contains(&(*edge_list_id, *edge_id))
+    }
+
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+}
+
+/// the coordinates of a linestring along with points between them, so that consecutive
+/// points are at most `step` meters apart
+fn sample_points(linestring: &LineString<f32>, step: f64) -> Result<Vec<Point<f32>>, SearchError> {
+    let mut points = vec![];
+    let coords = linestring.0.as_slice();
+    if let Some(first) = coords.first() {
+        points.push(Point(*first));
+    }
+    for pair in coords.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        let meters = haversine::coord_distance(&a, &b)
+            .map_err(|e| SearchError::BuildError(format!("invalid corridor path: {e}")))?
+            .get::<uom::si::length::meter>();
+        let n = (meters / step).ceil().max(1.0) as usize;
+        for i in 1..=n {
+            let t = (i as f64 / n as f64) as f32;
+            points.push(Point(a + (b - a) * t));
+        }
+    }
+    Ok(points)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo::line_string;
+
+    #[test]
+    fn test_sample_points() {
+        // about 11.1 km of latitude, sampled every 5 km
+        let linestring = line_string![(x: -105.0f32, y: 40.0), (x: -105.0, y: 40.1)];
+        let points = sample_points(&linestring, 5000.0).unwrap();
+        assert_eq!(points.len(), 4);
+        assert_eq!(points.first().unwrap().y(), 40.0);
+        assert_eq!(points.last().unwrap().y(), 40.1);
+    }
+}
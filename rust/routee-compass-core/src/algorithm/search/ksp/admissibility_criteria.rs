@@ -0,0 +1,250 @@
+use super::ksp_query::KspQuery;
+use crate::{
+    algorithm::search::{
+        edge_traversal::EdgeTraversal, search_algorithm::SearchAlgorithm,
+        search_algorithm_result::SearchAlgorithmResult, search_error::SearchError, Direction,
+        SearchInstance,
+    },
+    model::{
+        network::{EdgeId, EdgeListId, VertexId},
+        unit::AsF64,
+    },
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// tolerance on cost comparisons when testing if a subpath is a shortest path
+const COST_TOLERANCE: f64 = 1e-9;
+
+/// admissibility criteria for alternative routes, following Abraham, Ittai, et al.
+/// "Alternative routes in road networks." Journal of Experimental Algorithmics 18 (2013).
+/// alternatives that fail a criterion are removed from the result, leaving the shortest
+/// route and the alternatives that are meaningfully distinct from it and reasonable to
+/// drive. criteria that are not provided are not tested.
+///
+/// the parts of an alternative that leave the shortest route are called detours. the
+/// stretch and local optimality criteria are tested on each detour, which requires an
+/// additional path search per detour and criterion. costs are objective costs, and the
+/// shortest paths between detour vertices are searched from the initial search state.
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct AdmissibilityCriteria {
+    /// limited sharing: the most cost an alternative may share with the routes accepted
+    /// before it, as a fraction (from 0 to 1) of the cost of the shortest route
+    pub max_sharing: Option<f64>,
+    /// uniform bounded stretch: the most that the alternative and each of its detours
+    /// may cost over the shortest path between their endpoints, as a fraction (at least 0)
+    pub max_stretch: Option<f64>,
+    /// local optimality: the fraction (from 0 to 1) of the cost of the shortest route.
+    /// the subpath of this cost centered on each detour must itself be a shortest path
+    pub local_optimality: Option<f64>,
+}
+
+impl AdmissibilityCriteria {
+    /// removes the alternatives of a search result which fail the admissibility criteria.
+    /// the first route of the result is the shortest route, which is always kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `result` - the result of an alternative routes search
+    /// * `query` - the query of the alternative routes search
+    /// * `si` - search instance
+    /// * `underlying` - the path search algorithm, used to find the shortest paths between detour vertices
+    ///
+    /// # Returns
+    ///
+    /// The result with only the admissible routes
+    pub fn filter(
+        &self,
+        mut result: SearchAlgorithmResult,
+        query: &KspQuery,
+        si: &SearchInstance,
+        underlying: &SearchAlgorithm,
+    ) -> Result<SearchAlgorithmResult, SearchError> {
+        self.validate()?;
+        if result.routes.len() < 2 {
+            return Ok(result);
+        }
+        let mut routes = std::mem::take(&mut result.routes).into_iter();
+        let shortest = routes.next().unwrap_or_default();
+        let shortest_cost = route_cost(&shortest);
+        let shortest_edges = edge_set(&shortest);
+        let mut accepted_edges = shortest_edges.clone();
+        let mut accepted = vec![shortest];
+        for (idx, route) in routes.enumerate() {
+            if let Some(max_sharing) = self.max_sharing {
+                let shared = route
+                    .iter()
+                    .filter(|et| accepted_edges.contains(&(et.edge_list_id, et.edge_id)))
+                    .map(|et| et.cost.objective_cost.as_f64())
+                    .sum::<f64>();
+                if shared > max_sharing * shortest_cost + COST_TOLERANCE {
+                    log::debug!("alternative {} rejected, shares {shared} cost", idx + 1);
+                    continue;
+                }
+            }
+            let candidate = Candidate::new(&route, si)?;
+            let detours = candidate.detours(&shortest_edges);
+            if let Some(max_stretch) = self.max_stretch {
+                let bound = 1.0 + max_stretch;
+                let mut stretched = route_cost(&route) > bound * shortest_cost + COST_TOLERANCE;
+                for (start, end) in detours.iter() {
+                    if stretched {
+                        break;
+                    }
+                    stretched =
+                        !candidate.within_bound(*start, *end, bound, query, si, underlying)?;
+                }
+                if stretched {
+                    log::debug!("alternative {} rejected, exceeds stretch bound", idx + 1);
+                    continue;
+                }
+            }
+            if let Some(local_optimality) = self.local_optimality {
+                let radius = local_optimality * shortest_cost / 2.0;
+                let mut optimal = true;
+                for (start, end) in detours.iter() {
+                    let (from, to) = candidate.subpath_around(*start, *end, radius);
+                    if !candidate.within_bound(from, to, 1.0, query, si, underlying)? {
+                        optimal = false;
+                        break;
+                    }
+                }
+                if !optimal {
+                    log::debug!("alternative {} rejected, not locally optimal", idx + 1);
+                    continue;
+                }
+            }
+            accepted_edges.extend(edge_set(&route));
+            accepted.push(route);
+        }
+        result.routes = accepted;
+        Ok(result)
+    }
+
+    fn validate(&self) -> Result<(), SearchError> {
+        let fraction = |name: &str, value: Option<f64>| match value {
+            Some(v) if !(0.0..=1.0).contains(&v) => Err(SearchError::BuildError(format!(
+                "admissibility {name} must be in range [0, 1], found {v}"
+            ))),
+            _ => Ok(()),
+        };
+        fraction("max_sharing", self.max_sharing)?;
+        fraction("local_optimality", self.local_optimality)?;
+        match self.max_stretch {
+            Some(v) if !(v.is_finite() && v >= 0.0) => Err(SearchError::BuildError(format!(
+                "admissibility max_stretch must be non-negative, found {v}"
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// an alternative route with the vertices it visits and its cost up to each vertex
+struct Candidate<'a> {
+    route: &'a [EdgeTraversal],
+    vertices: Vec<VertexId>,
+    prefix_costs: Vec<f64>,
+}
+
+impl<'a> Candidate<'a> {
+    fn new(route: &'a [EdgeTraversal], si: &SearchInstance) -> Result<Self, SearchError> {
+        let mut vertices = Vec::with_capacity(route.len() + 1);
+        let mut prefix_costs = Vec::with_capacity(route.len() + 1);
+        prefix_costs.push(0.0);
+        for et in route.iter() {
+            vertices.push(si.graph.src_vertex_id(&et.edge_list_id, &et.edge_id)?);
+            let cost = prefix_costs.last().copied().unwrap_or_default();
+            prefix_costs.push(cost + et.cost.objective_cost.as_f64());
+        }
+        if let Some(et) = route.last() {
+            vertices.push(si.graph.dst_vertex_id(&et.edge_list_id, &et.edge_id)?);
+        }
+        Ok(Candidate {
+            route,
+            vertices,
+            prefix_costs,
+        })
+    }
+
+    /// the maximal runs of traversals that are not on the shortest route, as ranges
+    /// of vertex indices
+    fn detours(&self, shortest_edges: &HashSet<(EdgeListId, EdgeId)>) -> Vec<(usize, usize)> {
+        let mut detours = vec![];
+        let mut start: Option<usize> = None;
+        for (idx, et) in self.route.iter().enumerate() {
+            let shared = shortest_edges.contains(&(et.edge_list_id, et.edge_id));
+            match (start, shared) {
+                (None, false) => start = Some(idx),
+                (Some(s), true) => {
+                    detours.push((s, idx));
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        if let Some(s) = start {
+            detours.push((s, self.route.len()));
+        }
+        detours
+    }
+
+    /// the vertex indices of the subpath that extends `radius` in cost before and after
+    /// the midpoint of a detour, limited to the ends of the route
+    fn subpath_around(&self, start: usize, end: usize, radius: f64) -> (usize, usize) {
+        let midpoint = (self.prefix_costs[start] + self.prefix_costs[end]) / 2.0;
+        let center = (start..=end)
+            .find(|i| self.prefix_costs[*i] >= midpoint)
+            .unwrap_or(end);
+        let center_cost = self.prefix_costs[center];
+        let from = (0..=center)
+            .rev()
+            .find(|i| center_cost - self.prefix_costs[*i] >= radius)
+            .unwrap_or(0);
+        let to = (center..self.prefix_costs.len())
+            .find(|i| self.prefix_costs[*i] - center_cost >= radius)
+            .unwrap_or(self.prefix_costs.len() - 1);
+        (from, to)
+    }
+
+    /// true if the subpath between vertex indices `from` and `to` costs no more than
+    /// `bound` times the shortest path between its endpoints
+    fn within_bound(
+        &self,
+        from: usize,
+        to: usize,
+        bound: f64,
+        query: &KspQuery,
+        si: &SearchInstance,
+        underlying: &SearchAlgorithm,
+    ) -> Result<bool, SearchError> {
+        if from >= to {
+            return Ok(true);
+        }
+        let result = underlying.run_vertex_oriented(
+            self.vertices[from],
+            Some(self.vertices[to]),
+            query.user_query,
+            &Direction::Forward,
+            si,
+        )?;
+        let Some(shortest) = result.routes.first() else {
+            // the subpath itself connects these vertices, so this is only reached when
+            // the search terminates early, and the subpath cannot be tested
+            return Ok(true);
+        };
+        let subpath_cost = self.prefix_costs[to] - self.prefix_costs[from];
+        Ok(subpath_cost <= bound * route_cost(shortest) + COST_TOLERANCE)
+    }
+}
+
+fn route_cost(route: &[EdgeTraversal]) -> f64 {
+    route.iter().map(|et| et.cost.objective_cost.as_f64()).sum()
+}
+
+fn edge_set(route: &[EdgeTraversal]) -> HashSet<(EdgeListId, EdgeId)> {
+    route
+        .iter()
+        .map(|et| (et.edge_list_id, et.edge_id))
+        .collect()
+}
@@ -1,7 +1,10 @@
+mod admissibility_criteria;
 mod ksp_query;
 mod ksp_termination_criteria;
+pub mod penalty;
 pub mod svp;
 pub mod yens;
 
+pub use admissibility_criteria::AdmissibilityCriteria;
 pub use ksp_query::KspQuery;
 pub use ksp_termination_criteria::KspTerminationCriteria;
@@ -0,0 +1,152 @@
+use super::ksp_query::KspQuery;
+use crate::{
+    algorithm::search::{
+        edge_traversal::EdgeTraversal, search_algorithm::SearchAlgorithm,
+        search_algorithm_result::SearchAlgorithmResult, search_error::SearchError,
+        util::RouteSimilarityFunction, Direction, SearchInstance,
+    },
+    model::{
+        cost::EdgePenalty,
+        unit::{AsF64, Cost},
+    },
+};
+use itertools::Itertools;
+use std::sync::Arc;
+
+/// finds alternative routes with the penalty method. after each search, the objective
+/// cost of the edges on the route it found is multiplied by the penalty factor, and the
+/// search is repeated, so that each search is pushed away from the routes found before
+/// it. this runs one search per alternative, which is cheaper than Yen's algorithm, at
+/// the expense of returning routes that are not strictly the next-shortest.
+///
+/// routes that repeat an accepted route, or that are similar to an accepted route by the
+/// (optional) similarity function, are rejected. the objective costs of the returned
+/// routes do not include the penalties.
+///
+/// # Arguments
+///
+/// * `query` - the origin, destination and number of routes `k` to find
+/// * `penalty_factor` - multiplier (greater than 1) on the cost of edges on found routes
+/// * `max_searches` - the most searches to run, including the search for the shortest route
+/// * `similarity` - if provided, rejects routes similar to an accepted route
+/// * `si` - search instance
+/// * `underlying` - the path search algorithm
+///
+/// # Returns
+///
+/// The search tree of the shortest route, along with all accepted routes, shortest first
+pub fn run(
+    query: &KspQuery,
+    penalty_factor: f64,
+    max_searches: usize,
+    similarity: Option<&RouteSimilarityFunction>,
+    si: &SearchInstance,
+    underlying: &SearchAlgorithm,
+) -> Result<SearchAlgorithmResult, SearchError> {
+    if !(penalty_factor.is_finite() && penalty_factor > 1.0) {
+        return Err(SearchError::BuildError(format!(
+            "penalty method penalty_factor must be greater than 1, found {penalty_factor}"
+        )));
+    }
+    let shortest = underlying.run_vertex_oriented(
+        query.source,
+        Some(query.target),
+        query.user_query,
+        &Direction::Forward,
+        si,
+    )?;
+    let Some(shortest_route) = shortest.routes.first().cloned() else {
+        return Ok(SearchAlgorithmResult::default());
+    };
+    let mut iterations: u64 = 1; // number of times we call underlying search
+    let mut max_frontier_size = shortest.max_frontier_size;
+    let mut peak_tree_size = shortest.peak_tree_size;
+    let mut penalty = EdgePenalty::default();
+    let mut latest_route = shortest_route.clone();
+    let mut accepted: Vec<Vec<EdgeTraversal>> = vec![shortest_route];
+
+    while accepted.len() < query.k && (iterations as usize) < max_searches {
+        for et in latest_route.iter() {
+            penalty.penalize(et.edge_list_id, et.edge_id, penalty_factor);
+        }
+        let mut penalized_si = si.clone();
+        penalized_si.cost_model = Arc::new(
+            si.cost_model
+                .as_ref()
+                .clone()
+                .with_edge_penalty(penalty.clone()),
+        );
+        penalized_si.record_settled = false;
+        let result = underlying.run_vertex_oriented(
+            query.source,
+            Some(query.target),
+            query.user_query,
+            &Direction::Forward,
+            &penalized_si,
+        )?;
+        iterations += 1;
+        max_frontier_size = max_frontier_size.max(result.max_frontier_size);
+        peak_tree_size = peak_tree_size.max(result.peak_tree_size);
+        let Some(route) = result.routes.into_iter().next() else {
+            break;
+        };
+        latest_route = remove_penalties(route, &penalized_si)?;
+
+        if accepted.iter().any(|a| same_path(a, &latest_route)) {
+            continue;
+        }
+        if let Some(similarity) = similarity {
+            let candidate = latest_route.iter().collect_vec();
+            let mut similar = false;
+            for route in accepted.iter() {
+                if similarity.clone().test_similarity(
+                    &route.iter().collect_vec(),
+                    &candidate,
+                    si,
+                )? {
+                    similar = true;
+                    break;
+                }
+            }
+            if similar {
+                continue;
+            }
+        }
+        accepted.push(latest_route.clone());
+    }
+
+    let result = SearchAlgorithmResult {
+        trees: shortest.trees,
+        routes: accepted,
+        iterations,
+        max_frontier_size,
+        peak_tree_size,
+        terminated: None,
+        settled: vec![],
+    };
+    Ok(result)
+}
+
+/// restores the objective cost of each traversal of a route found with penalties
+fn remove_penalties(
+    route: Vec<EdgeTraversal>,
+    penalized_si: &SearchInstance,
+) -> Result<Vec<EdgeTraversal>, SearchError> {
+    route
+        .into_iter()
+        .map(|mut et| {
+            let edge = penalized_si.graph.get_edge(&et.edge_list_id, &et.edge_id)?;
+            let multiplier = penalized_si.cost_model.edge_penalty_multiplier(edge);
+            et.cost.objective_cost = Cost::new(et.cost.objective_cost.as_f64() / multiplier);
+            Ok(et)
+        })
+        .collect()
+}
+
+/// compares two routes by their sequence of edges, returning true if they are the same
+fn same_path(a: &[EdgeTraversal], b: &[EdgeTraversal]) -> bool {
+    a.len() == b.len()
+        && a.iter()
+            .zip(b)
+            .all(|(a, b)| a.edge_list_id == b.edge_list_id && a.edge_id == b.edge_id)
+}
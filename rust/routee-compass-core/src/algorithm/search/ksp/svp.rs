@@ -24,7 +24,10 @@ pub fn run(
         trees: fwd_trees,
         routes: _,
         iterations: fwd_iterations,
+        max_frontier_size: fwd_max_frontier_size,
+        peak_tree_size: fwd_peak_tree_size,
         terminated: fwd_terminated,
+        ..
     } = underlying.run_vertex_oriented(
         query.source,
         Some(query.target),
@@ -36,7 +39,10 @@ pub fn run(
         trees: rev_trees,
         routes: _,
         iterations: rev_iterations,
+        max_frontier_size: rev_max_frontier_size,
+        peak_tree_size: rev_peak_tree_size,
         terminated: rev_terminated,
+        ..
     } = underlying.run_vertex_oriented(
         query.target,
         Some(query.source),
@@ -161,7 +167,10 @@ pub fn run(
         trees: vec![fwd_tree.clone(), rev_tree.clone()], // todo: figure out how to avoid this clone
         routes,
         iterations: fwd_iterations + rev_iterations + ksp_it, // todo: figure out how to report individually
+        max_frontier_size: fwd_max_frontier_size.max(rev_max_frontier_size),
+        peak_tree_size: fwd_peak_tree_size + rev_peak_tree_size,
         terminated,
+        settled: vec![],
     };
     Ok(result)
 }
@@ -46,6 +46,8 @@ pub fn run(
     let shortest_path = get_first_route(&shortest)?;
     let mut accepted: Vec<Vec<EdgeTraversal>> = vec![shortest_path.to_owned()];
     let mut iterations: u64 = 1; // number of times we call underlying search
+    let mut max_frontier_size = shortest.max_frontier_size;
+    let mut peak_tree_size = shortest.peak_tree_size;
 
     while accepted.len() < query.k {
         if termination.terminate_search(query.k, accepted.len()) {
@@ -112,6 +114,12 @@ pub fn run(
                 termination_model: si.termination_model.clone(),
                 label_model: si.label_model.clone(),
                 default_edge_list: si.default_edge_list,
+                heuristic: si.heuristic,
+                heuristic_weight: si.heuristic_weight,
+                record_settled: false,
+                max_cost: si.max_cost,
+                corridor: si.corridor.clone(),
+                initial_state: si.initial_state.clone(),
             };
             let spur_result = underlying.run_vertex_oriented(
                 spur_vertex_id,
@@ -121,6 +129,8 @@ pub fn run(
                 &yens_si,
             )?;
             iterations += 1;
+            max_frontier_size = max_frontier_size.max(spur_result.max_frontier_size);
+            peak_tree_size = peak_tree_size.max(spur_result.peak_tree_size);
 
             let spur_path = get_first_route(&spur_result)?;
             let candidate_path = root_path
@@ -160,7 +170,10 @@ pub fn run(
         trees: shortest.trees,
         routes: accepted,
         iterations,
+        max_frontier_size,
+        peak_tree_size,
         terminated: None,
+        settled: vec![],
     };
     Ok(result)
 }
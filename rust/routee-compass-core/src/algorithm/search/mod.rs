@@ -1,5 +1,8 @@
+mod candidate_selection;
+mod corridor;
 mod direction;
 mod edge_traversal;
+mod no_path_diagnostics;
 mod search_algorithm;
 mod search_algorithm_config;
 mod search_algorithm_result;
@@ -7,15 +10,21 @@ mod search_error;
 mod search_instance;
 mod search_result;
 mod search_tree;
+mod search_tree_budget;
 mod search_tree_node;
+mod search_tree_storage;
+mod settled_label;
 mod termination_behavior;
 
 pub mod a_star;
 pub mod ksp;
 pub mod search_pruning;
 pub mod util;
+pub use candidate_selection::CandidateSelection;
+pub use corridor::{Corridor, CorridorQuery, CorridorType};
 pub use direction::Direction;
 pub use edge_traversal::EdgeTraversal;
+pub use no_path_diagnostics::NoPathDiagnostics;
 pub use search_algorithm::SearchAlgorithm;
 pub use search_algorithm_config::SearchAlgorithmConfig;
 pub use search_algorithm_result::SearchAlgorithmResult;
@@ -23,5 +32,8 @@ pub use search_error::SearchError;
 pub use search_instance::SearchInstance;
 pub use search_result::SearchResult;
 pub use search_tree::{SearchTree, SearchTreeError};
+pub use search_tree_budget::{SearchTreeBudget, SearchTreeBudgetPolicy};
 pub use search_tree_node::SearchTreeNode;
+pub use search_tree_storage::{SearchTreeStorage, StateArena};
+pub use settled_label::SettledLabel;
 pub use termination_behavior::TerminationFailurePolicy;
@@ -0,0 +1,106 @@
+use super::SearchTree;
+use crate::model::network::{Graph, VertexId};
+use crate::util::geo::haversine;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt::Display;
+use uom::si::length::meter;
+
+/// context collected when a search exhausts its frontier without reaching the destination.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct NoPathDiagnostics {
+    pub source: VertexId,
+    pub target: VertexId,
+    /// strongly connected component of the source, if components were computed for the graph
+    pub source_component: Option<usize>,
+    /// strongly connected component of the target, if components were computed for the graph
+    pub target_component: Option<usize>,
+    /// number of distinct vertices settled by the search
+    pub settled_vertices: usize,
+    /// the settled vertex closest to the target by straight line distance
+    pub nearest_settled_vertex: Option<VertexId>,
+    /// straight line distance from the nearest settled vertex to the target
+    pub nearest_settled_distance_meters: Option<f64>,
+    /// number of edges the constraint models rejected during the search
+    pub constraint_rejections: usize,
+    /// true if the constraint models rejected any edge, in which case the target may be
+    /// reachable without the constraints of this query
+    pub pruned_by_constraints: bool,
+}
+
+impl NoPathDiagnostics {
+    /// collects diagnostics for a search from `source` that could not reach `target`
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - origin of the search
+    /// * `target` - destination of the search
+    /// * `tree` - search tree at the time the frontier was exhausted
+    /// * `graph` - graph that was searched
+    /// * `constraint_rejections` - number of edges rejected by constraint models
+    pub fn new(
+        source: VertexId,
+        target: VertexId,
+        tree: &SearchTree,
+        graph: &Graph,
+        constraint_rejections: usize,
+    ) -> NoPathDiagnostics {
+        let settled: HashSet<VertexId> = tree
+            .keys()
+            .map(|label| *label.vertex_id())
+            .chain(std::iter::once(source))
+            .collect();
+        let nearest = graph.get_vertex(&target).ok().and_then(|dst| {
+            settled
+                .iter()
+                .filter_map(|vertex_id| {
+                    let vertex = graph.get_vertex(vertex_id).ok()?;
+                    let distance =
+                        haversine::coord_distance(&vertex.coordinate, &dst.coordinate).ok()?;
+                    Some((*vertex_id, distance.get::<meter>()))
+                })
+                .min_by(|(a_id, a), (b_id, b)| a.total_cmp(b).then(a_id.cmp(b_id)))
+        });
+        NoPathDiagnostics {
+            source,
+            target,
+            source_component: graph.component_id(&source),
+            target_component: graph.component_id(&target),
+            settled_vertices: settled.len(),
+            nearest_settled_vertex: nearest.map(|(vertex_id, _)| vertex_id),
+            nearest_settled_distance_meters: nearest.map(|(_, distance)| distance),
+            constraint_rejections,
+            pruned_by_constraints: constraint_rejections > 0,
+        }
+    }
+}
+
+impl Display for NoPathDiagnostics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "no path exists between vertices {} and {} after settling {} vertices",
+            self.source, self.target, self.settled_vertices
+        )?;
+        if let (Some(src), Some(dst)) = (self.source_component, self.target_component) {
+            write!(f, ", origin is in strongly connected component {src} and destination in component {dst}")?;
+        }
+        if let (Some(vertex_id), Some(distance)) = (
+            self.nearest_settled_vertex,
+            self.nearest_settled_distance_meters,
+        ) {
+            write!(
+                f,
+                ", nearest settled vertex {vertex_id} is {distance:.1} meters from the destination"
+            )?;
+        }
+        if self.pruned_by_constraints {
+            write!(
+                f,
+                ", constraint models rejected {} edges",
+                self.constraint_rejections
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -1,17 +1,21 @@
 use super::edge_traversal::EdgeTraversal;
+use super::ksp::AdmissibilityCriteria;
 use super::ksp::KspQuery;
 use super::ksp::KspTerminationCriteria;
-use super::ksp::{svp, yens};
+use super::ksp::{penalty, svp, yens};
 use super::search_algorithm_result::SearchAlgorithmResult;
 use super::search_error::SearchError;
 use super::util::RouteSimilarityFunction;
+use super::CandidateSelection;
 use super::SearchInstance;
 use super::{a_star, direction::Direction};
 use crate::algorithm::search::search_algorithm_config::SearchAlgorithmConfig;
-use crate::algorithm::search::TerminationFailurePolicy;
+use crate::algorithm::search::{SearchTreeBudget, SearchTreeStorage, TerminationFailurePolicy};
 use crate::model::cost::TraversalCost;
+use crate::model::map::NearestSearchResult;
 use crate::model::network::EdgeListId;
 use crate::model::network::{EdgeId, VertexId};
+use crate::model::unit::AsF64;
 
 #[derive(Clone, Debug)]
 pub enum SearchAlgorithm {
@@ -23,6 +27,10 @@ pub enum SearchAlgorithm {
         termination_behavior: TerminationFailurePolicy,
         /// if true, use a cost estimate heuristic to guide the search towards destinations
         a_star: bool,
+        /// if provided, limits the memory held by the search tree
+        memory_budget: Option<SearchTreeBudget>,
+        /// how the search tree stores the result states of its branches
+        tree_storage: SearchTreeStorage,
     },
     /// KSP using the single via paths algorithm.
     KspSingleVia {
@@ -35,6 +43,8 @@ pub enum SearchAlgorithm {
         similarity: Option<RouteSimilarityFunction>,
         /// termination criteria for the inner path search function
         termination: Option<KspTerminationCriteria>,
+        /// if provided, removes alternatives that are not admissible
+        admissibility: Option<AdmissibilityCriteria>,
     },
     /// KSP using Yen's Algorithm
     Yens {
@@ -47,10 +57,40 @@ pub enum SearchAlgorithm {
         similarity: Option<RouteSimilarityFunction>,
         /// termination criteria for the inner path search function
         termination: Option<KspTerminationCriteria>,
+        /// if provided, removes alternatives that are not admissible
+        admissibility: Option<AdmissibilityCriteria>,
+    },
+    /// alternative routes using the penalty method
+    Penalty {
+        /// number of routes to find, including the shortest route
+        k: usize,
+        /// path search algorithm to use
+        underlying: Box<SearchAlgorithm>,
+        /// multiplier on the cost of edges on previously found routes
+        penalty_factor: f64,
+        /// most searches to run, defaulting to 2 * k
+        max_searches: Option<usize>,
+        /// if provided, filters out routes based on their similarity to the
+        /// routes that were already accepted
+        similarity: Option<RouteSimilarityFunction>,
+        /// if provided, removes alternatives that are not admissible
+        admissibility: Option<AdmissibilityCriteria>,
     },
 }
 
 impl SearchAlgorithm {
+    pub const DEFAULT_PENALTY_FACTOR: f64 = 1.4;
+
+    /// true if this algorithm, or the path search underlying it, uses A* cost estimates
+    pub fn is_a_star(&self) -> bool {
+        match self {
+            SearchAlgorithm::SingleSourceShortestPath { a_star, .. } => *a_star,
+            SearchAlgorithm::KspSingleVia { underlying, .. } => underlying.is_a_star(),
+            SearchAlgorithm::Yens { underlying, .. } => underlying.is_a_star(),
+            SearchAlgorithm::Penalty { underlying, .. } => underlying.is_a_star(),
+        }
+    }
+
     pub fn run_vertex_oriented(
         &self,
         src_id: VertexId,
@@ -63,9 +103,18 @@ impl SearchAlgorithm {
             SearchAlgorithm::SingleSourceShortestPath {
                 termination_behavior,
                 a_star,
+                memory_budget,
+                tree_storage,
             } => {
-                let search_result =
-                    a_star::run_vertex_oriented(src_id, dst_id_opt, direction, *a_star, si)?;
+                let search_result = a_star::run_vertex_oriented(
+                    src_id,
+                    dst_id_opt,
+                    direction,
+                    *a_star,
+                    memory_budget.as_ref(),
+                    *tree_storage,
+                    si,
+                )?;
                 termination_behavior.handle_termination(&search_result, dst_id_opt.is_some())?;
 
                 let routes = match dst_id_opt {
@@ -79,7 +128,10 @@ impl SearchAlgorithm {
                     trees: vec![search_result.tree],
                     routes,
                     iterations: search_result.iterations,
+                    max_frontier_size: search_result.max_frontier_size,
+                    peak_tree_size: search_result.peak_tree_size,
                     terminated: search_result.terminated.clone(),
+                    settled: search_result.settled,
                 })
             }
             SearchAlgorithm::Yens {
@@ -87,6 +139,7 @@ impl SearchAlgorithm {
                 underlying,
                 similarity,
                 termination,
+                admissibility,
             } => {
                 let dst_id = dst_id_opt.ok_or_else(|| {
                     SearchError::BuildError(String::from(
@@ -96,13 +149,15 @@ impl SearchAlgorithm {
                 let sim_fn = similarity.clone().unwrap_or_default();
                 let term_fn = termination.clone().unwrap_or_default();
                 let ksp_query = KspQuery::new(src_id, dst_id, query, *k)?;
-                yens::run(&ksp_query, &term_fn, &sim_fn, si, underlying)
+                let result = yens::run(&ksp_query, &term_fn, &sim_fn, si, underlying)?;
+                filter_admissible(result, admissibility.as_ref(), &ksp_query, si, underlying)
             }
             SearchAlgorithm::KspSingleVia {
                 k,
                 underlying,
                 similarity,
                 termination,
+                admissibility,
             } => {
                 let dst_id = dst_id_opt.ok_or_else(|| {
                     SearchError::BuildError(String::from(
@@ -112,10 +167,222 @@ impl SearchAlgorithm {
                 let sim_fn = similarity.clone().unwrap_or_default();
                 let term_fn = termination.clone().unwrap_or_default();
                 let ksp_query = KspQuery::new(src_id, dst_id, query, *k)?;
-                svp::run(&ksp_query, &term_fn, &sim_fn, si, underlying)
+                let result = svp::run(&ksp_query, &term_fn, &sim_fn, si, underlying)?;
+                filter_admissible(result, admissibility.as_ref(), &ksp_query, si, underlying)
+            }
+            SearchAlgorithm::Penalty {
+                k,
+                underlying,
+                penalty_factor,
+                max_searches,
+                similarity,
+                admissibility,
+            } => {
+                let dst_id = dst_id_opt.ok_or_else(|| {
+                    SearchError::BuildError(String::from(
+                        "attempting to run penalty alternatives algorithm without destination",
+                    ))
+                })?;
+                let ksp_query = KspQuery::new(src_id, dst_id, query, *k)?;
+                let max_searches = max_searches.unwrap_or(ksp_query.k * 2);
+                let result = penalty::run(
+                    &ksp_query,
+                    *penalty_factor,
+                    max_searches,
+                    similarity.as_ref(),
+                    si,
+                    underlying,
+                )?;
+                filter_admissible(result, admissibility.as_ref(), &ksp_query, si, underlying)
+            }
+        }
+    }
+
+    /// runs a single one-to-many search rooted at `root` that settles every vertex in
+    /// `leaves`, returning a result for each leaf in the order provided. with a forward
+    /// direction, the root is the shared origin and each route runs from the root to a
+    /// leaf. with a reverse direction, the root is the shared destination and each route
+    /// runs from a leaf to the root.
+    ///
+    /// the search tree is shared by all leaves, so it is not included in the results,
+    /// and each result reports the iterations and frontier size of the shared search.
+    /// only supported by the SingleSourceShortestPath algorithm, which runs as Dijkstra's
+    /// algorithm as there is no single destination to guide an A* cost estimate.
+    pub fn run_vertex_oriented_many(
+        &self,
+        root: VertexId,
+        leaves: &[VertexId],
+        direction: &Direction,
+        si: &SearchInstance,
+    ) -> Result<Vec<Result<SearchAlgorithmResult, SearchError>>, SearchError> {
+        let SearchAlgorithm::SingleSourceShortestPath {
+            termination_behavior,
+            memory_budget,
+            tree_storage,
+            ..
+        } = self
+        else {
+            return Err(SearchError::BuildError(String::from(
+                "one-to-many search is only supported by the SingleSourceShortestPath algorithm",
+            )));
+        };
+        let search_result = a_star::run_vertex_oriented_many(
+            root,
+            leaves,
+            direction,
+            memory_budget.as_ref(),
+            *tree_storage,
+            si,
+        )?;
+        termination_behavior.handle_termination(&search_result, true)?;
+        let results = leaves
+            .iter()
+            .map(|leaf| {
+                let route = if *leaf == root {
+                    vec![]
+                } else {
+                    search_result.tree.backtrack(*leaf)?
+                };
+                Ok(SearchAlgorithmResult {
+                    trees: vec![],
+                    routes: vec![route],
+                    iterations: search_result.iterations,
+                    max_frontier_size: search_result.max_frontier_size,
+                    peak_tree_size: search_result.peak_tree_size,
+                    terminated: search_result.terminated.clone(),
+                    settled: vec![],
+                })
+            })
+            .collect();
+        Ok(results)
+    }
+
+    /// runs a single search from every origin candidate toward every destination
+    /// candidate, returning the cheapest route found along with the candidates it uses.
+    /// candidates are vertices, or edges which the route starts or ends with. the search
+    /// starts from every origin at once, as if from a virtual super-source, so a route
+    /// from a poorly snapped origin, such as the wrong side of a divided road, is avoided
+    /// when another candidate leads to a cheaper route. without destinations, the
+    /// resulting tree grows from every origin candidate.
+    ///
+    /// only supported by the SingleSourceShortestPath algorithm.
+    pub fn run_candidates(
+        &self,
+        origins: &[NearestSearchResult],
+        destinations: &[NearestSearchResult],
+        direction: &Direction,
+        si: &SearchInstance,
+    ) -> Result<(SearchAlgorithmResult, CandidateSelection), SearchError> {
+        let SearchAlgorithm::SingleSourceShortestPath {
+            termination_behavior,
+            a_star,
+            memory_budget,
+            tree_storage,
+        } = self
+        else {
+            return Err(SearchError::BuildError(String::from(
+                "origin and destination candidates are only supported by the SingleSourceShortestPath algorithm",
+            )));
+        };
+        let sources = origins
+            .iter()
+            .map(|o| match o {
+                NearestSearchResult::NearestVertex(v) => Ok(*v),
+                NearestSearchResult::NearestEdge(el, e) => si.graph.dst_vertex_id(el, e),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let targets = destinations
+            .iter()
+            .map(|d| match d {
+                NearestSearchResult::NearestVertex(v) => Ok(*v),
+                NearestSearchResult::NearestEdge(el, e) => si.graph.src_vertex_id(el, e),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let search_result = a_star::run_vertex_oriented_candidates(
+            &sources,
+            &targets,
+            direction,
+            *a_star,
+            memory_budget.as_ref(),
+            *tree_storage,
+            si,
+        )?;
+        termination_behavior.handle_termination(&search_result, !targets.is_empty())?;
+
+        // the search ends at the cheapest reachable target, so the cheapest route
+        // among the targets in the tree identifies the destination candidate
+        let mut best: Option<(usize, Vec<EdgeTraversal>, f64)> = None;
+        for (index, target) in targets.iter().enumerate() {
+            if search_result.tree.get_min_cost_label(*target).is_none() {
+                continue;
+            }
+            let route = search_result.tree.backtrack(*target)?;
+            let cost = route
+                .iter()
+                .map(|et| et.cost.objective_cost.as_f64())
+                .sum::<f64>();
+            if best.as_ref().is_none_or(|(_, _, c)| cost < *c) {
+                best = Some((index, route, cost));
             }
         }
+        let mut selection = CandidateSelection::default();
+        let mut routes = vec![];
+        if let Some((destination, mut route, _)) = best {
+            let origin_vertex = match route.first() {
+                Some(et) => si.graph.src_vertex_id(&et.edge_list_id, &et.edge_id)?,
+                None => targets[destination],
+            };
+            let origin = sources.iter().position(|s| *s == origin_vertex);
+            let initial_state = si.initial_state()?;
+            let origin_edge = match origin.map(|o| origins[o]) {
+                Some(NearestSearchResult::NearestEdge(el, e)) => {
+                    route.insert(
+                        0,
+                        EdgeTraversal {
+                            edge_list_id: el,
+                            edge_id: e,
+                            cost: TraversalCost::default(),
+                            result_state: initial_state.clone(),
+                        },
+                    );
+                    Some((el, e))
+                }
+                _ => None,
+            };
+            if let NearestSearchResult::NearestEdge(el, e) = destinations[destination] {
+                if origin_edge != Some((el, e)) {
+                    let final_state = route
+                        .last()
+                        .map(|et| et.result_state.clone())
+                        .unwrap_or(initial_state);
+                    route.push(EdgeTraversal {
+                        edge_list_id: el,
+                        edge_id: e,
+                        cost: TraversalCost::default(),
+                        result_state: final_state,
+                    });
+                }
+            }
+            selection = CandidateSelection {
+                origin,
+                destination: Some(destination),
+            };
+            routes.push(route);
+        }
+
+        let result = SearchAlgorithmResult {
+            trees: vec![search_result.tree],
+            routes,
+            iterations: search_result.iterations,
+            max_frontier_size: search_result.max_frontier_size,
+            peak_tree_size: search_result.peak_tree_size,
+            terminated: search_result.terminated.clone(),
+            settled: search_result.settled,
+        };
+        Ok((result, selection))
     }
+
     pub fn run_edge_oriented(
         &self,
         src: (EdgeListId, EdgeId),
@@ -128,9 +395,18 @@ impl SearchAlgorithm {
             SearchAlgorithm::SingleSourceShortestPath {
                 termination_behavior,
                 a_star,
+                memory_budget,
+                tree_storage,
             } => {
-                let search_result =
-                    a_star::run_edge_oriented(src, dst_opt, direction, *a_star, si)?;
+                let search_result = a_star::run_edge_oriented(
+                    src,
+                    dst_opt,
+                    direction,
+                    *a_star,
+                    memory_budget.as_ref(),
+                    *tree_storage,
+                    si,
+                )?;
 
                 termination_behavior.handle_termination(&search_result, dst_opt.is_some())?;
 
@@ -147,7 +423,10 @@ impl SearchAlgorithm {
                     trees: vec![search_result.tree],
                     routes,
                     iterations: search_result.iterations,
+                    max_frontier_size: search_result.max_frontier_size,
+                    peak_tree_size: search_result.peak_tree_size,
                     terminated: search_result.terminated.clone(),
+                    settled: search_result.settled,
                 })
             }
             SearchAlgorithm::KspSingleVia {
@@ -155,13 +434,18 @@ impl SearchAlgorithm {
                 underlying: _,
                 similarity: _,
                 termination: _,
+                admissibility: _,
             } => run_edge_oriented(src, dst_opt, query, direction, self, si),
             SearchAlgorithm::Yens {
                 k: _,
                 underlying: _,
                 similarity: _,
                 termination: _,
+                admissibility: _,
             } => run_edge_oriented(src, dst_opt, query, direction, self, si),
+            SearchAlgorithm::Penalty { .. } => {
+                run_edge_oriented(src, dst_opt, query, direction, self, si)
+            }
         }
     }
 }
@@ -171,21 +455,31 @@ impl From<&SearchAlgorithmConfig> for SearchAlgorithm {
         match value {
             SearchAlgorithmConfig::Dijkstras {
                 termination_behavior,
+                memory_budget,
+                tree_storage,
             } => Self::SingleSourceShortestPath {
                 termination_behavior: termination_behavior.clone().unwrap_or_default(),
                 a_star: false,
+                memory_budget: memory_budget.clone(),
+                tree_storage: tree_storage.unwrap_or_default(),
             },
             SearchAlgorithmConfig::AStar {
                 termination_behavior,
+                memory_budget,
+                tree_storage,
+                ..
             } => Self::SingleSourceShortestPath {
                 termination_behavior: termination_behavior.clone().unwrap_or_default(),
                 a_star: true,
+                memory_budget: memory_budget.clone(),
+                tree_storage: tree_storage.unwrap_or_default(),
             },
             SearchAlgorithmConfig::KspSingleVia {
                 k,
                 underlying,
                 similarity,
                 termination,
+                admissibility,
             } => {
                 let underlying: Box<SearchAlgorithm> = Box::new(underlying.as_ref().into());
                 Self::KspSingleVia {
@@ -193,6 +487,7 @@ impl From<&SearchAlgorithmConfig> for SearchAlgorithm {
                     underlying,
                     similarity: similarity.clone(),
                     termination: termination.clone(),
+                    admissibility: admissibility.clone(),
                 }
             }
             SearchAlgorithmConfig::Yens {
@@ -200,6 +495,7 @@ impl From<&SearchAlgorithmConfig> for SearchAlgorithm {
                 underlying,
                 similarity,
                 termination,
+                admissibility,
             } => {
                 let underlying: Box<SearchAlgorithm> = Box::new(underlying.as_ref().into());
                 Self::Yens {
@@ -207,12 +503,45 @@ impl From<&SearchAlgorithmConfig> for SearchAlgorithm {
                     underlying,
                     similarity: similarity.clone(),
                     termination: termination.clone(),
+                    admissibility: admissibility.clone(),
+                }
+            }
+            SearchAlgorithmConfig::Penalty {
+                k,
+                underlying,
+                penalty_factor,
+                max_searches,
+                similarity,
+                admissibility,
+            } => {
+                let underlying: Box<SearchAlgorithm> = Box::new(underlying.as_ref().into());
+                Self::Penalty {
+                    k: *k,
+                    underlying,
+                    penalty_factor: penalty_factor.unwrap_or(Self::DEFAULT_PENALTY_FACTOR),
+                    max_searches: *max_searches,
+                    similarity: similarity.clone(),
+                    admissibility: admissibility.clone(),
                 }
             }
         }
     }
 }
 
+/// applies the admissibility criteria of an alternative routes algorithm, if provided
+fn filter_admissible(
+    result: SearchAlgorithmResult,
+    admissibility: Option<&AdmissibilityCriteria>,
+    query: &KspQuery,
+    si: &SearchInstance,
+    underlying: &SearchAlgorithm,
+) -> Result<SearchAlgorithmResult, SearchError> {
+    match admissibility {
+        Some(criteria) => criteria.filter(result, query, si, underlying),
+        None => Ok(result),
+    }
+}
+
 // convenience method when origin and destination are specified using
 /// edge ids instead of vertex ids. invokes a vertex-oriented search
 /// from the out-vertex of the source edge to the in-vertex of the
@@ -228,7 +557,7 @@ pub fn run_edge_oriented(
     si: &SearchInstance,
 ) -> Result<SearchAlgorithmResult, SearchError> {
     // 1. guard against edge conditions (src==dst, src.dst_v == dst.src_v)
-    let initial_state = si.state_model.initial_state(None)?;
+    let initial_state = si.initial_state()?;
     let e1_src = si.graph.src_vertex_id(&source.0, &source.1)?;
     let e1_label = si
         .label_model
@@ -238,7 +567,7 @@ pub fn run_edge_oriented(
         edge_list_id: source.0,
         edge_id: source.1,
         cost: TraversalCost::default(),
-        result_state: si.state_model.initial_state(None)?,
+        result_state: si.initial_state()?,
     };
 
     match target {
@@ -247,7 +576,10 @@ pub fn run_edge_oriented(
                 mut trees,
                 mut routes,
                 iterations,
+                max_frontier_size,
+                peak_tree_size,
                 terminated,
+                settled,
             } = alg.run_vertex_oriented(e1_dst, None, query, direction, si)?;
 
             let dst_label =
@@ -271,7 +603,10 @@ pub fn run_edge_oriented(
                 trees,
                 routes,
                 iterations: iterations + 1,
+                max_frontier_size,
+                peak_tree_size,
                 terminated,
+                settled,
             };
             Ok(updated)
         }
@@ -290,7 +625,10 @@ pub fn run_edge_oriented(
                 trees,
                 mut routes,
                 iterations,
+                max_frontier_size,
+                peak_tree_size,
                 terminated,
+                settled,
             } = alg.run_vertex_oriented(e1_dst, Some(e2_src), query, direction, si)?;
 
             if trees.is_empty() {
@@ -318,7 +656,10 @@ pub fn run_edge_oriented(
                 trees,
                 routes,
                 iterations: iterations + 2,
+                max_frontier_size,
+                peak_tree_size,
                 terminated,
+                settled,
             };
             Ok(result)
         }
@@ -1,7 +1,10 @@
 use serde::{Deserialize, Serialize};
 
 use crate::algorithm::search::{
-    ksp::KspTerminationCriteria, util::RouteSimilarityFunction, TerminationFailurePolicy,
+    a_star::HeuristicConfig,
+    ksp::{AdmissibilityCriteria, KspTerminationCriteria},
+    util::RouteSimilarityFunction,
+    SearchTreeBudget, SearchTreeStorage, TerminationFailurePolicy,
 };
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -12,12 +15,25 @@ pub enum SearchAlgorithmConfig {
     Dijkstras {
         /// optional argument to define handling of terminated searches
         termination_behavior: Option<TerminationFailurePolicy>,
+        /// optional limit on the memory held by the search tree
+        memory_budget: Option<SearchTreeBudget>,
+        /// optional storage of the search tree states, "nodes" (default) or "arena"
+        tree_storage: Option<SearchTreeStorage>,
     },
     /// Classic best-first search algorithm.
     #[serde(rename = "a*")]
     AStar {
         /// optional argument to define handling of terminated searches
         termination_behavior: Option<TerminationFailurePolicy>,
+        /// optional limit on the memory held by the search tree
+        memory_budget: Option<SearchTreeBudget>,
+        /// optional storage of the search tree states, "nodes" (default) or "arena"
+        tree_storage: Option<SearchTreeStorage>,
+        /// optional heuristic to replace the traversal model cost estimates
+        heuristic: Option<HeuristicConfig>,
+        /// optional weight (no less than 1) on the cost estimates for weighted A*,
+        /// which explores fewer vertices but may return a more costly route
+        heuristic_weight: Option<f64>,
     },
     /// K-shortest paths algorithm that relies on a novel bidirectional search algorithm
     /// combined with a map-algebraic heuristic to identify midpoints on approximate ksp
@@ -36,6 +52,8 @@ pub enum SearchAlgorithmConfig {
         similarity: Option<RouteSimilarityFunction>,
         /// termination criteria for the inner path search function
         termination: Option<KspTerminationCriteria>,
+        /// if provided, removes alternatives that are not admissible
+        admissibility: Option<AdmissibilityCriteria>,
     },
     /// K-shortest paths algorithm that relies on successive edge cuts to find alternatives
     /// to the true shortest path. Taken from the paper Yen, Jin Y. "An algorithm for finding
@@ -51,5 +69,54 @@ pub enum SearchAlgorithmConfig {
         similarity: Option<RouteSimilarityFunction>,
         /// termination criteria for the inner path search function
         termination: Option<KspTerminationCriteria>,
+        /// if provided, removes alternatives that are not admissible
+        admissibility: Option<AdmissibilityCriteria>,
     },
+    /// Alternative routes via the penalty method: after each search, the objective cost
+    /// of the edges on the route found is multiplied by a penalty factor and the search
+    /// is repeated, steering each search away from the routes found before it. Runs a
+    /// single search per alternative, which is cheaper than the KSP algorithms.
+    Penalty {
+        /// number of routes to find, including the shortest route
+        k: usize,
+        /// path search algorithm to use
+        underlying: Box<SearchAlgorithmConfig>,
+        /// multiplier (greater than 1) on the cost of edges on previously found routes.
+        /// defaults to 1.4.
+        penalty_factor: Option<f64>,
+        /// most searches to run, including the search for the shortest route, as a
+        /// search may repeat a route that was already found. defaults to 2 * k.
+        max_searches: Option<usize>,
+        /// if provided, filters out routes based on their similarity to the routes
+        /// that were already accepted
+        similarity: Option<RouteSimilarityFunction>,
+        /// if provided, removes alternatives that are not admissible
+        admissibility: Option<AdmissibilityCriteria>,
+    },
+}
+
+impl SearchAlgorithmConfig {
+    /// the heuristic configured for A*, including an A* search underlying a KSP algorithm
+    pub fn heuristic(&self) -> Option<&HeuristicConfig> {
+        match self {
+            SearchAlgorithmConfig::Dijkstras { .. } => None,
+            SearchAlgorithmConfig::AStar { heuristic, .. } => heuristic.as_ref(),
+            SearchAlgorithmConfig::KspSingleVia { underlying, .. } => underlying.heuristic(),
+            SearchAlgorithmConfig::Yens { underlying, .. } => underlying.heuristic(),
+            SearchAlgorithmConfig::Penalty { underlying, .. } => underlying.heuristic(),
+        }
+    }
+
+    /// the weighted A* heuristic weight, including an A* search underlying a KSP algorithm
+    pub fn heuristic_weight(&self) -> Option<f64> {
+        match self {
+            SearchAlgorithmConfig::Dijkstras { .. } => None,
+            SearchAlgorithmConfig::AStar {
+                heuristic_weight, ..
+            } => *heuristic_weight,
+            SearchAlgorithmConfig::KspSingleVia { underlying, .. } => underlying.heuristic_weight(),
+            SearchAlgorithmConfig::Yens { underlying, .. } => underlying.heuristic_weight(),
+            SearchAlgorithmConfig::Penalty { underlying, .. } => underlying.heuristic_weight(),
+        }
+    }
 }
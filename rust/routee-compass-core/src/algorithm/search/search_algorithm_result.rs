@@ -1,12 +1,18 @@
 use allocative::Allocative;
 
 use super::edge_traversal::EdgeTraversal;
-use crate::algorithm::search::SearchTree;
+use crate::algorithm::search::{SearchTree, SettledLabel};
 
 #[derive(Default, Allocative)]
 pub struct SearchAlgorithmResult {
     pub trees: Vec<SearchTree>,
     pub routes: Vec<Vec<EdgeTraversal>>,
     pub iterations: u64,
+    /// largest frontier size observed across the searches run to create this result
+    pub max_frontier_size: usize,
+    /// largest search tree size observed across the searches run to create this result
+    pub peak_tree_size: usize,
     pub terminated: Option<String>,
+    /// labels in the order they were settled by the search, if recorded
+    pub settled: Vec<SettledLabel>,
 }
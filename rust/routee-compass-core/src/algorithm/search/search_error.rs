@@ -1,12 +1,12 @@
 use crate::{
-    algorithm::search::SearchTreeError,
+    algorithm::search::{NoPathDiagnostics, SearchTreeError},
     model::{
         constraint::ConstraintModelError,
         cost::CostModelError,
         label::label_model_error::LabelModelError,
         network::{EdgeId, NetworkError, VertexId},
         state::StateModelError,
-        termination::TerminationModelError,
+        termination::{MemoryUnit, TerminationModelError},
         traversal::TraversalModelError,
     },
 };
@@ -57,8 +57,16 @@ pub enum SearchError {
     },
     #[error("query terminated due to {0}")]
     QueryTerminated(String),
+    #[error("search tree exceeded memory budget of {limit} {unit} with {tree_size} nodes")]
+    MemoryBudgetExceeded {
+        limit: f64,
+        unit: MemoryUnit,
+        tree_size: usize,
+    },
     #[error("no path exists between vertices {0} and {1} after searching {2} edges")]
     NoPathExistsBetweenVertices(VertexId, VertexId, usize),
+    #[error("{0}")]
+    NoPathExists(Box<NoPathDiagnostics>),
     #[error("no path exists between edges {0} and {1} after searching {2} edges")]
     NoPathExistsBetweenEdges(EdgeId, EdgeId, usize),
     #[error("error accessing shared read-only dataset: {0}")]
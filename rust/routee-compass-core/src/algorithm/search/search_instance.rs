@@ -1,14 +1,17 @@
 use crate::{
-    algorithm::search::{Direction, EdgeTraversal, SearchError, SearchTree},
+    algorithm::search::{
+        a_star::FreeFlowHeuristic, Corridor, Direction, EdgeTraversal, SearchError, SearchTree,
+    },
     model::{
         constraint::ConstraintModel,
         cost::CostModel,
         label::{label_model::LabelModel, Label},
         map::MapModel,
         network::{EdgeId, EdgeListId, Graph},
-        state::StateModel,
+        state::{StateModel, StateModelError, StateVariable},
         termination::TerminationModel,
         traversal::TraversalModel,
+        unit::Cost,
     },
 };
 use std::sync::Arc;
@@ -17,6 +20,8 @@ use std::sync::Arc;
 /// to execute a search query. It encapsulates the graph, constraints, traversal logic,
 /// cost calculations, and termination criteria, providing a unified interface for
 /// search algorithms to interact with the underlying network and models.
+/// all models are shared references, so cloning a `SearchInstance` is cheap.
+#[derive(Clone)]
 pub struct SearchInstance {
     pub graph: Arc<Graph>,
     pub constraint_models: Vec<Arc<dyn ConstraintModel>>,
@@ -27,9 +32,31 @@ pub struct SearchInstance {
     pub termination_model: Arc<TerminationModel>,
     pub label_model: Arc<dyn LabelModel>,
     pub default_edge_list: Option<usize>,
+    /// if present, replaces the traversal model cost estimates used by A*
+    pub heuristic: Option<FreeFlowHeuristic>,
+    /// scales the A* cost estimates for weighted A*. 1.0 runs an unweighted search
+    pub heuristic_weight: f64,
+    /// if true, searches record the order of their settled labels for debugging
+    pub record_settled: bool,
+    /// if present, searches do not extend the tree beyond this objective cost from the root
+    pub max_cost: Option<Cost>,
+    /// if present, searches only traverse the edges in this corridor
+    pub corridor: Option<Arc<Corridor>>,
+    /// if present, searches start from this state vector instead of the initial values
+    /// of the state model, such as when re-planning a trip that is underway
+    pub initial_state: Option<Vec<StateVariable>>,
 }
 
 impl SearchInstance {
+    /// the state vector at the root of a search: the initial state of this instance,
+    /// if set, and otherwise the initial values of the state model.
+    pub fn initial_state(&self) -> Result<Vec<StateVariable>, StateModelError> {
+        match &self.initial_state {
+            Some(state) => Ok(state.clone()),
+            None => self.state_model.initial_state(None),
+        }
+    }
+
     /// Retrieves the traversal model that should be used for traversal estimation
     /// when no specific edge is being traversed (e.g., at the start of a search or
     /// when calculating heuristics). It falls back to the default edge list's model.
@@ -103,7 +130,7 @@ impl SearchInstance {
         path: &[(EdgeListId, EdgeId)],
     ) -> Result<Vec<EdgeTraversal>, SearchError> {
         let mut edge_traversals = Vec::with_capacity(path.len());
-        let mut current_state = self.state_model.initial_state(None)?;
+        let mut current_state = self.initial_state()?;
         let mut tree = SearchTree::new(Direction::Forward);
 
         let mut prev_label = if let Some((edge_list_id, edge_id)) = path.first() {
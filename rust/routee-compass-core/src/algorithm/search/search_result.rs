@@ -1,4 +1,4 @@
-use crate::algorithm::search::SearchTree;
+use crate::algorithm::search::{SearchTree, SettledLabel};
 
 /// the result of running a [`super::SearchAlgorithm`].
 #[derive(Default)]
@@ -7,10 +7,17 @@ pub struct SearchResult {
     pub tree: SearchTree,
     /// number of iterations run to create this tree
     pub iterations: u64,
+    /// largest number of labels held in the frontier at any point during the search
+    pub max_frontier_size: usize,
+    /// largest number of labels held in the tree at any point during the search.
+    /// may exceed the final tree size if the tree was pruned.
+    pub peak_tree_size: usize,
     /// if present, a message explaining a forced termination of the search.
     /// if not present, the search terminated naturally by reaching an
     /// empty frontier state.
     pub terminated: Option<String>,
+    /// labels in the order they were settled, if the search instance records them
+    pub settled: Vec<SettledLabel>,
 }
 
 impl SearchResult {
@@ -20,7 +27,10 @@ impl SearchResult {
         SearchResult {
             tree,
             iterations,
+            max_frontier_size: 0,
+            peak_tree_size: 0,
             terminated: None,
+            settled: vec![],
         }
     }
 
@@ -31,7 +41,28 @@ impl SearchResult {
         SearchResult {
             tree,
             iterations,
+            max_frontier_size: 0,
+            peak_tree_size: 0,
             terminated: Some(explanation),
+            settled: vec![],
         }
     }
+
+    /// sets the largest frontier size observed while running the search.
+    pub fn with_max_frontier_size(mut self, max_frontier_size: usize) -> SearchResult {
+        self.max_frontier_size = max_frontier_size;
+        self
+    }
+
+    /// sets the largest search tree size observed while running the search.
+    pub fn with_peak_tree_size(mut self, peak_tree_size: usize) -> SearchResult {
+        self.peak_tree_size = peak_tree_size;
+        self
+    }
+
+    /// sets the labels settled by the search, in the order they were settled.
+    pub fn with_settled(mut self, settled: Vec<SettledLabel>) -> SearchResult {
+        self.settled = settled;
+        self
+    }
 }
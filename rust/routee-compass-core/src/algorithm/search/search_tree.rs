@@ -1,11 +1,13 @@
-use super::{EdgeTraversal, SearchTreeNode};
+use super::{EdgeTraversal, SearchTreeNode, SearchTreeStorage, StateArena};
 use crate::algorithm::search::search_pruning;
 use crate::model::label::LabelModel;
 use crate::model::network::{EdgeId, EdgeListId, Graph, NetworkError, VertexId};
+use crate::model::state::StateVariable;
 use crate::model::unit::AsF64;
 use crate::{algorithm::search::Direction, model::label::Label};
 use allocative::Allocative;
 use ordered_float::OrderedFloat;
+use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
@@ -21,6 +23,8 @@ pub struct SearchTree {
     root: Option<Label>,
     /// Tree orientation for bi-directional search support
     direction: Direction,
+    /// result states of the branches, if the tree uses arena storage
+    arena: Option<StateArena>,
 }
 
 impl Default for SearchTree {
@@ -32,11 +36,22 @@ impl Default for SearchTree {
 impl SearchTree {
     /// Create a new empty search tree with the specified orientation
     pub fn new(direction: Direction) -> Self {
+        Self::with_storage(direction, SearchTreeStorage::default())
+    }
+
+    /// Create a new empty search tree that stores the result states of its branches
+    /// as specified
+    pub fn with_storage(direction: Direction, storage: SearchTreeStorage) -> Self {
+        let arena = match storage {
+            SearchTreeStorage::Nodes => None,
+            SearchTreeStorage::Arena => Some(StateArena::default()),
+        };
         Self {
             nodes: HashMap::new(),
             labels: HashMap::new(),
             root: None,
             direction,
+            arena,
         }
     }
 
@@ -50,18 +65,23 @@ impl SearchTree {
     /// Set the root node of the tree
     pub fn set_root(&mut self, root_label: Label) {
         let root_node = SearchTreeNode::new_root(self.direction);
-        self.nodes.insert(root_label.clone(), root_node);
-        if root_label.needs_vertex_map_storage() {
-            self.labels
-                .entry(*root_label.vertex_id())
-                .and_modify(|l| {
-                    let _ = l.insert(root_label.clone());
-                })
-                .or_insert(HashSet::from([root_label.clone()]));
-        }
+        self.insert_node(root_label.clone(), root_node);
         self.root = Some(root_label);
     }
 
+    /// Adds another root node to the tree, such as for a search that starts from several
+    /// candidate origins. the first root added remains the value of [`SearchTree::root`],
+    /// and backtracking from any label stops at whichever root it descends from.
+    pub fn add_root(&mut self, root_label: Label) {
+        match self.root.take() {
+            None => self.set_root(root_label),
+            Some(first) => {
+                self.set_root(root_label);
+                self.root = Some(first);
+            }
+        }
+    }
+
     /// Insert the trajectory (parent) -[edge]-> (child) as a node in the tree.
     /// Note: dominated entries should be pruned by the caller before insertion.
     pub fn insert(
@@ -89,20 +109,144 @@ impl SearchTree {
         }
 
         // Create the new node
-        let new_node =
+        let mut new_node =
             SearchTreeNode::new_child(edge_traversal, parent_label.clone(), self.direction);
+        self.store_state(&mut new_node)?;
 
         // Insert the new node
-        self.nodes.insert(child_label.clone(), new_node);
-        if child_label.needs_vertex_map_storage() {
+        self.insert_node(child_label, new_node);
+
+        Ok(())
+    }
+
+    /// stores a node and indexes its label by vertex, without updating child counts
+    fn insert_node(&mut self, label: Label, node: SearchTreeNode) {
+        if label.needs_vertex_map_storage() {
             self.labels
-                .entry(*child_label.vertex_id())
-                .and_modify(|l| {
-                    let _ = l.insert(child_label.clone());
-                })
-                .or_insert(HashSet::from([child_label.clone()]));
+                .entry(*label.vertex_id())
+                .or_default()
+                .insert(label.clone());
+        }
+        if let Some(replaced) = self.nodes.insert(label, node) {
+            self.free_state(&replaced);
+        }
+    }
+
+    /// moves the result state of a new branch into the state arena, if this tree has one
+    fn store_state(&mut self, node: &mut SearchTreeNode) -> Result<(), SearchTreeError> {
+        if let (
+            Some(arena),
+            SearchTreeNode::Branch {
+                incoming_edge,
+                state_slot,
+                ..
+            },
+        ) = (self.arena.as_mut(), node)
+        {
+            let state = std::mem::take(&mut incoming_edge.result_state);
+            *state_slot = Some(arena.insert(&state)?);
+        }
+        Ok(())
+    }
+
+    /// releases the slot of a removed branch in the state arena, if this tree has one
+    fn free_state(&mut self, node: &SearchTreeNode) {
+        if let (Some(arena), Some(slot)) = (self.arena.as_mut(), node.state_slot()) {
+            arena.remove(slot);
+        }
+    }
+
+    /// Extract the subtree rooted at a label as a new tree with the same direction, where
+    /// the label is the root. each edge traversal is copied as-is, so the state of each
+    /// edge traversal still reflects the search from the original root.
+    ///
+    /// # Arguments
+    /// * `label` - label of this tree that becomes the root of the subtree
+    ///
+    /// # Returns
+    /// The label along with every label that descends from it
+    pub fn subtree(&self, label: &Label) -> Result<SearchTree, SearchTreeError> {
+        if !self.nodes.contains_key(label) {
+            return Err(SearchTreeError::LabelNotFound(label.clone()));
+        }
+        let mut children: HashMap<&Label, Vec<&Label>> = HashMap::new();
+        for (child, node) in self.nodes.iter() {
+            if let Some(parent) = node.parent_label() {
+                children.entry(parent).or_default().push(child);
+            }
+        }
+
+        let mut subtree = SearchTree::with_storage(self.direction, self.storage());
+        subtree.set_root(label.clone());
+        let mut stack = vec![label];
+        while let Some(parent) = stack.pop() {
+            for child in children.get(parent).into_iter().flatten() {
+                if subtree.contains(child) {
+                    return Err(SearchTreeError::CycleDetected(format!(
+                        "label {child} reached twice while extracting subtree at {label}"
+                    )));
+                }
+                let node = self
+                    .get(child)
+                    .ok_or_else(|| SearchTreeError::MissingNodeForLabel((*child).clone()))?;
+                let mut node = with_owned_state(node, self.arena.as_ref());
+                subtree.store_state(&mut node)?;
+                subtree.insert_node((*child).clone(), node);
+                stack.push(child);
+            }
+        }
+        if let Some(root) = subtree.nodes.get_mut(label) {
+            for _ in children.get(label).into_iter().flatten() {
+                root.increment_child_count();
+            }
         }
+        Ok(subtree)
+    }
 
+    /// Re-root this tree at one of its labels, discarding every label that does not
+    /// descend from it. see [`SearchTree::subtree`].
+    pub fn reroot(&mut self, label: &Label) -> Result<(), SearchTreeError> {
+        *self = self.subtree(label)?;
+        Ok(())
+    }
+
+    /// Merge another tree with the same direction into this tree. a root of the other
+    /// tree that is also a label of this tree has its branches grafted onto that label,
+    /// while any other root is added as another root of this tree. if a label is in both
+    /// trees, the node of this tree is kept, and the labels that descend from it in the
+    /// other tree are attached to it.
+    pub fn merge(&mut self, other: SearchTree) -> Result<(), SearchTreeError> {
+        if other.direction != self.direction {
+            return Err(SearchTreeError::InvalidBranchStructure(format!(
+                "cannot merge a {:?} tree into a {:?} tree",
+                other.direction, self.direction
+            )));
+        }
+        let mut new_parents = vec![];
+        for (label, node) in other.nodes.iter() {
+            if self.nodes.contains_key(label) {
+                continue;
+            }
+            match with_owned_state(node, other.arena.as_ref()) {
+                SearchTreeNode::Root { .. } => self.add_root(label.clone()),
+                SearchTreeNode::Branch {
+                    incoming_edge,
+                    parent,
+                    ..
+                } => {
+                    new_parents.push(parent.clone());
+                    let mut node = SearchTreeNode::new_child(incoming_edge, parent, self.direction);
+                    self.store_state(&mut node)?;
+                    self.insert_node(label.clone(), node);
+                }
+            }
+        }
+        for parent in new_parents.into_iter() {
+            self.nodes
+                .get_mut(&parent)
+                .ok_or(SearchTreeError::ParentNotFound(parent))?
+                .increment_child_count();
+        }
         Ok(())
     }
 
@@ -115,6 +259,8 @@ impl SearchTree {
             .remove(label)
             .ok_or_else(|| SearchTreeError::LabelNotFound(label.clone()))?;
 
+        self.free_state(&node);
+
         // Decrement child count of parent
         if let Some(parent_label) = node.parent_label() {
             if let Some(parent_node) = self.nodes.get_mut(parent_label) {
@@ -154,6 +300,33 @@ impl SearchTree {
         self.nodes.get(label)
     }
 
+    /// Get the result state of the edge traversal that reached a label, from its node
+    /// or from the state arena. None if the label is a root or is not in the tree.
+    pub fn get_state(&self, label: &Label) -> Option<&[StateVariable]> {
+        let node = self.nodes.get(label)?;
+        match (node.state_slot(), &self.arena) {
+            (Some(slot), Some(arena)) => arena.get(slot),
+            _ => node.incoming_edge().map(|et| et.result_state.as_slice()),
+        }
+    }
+
+    /// Get a node by its label, holding the result state of its incoming edge. with
+    /// arena storage, the node is copied so that it can hold the state.
+    pub fn get_with_state(&self, label: &Label) -> Option<Cow<'_, SearchTreeNode>> {
+        let node = self.nodes.get(label)?;
+        match (node.state_slot(), &self.arena) {
+            (Some(_), Some(arena)) => Some(Cow::Owned(with_owned_state(node, Some(arena)))),
+            _ => Some(Cow::Borrowed(node)),
+        }
+    }
+
+    /// Get the edge traversal that reached a label along with its result state. with
+    /// arena storage, the traversal is copied so that it can hold the state.
+    pub fn get_edge_traversal(&self, label: &Label) -> Option<Cow<'_, EdgeTraversal>> {
+        let node = self.nodes.get(label)?;
+        edge_traversal_with_state(node, self.arena.as_ref())
+    }
+
     /// gets the label with the minimum cost associated with a vertex
     pub fn get_min_cost_label(&self, vertex: VertexId) -> Option<&Label> {
         self.get_label_by(vertex, min_cost_ordering, true)
@@ -248,6 +421,14 @@ impl SearchTree {
         self.direction
     }
 
+    /// Get how the tree stores the result states of its branches
+    pub fn storage(&self) -> SearchTreeStorage {
+        match self.arena {
+            None => SearchTreeStorage::Nodes,
+            Some(_) => SearchTreeStorage::Arena,
+        }
+    }
+
     /// Backtrack from a leaf vertex to construct a path using the tree's inherent direction
     /// and limit the backtracking depth to some nonzero count of edges.
     ///
@@ -337,16 +518,15 @@ impl SearchTree {
                 .ok_or_else(|| SearchTreeError::LabelNotFound(current_label.clone()))?;
 
             // If this is the root, we're done, otherwise traverse path
-            match current_node {
-                SearchTreeNode::Root { .. } => break,
-                SearchTreeNode::Branch {
-                    incoming_edge,
-                    parent,
-                    ..
-                } => {
-                    path.push(incoming_edge.clone());
+            match (
+                current_node.parent_label(),
+                edge_traversal_with_state(current_node, self.arena.as_ref()),
+            ) {
+                (Some(parent), Some(incoming_edge)) => {
+                    path.push(incoming_edge.into_owned());
                     current_label = parent;
                 }
+                _ => break,
             }
             steps += 1;
         }
@@ -372,8 +552,25 @@ impl SearchTree {
         self.nodes.values()
     }
 
+    /// Estimate the memory held by this tree in bytes, using allocative.
+    /// This traverses the whole tree and should not be called on every search iteration.
+    pub fn estimate_memory_bytes(&self) -> f64 {
+        let root_bytes = allocative::size_of_unique(self) as f64;
+        let node_bytes = self
+            .nodes()
+            .map(|n| allocative::size_of_unique(n) as f64)
+            .sum::<f64>();
+        let label_bytes = self
+            .labels()
+            .map(|l| allocative::size_of_unique(l) as f64)
+            .sum::<f64>();
+        root_bytes + node_bytes + label_bytes
+    }
+
     /// Get the incoming edge for a vertex by finding its minimum cost label.
     /// This is an optimized version for getting just the parent edge without full backtracking.
+    /// With arena storage, the result state of the edge traversal is empty, see
+    /// [`SearchTree::get_state`].
     ///
     /// # Arguments
     /// * `vertex` - The vertex ID to get the incoming edge for
@@ -387,6 +584,50 @@ impl SearchTree {
     }
 }
 
+/// the incoming edge of a node with its result state, copied out of the state arena if
+/// the node keeps its state there
+fn edge_traversal_with_state<'a>(
+    node: &'a SearchTreeNode,
+    arena: Option<&StateArena>,
+) -> Option<Cow<'a, EdgeTraversal>> {
+    let incoming_edge = node.incoming_edge()?;
+    match (node.state_slot(), arena) {
+        (Some(slot), Some(arena)) => {
+            let mut owned = incoming_edge.clone();
+            owned.result_state = arena.get(slot)?.to_vec();
+            Some(Cow::Owned(owned))
+        }
+        _ => Some(Cow::Borrowed(incoming_edge)),
+    }
+}
+
+/// copies a node so that it holds its own result state, to be stored in another tree
+fn with_owned_state(node: &SearchTreeNode, arena: Option<&StateArena>) -> SearchTreeNode {
+    match node {
+        SearchTreeNode::Root { .. } => node.clone(),
+        SearchTreeNode::Branch {
+            incoming_edge,
+            parent,
+            direction,
+            child_count,
+            state_slot,
+        } => {
+            let mut incoming_edge = incoming_edge.clone();
+            if let (Some(slot), Some(arena)) = (state_slot, arena) {
+                incoming_edge.result_state =
+                    arena.get(*slot).map(<[_]>::to_vec).unwrap_or_default();
+            }
+            SearchTreeNode::Branch {
+                incoming_edge,
+                parent: parent.clone(),
+                direction: *direction,
+                child_count: *child_count,
+                state_slot: None,
+            }
+        }
+    }
+}
+
 /// helper function to construct the min cost ordering
 fn min_cost_ordering(pair: &(&Label, Option<&EdgeTraversal>)) -> OrderedFloat<f64> {
     let (_, et) = pair;
@@ -428,6 +669,7 @@ mod tests {
         cost::TraversalCost,
         label::default::vertex_label_model::VertexLabelModel,
         network::{EdgeId, EdgeListId, VertexId},
+        state::StateVariable,
         unit::Cost,
     };
 
@@ -454,6 +696,29 @@ mod tests {
         assert!(root_node.is_root());
     }
 
+    #[test]
+    fn test_add_root() {
+        let first_root = create_test_label(0);
+        let second_root = create_test_label(1);
+        let mut tree = SearchTree::with_root(first_root.clone(), Direction::Forward);
+        tree.add_root(second_root.clone());
+        assert_eq!(tree.root(), Some(&first_root));
+        assert!(tree.get(&second_root).unwrap().is_root());
+
+        // backtracking stops at the root the path descends from
+        let child_label = create_test_label(2);
+        tree.insert(
+            second_root.clone(),
+            create_test_edge_traversal(7, 10.0),
+            child_label,
+            mock_label_model(),
+        )
+        .unwrap();
+        let path = tree.backtrack(VertexId(2)).unwrap();
+        assert_eq!(path.len(), 1);
+        assert_eq!(path[0].edge_id, EdgeId(7));
+    }
+
     #[test]
     fn test_insert_child_nodes() {
         let root_label = create_test_label(0);
@@ -1502,6 +1767,149 @@ mod tests {
         assert!(edge_none.is_none());
     }
 
+    /// a tree 0 -> 1 -> 2, 1 -> 3 and 0 -> 4 where edge i leads to vertex i
+    fn create_branching_tree() -> SearchTree {
+        let mut tree = SearchTree::with_root(create_test_label(0), Direction::Forward);
+        for (parent, child) in [(0, 1), (1, 2), (1, 3), (0, 4)] {
+            tree.insert(
+                create_test_label(parent),
+                create_test_edge_traversal(child, child as f64),
+                create_test_label(child),
+                mock_label_model(),
+            )
+            .unwrap();
+        }
+        tree
+    }
+
+    #[test]
+    fn test_subtree() {
+        let tree = create_branching_tree();
+        let subtree = tree.subtree(&create_test_label(1)).unwrap();
+        assert_eq!(subtree.len(), 3);
+        assert_eq!(subtree.root(), Some(&create_test_label(1)));
+        let root = subtree.get(&create_test_label(1)).unwrap();
+        assert!(root.is_root());
+        assert_eq!(root.child_count(), 2);
+        assert!(!subtree.contains(&create_test_label(4)));
+        let path = subtree.backtrack(VertexId(2)).unwrap();
+        assert_eq!(path.len(), 1);
+        assert_eq!(path[0].edge_id, EdgeId(2));
+
+        let missing = tree.subtree(&create_test_label(99));
+        assert!(matches!(missing, Err(SearchTreeError::LabelNotFound(_))));
+    }
+
+    #[test]
+    fn test_reroot() {
+        let mut tree = create_branching_tree();
+        tree.reroot(&create_test_label(1)).unwrap();
+        assert_eq!(tree.len(), 3);
+        assert_eq!(tree.root(), Some(&create_test_label(1)));
+        assert!(tree.backtrack(VertexId(4)).is_err());
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut tree = create_branching_tree();
+        let mut other = tree.subtree(&create_test_label(4)).unwrap();
+        other
+            .insert(
+                create_test_label(4),
+                create_test_edge_traversal(5, 5.0),
+                create_test_label(5),
+                mock_label_model(),
+            )
+            .unwrap();
+        let other_root = SearchTree::with_root(create_test_label(6), Direction::Forward);
+        tree.merge(other).unwrap();
+        tree.merge(other_root).unwrap();
+        assert_eq!(tree.len(), 7);
+        assert_eq!(tree.root(), Some(&create_test_label(0)));
+        assert!(tree.get(&create_test_label(6)).unwrap().is_root());
+        assert_eq!(tree.get(&create_test_label(4)).unwrap().child_count(), 1);
+        let path = tree.backtrack(VertexId(5)).unwrap();
+        let edge_ids = path.iter().map(|e| e.edge_id).collect::<Vec<_>>();
+        assert_eq!(edge_ids, vec![EdgeId(4), EdgeId(5)]);
+
+        let reverse = SearchTree::with_root(create_test_label(0), Direction::Reverse);
+        assert!(tree.merge(reverse).is_err());
+    }
+
+    #[test]
+    fn test_arena_storage() {
+        // states are stored in the arena and read back from it, and the slot of a
+        // removed branch is reused by the next branch inserted
+        let mut tree = SearchTree::with_storage(Direction::Forward, SearchTreeStorage::Arena);
+        tree.set_root(create_test_label(0));
+        for (parent, child) in [(0, 1), (1, 2), (1, 3), (0, 4)] {
+            let mut et = create_test_edge_traversal(child, child as f64);
+            et.result_state = vec![StateVariable(child as f64), StateVariable(1.0)];
+            tree.insert(
+                create_test_label(parent),
+                et,
+                create_test_label(child),
+                mock_label_model(),
+            )
+            .unwrap();
+        }
+        let node = tree.get(&create_test_label(3)).unwrap();
+        assert!(node.incoming_edge().unwrap().result_state.is_empty());
+        assert_eq!(
+            tree.get_state(&create_test_label(3)),
+            Some(&[StateVariable(3.0), StateVariable(1.0)][..])
+        );
+        assert!(tree.get_state(&create_test_label(0)).is_none());
+        let path = tree.backtrack(VertexId(2)).unwrap();
+        let states = path.iter().map(|e| e.result_state[0]).collect::<Vec<_>>();
+        assert_eq!(states, vec![StateVariable(1.0), StateVariable(2.0)]);
+
+        let slot = tree.get(&create_test_label(4)).unwrap().state_slot();
+        tree.remove(&create_test_label(4)).unwrap();
+        let mut et = create_test_edge_traversal(5, 5.0);
+        et.result_state = vec![StateVariable(5.0), StateVariable(1.0)];
+        tree.insert(
+            create_test_label(3),
+            et,
+            create_test_label(5),
+            mock_label_model(),
+        )
+        .unwrap();
+        assert_eq!(tree.get(&create_test_label(5)).unwrap().state_slot(), slot);
+        let edge = tree.get_edge_traversal(&create_test_label(5)).unwrap();
+        assert_eq!(
+            edge.result_state,
+            vec![StateVariable(5.0), StateVariable(1.0)]
+        );
+
+        // subtrees keep the storage and the states of the tree they are taken from
+        let subtree = tree.subtree(&create_test_label(1)).unwrap();
+        assert_eq!(subtree.storage(), SearchTreeStorage::Arena);
+        let path = subtree.backtrack(VertexId(5)).unwrap();
+        let states = path.iter().map(|e| e.result_state[0]).collect::<Vec<_>>();
+        assert_eq!(states, vec![StateVariable(3.0), StateVariable(5.0)]);
+
+        // merging an arena tree into a tree with node storage moves the states into nodes
+        let mut nodes = SearchTree::with_root(create_test_label(0), Direction::Forward);
+        let mut et = create_test_edge_traversal(1, 1.0);
+        et.result_state = vec![StateVariable(1.0), StateVariable(1.0)];
+        nodes
+            .insert(
+                create_test_label(0),
+                et,
+                create_test_label(1),
+                mock_label_model(),
+            )
+            .unwrap();
+        nodes.merge(subtree).unwrap();
+        let node = nodes.get(&create_test_label(5)).unwrap();
+        assert_eq!(node.state_slot(), None);
+        assert_eq!(
+            node.incoming_edge().unwrap().result_state,
+            vec![StateVariable(5.0), StateVariable(1.0)]
+        );
+    }
+
     fn mock_label_model() -> Arc<dyn LabelModel> {
         Arc::new(VertexLabelModel)
     }
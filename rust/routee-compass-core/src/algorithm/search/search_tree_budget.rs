@@ -0,0 +1,224 @@
+use super::{SearchError, SearchTree};
+use crate::model::{label::Label, termination::MemoryUnit, unit::Cost};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// limits the memory held by the [`SearchTree`] of a single search. the size of the
+/// tree is estimated with allocative, which traverses the whole tree, so it is only
+/// tested every `frequency` iterations.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct SearchTreeBudget {
+    /// maximum estimated size of the search tree
+    pub limit: f64,
+    #[serde(default)]
+    pub unit: MemoryUnit,
+    /// the budget is tested every `frequency` iterations. defaults to
+    /// [`SearchTreeBudget::DEFAULT_FREQUENCY`].
+    pub frequency: Option<u64>,
+    /// behavior when the budget is exceeded
+    #[serde(default)]
+    pub policy: SearchTreeBudgetPolicy,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum SearchTreeBudgetPolicy {
+    /// fail the search with a [`SearchError::MemoryBudgetExceeded`] error
+    #[default]
+    Terminate,
+    /// remove the leaves of the tree with the highest cost from the origin until the
+    /// tree shrinks to `target_fraction` of its size. pruned branches are not revisited,
+    /// so a path search may fail to reach a destination whose branch was pruned.
+    Prune {
+        /// fraction of the current tree size to keep when pruning. defaults to 0.8.
+        target_fraction: Option<f64>,
+    },
+}
+
+impl SearchTreeBudget {
+    pub const DEFAULT_TARGET_FRACTION: f64 = 0.8;
+    pub const DEFAULT_FREQUENCY: u64 = 10_000;
+
+    /// true if the budget is tested on this iteration
+    pub fn should_test(&self, iteration: u64) -> bool {
+        let frequency = self.frequency.unwrap_or(Self::DEFAULT_FREQUENCY).max(1);
+        iteration.is_multiple_of(frequency)
+    }
+
+    /// estimated size of the tree in the configured memory unit
+    pub fn tree_size(&self, tree: &SearchTree) -> f64 {
+        self.unit.convert(tree.estimate_memory_bytes())
+    }
+
+    /// tests the tree against this budget, applying the budget policy if the tree
+    /// exceeds it. returns the number of tree nodes pruned.
+    ///
+    /// # Arguments
+    ///
+    /// * `tree` - the search tree being grown
+    /// * `costs` - the cost from the origin to each label in the tree
+    /// * `iteration` - the current search iteration
+    pub fn apply(
+        &self,
+        tree: &mut SearchTree,
+        costs: &HashMap<Label, Cost>,
+        iteration: u64,
+    ) -> Result<usize, SearchError> {
+        if !self.should_test(iteration) {
+            return Ok(0);
+        }
+        let size = self.tree_size(tree);
+        if size <= self.limit {
+            return Ok(0);
+        }
+        match &self.policy {
+            SearchTreeBudgetPolicy::Terminate => Err(SearchError::MemoryBudgetExceeded {
+                limit: self.limit,
+                unit: self.unit,
+                tree_size: tree.len(),
+            }),
+            SearchTreeBudgetPolicy::Prune { target_fraction } => {
+                let fraction = target_fraction.unwrap_or(Self::DEFAULT_TARGET_FRACTION);
+                if !(0.0..1.0).contains(&fraction) {
+                    return Err(SearchError::BuildError(format!(
+                        "search tree budget target_fraction must be in [0, 1), found {fraction}"
+                    )));
+                }
+                let target = (tree.len() as f64 * fraction).floor() as usize;
+                prune_costliest_leaves(tree, costs, target)
+            }
+        }
+    }
+}
+
+/// removes leaves with the highest cost from the origin until the tree has at most
+/// `target` nodes or only the root remains. removing a leaf may turn its parent
+/// into a leaf, which is then eligible in the next round.
+fn prune_costliest_leaves(
+    tree: &mut SearchTree,
+    costs: &HashMap<Label, Cost>,
+    target: usize,
+) -> Result<usize, SearchError> {
+    let mut pruned = 0;
+    while tree.len() > target.max(1) {
+        let mut leaves = tree
+            .iter()
+            .filter(|(_, node)| !node.is_root() && node.is_prunable())
+            .map(|(label, _)| {
+                let cost = costs.get(label).copied().unwrap_or(Cost::INFINITY);
+                (label.clone(), cost)
+            })
+            .collect::<Vec<_>>();
+        if leaves.is_empty() {
+            break;
+        }
+        leaves.sort_by(|(_, a), (_, b)| b.cmp(a));
+        let n_remove = (tree.len() - target.max(1)).min(leaves.len());
+        for (label, _) in leaves.into_iter().take(n_remove) {
+            tree.remove(&label)?;
+            pruned += 1;
+        }
+    }
+    Ok(pruned)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algorithm::search::{Direction, EdgeTraversal};
+    use crate::model::{
+        cost::TraversalCost,
+        label::default::vertex_label_model::VertexLabelModel,
+        network::{EdgeId, EdgeListId, VertexId},
+    };
+    use std::sync::Arc;
+
+    /// builds a tree with a root at vertex 0 and a chain 0 -> 1 -> 2 plus leaves 0 -> 3
+    /// and 0 -> 4, where the cost of each label is its vertex id.
+    fn mock_tree() -> (SearchTree, HashMap<Label, Cost>) {
+        let mut tree = SearchTree::with_root(Label::Vertex(VertexId(0)), Direction::Forward);
+        let mut costs = HashMap::from([(Label::Vertex(VertexId(0)), Cost::ZERO)]);
+        for (parent, child) in [(0, 1), (1, 2), (0, 3), (0, 4)] {
+            let et = EdgeTraversal {
+                edge_list_id: EdgeListId(0),
+                edge_id: EdgeId(child),
+                cost: TraversalCost::default(),
+                result_state: vec![],
+            };
+            tree.insert(
+                Label::Vertex(VertexId(parent)),
+                et,
+                Label::Vertex(VertexId(child)),
+                Arc::new(VertexLabelModel {}),
+            )
+            .expect("test invariant failed");
+            costs.insert(Label::Vertex(VertexId(child)), Cost::new(child as f64));
+        }
+        (tree, costs)
+    }
+
+    #[test]
+    fn test_within_budget() {
+        let (mut tree, costs) = mock_tree();
+        let budget = SearchTreeBudget {
+            limit: 1.0,
+            unit: MemoryUnit::Gigabytes,
+            frequency: None,
+            policy: SearchTreeBudgetPolicy::Terminate,
+        };
+        assert_eq!(budget.apply(&mut tree, &costs, 0).ok(), Some(0));
+        assert_eq!(tree.len(), 5);
+    }
+
+    #[test]
+    fn test_terminate_policy() {
+        let (mut tree, costs) = mock_tree();
+        let budget = SearchTreeBudget {
+            limit: 0.0,
+            unit: MemoryUnit::Megabytes,
+            frequency: Some(10),
+            policy: SearchTreeBudgetPolicy::Terminate,
+        };
+        assert_eq!(budget.apply(&mut tree, &costs, 5).ok(), Some(0));
+        let result = budget.apply(&mut tree, &costs, 10);
+        assert!(matches!(
+            result,
+            Err(SearchError::MemoryBudgetExceeded { tree_size: 5, .. })
+        ));
+    }
+
+    #[test]
+    fn test_default_frequency() {
+        let (mut tree, costs) = mock_tree();
+        let budget = SearchTreeBudget {
+            limit: 0.0,
+            unit: MemoryUnit::Megabytes,
+            frequency: None,
+            policy: SearchTreeBudgetPolicy::Terminate,
+        };
+        assert!(!budget.should_test(1));
+        assert_eq!(budget.apply(&mut tree, &costs, 1).ok(), Some(0));
+        assert!(budget.should_test(SearchTreeBudget::DEFAULT_FREQUENCY));
+        assert!(budget.apply(&mut tree, &costs, 20_000).is_err());
+    }
+
+    #[test]
+    fn test_prune_policy_removes_costliest_leaves() {
+        let (mut tree, costs) = mock_tree();
+        let budget = SearchTreeBudget {
+            limit: 0.0,
+            unit: MemoryUnit::Megabytes,
+            frequency: None,
+            policy: SearchTreeBudgetPolicy::Prune {
+                target_fraction: Some(0.6),
+            },
+        };
+        let pruned = budget.apply(&mut tree, &costs, 0).expect("prune failed");
+        // 5 nodes * 0.6 = 3 nodes kept. leaves are 2, 3 and 4, so 4 and 3 are removed.
+        assert_eq!(pruned, 2);
+        assert!(tree.contains(&Label::Vertex(VertexId(2))));
+        assert!(!tree.contains(&Label::Vertex(VertexId(3))));
+        assert!(!tree.contains(&Label::Vertex(VertexId(4))));
+    }
+}
@@ -24,6 +24,10 @@ pub enum SearchTreeNode {
         direction: Direction,
         /// Number of nodes in the tree that have this node as a parent
         child_count: usize,
+        /// slot of the result state in the state arena of the tree, if the tree uses
+        /// arena storage. the result state of the incoming edge is then left empty.
+        #[serde(skip)]
+        state_slot: Option<usize>,
     },
 }
 
@@ -41,6 +45,7 @@ impl SearchTreeNode {
             parent,
             direction,
             child_count: 0,
+            state_slot: None,
         }
     }
 
@@ -51,6 +56,8 @@ impl SearchTreeNode {
         }
     }
 
+    /// the edge traversal that led to this node. in a tree with arena storage, the
+    /// result state is held by the tree, see [`super::SearchTree::get_state`].
     pub fn incoming_edge(&self) -> Option<&EdgeTraversal> {
         match self {
             SearchTreeNode::Root { .. } => None,
@@ -58,6 +65,13 @@ impl SearchTreeNode {
         }
     }
 
+    pub fn state_slot(&self) -> Option<usize> {
+        match self {
+            SearchTreeNode::Root { .. } => None,
+            SearchTreeNode::Branch { state_slot, .. } => *state_slot,
+        }
+    }
+
     pub fn is_root(&self) -> bool {
         match self {
             SearchTreeNode::Root { .. } => true,
@@ -0,0 +1,115 @@
+use super::SearchTreeError;
+use crate::model::state::StateVariable;
+use allocative::Allocative;
+use serde::{Deserialize, Serialize};
+
+/// how a [`super::SearchTree`] stores the result state of each of its branches.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, Allocative)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchTreeStorage {
+    /// each branch owns the state vector of its edge traversal
+    #[default]
+    Nodes,
+    /// the state vectors of all branches are stored in a contiguous [`StateArena`],
+    /// indexed by branch. this avoids one allocation per branch and keeps states close
+    /// together in memory, which may speed up long searches. the states of pruned
+    /// branches are reused by the branches added after them.
+    Arena,
+}
+
+/// a slab of fixed-width state vectors, stored end to end in a single buffer. the width
+/// is set by the first state inserted, as every state of a search has the same length.
+#[derive(Clone, Debug, Default, Allocative)]
+pub struct StateArena {
+    width: Option<usize>,
+    values: Vec<StateVariable>,
+    /// slots of removed states, which are filled before growing the buffer
+    free: Vec<usize>,
+}
+
+impl StateArena {
+    /// stores a state vector, returning the slot it was stored in
+    pub fn insert(&mut self, state: &[StateVariable]) -> Result<usize, SearchTreeError> {
+        let width = *self.width.get_or_insert(state.len());
+        if state.len() != width {
+            return Err(SearchTreeError::InvalidBranchStructure(format!(
+                "state arena holds states of length {width}, cannot store a state of length {}",
+                state.len()
+            )));
+        }
+        match self.free.pop() {
+            Some(slot) => {
+                let start = slot * width;
+                self.values[start..start + width].copy_from_slice(state);
+                Ok(slot)
+            }
+            None if width == 0 => Ok(0),
+            None => {
+                self.values.extend_from_slice(state);
+                Ok(self.values.len() / width - 1)
+            }
+        }
+    }
+
+    /// the state vector stored in a slot
+    pub fn get(&self, slot: usize) -> Option<&[StateVariable]> {
+        let width = self.width?;
+        let start = slot.checked_mul(width)?;
+        self.values.get(start..start + width)
+    }
+
+    /// frees a slot to be filled by a later state
+    pub fn remove(&mut self, slot: usize) {
+        if self.width.is_some_and(|w| w > 0) {
+            self.free.push(slot);
+        }
+    }
+
+    /// the number of states held in the arena
+    pub fn len(&self) -> usize {
+        match self.width {
+            Some(width) if width > 0 => self.values.len() / width - self.free.len(),
+            _ => 0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arena_reuses_slots() {
+        let mut arena = StateArena::default();
+        let a = arena
+            .insert(&[StateVariable(1.0), StateVariable(2.0)])
+            .unwrap();
+        let b = arena
+            .insert(&[StateVariable(3.0), StateVariable(4.0)])
+            .unwrap();
+        assert_eq!((a, b), (0, 1));
+        assert_eq!(
+            arena.get(b),
+            Some(&[StateVariable(3.0), StateVariable(4.0)][..])
+        );
+        assert_eq!(arena.len(), 2);
+
+        arena.remove(a);
+        assert_eq!(arena.len(), 1);
+        let c = arena
+            .insert(&[StateVariable(5.0), StateVariable(6.0)])
+            .unwrap();
+        assert_eq!(c, a);
+        assert_eq!(
+            arena.get(c),
+            Some(&[StateVariable(5.0), StateVariable(6.0)][..])
+        );
+        assert_eq!(arena.get(2), None);
+
+        assert!(arena.insert(&[StateVariable(7.0)]).is_err());
+    }
+}
@@ -0,0 +1,16 @@
+use crate::model::{network::VertexId, unit::Cost};
+use allocative::Allocative;
+use serde::{Deserialize, Serialize};
+
+/// a label settled (removed from the frontier and expanded) by a search. searches that
+/// record their settled labels list them in the order they were settled, which shows
+/// how a heuristic or constraint model shapes the explored area.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Allocative)]
+pub struct SettledLabel {
+    /// vertex of the settled label
+    pub vertex_id: VertexId,
+    /// objective cost from the search root to the label
+    pub cost: Cost,
+    /// frontier priority of the label, its cost plus any A* cost estimate
+    pub priority: Cost,
+}
@@ -1,3 +1,4 @@
+use crate::algorithm::search::{Direction, SearchTree};
 use crate::model::{
     constraint::ConstraintModel,
     network::{Edge, EdgeId},
@@ -29,6 +30,8 @@ impl ConstraintModel for EdgeCutConstraintModel {
         &self,
         edge: &Edge,
         previous_edge: Option<&Edge>,
+        tree: &SearchTree,
+        direction: &Direction,
         state: &[crate::model::state::StateVariable],
         state_model: &crate::model::state::StateModel,
     ) -> Result<bool, crate::model::constraint::ConstraintModelError> {
@@ -36,10 +39,19 @@ impl ConstraintModel for EdgeCutConstraintModel {
             Ok(false)
         } else {
             self.underlying
-                .valid_frontier(edge, previous_edge, state, state_model)
+                .valid_frontier(edge, previous_edge, tree, direction, state, state_model)
         }
     }
 
+    fn valid_traversal(
+        &self,
+        edge: &Edge,
+        state: &[crate::model::state::StateVariable],
+        state_model: &crate::model::state::StateModel,
+    ) -> Result<bool, crate::model::constraint::ConstraintModelError> {
+        self.underlying.valid_traversal(edge, state, state_model)
+    }
+
     fn valid_edge(
         &self,
         edge: &Edge,
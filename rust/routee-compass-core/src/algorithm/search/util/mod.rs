@@ -1,5 +1,7 @@
 mod edge_cut_constraint_model;
 mod route_similarity_function;
+mod u_turn_constraint_model;
 
 pub use edge_cut_constraint_model::EdgeCutConstraintModel;
 pub use route_similarity_function::RouteSimilarityFunction;
+pub use u_turn_constraint_model::UTurnConstraintModel;
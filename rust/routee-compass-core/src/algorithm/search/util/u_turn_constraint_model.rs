@@ -0,0 +1,158 @@
+use crate::algorithm::search::{Direction, SearchTree};
+use crate::model::{
+    constraint::{ConstraintModel, ConstraintModelError},
+    network::{Edge, Graph},
+    state::{StateModel, StateVariable},
+};
+use std::sync::Arc;
+
+/// A wrapper of the user-generated ConstraintModel which prohibits immediate U-turns,
+/// where the search traverses the reverse of the edge it just traversed. U-turns are
+/// still allowed at dead ends, where the reverse edge is the only way out of a vertex.
+/// requires a [Graph] built with reverse edge pairings.
+pub struct UTurnConstraintModel {
+    pub underlying: Arc<dyn ConstraintModel>,
+    graph: Arc<Graph>,
+}
+
+impl UTurnConstraintModel {
+    pub fn new(underlying: Arc<dyn ConstraintModel>, graph: Arc<Graph>) -> UTurnConstraintModel {
+        UTurnConstraintModel { underlying, graph }
+    }
+
+    /// true if the edge is the only edge leaving the vertex where the U-turn would occur.
+    fn is_dead_end(&self, edge: &Edge, direction: &Direction) -> bool {
+        let pivot = match direction {
+            Direction::Forward => edge.src_vertex_id,
+            Direction::Reverse => edge.dst_vertex_id,
+        };
+        let is_dead_end = self
+            .graph
+            .incident_edges_iter(&pivot, direction)
+            .all(|(el_id, e_id)| *el_id == edge.edge_list_id && *e_id == edge.edge_id);
+        is_dead_end
+    }
+}
+
+impl ConstraintModel for UTurnConstraintModel {
+    fn valid_frontier(
+        &self,
+        edge: &Edge,
+        previous_edge: Option<&Edge>,
+        tree: &SearchTree,
+        direction: &Direction,
+        state: &[StateVariable],
+        state_model: &StateModel,
+    ) -> Result<bool, ConstraintModelError> {
+        if let Some(previous_edge) = previous_edge {
+            if self.graph.is_u_turn(previous_edge, edge) && !self.is_dead_end(edge, direction) {
+                return Ok(false);
+            }
+        }
+        self.underlying
+            .valid_frontier(edge, previous_edge, tree, direction, state, state_model)
+    }
+
+    fn valid_traversal(
+        &self,
+        edge: &Edge,
+        state: &[StateVariable],
+        state_model: &StateModel,
+    ) -> Result<bool, ConstraintModelError> {
+        self.underlying.valid_traversal(edge, state, state_model)
+    }
+
+    fn valid_edge(&self, edge: &Edge) -> Result<bool, ConstraintModelError> {
+        self.underlying.valid_edge(edge)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::{
+        constraint::default::no_restriction::NoRestriction,
+        network::{EdgeId, EdgeList, EdgeListId, Vertex},
+    };
+    use indexmap::IndexMap;
+    use uom::si::{f64::Length, length::meter};
+
+    /// a line graph 0 <-> 1 <-> 2 where vertex 2 is a dead end
+    fn mock_graph() -> Graph {
+        let vertices = vec![
+            Vertex::new(0, 0.0, 0.0),
+            Vertex::new(1, 1.0, 0.0),
+            Vertex::new(2, 2.0, 0.0),
+        ];
+        let edges = vec![
+            Edge::new(0, 0, 0, 1, Length::new::<meter>(1.0)),
+            Edge::new(0, 1, 1, 0, Length::new::<meter>(1.0)),
+            Edge::new(0, 2, 1, 2, Length::new::<meter>(1.0)),
+            Edge::new(0, 3, 2, 1, Length::new::<meter>(1.0)),
+        ];
+        let mut adj = vec![IndexMap::new(); vertices.len()];
+        let mut rev = vec![IndexMap::new(); vertices.len()];
+        for edge in edges.iter() {
+            adj[edge.src_vertex_id.0].insert((EdgeListId(0), edge.edge_id), edge.dst_vertex_id);
+            rev[edge.dst_vertex_id.0].insert((EdgeListId(0), edge.edge_id), edge.src_vertex_id);
+        }
+        let reverse_edges: Box<[Option<EdgeId>]> = Box::new([
+            Some(EdgeId(1)),
+            Some(EdgeId(0)),
+            Some(EdgeId(3)),
+            Some(EdgeId(2)),
+        ]);
+        Graph {
+            vertices: vertices.into_boxed_slice(),
+            edge_lists: vec![EdgeList(edges.into_boxed_slice())],
+            adj: adj.into_boxed_slice(),
+            rev: rev.into_boxed_slice(),
+            reverse_edges: Some(Box::new([reverse_edges])),
+            components: None,
+        }
+    }
+
+    fn test_valid_frontier(previous_edge: usize, edge: usize, direction: Direction) -> bool {
+        let graph = Arc::new(mock_graph());
+        let model = UTurnConstraintModel::new(Arc::new(NoRestriction {}), graph.clone());
+        let edge_list = graph.get_edge_list(&EdgeListId(0)).unwrap();
+        let previous_edge = edge_list.get(&EdgeId(previous_edge));
+        let edge = edge_list.get(&EdgeId(edge)).unwrap();
+        let tree = SearchTree::new(direction);
+        model
+            .valid_frontier(
+                edge,
+                previous_edge,
+                &tree,
+                &direction,
+                &[],
+                &StateModel::empty(),
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn test_u_turn_prohibited() {
+        assert!(!test_valid_frontier(0, 1, Direction::Forward));
+    }
+
+    #[test]
+    fn test_continuing_allowed() {
+        assert!(test_valid_frontier(0, 2, Direction::Forward));
+    }
+
+    #[test]
+    fn test_u_turn_allowed_at_dead_end() {
+        assert!(test_valid_frontier(2, 3, Direction::Forward));
+    }
+
+    #[test]
+    fn test_reverse_u_turn_prohibited() {
+        assert!(!test_valid_frontier(2, 3, Direction::Reverse));
+    }
+
+    #[test]
+    fn test_reverse_u_turn_allowed_at_dead_end() {
+        assert!(test_valid_frontier(0, 1, Direction::Reverse));
+    }
+}
@@ -6,10 +6,11 @@ use super::{error::ConstraintModelError, ConstraintModelService};
 /// constraint model and builds a [ConstraintModel].
 ///
 /// A [`ConstraintModelBuilder`] instance should be an empty struct that implements
-/// this trait.
+/// this trait. Models are built concurrently when the app loads, so builders must be
+/// thread safe.
 ///
 /// [ConstraintModel]: routee_compass_core::model::constraint::ConstraintModel
-pub trait ConstraintModelBuilder {
+pub trait ConstraintModelBuilder: Send + Sync {
     /// Builds a [ConstraintModel] from JSON configuration.
     ///
     /// # Arguments
@@ -0,0 +1,38 @@
+use super::avoid_edges_builder_config::AvoidEdgesBuilderConfig;
+use super::avoid_edges_service::AvoidEdgesFrontierService;
+use crate::model::constraint::{
+    default::road_class::road_class_ops, ConstraintModelBuilder, ConstraintModelError,
+    ConstraintModelService,
+};
+use std::{collections::HashMap, path::PathBuf, sync::Arc};
+
+pub struct AvoidEdgesBuilder {}
+
+impl ConstraintModelBuilder for AvoidEdgesBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn ConstraintModelService>, ConstraintModelError> {
+        let config: AvoidEdgesBuilderConfig =
+            serde_json::from_value(parameters.clone()).map_err(|e| {
+                ConstraintModelError::BuildError(format!(
+                    "failed to read avoid edges configuration: {e}"
+                ))
+            })?;
+
+        let (road_class_by_edge, road_class_mapping) = match &config.road_class_input_file {
+            None => (None, HashMap::new()),
+            Some(file) => {
+                let (encoded, mapping) =
+                    road_class_ops::read_road_class_file(&PathBuf::from(file))?;
+                (Some(Arc::new(encoded)), mapping)
+            }
+        };
+
+        let m: Arc<dyn ConstraintModelService> = Arc::new(AvoidEdgesFrontierService {
+            road_class_by_edge,
+            road_class_mapping: Arc::new(road_class_mapping),
+        });
+        Ok(m)
+    }
+}
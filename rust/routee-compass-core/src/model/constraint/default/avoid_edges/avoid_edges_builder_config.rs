@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct AvoidEdgesBuilderConfig {
+    /// optional file with one road class label per edge. required if queries
+    /// are to provide `avoid_road_classes`.
+    pub road_class_input_file: Option<String>,
+}
@@ -0,0 +1,123 @@
+use super::avoid_edges_service::AvoidEdgesFrontierService;
+use crate::algorithm::search::{Direction, SearchTree};
+use crate::model::{
+    constraint::{ConstraintModel, ConstraintModelError},
+    network::{Edge, EdgeId},
+    state::{StateModel, StateVariable},
+};
+use std::{collections::HashSet, sync::Arc};
+
+/// excludes a per-query set of edges from the search, provided either as
+/// explicit edge ids or as road class labels.
+pub struct AvoidEdgesConstraintModel {
+    pub service: Arc<AvoidEdgesFrontierService>,
+    pub avoid_edges: HashSet<EdgeId>,
+    pub avoid_road_classes: HashSet<u8>,
+}
+
+impl ConstraintModel for AvoidEdgesConstraintModel {
+    fn valid_frontier(
+        &self,
+        edge: &Edge,
+        _previous_edge: Option<&Edge>,
+        _tree: &SearchTree,
+        _direction: &Direction,
+        _state: &[StateVariable],
+        _state_model: &StateModel,
+    ) -> Result<bool, ConstraintModelError> {
+        self.valid_edge(edge)
+    }
+
+    fn valid_edge(&self, edge: &Edge) -> Result<bool, ConstraintModelError> {
+        if self.avoid_edges.contains(&edge.edge_id) {
+            return Ok(false);
+        }
+        match (
+            &self.service.road_class_by_edge,
+            self.avoid_road_classes.is_empty(),
+        ) {
+            (Some(road_class_by_edge), false) => road_class_by_edge
+                .get(edge.edge_id.0)
+                .ok_or_else(|| {
+                    ConstraintModelError::ConstraintModelError(format!(
+                        "edge id {} missing from avoid edges road class file",
+                        edge.edge_id
+                    ))
+                })
+                .map(|road_class| !self.avoid_road_classes.contains(road_class)),
+            _ => Ok(true),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::constraint::ConstraintModelService;
+    use serde_json::{json, Value};
+    use std::collections::HashMap;
+    use uom::si::f64::Length;
+
+    fn mock_service(with_road_classes: bool) -> AvoidEdgesFrontierService {
+        if with_road_classes {
+            AvoidEdgesFrontierService {
+                road_class_by_edge: Some(Arc::new(vec![0, 1, 0].into_boxed_slice())),
+                road_class_mapping: Arc::new(HashMap::from([
+                    (String::from("motorway"), 0),
+                    (String::from("residential"), 1),
+                ])),
+            }
+        } else {
+            AvoidEdgesFrontierService {
+                road_class_by_edge: None,
+                road_class_mapping: Arc::new(HashMap::new()),
+            }
+        }
+    }
+
+    fn mock_edge(edge_id: usize) -> Edge {
+        Edge::new(0, edge_id, 0, 1, Length::new::<uom::si::length::meter>(1.0))
+    }
+
+    fn build(service: AvoidEdgesFrontierService, query: Value) -> Arc<dyn ConstraintModel> {
+        service
+            .build(&query, Arc::new(StateModel::empty()))
+            .expect("failed to build avoid edges model")
+    }
+
+    #[test]
+    fn test_empty_query_allows_all_edges() {
+        let model = build(mock_service(true), json!({}));
+        for edge_id in 0..3 {
+            assert!(model.valid_edge(&mock_edge(edge_id)).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_avoid_edge_ids() {
+        let model = build(mock_service(false), json!({"avoid_edges": [1]}));
+        assert!(model.valid_edge(&mock_edge(0)).unwrap());
+        assert!(!model.valid_edge(&mock_edge(1)).unwrap());
+        assert!(model.valid_edge(&mock_edge(2)).unwrap());
+    }
+
+    #[test]
+    fn test_avoid_road_classes() {
+        let model = build(
+            mock_service(true),
+            json!({"avoid_road_classes": ["motorway"]}),
+        );
+        assert!(!model.valid_edge(&mock_edge(0)).unwrap());
+        assert!(model.valid_edge(&mock_edge(1)).unwrap());
+        assert!(!model.valid_edge(&mock_edge(2)).unwrap());
+    }
+
+    #[test]
+    fn test_avoid_road_classes_without_file_fails() {
+        let result = mock_service(false).build(
+            &json!({"avoid_road_classes": ["motorway"]}),
+            Arc::new(StateModel::empty()),
+        );
+        assert!(result.is_err());
+    }
+}
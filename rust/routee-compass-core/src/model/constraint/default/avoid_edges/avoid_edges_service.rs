@@ -0,0 +1,57 @@
+use super::avoid_edges_model::AvoidEdgesConstraintModel;
+use crate::model::{
+    constraint::{
+        default::road_class::road_class_ops, ConstraintModel, ConstraintModelError,
+        ConstraintModelService,
+    },
+    network::EdgeId,
+    state::StateModel,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+#[derive(Clone)]
+pub struct AvoidEdgesFrontierService {
+    pub road_class_by_edge: Option<Arc<Box<[u8]>>>,
+    pub road_class_mapping: Arc<HashMap<String, u8>>,
+}
+
+impl ConstraintModelService for AvoidEdgesFrontierService {
+    fn build(
+        &self,
+        query: &serde_json::Value,
+        _state_model: Arc<StateModel>,
+    ) -> Result<Arc<dyn ConstraintModel>, ConstraintModelError> {
+        let avoid_edges: HashSet<EdgeId> = match query.get("avoid_edges") {
+            None => HashSet::new(),
+            Some(value) => serde_json::from_value(value.clone()).map_err(|e| {
+                ConstraintModelError::BuildError(format!(
+                    "query 'avoid_edges' value must be an array of edge ids: {e}"
+                ))
+            })?,
+        };
+
+        let avoid_road_classes: HashSet<u8> = match query.get("avoid_road_classes") {
+            None => HashSet::new(),
+            Some(_) if self.road_class_by_edge.is_none() => {
+                return Err(ConstraintModelError::BuildError(String::from(
+                    "query has 'avoid_road_classes' but the avoid_edges constraint model was not configured with a road_class_input_file",
+                )));
+            }
+            Some(value) => {
+                let road_classes =
+                    road_class_ops::read_road_classes_from_query("avoid_road_classes", value)?;
+                road_class_ops::encode_road_classes(&road_classes, &self.road_class_mapping)?
+            }
+        };
+
+        let model = AvoidEdgesConstraintModel {
+            service: Arc::new(self.clone()),
+            avoid_edges,
+            avoid_road_classes,
+        };
+        Ok(Arc::new(model))
+    }
+}
@@ -0,0 +1,4 @@
+pub mod avoid_edges_builder;
+pub mod avoid_edges_builder_config;
+pub mod avoid_edges_model;
+pub mod avoid_edges_service;
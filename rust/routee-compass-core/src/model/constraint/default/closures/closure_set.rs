@@ -0,0 +1,131 @@
+use crate::model::network::EdgeId;
+use crate::util::fs::fs_utils;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::{collections::HashSet, path::Path};
+
+/// a road closure, such as from a DOT incident feed, on a single edge.
+#[derive(Clone, Debug)]
+pub struct Closure {
+    pub edge_id: EdgeId,
+    pub start_time: DateTime<Utc>,
+    /// if None, the closure has no known end
+    pub end_time: Option<DateTime<Utc>>,
+}
+
+impl Closure {
+    /// true if the closure is in effect at the provided time
+    pub fn is_active(&self, time: &DateTime<Utc>) -> bool {
+        self.start_time <= *time && self.end_time.map(|end| *time < end).unwrap_or(true)
+    }
+}
+
+/// the closures read from a closure file.
+#[derive(Clone, Debug, Default)]
+pub struct ClosureSet {
+    pub closures: Vec<Closure>,
+}
+
+#[derive(Deserialize)]
+struct ClosureRow {
+    edge_id: usize,
+    start_time: String,
+    end_time: Option<String>,
+}
+
+impl ClosureSet {
+    /// reads a CSV file with columns `edge_id`, `start_time` and `end_time`
+    pub fn from_csv(path: &Path) -> Result<ClosureSet, String> {
+        let file = fs_utils::open_reader(path).map_err(|e| e.to_string())?;
+        let closures = csv::Reader::from_reader(file)
+            .deserialize::<ClosureRow>()
+            .map(|row| {
+                let row = row.map_err(|e| e.to_string())?;
+                let end_time = match row.end_time.as_deref().map(str::trim) {
+                    None | Some("") => None,
+                    Some(end) => Some(parse_time(end)?),
+                };
+                Ok(Closure {
+                    edge_id: EdgeId(row.edge_id),
+                    start_time: parse_time(&row.start_time)?,
+                    end_time,
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        log::info!(
+            "read {} closures from closure file {}",
+            closures.len(),
+            path.to_string_lossy()
+        );
+        Ok(ClosureSet { closures })
+    }
+
+    /// the most recent time at or before the provided time at which a closure started or
+    /// ended. the closed edges only change at these times.
+    pub fn last_change_at(&self, time: &DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.closures
+            .iter()
+            .flat_map(|c| std::iter::once(c.start_time).chain(c.end_time))
+            .filter(|t| t <= time)
+            .max()
+    }
+
+    /// the edges closed at the provided time
+    pub fn closed_edges_at(&self, time: &DateTime<Utc>) -> HashSet<EdgeId> {
+        self.closures
+            .iter()
+            .filter(|c| c.is_active(time))
+            .map(|c| c.edge_id)
+            .collect()
+    }
+}
+
+fn parse_time(value: &str) -> Result<DateTime<Utc>, String> {
+    DateTime::parse_from_rfc3339(value.trim())
+        .map(|t| t.with_timezone(&Utc))
+        .map_err(|e| format!("invalid closure time '{value}', expected RFC 3339: {e}"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_closed_edges_at() {
+        let mut file = tempfile::NamedTempFile::new().expect("failed to create temp file");
+        writeln!(
+            file,
+            "edge_id,start_time,end_time\n\
+             0,2025-06-01T08:00:00Z,2025-06-01T10:00:00Z\n\
+             1,2025-06-01T09:00:00Z,\n\
+             2,2025-06-02T00:00:00-06:00,2025-06-03T00:00:00-06:00"
+        )
+        .expect("failed to write temp file");
+        let closures = ClosureSet::from_csv(file.path()).expect("failed to read closures");
+        let at = |t: &str| {
+            let time = parse_time(t).expect("invalid test time");
+            let mut closed = closures
+                .closed_edges_at(&time)
+                .into_iter()
+                .collect::<Vec<_>>();
+            closed.sort();
+            closed
+        };
+        assert_eq!(at("2025-06-01T07:00:00Z"), vec![]);
+        assert_eq!(at("2025-06-01T09:30:00Z"), vec![EdgeId(0), EdgeId(1)]);
+        assert_eq!(at("2025-06-01T10:00:00Z"), vec![EdgeId(1)]);
+        assert_eq!(at("2025-06-02T07:00:00Z"), vec![EdgeId(1), EdgeId(2)]);
+
+        let last_change = |t: &str| closures.last_change_at(&parse_time(t).unwrap());
+        assert_eq!(last_change("2025-06-01T07:00:00Z"), None);
+        assert_eq!(
+            last_change("2025-06-01T10:30:00Z"),
+            parse_time("2025-06-01T10:00:00Z").ok()
+        );
+        assert_eq!(
+            last_change("2025-06-04T00:00:00Z"),
+            parse_time("2025-06-03T00:00:00-06:00").ok()
+        );
+    }
+}
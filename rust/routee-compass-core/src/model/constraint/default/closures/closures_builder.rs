@@ -0,0 +1,32 @@
+use super::closures_builder_config::ClosuresBuilderConfig;
+use super::closures_service::ClosuresService;
+use crate::model::constraint::{
+    ConstraintModelBuilder, ConstraintModelError, ConstraintModelService,
+};
+use crate::util::fs::polled_file::PolledFile;
+use std::{sync::Arc, time::Duration};
+
+pub struct ClosuresBuilder {}
+
+impl ConstraintModelBuilder for ClosuresBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn ConstraintModelService>, ConstraintModelError> {
+        let config: ClosuresBuilderConfig =
+            serde_json::from_value(parameters.clone()).map_err(|e| {
+                ConstraintModelError::BuildError(format!(
+                    "failed to read closures configuration: {e}"
+                ))
+            })?;
+        let poll_interval = Duration::from_secs(
+            config
+                .poll_interval_seconds
+                .unwrap_or(ClosuresBuilderConfig::DEFAULT_POLL_INTERVAL_SECONDS),
+        );
+        let m: Arc<dyn ConstraintModelService> = Arc::new(ClosuresService {
+            closure_file: Arc::new(PolledFile::new(&config.closure_file, poll_interval)),
+        });
+        Ok(m)
+    }
+}
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ClosuresBuilderConfig {
+    /// CSV file with columns `edge_id`, `start_time` and `end_time`, replaced periodically
+    /// by an incident feed. times are RFC 3339 timestamps, and an empty `end_time` marks a
+    /// closure without a known end. the file need not exist when the app is loaded, and a
+    /// relative path is resolved from the working directory.
+    pub closure_file: String,
+    /// minimum time between checks of the file for new closures. defaults to 60 seconds.
+    pub poll_interval_seconds: Option<u64>,
+}
+
+impl ClosuresBuilderConfig {
+    pub const DEFAULT_POLL_INTERVAL_SECONDS: u64 = 60;
+}
@@ -0,0 +1,30 @@
+use crate::algorithm::search::{Direction, SearchTree};
+use crate::model::{
+    constraint::{ConstraintModel, ConstraintModelError},
+    network::{Edge, EdgeId},
+    state::{StateModel, StateVariable},
+};
+use std::collections::HashSet;
+
+/// excludes edges with a closure active at the time of the query.
+pub struct ClosuresConstraintModel {
+    pub closed_edges: HashSet<EdgeId>,
+}
+
+impl ConstraintModel for ClosuresConstraintModel {
+    fn valid_frontier(
+        &self,
+        edge: &Edge,
+        _previous_edge: Option<&Edge>,
+        _tree: &SearchTree,
+        _direction: &Direction,
+        _state: &[StateVariable],
+        _state_model: &StateModel,
+    ) -> Result<bool, ConstraintModelError> {
+        self.valid_edge(edge)
+    }
+
+    fn valid_edge(&self, edge: &Edge) -> Result<bool, ConstraintModelError> {
+        Ok(!self.closed_edges.contains(&edge.edge_id))
+    }
+}
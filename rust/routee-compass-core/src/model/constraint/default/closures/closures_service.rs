@@ -0,0 +1,114 @@
+use super::{closure_set::ClosureSet, closures_model::ClosuresConstraintModel};
+use crate::model::{
+    constraint::{ConstraintModel, ConstraintModelError, ConstraintModelService},
+    state::{DepartureTime, StateModel},
+};
+use crate::util::fs::polled_file::PolledFile;
+use chrono::Utc;
+use std::{sync::Arc, time::UNIX_EPOCH};
+
+/// builds closure constraint models from a closure file that is periodically replaced
+/// by an incident feed. new closures are read when the file changes and swapped in
+/// without interrupting searches that are already running, which keep the closures
+/// that were active when they started.
+pub struct ClosuresService {
+    pub closure_file: Arc<PolledFile<ClosureSet>>,
+}
+
+impl ClosuresService {
+    /// query key with an optional RFC 3339 time at which closures are tested. if
+    /// omitted, the closures active at the current time are used.
+    pub const DEPARTURE_TIME_KEY: &'static str = DepartureTime::QUERY_KEY;
+}
+
+impl ConstraintModelService for ClosuresService {
+    fn build(
+        &self,
+        query: &serde_json::Value,
+        _state_model: Arc<StateModel>,
+    ) -> Result<Arc<dyn ConstraintModel>, ConstraintModelError> {
+        let time = match DepartureTime::from_query(query) {
+            Ok(Some(departure)) => departure.0,
+            Ok(None) => Utc::now(),
+            Err(e) => return Err(ConstraintModelError::BuildError(e)),
+        };
+        let closures = self
+            .closure_file
+            .current(|path, _| ClosureSet::from_csv(path))
+            .map_err(ConstraintModelError::ConstraintModelError)?;
+        let closed_edges = closures
+            .map(|(_, set)| set.closed_edges_at(&time))
+            .unwrap_or_default();
+        Ok(Arc::new(ClosuresConstraintModel { closed_edges }))
+    }
+
+    /// the modification time of the closure file in use, along with the last time a
+    /// closure started or ended, so that the version changes whenever the closures
+    /// active at the current time change
+    fn data_version(&self) -> Result<Option<String>, ConstraintModelError> {
+        let closures = self
+            .closure_file
+            .current(|path, _| ClosureSet::from_csv(path))
+            .map_err(ConstraintModelError::ConstraintModelError)?;
+        let version = match closures {
+            Some((modified, set)) => {
+                let modified = modified.duration_since(UNIX_EPOCH).unwrap_or_default();
+                let last_change = set
+                    .last_change_at(&Utc::now())
+                    .map(|t| t.timestamp_millis().to_string())
+                    .unwrap_or_default();
+                format!("closures:{}:{last_change}", modified.as_nanos())
+            }
+            None => String::from("closures:none"),
+        };
+        Ok(Some(version))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::network::Edge;
+    use serde_json::json;
+    use std::{io::Write, time::Duration};
+    use uom::si::f64::Length;
+
+    #[test]
+    fn test_closure_at_departure_time() {
+        let mut file = tempfile::NamedTempFile::new().expect("failed to create temp file");
+        writeln!(
+            file,
+            "edge_id,start_time,end_time\n1,2025-06-01T08:00:00Z,2025-06-01T10:00:00Z"
+        )
+        .expect("failed to write temp file");
+        let service = ClosuresService {
+            closure_file: Arc::new(PolledFile::new(file.path(), Duration::from_secs(60))),
+        };
+        let edge = Edge::new(0, 1, 0, 1, Length::new::<uom::si::length::meter>(1.0));
+        let during = service
+            .build(
+                &json!({"departure_time": "2025-06-01T09:00:00Z"}),
+                Arc::new(StateModel::empty()),
+            )
+            .expect("failed to build closures model");
+        assert!(!during.valid_edge(&edge).unwrap());
+        let after = service
+            .build(
+                &json!({"departure_time": "2025-06-01T11:00:00Z"}),
+                Arc::new(StateModel::empty()),
+            )
+            .expect("failed to build closures model");
+        assert!(after.valid_edge(&edge).unwrap());
+
+        // the closure has ended, so the version names its end as the last change
+        let version = service
+            .data_version()
+            .unwrap()
+            .expect("expected a data version");
+        let end = chrono::DateTime::parse_from_rfc3339("2025-06-01T10:00:00Z").unwrap();
+        assert!(
+            version.ends_with(&format!(":{}", end.timestamp_millis())),
+            "{version}"
+        );
+    }
+}
@@ -0,0 +1,5 @@
+pub mod closure_set;
+pub mod closures_builder;
+pub mod closures_builder_config;
+pub mod closures_model;
+pub mod closures_service;
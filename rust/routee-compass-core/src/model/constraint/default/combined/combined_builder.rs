@@ -4,16 +4,17 @@ use crate::model::constraint::{
     ConstraintModelBuilder, ConstraintModelError, ConstraintModelService,
 };
 use itertools::Itertools;
-use std::{collections::HashMap, rc::Rc, sync::Arc};
+use rayon::prelude::*;
+use std::{collections::HashMap, sync::Arc};
 
 use super::combined_service::CombinedFrontierService;
 
 pub struct CombinedConstraintModelBuilder {
-    pub builders: HashMap<String, Rc<dyn ConstraintModelBuilder>>,
+    pub builders: HashMap<String, Arc<dyn ConstraintModelBuilder>>,
 }
 
 impl CombinedConstraintModelBuilder {
-    pub fn new(builders: HashMap<String, Rc<dyn ConstraintModelBuilder>>) -> Self {
+    pub fn new(builders: HashMap<String, Arc<dyn ConstraintModelBuilder>>) -> Self {
         Self { builders }
     }
 
@@ -48,8 +49,9 @@ impl ConstraintModelBuilder for CombinedConstraintModelBuilder {
             .get_config_array(&"models", &constraint_key)
             .map_err(|e| ConstraintModelError::BuildError(e.to_string()))?;
 
+        // each model reads its own input files, so the models are built concurrently
         let inner_services = params
-            .iter()
+            .par_iter()
             .map(|p| self.build_service(p))
             .collect::<Result<Vec<Arc<dyn ConstraintModelService>>, CompassConfigurationError>>()
             .map_err(|e| ConstraintModelError::BuildError(e.to_string()))?;
@@ -1,3 +1,4 @@
+use crate::algorithm::search::{Direction, SearchTree};
 use crate::model::{
     constraint::{ConstraintModel, ConstraintModelError},
     network::Edge,
@@ -14,13 +15,36 @@ impl ConstraintModel for CombinedConstraintModel {
         &self,
         edge: &Edge,
         previous_edge: Option<&Edge>,
+        tree: &SearchTree,
+        direction: &Direction,
         state: &[StateVariable],
         state_model: &StateModel,
     ) -> Result<bool, ConstraintModelError> {
         // If any of the inner models return an invalid frontier, it invalidates the whole set and we
         // return an early false. We only return true if all the frontiers are valid.
         for constraint_model in self.inner_models.iter() {
-            if !constraint_model.valid_frontier(edge, previous_edge, state, state_model)? {
+            if !constraint_model.valid_frontier(
+                edge,
+                previous_edge,
+                tree,
+                direction,
+                state,
+                state_model,
+            )? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    fn valid_traversal(
+        &self,
+        edge: &Edge,
+        state: &[StateVariable],
+        state_model: &StateModel,
+    ) -> Result<bool, ConstraintModelError> {
+        for constraint_model in self.inner_models.iter() {
+            if !constraint_model.valid_traversal(edge, state, state_model)? {
                 return Ok(false);
             }
         }
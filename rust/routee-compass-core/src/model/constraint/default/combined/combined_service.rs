@@ -24,4 +24,18 @@ impl ConstraintModelService for CombinedFrontierService {
         let model = CombinedConstraintModel { inner_models };
         Ok(Arc::new(model))
     }
+
+    fn data_version(&self) -> Result<Option<String>, ConstraintModelError> {
+        let versions = self
+            .inner_services
+            .iter()
+            .map(|s| s.data_version())
+            .collect::<Result<Vec<_>, ConstraintModelError>>()?;
+        let versions: Vec<String> = versions.into_iter().flatten().collect();
+        if versions.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(versions.join(",")))
+        }
+    }
 }
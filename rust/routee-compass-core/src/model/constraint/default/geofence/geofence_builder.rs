@@ -0,0 +1,74 @@
+use super::{
+    geofence_builder_config::GeofenceBuilderConfig, geofence_ops,
+    geofence_service::GeofenceFrontierService,
+};
+use crate::{
+    model::{
+        constraint::{ConstraintModelBuilder, ConstraintModelError, ConstraintModelService},
+        network::{EdgeId, EdgeListId},
+    },
+    util::{
+        fs::read_utils,
+        geo::{geo_io_utils, PolygonalRTree},
+    },
+};
+use kdam::Bar;
+use std::sync::Arc;
+
+pub struct GeofenceBuilder {}
+
+impl ConstraintModelBuilder for GeofenceBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn ConstraintModelService>, ConstraintModelError> {
+        let config: GeofenceBuilderConfig =
+            serde_json::from_value(parameters.clone()).map_err(|e| {
+                ConstraintModelError::BuildError(format!(
+                    "failed to read geofence configuration: {e}"
+                ))
+            })?;
+
+        let linestrings = read_utils::read_raw_file(
+            &config.geometry_input_file,
+            geo_io_utils::parse_wkt_linestring,
+            Some(Bar::builder().desc("geofence edge geometries")),
+            None,
+        )
+        .map_err(|e| {
+            ConstraintModelError::BuildError(format!(
+                "failed to load file at {}: {e}",
+                config.geometry_input_file
+            ))
+        })?;
+        let edge_list_id = EdgeListId(config.edge_list_id.unwrap_or_default());
+        let edge_geometries = linestrings
+            .into_vec()
+            .into_iter()
+            .enumerate()
+            .map(|(idx, l)| (geo::Geometry::LineString(l), (edge_list_id, EdgeId(idx))))
+            .collect::<Vec<_>>();
+        let edge_rtree = PolygonalRTree::new(edge_geometries).map_err(|e| {
+            ConstraintModelError::BuildError(format!(
+                "failed to build spatial index over edge geometries: {e}"
+            ))
+        })?;
+
+        let intersecting_edges = match &config.geofence_input_file {
+            None => None,
+            Some(f) => {
+                let polygons = geofence_ops::read_polygons_from_file(f)?;
+                let edges = geofence_ops::intersecting_ids(&edge_rtree, &polygons)?;
+                Some(Arc::new(edges))
+            }
+        };
+
+        let service = GeofenceFrontierService {
+            edge_list_id,
+            edge_rtree: Arc::new(edge_rtree),
+            intersecting_edges,
+            policy: config.policy,
+        };
+        Ok(Arc::new(service))
+    }
+}
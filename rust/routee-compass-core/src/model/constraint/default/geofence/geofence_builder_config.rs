@@ -0,0 +1,18 @@
+use super::GeofencePolicy;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct GeofenceBuilderConfig {
+    /// enumerated WKT LineString file with one geometry per edge, in the
+    /// same format as the map model edge geometries.
+    pub geometry_input_file: String,
+    /// edge list of the geometries in the geometry input file, 0 if not provided. this
+    /// should be the position of the edge list this constraint model is configured for.
+    pub edge_list_id: Option<usize>,
+    /// optional GeoJSON file of polygonal geofences applied to every query
+    pub geofence_input_file: Option<String>,
+    /// default policy for edges intersecting the geofence, "exclude" if not provided
+    #[serde(default)]
+    pub policy: GeofencePolicy,
+}
@@ -0,0 +1,151 @@
+use super::GeofencePolicy;
+use crate::algorithm::search::{Direction, SearchTree};
+use crate::model::{
+    constraint::{ConstraintModel, ConstraintModelError},
+    network::{Edge, EdgeId, EdgeListId},
+    state::{StateModel, StateVariable},
+};
+use std::{collections::HashSet, sync::Arc};
+
+/// removes edges from the search based on their intersection with a set of
+/// polygonal geofences, such as a low-emission zone cordon. when neither the
+/// configuration nor the query provides a geofence, every edge is valid.
+pub struct GeofenceConstraintModel {
+    /// edge list of the geometries the geofences were intersected with
+    pub edge_list_id: EdgeListId,
+    pub intersecting_edges: Option<Arc<HashSet<(EdgeListId, EdgeId)>>>,
+    pub policy: GeofencePolicy,
+}
+
+impl ConstraintModel for GeofenceConstraintModel {
+    fn valid_frontier(
+        &self,
+        edge: &Edge,
+        _previous_edge: Option<&Edge>,
+        _tree: &SearchTree,
+        _direction: &Direction,
+        _state: &[StateVariable],
+        _state_model: &StateModel,
+    ) -> Result<bool, ConstraintModelError> {
+        self.valid_edge(edge)
+    }
+
+    fn valid_edge(&self, edge: &Edge) -> Result<bool, ConstraintModelError> {
+        if edge.edge_list_id != self.edge_list_id {
+            return Err(ConstraintModelError::ConstraintModelError(format!(
+                "geofence built over geometries of edge list {} cannot test edge {} of edge list {}, set the geofence 'edge_list_id' to the position of its edge list",
+                self.edge_list_id, edge.edge_id, edge.edge_list_id
+            )));
+        }
+        match &self.intersecting_edges {
+            None => Ok(true),
+            Some(edges) => {
+                let intersects = edges.contains(&(edge.edge_list_id, edge.edge_id));
+                Ok(self.policy.is_valid(intersects))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        model::constraint::{
+            default::geofence::geofence_service::GeofenceFrontierService, ConstraintModelService,
+        },
+        util::geo::PolygonalRTree,
+    };
+    use geo::line_string;
+    use serde_json::json;
+    use uom::si::f64::Length;
+
+    /// two edges: edge 0 lies inside the unit square near the origin, edge 1 lies far away
+    fn mock_service() -> GeofenceFrontierService {
+        let edges = vec![
+            (
+                geo::Geometry::LineString(line_string![(x: 0.2f32, y: 0.2), (x: 0.8, y: 0.8)]),
+                (EdgeListId(0), EdgeId(0)),
+            ),
+            (
+                geo::Geometry::LineString(line_string![(x: 5.0f32, y: 5.0), (x: 6.0, y: 6.0)]),
+                (EdgeListId(0), EdgeId(1)),
+            ),
+        ];
+        GeofenceFrontierService {
+            edge_list_id: EdgeListId(0),
+            edge_rtree: Arc::new(PolygonalRTree::new(edges).unwrap()),
+            intersecting_edges: None,
+            policy: GeofencePolicy::Exclude,
+        }
+    }
+
+    fn unit_square() -> serde_json::Value {
+        json!({
+            "type": "Polygon",
+            "coordinates": [[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0], [0.0, 0.0]]]
+        })
+    }
+
+    fn mock_edge(edge_id: usize) -> Edge {
+        Edge::new(0, edge_id, 0, 1, Length::new::<uom::si::length::meter>(1.0))
+    }
+
+    #[test]
+    fn test_no_geofence_all_valid() {
+        let model = mock_service()
+            .build(&json!({}), Arc::new(StateModel::empty()))
+            .unwrap();
+        assert!(model.valid_edge(&mock_edge(0)).unwrap());
+        assert!(model.valid_edge(&mock_edge(1)).unwrap());
+    }
+
+    #[test]
+    fn test_include_policy_without_geofence_all_valid() {
+        let query = json!({ "geofence_policy": "include" });
+        let model = mock_service()
+            .build(&query, Arc::new(StateModel::empty()))
+            .unwrap();
+        assert!(model.valid_edge(&mock_edge(0)).unwrap());
+        assert!(model.valid_edge(&mock_edge(1)).unwrap());
+    }
+
+    #[test]
+    fn test_edge_from_other_edge_list_fails() {
+        let model = mock_service()
+            .build(&json!({}), Arc::new(StateModel::empty()))
+            .unwrap();
+        let edge = Edge::new(1, 0, 0, 1, Length::new::<uom::si::length::meter>(1.0));
+        assert!(model.valid_edge(&edge).is_err());
+    }
+
+    #[test]
+    fn test_query_geofence_exclude() {
+        let query = json!({ "geofence": unit_square() });
+        let model = mock_service()
+            .build(&query, Arc::new(StateModel::empty()))
+            .unwrap();
+        assert!(!model.valid_edge(&mock_edge(0)).unwrap());
+        assert!(model.valid_edge(&mock_edge(1)).unwrap());
+    }
+
+    #[test]
+    fn test_query_geofence_include() {
+        let query = json!({
+            "geofence": { "type": "Feature", "properties": {}, "geometry": unit_square() },
+            "geofence_policy": "include"
+        });
+        let model = mock_service()
+            .build(&query, Arc::new(StateModel::empty()))
+            .unwrap();
+        assert!(model.valid_edge(&mock_edge(0)).unwrap());
+        assert!(!model.valid_edge(&mock_edge(1)).unwrap());
+    }
+
+    #[test]
+    fn test_non_polygonal_geofence_fails() {
+        let query = json!({ "geofence": { "type": "Point", "coordinates": [0.5, 0.5] } });
+        let result = mock_service().build(&query, Arc::new(StateModel::empty()));
+        assert!(result.is_err());
+    }
+}
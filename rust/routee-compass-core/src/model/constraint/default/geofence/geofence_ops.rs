@@ -0,0 +1,88 @@
+use crate::{
+    model::constraint::ConstraintModelError,
+    util::{
+        fs::fs_utils,
+        geo::{geo_io_utils, PolygonalRTree},
+    },
+};
+use geo::Geometry;
+use geojson::GeoJson;
+use std::{collections::HashSet, hash::Hash};
+
+/// reads polygonal geometries from a GeoJSON document. accepts a GeoJSON
+/// Geometry, Feature, or FeatureCollection.
+///
+/// # Arguments
+///
+/// * `value` - GeoJSON document
+///
+/// # Returns
+///
+/// the Polygon and MultiPolygon geometries in the document, or an error
+/// if the document is invalid or contains non-polygonal geometries
+pub fn read_polygons(
+    value: &serde_json::Value,
+) -> Result<Vec<Geometry<f32>>, ConstraintModelError> {
+    let geojson = GeoJson::from_json_value(value.clone()).map_err(|e| {
+        ConstraintModelError::BuildError(format!("geofence is not valid GeoJSON: {e}"))
+    })?;
+    let geometries: Vec<geojson::Geometry> = match geojson {
+        GeoJson::Geometry(g) => vec![g],
+        GeoJson::Feature(f) => f.geometry.into_iter().collect(),
+        GeoJson::FeatureCollection(fc) => {
+            fc.features.into_iter().filter_map(|f| f.geometry).collect()
+        }
+    };
+    geometries
+        .into_iter()
+        .enumerate()
+        .map(|(idx, g)| {
+            let geometry_f64: Geometry<f64> = g.try_into().map_err(|e| {
+                ConstraintModelError::BuildError(format!(
+                    "geofence geometry {idx} cannot be deserialized: {e}"
+                ))
+            })?;
+            match geometry_f64 {
+                Geometry::Polygon(_) | Geometry::MultiPolygon(_) => Ok(()),
+                _ => Err(ConstraintModelError::BuildError(format!(
+                    "geofence geometry {idx} is not polygonal"
+                ))),
+            }?;
+            geo_io_utils::downsample_geometry(geometry_f64).map_err(|e| {
+                ConstraintModelError::BuildError(format!(
+                    "geofence geometry {idx} cannot be downsampled to f32: {e}"
+                ))
+            })
+        })
+        .collect()
+}
+
+/// reads polygonal geometries from a GeoJSON file.
+pub fn read_polygons_from_file(filepath: &str) -> Result<Vec<Geometry<f32>>, ConstraintModelError> {
+    let contents = fs_utils::read_to_string(filepath).map_err(|e| {
+        ConstraintModelError::BuildError(format!(
+            "file read failed for geofence file '{filepath}': {e}"
+        ))
+    })?;
+    let value: serde_json::Value = serde_json::from_str(&contents).map_err(|e| {
+        ConstraintModelError::BuildError(format!(
+            "unable to parse geofence file '{filepath}' as JSON: {e}"
+        ))
+    })?;
+    read_polygons(&value)
+}
+
+/// collects the ids of all geometries in the rtree that intersect any of the polygons.
+pub fn intersecting_ids<D: Copy + Eq + Hash>(
+    rtree: &PolygonalRTree<f32, D>,
+    polygons: &[Geometry<f32>],
+) -> Result<HashSet<D>, ConstraintModelError> {
+    let mut result = HashSet::new();
+    for polygon in polygons.iter() {
+        let nodes = rtree.intersection(polygon).map_err(|e| {
+            ConstraintModelError::BuildError(format!("failure intersecting geofence: {e}"))
+        })?;
+        result.extend(nodes.map(|node| node.data));
+    }
+    Ok(result)
+}
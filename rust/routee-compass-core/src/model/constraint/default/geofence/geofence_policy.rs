@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+/// describes how edges intersecting a geofence are treated by the search.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GeofencePolicy {
+    /// edges intersecting the geofence are removed from the search (cordon)
+    #[default]
+    Exclude,
+    /// only edges intersecting the geofence may be traversed
+    Include,
+}
+
+impl GeofencePolicy {
+    /// tests if an edge is valid given whether it intersects the geofence
+    pub fn is_valid(&self, intersects: bool) -> bool {
+        match self {
+            GeofencePolicy::Exclude => !intersects,
+            GeofencePolicy::Include => intersects,
+        }
+    }
+}
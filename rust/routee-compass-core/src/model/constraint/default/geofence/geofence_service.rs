@@ -0,0 +1,55 @@
+use super::{geofence_model::GeofenceConstraintModel, geofence_ops, GeofencePolicy};
+use crate::{
+    model::{
+        constraint::{ConstraintModel, ConstraintModelError, ConstraintModelService},
+        network::{EdgeId, EdgeListId},
+        state::StateModel,
+    },
+    util::geo::PolygonalRTree,
+};
+use std::{collections::HashSet, sync::Arc};
+
+#[derive(Clone)]
+pub struct GeofenceFrontierService {
+    /// edge list of the geometries in the rtree
+    pub edge_list_id: EdgeListId,
+    pub edge_rtree: Arc<PolygonalRTree<f32, (EdgeListId, EdgeId)>>,
+    /// edges intersecting the geofences loaded from configuration, if any were configured
+    pub intersecting_edges: Option<Arc<HashSet<(EdgeListId, EdgeId)>>>,
+    pub policy: GeofencePolicy,
+}
+
+impl ConstraintModelService for GeofenceFrontierService {
+    fn build(
+        &self,
+        query: &serde_json::Value,
+        _state_model: Arc<StateModel>,
+    ) -> Result<Arc<dyn ConstraintModel>, ConstraintModelError> {
+        let policy = match query.get("geofence_policy") {
+            None => self.policy,
+            Some(p) => serde_json::from_value(p.clone()).map_err(|e| {
+                ConstraintModelError::BuildError(format!(
+                    "query 'geofence_policy' must be one of 'exclude' or 'include': {e}"
+                ))
+            })?,
+        };
+
+        // geofences provided in the query are combined with any configured geofences
+        let intersecting_edges = match query.get("geofence") {
+            None => self.intersecting_edges.clone(),
+            Some(geofence) => {
+                let polygons = geofence_ops::read_polygons(geofence)?;
+                let mut edges = geofence_ops::intersecting_ids(&self.edge_rtree, &polygons)?;
+                edges.extend(self.intersecting_edges.iter().flat_map(|e| e.iter()));
+                Some(Arc::new(edges))
+            }
+        };
+
+        let model = GeofenceConstraintModel {
+            edge_list_id: self.edge_list_id,
+            intersecting_edges,
+            policy,
+        };
+        Ok(Arc::new(model))
+    }
+}
@@ -0,0 +1,8 @@
+pub mod geofence_builder;
+pub mod geofence_builder_config;
+pub mod geofence_model;
+pub mod geofence_ops;
+mod geofence_policy;
+pub mod geofence_service;
+
+pub use geofence_policy::GeofencePolicy;
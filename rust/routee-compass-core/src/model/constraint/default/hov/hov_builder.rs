@@ -0,0 +1,47 @@
+use super::hov_builder_config::HovBuilderConfig;
+use super::hov_service::HovFrontierService;
+use crate::{
+    model::constraint::{ConstraintModelBuilder, ConstraintModelError, ConstraintModelService},
+    util::fs::{read_decoders, read_utils},
+};
+use kdam::Bar;
+use std::{path::PathBuf, sync::Arc};
+
+pub struct HovBuilder {}
+
+impl ConstraintModelBuilder for HovBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn ConstraintModelService>, ConstraintModelError> {
+        let config: HovBuilderConfig = serde_json::from_value(parameters.clone()).map_err(|e| {
+            ConstraintModelError::BuildError(format!("failed to read hov configuration: {e}"))
+        })?;
+
+        let hov_file = PathBuf::from(&config.hov_input_file);
+        let hov_by_edge: Box<[bool]> = read_utils::read_raw_file(
+            &hov_file,
+            read_decoders::bool,
+            Some(Bar::builder().desc("hov lanes")),
+            None,
+        )
+        .map_err(|e| {
+            ConstraintModelError::BuildError(format!(
+                "failed to load file at {file_path:?}: {e}",
+                file_path = hov_file
+            ))
+        })?;
+
+        let service = HovFrontierService {
+            hov_by_edge: Arc::new(hov_by_edge),
+            min_occupancy: config
+                .min_occupancy
+                .unwrap_or(HovBuilderConfig::DEFAULT_MIN_OCCUPANCY),
+            default_occupancy: config
+                .default_occupancy
+                .unwrap_or(HovBuilderConfig::DEFAULT_OCCUPANCY),
+            toll_feature: config.toll_feature,
+        };
+        Ok(Arc::new(service))
+    }
+}
@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct HovBuilderConfig {
+    /// file with one boolean row per edge, true if the edge is an HOV/express lane
+    pub hov_input_file: String,
+    /// minimum vehicle occupancy required to use an HOV edge. default 2.
+    pub min_occupancy: Option<u64>,
+    /// occupancy assumed when a query does not provide one. default 1.
+    pub default_occupancy: Option<u64>,
+    /// optional state variable name. when the value of this variable is
+    /// greater than zero, the vehicle has engaged a toll (HOT lane) and
+    /// may use HOV edges regardless of occupancy.
+    pub toll_feature: Option<String>,
+}
+
+impl HovBuilderConfig {
+    pub const DEFAULT_MIN_OCCUPANCY: u64 = 2;
+    pub const DEFAULT_OCCUPANCY: u64 = 1;
+}
@@ -0,0 +1,187 @@
+use super::hov_service::HovFrontierService;
+use crate::algorithm::search::{Direction, SearchTree};
+use crate::model::{
+    constraint::{ConstraintModel, ConstraintModelError},
+    network::Edge,
+    state::{StateModel, StateVariable},
+};
+use std::sync::Arc;
+
+/// restricts HOV/express lane edges to vehicles whose query occupancy meets
+/// the configured threshold. when a toll feature is configured, vehicles that
+/// have engaged the toll may also use these edges (HOT lanes).
+pub struct HovConstraintModel {
+    pub service: Arc<HovFrontierService>,
+    pub occupancy: u64,
+}
+
+impl HovConstraintModel {
+    fn is_hov_edge(&self, edge: &Edge) -> Result<bool, ConstraintModelError> {
+        self.service
+            .hov_by_edge
+            .get(edge.edge_id.0)
+            .copied()
+            .ok_or_else(|| {
+                ConstraintModelError::ConstraintModelError(format!(
+                    "edge id {} missing from hov constraint model file",
+                    edge.edge_id
+                ))
+            })
+    }
+
+    fn meets_occupancy(&self) -> bool {
+        self.occupancy >= self.service.min_occupancy
+    }
+}
+
+impl ConstraintModel for HovConstraintModel {
+    fn valid_frontier(
+        &self,
+        edge: &Edge,
+        _previous_edge: Option<&Edge>,
+        _tree: &SearchTree,
+        _direction: &Direction,
+        state: &[StateVariable],
+        state_model: &StateModel,
+    ) -> Result<bool, ConstraintModelError> {
+        if self.meets_occupancy() || !self.is_hov_edge(edge)? {
+            return Ok(true);
+        }
+        match &self.service.toll_feature {
+            None => Ok(false),
+            Some(toll_feature) => {
+                let toll = state_model
+                    .get_raw_state_variable(state, toll_feature)
+                    .map_err(|e| ConstraintModelError::ConstraintModelError(e.to_string()))?;
+                Ok(toll.0 > 0.0)
+            }
+        }
+    }
+
+    fn valid_edge(&self, edge: &Edge) -> Result<bool, ConstraintModelError> {
+        // with a toll feature, validity depends on the search state, so the
+        // edge cannot be ruled out independently of a search.
+        if self.meets_occupancy() || self.service.toll_feature.is_some() {
+            return Ok(true);
+        }
+        Ok(!self.is_hov_edge(edge)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::{
+        constraint::ConstraintModelService,
+        state::{CustomVariableConfig, StateVariableConfig},
+    };
+    use serde_json::{json, Value};
+    use uom::si::f64::Length;
+
+    fn mock_service(toll_feature: Option<&str>) -> HovFrontierService {
+        HovFrontierService {
+            hov_by_edge: Arc::new(vec![false, true].into_boxed_slice()),
+            min_occupancy: 2,
+            default_occupancy: 1,
+            toll_feature: toll_feature.map(String::from),
+        }
+    }
+
+    fn mock_state_model() -> Arc<StateModel> {
+        Arc::new(StateModel::new(vec![(
+            String::from("hot_toll"),
+            StateVariableConfig::Custom {
+                custom_type: String::from("toll_engaged"),
+                value: CustomVariableConfig::Boolean { initial: false },
+                accumulator: true,
+            },
+        )]))
+    }
+
+    fn mock_edge(edge_id: usize) -> Edge {
+        Edge::new(0, edge_id, 0, 1, Length::new::<uom::si::length::meter>(1.0))
+    }
+
+    fn build(service: HovFrontierService, query: Value) -> Arc<dyn ConstraintModel> {
+        service
+            .build(&query, mock_state_model())
+            .expect("test invariant failed: unable to build hov model")
+    }
+
+    #[test]
+    fn test_general_purpose_edge_always_valid() {
+        let model = build(mock_service(None), json!({}));
+        assert!(model.valid_edge(&mock_edge(0)).unwrap());
+    }
+
+    #[test]
+    fn test_hov_edge_invalid_for_single_occupant() {
+        let model = build(mock_service(None), json!({}));
+        assert!(!model.valid_edge(&mock_edge(1)).unwrap());
+        let state_model = mock_state_model();
+        let state = state_model.initial_state(None).unwrap();
+        let tree = SearchTree::new(Direction::Forward);
+        let result = model
+            .valid_frontier(
+                &mock_edge(1),
+                None,
+                &tree,
+                &Direction::Forward,
+                &state,
+                &state_model,
+            )
+            .unwrap();
+        assert!(!result);
+    }
+
+    #[test]
+    fn test_hov_edge_valid_with_occupancy() {
+        let model = build(mock_service(None), json!({"occupancy": 3}));
+        assert!(model.valid_edge(&mock_edge(1)).unwrap());
+    }
+
+    #[test]
+    fn test_hov_edge_valid_with_toll_engaged() {
+        let model = build(mock_service(Some("hot_toll")), json!({"occupancy": 1}));
+        let state_model = mock_state_model();
+        let mut state = state_model.initial_state(None).unwrap();
+        let tree = SearchTree::new(Direction::Forward);
+        let no_toll = model
+            .valid_frontier(
+                &mock_edge(1),
+                None,
+                &tree,
+                &Direction::Forward,
+                &state,
+                &state_model,
+            )
+            .unwrap();
+        assert!(!no_toll);
+        state_model
+            .set_custom_bool(&mut state, "hot_toll", &true)
+            .unwrap();
+        let with_toll = model
+            .valid_frontier(
+                &mock_edge(1),
+                None,
+                &tree,
+                &Direction::Forward,
+                &state,
+                &state_model,
+            )
+            .unwrap();
+        assert!(with_toll);
+    }
+
+    #[test]
+    fn test_unknown_toll_feature_fails_build() {
+        let result = mock_service(Some("missing")).build(&json!({}), mock_state_model());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_invalid_occupancy_fails_build() {
+        let result = mock_service(None).build(&json!({"occupancy": "two"}), mock_state_model());
+        assert!(result.is_err());
+    }
+}
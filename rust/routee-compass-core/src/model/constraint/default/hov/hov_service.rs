@@ -0,0 +1,46 @@
+use super::hov_model::HovConstraintModel;
+use crate::model::{
+    constraint::{ConstraintModel, ConstraintModelError, ConstraintModelService},
+    state::StateModel,
+};
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct HovFrontierService {
+    pub hov_by_edge: Arc<Box<[bool]>>,
+    pub min_occupancy: u64,
+    pub default_occupancy: u64,
+    pub toll_feature: Option<String>,
+}
+
+impl ConstraintModelService for HovFrontierService {
+    fn build(
+        &self,
+        query: &serde_json::Value,
+        state_model: Arc<StateModel>,
+    ) -> Result<Arc<dyn ConstraintModel>, ConstraintModelError> {
+        let occupancy = match query.get("occupancy") {
+            None => self.default_occupancy,
+            Some(value) => value.as_u64().ok_or_else(|| {
+                ConstraintModelError::BuildError(format!(
+                    "query 'occupancy' value must be a non-negative integer, found '{value}'"
+                ))
+            })?,
+        };
+
+        if let Some(toll_feature) = &self.toll_feature {
+            if !state_model.contains_key(toll_feature) {
+                return Err(ConstraintModelError::BuildError(format!(
+                    "hov constraint model toll_feature '{toll_feature}' is not a state variable, should be one of {}",
+                    state_model.get_names()
+                )));
+            }
+        }
+
+        let model = HovConstraintModel {
+            service: Arc::new(self.clone()),
+            occupancy,
+        };
+        Ok(Arc::new(model))
+    }
+}
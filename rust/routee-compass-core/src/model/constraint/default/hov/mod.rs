@@ -0,0 +1,4 @@
+pub mod hov_builder;
+pub mod hov_builder_config;
+pub mod hov_model;
+pub mod hov_service;
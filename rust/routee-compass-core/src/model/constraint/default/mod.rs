@@ -1,6 +1,11 @@
+pub mod avoid_edges;
+pub mod closures;
 pub mod combined;
+pub mod geofence;
+pub mod hov;
 pub mod no_restriction;
 pub mod no_restriction_builder;
 pub mod road_class;
+pub mod time_window;
 pub mod turn_restrictions;
 pub mod vehicle_restrictions;
@@ -1,3 +1,4 @@
+use crate::algorithm::search::{Direction, SearchTree};
 use crate::model::{
     constraint::{error::ConstraintModelError, ConstraintModel, ConstraintModelService},
     network::Edge,
@@ -13,6 +14,8 @@ impl ConstraintModel for NoRestriction {
         &self,
         _edge: &Edge,
         _previos_edge: Option<&Edge>,
+        _tree: &SearchTree,
+        _direction: &Direction,
         _state: &[crate::model::state::StateVariable],
         _state_model: &StateModel,
     ) -> Result<bool, ConstraintModelError> {
@@ -1,4 +1,5 @@
 pub mod road_class_builder;
 pub mod road_class_builder_config;
 pub mod road_class_model;
+pub mod road_class_ops;
 pub mod road_class_service;
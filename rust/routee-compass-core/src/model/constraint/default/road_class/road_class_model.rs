@@ -1,4 +1,5 @@
 use super::road_class_service::RoadClassFrontierService;
+use crate::algorithm::search::{Direction, SearchTree};
 use crate::model::{
     constraint::{ConstraintModel, ConstraintModelError},
     network::Edge,
@@ -16,6 +17,8 @@ impl ConstraintModel for RoadClassConstraintModel {
         &self,
         edge: &Edge,
         _previous_edge: Option<&Edge>,
+        _tree: &SearchTree,
+        _direction: &Direction,
         _state: &[StateVariable],
         _state_model: &StateModel,
     ) -> Result<bool, ConstraintModelError> {
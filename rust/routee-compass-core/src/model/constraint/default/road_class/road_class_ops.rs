@@ -0,0 +1,112 @@
+use crate::{
+    model::constraint::ConstraintModelError,
+    util::fs::{read_decoders, read_utils},
+};
+use kdam::Bar;
+use serde_json::Value;
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+};
+
+/// road classes encoded by EdgeId index along with the mapping from label to encoded value
+pub type EncodedRoadClasses = (Box<[u8]>, HashMap<String, u8>);
+
+/// reads a road class file with one road class label per edge, encoding each
+/// unique label as a u8 identifier.
+///
+/// # Returns
+///
+/// the encoded road class by EdgeId index along with the mapping from label to identifier
+pub fn read_road_class_file(
+    road_class_file: &Path,
+) -> Result<EncodedRoadClasses, ConstraintModelError> {
+    let road_class_lookup: Box<[String]> = read_utils::read_raw_file(
+        road_class_file,
+        read_decoders::string,
+        Some(Bar::builder().desc("road class")),
+        None,
+    )
+    .map_err(|e| {
+        ConstraintModelError::BuildError(format!(
+            "failed to load file at {file_path:?}: {e}",
+            file_path = road_class_file
+        ))
+    })?;
+
+    let mut mapping = HashMap::new();
+    let mut encoded = Vec::with_capacity(road_class_lookup.len());
+    let mut next_id = 0usize;
+
+    for class in road_class_lookup.iter() {
+        let id = match mapping.get(class) {
+            Some(id) => *id,
+            None => {
+                let id_usize = next_id;
+                if id_usize > u8::MAX as usize {
+                    return Err(ConstraintModelError::BuildError(
+                        "too many unique road classes, max is 256".to_string(),
+                    ));
+                }
+                next_id += 1;
+                let id = id_usize as u8;
+                mapping.insert(class.clone(), id);
+                id
+            }
+        };
+        encoded.push(id);
+    }
+
+    Ok((encoded.into_boxed_slice(), mapping))
+}
+
+/// decodes a query value into a set of road class labels
+///
+/// # Arguments
+///
+/// * `key` - the query key the value was found at, used for error messages
+/// * `value` - the query value, expected to be an array
+pub fn read_road_classes_from_query(
+    key: &str,
+    value: &Value,
+) -> Result<HashSet<String>, ConstraintModelError> {
+    let arr = value.as_array().ok_or_else(|| {
+        ConstraintModelError::BuildError(format!(
+            "query '{key}' value must be an array, found '{value}'"
+        ))
+    })?;
+    // if the value is a string (or number or bool), store it as a valid road class
+    let arr_str = arr
+        .iter()
+        .enumerate()
+        .map(|(idx, c)| match c {
+            Value::Bool(b) => Ok(b.to_string()),
+            Value::Number(number) => Ok(number.to_string()),
+            Value::String(string) => Ok(string.clone()),
+            _ => Err(ConstraintModelError::BuildError(format!(
+                "query '{key}[{idx}]' value must be a string, found '{c}'"
+            ))),
+        })
+        .collect::<Result<HashSet<_>, _>>()?;
+
+    Ok(arr_str)
+}
+
+/// maps a set of road class labels to their encoded identifiers, failing if
+/// any label is not found in the mapping.
+pub fn encode_road_classes(
+    road_classes: &HashSet<String>,
+    mapping: &HashMap<String, u8>,
+) -> Result<HashSet<u8>, ConstraintModelError> {
+    road_classes
+        .iter()
+        .map(|c| {
+            mapping.get(c).copied().ok_or_else(|| {
+                ConstraintModelError::BuildError(format!(
+                    "road class '{}' not found in road class mapping",
+                    c
+                ))
+            })
+        })
+        .collect()
+}
@@ -1,13 +1,10 @@
 use super::road_class_model::RoadClassConstraintModel;
+use super::road_class_ops;
 use crate::model::{
     constraint::{ConstraintModel, ConstraintModelError, ConstraintModelService},
     state::StateModel,
 };
-use serde_json::Value;
-use std::{
-    collections::{HashMap, HashSet},
-    sync::Arc,
-};
+use std::{collections::HashMap, sync::Arc};
 
 #[derive(Clone)]
 pub struct RoadClassFrontierService {
@@ -21,21 +18,14 @@ impl ConstraintModelService for RoadClassFrontierService {
         query: &serde_json::Value,
         _state_model: Arc<StateModel>,
     ) -> Result<Arc<dyn ConstraintModel>, ConstraintModelError> {
-        let query_road_classes = match query.get("road_classes").map(read_road_classes_from_query) {
+        let query_road_classes = match query
+            .get("road_classes")
+            .map(|v| road_class_ops::read_road_classes_from_query("road_classes", v))
+        {
             Some(Err(e)) => Err(e),
             Some(Ok(road_classes)) => {
-                let mapped: Result<HashSet<u8>, ConstraintModelError> = road_classes
-                    .iter()
-                    .map(|c| {
-                        self.road_class_mapping.get(c).copied().ok_or_else(|| {
-                            ConstraintModelError::BuildError(format!(
-                                "road class '{}' not found in road class mapping",
-                                c
-                            ))
-                        })
-                    })
-                    .collect();
-                mapped.map(Some)
+                road_class_ops::encode_road_classes(&road_classes, &self.road_class_mapping)
+                    .map(Some)
             }
             None => Ok(None),
         }?;
@@ -48,27 +38,3 @@ impl ConstraintModelService for RoadClassFrontierService {
         Ok(Arc::new(model))
     }
 }
-
-/// decodes the query `road_classes` value into a set of road class identifiers
-fn read_road_classes_from_query(value: &Value) -> Result<HashSet<String>, ConstraintModelError> {
-    let arr = value.as_array().ok_or_else(|| {
-        ConstraintModelError::BuildError(format!(
-            "query 'road_classes' value must be an array, found '{value}'"
-        ))
-    })?;
-    // if the value is a string (or number or bool), store it as a valid road class
-    let arr_str = arr
-        .iter()
-        .enumerate()
-        .map(|(idx, c)| match c {
-            Value::Bool(b) => Ok(b.to_string()),
-            Value::Number(number) => Ok(number.to_string()),
-            Value::String(string) => Ok(string.clone()),
-            _ => Err(ConstraintModelError::BuildError(format!(
-                "query 'road_classes[{idx}]' value must be a string, found '{c}'"
-            ))),
-        })
-        .collect::<Result<HashSet<_>, _>>()?;
-
-    Ok(arr_str)
-}
@@ -0,0 +1,3 @@
+pub mod time_window_builder;
+pub mod time_window_model;
+pub mod time_window_service;
@@ -0,0 +1,17 @@
+use super::time_window_service::TimeWindowService;
+use crate::model::constraint::{
+    ConstraintModelBuilder, ConstraintModelError, ConstraintModelService,
+};
+use std::sync::Arc;
+
+pub struct TimeWindowBuilder {}
+
+impl ConstraintModelBuilder for TimeWindowBuilder {
+    fn build(
+        &self,
+        _parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn ConstraintModelService>, ConstraintModelError> {
+        let m: Arc<dyn ConstraintModelService> = Arc::new(TimeWindowService {});
+        Ok(m)
+    }
+}
@@ -0,0 +1,115 @@
+use crate::algorithm::search::{Direction, SearchTree};
+use crate::model::{
+    constraint::{ConstraintModel, ConstraintModelError},
+    network::Edge,
+    state::{StateModel, StateVariable},
+    traversal::default::fieldname,
+};
+use uom::si::f64::Time;
+
+/// rejects traversals that end after the latest arrival of the query's arrival window.
+/// since trip time only increases along a route, a traversal ending after the window
+/// closes cannot arrive at the destination within it.
+pub struct TimeWindowConstraintModel {
+    pub latest_arrival: Option<Time>,
+}
+
+impl TimeWindowConstraintModel {
+    fn within_window(
+        &self,
+        state: &[StateVariable],
+        state_model: &StateModel,
+    ) -> Result<bool, ConstraintModelError> {
+        match self.latest_arrival {
+            None => Ok(true),
+            Some(latest_arrival) => {
+                let trip_time = state_model
+                    .get_time(state, fieldname::TRIP_TIME)
+                    .map_err(|e| ConstraintModelError::ConstraintModelError(e.to_string()))?;
+                Ok(trip_time <= latest_arrival)
+            }
+        }
+    }
+}
+
+impl ConstraintModel for TimeWindowConstraintModel {
+    fn valid_frontier(
+        &self,
+        _edge: &Edge,
+        _previous_edge: Option<&Edge>,
+        _tree: &SearchTree,
+        _direction: &Direction,
+        state: &[StateVariable],
+        state_model: &StateModel,
+    ) -> Result<bool, ConstraintModelError> {
+        self.within_window(state, state_model)
+    }
+
+    fn valid_traversal(
+        &self,
+        _edge: &Edge,
+        state: &[StateVariable],
+        state_model: &StateModel,
+    ) -> Result<bool, ConstraintModelError> {
+        self.within_window(state, state_model)
+    }
+
+    fn valid_edge(&self, _edge: &Edge) -> Result<bool, ConstraintModelError> {
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::{
+        constraint::{
+            default::time_window::time_window_service::TimeWindowService, ConstraintModelService,
+        },
+        state::StateVariableConfig,
+        unit::TimeUnit,
+    };
+    use serde_json::json;
+    use std::sync::Arc;
+    use uom::{si::time::minute, ConstZero};
+
+    #[test]
+    fn test_rejects_late_traversals() {
+        let state_model = Arc::new(
+            StateModel::empty()
+                .register(
+                    vec![],
+                    vec![(
+                        String::from(fieldname::TRIP_TIME),
+                        StateVariableConfig::Time {
+                            initial: Time::ZERO,
+                            accumulator: true,
+                            output_unit: Some(TimeUnit::Minutes),
+                        },
+                    )],
+                )
+                .unwrap(),
+        );
+        let query = json!({"arrival_window": {"start": 10.0, "end": 0.5, "time_unit": "hours"}});
+        assert!(TimeWindowService {}
+            .build(&query, state_model.clone())
+            .is_err());
+        let query = json!({"arrival_window": {"end": 0.5, "time_unit": "hours"}});
+        let model = TimeWindowService {}
+            .build(&query, state_model.clone())
+            .unwrap();
+        let edge = Edge::new(0, 0, 0, 1, uom::si::f64::Length::ZERO);
+        let mut state = state_model.initial_state(None).unwrap();
+        for (minutes, expected) in [(29.0, true), (30.0, true), (31.0, false)] {
+            state_model
+                .set_time(
+                    &mut state,
+                    fieldname::TRIP_TIME,
+                    &Time::new::<minute>(minutes),
+                )
+                .unwrap();
+            let valid = model.valid_traversal(&edge, &state, &state_model).unwrap();
+            assert_eq!(valid, expected, "trip time of {minutes} minutes");
+        }
+    }
+}
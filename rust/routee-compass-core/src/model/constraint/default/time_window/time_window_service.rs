@@ -0,0 +1,23 @@
+use super::time_window_model::TimeWindowConstraintModel;
+use crate::model::{
+    constraint::{ConstraintModel, ConstraintModelError, ConstraintModelService},
+    state::StateModel,
+    traversal::default::time_window::ArrivalWindow,
+};
+use std::sync::Arc;
+
+pub struct TimeWindowService {}
+
+impl ConstraintModelService for TimeWindowService {
+    fn build(
+        &self,
+        query: &serde_json::Value,
+        _state_model: Arc<StateModel>,
+    ) -> Result<Arc<dyn ConstraintModel>, ConstraintModelError> {
+        let latest_arrival = ArrivalWindow::from_query(query)
+            .map_err(ConstraintModelError::BuildError)?
+            .and_then(|window| window.end());
+        let model = TimeWindowConstraintModel { latest_arrival };
+        Ok(Arc::new(model))
+    }
+}
@@ -1,3 +1,4 @@
+use crate::algorithm::search::{Direction, SearchTree};
 use crate::model::{
     constraint::{ConstraintModel, ConstraintModelError},
     network::Edge,
@@ -16,13 +17,17 @@ impl ConstraintModel for TurnRestrictionConstraintModel {
         &self,
         edge: &Edge,
         previous_edge: Option<&Edge>,
+        _tree: &SearchTree,
+        _direction: &Direction,
         _state: &[StateVariable],
         _state_model: &StateModel,
     ) -> Result<bool, ConstraintModelError> {
         match previous_edge {
             Some(previous_edge) => {
                 let edge_pair = RestrictedEdgePair {
+                    prev_edge_list_id: previous_edge.edge_list_id,
                     prev_edge_id: previous_edge.edge_id,
+                    next_edge_list_id: edge.edge_list_id,
                     next_edge_id: edge.edge_id,
                 };
                 if self.service.restricted_edge_pairs.contains(&edge_pair) {
@@ -1,15 +1,22 @@
 use super::turn_restriction_model::TurnRestrictionConstraintModel;
 use crate::model::{
     constraint::{ConstraintModel, ConstraintModelError, ConstraintModelService},
-    network::EdgeId,
+    network::{EdgeId, EdgeListId},
     state::StateModel,
 };
 use serde::Deserialize;
 use std::{collections::HashSet, sync::Arc};
 
+/// a restricted turn from one edge to the next. edges are identified by their edge list
+/// and edge id, as edge ids repeat across edge lists. the edge list columns may be
+/// omitted from a turn restriction file, in which case both edges are on edge list 0.
 #[derive(Eq, PartialEq, Hash, Deserialize, Clone)]
 pub struct RestrictedEdgePair {
+    #[serde(default)]
+    pub prev_edge_list_id: EdgeListId,
     pub prev_edge_id: EdgeId,
+    #[serde(default)]
+    pub next_edge_list_id: EdgeListId,
     pub next_edge_id: EdgeId,
 }
 
@@ -29,3 +36,31 @@ impl ConstraintModelService for TurnRestrictionFrontierService {
         Ok(Arc::new(model))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_read_restricted_edge_pairs() {
+        let read = |csv_str: &str| {
+            csv::Reader::from_reader(csv_str.as_bytes())
+                .deserialize::<RestrictedEdgePair>()
+                .collect::<Result<Vec<_>, _>>()
+                .expect("failed to read restrictions")
+        };
+        let single = read("prev_edge_id,next_edge_id\n4,7\n");
+        assert!(single[0].prev_edge_list_id == EdgeListId(0));
+        assert!(single[0].next_edge_list_id == EdgeListId(0));
+
+        // the same edge ids on different edge lists are different turns
+        let multi = read(
+            "prev_edge_list_id,prev_edge_id,next_edge_list_id,next_edge_id\n0,4,1,7\n1,4,1,7\n",
+        );
+        assert!(multi[0].prev_edge_list_id == EdgeListId(0));
+        assert!(multi[0].next_edge_list_id == EdgeListId(1));
+        let restricted: HashSet<RestrictedEdgePair> = multi.into_iter().collect();
+        assert_eq!(restricted.len(), 2);
+        assert!(!restricted.contains(&single[0]));
+    }
+}
@@ -0,0 +1,60 @@
+use super::{VehicleParameter, VehicleParameterType, VehicleRestriction};
+use indexmap::IndexMap;
+
+/// the collection of vehicle restrictions found on a single edge.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EdgeRestrictions {
+    /// restrictions that invalidate the edge whenever they are violated
+    pub restrictions: IndexMap<VehicleParameterType, VehicleRestriction>,
+    /// combination restrictions by group id. a combination invalidates the edge only
+    /// when every restriction in the group is violated.
+    pub combinations: IndexMap<String, Vec<VehicleRestriction>>,
+}
+
+impl EdgeRestrictions {
+    /// adds a restriction to this edge, either as a standalone restriction or
+    /// as a member of a combination group.
+    pub fn add(&mut self, restriction: VehicleRestriction, group: Option<&str>) {
+        match group {
+            None => {
+                self.restrictions
+                    .insert(restriction.vehicle_parameter_type().clone(), restriction);
+            }
+            Some(group) => {
+                self.combinations
+                    .entry(group.to_string())
+                    .or_default()
+                    .push(restriction);
+            }
+        }
+    }
+
+    /// tests whether a vehicle described by these query parameters may traverse this edge.
+    ///
+    /// a restriction is only violated when the query provides the matching parameter.
+    /// for combinations, all restrictions in the group must be matched by a query parameter
+    /// and violated in order to restrict the edge.
+    pub fn is_valid(&self, vehicle_parameters: &[VehicleParameter]) -> bool {
+        for p in vehicle_parameters.iter() {
+            match self.restrictions.get(p.vehicle_parameter_type()) {
+                Some(r) if !r.within_restriction(p) => return false,
+                _ => {}
+            }
+        }
+
+        for group in self.combinations.values() {
+            let all_violated = group.iter().all(|r| {
+                vehicle_parameters
+                    .iter()
+                    .find(|p| p.vehicle_parameter_type() == r.vehicle_parameter_type())
+                    .map(|p| !r.within_restriction(p))
+                    .unwrap_or(false)
+            });
+            if !group.is_empty() && all_violated {
+                return false;
+            }
+        }
+
+        true
+    }
+}
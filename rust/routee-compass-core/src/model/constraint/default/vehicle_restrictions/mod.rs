@@ -1,4 +1,5 @@
 mod comparison_operation;
+mod edge_restrictions;
 mod vehicle_parameter;
 mod vehicle_parameter_config;
 mod vehicle_parameter_type;
@@ -11,7 +12,8 @@ mod vehicle_restriction_row;
 mod vehicle_restriction_service;
 
 pub use comparison_operation::ComparisonOperation;
-pub use vehicle_parameter::VehicleParameter;
+pub use edge_restrictions::EdgeRestrictions;
+pub use vehicle_parameter::{derive_weight_per_axle, VehicleParameter};
 pub use vehicle_parameter_config::VehicleParameterConfig;
 pub use vehicle_parameter_type::VehicleParameterType;
 pub use vehicle_restriction::VehicleRestriction;
@@ -11,6 +11,7 @@ pub enum VehicleParameter {
     TrailerLength { value: Length },
     TotalWeight { value: Mass },
     WeightPerAxle { value: Mass },
+    AxleCount { value: u64 },
 }
 
 impl VehicleParameter {
@@ -23,10 +24,40 @@ impl VehicleParameter {
             VehicleParameter::TrailerLength { .. } => &VPT::TrailerLength,
             VehicleParameter::TotalWeight { .. } => &VPT::TotalWeight,
             VehicleParameter::WeightPerAxle { .. } => &VPT::WeightPerAxle,
+            VehicleParameter::AxleCount { .. } => &VPT::AxleCount,
         }
     }
 }
 
+/// derives the weight per axle from the total weight and axle count when a query
+/// provides both but does not provide the weight per axle directly.
+///
+/// # Arguments
+///
+/// * `parameters` - the vehicle parameters of a query
+///
+/// # Returns
+///
+/// the derived weight per axle parameter, if it can be derived and is not already present
+pub fn derive_weight_per_axle(parameters: &[VehicleParameter]) -> Option<VehicleParameter> {
+    let mut total_weight = None;
+    let mut axle_count = None;
+    for p in parameters.iter() {
+        match p {
+            VehicleParameter::WeightPerAxle { .. } => return None,
+            VehicleParameter::TotalWeight { value } => total_weight = Some(*value),
+            VehicleParameter::AxleCount { value } => axle_count = Some(*value),
+            _ => {}
+        }
+    }
+    match (total_weight, axle_count) {
+        (Some(weight), Some(axles)) if axles > 0 => Some(VehicleParameter::WeightPerAxle {
+            value: weight / axles as f64,
+        }),
+        _ => None,
+    }
+}
+
 impl std::fmt::Display for VehicleParameter {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -44,6 +75,9 @@ impl std::fmt::Display for VehicleParameter {
             VehicleParameter::WeightPerAxle { value } => {
                 write!(f, "weight per axle: {value:?}")
             }
+            VehicleParameter::AxleCount { value } => {
+                write!(f, "axle count: {value}")
+            }
         }
     }
 }
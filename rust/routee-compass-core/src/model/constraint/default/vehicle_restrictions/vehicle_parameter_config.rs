@@ -13,6 +13,7 @@ pub enum VehicleParameterConfig {
     TrailerLength { value: f64, unit: DistanceUnit },
     TotalWeight { value: f64, unit: WeightUnit },
     WeightPerAxle { value: f64, unit: WeightUnit },
+    AxleCount { value: u64 },
 }
 
 impl From<VehicleParameterConfig> for VehicleParameter {
@@ -40,6 +41,7 @@ impl From<VehicleParameterConfig> for VehicleParameter {
                     value: unit.to_uom(value),
                 }
             }
+            VehicleParameterConfig::AxleCount { value } => VehicleParameter::AxleCount { value },
         }
     }
 }
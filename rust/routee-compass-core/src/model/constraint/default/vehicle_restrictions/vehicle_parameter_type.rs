@@ -10,6 +10,7 @@ pub enum VehicleParameterType {
     TrailerLength,
     TotalWeight,
     WeightPerAxle,
+    AxleCount,
 }
 
 impl std::fmt::Display for VehicleParameterType {
@@ -21,6 +22,7 @@ impl std::fmt::Display for VehicleParameterType {
             Self::TrailerLength => "trailer_length".to_string(),
             Self::TotalWeight => "total_weight".to_string(),
             Self::WeightPerAxle => "weight_per_axle".to_string(),
+            Self::AxleCount => "axle_count".to_string(),
         };
         write!(f, "{s}")
     }
@@ -37,6 +39,7 @@ impl FromStr for VehicleParameterType {
             "trailer_length" => Ok(Self::TrailerLength),
             "total_weight" => Ok(Self::TotalWeight),
             "weight_per_axle" => Ok(Self::WeightPerAxle),
+            "axle_count" => Ok(Self::AxleCount),
             _ => Err(format!("unknown VehicleParameterType {s}")),
         }
     }
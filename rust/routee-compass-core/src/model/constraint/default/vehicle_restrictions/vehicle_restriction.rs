@@ -102,6 +102,18 @@ impl TryFrom<&RestrictionRow> for VehicleRestriction {
                     })?
                     .to_uom(row.value),
             }),
+            VPT::AxleCount => {
+                if row.value < 0.0 || row.value.fract() != 0.0 {
+                    Err(ConstraintModelError::BuildError(format!(
+                        "axle count restriction on edge {} must be a non-negative integer, found {}",
+                        row.edge_id, row.value
+                    )))
+                } else {
+                    Ok(VehicleParameter::AxleCount {
+                        value: row.value as u64,
+                    })
+                }
+            }
         }?;
         let comparison_operation = row.operation.clone();
         Ok(VehicleRestriction {
@@ -1,6 +1,6 @@
 use super::{
-    vehicle_restriction_builder_config::VehicleRestrictionBuilderConfig, RestrictionRow,
-    VehicleParameterType, VehicleRestriction, VehicleRestrictionFrontierService,
+    vehicle_restriction_builder_config::VehicleRestrictionBuilderConfig, EdgeRestrictions,
+    RestrictionRow, VehicleRestriction, VehicleRestrictionFrontierService,
 };
 use crate::{
     model::{
@@ -9,7 +9,6 @@ use crate::{
     },
     util::fs::read_utils,
 };
-use indexmap::IndexMap;
 use kdam::Bar;
 use std::{collections::HashMap, path::PathBuf, sync::Arc};
 
@@ -42,8 +41,7 @@ impl ConstraintModelBuilder for VehicleRestrictionBuilder {
 
 pub fn vehicle_restriction_lookup_from_file(
     vehicle_restriction_input_file: &PathBuf,
-) -> Result<HashMap<EdgeId, IndexMap<VehicleParameterType, VehicleRestriction>>, ConstraintModelError>
-{
+) -> Result<HashMap<EdgeId, EdgeRestrictions>, ConstraintModelError> {
     let rows: Vec<RestrictionRow> = read_utils::from_csv(
         &vehicle_restriction_input_file,
         true,
@@ -57,22 +55,13 @@ pub fn vehicle_restriction_lookup_from_file(
     })?
     .to_vec();
 
-    let mut vehicle_restriction_lookup: HashMap<
-        EdgeId,
-        IndexMap<VehicleParameterType, VehicleRestriction>,
-    > = HashMap::new();
+    let mut vehicle_restriction_lookup: HashMap<EdgeId, EdgeRestrictions> = HashMap::new();
     for row in rows {
         let restriction = VehicleRestriction::try_from(&row)?;
-        match vehicle_restriction_lookup.get_mut(&row.edge_id) {
-            None => {
-                let mut restrictions = IndexMap::new();
-                restrictions.insert(restriction.vehicle_parameter_type().clone(), restriction);
-                vehicle_restriction_lookup.insert(row.edge_id, restrictions);
-            }
-            Some(restrictions) => {
-                restrictions.insert(restriction.vehicle_parameter_type().clone(), restriction);
-            }
-        }
+        vehicle_restriction_lookup
+            .entry(row.edge_id)
+            .or_default()
+            .add(restriction, row.group.as_deref());
     }
     Ok(vehicle_restriction_lookup)
 }
@@ -1,4 +1,5 @@
 use super::{VehicleParameter, VehicleRestrictionFrontierService};
+use crate::algorithm::search::{Direction, SearchTree};
 use crate::model::{
     constraint::{ConstraintModel, ConstraintModelError},
     network::Edge,
@@ -16,6 +17,8 @@ impl ConstraintModel for VehicleRestrictionConstraintModel {
         &self,
         edge: &Edge,
         _previos_edge: Option<&Edge>,
+        _tree: &SearchTree,
+        _direction: &Direction,
         _state: &[StateVariable],
         _state_model: &StateModel,
     ) -> Result<bool, ConstraintModelError> {
@@ -39,18 +42,8 @@ fn validate_edge(
         (Some(vehicle_restrictions), _) => vehicle_restrictions,
     };
 
-    // for each parameter of this constraint model, test if the parameter is valid for any matching restriction
-    for p in model.vehicle_parameters.iter() {
-        let p_type = p.vehicle_parameter_type();
-        match restrictions.get(p_type) {
-            Some(r) if !r.within_restriction(p) => {
-                return Ok(false);
-            }
-            _ => {}
-        }
-    }
-
-    Ok(true)
+    // test each parameter of this constraint model against the standalone and combination restrictions
+    Ok(restrictions.is_valid(&model.vehicle_parameters))
 }
 
 #[cfg(test)]
@@ -115,6 +108,48 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_e2e_axle_count_exceeded() {
+        let model = build_model("test_bridge_restrictions.csv", "five_axles.json");
+        let edge = mock_edge();
+        match model.valid_edge(&edge) {
+            Ok(is_ok) => assert!(!is_ok),
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn test_e2e_derived_weight_per_axle_exceeded() {
+        // 80000 pounds over 2 axles exceeds the 20000 pound per-axle bridge limit
+        let model = build_model("test_bridge_restrictions.csv", "heavy_two_axles.json");
+        let edge = mock_edge();
+        match model.valid_edge(&edge) {
+            Ok(is_ok) => assert!(!is_ok),
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn test_e2e_combination_partially_violated() {
+        // heavy but short vehicle with enough axles only violates half of the combination
+        let model = build_model("test_bridge_restrictions.csv", "heavy_short.json");
+        let edge = mock_edge();
+        match model.valid_edge(&edge) {
+            Ok(is_ok) => assert!(is_ok),
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn test_e2e_combination_fully_violated() {
+        let model = build_model("test_bridge_restrictions.csv", "heavy_long.json");
+        let edge = mock_edge();
+        match model.valid_edge(&edge) {
+            Ok(is_ok) => assert!(!is_ok),
+            Err(e) => panic!("{}", e),
+        }
+    }
+
     fn build_model(restriction_filename: &str, query_filename: &str) -> Arc<dyn ConstraintModel> {
         let restriction_file = test_filepath(restriction_filename);
         let conf = json!({
@@ -9,4 +9,9 @@ pub struct RestrictionRow {
     pub value: f64,
     pub operation: ComparisonOperation,
     pub unit: String,
+    /// optional combination group identifier. rows on the same edge sharing a group
+    /// are evaluated together: the edge is restricted only when every restriction in
+    /// the group is violated, such as "total weight > 20 tons AND total length > 15 meters".
+    #[serde(default)]
+    pub group: Option<String>,
 }
@@ -1,20 +1,17 @@
 use super::{
-    vehicle_restriction_model::VehicleRestrictionConstraintModel,
-    vehicle_restriction_query::VehicleRestrictionQuery, VehicleParameter, VehicleParameterType,
-    VehicleRestriction,
+    derive_weight_per_axle, vehicle_restriction_model::VehicleRestrictionConstraintModel,
+    vehicle_restriction_query::VehicleRestrictionQuery, EdgeRestrictions, VehicleParameter,
 };
 use crate::model::{
     constraint::{ConstraintModel, ConstraintModelError, ConstraintModelService},
     network::EdgeId,
     state::StateModel,
 };
-use indexmap::IndexMap;
 use std::{collections::HashMap, sync::Arc};
 
 #[derive(Clone)]
 pub struct VehicleRestrictionFrontierService {
-    pub vehicle_restriction_lookup:
-        Arc<HashMap<EdgeId, IndexMap<VehicleParameterType, VehicleRestriction>>>,
+    pub vehicle_restriction_lookup: Arc<HashMap<EdgeId, EdgeRestrictions>>,
 }
 
 impl ConstraintModelService for VehicleRestrictionFrontierService {
@@ -30,11 +27,14 @@ impl ConstraintModelService for VehicleRestrictionFrontierService {
                     "Unable to deserialize vehicle restriction query: {e}"
                 ))
             })?;
-        let vehicle_parameters: Vec<VehicleParameter> = restriction_query
+        let mut vehicle_parameters: Vec<VehicleParameter> = restriction_query
             .vehicle_parameters
             .into_iter()
             .map(|vpc| vpc.into())
             .collect();
+        if let Some(weight_per_axle) = derive_weight_per_axle(&vehicle_parameters) {
+            vehicle_parameters.push(weight_per_axle);
+        }
         let model = VehicleRestrictionConstraintModel {
             service,
             vehicle_parameters,
@@ -1,4 +1,5 @@
 use super::error::ConstraintModelError;
+use crate::algorithm::search::{Direction, SearchTree};
 use crate::model::{
     network::Edge,
     state::{StateModel, StateVariable},
@@ -17,6 +18,8 @@ pub trait ConstraintModel: Send + Sync {
     ///
     /// * `edge` - the edge to traverse
     /// * `previous_edge` - the edge traversed before this one, used to determine if this edge is a valid part of the frontier
+    /// * `tree` - the search tree built so far, for restrictions that depend on the path history
+    /// * `direction` - the direction of the search, which determines whether the tree is traced back toward the origin or the destination
     /// * `state` - the state of the traversal at the beginning of this edge
     /// * `state_model` - provides operations on the state vector
     ///
@@ -27,10 +30,33 @@ pub trait ConstraintModel: Send + Sync {
         &self,
         edge: &Edge,
         previous_edge: Option<&Edge>,
+        tree: &SearchTree,
+        direction: &Direction,
         state: &[StateVariable],
         state_model: &StateModel,
     ) -> Result<bool, ConstraintModelError>;
 
+    /// Validates the state that results from traversing an edge, before the traversal
+    /// is added to the search tree. By default, all traversals are valid.
+    ///
+    /// # Arguments
+    ///
+    /// * `edge` - the edge that was traversed
+    /// * `state` - the state of the traversal at the end of this edge
+    /// * `state_model` - provides operations on the state vector
+    ///
+    /// # Returns
+    ///
+    /// True if the traversal is valid, false otherwise
+    fn valid_traversal(
+        &self,
+        _edge: &Edge,
+        _state: &[StateVariable],
+        _state_model: &StateModel,
+    ) -> Result<bool, ConstraintModelError> {
+        Ok(true)
+    }
+
     /// Validates an edge independent of a search state, noting whether it
     /// is simply impassable with this ConstraintModel configuration. Can be
     /// called by valid_frontier as a cheaper first-pass operation. Also
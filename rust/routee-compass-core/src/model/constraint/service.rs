@@ -33,4 +33,12 @@ pub trait ConstraintModelService: Send + Sync {
         query: &serde_json::Value,
         state_model: Arc<StateModel>,
     ) -> Result<Arc<dyn ConstraintModel>, ConstraintModelError>;
+
+    /// Identifies the live data read by the models this service builds, such as the
+    /// closures of an incident feed, so that responses computed from earlier data are
+    /// not reused. Services whose models only read data loaded with the service return
+    /// None.
+    fn data_version(&self) -> Result<Option<String>, ConstraintModelError> {
+        Ok(None)
+    }
 }
@@ -1,13 +1,15 @@
 use super::{
-    cost_ops, network::NetworkCostRate, CostAggregation, CostFeature, TraversalCost,
-    VehicleCostRate,
+    cost_ops, network::NetworkCostRate, CostAggregation, CostFeature, EdgePenalty, EdgePreference,
+    MonetaryCost, ReferenceRoute, TraversalCost, VehicleCostRate,
 };
 use crate::algorithm::search::SearchTree;
 use crate::model::cost::CostModelError;
 use crate::model::network::Edge;
 use crate::model::network::Vertex;
+use crate::model::state::InputFeature;
 use crate::model::state::StateModel;
 use crate::model::state::StateVariable;
+use crate::model::unit::Cost;
 use indexmap::IndexMap;
 use itertools::Itertools;
 use serde_json::json;
@@ -17,12 +19,17 @@ use std::sync::Arc;
 /// implementation of a model for calculating Cost from a state transition.
 /// vectorized, where each index in these vectors matches the corresponding index
 /// in the state model.
+#[derive(Clone)]
 pub struct CostModel {
     features: IndexMap<String, CostFeature>,
     weights_mapping: Arc<HashMap<String, f64>>,
     vehicle_rate_mapping: Arc<HashMap<String, VehicleCostRate>>,
     network_rate_mapping: Arc<HashMap<String, NetworkCostRate>>,
     cost_aggregation: CostAggregation,
+    edge_preference: Option<EdgePreference>,
+    reference_route: Option<ReferenceRoute>,
+    edge_penalty: Option<EdgePenalty>,
+    monetary_cost: Option<MonetaryCost>,
 }
 
 impl CostModel {
@@ -58,6 +65,10 @@ impl CostModel {
             ));
         }
 
+        for rate in vehicle_rate_mapping.values() {
+            rate.validate()?;
+        }
+
         let mut features = IndexMap::new();
         let mut total_weight = 0.0;
 
@@ -88,9 +99,77 @@ impl CostModel {
             vehicle_rate_mapping,
             network_rate_mapping,
             cost_aggregation,
+            edge_preference: None,
+            reference_route: None,
+            edge_penalty: None,
+            monetary_cost: None,
         })
     }
 
+    /// records the monetary prices used to build this cost model's weights and
+    /// vehicle rates so that they are reported when serializing the cost model.
+    pub fn with_monetary_cost(mut self, monetary_cost: MonetaryCost) -> CostModel {
+        self.monetary_cost = Some(monetary_cost);
+        self
+    }
+
+    /// attaches a per-query edge preference to this cost model, which scales
+    /// the objective cost of traversing preferred edges.
+    pub fn with_edge_preference(mut self, edge_preference: EdgePreference) -> CostModel {
+        self.edge_preference = Some(edge_preference);
+        self
+    }
+
+    /// attaches a per-query reference route to this cost model, which penalizes
+    /// the objective cost of traversing edges that are not on the reference route.
+    pub fn with_reference_route(mut self, reference_route: ReferenceRoute) -> CostModel {
+        self.reference_route = Some(reference_route);
+        self
+    }
+
+    /// attaches penalties on the objective cost of traversing specific edges, replacing
+    /// any previous penalties.
+    pub fn with_edge_penalty(mut self, edge_penalty: EdgePenalty) -> CostModel {
+        self.edge_penalty = Some(edge_penalty);
+        self
+    }
+
+    /// builds a cost model with the same vehicle and network rates and cost aggregation
+    /// as this one but with new weights, such as a cost model that minimizes a single
+    /// feature. per-query edge preferences, reference routes, penalties and prices are not
+    /// carried over.
+    pub fn reweighted(
+        &self,
+        weights_mapping: HashMap<String, f64>,
+        state_model: Arc<StateModel>,
+    ) -> Result<CostModel, CostModelError> {
+        CostModel::new(
+            Arc::new(weights_mapping),
+            self.vehicle_rate_mapping.clone(),
+            self.network_rate_mapping.clone(),
+            self.cost_aggregation,
+            state_model,
+        )
+    }
+
+    /// the state variables read by the vehicle cost rates of this cost model, as the
+    /// types of their rates. rates for variables outside of the state model are unused
+    /// and are not listed.
+    pub fn input_features(&self) -> Vec<InputFeature> {
+        self.features
+            .keys()
+            .filter_map(|name| self.vehicle_rate_mapping.get(name)?.input_feature(name))
+            .collect()
+    }
+
+    /// the penalty multiplier on the objective cost of traversing this edge
+    pub fn edge_penalty_multiplier(&self, edge: &Edge) -> f64 {
+        self.edge_penalty
+            .as_ref()
+            .map(|p| p.multiplier(edge))
+            .unwrap_or(1.0)
+    }
+
     /// calculates the total trip cost of traversing the provided edge.
     ///
     /// For accumulator features, the cost is computed as the difference between
@@ -107,52 +186,38 @@ impl CostModel {
     ) -> Result<TraversalCost, CostModelError> {
         let mut result = TraversalCost::default();
         for (name, feature) in self.features.iter() {
-            let v_cost = if feature.is_accumulator {
-                let current_cost =
-                    feature
-                        .vehicle_cost_rate
-                        .compute_cost(name, current_state, state_model)?;
-                let previous_cost =
-                    feature
-                        .vehicle_cost_rate
-                        .compute_cost(name, previous_state, state_model)?;
-                current_cost - previous_cost
-            } else {
-                feature
-                    .vehicle_cost_rate
-                    .compute_cost(name, current_state, state_model)?
-            };
-
-            let n_cost = if feature.is_accumulator {
-                let current_network_cost = feature.network_cost_rate.network_cost(
-                    trajectory,
-                    current_state,
-                    tree,
-                    state_model,
-                )?;
-                let previous_network_cost = feature.network_cost_rate.network_cost(
-                    trajectory,
-                    previous_state,
-                    tree,
-                    state_model,
-                )?;
-                current_network_cost - previous_network_cost
-            } else {
-                feature.network_cost_rate.network_cost(
-                    trajectory,
-                    current_state,
-                    tree,
-                    state_model,
-                )?
-            };
-
-            let cost = v_cost + n_cost;
+            let cost = feature_traversal_cost(
+                feature,
+                trajectory,
+                previous_state,
+                current_state,
+                tree,
+                state_model,
+            )?;
             result.insert(name, cost, feature.weight);
         }
+        if let Some(edge_preference) = &self.edge_preference {
+            let (_, edge, _) = trajectory;
+            result.objective_cost *= edge_preference.multiplier(edge);
+        }
+        if let Some(reference_route) = &self.reference_route {
+            let (_, edge, _) = trajectory;
+            result.objective_cost *= reference_route.multiplier(edge);
+        }
+        if let Some(edge_penalty) = &self.edge_penalty {
+            let (_, edge, _) = trajectory;
+            result.objective_cost *= edge_penalty.multiplier(edge);
+        }
         Ok(result)
     }
 
     /// calculates the total trip cost of traversing the provided edge.
+    ///
+    /// features with a nonlinear [`VehicleCostRate::Transformed`] rate are estimated
+    /// at zero. the estimate is computed from the initial state rather than the state
+    /// of the label being extended, and the marginal cost of a transform depends on
+    /// the cost accumulated so far, so any nonzero estimate could exceed the remaining
+    /// cost and make the A* heuristic inadmissible.
     pub fn estimate_cost(
         &self,
         state: &[StateVariable],
@@ -160,11 +225,16 @@ impl CostModel {
     ) -> Result<TraversalCost, CostModelError> {
         let mut result = TraversalCost::default();
         for (name, feature) in self.features.iter() {
-            let v_cost = feature
-                .vehicle_cost_rate
-                .compute_cost(name, state, state_model)?;
+            let v_cost = match &feature.vehicle_cost_rate {
+                VehicleCostRate::Transformed { .. } => Cost::ZERO,
+                rate => rate.compute_cost(name, state, state_model)?,
+            };
             result.insert(name, v_cost, feature.weight);
         }
+        // preferred edges may cost less than the unscaled estimate
+        if let Some(edge_preference) = &self.edge_preference {
+            result.objective_cost *= edge_preference.min_multiplier();
+        }
         Ok(result)
     }
 
@@ -204,6 +274,20 @@ impl CostModel {
             json![self.cost_aggregation],
         );
 
+        if let Some(monetary_cost) = &self.monetary_cost {
+            result.insert(Self::MONETARY_COST.to_string(), json![monetary_cost]);
+        }
+
+        if let Some(reference_route) = &self.reference_route {
+            result.insert(
+                Self::REFERENCE_ROUTE.to_string(),
+                json![{
+                    "edge_count": reference_route.edges.len(),
+                    "deviation_penalty": reference_route.deviation_penalty,
+                }],
+            );
+        }
+
         Ok(json![result])
     }
 
@@ -213,12 +297,65 @@ impl CostModel {
     const WEIGHT: &'static str = "weight";
     const COST_AGGREGATION: &'static str = "cost_aggregation";
     const DESCRIPTION: &'static str = "description";
+    const MONETARY_COST: &'static str = "monetary_cost";
+    const REFERENCE_ROUTE: &'static str = "reference_route";
+}
+
+/// computes the cost of a single feature for an edge traversal, prior to weighting.
+///
+/// For accumulator features, the cost is computed as the difference between
+/// the current and previous state (delta). For non-accumulator features,
+/// the cost is computed directly from the current state value.
+fn feature_traversal_cost(
+    feature: &CostFeature,
+    trajectory: (&Vertex, &Edge, &Vertex),
+    previous_state: &[StateVariable],
+    current_state: &[StateVariable],
+    tree: &SearchTree,
+    state_model: &StateModel,
+) -> Result<Cost, CostModelError> {
+    let v_cost = if feature.is_accumulator {
+        let current_cost =
+            feature
+                .vehicle_cost_rate
+                .compute_cost(&feature.name, current_state, state_model)?;
+        let previous_cost =
+            feature
+                .vehicle_cost_rate
+                .compute_cost(&feature.name, previous_state, state_model)?;
+        current_cost - previous_cost
+    } else {
+        feature
+            .vehicle_cost_rate
+            .compute_cost(&feature.name, current_state, state_model)?
+    };
+
+    let n_cost = if feature.is_accumulator {
+        let current_network_cost =
+            feature
+                .network_cost_rate
+                .network_cost(trajectory, current_state, tree, state_model)?;
+        let previous_network_cost = feature.network_cost_rate.network_cost(
+            trajectory,
+            previous_state,
+            tree,
+            state_model,
+        )?;
+        current_network_cost - previous_network_cost
+    } else {
+        feature
+            .network_cost_rate
+            .network_cost(trajectory, current_state, tree, state_model)?
+    };
+
+    Ok(v_cost + n_cost)
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
     use crate::algorithm::search::Direction;
+    use crate::model::cost::CostTransform;
     use crate::model::network::{EdgeId, EdgeListId, VertexId};
     use crate::model::state::StateVariableConfig;
     use crate::model::unit::{AsF64, Cost, DistanceUnit, TimeUnit};
@@ -737,6 +874,49 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_estimate_cost_transformed_rate_is_zero() {
+        let features = vec![(
+            "distance".to_string(),
+            StateVariableConfig::Distance {
+                initial: Length::new::<meter>(0.0),
+                accumulator: true,
+                output_unit: Some(DistanceUnit::Meters),
+            },
+        )];
+        let state_model = Arc::new(StateModel::new(features));
+        let weights = Arc::new(HashMap::from([("distance".to_string(), 1.0)]));
+        // a concave transform, which costs less per meter as a trip gets longer
+        let vehicle_rates = Arc::new(HashMap::from([(
+            "distance".to_string(),
+            VehicleCostRate::Transformed {
+                rate: Box::new(VehicleCostRate::Distance {
+                    factor: 1.0,
+                    unit: DistanceUnit::Meters,
+                }),
+                transform: CostTransform::PiecewiseLinear {
+                    breakpoints: vec![(0.0, 0.0), (100.0, 100.0), (200.0, 110.0)],
+                },
+            },
+        )]));
+        let cost_model = CostModel::new(
+            weights,
+            vehicle_rates,
+            Arc::new(HashMap::new()),
+            CostAggregation::Sum,
+            state_model.clone(),
+        )
+        .expect("Failed to create cost model");
+
+        // 100 meters remain, which would be estimated at 100 from the initial state but
+        // only cost 10 after the first 100 meters of a trip
+        let result = cost_model
+            .estimate_cost(&[StateVariable(100.0)], &state_model)
+            .expect("Failed to estimate cost");
+        // costs are clamped to a small positive value rather than zero
+        assert!(result.objective_cost.as_f64() < 1e-6);
+    }
+
     #[test]
     fn test_zero_weight_feature_has_no_objective_cost() {
         // Setup: Create a state model with distance
@@ -957,4 +1137,148 @@ mod test {
             result_acc.total_cost.as_f64() * 3.0
         );
     }
+
+    #[test]
+    fn test_traversal_cost_edge_preference_scales_objective() {
+        let features = vec![(
+            "distance".to_string(),
+            StateVariableConfig::Distance {
+                initial: Length::new::<meter>(0.0),
+                accumulator: true,
+                output_unit: Some(DistanceUnit::Meters),
+            },
+        )];
+        let state_model = Arc::new(StateModel::new(features));
+        let weights = Arc::new(HashMap::from([("distance".to_string(), 1.0)]));
+        let vehicle_rates = Arc::new(HashMap::from([(
+            "distance".to_string(),
+            VehicleCostRate::Distance {
+                factor: 1.0,
+                unit: DistanceUnit::Meters,
+            },
+        )]));
+        let cost_model = CostModel::new(
+            weights,
+            vehicle_rates,
+            Arc::new(HashMap::new()),
+            CostAggregation::Sum,
+            state_model.clone(),
+        )
+        .expect("Failed to create cost model")
+        .with_edge_preference(EdgePreference {
+            edges: [EdgeId(0)].into_iter().collect(),
+            road_classes: Default::default(),
+            road_class_by_edge: None,
+            factor: 0.5,
+        });
+
+        let previous_state = vec![StateVariable(0.0)];
+        let current_state = vec![StateVariable(100.0)];
+        let v1 = create_vertex(VertexId(0));
+        let v2 = create_vertex(VertexId(1));
+        let preferred = create_edge(EdgeId(0), VertexId(0), VertexId(1));
+        let other = create_edge(EdgeId(1), VertexId(0), VertexId(1));
+        let tree = create_test_tree();
+
+        let preferred_cost = cost_model
+            .traversal_cost(
+                (&v1, &preferred, &v2),
+                &previous_state,
+                &current_state,
+                &tree,
+                &state_model,
+            )
+            .expect("Failed to compute traversal cost");
+        let other_cost = cost_model
+            .traversal_cost(
+                (&v1, &other, &v2),
+                &previous_state,
+                &current_state,
+                &tree,
+                &state_model,
+            )
+            .expect("Failed to compute traversal cost");
+
+        assert_eq!(preferred_cost.total_cost, other_cost.total_cost);
+        assert_eq!(
+            preferred_cost.objective_cost.as_f64(),
+            other_cost.objective_cost.as_f64() * 0.5
+        );
+
+        // the estimate is scaled by the factor, so that it does not exceed the cost
+        // of a route on preferred edges
+        let estimate = cost_model
+            .estimate_cost(&current_state, &state_model)
+            .expect("Failed to estimate cost");
+        assert!(estimate.objective_cost <= preferred_cost.objective_cost);
+        assert_eq!(estimate.total_cost, other_cost.total_cost);
+    }
+
+    #[test]
+    fn test_traversal_cost_reference_route_penalizes_deviation() {
+        let features = vec![(
+            "distance".to_string(),
+            StateVariableConfig::Distance {
+                initial: Length::new::<meter>(0.0),
+                accumulator: true,
+                output_unit: Some(DistanceUnit::Meters),
+            },
+        )];
+        let state_model = Arc::new(StateModel::new(features));
+        let weights = Arc::new(HashMap::from([("distance".to_string(), 1.0)]));
+        let vehicle_rates = Arc::new(HashMap::from([(
+            "distance".to_string(),
+            VehicleCostRate::Distance {
+                factor: 1.0,
+                unit: DistanceUnit::Meters,
+            },
+        )]));
+        let cost_model = CostModel::new(
+            weights,
+            vehicle_rates,
+            Arc::new(HashMap::new()),
+            CostAggregation::Sum,
+            state_model.clone(),
+        )
+        .expect("Failed to create cost model")
+        .with_reference_route(ReferenceRoute {
+            edges: [EdgeId(0)].into_iter().collect(),
+            deviation_penalty: 0.25,
+        });
+
+        let previous_state = vec![StateVariable(0.0)];
+        let current_state = vec![StateVariable(100.0)];
+        let v1 = create_vertex(VertexId(0));
+        let v2 = create_vertex(VertexId(1));
+        let on_route = create_edge(EdgeId(0), VertexId(0), VertexId(1));
+        let off_route = create_edge(EdgeId(1), VertexId(0), VertexId(1));
+        let tree = create_test_tree();
+
+        let on_route_cost = cost_model
+            .traversal_cost(
+                (&v1, &on_route, &v2),
+                &previous_state,
+                &current_state,
+                &tree,
+                &state_model,
+            )
+            .expect("Failed to compute traversal cost");
+        let off_route_cost = cost_model
+            .traversal_cost(
+                (&v1, &off_route, &v2),
+                &previous_state,
+                &current_state,
+                &tree,
+                &state_model,
+            )
+            .expect("Failed to compute traversal cost");
+
+        assert_eq!(on_route_cost.total_cost, off_route_cost.total_cost);
+        assert_eq!(
+            off_route_cost.objective_cost.as_f64(),
+            on_route_cost.objective_cost.as_f64() * 1.25
+        );
+        let info = cost_model.serialize_cost_info().unwrap();
+        assert_eq!(info["reference_route"]["edge_count"], json!(1));
+    }
 }
@@ -15,6 +15,9 @@ pub struct CostModelConfig {
     pub weights: Option<HashMap<String, f64>>,
     pub cost_aggregation: Option<CostAggregation>,
     pub ignore_unknown_user_provided_weights: Option<bool>,
+    /// optional file with one road class label per edge, enabling queries
+    /// to provide `prefer_road_classes`.
+    pub road_class_input_file: Option<String>,
 }
 
 impl CostModelConfig {
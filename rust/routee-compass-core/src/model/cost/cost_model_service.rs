@@ -1,11 +1,16 @@
 use crate::config::{CompassConfigurationError, ConfigJsonExtensions};
-use crate::model::cost::{CostModelConfig, CostModelError};
+use crate::model::constraint::default::road_class::road_class_ops;
+use crate::model::cost::{
+    CostModelConfig, CostModelError, EdgePreference, MonetaryCost, ReferenceRoute,
+};
 use crate::model::{
     cost::{network::NetworkCostRate, CostAggregation, CostModel, VehicleCostRate},
+    network::EdgeId,
     state::StateModel,
 };
 use std::{
     collections::{HashMap, HashSet},
+    path::PathBuf,
     sync::Arc,
 };
 
@@ -15,6 +20,8 @@ pub struct CostModelService {
     pub weights: Arc<HashMap<String, f64>>,
     pub cost_aggregation: CostAggregation,
     pub ignore_unknown_weights: bool,
+    pub road_class_by_edge: Option<Arc<Box<[u8]>>>,
+    pub road_class_mapping: Arc<HashMap<String, u8>>,
 }
 
 impl CostModelService {
@@ -26,7 +33,13 @@ impl CostModelService {
     /// ```python
     /// {
     ///   "state_variable_names": [],  # list of state variables to convert to costs
-    ///   "cost_aggregation": ''     # operation for combining costs, 'sum' or 'mul'
+    ///   "cost_aggregation": '',    # operation for combining costs, 'sum' or 'mul'
+    ///   "prefer_edges": [],        # optional edge ids whose objective cost is scaled by prefer_factor
+    ///   "prefer_road_classes": [], # optional road classes whose objective cost is scaled by prefer_factor
+    ///   "prefer_factor": 0.5,      # optional multiplier for preferred edges, must be positive
+    ///   "reference_edges": [],     # optional edge ids of a reference route to stay close to
+    ///   "reference_deviation_penalty": 0.25, # optional objective cost penalty for edges off the reference route
+    ///   "monetary_cost": {}        # optional prices converting time, energy and tolls into currency
     /// }
     /// ```
     ///
@@ -60,6 +73,18 @@ impl CostModelService {
             })
             .unwrap_or(self.weights.clone());
 
+        // monetary prices replace the weights and vehicle rates of the features they price
+        let monetary_cost: Option<MonetaryCost> =
+            query.get_config_serde_optional(&"monetary_cost", &"cost_model")?;
+        let weights = match &monetary_cost {
+            Some(monetary_cost) => {
+                let mut merged_weights = weights.as_ref().clone();
+                merged_weights.extend(monetary_cost.weights());
+                Arc::new(merged_weights)
+            }
+            None => weights,
+        };
+
         // // union the requested state variables with those in the existing traversal model
         // // load only indices that appear in coefficients object
         let state_indices = state_model.to_vec();
@@ -103,6 +128,14 @@ impl CostModelService {
                 }
                 None => self.vehicle_rates.clone(),
             })?;
+        let vehicle_rates = match &monetary_cost {
+            Some(monetary_cost) => {
+                let mut merged_rates = vehicle_rates.as_ref().clone();
+                merged_rates.extend(monetary_cost.vehicle_rates());
+                Arc::new(merged_rates)
+            }
+            None => vehicle_rates,
+        };
 
         let cost_aggregation: CostAggregation = query
             .get_config_serde_optional(&"cost_aggregation", &"cost_model")?
@@ -121,22 +154,149 @@ impl CostModelService {
             ))
         })?;
 
-        Ok(model)
+        let model = match monetary_cost {
+            Some(monetary_cost) => model.with_monetary_cost(monetary_cost),
+            None => model,
+        };
+
+        let model = match self.build_edge_preference(query)? {
+            Some(edge_preference) => model.with_edge_preference(edge_preference),
+            None => model,
+        };
+
+        match build_reference_route(query)? {
+            Some(reference_route) => Ok(model.with_reference_route(reference_route)),
+            None => Ok(model),
+        }
+    }
+
+    /// reads the optional `prefer_edges`, `prefer_road_classes` and `prefer_factor`
+    /// query keys. returns None when no preferred edges are provided.
+    fn build_edge_preference(
+        &self,
+        query: &serde_json::Value,
+    ) -> Result<Option<EdgePreference>, CompassConfigurationError> {
+        let edges: HashSet<EdgeId> = query
+            .get_config_serde_optional(&"prefer_edges", &"cost_model")?
+            .unwrap_or_default();
+
+        let road_classes: HashSet<u8> = match query.get("prefer_road_classes") {
+            None => HashSet::new(),
+            Some(_) if self.road_class_by_edge.is_none() => {
+                return Err(CompassConfigurationError::UserConfigurationError(String::from(
+                    "query has 'prefer_road_classes' but the cost model was not configured with a road_class_input_file",
+                )));
+            }
+            Some(value) => {
+                road_class_ops::read_road_classes_from_query("prefer_road_classes", value)
+                    .and_then(|rc| {
+                        road_class_ops::encode_road_classes(&rc, &self.road_class_mapping)
+                    })
+                    .map_err(|e| CompassConfigurationError::UserConfigurationError(e.to_string()))?
+            }
+        };
+
+        if edges.is_empty() && road_classes.is_empty() {
+            return Ok(None);
+        }
+
+        let factor: f64 = query
+            .get_config_serde_optional(&"prefer_factor", &"cost_model")?
+            .unwrap_or(EdgePreference::DEFAULT_FACTOR);
+        if !(factor.is_finite() && factor > 0.0) {
+            return Err(CompassConfigurationError::UserConfigurationError(format!(
+                "query 'prefer_factor' must be a positive number, found {factor}"
+            )));
+        }
+
+        Ok(Some(EdgePreference {
+            edges,
+            road_classes,
+            road_class_by_edge: self.road_class_by_edge.clone(),
+            factor,
+        }))
+    }
+}
+
+/// reads the optional `reference_edges` and `reference_deviation_penalty` query keys.
+/// returns None when no reference route is provided.
+fn build_reference_route(
+    query: &serde_json::Value,
+) -> Result<Option<ReferenceRoute>, CompassConfigurationError> {
+    let edges: Option<HashSet<EdgeId>> =
+        query.get_config_serde_optional(&"reference_edges", &"cost_model")?;
+    let Some(edges) = edges else {
+        return Ok(None);
+    };
+    let deviation_penalty: f64 = query
+        .get_config_serde_optional(&"reference_deviation_penalty", &"cost_model")?
+        .unwrap_or(ReferenceRoute::DEFAULT_DEVIATION_PENALTY);
+    if !(deviation_penalty.is_finite() && deviation_penalty >= 0.0) {
+        return Err(CompassConfigurationError::UserConfigurationError(format!(
+            "query 'reference_deviation_penalty' must be a non-negative number, found {deviation_penalty}"
+        )));
     }
+    Ok(Some(ReferenceRoute {
+        edges,
+        deviation_penalty,
+    }))
 }
 
 impl TryFrom<&CostModelConfig> for CostModelService {
     fn try_from(value: &CostModelConfig) -> Result<Self, Self::Error> {
         let network_rates = value.get_network_rates()?;
+        let (road_class_by_edge, road_class_mapping) = match &value.road_class_input_file {
+            None => (None, HashMap::new()),
+            Some(file) => {
+                let (encoded, mapping) = road_class_ops::read_road_class_file(&PathBuf::from(file))
+                    .map_err(|e| CostModelError::BuildError(e.to_string()))?;
+                (Some(Arc::new(encoded)), mapping)
+            }
+        };
         let service = CostModelService {
             vehicle_rates: Arc::new(value.vehicle_rates.clone().unwrap_or_default()),
             network_rates: Arc::new(network_rates),
             weights: Arc::new(value.weights.clone().unwrap_or_default()),
             cost_aggregation: value.cost_aggregation.unwrap_or_default(),
             ignore_unknown_weights: value.ignore_unknown_user_provided_weights.unwrap_or(true),
+            road_class_by_edge,
+            road_class_mapping: Arc::new(road_class_mapping),
         };
         Ok(service)
     }
 
     type Error = CostModelError;
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    fn mock_service() -> CostModelService {
+        CostModelService {
+            vehicle_rates: Arc::new(HashMap::new()),
+            network_rates: Arc::new(HashMap::new()),
+            weights: Arc::new(HashMap::new()),
+            cost_aggregation: CostAggregation::Sum,
+            ignore_unknown_weights: true,
+            road_class_by_edge: None,
+            road_class_mapping: Arc::new(HashMap::new()),
+        }
+    }
+
+    #[test]
+    fn test_prefer_factor_must_be_positive() {
+        let service = mock_service();
+        let query =
+            |factor: serde_json::Value| json!({"prefer_edges": [0], "prefer_factor": factor});
+        let preference = service
+            .build_edge_preference(&query(json!(0.25)))
+            .unwrap()
+            .unwrap();
+        assert_eq!(preference.min_multiplier(), 0.25);
+        assert!(service.build_edge_preference(&query(json!(0.0))).is_err());
+        assert!(service.build_edge_preference(&query(json!(-1.0))).is_err());
+        assert!(service.build_edge_preference(&query(json!("inf"))).is_err());
+    }
+}
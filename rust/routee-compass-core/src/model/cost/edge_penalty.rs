@@ -0,0 +1,25 @@
+use crate::model::network::{Edge, EdgeId, EdgeListId};
+use std::collections::HashMap;
+
+/// multipliers on the objective cost of traversing specific edges, used to steer
+/// repeated searches away from the routes they have already found. the total cost
+/// is unchanged.
+#[derive(Clone, Debug, Default)]
+pub struct EdgePenalty {
+    pub factors: HashMap<(EdgeListId, EdgeId), f64>,
+}
+
+impl EdgePenalty {
+    /// multiplies the penalty of an edge by a factor
+    pub fn penalize(&mut self, edge_list_id: EdgeListId, edge_id: EdgeId, factor: f64) {
+        *self.factors.entry((edge_list_id, edge_id)).or_insert(1.0) *= factor;
+    }
+
+    /// the multiplier to apply to the objective cost of traversing this edge
+    pub fn multiplier(&self, edge: &Edge) -> f64 {
+        self.factors
+            .get(&(edge.edge_list_id, edge.edge_id))
+            .copied()
+            .unwrap_or(1.0)
+    }
+}
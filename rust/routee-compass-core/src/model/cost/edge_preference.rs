@@ -0,0 +1,48 @@
+use crate::model::network::{Edge, EdgeId};
+use std::{collections::HashSet, sync::Arc};
+
+/// a per-query set of preferred edges, provided either as explicit edge ids
+/// or as road class labels. the objective cost of traversing a preferred edge
+/// is scaled by the preference factor, leaving the total cost unchanged.
+///
+/// a factor below 1.0 makes preferred edges cheaper than the cost estimates used by
+/// a-star assume, so estimates are scaled by [`EdgePreference::min_multiplier`] to
+/// remain a lower bound on the remaining objective cost.
+#[derive(Clone, Debug)]
+pub struct EdgePreference {
+    pub edges: HashSet<EdgeId>,
+    pub road_classes: HashSet<u8>,
+    pub road_class_by_edge: Option<Arc<Box<[u8]>>>,
+    pub factor: f64,
+}
+
+impl EdgePreference {
+    pub const DEFAULT_FACTOR: f64 = 0.5;
+
+    /// the multiplier to apply to the objective cost of traversing this edge
+    pub fn multiplier(&self, edge: &Edge) -> f64 {
+        if self.is_preferred(edge) {
+            self.factor
+        } else {
+            1.0
+        }
+    }
+
+    /// the smallest multiplier applied to the objective cost of any edge
+    pub fn min_multiplier(&self) -> f64 {
+        self.factor.min(1.0)
+    }
+
+    fn is_preferred(&self, edge: &Edge) -> bool {
+        if self.edges.contains(&edge.edge_id) {
+            return true;
+        }
+        match &self.road_class_by_edge {
+            Some(road_class_by_edge) if !self.road_classes.is_empty() => road_class_by_edge
+                .get(edge.edge_id.0)
+                .map(|rc| self.road_classes.contains(rc))
+                .unwrap_or(false),
+            _ => false,
+        }
+    }
+}
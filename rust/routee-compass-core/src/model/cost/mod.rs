@@ -5,7 +5,11 @@ mod cost_model_config;
 mod cost_model_error;
 pub mod cost_model_service;
 pub mod cost_ops;
+mod edge_penalty;
+mod edge_preference;
+mod monetary_cost;
 pub mod network;
+mod reference_route;
 pub mod traversal_cost;
 mod vehicle;
 
@@ -14,5 +18,10 @@ pub use cost_feature::CostFeature;
 pub use cost_model::CostModel;
 pub use cost_model_config::CostModelConfig;
 pub use cost_model_error::CostModelError;
+pub use edge_penalty::EdgePenalty;
+pub use edge_preference::EdgePreference;
+pub use monetary_cost::{EnergyPrice, MonetaryCost};
+pub use reference_route::ReferenceRoute;
 pub use traversal_cost::TraversalCost;
+pub use vehicle::cost_transform::CostTransform;
 pub use vehicle::vehicle_cost_rate::VehicleCostRate;
@@ -0,0 +1,118 @@
+use crate::model::{
+    cost::VehicleCostRate,
+    unit::{EnergyUnit, TimeUnit},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// query-supplied prices which convert time, energy and toll state features into
+/// a common monetary cost. each priced feature is given a weight of 1.0 so that the
+/// objective is expressed in currency units. features that are not priced keep their
+/// configured weights and rates.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct MonetaryCost {
+    /// currency per `time_unit` applied to each of the `time_features`
+    pub value_of_time: Option<f64>,
+    /// time unit of the value of time, defaults to hours
+    pub time_unit: Option<TimeUnit>,
+    /// time features to price with the value of time, defaults to `trip_time`
+    pub time_features: Option<Vec<String>>,
+    /// by energy feature name, the price of energy in currency per unit
+    pub energy_prices: Option<HashMap<String, EnergyPrice>>,
+    /// features whose state values are already expressed in currency, such as tolls
+    pub toll_features: Option<Vec<String>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct EnergyPrice {
+    pub price: f64,
+    pub unit: EnergyUnit,
+}
+
+impl MonetaryCost {
+    pub const DEFAULT_TIME_FEATURE: &'static str = "trip_time";
+
+    /// creates a vehicle cost rate for each priced feature.
+    pub fn vehicle_rates(&self) -> HashMap<String, VehicleCostRate> {
+        let mut rates = HashMap::new();
+        if let Some(value_of_time) = self.value_of_time {
+            let unit = self.time_unit.unwrap_or(TimeUnit::Hours);
+            for name in self.get_time_features() {
+                let rate = VehicleCostRate::Time {
+                    factor: value_of_time,
+                    unit,
+                };
+                rates.insert(name, rate);
+            }
+        }
+        for (name, energy_price) in self.energy_prices.iter().flatten() {
+            let rate = VehicleCostRate::Energy {
+                factor: energy_price.price,
+                unit: energy_price.unit,
+            };
+            rates.insert(name.clone(), rate);
+        }
+        for name in self.toll_features.iter().flatten() {
+            rates.insert(name.clone(), VehicleCostRate::Raw);
+        }
+        rates
+    }
+
+    /// creates a unit weight for each priced feature.
+    pub fn weights(&self) -> HashMap<String, f64> {
+        self.vehicle_rates()
+            .into_keys()
+            .map(|name| (name, 1.0))
+            .collect()
+    }
+
+    fn get_time_features(&self) -> Vec<String> {
+        self.time_features
+            .clone()
+            .unwrap_or_else(|| vec![String::from(Self::DEFAULT_TIME_FEATURE)])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_vehicle_rates() {
+        let monetary: MonetaryCost = serde_json::from_value(json!({
+            "value_of_time": 20.0,
+            "energy_prices": {
+                "trip_energy_electric": { "price": 0.5, "unit": "kwh" }
+            },
+            "toll_features": ["trip_toll"]
+        }))
+        .expect("should deserialize");
+        let rates = monetary.vehicle_rates();
+        assert!(matches!(
+            rates.get("trip_time"),
+            Some(VehicleCostRate::Time {
+                factor: 20.0,
+                unit: TimeUnit::Hours
+            })
+        ));
+        assert!(matches!(
+            rates.get("trip_energy_electric"),
+            Some(VehicleCostRate::Energy { factor: 0.5, .. })
+        ));
+        assert!(matches!(rates.get("trip_toll"), Some(VehicleCostRate::Raw)));
+        assert_eq!(monetary.weights().len(), 3);
+    }
+
+    #[test]
+    fn test_no_value_of_time() {
+        let monetary: MonetaryCost =
+            serde_json::from_value(json!({"toll_features": ["trip_toll"]}))
+                .expect("should deserialize");
+        let rates = monetary.vehicle_rates();
+        assert!(!rates.contains_key("trip_time"));
+        assert_eq!(rates.len(), 1);
+    }
+}
@@ -0,0 +1,29 @@
+use crate::model::network::{Edge, EdgeId};
+use std::collections::HashSet;
+
+/// a per-query reference route, such as a route taken on a previous day, given as
+/// a set of edge ids. the objective cost of traversing an edge that is not on the
+/// reference route is increased by the deviation penalty, leaving the total cost
+/// unchanged, so that a search only deviates from the reference route when doing
+/// so saves more than the penalty.
+///
+/// since the penalty only increases costs, the cost estimate used by a-star remains
+/// a lower bound.
+#[derive(Clone, Debug)]
+pub struct ReferenceRoute {
+    pub edges: HashSet<EdgeId>,
+    pub deviation_penalty: f64,
+}
+
+impl ReferenceRoute {
+    pub const DEFAULT_DEVIATION_PENALTY: f64 = 0.25;
+
+    /// the multiplier to apply to the objective cost of traversing this edge
+    pub fn multiplier(&self, edge: &Edge) -> f64 {
+        if self.edges.contains(&edge.edge_id) {
+            1.0
+        } else {
+            1.0 + self.deviation_penalty
+        }
+    }
+}
@@ -0,0 +1,215 @@
+use crate::model::cost::CostModelError;
+use serde::{Deserialize, Serialize};
+
+/// a nonlinear function applied to a vehicle cost value. for accumulated state
+/// features, the cost model takes the difference of the transformed values before
+/// and after an edge traversal, so a convex transform yields increasing marginal
+/// cost over the course of a trip. transforms must be non-decreasing over
+/// non-negative costs so that traversal costs remain positive, which
+/// [`CostTransform::validate`] enforces.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CostTransform {
+    /// linear interpolation between (x, y) breakpoints, sorted by x. values outside
+    /// of the breakpoint domain are extrapolated using the slope of the nearest segment.
+    PiecewiseLinear { breakpoints: Vec<(f64, f64)> },
+    /// polynomial with coefficients in increasing order of degree, such that
+    /// `[c0, c1, c2]` computes `c0 + c1 * x + c2 * x^2`.
+    Polynomial { coefficients: Vec<f64> },
+    /// logistic curve `max / (1 + exp(-steepness * (x - midpoint)))`.
+    Logistic {
+        max: f64,
+        midpoint: f64,
+        steepness: f64,
+    },
+}
+
+impl CostTransform {
+    /// applies this transform to a cost value.
+    pub fn apply(&self, x: f64) -> f64 {
+        match self {
+            CostTransform::PiecewiseLinear { breakpoints } => interpolate(breakpoints, x),
+            CostTransform::Polynomial { coefficients } => coefficients
+                .iter()
+                .rev()
+                .fold(0.0, |acc, coefficient| acc * x + coefficient),
+            CostTransform::Logistic {
+                max,
+                midpoint,
+                steepness,
+            } => max / (1.0 + (-steepness * (x - midpoint)).exp()),
+        }
+    }
+
+    /// confirms the transform parameters describe a valid function.
+    pub fn validate(&self) -> Result<(), CostModelError> {
+        match self {
+            CostTransform::PiecewiseLinear { breakpoints } => {
+                if breakpoints.len() < 2 {
+                    return Err(CostModelError::InvalidConfiguration(format!(
+                        "piecewise linear transform requires at least 2 breakpoints, found {}",
+                        breakpoints.len()
+                    )));
+                }
+                let sorted = breakpoints.windows(2).all(|w| w[0].0 < w[1].0);
+                if !sorted {
+                    return Err(CostModelError::InvalidConfiguration(String::from(
+                        "piecewise linear transform breakpoints must be sorted by strictly increasing x value",
+                    )));
+                }
+                let non_decreasing = breakpoints.windows(2).all(|w| w[0].1 <= w[1].1);
+                if !non_decreasing {
+                    return Err(CostModelError::InvalidConfiguration(String::from(
+                        "piecewise linear transform must be non-decreasing, but a breakpoint y value is less than the one before it",
+                    )));
+                }
+                Ok(())
+            }
+            CostTransform::Polynomial { coefficients } if coefficients.is_empty() => {
+                Err(CostModelError::InvalidConfiguration(String::from(
+                    "polynomial transform requires at least one coefficient",
+                )))
+            }
+            // non-negative coefficients of degree 1 and up make the polynomial
+            // non-decreasing for non-negative costs. this rejects some non-decreasing
+            // polynomials, but testing for them in general requires finding roots.
+            CostTransform::Polynomial { coefficients } => {
+                match coefficients.iter().skip(1).position(|c| *c < 0.0) {
+                    Some(idx) => Err(CostModelError::InvalidConfiguration(format!(
+                        "polynomial transform coefficients of degree 1 and up must be non-negative so that the transform is non-decreasing, found {} for degree {}",
+                        coefficients[idx + 1],
+                        idx + 1
+                    ))),
+                    None => Ok(()),
+                }
+            }
+            CostTransform::Logistic { max, .. } if *max <= 0.0 => {
+                Err(CostModelError::InvalidConfiguration(format!(
+                    "logistic transform max must be positive, found {max}"
+                )))
+            }
+            CostTransform::Logistic { steepness, .. } if *steepness < 0.0 => {
+                Err(CostModelError::InvalidConfiguration(format!(
+                    "logistic transform steepness must be non-negative so that the transform is non-decreasing, found {steepness}"
+                )))
+            }
+            CostTransform::Logistic { .. } => Ok(()),
+        }
+    }
+}
+
+impl std::fmt::Display for CostTransform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CostTransform::PiecewiseLinear { breakpoints } => {
+                write!(f, "piecewise linear with {} breakpoints", breakpoints.len())
+            }
+            CostTransform::Polynomial { coefficients } => {
+                write!(
+                    f,
+                    "polynomial of degree {}",
+                    coefficients.len().saturating_sub(1)
+                )
+            }
+            CostTransform::Logistic {
+                max,
+                midpoint,
+                steepness,
+            } => write!(
+                f,
+                "logistic with max {max}, midpoint {midpoint} and steepness {steepness}"
+            ),
+        }
+    }
+}
+
+/// interpolates along a sorted set of breakpoints, assumed to have at least 2 entries.
+fn interpolate(breakpoints: &[(f64, f64)], x: f64) -> f64 {
+    let idx = breakpoints
+        .partition_point(|(bx, _)| *bx < x)
+        .clamp(1, breakpoints.len() - 1);
+    let (x0, y0) = breakpoints[idx - 1];
+    let (x1, y1) = breakpoints[idx];
+    y0 + (x - x0) * (y1 - y0) / (x1 - x0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::CostTransform;
+
+    #[test]
+    fn test_piecewise_linear() {
+        let transform = CostTransform::PiecewiseLinear {
+            breakpoints: vec![(0.0, 0.0), (10.0, 10.0), (20.0, 30.0)],
+        };
+        assert!(transform.validate().is_ok());
+        assert_eq!(transform.apply(5.0), 5.0);
+        assert_eq!(transform.apply(10.0), 10.0);
+        assert_eq!(transform.apply(15.0), 20.0);
+        // extrapolates from the last segment
+        assert_eq!(transform.apply(25.0), 40.0);
+        // extrapolates from the first segment
+        assert_eq!(transform.apply(-5.0), -5.0);
+    }
+
+    #[test]
+    fn test_piecewise_linear_unsorted_invalid() {
+        let transform = CostTransform::PiecewiseLinear {
+            breakpoints: vec![(10.0, 0.0), (0.0, 10.0)],
+        };
+        assert!(transform.validate().is_err());
+    }
+
+    #[test]
+    fn test_decreasing_transforms_invalid() {
+        let piecewise = CostTransform::PiecewiseLinear {
+            breakpoints: vec![(0.0, 10.0), (10.0, 5.0)],
+        };
+        assert!(piecewise.validate().is_err());
+        let polynomial = CostTransform::Polynomial {
+            coefficients: vec![100.0, -1.0],
+        };
+        assert!(polynomial.validate().is_err());
+        // a negative constant term only shifts the transform
+        let shifted = CostTransform::Polynomial {
+            coefficients: vec![-1.0, 1.0, 0.5],
+        };
+        assert!(shifted.validate().is_ok());
+        let logistic = CostTransform::Logistic {
+            max: 10.0,
+            midpoint: 5.0,
+            steepness: -1.0,
+        };
+        assert!(logistic.validate().is_err());
+    }
+
+    #[test]
+    fn test_polynomial() {
+        let transform = CostTransform::Polynomial {
+            coefficients: vec![1.0, 2.0, 3.0],
+        };
+        assert_eq!(transform.apply(2.0), 1.0 + 4.0 + 12.0);
+    }
+
+    #[test]
+    fn test_logistic() {
+        let transform = CostTransform::Logistic {
+            max: 10.0,
+            midpoint: 5.0,
+            steepness: 1.0,
+        };
+        assert_eq!(transform.apply(5.0), 5.0);
+        assert!(transform.apply(100.0) > 9.99);
+        assert!(transform.apply(-100.0) < 0.01);
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let transform: CostTransform = serde_json::from_value(serde_json::json!({
+            "type": "polynomial",
+            "coefficients": [0.0, 1.0, 0.01]
+        }))
+        .expect("should deserialize");
+        assert!(matches!(transform, CostTransform::Polynomial { .. }));
+    }
+}
@@ -1 +1,2 @@
+pub mod cost_transform;
 pub mod vehicle_cost_rate;
@@ -1,7 +1,9 @@
 use crate::model::{
-    cost::CostModelError,
-    state::{CustomVariableType, StateModel, StateVariable},
-    unit::{Cost, DistanceUnit, EnergyUnit, RatioUnit, SpeedUnit, TemperatureUnit, TimeUnit},
+    cost::{CostModelError, CostTransform},
+    state::{CustomVariableType, InputFeature, StateModel, StateVariable},
+    unit::{
+        AsF64, Cost, DistanceUnit, EnergyUnit, RatioUnit, SpeedUnit, TemperatureUnit, TimeUnit,
+    },
 };
 use serde::{Deserialize, Serialize};
 use uom::si::f64::*;
@@ -53,10 +55,12 @@ pub enum VehicleCostRate {
         variable_type: CustomVariableType,
         factor: Option<f64>,
     },
+    /// applies a nonlinear transform to the cost computed by another rate
+    Transformed {
+        rate: Box<VehicleCostRate>,
+        transform: CostTransform,
+    },
     // Combined(Vec<VehicleCostRate>),
-    // leaving room for extension if we need to do any fancier math, maybe not needed
-    // Poly2 { x0: f64, x1: f64 },
-    // Exp { base: f64, exp_coefficient: f64 },
 }
 
 impl VehicleCostRate {
@@ -68,12 +72,27 @@ impl VehicleCostRate {
         state: &[StateVariable],
         state_model: &StateModel,
     ) -> Result<Cost, CostModelError> {
+        if let VehicleCostRate::Transformed { rate, transform } = self {
+            let cost = rate.compute_cost(name, state, state_model)?;
+            return Ok(Cost::new(transform.apply(cost.as_f64())));
+        }
         let raw = self.get_raw(name, state, state_model)?;
         let cost_factor = self.get_factor();
         let cost = Cost::new(raw * cost_factor);
         Ok(cost)
     }
 
+    /// confirms any nonlinear transforms in this rate are valid.
+    pub fn validate(&self) -> Result<(), CostModelError> {
+        match self {
+            VehicleCostRate::Transformed { rate, transform } => {
+                rate.validate()?;
+                transform.validate()
+            }
+            _ => Ok(()),
+        }
+    }
+
     pub fn get_unit_name(&self) -> Option<String> {
         match self {
             VehicleCostRate::Zero => None,
@@ -85,6 +104,45 @@ impl VehicleCostRate {
             VehicleCostRate::Ratio { unit, .. } => Some(unit.to_string()),
             VehicleCostRate::Temperature { unit, .. } => Some(unit.to_string()),
             VehicleCostRate::Custom { variable_type, .. } => Some(variable_type.to_string()),
+            VehicleCostRate::Transformed { rate, .. } => rate.get_unit_name(),
+        }
+    }
+
+    /// the input feature read by this rate from the state variable with this name,
+    /// or None if the rate does not read the variable as a specific type.
+    pub fn input_feature(&self, name: &str) -> Option<InputFeature> {
+        let name = name.to_string();
+        match self {
+            VehicleCostRate::Zero | VehicleCostRate::Raw => None,
+            VehicleCostRate::Distance { unit, .. } => Some(InputFeature::Distance {
+                name,
+                unit: Some(*unit),
+            }),
+            VehicleCostRate::Time { unit, .. } => Some(InputFeature::Time {
+                name,
+                unit: Some(*unit),
+            }),
+            VehicleCostRate::Speed { unit, .. } => Some(InputFeature::Speed {
+                name,
+                unit: Some(*unit),
+            }),
+            VehicleCostRate::Energy { unit, .. } => Some(InputFeature::Energy {
+                name,
+                unit: Some(*unit),
+            }),
+            VehicleCostRate::Ratio { unit, .. } => Some(InputFeature::Ratio {
+                name,
+                unit: Some(*unit),
+            }),
+            VehicleCostRate::Temperature { unit, .. } => Some(InputFeature::Temperature {
+                name,
+                unit: Some(*unit),
+            }),
+            VehicleCostRate::Custom { variable_type, .. } => Some(InputFeature::Custom {
+                name,
+                unit: variable_type.to_string(),
+            }),
+            VehicleCostRate::Transformed { rate, .. } => rate.input_feature(&name),
         }
     }
 
@@ -100,6 +158,7 @@ impl VehicleCostRate {
             VehicleCostRate::Ratio { factor, .. } => *factor,
             VehicleCostRate::Temperature { factor, .. } => *factor,
             VehicleCostRate::Custom { factor, .. } => factor.unwrap_or(1.0),
+            VehicleCostRate::Transformed { rate, .. } => rate.get_factor(),
         }
     }
 
@@ -168,12 +227,16 @@ impl VehicleCostRate {
                     }
                 }
             },
+            VehicleCostRate::Transformed { rate, .. } => rate.get_raw(name, state, state_model),
         }
     }
 }
 
 impl std::fmt::Display for VehicleCostRate {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let VehicleCostRate::Transformed { rate, transform } = self {
+            return write!(f, "{rate} transformed by {transform}");
+        }
         let factor = match self {
             VehicleCostRate::Zero => None,
             VehicleCostRate::Raw => None,
@@ -184,6 +247,7 @@ impl std::fmt::Display for VehicleCostRate {
             VehicleCostRate::Ratio { factor, .. } => Some(*factor),
             VehicleCostRate::Temperature { factor, .. } => Some(*factor),
             VehicleCostRate::Custom { factor, .. } => factor.to_owned(),
+            VehicleCostRate::Transformed { .. } => None,
         };
         let s = match (factor, self.get_unit_name()) {
             (None, None) => String::from("zeroed"),
@@ -1 +1,3 @@
+pub mod soc_bucket_label_model;
+pub mod time_bucket_label_model;
 pub mod vertex_label_model;
@@ -0,0 +1,213 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::{
+    label::{Label, LabelModel, LabelModelBuilder, LabelModelError, LabelModelService},
+    network::VertexId,
+    state::{StateModel, StateVariable},
+    unit::RatioUnit,
+};
+
+/// name of the state of charge state variable written by the energy traversal models
+const DEFAULT_SOC_FEATURE: &str = "trip_soc";
+
+/// configuration for the SOC bucket label model, read from the `[label]` section.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SocBucketLabelConfig {
+    /// width of each state of charge bucket, in `ratio_unit`
+    pub bucket_size: f64,
+    /// unit of the `bucket_size`, percent by default
+    #[serde(default)]
+    pub ratio_unit: Option<RatioUnit>,
+    /// name of the state of charge state variable to discretize, `trip_soc` by default
+    #[serde(default)]
+    pub feature: Option<String>,
+}
+
+/// labels vertices with the bucket of the battery state of charge (SOC) at which they
+/// are reached, so that an energy-feasible search keeps a more costly route that arrives
+/// with more charge alongside a cheaper route that arrives nearly empty. for a SOC `s`
+/// and bucket size `b`, the label state is `floor(s / b)`.
+///
+/// a higher bucket dominates a lower one when it is no more costly to reach.
+pub struct SocBucketLabelModel {
+    pub bucket_size: uom::si::f64::Ratio,
+    pub feature: String,
+}
+
+impl SocBucketLabelModel {
+    pub fn new(
+        bucket_size: uom::si::f64::Ratio,
+        feature: String,
+    ) -> Result<SocBucketLabelModel, LabelModelError> {
+        let size = bucket_size.value;
+        if !size.is_finite() || size <= 0.0 || size > 1.0 {
+            return Err(LabelModelError::LabelModelError(format!(
+                "soc bucket label model bucket_size must be in (0, 100] percent, found {} percent",
+                size * 100.0
+            )));
+        }
+        Ok(SocBucketLabelModel {
+            bucket_size,
+            feature,
+        })
+    }
+}
+
+impl LabelModel for SocBucketLabelModel {
+    fn label_from_state(
+        &self,
+        vertex_id: VertexId,
+        state: &[StateVariable],
+        state_model: &StateModel,
+    ) -> Result<Label, LabelModelError> {
+        let soc = state_model.get_ratio(state, &self.feature)?;
+        let bucket = (soc / self.bucket_size).value.floor().max(0.0) as usize;
+        Ok(Label::VertexWithIntState {
+            vertex_id,
+            state: bucket,
+        })
+    }
+
+    /// a label in a higher state of charge bucket dominates a label in a lower bucket.
+    fn compare(&self, prev: &Label, next: &Label) -> Result<std::cmp::Ordering, LabelModelError> {
+        match (prev, next) {
+            (
+                Label::VertexWithIntState { state: s1, .. },
+                Label::VertexWithIntState { state: s2, .. },
+            ) => Ok(s1.cmp(s2)),
+            _ => Err(LabelModelError::MismatchLabelTypes(
+                prev.clone(),
+                next.clone(),
+            )),
+        }
+    }
+}
+
+impl LabelModelService for SocBucketLabelModel {
+    fn build(
+        &self,
+        _query: &serde_json::Value,
+        state_model: Arc<StateModel>,
+    ) -> Result<Arc<dyn LabelModel>, LabelModelError> {
+        if !state_model.contains_key(&self.feature) {
+            return Err(LabelModelError::LabelModelError(format!(
+                "soc bucket label model feature '{}' not found in state model with features [{}]",
+                self.feature,
+                state_model.get_names()
+            )));
+        }
+        let model = SocBucketLabelModel {
+            bucket_size: self.bucket_size,
+            feature: self.feature.clone(),
+        };
+        Ok(Arc::new(model))
+    }
+}
+
+pub struct SocBucketLabelModelBuilder;
+
+impl LabelModelBuilder for SocBucketLabelModelBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn LabelModelService>, LabelModelError> {
+        let config: SocBucketLabelConfig =
+            serde_json::from_value(parameters.clone()).map_err(|e| {
+                LabelModelError::LabelModelError(format!(
+                    "failed to deserialize soc bucket label configuration: {e}"
+                ))
+            })?;
+        let bucket_size = config
+            .ratio_unit
+            .unwrap_or(RatioUnit::Percent)
+            .to_uom(config.bucket_size);
+        let feature = config
+            .feature
+            .unwrap_or_else(|| String::from(DEFAULT_SOC_FEATURE));
+        let model = SocBucketLabelModel::new(bucket_size, feature)?;
+        Ok(Arc::new(model))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::state::StateVariableConfig;
+    use serde_json::json;
+    use std::cmp::Ordering;
+    use uom::si::{f64::Ratio, ratio::percent};
+
+    fn state_model() -> StateModel {
+        StateModel::new(vec![(
+            String::from(DEFAULT_SOC_FEATURE),
+            StateVariableConfig::Ratio {
+                initial: Ratio::new::<percent>(100.0),
+                accumulator: true,
+                output_unit: None,
+            },
+        )])
+    }
+
+    fn build_model(parameters: serde_json::Value) -> Arc<dyn LabelModel> {
+        SocBucketLabelModelBuilder
+            .build(&parameters)
+            .expect("test invariant failed")
+            .build(&json!({}), Arc::new(state_model()))
+            .expect("test invariant failed")
+    }
+
+    #[test]
+    fn test_label_from_state() {
+        let model = build_model(json!({"type": "soc_bucket", "bucket_size": 10}));
+        let state_model = state_model();
+        let mut state = state_model.initial_state(None).unwrap();
+        for (soc, expected) in [(100.0, 10), (55.0, 5), (9.9, 0), (0.0, 0)] {
+            state_model
+                .set_ratio(&mut state, DEFAULT_SOC_FEATURE, &Ratio::new::<percent>(soc))
+                .unwrap();
+            let label = model
+                .label_from_state(VertexId(3), &state, &state_model)
+                .unwrap();
+            assert_eq!(
+                label,
+                Label::VertexWithIntState {
+                    vertex_id: VertexId(3),
+                    state: expected
+                },
+                "soc of {soc} percent"
+            );
+        }
+    }
+
+    #[test]
+    fn test_higher_bucket_dominates() {
+        let model = build_model(json!({"bucket_size": 0.25, "ratio_unit": "decimal"}));
+        let low = Label::VertexWithIntState {
+            vertex_id: VertexId(0),
+            state: 1,
+        };
+        let high = Label::VertexWithIntState {
+            vertex_id: VertexId(0),
+            state: 3,
+        };
+        assert_eq!(model.compare(&low, &high).unwrap(), Ordering::Less);
+        assert_eq!(model.compare(&high, &low).unwrap(), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_invalid_configuration() {
+        assert!(SocBucketLabelModelBuilder
+            .build(&json!({"bucket_size": 0}))
+            .is_err());
+        assert!(SocBucketLabelModelBuilder
+            .build(&json!({"bucket_size": 150}))
+            .is_err());
+        let missing_feature = SocBucketLabelModelBuilder
+            .build(&json!({"bucket_size": 10, "feature": "battery_soc"}))
+            .unwrap()
+            .build(&json!({}), Arc::new(state_model()));
+        assert!(missing_feature.is_err());
+    }
+}
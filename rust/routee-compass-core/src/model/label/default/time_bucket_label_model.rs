@@ -0,0 +1,209 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::{
+    label::{Label, LabelModel, LabelModelBuilder, LabelModelError, LabelModelService},
+    network::VertexId,
+    state::{StateModel, StateVariable},
+    traversal::default::fieldname,
+    unit::TimeUnit,
+};
+
+/// configuration for the time bucket label model, read from the `[label]` section.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeBucketLabelConfig {
+    /// width of each time bucket, in `time_unit`
+    pub bucket_size: f64,
+    /// unit of the `bucket_size`, minutes by default
+    #[serde(default)]
+    pub time_unit: TimeUnit,
+    /// name of the time state variable to discretize, `trip_time` by default
+    #[serde(default)]
+    pub feature: Option<String>,
+}
+
+/// labels vertices with the bucket of the trip time at which they are reached, so that
+/// a time-dependent search (with time-varying speeds or restrictions) may keep one
+/// label per bucket at a vertex instead of one label per vertex. for a trip time `t`
+/// and bucket size `b`, the label state is `floor(t / b)`.
+///
+/// an earlier bucket dominates a later one when it is no more costly to reach.
+pub struct TimeBucketLabelModel {
+    pub bucket_size: uom::si::f64::Time,
+    pub feature: String,
+}
+
+impl TimeBucketLabelModel {
+    pub fn new(
+        bucket_size: uom::si::f64::Time,
+        feature: String,
+    ) -> Result<TimeBucketLabelModel, LabelModelError> {
+        let size = bucket_size.value;
+        if !size.is_finite() || size <= 0.0 {
+            return Err(LabelModelError::LabelModelError(format!(
+                "time bucket label model bucket_size must be a positive number, found {size} seconds"
+            )));
+        }
+        Ok(TimeBucketLabelModel {
+            bucket_size,
+            feature,
+        })
+    }
+}
+
+impl LabelModel for TimeBucketLabelModel {
+    fn label_from_state(
+        &self,
+        vertex_id: VertexId,
+        state: &[StateVariable],
+        state_model: &StateModel,
+    ) -> Result<Label, LabelModelError> {
+        let time = state_model.get_time(state, &self.feature)?;
+        let bucket = (time / self.bucket_size).value.floor().max(0.0) as usize;
+        Ok(Label::VertexWithIntState {
+            vertex_id,
+            state: bucket,
+        })
+    }
+
+    /// a label in an earlier time bucket dominates a label in a later bucket.
+    fn compare(&self, prev: &Label, next: &Label) -> Result<std::cmp::Ordering, LabelModelError> {
+        match (prev, next) {
+            (
+                Label::VertexWithIntState { state: s1, .. },
+                Label::VertexWithIntState { state: s2, .. },
+            ) => Ok(s2.cmp(s1)),
+            _ => Err(LabelModelError::MismatchLabelTypes(
+                prev.clone(),
+                next.clone(),
+            )),
+        }
+    }
+}
+
+impl LabelModelService for TimeBucketLabelModel {
+    fn build(
+        &self,
+        _query: &serde_json::Value,
+        state_model: Arc<StateModel>,
+    ) -> Result<Arc<dyn LabelModel>, LabelModelError> {
+        // fail at query time rather than on the first label if the time feature is missing
+        if !state_model.contains_key(&self.feature) {
+            return Err(LabelModelError::LabelModelError(format!(
+                "time bucket label model feature '{}' not found in state model with features [{}]",
+                self.feature,
+                state_model.get_names()
+            )));
+        }
+        let model = TimeBucketLabelModel {
+            bucket_size: self.bucket_size,
+            feature: self.feature.clone(),
+        };
+        Ok(Arc::new(model))
+    }
+}
+
+pub struct TimeBucketLabelModelBuilder;
+
+impl LabelModelBuilder for TimeBucketLabelModelBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn LabelModelService>, LabelModelError> {
+        let config: TimeBucketLabelConfig =
+            serde_json::from_value(parameters.clone()).map_err(|e| {
+                LabelModelError::LabelModelError(format!(
+                    "failed to deserialize time bucket label configuration: {e}"
+                ))
+            })?;
+        let bucket_size = config.time_unit.to_uom(config.bucket_size);
+        let feature = config
+            .feature
+            .unwrap_or_else(|| String::from(fieldname::TRIP_TIME));
+        let model = TimeBucketLabelModel::new(bucket_size, feature)?;
+        Ok(Arc::new(model))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::state::StateVariableConfig;
+    use serde_json::json;
+    use std::cmp::Ordering;
+    use uom::si::{f64::Time, time::minute};
+
+    fn state_model() -> StateModel {
+        StateModel::new(vec![(
+            String::from(fieldname::TRIP_TIME),
+            StateVariableConfig::Time {
+                initial: Time::new::<minute>(0.0),
+                accumulator: true,
+                output_unit: None,
+            },
+        )])
+    }
+
+    fn build_model(parameters: serde_json::Value) -> Arc<dyn LabelModel> {
+        TimeBucketLabelModelBuilder
+            .build(&parameters)
+            .expect("test invariant failed")
+            .build(&json!({}), Arc::new(state_model()))
+            .expect("test invariant failed")
+    }
+
+    #[test]
+    fn test_label_from_state() {
+        let model = build_model(json!({"type": "time_bucket", "bucket_size": 15}));
+        let state_model = state_model();
+        let mut state = state_model.initial_state(None).unwrap();
+        for (minutes, expected) in [(0.0, 0), (14.9, 0), (15.0, 1), (47.0, 3)] {
+            state_model
+                .set_time(
+                    &mut state,
+                    fieldname::TRIP_TIME,
+                    &Time::new::<minute>(minutes),
+                )
+                .unwrap();
+            let label = model
+                .label_from_state(VertexId(7), &state, &state_model)
+                .unwrap();
+            assert_eq!(
+                label,
+                Label::VertexWithIntState {
+                    vertex_id: VertexId(7),
+                    state: expected
+                },
+                "trip time of {minutes} minutes"
+            );
+        }
+    }
+
+    #[test]
+    fn test_earlier_bucket_dominates() {
+        let model = build_model(json!({"bucket_size": 5, "time_unit": "seconds"}));
+        let early = Label::VertexWithIntState {
+            vertex_id: VertexId(0),
+            state: 2,
+        };
+        let late = Label::VertexWithIntState {
+            vertex_id: VertexId(0),
+            state: 4,
+        };
+        assert_eq!(model.compare(&late, &early).unwrap(), Ordering::Less);
+        assert_eq!(model.compare(&early, &late).unwrap(), Ordering::Greater);
+        assert_eq!(model.compare(&early, &early).unwrap(), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_invalid_configuration() {
+        let zero = TimeBucketLabelModelBuilder.build(&json!({"bucket_size": 0}));
+        assert!(zero.is_err());
+        let missing_feature = TimeBucketLabelModelBuilder
+            .build(&json!({"bucket_size": 10, "feature": "trip_duration"}))
+            .unwrap()
+            .build(&json!({}), Arc::new(state_model()));
+        assert!(missing_feature.is_err());
+    }
+}
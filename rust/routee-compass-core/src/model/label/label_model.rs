@@ -14,8 +14,12 @@ use crate::model::{
 ///
 /// See the [`super::default`] module for implementations bundled with RouteE Compass:
 ///   - [VertexLabelModel]: creates simple vertex-based labels without state information
+///   - [TimeBucketLabelModel]: labels vertices with a discretized trip time for time-dependent searches
+///   - [SocBucketLabelModel]: labels vertices with a discretized battery state of charge
 ///
 /// [VertexLabelModel]: super::default::vertex_label_model::VertexLabelModel
+/// [TimeBucketLabelModel]: super::default::time_bucket_label_model::TimeBucketLabelModel
+/// [SocBucketLabelModel]: super::default::soc_bucket_label_model::SocBucketLabelModel
 pub trait LabelModel: Send + Sync {
     /// Creates a label from the current search state at a given vertex.
     ///
@@ -17,7 +17,7 @@ use crate::model::label::{
 /// create multiple model instances as needed for each query.
 ///
 /// [LabelModelService]: super::label_model_service::LabelModelService
-pub trait LabelModelBuilder {
+pub trait LabelModelBuilder: Send + Sync {
     /// Builds a [LabelModelService] from JSON configuration.
     ///
     /// This method is called during application startup to create the service
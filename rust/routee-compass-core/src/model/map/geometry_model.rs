@@ -3,11 +3,17 @@ use std::sync::Arc;
 use super::map_error::MapError;
 use crate::{
     model::network::{EdgeId, EdgeListId, Graph},
-    util::{fs::read_utils, geo::geo_io_utils},
+    util::{
+        fs::{parquet_utils, read_utils},
+        geo::{crs::Crs, geo_io_utils},
+    },
 };
-use geo::LineString;
+use geo::{Geometry, LineString};
 use kdam::{Bar, BarExt};
 
+/// default WKB geometry column of a GeoParquet edge layer
+const DEFAULT_GEOMETRY_COLUMN: &str = "geometry";
+
 /// model for link geometries by edge id. can be constructed either
 /// from edge geometry dataset ([`GeometryModel::new_from_edges`]) or
 /// from the vertices ([`GeometryModel::new_from_vertices`]) by simply
@@ -24,11 +30,15 @@ impl GeometryModel {
         Ok(GeometryModel(edges))
     }
 
-    /// use a user-provided enumerated textfile input to load LineString geometries
+    /// use a user-provided enumerated textfile input or GeoParquet edge layer to load
+    /// LineString geometries, transforming them to WGS84 from the provided coordinate
+    /// reference system
     pub fn new_from_edges(
         geometry_input_file: &String,
+        geometry_column: Option<&str>,
         edge_list_id: EdgeListId,
         graph: Arc<Graph>,
+        srid: Option<&Crs>,
     ) -> Result<GeometryModel, MapError> {
         let edge_list = graph.get_edge_list(&edge_list_id).map_err(|e| {
             MapError::BuildError(format!(
@@ -37,7 +47,30 @@ impl GeometryModel {
         })?;
 
         let edge_list_len = edge_list.len();
-        let linestrings = read_linestrings(geometry_input_file, edge_list_len)?;
+        let mut linestrings = if parquet_utils::is_geopackage(geometry_input_file) {
+            return Err(MapError::BuildError(parquet_utils::geopackage_unsupported(
+                geometry_input_file,
+            )));
+        } else if parquet_utils::is_parquet(geometry_input_file) {
+            let column = geometry_column.unwrap_or(DEFAULT_GEOMETRY_COLUMN);
+            read_geoparquet_linestrings(geometry_input_file, column)?
+        } else {
+            read_linestrings(geometry_input_file, edge_list_len)?
+        };
+        if let Some(crs) = srid.filter(|crs| !crs.is_wgs84()) {
+            log::info!("transforming geometries in {geometry_input_file} from {crs} to WGS84");
+            linestrings = linestrings
+                .iter()
+                .enumerate()
+                .map(|(edge_id, linestring)| {
+                    crs.linestring_to_wgs84(linestring).map_err(|e| {
+                        MapError::BuildError(format!(
+                            "geometry file {geometry_input_file} edge {edge_id}: {e}"
+                        ))
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+        }
 
         if linestrings.len() != edge_list_len {
             Err(MapError::BuildError(format!("edge list {edge_list_id} geometry file {geometry_input_file} should have {edge_list_len} rows, found {}", linestrings.len())))
@@ -75,6 +108,34 @@ fn read_linestrings(
     Ok(geoms)
 }
 
+fn read_geoparquet_linestrings(
+    geometry_input_file: &String,
+    column: &str,
+) -> Result<Vec<geo::LineString<f32>>, MapError> {
+    let failed =
+        |e: String| MapError::BuildError(format!("error loading {geometry_input_file}: {e}"));
+    let rows = parquet_utils::row_iter(geometry_input_file).map_err(failed)?;
+    rows.enumerate()
+        .map(|(edge_id, row)| {
+            let row = row.map_err(|e| failed(e.to_string()))?;
+            let linestring = match parquet_utils::get_geometry(&row, column).map_err(failed)? {
+                Geometry::LineString(l) => l,
+                Geometry::MultiLineString(mls) => LineString::from_iter(mls.into_iter().flatten()),
+                _ => {
+                    return Err(failed(format!(
+                        "edge {edge_id} geometry is not a LineString"
+                    )))
+                }
+            };
+            let coords = linestring
+                .into_iter()
+                .map(|c| geo::coord! { x: c.x as f32, y: c.y as f32 })
+                .collect::<Vec<_>>();
+            Ok(LineString::new(coords))
+        })
+        .collect()
+}
+
 fn create_linestrings_from_vertices(
     graph: Arc<Graph>,
     edge_list_id: EdgeListId,
@@ -0,0 +1,19 @@
+use crate::model::network::{EdgeId, EdgeListId};
+use geo::Point;
+use serde::{Deserialize, Serialize};
+use uom::si::f64::Length;
+
+/// an edge near a query point, along with the location on the edge closest to that point.
+/// produced by [`super::MapModel::k_nearest`] and [`super::MapModel::edges_within`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct MapCandidate {
+    pub edge_list_id: EdgeListId,
+    pub edge_id: EdgeId,
+    /// great circle distance from the query point to the snapped coordinate
+    pub distance: Length,
+    /// the point on the edge geometry closest to the query point
+    pub coordinate: Point<f32>,
+    /// position of the snapped coordinate along the edge geometry, from 0 at the
+    /// start of the edge to 1 at the end
+    pub offset_fraction: f64,
+}
@@ -1,6 +1,6 @@
 use super::{map_error::MapError, spatial_index_ops as ops};
 use crate::model::network::{Edge, EdgeId, EdgeListId};
-use geo::{LineString, Point};
+use geo::{Distance, Euclidean, LineString, Point};
 use rstar::{PointDistance, RTreeObject, AABB};
 use uom::si::f64::Length;
 
@@ -10,6 +10,7 @@ pub struct MapEdgeRTreeObject {
     pub edge_list_id: EdgeListId,
     pub edge_id: EdgeId,
     pub envelope: AABB<Point<f32>>,
+    pub geometry: LineString<f32>,
 }
 
 impl MapEdgeRTreeObject {
@@ -18,6 +19,7 @@ impl MapEdgeRTreeObject {
             edge_list_id: edge.edge_list_id,
             edge_id: edge.edge_id,
             envelope: linestring.envelope(),
+            geometry: linestring.clone(),
         }
     }
 
@@ -27,7 +29,9 @@ impl MapEdgeRTreeObject {
         tolerance: &Option<Length>,
     ) -> Result<bool, MapError> {
         match tolerance {
-            Some(dist) => ops::test_threshold(&self.envelope, point, *dist),
+            // the bounding box is never farther than the edge, so it rules out most edges cheaply
+            Some(dist) if !ops::test_threshold(&self.envelope, point, *dist)? => Ok(false),
+            Some(dist) => ops::test_linestring_threshold(&self.geometry, point, *dist),
             None => Ok(true),
         }
     }
@@ -38,7 +42,7 @@ impl MapEdgeRTreeObject {
         tolerance: &Option<Length>,
     ) -> Result<(), MapError> {
         match tolerance {
-            Some(dist) => ops::within_threshold(&self.envelope, point, *dist),
+            Some(dist) => ops::within_linestring_threshold(&self.geometry, point, *dist),
             None => Ok(()),
         }
     }
@@ -53,7 +57,10 @@ impl RTreeObject for MapEdgeRTreeObject {
 }
 
 impl PointDistance for MapEdgeRTreeObject {
+    /// squared distance in coordinate space from the point to the edge geometry, which
+    /// orders nearest neighbor queries by the edge rather than its bounding box
     fn distance_2(&self, point: &Point<f32>) -> f32 {
-        self.envelope.distance_2(point)
+        let distance = Euclidean.distance(point, &self.geometry);
+        distance * distance
     }
 }
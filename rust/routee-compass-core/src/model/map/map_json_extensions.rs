@@ -1,6 +1,11 @@
-use super::{map_error::MapError, map_json_key::MapJsonKey};
+use super::{
+    map_error::MapError, map_json_key::MapJsonKey, map_model_config::DistanceTolerance,
+    snap_report::SnapReport,
+};
 use crate::model::network::{EdgeId, EdgeListId, VertexId};
+use crate::util::geo::crs::{Crs, WGS84_SRID};
 use geo;
+use uom::si::f64::Length;
 
 pub trait MapJsonExtensions {
     fn get_origin_coordinate(&self) -> Result<geo::Coord<f32>, MapError>;
@@ -21,9 +26,137 @@ pub trait MapJsonExtensions {
     fn get_destination_vertex(&self) -> Result<Option<VertexId>, MapError>;
     fn get_origin_edge(&self) -> Result<(EdgeListId, EdgeId), MapError>;
     fn get_destination_edge(&self) -> Result<Option<(EdgeListId, EdgeId)>, MapError>;
+    fn apply_input_srid(&mut self) -> Result<(), MapError>;
+    fn add_snap_report(&mut self, key: MapJsonKey, report: &SnapReport) -> Result<(), MapError>;
+    fn get_max_snap_distance(&self) -> Result<Option<Length>, MapError>;
+    fn add_snap_candidates(
+        &mut self,
+        key: MapJsonKey,
+        reports: &[SnapReport],
+    ) -> Result<(), MapError>;
+    fn get_snap_candidates(&self, key: MapJsonKey) -> Result<Option<Vec<SnapReport>>, MapError>;
+    fn get_endpoint_candidates(&self) -> Result<Option<usize>, MapError>;
 }
 
 impl MapJsonExtensions for serde_json::Value {
+    /// if the query declares an `input_srid`, transforms its origin and destination
+    /// coordinates to WGS84 in place and updates `input_srid` to match.
+    fn apply_input_srid(&mut self) -> Result<(), MapError> {
+        let crs: Crs = match self.get(MapJsonKey::InputSrid.as_str()) {
+            None => return Ok(()),
+            Some(value) => serde_json::from_value(value.clone()).map_err(|e| {
+                MapError::InputDeserializingError(MapJsonKey::InputSrid.to_string(), e.to_string())
+            })?,
+        };
+        if crs.is_wgs84() {
+            return Ok(());
+        }
+        let pairs = [
+            (MapJsonKey::OriginX, MapJsonKey::OriginY),
+            (MapJsonKey::DestinationX, MapJsonKey::DestinationY),
+        ];
+        for (x_field, y_field) in pairs {
+            let x = self.get(x_field.as_str()).and_then(|v| v.as_f64());
+            let y = self.get(y_field.as_str()).and_then(|v| v.as_f64());
+            if let (Some(x), Some(y)) = (x, y) {
+                let coord = crs
+                    .to_wgs84(geo::Coord { x, y })
+                    .map_err(MapError::MapMatchError)?;
+                self[x_field.as_str()] = serde_json::json!(coord.x);
+                self[y_field.as_str()] = serde_json::json!(coord.y);
+            }
+        }
+        self[MapJsonKey::InputSrid.as_str()] = serde_json::json!(WGS84_SRID);
+        Ok(())
+    }
+
+    /// records where an origin or destination coordinate was snapped to the graph
+    fn add_snap_report(&mut self, key: MapJsonKey, report: &SnapReport) -> Result<(), MapError> {
+        let value = serde_json::to_value(report)
+            .map_err(|e| MapError::InputDeserializingError(key.to_string(), e.to_string()))?;
+        match self {
+            serde_json::Value::Object(map) => {
+                map.insert(key.to_string(), value);
+                Ok(())
+            }
+            _ => Err(MapError::InputDeserializingError(
+                String::from("<user query>"),
+                String::from("json object"),
+            )),
+        }
+    }
+
+    /// reads the optional `max_snap_distance` of a query, which overrides the configured
+    /// map matching tolerance for its origin and destination coordinates
+    fn get_max_snap_distance(&self) -> Result<Option<Length>, MapError> {
+        let key = MapJsonKey::MaxSnapDistance;
+        match self.get(key.as_str()) {
+            None => Ok(None),
+            Some(value) => {
+                let tolerance: DistanceTolerance =
+                    serde_json::from_value(value.clone()).map_err(|e| {
+                        MapError::InputDeserializingError(key.to_string(), e.to_string())
+                    })?;
+                if !(tolerance.distance.is_finite() && tolerance.distance >= 0.0) {
+                    return Err(MapError::InputDeserializingError(
+                        key.to_string(),
+                        format!(
+                            "distance must be non-negative, found {}",
+                            tolerance.distance
+                        ),
+                    ));
+                }
+                Ok(Some(tolerance.to_uom()))
+            }
+        }
+    }
+
+    /// records the candidate locations an origin or destination coordinate was snapped
+    /// to, nearest first
+    fn add_snap_candidates(
+        &mut self,
+        key: MapJsonKey,
+        reports: &[SnapReport],
+    ) -> Result<(), MapError> {
+        let value = serde_json::to_value(reports)
+            .map_err(|e| MapError::InputDeserializingError(key.to_string(), e.to_string()))?;
+        match self {
+            serde_json::Value::Object(map) => {
+                map.insert(key.to_string(), value);
+                Ok(())
+            }
+            _ => Err(MapError::InputDeserializingError(
+                String::from("<user query>"),
+                String::from("json object"),
+            )),
+        }
+    }
+
+    fn get_snap_candidates(&self, key: MapJsonKey) -> Result<Option<Vec<SnapReport>>, MapError> {
+        match self.get(key.as_str()) {
+            None => Ok(None),
+            Some(value) => serde_json::from_value(value.clone())
+                .map(Some)
+                .map_err(|e| MapError::InputDeserializingError(key.to_string(), e.to_string())),
+        }
+    }
+
+    /// reads the optional `endpoint_candidates` of a query, which overrides the configured
+    /// number of candidates retained when map matching its origin and destination coordinates
+    fn get_endpoint_candidates(&self) -> Result<Option<usize>, MapError> {
+        let key = MapJsonKey::EndpointCandidates;
+        match self.get(key.as_str()) {
+            None => Ok(None),
+            Some(value) => match value.as_u64() {
+                Some(k) if k > 0 => Ok(Some(k as usize)),
+                _ => Err(MapError::InputDeserializingError(
+                    key.to_string(),
+                    format!("expected a positive integer, found {value}"),
+                )),
+            },
+        }
+    }
+
     fn get_origin_coordinate(&self) -> Result<geo::Coord<f32>, MapError> {
         let origin_x = self
             .get(MapJsonKey::OriginX.to_string())
@@ -231,3 +364,47 @@ impl MapJsonExtensions for serde_json::Value {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_apply_input_srid() {
+        let mut query = serde_json::json!({
+            "input_srid": 32613,
+            "origin_x": 500000.0,
+            "origin_y": 4427757.22,
+        });
+        query.apply_input_srid().unwrap();
+        let origin = query.get_origin_coordinate().unwrap();
+        assert!((origin.x + 105.0).abs() < 1e-5 && (origin.y - 40.0).abs() < 1e-5);
+        assert_eq!(query["input_srid"], serde_json::json!(WGS84_SRID));
+
+        // applying again leaves the WGS84 coordinates unchanged
+        query.apply_input_srid().unwrap();
+        assert_eq!(query.get_origin_coordinate().unwrap(), origin);
+
+        let mut unsupported =
+            serde_json::json!({"input_srid": 2232, "origin_x": 0.0, "origin_y": 0.0});
+        assert!(unsupported.apply_input_srid().is_err());
+    }
+
+    #[test]
+    fn test_get_max_snap_distance() {
+        let query = serde_json::json!({
+            "max_snap_distance": {"distance": 0.5, "unit": "kilometers"}
+        });
+        let distance = query.get_max_snap_distance().unwrap().unwrap();
+        assert!((distance.get::<uom::si::length::meter>() - 500.0).abs() < 1e-9);
+        assert!(serde_json::json!({})
+            .get_max_snap_distance()
+            .unwrap()
+            .is_none());
+
+        let negative = serde_json::json!({
+            "max_snap_distance": {"distance": -1.0, "unit": "meters"}
+        });
+        assert!(negative.get_max_snap_distance().is_err());
+    }
+}
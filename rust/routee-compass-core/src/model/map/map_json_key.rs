@@ -14,6 +14,13 @@ pub enum MapJsonKey {
     OriginEdge,
     DestinationEdgeList,
     DestinationEdge,
+    InputSrid,
+    OriginSnap,
+    DestinationSnap,
+    MaxSnapDistance,
+    OriginCandidates,
+    DestinationCandidates,
+    EndpointCandidates,
 }
 
 impl MapJsonKey {
@@ -29,6 +36,13 @@ impl MapJsonKey {
             MapJsonKey::OriginEdge => "origin_edge",
             MapJsonKey::DestinationEdgeList => "destination_edge_list",
             MapJsonKey::DestinationEdge => "destination_edge",
+            MapJsonKey::InputSrid => "input_srid",
+            MapJsonKey::OriginSnap => "origin_snap",
+            MapJsonKey::DestinationSnap => "destination_snap",
+            MapJsonKey::MaxSnapDistance => "max_snap_distance",
+            MapJsonKey::OriginCandidates => "origin_candidates",
+            MapJsonKey::DestinationCandidates => "destination_candidates",
+            MapJsonKey::EndpointCandidates => "endpoint_candidates",
         }
     }
 }
@@ -1,24 +1,39 @@
+use super::map_candidate::MapCandidate;
 use super::map_error::MapError;
+use super::map_json_extensions::MapJsonExtensions;
 use super::map_model_config::MapModelConfig;
 use super::matching_type::MatchingType;
 use super::spatial_index::SpatialIndex;
+use super::spatial_index_ops as ops;
+use super::spatial_index_type::SpatialIndexType;
 use super::{geometry_model::GeometryModel, matching_type::MapInputResult};
 use crate::algorithm::search::SearchInstance;
 use crate::model::map::map_model_config::MapModelGeometryConfig;
+use crate::model::map::MapEdgeRTreeObject;
 use crate::model::network::{EdgeId, EdgeListId, Graph};
-use geo::LineString;
-use std::sync::Arc;
+use geo::{LineLocatePoint, LineString, Point};
+use rstar::AABB;
+use std::sync::{Arc, OnceLock};
+use uom::si::f64::Length;
 
 pub struct MapModel {
     /// way in which map matching is attempted
     pub matching_type: MatchingType,
-    /// index used during map matching
+    /// index of the configured [`SpatialIndexType`], used to map match origins and destinations
     pub spatial_index: SpatialIndex,
+    /// index of the other [`SpatialIndexType`], built on first use by a consumer that
+    /// requires it, such as LCSS map matching which requires an edge-oriented index
+    alternate_spatial_index: OnceLock<SpatialIndex>,
+    graph: Arc<Graph>,
+    tolerance: Option<Length>,
     /// collection of geometries associated with the graph edge lists
     pub geometry: Vec<GeometryModel>,
     /// allow for queries without a destination location, such as when generating
     /// shortest path trees or isochrones.
     pub queries_without_destinations: bool,
+    /// number of nearest valid candidates retained when map matching origin and
+    /// destination coordinates
+    pub endpoint_candidates: usize,
 }
 
 impl MapModel {
@@ -35,15 +50,28 @@ impl MapModel {
                     }
                     MapModelGeometryConfig::FromLinestrings {
                         geometry_input_file,
+                        geometry_column,
+                        srid,
                     } => GeometryModel::new_from_edges(
                         geometry_input_file,
+                        geometry_column.as_deref(),
                         edge_list_id,
                         graph.clone(),
+                        srid.as_ref(),
                     ),
                 }
             })
             .collect::<Result<Vec<_>, _>>()?;
         let queries_without_destinations = config.queries_without_destinations;
+        let endpoint_candidates = match config.endpoint_candidates {
+            None => 1,
+            Some(0) => {
+                return Err(MapError::BuildError(String::from(
+                    "endpoint_candidates must be at least 1",
+                )))
+            }
+            Some(k) => k,
+        };
         let tolerance = config.tolerance.as_ref().map(|t| t.to_uom());
         let matching_type =
             MatchingType::deserialize_matching_types(config.matching_type.as_ref())?;
@@ -54,8 +82,34 @@ impl MapModel {
         Ok(MapModel {
             matching_type,
             spatial_index,
+            alternate_spatial_index: OnceLock::new(),
+            graph,
+            tolerance,
             geometry,
             queries_without_destinations,
+            endpoint_candidates,
+        })
+    }
+
+    /// gets a spatial index of the requested type. the configured index is returned if it
+    /// has this type, otherwise an index of this type is built on first use and kept for
+    /// subsequent calls, so that consumers with different requirements share one map model.
+    pub fn get_spatial_index(&self, spatial_index_type: &SpatialIndexType) -> &SpatialIndex {
+        let configured_type = match self.spatial_index {
+            SpatialIndex::VertexOrientedIndex { .. } => SpatialIndexType::VertexOriented,
+            SpatialIndex::EdgeOrientedIndex { .. } => SpatialIndexType::EdgeOriented,
+        };
+        if configured_type == *spatial_index_type {
+            return &self.spatial_index;
+        }
+        self.alternate_spatial_index.get_or_init(|| {
+            log::debug!("building {spatial_index_type:?} spatial index on first use");
+            SpatialIndex::build(
+                spatial_index_type,
+                self.graph.clone(),
+                &self.geometry,
+                self.tolerance,
+            )
         })
     }
 
@@ -73,11 +127,142 @@ impl MapModel {
             .ok_or(MapError::MissingEdgeId(*edge_list_id, *edge_id))
     }
 
+    /// finds the `k` edges nearest to a point, ordered by distance, along with the
+    /// point on each edge closest to the query point. uses the edge-oriented spatial
+    /// index, building it if the configured index is vertex-oriented.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - query point
+    /// * `k` - maximum number of candidates to return
+    /// * `tolerance` - if provided, candidates farther than this distance are excluded
+    ///
+    /// # Returns
+    ///
+    /// up to `k` candidates, nearest first
+    pub fn k_nearest(
+        &self,
+        point: &Point<f32>,
+        k: usize,
+        tolerance: Option<Length>,
+    ) -> Result<Vec<MapCandidate>, MapError> {
+        let rtree = match self.get_spatial_index(&SpatialIndexType::EdgeOriented) {
+            SpatialIndex::EdgeOrientedIndex { rtree, .. } => rtree,
+            SpatialIndex::VertexOrientedIndex { .. } => {
+                return Err(MapError::InternalError(String::from(
+                    "expected an edge-oriented spatial index",
+                )))
+            }
+        };
+        if k == 0 {
+            return Ok(vec![]);
+        }
+
+        // the rtree yields edges by the distance to their bounding box, which never exceeds
+        // the distance to the edge itself, so the search can stop once a bounding box is
+        // farther than the k-th nearest edge found so far.
+        let mut found: Vec<(f32, MapCandidate)> = Vec::with_capacity(k);
+        for (obj, envelope_distance_2) in rtree.nearest_neighbor_iter_with_distance_2(point) {
+            if found.len() == k && envelope_distance_2 > found[k - 1].0 {
+                break;
+            }
+            if !obj.test_threshold(point, &tolerance)? {
+                continue;
+            }
+            let Some(candidate) = self.snap_to_edge(obj, point)? else {
+                continue;
+            };
+            if tolerance.is_some_and(|t| candidate.distance > t) {
+                continue;
+            }
+            let delta = point.0 - candidate.coordinate.0;
+            let distance_2 = delta.x * delta.x + delta.y * delta.y;
+            let position = found.partition_point(|(d, _)| *d <= distance_2);
+            found.insert(position, (distance_2, candidate));
+            found.truncate(k);
+        }
+
+        let mut candidates = found.into_iter().map(|(_, c)| c).collect::<Vec<_>>();
+        candidates.sort_by(|a, b| a.distance.value.total_cmp(&b.distance.value));
+        Ok(candidates)
+    }
+
+    /// finds every edge within a distance of a point, ordered by distance, along with
+    /// the point on each edge closest to the query point. uses the edge-oriented spatial
+    /// index, building it if the configured index is vertex-oriented.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - query point
+    /// * `distance` - search radius around the point
+    ///
+    /// # Returns
+    ///
+    /// the candidates within the radius, nearest first
+    pub fn edges_within(
+        &self,
+        point: &Point<f32>,
+        distance: Length,
+    ) -> Result<Vec<MapCandidate>, MapError> {
+        let rtree = match self.get_spatial_index(&SpatialIndexType::EdgeOriented) {
+            SpatialIndex::EdgeOrientedIndex { rtree, .. } => rtree,
+            SpatialIndex::VertexOrientedIndex { .. } => {
+                return Err(MapError::InternalError(String::from(
+                    "expected an edge-oriented spatial index",
+                )))
+            }
+        };
+        // a box of degrees that contains the search radius. a degree of latitude is at
+        // least 110.5km, and a degree of longitude shrinks with the cosine of the latitude.
+        let meters = distance.get::<uom::si::length::meter>() as f32;
+        let dy = meters / 110_000.0;
+        let dx = dy / point.y().to_radians().cos().max(1e-6);
+        let envelope = AABB::from_corners(
+            Point::new(point.x() - dx, point.y() - dy),
+            Point::new(point.x() + dx, point.y() + dy),
+        );
+
+        let mut candidates = vec![];
+        for obj in rtree.locate_in_envelope_intersecting(&envelope) {
+            if let Some(candidate) = self.snap_to_edge(obj, point)? {
+                if candidate.distance <= distance {
+                    candidates.push(candidate);
+                }
+            }
+        }
+        candidates.sort_by(|a, b| a.distance.value.total_cmp(&b.distance.value));
+        Ok(candidates)
+    }
+
+    /// the point on an edge nearest to the query point, as a map candidate
+    fn snap_to_edge(
+        &self,
+        obj: &MapEdgeRTreeObject,
+        point: &Point<f32>,
+    ) -> Result<Option<MapCandidate>, MapError> {
+        let linestring = self.get_linestring(&obj.edge_list_id, &obj.edge_id)?;
+        let Some((snapped, distance)) = ops::nearest_point_on_linestring(linestring, point)? else {
+            return Ok(None);
+        };
+        let offset_fraction = linestring
+            .line_locate_point(&snapped)
+            .map(f64::from)
+            .unwrap_or_default();
+        Ok(Some(MapCandidate {
+            edge_list_id: obj.edge_list_id,
+            edge_id: obj.edge_id,
+            distance,
+            coordinate: snapped,
+            offset_fraction,
+        }))
+    }
+
     pub fn map_match(
         &self,
         query: &mut serde_json::Value,
         si: &SearchInstance,
     ) -> Result<(), MapError> {
+        query.apply_input_srid()?;
         self.matching_type.process_origin(query, si)?;
         let result = self.matching_type.process_destination(query, si)?;
         match result {
@@ -88,3 +273,119 @@ impl MapModel {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::map::SpatialIndexType;
+    use crate::model::network::{Edge, EdgeList, Vertex};
+    use indexmap::IndexMap;
+    use uom::si::length::meter;
+
+    /// two parallel east-west edges, 0.001 degrees apart, plus a short edge far to the east
+    fn build_graph() -> Graph {
+        let vertices = vec![
+            Vertex::new(0, 0.0, 0.0),
+            Vertex::new(1, 0.01, 0.0),
+            Vertex::new(2, 0.0, 0.001),
+            Vertex::new(3, 0.01, 0.001),
+            Vertex::new(4, 1.0, 0.0),
+            Vertex::new(5, 1.001, 0.0),
+        ];
+        let edges = vec![
+            Edge::new(0, 0, 0, 1, Length::new::<meter>(1112.0)),
+            Edge::new(0, 1, 2, 3, Length::new::<meter>(1112.0)),
+            Edge::new(0, 2, 4, 5, Length::new::<meter>(111.0)),
+        ];
+        let mut adj = vec![IndexMap::new(); vertices.len()];
+        let mut rev = vec![IndexMap::new(); vertices.len()];
+        for edge in &edges {
+            adj[edge.src_vertex_id.0].insert((EdgeListId(0), edge.edge_id), edge.dst_vertex_id);
+            rev[edge.dst_vertex_id.0].insert((EdgeListId(0), edge.edge_id), edge.src_vertex_id);
+        }
+        Graph {
+            vertices: vertices.into_boxed_slice(),
+            edge_lists: vec![EdgeList(edges.into_boxed_slice())],
+            adj: adj.into_boxed_slice(),
+            rev: rev.into_boxed_slice(),
+            reverse_edges: None,
+            components: None,
+        }
+    }
+
+    fn build_map_model(spatial_index_type: SpatialIndexType) -> MapModel {
+        let config = MapModelConfig {
+            spatial_index_type: Some(spatial_index_type),
+            ..Default::default()
+        };
+        MapModel::new(Arc::new(build_graph()), &config).expect("failed to build map model")
+    }
+
+    #[test]
+    fn test_k_nearest() {
+        let map_model = build_map_model(SpatialIndexType::EdgeOriented);
+        let point = Point::new(0.0025, 0.0002);
+        let candidates = map_model
+            .k_nearest(&point, 2, None)
+            .expect("k nearest failed");
+        let edge_ids = candidates.iter().map(|c| c.edge_id).collect::<Vec<_>>();
+        assert_eq!(edge_ids, vec![EdgeId(0), EdgeId(1)]);
+
+        let nearest = &candidates[0];
+        assert!((nearest.coordinate.x() - 0.0025).abs() < 1e-6);
+        assert!(nearest.coordinate.y().abs() < 1e-6);
+        assert!((nearest.offset_fraction - 0.25).abs() < 1e-4);
+        assert!((nearest.distance.get::<meter>() - 22.2).abs() < 0.5);
+        assert!(candidates[1].distance > nearest.distance);
+
+        // the tolerance excludes the edge 89 meters away
+        let within_tolerance = map_model
+            .k_nearest(&point, 3, Some(Length::new::<meter>(50.0)))
+            .expect("k nearest failed");
+        assert_eq!(within_tolerance.len(), 1);
+        assert_eq!(within_tolerance[0].edge_id, EdgeId(0));
+
+        let all = map_model
+            .k_nearest(&point, 10, None)
+            .expect("k nearest failed");
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[2].edge_id, EdgeId(2));
+    }
+
+    #[test]
+    fn test_edges_within() {
+        let map_model = build_map_model(SpatialIndexType::VertexOriented);
+        let point = Point::new(0.0025, 0.0002);
+        let candidates = map_model
+            .edges_within(&point, Length::new::<meter>(100.0))
+            .expect("edges within failed");
+        let edge_ids = candidates.iter().map(|c| c.edge_id).collect::<Vec<_>>();
+        assert_eq!(edge_ids, vec![EdgeId(0), EdgeId(1)]);
+        assert!((candidates[0].offset_fraction - 0.25).abs() < 1e-4);
+
+        // the edge 89 meters away is outside of a 50 meter radius
+        let candidates = map_model
+            .edges_within(&point, Length::new::<meter>(50.0))
+            .expect("edges within failed");
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].edge_id, EdgeId(0));
+    }
+
+    #[test]
+    fn test_k_nearest_with_vertex_index() {
+        // the edge index is built on demand when the configured index is vertex-oriented
+        let map_model = build_map_model(SpatialIndexType::VertexOriented);
+        assert!(!map_model.spatial_index.is_edge_oriented());
+        let candidates = map_model
+            .k_nearest(&Point::new(0.0025, 0.0002), 1, None)
+            .expect("k nearest failed");
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].edge_id, EdgeId(0));
+        assert!(map_model
+            .get_spatial_index(&SpatialIndexType::EdgeOriented)
+            .is_edge_oriented());
+        assert!(!map_model
+            .get_spatial_index(&SpatialIndexType::VertexOriented)
+            .is_edge_oriented());
+    }
+}
@@ -5,6 +5,7 @@
 use crate::{
     config::OneOrMany,
     model::{map::SpatialIndexType, unit::DistanceUnit},
+    util::geo::crs::Crs,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -22,6 +23,11 @@ pub struct MapModelConfig {
     pub geometry: OneOrMany<MapModelGeometryConfig>,
     /// allow source-only queries for shortest path tree outputs
     pub queries_without_destinations: bool,
+    /// number of nearest valid candidates retained when map matching origin and destination
+    /// coordinates. with more than one, the search starts from every origin candidate and
+    /// ends at the best destination candidate. defaults to 1.
+    #[serde(default)]
+    pub endpoint_candidates: Option<usize>,
 }
 
 /// for a given EdgeList, the source of its geometries. this can be
@@ -33,8 +39,16 @@ pub struct MapModelConfig {
 pub enum MapModelGeometryConfig {
     FromVertices,
     FromLinestrings {
-        /// file containing edge geometries for this [`EdgeList`]
+        /// file containing edge geometries for this [`EdgeList`], either WKT LineStrings
+        /// with one row per edge or, if the file ends in `.parquet`, a GeoParquet edge layer
         geometry_input_file: String,
+        /// WKB geometry column of a GeoParquet edge layer, "geometry" by default
+        #[serde(default)]
+        geometry_column: Option<String>,
+        /// coordinate reference system of the geometries, which are transformed to
+        /// WGS84 when loaded. defaults to WGS84.
+        #[serde(default)]
+        srid: Option<Crs>,
     },
 }
 
@@ -46,6 +60,7 @@ impl Default for MapModelConfig {
             spatial_index_type: Default::default(),
             geometry: OneOrMany::One(MapModelGeometryConfig::FromVertices),
             queries_without_destinations: Default::default(),
+            endpoint_candidates: Default::default(),
         }
     }
 }
@@ -1,10 +1,13 @@
 use super::{
-    map_error::MapError, map_json_extensions::MapJsonExtensions,
-    nearest_search_result::NearestSearchResult,
+    map_error::MapError, map_json_extensions::MapJsonExtensions, map_json_key::MapJsonKey,
+    nearest_search_result::NearestSearchResult, snap_report::SnapReport,
 };
 use crate::{
     algorithm::search::SearchInstance,
-    model::{constraint::ConstraintModel, network::Edge},
+    model::{
+        constraint::ConstraintModel,
+        network::{Edge, VertexId},
+    },
 };
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
@@ -160,31 +163,22 @@ impl MatchingType {
                 // iterate through nearest values in the spatial index to this point that
                 // are within our matching tolerance and validate them with the constraint model
                 let src_point = geo::Point(query.get_origin_coordinate()?);
-                for nearest in si.map_model.spatial_index.nearest_graph_id_iter(&src_point) {
-                    match nearest {
-                        NearestSearchResult::NearestVertex(vertex_id) => {
-                            // if any of the out-edges of this vertex are valid, we can finish
-                            let edges = si.graph.out_edges(&vertex_id).iter().map(|(edge_list_id, edge_id)| si.graph.get_edge(edge_list_id, edge_id)).collect::<Result<Vec<_>, _>>().map_err(|e| MapError::MapMatchError(format!("while attempting to validate vertex id {vertex_id} for map matching, the underlying Graph model caused an error: {e}")))?;
-                            for edge in edges.into_iter() {
-                                let fm =  si.get_constraint_model(&edge.edge_list_id).map_err(|e| MapError::InternalError(format!("while map matching point {}, failed to retrieve constraint model for out edge list '{}', edge '{}': {e}", src_point.to_wkt(), edge.edge_list_id, edge.edge_id)))?;
-                                let is_valid = test_edge(edge, fm)?;
-                                if is_valid {
-                                    query.add_origin_vertex(vertex_id)?;
-                                    return Ok(());
-                                }
-                            }
-                            continue;
+                let candidates = point_candidates(&src_point, query, si)?;
+                if let Some(nearest) = candidates.first() {
+                    match nearest.nearest_search_result() {
+                        Some(NearestSearchResult::NearestVertex(vertex_id)) => {
+                            query.add_origin_vertex(vertex_id)?
                         }
-                        NearestSearchResult::NearestEdge(edge_list_id, edge_id) => {
-                            let edge = si.graph.get_edge(&edge_list_id, &edge_id).map_err(|e| MapError::MapMatchError(format!("while attempting to validate edge_list_id '{edge_list_id}', edge_id {edge_id} from nearest neighbor search for map matching, the underlying Graph model caused an error: {e}")))?;
-                            let fm =  si.get_constraint_model(&edge_list_id).map_err(|e| MapError::InternalError(format!("while map matching edge_list_id '{edge_list_id}', edge_id '{edge_id}', failed to retrieve constraint model for out edge list '{edge_list_id}', edge '{edge_id}': {e}")))?;
-                            let is_valid = test_edge(edge, fm)?;
-                            if is_valid {
-                                query.add_origin_edge(edge_list_id, edge_id)?;
-                                return Ok(());
-                            }
+                        Some(NearestSearchResult::NearestEdge(edge_list_id, edge_id)) => {
+                            query.add_origin_edge(edge_list_id, edge_id)?
                         }
+                        None => {}
+                    }
+                    query.add_snap_report(MapJsonKey::OriginSnap, nearest)?;
+                    if candidates.len() > 1 {
+                        query.add_snap_candidates(MapJsonKey::OriginCandidates, &candidates)?;
                     }
+                    return Ok(());
                 }
                 Err(MapError::MapMatchError(format!(
                     "attempted to match query origin coordinate ({}, {}) to map but exausted all possibilities",
@@ -270,31 +264,23 @@ impl MatchingType {
                     None => return Ok(MapInputResult::NotFound),
                 };
 
-                for nearest in si.map_model.spatial_index.nearest_graph_id_iter(&dst_point) {
-                    match nearest {
-                        NearestSearchResult::NearestVertex(vertex_id) => {
-                            // if any of the out-edges of this vertex are valid, we can finish
-                            let edges = si.graph.out_edges(&vertex_id).iter().map(|(edge_list_id, edge_id)| si.graph.get_edge(edge_list_id, edge_id)).collect::<Result<Vec<_>, _>>().map_err(|e| MapError::MapMatchError(format!("while attempting to validate vertex id {vertex_id} for map matching, the underlying Graph model caused an error: {e}")))?;
-                            for edge in edges.into_iter() {
-                                let fm =  si.get_constraint_model(&edge.edge_list_id).map_err(|e| MapError::InternalError(format!("while map matching point '{}', failed to retrieve constraint model for out edge list '{}', edge '{}': {e}", dst_point.to_wkt(), edge.edge_list_id, edge.edge_id)))?;
-                                let is_valid = test_edge(edge, fm)?;
-                                if is_valid {
-                                    query.add_destination_vertex(vertex_id)?;
-                                    return Ok(MapInputResult::Found);
-                                }
-                            }
-                            continue;
+                let candidates = point_candidates(&dst_point, query, si)?;
+                if let Some(nearest) = candidates.first() {
+                    match nearest.nearest_search_result() {
+                        Some(NearestSearchResult::NearestVertex(vertex_id)) => {
+                            query.add_destination_vertex(vertex_id)?
                         }
-                        NearestSearchResult::NearestEdge(edge_list_id, edge_id) => {
-                            let edge = si.graph.get_edge(&edge_list_id, &edge_id).map_err(|e| MapError::MapMatchError(format!("while attempting to validate edge id {edge_id} from nearest neighbor search for map matching, the underlying Graph model caused an error: {e}")))?;
-                            let fm =  si.get_constraint_model(&edge_list_id).map_err(|e| MapError::InternalError(format!("while map matching edge_list_id '{edge_list_id}', edge_id '{edge_id}', failed to retrieve constraint model for out edge list '{edge_list_id}', edge '{edge_id}': {e}")))?;
-                            let is_valid = test_edge(edge, fm)?;
-                            if is_valid {
-                                query.add_destination_edge(edge_list_id, edge_id)?;
-                                return Ok(MapInputResult::Found);
-                            }
+                        Some(NearestSearchResult::NearestEdge(edge_list_id, edge_id)) => {
+                            query.add_destination_edge(edge_list_id, edge_id)?
                         }
+                        None => {}
                     }
+                    query.add_snap_report(MapJsonKey::DestinationSnap, nearest)?;
+                    if candidates.len() > 1 {
+                        query
+                            .add_snap_candidates(MapJsonKey::DestinationCandidates, &candidates)?;
+                    }
+                    return Ok(MapInputResult::Found);
                 }
                 Err(MapError::MapMatchError(format!(
                     "attempted to match query destination coordinate ({}, {}) to map but exausted all possibilities",
@@ -306,6 +292,65 @@ impl MatchingType {
     }
 }
 
+/// finds the valid map matches nearest to a point, nearest first, within the map matching
+/// tolerance of the query. only the nearest match is kept unless more endpoint candidates
+/// are configured or requested on the query. a vertex is a valid match if any of its
+/// out-edges is valid for the constraint model, and an edge if it is valid itself.
+fn point_candidates(
+    point: &geo::Point<f32>,
+    query: &serde_json::Value,
+    si: &SearchInstance,
+) -> Result<Vec<SnapReport>, MapError> {
+    let tolerance = query
+        .get_max_snap_distance()?
+        .or(si.map_model.spatial_index.tolerance());
+    let k = query
+        .get_endpoint_candidates()?
+        .unwrap_or(si.map_model.endpoint_candidates);
+    let mut candidates = vec![];
+    let spatial_index = &si.map_model.spatial_index;
+    for nearest in spatial_index.nearest_graph_id_iter_within(point, tolerance) {
+        if candidates.len() >= k {
+            break;
+        }
+        match nearest {
+            NearestSearchResult::NearestVertex(vertex_id) => {
+                let edges = si.graph.out_edges(&vertex_id).iter().map(|(edge_list_id, edge_id)| si.graph.get_edge(edge_list_id, edge_id)).collect::<Result<Vec<_>, _>>().map_err(|e| MapError::MapMatchError(format!("while attempting to validate vertex id {vertex_id} for map matching, the underlying Graph model caused an error: {e}")))?;
+                for edge in edges.into_iter() {
+                    let fm = si.get_constraint_model(&edge.edge_list_id).map_err(|e| MapError::InternalError(format!("while map matching point {}, failed to retrieve constraint model for out edge list '{}', edge '{}': {e}", point.to_wkt(), edge.edge_list_id, edge.edge_id)))?;
+                    if test_edge(edge, fm)? {
+                        candidates.push(vertex_snap_report(point, &vertex_id, si)?);
+                        break;
+                    }
+                }
+            }
+            NearestSearchResult::NearestEdge(edge_list_id, edge_id) => {
+                let edge = si.graph.get_edge(&edge_list_id, &edge_id).map_err(|e| MapError::MapMatchError(format!("while attempting to validate edge_list_id '{edge_list_id}', edge_id {edge_id} from nearest neighbor search for map matching, the underlying Graph model caused an error: {e}")))?;
+                let fm = si.get_constraint_model(&edge_list_id).map_err(|e| MapError::InternalError(format!("while map matching edge_list_id '{edge_list_id}', edge_id '{edge_id}', failed to retrieve constraint model for out edge list '{edge_list_id}', edge '{edge_id}': {e}")))?;
+                if test_edge(edge, fm)? {
+                    let linestring = si.map_model.get_linestring(&edge_list_id, &edge_id)?;
+                    let report = SnapReport::from_edge(point, edge_list_id, edge_id, linestring)?;
+                    candidates.push(report);
+                }
+            }
+        }
+    }
+    Ok(candidates)
+}
+
+fn vertex_snap_report(
+    point: &geo::Point<f32>,
+    vertex_id: &VertexId,
+    si: &SearchInstance,
+) -> Result<SnapReport, MapError> {
+    let vertex = si.graph.get_vertex(vertex_id).map_err(|e| {
+        MapError::MapMatchError(format!(
+            "while reporting the snapped location of vertex id {vertex_id}, the underlying Graph model caused an error: {e}"
+        ))
+    })?;
+    SnapReport::from_vertex(point, vertex)
+}
+
 fn test_edge(edge: &Edge, fm: Arc<dyn ConstraintModel>) -> Result<bool, MapError> {
     let is_valid = fm.valid_edge(edge).map_err(|e| MapError::MapMatchError(format!("while attempting to validate edge id {} for map matching, the underlying ConstraintModel caused an error: {}", edge.edge_id, e)))?;
     Ok(is_valid)
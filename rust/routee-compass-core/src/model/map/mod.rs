@@ -1,5 +1,6 @@
 mod geometry_input;
 mod geometry_model;
+mod map_candidate;
 mod map_edge_rtree_object;
 mod map_error;
 mod map_json_extensions;
@@ -9,12 +10,14 @@ mod map_model_config;
 mod map_vertex_rtree_object;
 mod matching_type;
 mod nearest_search_result;
+mod snap_report;
 mod spatial_index;
 pub mod spatial_index_ops;
 mod spatial_index_type;
 
 pub use geometry_input::GeometryInput;
 pub use geometry_model::GeometryModel;
+pub use map_candidate::MapCandidate;
 pub use map_edge_rtree_object::MapEdgeRTreeObject;
 pub use map_error::MapError;
 pub use map_json_extensions::MapJsonExtensions;
@@ -25,5 +28,6 @@ pub use map_model_config::{MapModelConfig, MapModelGeometryConfig};
 pub use map_vertex_rtree_object::MapVertexRTreeObject;
 pub use matching_type::{MapInputResult, MatchingType};
 pub use nearest_search_result::NearestSearchResult;
+pub use snap_report::SnapReport;
 pub use spatial_index::SpatialIndex;
 pub use spatial_index_type::SpatialIndexType;
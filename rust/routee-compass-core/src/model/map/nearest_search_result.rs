@@ -2,6 +2,7 @@ use crate::model::network::{EdgeId, EdgeListId, VertexId};
 
 /// simple 'Either' return type that covers both vertex-oriented and edge-oriented
 /// search implementations.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum NearestSearchResult {
     NearestVertex(VertexId),
     NearestEdge(EdgeListId, EdgeId),
@@ -0,0 +1,94 @@
+use super::{map_error::MapError, spatial_index_ops as ops, NearestSearchResult};
+use crate::model::network::{EdgeId, EdgeListId, Vertex, VertexId};
+use crate::util::geo::haversine;
+use geo::{LineString, Point};
+use serde::{Deserialize, Serialize};
+use uom::si::length::meter;
+
+/// describes where a query coordinate was snapped to the graph during map matching,
+/// so that users can judge the quality of a match, such as an origin that was snapped
+/// to an edge hundreds of meters away.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SnapReport {
+    /// longitude of the snapped coordinate
+    pub x: f32,
+    /// latitude of the snapped coordinate
+    pub y: f32,
+    /// great circle distance from the query coordinate to the snapped coordinate
+    pub distance_meters: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vertex_id: Option<VertexId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edge_list_id: Option<EdgeListId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edge_id: Option<EdgeId>,
+}
+
+impl SnapReport {
+    /// the vertex or edge this report snapped to
+    pub fn nearest_search_result(&self) -> Option<NearestSearchResult> {
+        match (self.vertex_id, self.edge_list_id, self.edge_id) {
+            (Some(vertex_id), _, _) => Some(NearestSearchResult::NearestVertex(vertex_id)),
+            (None, Some(edge_list_id), Some(edge_id)) => {
+                Some(NearestSearchResult::NearestEdge(edge_list_id, edge_id))
+            }
+            _ => None,
+        }
+    }
+
+    /// reports a query point snapped to a graph vertex
+    pub fn from_vertex(point: &Point<f32>, vertex: &Vertex) -> Result<SnapReport, MapError> {
+        let distance = haversine::coord_distance(&point.0, &vertex.coordinate)
+            .map_err(MapError::MapMatchError)?;
+        Ok(SnapReport {
+            x: vertex.x(),
+            y: vertex.y(),
+            distance_meters: distance.get::<meter>(),
+            vertex_id: Some(vertex.vertex_id),
+            edge_list_id: None,
+            edge_id: None,
+        })
+    }
+
+    /// reports a query point snapped to the nearest point on the geometry of a graph edge
+    pub fn from_edge(
+        point: &Point<f32>,
+        edge_list_id: EdgeListId,
+        edge_id: EdgeId,
+        linestring: &LineString<f32>,
+    ) -> Result<SnapReport, MapError> {
+        let (snapped, distance) =
+            ops::nearest_point_on_linestring(linestring, point)?.ok_or_else(|| {
+                MapError::MapMatchError(format!(
+                    "geometry for edge list {edge_list_id} edge {edge_id} has no coordinates"
+                ))
+            })?;
+        Ok(SnapReport {
+            x: snapped.x(),
+            y: snapped.y(),
+            distance_meters: distance.get::<meter>(),
+            vertex_id: None,
+            edge_list_id: Some(edge_list_id),
+            edge_id: Some(edge_id),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_from_edge() {
+        let linestring = LineString::from(vec![(0.0f32, 0.0f32), (0.01, 0.0)]);
+        let point = Point::new(0.005, 0.001);
+        let report = SnapReport::from_edge(&point, EdgeListId(0), EdgeId(3), &linestring).unwrap();
+        assert!((report.x - 0.005).abs() < 1e-6);
+        assert!(report.y.abs() < 1e-6);
+        // 0.001 degrees of latitude is about 111 meters
+        assert!((report.distance_meters - 111.2).abs() < 1.0);
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["edge_id"], serde_json::json!(3));
+        assert!(json.get("vertex_id").is_none());
+    }
+}
@@ -21,7 +21,8 @@ pub enum SpatialIndex {
 }
 
 impl SpatialIndex {
-    /// build a spatial index of the declared [`SpatialIndexType`]
+    /// build a spatial index of the declared [`SpatialIndexType`]. vertices and edges outside
+    /// of the routable components of the graph are not indexed.
     pub fn build(
         spatial_index_type: &SpatialIndexType,
         graph: Arc<Graph>,
@@ -30,7 +31,13 @@ impl SpatialIndex {
     ) -> SpatialIndex {
         match spatial_index_type {
             SpatialIndexType::VertexOriented => {
-                SpatialIndex::new_vertex_oriented(&graph.clone().vertices, tolerance)
+                let vertices = graph
+                    .vertices
+                    .iter()
+                    .filter(|v| graph.is_routable_vertex(&v.vertex_id))
+                    .copied()
+                    .collect::<Vec<_>>();
+                SpatialIndex::new_vertex_oriented(&vertices, tolerance)
             }
             SpatialIndexType::EdgeOriented => {
                 SpatialIndex::new_edge_oriented(graph, geometry_models, tolerance)
@@ -60,6 +67,10 @@ impl SpatialIndex {
         let entries: Vec<MapEdgeRTreeObject> = graph
             .edges()
             .zip(geometry_models.iter().flat_map(|g| g.geometries()))
+            .filter(|(e, _)| {
+                graph.is_routable_vertex(&e.src_vertex_id)
+                    && graph.is_routable_vertex(&e.dst_vertex_id)
+            })
             .map(|(e, g)| MapEdgeRTreeObject::new(e, g))
             .collect();
         let rtree = RTree::bulk_load(entries.to_vec());
@@ -96,19 +107,29 @@ impl SpatialIndex {
     pub fn nearest_graph_id_iter<'a>(
         &'a self,
         point: &'a Point<f32>,
+    ) -> Box<dyn Iterator<Item = NearestSearchResult> + 'a> {
+        self.nearest_graph_id_iter_within(point, self.tolerance())
+    }
+
+    /// builds an iterator over map edges ordered by nearness to the given point, keeping
+    /// only those within the provided distance tolerance instead of the configured one.
+    pub fn nearest_graph_id_iter_within<'a>(
+        &'a self,
+        point: &'a Point<f32>,
+        tolerance: Option<Length>,
     ) -> Box<dyn Iterator<Item = NearestSearchResult> + 'a> {
         match self {
-            SpatialIndex::VertexOrientedIndex { rtree, tolerance } => {
+            SpatialIndex::VertexOrientedIndex { rtree, .. } => {
                 let iter = rtree
                     .nearest_neighbor_iter_with_distance_2(point)
-                    .filter(|(obj, _)| obj.test_threshold(point, tolerance).unwrap_or(false))
+                    .filter(move |(obj, _)| obj.test_threshold(point, &tolerance).unwrap_or(false))
                     .map(|(next, _)| NearestSearchResult::NearestVertex(next.vertex_id));
                 Box::new(iter)
             }
-            SpatialIndex::EdgeOrientedIndex { rtree, tolerance } => {
+            SpatialIndex::EdgeOrientedIndex { rtree, .. } => {
                 let iter = rtree
                     .nearest_neighbor_iter_with_distance_2(point)
-                    .filter(|(obj, _)| obj.test_threshold(point, tolerance).unwrap_or(false))
+                    .filter(move |(obj, _)| obj.test_threshold(point, &tolerance).unwrap_or(false))
                     .map(|(next, _)| {
                         NearestSearchResult::NearestEdge(next.edge_list_id, next.edge_id)
                     });
@@ -117,6 +138,14 @@ impl SpatialIndex {
         }
     }
 
+    /// the configured map matching distance tolerance
+    pub fn tolerance(&self) -> Option<Length> {
+        match self {
+            SpatialIndex::VertexOrientedIndex { tolerance, .. } => *tolerance,
+            SpatialIndex::EdgeOrientedIndex { tolerance, .. } => *tolerance,
+        }
+    }
+
     /// Returns true if this is an edge-oriented spatial index.
     pub fn is_edge_oriented(&self) -> bool {
         matches!(self, SpatialIndex::EdgeOrientedIndex { .. })
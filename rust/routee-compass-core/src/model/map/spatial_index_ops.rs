@@ -1,6 +1,6 @@
 use super::map_error::MapError;
 use crate::util::geo::haversine;
-use geo::{Coord, Point};
+use geo::{Coord, LineString, Point};
 use rstar::AABB;
 use uom::si::f64::Length;
 
@@ -60,13 +60,117 @@ pub fn within_threshold(
     }
 }
 
+/// finds the point on a linestring nearest to `point` along with the great circle distance
+/// between them. each segment is projected onto a local equirectangular plane centered on
+/// `point`, where longitudes are scaled by the cosine of its latitude, so that the nearest
+/// point on long or diagonal segments is found correctly away from the equator. the distance
+/// to the nearest point is then measured with the haversine formula.
+///
+/// # Returns
+///
+/// the nearest point and its distance, or None if the linestring is empty
+pub fn nearest_point_on_linestring(
+    linestring: &LineString<f32>,
+    point: &Point<f32>,
+) -> Result<Option<(Point<f32>, Length)>, MapError> {
+    let p = point.0;
+    let scale_x = (p.y as f64).to_radians().cos();
+    let project = |c: &Coord<f32>| ((c.x - p.x) as f64 * scale_x, (c.y - p.y) as f64);
+
+    let mut nearest: Option<(Coord<f32>, f64)> = None;
+    let mut update = |candidate: Coord<f32>, planar_distance_2: f64| {
+        if nearest.is_none_or(|(_, d)| planar_distance_2 < d) {
+            nearest = Some((candidate, planar_distance_2));
+        }
+    };
+    match linestring.0.as_slice() {
+        [] => return Ok(None),
+        [only] => {
+            let (x, y) = project(only);
+            update(*only, x * x + y * y);
+        }
+        coords => {
+            for segment in coords.windows(2) {
+                let (a, b) = (segment[0], segment[1]);
+                let (ax, ay) = project(&a);
+                let (bx, by) = project(&b);
+                let (dx, dy) = (bx - ax, by - ay);
+                let length_2 = dx * dx + dy * dy;
+                // the query point is the origin of the projected plane
+                let t = if length_2 > 0.0 {
+                    (-(ax * dx + ay * dy) / length_2).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+                let (x, y) = (ax + t * dx, ay + t * dy);
+                let candidate = Coord {
+                    x: a.x + (t * (b.x - a.x) as f64) as f32,
+                    y: a.y + (t * (b.y - a.y) as f64) as f32,
+                };
+                update(candidate, x * x + y * y);
+            }
+        }
+    }
+
+    match nearest {
+        None => Ok(None),
+        Some((coord, _)) => {
+            let distance =
+                haversine::coord_distance(&p, &coord).map_err(MapError::MapMatchError)?;
+            Ok(Some((Point(coord), distance)))
+        }
+    }
+}
+
+/// great circle distance from a point to the nearest point on a linestring.
+/// see [`nearest_point_on_linestring`].
+pub fn linestring_distance(
+    linestring: &LineString<f32>,
+    point: &Point<f32>,
+) -> Result<Length, MapError> {
+    nearest_point_on_linestring(linestring, point)?
+        .map(|(_, distance)| distance)
+        .ok_or_else(|| MapError::MapMatchError(String::from("linestring has no coordinates")))
+}
+
+/// tests whether a point is within the tolerance distance of a linestring. unlike
+/// [`test_threshold`], which measures to the bounding box of the linestring, this
+/// measures to the linestring itself.
+pub fn test_linestring_threshold(
+    linestring: &LineString<f32>,
+    other: &Point<f32>,
+    tolerance_distance: Length,
+) -> Result<bool, MapError> {
+    let distance = linestring_distance(linestring, other)?;
+    Ok(distance <= tolerance_distance)
+}
+
+/// fails if a point is farther than the tolerance distance from a linestring.
+pub fn within_linestring_threshold(
+    linestring: &LineString<f32>,
+    other: &Point<f32>,
+    tolerance_distance: Length,
+) -> Result<(), MapError> {
+    let this_distance = linestring_distance(linestring, other)?;
+    if this_distance > tolerance_distance {
+        Err(MapError::MapMatchError(format!(
+            "coord {:?} is {} meters from the nearest edge, exceeding the distance tolerance of {} meters",
+            other,
+            this_distance.get::<uom::si::length::meter>(),
+            tolerance_distance.get::<uom::si::length::meter>(),
+        )))
+    } else {
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     use geo::{coord, Point};
     use rstar::AABB;
     use uom::si::f64::Length;
-    use uom::si::length::meter;
+    use uom::si::length::{kilometer, meter};
 
     #[test]
     fn test_threshold_upper_corner() {
@@ -97,4 +201,61 @@ mod test {
             result.err()
         );
     }
+
+    #[test]
+    fn test_linestring_threshold_long_diagonal() {
+        // the bounding box of a long diagonal edge covers points far from the edge itself
+        let linestring = LineString::from(vec![(0.0f32, 0.0f32), (1.0, 1.0)]);
+        let off_edge = Point(coord! { x: 0.9, y: 0.1 });
+        let tolerance = Length::new::<meter>(1000.0);
+        let envelope = AABB::from_corners(
+            Point(coord! { x: 0.0, y: 0.0 }),
+            Point(coord! { x: 1.0, y: 1.0 }),
+        );
+        assert!(test_threshold(&envelope, &off_edge, tolerance).unwrap());
+        assert!(!test_linestring_threshold(&linestring, &off_edge, tolerance).unwrap());
+        assert!(within_linestring_threshold(&linestring, &off_edge, tolerance).is_err());
+
+        // roughly 63km to the nearest point on the edge, at (0.5, 0.5)
+        let (nearest, distance) = nearest_point_on_linestring(&linestring, &off_edge)
+            .unwrap()
+            .unwrap();
+        assert!((nearest.x() - 0.5).abs() < 1e-3 && (nearest.y() - 0.5).abs() < 1e-3);
+        assert!((distance.get::<kilometer>() - 62.9).abs() < 0.5);
+
+        let near_edge = Point(coord! { x: 0.5, y: 0.5001 });
+        assert!(test_linestring_threshold(&linestring, &near_edge, tolerance).unwrap());
+        assert!(within_linestring_threshold(&linestring, &near_edge, tolerance).is_ok());
+    }
+
+    #[test]
+    fn test_linestring_distance_high_latitude() {
+        // compare against the minimum haversine distance to points sampled densely along
+        // a long diagonal segment, where unscaled longitudes would pick the wrong point
+        let (ax, ay, bx, by) = (10.0f32, 60.0f32, 12.0f32, 61.0f32);
+        let linestring = LineString::from(vec![(ax, ay), (bx, by)]);
+        let point = Point(coord! { x: 11.5, y: 60.2 });
+        let expected = (0..=10_000)
+            .map(|i| {
+                let t = i as f32 / 10_000.0;
+                let c = coord! { x: ax + t * (bx - ax), y: ay + t * (by - ay) };
+                haversine::coord_distance(&point.0, &c)
+                    .unwrap()
+                    .get::<meter>()
+            })
+            .fold(f64::INFINITY, f64::min);
+        let distance = linestring_distance(&linestring, &point)
+            .unwrap()
+            .get::<meter>();
+        assert!(
+            (distance - expected).abs() / expected < 0.01,
+            "expected {expected}, found {distance}"
+        );
+    }
+
+    #[test]
+    fn test_linestring_distance_empty() {
+        let linestring: LineString<f32> = LineString::new(vec![]);
+        assert!(linestring_distance(&linestring, &Point(coord! { x: 0.0, y: 0.0 })).is_err());
+    }
 }
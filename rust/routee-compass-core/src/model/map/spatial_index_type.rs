@@ -1,7 +1,7 @@
 //! configuration value to declare the type of [`super::SpatialIndex`] to build.
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
 pub enum SpatialIndexType {
     #[default]
     #[serde(rename = "vertex")]
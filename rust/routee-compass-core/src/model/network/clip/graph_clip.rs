@@ -0,0 +1,322 @@
+use super::GraphClipConfig;
+use crate::config::OneOrMany;
+use crate::model::network::{EdgeListConfig, GraphConfig, NetworkError};
+use crate::util::fs::{fs_utils, parquet_utils};
+use crate::util::geo::crs::Crs;
+use flate2::{write::GzEncoder, Compression};
+use geo::{Coord, Geometry, Intersects, Point};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+/// the graph files written by [`clip_graph`].
+#[derive(Debug, Clone)]
+pub struct ClippedGraph {
+    /// graph configuration reading from the clipped files, without a clip
+    pub config: GraphConfig,
+    /// the path of each clipped file by the path of the file it was clipped from
+    pub replaced_files: HashMap<String, String>,
+}
+
+/// writes the portion of a graph within the clip region to the clip output directory.
+///
+/// vertices are kept if their coordinate intersects the region, and edges are kept if both
+/// of their endpoints are kept. kept vertices and edges are assigned new ids in the order
+/// they appear in their files, and the `vertex_id`, `edge_id`, `src_vertex_id` and
+/// `dst_vertex_id` columns are rewritten with these ids. all other columns are copied.
+/// the attribute files of each edge list have one row per edge, with no header, and the
+/// rows of the kept edges are copied. files are written with gzip or zstd compression if
+/// the file they were clipped from was compressed with it.
+///
+/// # Arguments
+///
+/// * `config` - graph to clip
+/// * `clip` - region to clip the graph to and directory to write the clipped files to
+///
+/// # Returns
+///
+/// the configuration of the clipped graph and the paths of the clipped files
+pub fn clip_graph(
+    config: &GraphConfig,
+    clip: &GraphClipConfig,
+) -> Result<ClippedGraph, NetworkError> {
+    let region = clip.region().map_err(NetworkError::DatasetError)?;
+    let mut graph_files = std::iter::once(&config.vertex_list_input_file)
+        .chain(config.edge_list.iter().map(|el| &el.input_file));
+    if let Some(path) = graph_files.find(|path| parquet_utils::is_parquet(path)) {
+        return Err(NetworkError::DatasetError(format!(
+            "graph clipping requires CSV vertex and edge lists, found {path}"
+        )));
+    }
+    let output_directory = Path::new(&clip.output_directory);
+    std::fs::create_dir_all(output_directory)?;
+    let mut replaced_files: HashMap<String, String> = HashMap::new();
+
+    let vertex_file = output_path(output_directory, "", &config.vertex_list_input_file);
+    let vertex_ids = clip_vertices(
+        &config.vertex_list_input_file,
+        &vertex_file,
+        config.srid,
+        &region,
+    )?;
+    replaced_files.insert(config.vertex_list_input_file.clone(), vertex_file.clone());
+
+    let mut edge_lists = vec![];
+    let mut edge_count = 0;
+    for (idx, edge_list) in config.edge_list.iter().enumerate() {
+        let prefix = format!("edge_list_{idx}_");
+        let edge_file = output_path(output_directory, &prefix, &edge_list.input_file);
+        let rows = clip_edges(&edge_list.input_file, &edge_file, &vertex_ids)?;
+        replaced_files.insert(edge_list.input_file.clone(), edge_file.clone());
+        edge_count += rows.len();
+
+        let attribute_files = edge_list
+            .attribute_input_files
+            .as_ref()
+            .map(|files| {
+                files
+                    .iter()
+                    .map(|input| {
+                        let output = output_path(output_directory, &prefix, input);
+                        clip_rows(input, &output, &rows)?;
+                        replaced_files.insert(input.clone(), output.clone());
+                        Ok(output)
+                    })
+                    .collect::<Result<Vec<_>, NetworkError>>()
+            })
+            .transpose()?;
+        edge_lists.push(EdgeListConfig {
+            input_file: edge_file,
+            columns: None,
+            attribute_input_files: attribute_files,
+        });
+    }
+    log::info!(
+        "clipped graph to {} vertices and {} edges in {}",
+        vertex_ids.len(),
+        edge_count,
+        clip.output_directory
+    );
+
+    let edge_list = match &config.edge_list {
+        OneOrMany::One(_) => OneOrMany::One(edge_lists.remove(0)),
+        OneOrMany::Many(_) => OneOrMany::Many(edge_lists),
+    };
+    let clipped = GraphConfig {
+        vertex_list_input_file: vertex_file,
+        edge_list,
+        clip: None,
+        ..config.clone()
+    };
+    Ok(ClippedGraph {
+        config: clipped,
+        replaced_files,
+    })
+}
+
+/// writes the vertices intersecting the region, returning the new id of each kept vertex
+/// by its original id.
+fn clip_vertices(
+    input: &str,
+    output: &str,
+    srid: Option<Crs>,
+    region: &Geometry<f64>,
+) -> Result<HashMap<usize, usize>, NetworkError> {
+    let mut reader = csv::Reader::from_reader(open_reader(input)?);
+    let headers = reader.headers()?.clone();
+    let [id_col, x_col, y_col] = columns(&headers, ["vertex_id", "x", "y"], input)?;
+    let mut writer = csv::Writer::from_writer(ClipWriter::create(output, input)?);
+    writer.write_record(&headers)?;
+    let mut vertex_ids = HashMap::new();
+    for record in reader.records() {
+        let record = record?;
+        let vertex_id: usize = parse(&record, id_col, input)?;
+        let mut coord = Coord {
+            x: parse(&record, x_col, input)?,
+            y: parse(&record, y_col, input)?,
+        };
+        if let Some(crs) = srid.filter(|crs| !crs.is_wgs84()) {
+            coord = crs
+                .to_wgs84(coord)
+                .map_err(|e| NetworkError::DatasetError(format!("vertex {vertex_id}: {e}")))?;
+        }
+        if region.intersects(&Point::from(coord)) {
+            let new_id = vertex_ids.len();
+            vertex_ids.insert(vertex_id, new_id);
+            writer.write_record(replace_columns(&record, &[(id_col, new_id)]))?;
+        }
+    }
+    finish(writer)?;
+    Ok(vertex_ids)
+}
+
+/// writes the edges with both endpoints kept, returning the row index of each kept edge.
+fn clip_edges(
+    input: &str,
+    output: &str,
+    vertex_ids: &HashMap<usize, usize>,
+) -> Result<Vec<usize>, NetworkError> {
+    let mut reader = csv::Reader::from_reader(open_reader(input)?);
+    let headers = reader.headers()?.clone();
+    let [id_col, src_col, dst_col] = columns(
+        &headers,
+        ["edge_id", "src_vertex_id", "dst_vertex_id"],
+        input,
+    )?;
+    let mut writer = csv::Writer::from_writer(ClipWriter::create(output, input)?);
+    writer.write_record(&headers)?;
+    let mut rows = vec![];
+    for (row, record) in reader.records().enumerate() {
+        let record = record?;
+        let src: usize = parse(&record, src_col, input)?;
+        let dst: usize = parse(&record, dst_col, input)?;
+        if let (Some(src), Some(dst)) = (vertex_ids.get(&src), vertex_ids.get(&dst)) {
+            let replacements = [(id_col, rows.len()), (src_col, *src), (dst_col, *dst)];
+            writer.write_record(replace_columns(&record, &replacements))?;
+            rows.push(row);
+        }
+    }
+    finish(writer)?;
+    Ok(rows)
+}
+
+/// writes the lines of a header-less, row-indexed file at the given (sorted) row indices.
+fn clip_rows(input: &str, output: &str, rows: &[usize]) -> Result<(), NetworkError> {
+    let reader = BufReader::new(open_reader(input)?);
+    let mut writer = ClipWriter::create(output, input)?;
+    let mut remaining = rows.iter().peekable();
+    for (idx, line) in reader.lines().enumerate() {
+        if remaining.peek().is_none() {
+            break;
+        }
+        let line = line?;
+        if remaining.peek() == Some(&&idx) {
+            writeln!(writer, "{line}")?;
+            remaining.next();
+        }
+    }
+    if remaining.peek().is_some() {
+        return Err(NetworkError::DatasetError(format!(
+            "edge attribute file {input} has fewer rows than its edge list"
+        )));
+    }
+    writer.finish()?;
+    Ok(())
+}
+
+fn output_path(output_directory: &Path, prefix: &str, input: &str) -> String {
+    let filename = Path::new(input)
+        .file_name()
+        .map(|f| f.to_string_lossy().to_string())
+        .unwrap_or_default();
+    output_directory
+        .join(format!("{prefix}{filename}"))
+        .to_string_lossy()
+        .to_string()
+}
+
+fn open_reader(path: &str) -> Result<Box<dyn Read>, NetworkError> {
+    fs_utils::open_reader(path)
+        .map_err(|e| NetworkError::DatasetError(format!("failure opening graph file {path}: {e}")))
+}
+
+fn columns<const N: usize>(
+    headers: &csv::StringRecord,
+    names: [&str; N],
+    path: &str,
+) -> Result<[usize; N], NetworkError> {
+    let mut result = [0; N];
+    for (idx, name) in names.iter().enumerate() {
+        result[idx] = headers.iter().position(|h| h == *name).ok_or_else(|| {
+            NetworkError::DatasetError(format!("graph file {path} is missing column '{name}'"))
+        })?;
+    }
+    Ok(result)
+}
+
+fn parse<T: std::str::FromStr>(
+    record: &csv::StringRecord,
+    col: usize,
+    path: &str,
+) -> Result<T, NetworkError>
+where
+    T::Err: std::fmt::Display,
+{
+    let value = record.get(col).unwrap_or_default();
+    value.trim().parse::<T>().map_err(|e| {
+        NetworkError::DatasetError(format!(
+            "graph file {path} has invalid value '{value}' in row {:?}: {e}",
+            record.position().map(|p| p.line())
+        ))
+    })
+}
+
+fn replace_columns(record: &csv::StringRecord, replacements: &[(usize, usize)]) -> Vec<String> {
+    record
+        .iter()
+        .enumerate()
+        .map(
+            |(col, value)| match replacements.iter().find(|(c, _)| *c == col) {
+                Some((_, id)) => id.to_string(),
+                None => value.to_string(),
+            },
+        )
+        .collect()
+}
+
+fn finish(writer: csv::Writer<ClipWriter>) -> Result<(), NetworkError> {
+    let inner = writer.into_inner().map_err(|e| NetworkError::IOError {
+        source: e.into_error(),
+    })?;
+    inner.finish()?;
+    Ok(())
+}
+
+/// writes a clipped file, compressed if the file it was clipped from is compressed
+enum ClipWriter {
+    Plain(BufWriter<File>),
+    Gzip(Box<GzEncoder<BufWriter<File>>>),
+    Zstd(Box<zstd::Encoder<'static, BufWriter<File>>>),
+}
+
+impl ClipWriter {
+    fn create(output: &str, input: &str) -> Result<ClipWriter, NetworkError> {
+        let file = BufWriter::new(File::create(output)?);
+        if fs_utils::is_gzip(input) {
+            let encoder = GzEncoder::new(file, Compression::default());
+            Ok(ClipWriter::Gzip(Box::new(encoder)))
+        } else if fs_utils::is_zstd(input) {
+            let encoder = zstd::Encoder::new(file, 0)?;
+            Ok(ClipWriter::Zstd(Box::new(encoder)))
+        } else {
+            Ok(ClipWriter::Plain(file))
+        }
+    }
+
+    fn finish(self) -> std::io::Result<()> {
+        match self {
+            ClipWriter::Plain(mut w) => w.flush(),
+            ClipWriter::Gzip(w) => (*w).finish()?.flush(),
+            ClipWriter::Zstd(w) => (*w).finish()?.flush(),
+        }
+    }
+}
+
+impl Write for ClipWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            ClipWriter::Plain(w) => w.write(buf),
+            ClipWriter::Gzip(w) => w.write(buf),
+            ClipWriter::Zstd(w) => w.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            ClipWriter::Plain(w) => w.flush(),
+            ClipWriter::Gzip(w) => w.flush(),
+            ClipWriter::Zstd(w) => w.flush(),
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use geo::{Geometry, Rect};
+use serde::{Deserialize, Serialize};
+use wkt::TryFromWkt;
+
+/// configures loading the portion of a graph within a region, such as a regional app
+/// built from a national dataset. exactly one of `bbox` or `polygon` is required.
+///
+/// the vertices within the region and the edges with both endpoints within it are written
+/// to the `output_directory` with new, consecutive vertex and edge ids, along with the rows
+/// of each edge list's `attribute_input_files`, and the app is loaded from those files.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct GraphClipConfig {
+    /// WGS84 bounding box as `[min_x, min_y, max_x, max_y]`
+    pub bbox: Option<[f64; 4]>,
+    /// WGS84 polygon or multipolygon as a WKT string
+    pub polygon: Option<String>,
+    /// directory to write the clipped graph files to
+    pub output_directory: String,
+}
+
+impl GraphClipConfig {
+    /// the region to clip the graph to
+    pub fn region(&self) -> Result<Geometry<f64>, String> {
+        match (&self.bbox, &self.polygon) {
+            (Some([min_x, min_y, max_x, max_y]), None) => {
+                if !(min_x <= max_x && min_y <= max_y) {
+                    return Err(format!(
+                        "clip bbox must be [min_x, min_y, max_x, max_y], found {:?}",
+                        [min_x, min_y, max_x, max_y]
+                    ));
+                }
+                let rect = Rect::new((*min_x, *min_y), (*max_x, *max_y));
+                Ok(Geometry::Polygon(rect.to_polygon()))
+            }
+            (None, Some(wkt)) => {
+                let geometry = Geometry::<f64>::try_from_wkt_str(wkt)
+                    .map_err(|e| format!("failure decoding clip polygon: {e}"))?;
+                match geometry {
+                    Geometry::Polygon(_) | Geometry::MultiPolygon(_) => Ok(geometry),
+                    _ => Err(String::from(
+                        "clip polygon must be a WKT Polygon or MultiPolygon",
+                    )),
+                }
+            }
+            _ => Err(String::from(
+                "graph clip requires exactly one of 'bbox' or 'polygon'",
+            )),
+        }
+    }
+}
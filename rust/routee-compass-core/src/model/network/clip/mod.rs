@@ -0,0 +1,5 @@
+mod graph_clip;
+mod graph_clip_config;
+
+pub use graph_clip::{clip_graph, ClippedGraph};
+pub use graph_clip_config::GraphClipConfig;
@@ -3,8 +3,10 @@ use std::{fmt::Display, path::Path};
 use kdam::tqdm;
 
 use crate::{
-    model::network::{Edge, EdgeConfig, EdgeId, EdgeListId, NetworkError},
-    util::fs::read_utils,
+    model::network::{
+        geoparquet, Edge, EdgeConfig, EdgeId, EdgeListConfig, EdgeListId, NetworkError,
+    },
+    util::fs::{parquet_utils, read_utils},
 };
 
 /// An adjacency list covering some list of edges drawn over the Graph vertex list.
@@ -33,6 +35,24 @@ impl EdgeList {
         Ok(edge_list)
     }
 
+    /// reads the edge list of an [`EdgeListConfig`], either a CSV edge list or, if the
+    /// file ends in `.parquet`, a GeoParquet edge layer.
+    pub fn from_config(
+        config: &EdgeListConfig,
+        edge_list_id: EdgeListId,
+    ) -> Result<EdgeList, NetworkError> {
+        if parquet_utils::is_geopackage(&config.input_file) {
+            Err(NetworkError::DatasetError(
+                parquet_utils::geopackage_unsupported(&config.input_file),
+            ))
+        } else if parquet_utils::is_parquet(&config.input_file) {
+            let columns = config.columns.clone().unwrap_or_default();
+            geoparquet::read_edges(&config.input_file, &columns, edge_list_id)
+        } else {
+            EdgeList::new(&config.input_file, edge_list_id)
+        }
+    }
+
     /// number of edges in the Graph
     pub fn len(&self) -> usize {
         self.0.len()
@@ -0,0 +1,78 @@
+use super::{
+    Edge, EdgeColumns, EdgeId, EdgeList, EdgeListId, NetworkError, Vertex, VertexColumns, VertexId,
+};
+use crate::util::fs::parquet_utils;
+use geo::Geometry;
+use kdam::tqdm;
+use uom::si::{f64::Length, length::meter};
+
+/// reads the vertices of a graph from a GeoParquet vertex layer.
+pub fn read_vertices(path: &str, columns: &VertexColumns) -> Result<Box<[Vertex]>, NetworkError> {
+    let failed = |e: String| NetworkError::DatasetError(format!("vertex layer {path}: {e}"));
+    let rows = tqdm!(
+        parquet_utils::row_iter(path).map_err(failed)?,
+        desc = format!("graph vertices: {path}")
+    );
+    let vertices = rows
+        .enumerate()
+        .map(|(idx, row)| {
+            let row = row.map_err(|e| failed(e.to_string()))?;
+            let vertex_id = match &columns.vertex_id {
+                Some(column) => parquet_utils::get_usize(&row, column).map_err(failed)?,
+                None => idx,
+            };
+            let (x, y) = match (&columns.x, &columns.y) {
+                (Some(x), Some(y)) => (
+                    parquet_utils::get_f64(&row, x).map_err(failed)?,
+                    parquet_utils::get_f64(&row, y).map_err(failed)?,
+                ),
+                _ => match parquet_utils::get_geometry(&row, &columns.geometry).map_err(failed)? {
+                    Geometry::Point(p) => (p.x(), p.y()),
+                    _ => {
+                        return Err(failed(format!(
+                            "vertex {vertex_id} geometry is not a Point"
+                        )))
+                    }
+                },
+            };
+            Ok(Vertex::new(vertex_id, x as f32, y as f32))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    eprintln!();
+    Ok(vertices.into_boxed_slice())
+}
+
+/// reads an edge list from a GeoParquet edge layer.
+pub fn read_edges(
+    path: &str,
+    columns: &EdgeColumns,
+    edge_list_id: EdgeListId,
+) -> Result<EdgeList, NetworkError> {
+    let failed = |e: String| NetworkError::DatasetError(format!("edge layer {path}: {e}"));
+    let rows = tqdm!(
+        parquet_utils::row_iter(path).map_err(failed)?,
+        desc = format!("graph edge list {edge_list_id}: {path}")
+    );
+    let edges = rows
+        .enumerate()
+        .map(|(idx, row)| {
+            let row = row.map_err(|e| failed(e.to_string()))?;
+            let edge_id = match &columns.edge_id {
+                Some(column) => parquet_utils::get_usize(&row, column).map_err(failed)?,
+                None => idx,
+            };
+            let src = parquet_utils::get_usize(&row, &columns.src_vertex_id).map_err(failed)?;
+            let dst = parquet_utils::get_usize(&row, &columns.dst_vertex_id).map_err(failed)?;
+            let distance = parquet_utils::get_f64(&row, &columns.distance).map_err(failed)?;
+            Ok(Edge {
+                edge_list_id,
+                edge_id: EdgeId(edge_id),
+                src_vertex_id: VertexId(src),
+                dst_vertex_id: VertexId(dst),
+                distance: Length::new::<meter>(distance),
+            })
+        })
+        .collect::<Result<Vec<_>, NetworkError>>()?;
+    eprintln!();
+    Ok(EdgeList(edges.into_boxed_slice()))
+}
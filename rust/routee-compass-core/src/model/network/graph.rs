@@ -1,12 +1,18 @@
 use super::{Edge, EdgeId, EdgeList, NetworkError, Vertex, VertexId};
+use crate::algorithm::component::scc::strongly_connected_component_ids;
 use crate::algorithm::search::Direction;
+use crate::model::network::geoparquet;
+use crate::model::network::validate::{self, GraphValidationReport};
 use crate::model::network::EdgeListId;
-use crate::model::network::GraphConfig;
-use crate::util::fs::read_utils;
+use crate::model::network::{GraphComponents, GraphConfig};
+use crate::util::fs::{parquet_utils, read_utils};
+use crate::util::geo::InternalCoord;
 use indexmap::IndexMap;
 use itertools::Itertools;
 use kdam::tqdm;
 use kdam::Bar;
+use rayon::prelude::*;
+use std::time::Instant;
 
 /// Road network topology represented as an adjacency list.
 /// The `EdgeId` and `VertexId` values correspond to edge and
@@ -18,6 +24,8 @@ use kdam::Bar;
 /// * `rev` - the reverse-oriented adjacency list
 /// * `edges` - for each `EdgeId`, the corresponding `Edge` record
 /// * `vertices` - for each `VertexId`, the corresponding `Vertex` record
+/// * `reverse_edges` - if built, for each `EdgeListId` and `EdgeId`, the edge traversing the same vertices in the opposite direction
+/// * `components` - if built, the strongly connected component of each vertex
 ///
 /// # Performance
 ///
@@ -32,24 +40,86 @@ pub struct Graph {
     pub edge_lists: Vec<EdgeList>,
     pub adj: DenseAdjacencyList,
     pub rev: DenseAdjacencyList,
+    pub reverse_edges: Option<ReverseEdgeLookup>,
+    pub components: Option<GraphComponents>,
 }
 
 /// a graph adjacency list with an entry (possibly empty) for each VertexId in the Graph.
 pub type DenseAdjacencyList = Box<[IndexMap<(EdgeListId, EdgeId), VertexId>]>;
 
+/// for each EdgeListId, a lookup by EdgeId for the reverse of that edge within the same
+/// edge list, if one exists.
+pub type ReverseEdgeLookup = Box<[Box<[Option<EdgeId>]>]>;
+
+/// reads the vertex list of a graph, either a CSV vertex list or, if the file ends in
+/// `.parquet`, a GeoParquet vertex layer.
+fn read_vertices(config: &GraphConfig) -> Result<Box<[Vertex]>, NetworkError> {
+    let path = &config.vertex_list_input_file;
+    if parquet_utils::is_geopackage(path) {
+        Err(NetworkError::DatasetError(
+            parquet_utils::geopackage_unsupported(path),
+        ))
+    } else if parquet_utils::is_parquet(path) {
+        let columns = config.vertex_list_columns.clone().unwrap_or_default();
+        geoparquet::read_vertices(path, &columns)
+    } else {
+        read_utils::from_csv(
+            path,
+            true,
+            Some(Bar::builder().desc(format!("graph vertices: {path}"))),
+            None,
+        )
+        .map_err(|e| NetworkError::CsvError { source: e })
+    }
+}
+
+/// reads a graph input file, logging the time spent reading it
+fn with_file_timing<T>(
+    path: &str,
+    read: impl FnOnce() -> Result<T, NetworkError>,
+) -> Result<T, NetworkError> {
+    let start = Instant::now();
+    let result = read();
+    log::info!(
+        "read graph file {path} in {:.2} seconds",
+        start.elapsed().as_secs_f64()
+    );
+    result
+}
+
 impl TryFrom<&GraphConfig> for Graph {
     type Error = NetworkError;
 
     /// create a graph from a JSON argument. it should be an object that contains
     /// two keys, one for each file path.
     fn try_from(config: &GraphConfig) -> Result<Self, Self::Error> {
-        let vertices: Box<[Vertex]> = read_utils::from_csv(
-            &config.vertex_list_input_file,
-            true,
-            Some(Bar::builder().desc(format!("graph vertices: {}", config.vertex_list_input_file))),
-            None,
-        )
-        .map_err(|e| NetworkError::CsvError { source: e })?;
+        // the vertex list and each edge list are independent files, read concurrently
+        let (vertices_result, edge_lists_result) = rayon::join(
+            || with_file_timing(&config.vertex_list_input_file, || read_vertices(config)),
+            || {
+                let edge_list_configs = config.edge_list.iter().collect_vec();
+                edge_list_configs
+                    .into_par_iter()
+                    .enumerate()
+                    .map(|(idx, c)| {
+                        with_file_timing(&c.input_file, || {
+                            EdgeList::from_config(c, EdgeListId(idx))
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            },
+        );
+        let mut vertices = vertices_result?;
+        let edge_lists = edge_lists_result?;
+        if let Some(crs) = config.srid.filter(|crs| !crs.is_wgs84()) {
+            log::info!("transforming graph vertices from {crs} to WGS84");
+            for vertex in vertices.iter_mut() {
+                let coordinate = crs.coord_to_wgs84(vertex.coordinate.0).map_err(|e| {
+                    NetworkError::DatasetError(format!("vertex {}: {e}", vertex.vertex_id))
+                })?;
+                vertex.coordinate = InternalCoord(coordinate);
+            }
+        }
 
         let mut adj: Vec<IndexMap<(EdgeListId, EdgeId), VertexId>> =
             vec![IndexMap::new(); vertices.len()];
@@ -59,13 +129,6 @@ impl TryFrom<&GraphConfig> for Graph {
         // this callback is invoked when reading each line of the edge list input file and
         // inserts the adjacency information of the edge (src)-[edge]->(dst).
 
-        let edge_lists = config
-            .edge_list
-            .iter()
-            .enumerate()
-            .map(|(idx, c)| EdgeList::new(&c.input_file, EdgeListId(idx)))
-            .collect::<Result<Vec<_>, _>>()?;
-
         let total_edges = edge_lists.iter().map(|el| el.len()).sum::<usize>();
         log::info!(
             "loaded {} edge lists with a total of {} edges",
@@ -73,6 +136,24 @@ impl TryFrom<&GraphConfig> for Graph {
             total_edges
         );
 
+        if let Some(validation) = &config.validation {
+            let dangling_edges = validate::find_dangling_edges(vertices.len(), &edge_lists);
+            if !dangling_edges.is_empty() {
+                let report = GraphValidationReport {
+                    dangling_edges,
+                    ..Default::default()
+                };
+                if let Some(path) = &validation.report_output_file {
+                    validate::write_report(&report, path)?;
+                }
+                return Err(NetworkError::DatasetError(format!(
+                    "graph validation failed with {} edges referencing vertices missing from the vertex list, first: {:?}",
+                    report.dangling_edges.len(),
+                    report.dangling_edges[0]
+                )));
+            }
+        }
+
         let build_adjacencies_iter = tqdm!(
             edge_lists.iter().flat_map(|el| el.edges()),
             desc = "building adjacencies",
@@ -95,13 +176,50 @@ impl TryFrom<&GraphConfig> for Graph {
             )));
         }
 
-        let graph = Graph {
+        let mut graph = Graph {
             edge_lists,
             vertices,
             adj: adj.into_boxed_slice(),
             rev: rev.into_boxed_slice(),
+            reverse_edges: None,
+            components: None,
         };
 
+        // validation runs before reverse edges are paired so that dropped edges are not paired
+        if let Some(validation) = &config.validation {
+            validate::validate_graph(&mut graph, validation)?;
+        }
+
+        if let Some(scc_config) = &config.strongly_connected_components {
+            let components =
+                GraphComponents::new(strongly_connected_component_ids(&graph), scc_config);
+            let outside = graph
+                .edges()
+                .filter(|e| {
+                    !components.is_routable(&e.src_vertex_id)
+                        || !components.is_routable(&e.dst_vertex_id)
+                })
+                .map(|e| (e.edge_list_id, e.edge_id))
+                .collect_vec();
+            let n_dropped = outside
+                .iter()
+                .filter(|(edge_list_id, edge_id)| graph.drop_edge(edge_list_id, edge_id))
+                .count();
+            log::info!(
+                "found {} strongly connected components, the largest with {} vertices. \
+                routing on {} components, {} edges outside of them were dropped",
+                components.n_components(),
+                components.component_size(0),
+                components.n_routable_components(),
+                n_dropped
+            );
+            graph.components = Some(components);
+        }
+
+        if config.prevent_u_turns.unwrap_or_default() {
+            graph.reverse_edges = Some(build_reverse_edges(&graph.edge_lists, &graph.adj));
+        }
+
         Ok(graph)
     }
 }
@@ -324,6 +442,57 @@ impl Graph {
         }
     }
 
+    /// finds the reverse of an edge, which connects the same vertices in the opposite
+    /// direction within the same edge list. returns None if the edge has no reverse
+    /// or if reverse edges were not built for this graph.
+    pub fn reverse_edge(&self, edge: &Edge) -> Option<EdgeId> {
+        self.reverse_edges
+            .as_ref()?
+            .get(edge.edge_list_id.0)?
+            .get(edge.edge_id.0)
+            .copied()
+            .flatten()
+    }
+
+    /// removes an edge from the forward and reverse adjacencies so that searches do not
+    /// traverse it. the edge remains in its edge list so that edge ids are unchanged.
+    /// returns false if the edge is not in the adjacencies.
+    pub fn drop_edge(&mut self, edge_list_id: &EdgeListId, edge_id: &EdgeId) -> bool {
+        let (src, dst) = match self.get_edge(edge_list_id, edge_id) {
+            Ok(edge) => (edge.src_vertex_id, edge.dst_vertex_id),
+            Err(_) => return false,
+        };
+        let key = (*edge_list_id, *edge_id);
+        let removed = self
+            .adj
+            .get_mut(src.0)
+            .is_some_and(|out_edges| out_edges.shift_remove(&key).is_some());
+        if let Some(in_edges) = self.rev.get_mut(dst.0) {
+            in_edges.shift_remove(&key);
+        }
+        removed
+    }
+
+    /// true if searches may begin or end at this vertex. when the graph is restricted to
+    /// its largest strongly connected components, vertices outside of them are not routable.
+    pub fn is_routable_vertex(&self, vertex_id: &VertexId) -> bool {
+        self.components
+            .as_ref()
+            .is_none_or(|c| c.is_routable(vertex_id))
+    }
+
+    /// the strongly connected component of a vertex, if components were computed
+    pub fn component_id(&self, vertex_id: &VertexId) -> Option<usize> {
+        self.components.as_ref()?.component_id(vertex_id)
+    }
+
+    /// tests whether traversing `next_edge` immediately after `previous_edge`
+    /// would reverse back over the same road segment.
+    pub fn is_u_turn(&self, previous_edge: &Edge, next_edge: &Edge) -> bool {
+        previous_edge.edge_list_id == next_edge.edge_list_id
+            && self.reverse_edge(previous_edge) == Some(next_edge.edge_id)
+    }
+
     /// helper function to give the incident vertex to an edge based on a
     /// traversal direction.
     ///
@@ -429,6 +598,38 @@ impl Graph {
     }
 }
 
+/// pairs each edge with an edge in the same edge list that connects the same
+/// vertices in the opposite direction. self-loops are not paired.
+fn build_reverse_edges(
+    edge_lists: &[EdgeList],
+    adj: &[IndexMap<(EdgeListId, EdgeId), VertexId>],
+) -> ReverseEdgeLookup {
+    edge_lists
+        .iter()
+        .enumerate()
+        .map(|(idx, edge_list)| {
+            let edge_list_id = EdgeListId(idx);
+            edge_list
+                .edges()
+                .map(|edge| {
+                    adj.get(edge.dst_vertex_id.0).and_then(|out_edges| {
+                        out_edges
+                            .iter()
+                            .find(|((el_id, e_id), dst)| {
+                                *el_id == edge_list_id
+                                    && **dst == edge.src_vertex_id
+                                    && *e_id != edge.edge_id
+                            })
+                            .map(|((_, e_id), _)| *e_id)
+                    })
+                })
+                .collect::<Vec<_>>()
+                .into_boxed_slice()
+        })
+        .collect::<Vec<_>>()
+        .into_boxed_slice()
+}
+
 /// Appends an edge to an adjacency list.
 ///
 /// # Arguments
@@ -619,4 +820,26 @@ mod tests {
         assert_eq!(adj[0].len(), 1); // Still only one entry
         assert_eq!(adj[0][&(EdgeListId(0), EdgeId(0))], VertexId(2)); // Updated target
     }
+
+    #[test]
+    fn test_build_reverse_edges() {
+        let edges = vec![
+            create_test_edge(0, 0, 0, 1),
+            create_test_edge(0, 1, 1, 0),
+            create_test_edge(0, 2, 1, 2),
+        ];
+        let mut adj: Vec<IndexMap<(EdgeListId, EdgeId), VertexId>> = vec![IndexMap::new(); 3];
+        for edge in edges.iter() {
+            append_to_adjacency(edge, &mut adj, true).unwrap();
+        }
+        let edge_lists = vec![EdgeList(edges.into_boxed_slice())];
+
+        let result = build_reverse_edges(&edge_lists, &adj);
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(
+            result[0].as_ref(),
+            &[Some(EdgeId(1)), Some(EdgeId(0)), None]
+        );
+    }
 }
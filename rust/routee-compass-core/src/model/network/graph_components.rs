@@ -0,0 +1,121 @@
+use super::VertexId;
+use serde::{Deserialize, Serialize};
+
+/// configures the strongly connected components computed when the graph is loaded.
+/// origins and destinations that map match to a small component, such as an island of
+/// service roads, often have no path to the rest of the network.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StronglyConnectedComponentsConfig {
+    /// labels each vertex with its component for diagnostics without restricting the graph
+    Tag,
+    /// restricts the routable graph to the largest component
+    Largest,
+    /// restricts the routable graph to components with at least `min_size` vertices
+    MinSize { min_size: usize },
+}
+
+/// the strongly connected component of each vertex in a graph. component ids are
+/// ordered by decreasing size, so component 0 is the largest. vertices outside of the
+/// routable components have no edges in the graph adjacencies and are not map matched.
+#[derive(Debug, Clone)]
+pub struct GraphComponents {
+    component_ids: Box<[usize]>,
+    sizes: Box<[usize]>,
+    n_routable: usize,
+}
+
+impl GraphComponents {
+    /// builds the component lookup from the component id of each vertex, applying the
+    /// restriction in the provided configuration.
+    /// component ids must be ordered by decreasing size, as produced by
+    /// [`crate::algorithm::component::scc::strongly_connected_component_ids`].
+    pub fn new(component_ids: Vec<usize>, config: &StronglyConnectedComponentsConfig) -> Self {
+        let n_components = component_ids
+            .iter()
+            .max()
+            .map(|c| c + 1)
+            .unwrap_or_default();
+        let mut sizes = vec![0; n_components];
+        for c in component_ids.iter() {
+            sizes[*c] += 1;
+        }
+        // component ids are ordered by size, so the routable components are the first n
+        let n_routable = match config {
+            StronglyConnectedComponentsConfig::Tag => n_components,
+            StronglyConnectedComponentsConfig::Largest => n_components.min(1),
+            StronglyConnectedComponentsConfig::MinSize { min_size } => {
+                sizes.iter().filter(|s| *s >= min_size).count()
+            }
+        };
+        GraphComponents {
+            component_ids: component_ids.into_boxed_slice(),
+            sizes: sizes.into_boxed_slice(),
+            n_routable,
+        }
+    }
+
+    pub fn n_components(&self) -> usize {
+        self.sizes.len()
+    }
+
+    /// the component of a vertex, or None if the vertex is not in the graph
+    pub fn component_id(&self, vertex_id: &VertexId) -> Option<usize> {
+        self.component_ids.get(vertex_id.0).copied()
+    }
+
+    /// number of vertices in a component
+    pub fn component_size(&self, component_id: usize) -> usize {
+        self.sizes.get(component_id).copied().unwrap_or_default()
+    }
+
+    /// true if the vertex belongs to a component large enough to route on
+    pub fn is_routable(&self, vertex_id: &VertexId) -> bool {
+        self.component_id(vertex_id)
+            .is_some_and(|c| c < self.n_routable)
+    }
+
+    /// number of components large enough to route on
+    pub fn n_routable_components(&self) -> usize {
+        self.n_routable
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_routable_components() {
+        let component_ids = vec![0, 0, 0, 1, 1, 2];
+        let tagged = GraphComponents::new(
+            component_ids.clone(),
+            &StronglyConnectedComponentsConfig::Tag,
+        );
+        assert_eq!(tagged.n_components(), 3);
+        assert_eq!(tagged.component_id(&VertexId(4)), Some(1));
+        assert_eq!(tagged.component_id(&VertexId(6)), None);
+        assert!(tagged.is_routable(&VertexId(5)));
+
+        let largest = GraphComponents::new(
+            component_ids.clone(),
+            &StronglyConnectedComponentsConfig::Largest,
+        );
+        assert!(largest.is_routable(&VertexId(0)));
+        assert!(!largest.is_routable(&VertexId(3)));
+        assert_eq!(largest.n_routable_components(), 1);
+
+        // only component 0 is kept when several components tie for the largest
+        let tied = GraphComponents::new(vec![0, 1], &StronglyConnectedComponentsConfig::Largest);
+        assert!(tied.is_routable(&VertexId(0)));
+        assert!(!tied.is_routable(&VertexId(1)));
+
+        let min_size = GraphComponents::new(
+            component_ids,
+            &StronglyConnectedComponentsConfig::MinSize { min_size: 2 },
+        );
+        assert!(min_size.is_routable(&VertexId(3)));
+        assert!(!min_size.is_routable(&VertexId(5)));
+        assert_eq!(min_size.n_routable_components(), 2);
+    }
+}
@@ -1,14 +1,86 @@
 use serde::{Deserialize, Serialize};
 
 use crate::config::OneOrMany;
+use crate::model::network::clip::GraphClipConfig;
+use crate::model::network::validate::GraphValidationConfig;
+use crate::model::network::StronglyConnectedComponentsConfig;
+use crate::util::geo::crs::Crs;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct GraphConfig {
+    /// CSV vertex list, or GeoParquet vertex layer if the file ends in `.parquet`
     pub vertex_list_input_file: String,
+    /// column names of a GeoParquet vertex layer
+    pub vertex_list_columns: Option<VertexColumns>,
+    /// coordinate reference system of the vertex coordinates, which are transformed to
+    /// WGS84 when loaded. defaults to WGS84.
+    pub srid: Option<Crs>,
     pub edge_list: OneOrMany<EdgeListConfig>,
+    /// if true, pairs each edge with its reverse edge when building the graph
+    /// so that searches can disallow immediate U-turns. defaults to false.
+    pub prevent_u_turns: Option<bool>,
+    /// if provided, topology checks to run on the graph after it is loaded
+    pub validation: Option<GraphValidationConfig>,
+    /// if provided, computes the strongly connected component of each vertex, optionally
+    /// restricting the routable graph to the largest components
+    pub strongly_connected_components: Option<StronglyConnectedComponentsConfig>,
+    /// if provided, loads only the portion of the graph within a region
+    pub clip: Option<GraphClipConfig>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct EdgeListConfig {
+    /// CSV edge list, or GeoParquet edge layer if the file ends in `.parquet`
     pub input_file: String,
+    /// column names of a GeoParquet edge layer
+    pub columns: Option<EdgeColumns>,
+    /// edge-indexed files with one row per edge and no header, such as speed tables and
+    /// geometries, which are clipped along with the edge list when the graph is clipped
+    pub attribute_input_files: Option<Vec<String>>,
+}
+
+/// column names of a GeoParquet vertex layer. vertex ids are read from the `vertex_id`
+/// column if provided, and are otherwise the row index. coordinates are read from the
+/// `x` and `y` columns if both are provided, and are otherwise read from the WKB Point
+/// `geometry` column.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct VertexColumns {
+    pub vertex_id: Option<String>,
+    pub x: Option<String>,
+    pub y: Option<String>,
+    pub geometry: String,
+}
+
+impl Default for VertexColumns {
+    fn default() -> Self {
+        Self {
+            vertex_id: None,
+            x: None,
+            y: None,
+            geometry: String::from("geometry"),
+        }
+    }
+}
+
+/// column names of a GeoParquet edge layer. edge ids are read from the `edge_id` column
+/// if provided, and are otherwise the row index. distances are in meters.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct EdgeColumns {
+    pub edge_id: Option<String>,
+    pub src_vertex_id: String,
+    pub dst_vertex_id: String,
+    pub distance: String,
+}
+
+impl Default for EdgeColumns {
+    fn default() -> Self {
+        Self {
+            edge_id: None,
+            src_vertex_id: String::from("src_vertex_id"),
+            dst_vertex_id: String::from("dst_vertex_id"),
+            distance: String::from("distance"),
+        }
+    }
 }
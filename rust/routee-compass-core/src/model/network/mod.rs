@@ -1,11 +1,15 @@
+pub mod clip;
 mod edge;
 mod edge_config;
 mod edge_id;
 mod edge_list;
 mod edge_list_id;
+pub mod geoparquet;
 mod graph;
+mod graph_components;
 mod graph_config;
 mod network_error;
+pub mod validate;
 mod vertex;
 mod vertex_id;
 
@@ -15,7 +19,8 @@ pub use edge_id::EdgeId;
 pub use edge_list::EdgeList;
 pub use edge_list_id::EdgeListId;
 pub use graph::Graph;
-pub use graph_config::{EdgeListConfig, GraphConfig};
+pub use graph_components::{GraphComponents, StronglyConnectedComponentsConfig};
+pub use graph_config::{EdgeColumns, EdgeListConfig, GraphConfig, VertexColumns};
 pub use network_error::NetworkError;
 pub use vertex::Vertex;
 pub use vertex_id::VertexId;
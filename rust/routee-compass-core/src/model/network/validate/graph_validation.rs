@@ -0,0 +1,416 @@
+use super::{GraphValidationConfig, GraphValidationReport, ValidationPolicy};
+use crate::model::network::{Edge, EdgeId, EdgeList, EdgeListId, Graph, NetworkError, VertexId};
+use crate::util::geo::haversine;
+use std::collections::HashMap;
+use uom::si::{f64::Length, length::meter};
+
+/// length differences below this many meters are attributed to coordinate precision
+const MIN_LENGTH_DIFFERENCE_METERS: f64 = 1.0;
+
+/// finds edges referencing a vertex id that is not in the vertex list.
+pub fn find_dangling_edges(
+    n_vertices: usize,
+    edge_lists: &[EdgeList],
+) -> Vec<(EdgeListId, EdgeId)> {
+    edge_lists
+        .iter()
+        .flat_map(|el| el.edges())
+        .filter(|e| e.src_vertex_id.0 >= n_vertices || e.dst_vertex_id.0 >= n_vertices)
+        .map(|e| (e.edge_list_id, e.edge_id))
+        .collect()
+}
+
+/// runs the topology checks configured for a graph, repairing the edges found where the
+/// policy of a check is [`ValidationPolicy::Drop`] or [`ValidationPolicy::Fix`]. checks run
+/// in the order zero-length edges, length mismatches, reverse length mismatches and
+/// disconnected components, so components are found after any edges were dropped.
+///
+/// # Arguments
+///
+/// * `graph` - graph to validate, with adjacencies built and no dangling edges
+/// * `config` - policy for each check
+///
+/// # Returns
+///
+/// the report of all edges found, or an error listing the checks with policy
+/// [`ValidationPolicy::Error`] that found any edges.
+pub fn validate_graph(
+    graph: &mut Graph,
+    config: &GraphValidationConfig,
+) -> Result<GraphValidationReport, NetworkError> {
+    config.validate().map_err(NetworkError::DatasetError)?;
+    let tolerance = config.get_length_tolerance();
+    let mut report = GraphValidationReport::default();
+    let mut errors: Vec<String> = vec![];
+
+    // zero-length edges
+    let policy = config.zero_length_edges_policy();
+    if policy != ValidationPolicy::Ignore {
+        report.zero_length_edges = find_edges(graph, |e| e.distance.get::<meter>() <= 0.0);
+        for (edge_list_id, edge_id) in report.zero_length_edges.iter() {
+            match policy {
+                ValidationPolicy::Drop => {
+                    report.n_dropped += usize::from(graph.drop_edge(edge_list_id, edge_id))
+                }
+                ValidationPolicy::Fix => {
+                    let straight_line = straight_line_distance(graph, edge_list_id, edge_id)?;
+                    if straight_line.get::<meter>() > 0.0 {
+                        set_distance(graph, edge_list_id, edge_id, straight_line);
+                        report.n_fixed += 1;
+                    }
+                }
+                _ => {}
+            }
+        }
+        if policy == ValidationPolicy::Error && !report.zero_length_edges.is_empty() {
+            errors.push(format!(
+                "{} zero-length edges",
+                report.zero_length_edges.len()
+            ));
+        }
+    }
+
+    // edges shorter than the straight line between their endpoints
+    let policy = config.length_mismatches_policy();
+    if policy != ValidationPolicy::Ignore {
+        let mut mismatches = vec![];
+        for edge in graph.edges() {
+            let distance = edge.distance.get::<meter>();
+            let straight_line =
+                straight_line_distance(graph, &edge.edge_list_id, &edge.edge_id)?.get::<meter>();
+            if distance > 0.0
+                && distance < straight_line * (1.0 - tolerance)
+                && straight_line - distance > MIN_LENGTH_DIFFERENCE_METERS
+            {
+                mismatches.push((edge.edge_list_id, edge.edge_id, straight_line));
+            }
+        }
+        for (edge_list_id, edge_id, straight_line) in mismatches {
+            match policy {
+                ValidationPolicy::Drop => {
+                    report.n_dropped += usize::from(graph.drop_edge(&edge_list_id, &edge_id))
+                }
+                ValidationPolicy::Fix => {
+                    let distance = Length::new::<meter>(straight_line);
+                    set_distance(graph, &edge_list_id, &edge_id, distance);
+                    report.n_fixed += 1;
+                }
+                _ => {}
+            }
+            report.length_mismatches.push((edge_list_id, edge_id));
+        }
+        if policy == ValidationPolicy::Error && !report.length_mismatches.is_empty() {
+            errors.push(format!(
+                "{} edges shorter than the straight line between their endpoints",
+                report.length_mismatches.len()
+            ));
+        }
+    }
+
+    // pairs of reverse edges with different lengths
+    let policy = config.reverse_length_mismatches_policy();
+    if policy != ValidationPolicy::Ignore {
+        report.reverse_length_mismatches = find_reverse_length_mismatches(graph, tolerance);
+        if policy == ValidationPolicy::Fix {
+            for (edge_list_id, edge_id, reverse_id) in report.reverse_length_mismatches.iter() {
+                let (a, b) = (
+                    graph.get_edge(edge_list_id, edge_id)?.distance,
+                    graph.get_edge(edge_list_id, reverse_id)?.distance,
+                );
+                let mean = (a + b) / 2.0;
+                set_distance(graph, edge_list_id, edge_id, mean);
+                set_distance(graph, edge_list_id, reverse_id, mean);
+                report.n_fixed += 2;
+            }
+        }
+        if policy == ValidationPolicy::Error && !report.reverse_length_mismatches.is_empty() {
+            errors.push(format!(
+                "{} pairs of reverse edges with different lengths",
+                report.reverse_length_mismatches.len()
+            ));
+        }
+    }
+
+    // edges outside of the largest connected component
+    let policy = config.disconnected_components_policy();
+    if policy != ValidationPolicy::Ignore {
+        let (n_components, disconnected) = find_disconnected_edges(graph);
+        report.n_components = n_components;
+        report.disconnected_edges = disconnected;
+        if policy == ValidationPolicy::Drop {
+            for (edge_list_id, edge_id) in report.disconnected_edges.iter() {
+                report.n_dropped += usize::from(graph.drop_edge(edge_list_id, edge_id));
+            }
+        }
+        if policy == ValidationPolicy::Error && !report.disconnected_edges.is_empty() {
+            errors.push(format!(
+                "{} edges outside the largest of {} connected components",
+                report.disconnected_edges.len(),
+                report.n_components
+            ));
+        }
+    }
+
+    if report.is_empty() {
+        log::info!("graph validation found no issues");
+    } else {
+        log::warn!("graph validation found {}", report.summary());
+    }
+    if let Some(path) = &config.report_output_file {
+        write_report(&report, path)?;
+    }
+    if !errors.is_empty() {
+        return Err(NetworkError::DatasetError(format!(
+            "graph validation failed with {}",
+            errors.join(", ")
+        )));
+    }
+    Ok(report)
+}
+
+/// writes a validation report to a JSON file
+pub fn write_report(report: &GraphValidationReport, path: &str) -> Result<(), NetworkError> {
+    let file = std::fs::File::create(path)?;
+    serde_json::to_writer_pretty(file, report).map_err(|e| {
+        NetworkError::DatasetError(format!(
+            "failed writing graph validation report to {path}: {e}"
+        ))
+    })
+}
+
+fn find_edges(graph: &Graph, predicate: impl Fn(&Edge) -> bool) -> Vec<(EdgeListId, EdgeId)> {
+    graph
+        .edges()
+        .filter(|e| predicate(e))
+        .map(|e| (e.edge_list_id, e.edge_id))
+        .collect()
+}
+
+fn straight_line_distance(
+    graph: &Graph,
+    edge_list_id: &EdgeListId,
+    edge_id: &EdgeId,
+) -> Result<Length, NetworkError> {
+    let (src, _, dst) = graph.edge_triplet(edge_list_id, edge_id)?;
+    haversine::coord_distance(&src.coordinate, &dst.coordinate).map_err(|e| {
+        NetworkError::DatasetError(format!(
+            "cannot compute length of edge {edge_id} in edge list {edge_list_id}: {e}"
+        ))
+    })
+}
+
+fn set_distance(graph: &mut Graph, edge_list_id: &EdgeListId, edge_id: &EdgeId, distance: Length) {
+    if let Some(edge) = graph
+        .edge_lists
+        .get_mut(edge_list_id.0)
+        .and_then(|el| el.0.get_mut(edge_id.0))
+    {
+        edge.distance = distance;
+    }
+}
+
+fn find_reverse_length_mismatches(
+    graph: &Graph,
+    tolerance: f64,
+) -> Vec<(EdgeListId, EdgeId, EdgeId)> {
+    let mut mismatches = vec![];
+    for edge_list in graph.edge_lists.iter() {
+        let mut by_endpoints: HashMap<(VertexId, VertexId), &Edge> = HashMap::new();
+        for edge in edge_list.edges() {
+            by_endpoints
+                .entry((edge.src_vertex_id, edge.dst_vertex_id))
+                .or_insert(edge);
+        }
+        for edge in edge_list.edges() {
+            if edge.src_vertex_id == edge.dst_vertex_id {
+                continue;
+            }
+            let reverse = match by_endpoints.get(&(edge.dst_vertex_id, edge.src_vertex_id)) {
+                Some(reverse) if reverse.edge_id.0 > edge.edge_id.0 => reverse,
+                _ => continue,
+            };
+            let a = edge.distance.get::<meter>();
+            let b = reverse.distance.get::<meter>();
+            let difference = (a - b).abs();
+            // zero-length edges are found by their own check
+            if a > 0.0
+                && b > 0.0
+                && difference > tolerance * a.max(b)
+                && difference > MIN_LENGTH_DIFFERENCE_METERS
+            {
+                mismatches.push((edge.edge_list_id, edge.edge_id, reverse.edge_id));
+            }
+        }
+    }
+    mismatches
+}
+
+/// finds the weakly connected components of the edges in the graph adjacencies, returning
+/// the number of components with at least one edge and the edges outside of the component
+/// with the most vertices.
+fn find_disconnected_edges(graph: &Graph) -> (usize, Vec<(EdgeListId, EdgeId)>) {
+    let mut parents: Vec<usize> = (0..graph.n_vertices()).collect();
+    for (src, out_edges) in graph.adj.iter().enumerate() {
+        for dst in out_edges.values() {
+            let (a, b) = (find_root(&mut parents, src), find_root(&mut parents, dst.0));
+            if a != b {
+                parents[a] = b;
+            }
+        }
+    }
+
+    let mut sizes: HashMap<usize, usize> = HashMap::new();
+    for (vertex, out_edges) in graph.adj.iter().enumerate() {
+        if !out_edges.is_empty() || !graph.rev[vertex].is_empty() {
+            *sizes.entry(find_root(&mut parents, vertex)).or_default() += 1;
+        }
+    }
+    let largest = sizes
+        .iter()
+        .max_by_key(|(root, size)| (**size, std::cmp::Reverse(**root)))
+        .map(|(root, _)| *root);
+
+    let mut disconnected = vec![];
+    for (src, out_edges) in graph.adj.iter().enumerate() {
+        if Some(find_root(&mut parents, src)) != largest {
+            disconnected.extend(out_edges.keys().copied());
+        }
+    }
+    disconnected.sort();
+    (sizes.len(), disconnected)
+}
+
+fn find_root(parents: &mut [usize], vertex: usize) -> usize {
+    let mut root = vertex;
+    while parents[root] != root {
+        root = parents[root];
+    }
+    // path compression
+    let mut current = vertex;
+    while parents[current] != root {
+        let next = parents[current];
+        parents[current] = root;
+        current = next;
+    }
+    root
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::network::Vertex;
+    use indexmap::IndexMap;
+    use uom::si::length::kilometer;
+
+    /// two triangles of vertices about 1.1 km apart, with a one-way edge between them
+    /// and reverse edges within the first triangle
+    fn build_graph(distances_km: &[f64]) -> Graph {
+        let vertices = vec![
+            Vertex::new(0, -105.0, 39.0),
+            Vertex::new(1, -105.0, 39.01),
+            Vertex::new(2, -104.99, 39.0),
+            Vertex::new(3, -104.0, 40.0),
+            Vertex::new(4, -104.0, 40.01),
+        ];
+        let endpoints = [(0, 1), (1, 0), (1, 2), (2, 0), (3, 4), (4, 3)];
+        let edges = endpoints
+            .iter()
+            .zip(distances_km)
+            .enumerate()
+            .map(|(idx, ((src, dst), km))| {
+                Edge::new(0, idx, *src, *dst, Length::new::<kilometer>(*km))
+            })
+            .collect::<Vec<_>>();
+        let mut adj = vec![IndexMap::new(); vertices.len()];
+        let mut rev = vec![IndexMap::new(); vertices.len()];
+        for edge in edges.iter() {
+            let key = (edge.edge_list_id, edge.edge_id);
+            adj[edge.src_vertex_id.0].insert(key, edge.dst_vertex_id);
+            rev[edge.dst_vertex_id.0].insert(key, edge.src_vertex_id);
+        }
+        Graph {
+            vertices: vertices.into_boxed_slice(),
+            edge_lists: vec![EdgeList(edges.into_boxed_slice())],
+            adj: adj.into_boxed_slice(),
+            rev: rev.into_boxed_slice(),
+            reverse_edges: None,
+            components: None,
+        }
+    }
+
+    fn edge_ids(edges: &[(EdgeListId, EdgeId)]) -> Vec<usize> {
+        edges.iter().map(|(_, e)| e.0).collect()
+    }
+
+    #[test]
+    fn test_validate_report() {
+        // edge 1 is shorter than its reverse, edge 2 is shorter than the straight line
+        // and edge 5 has zero length
+        let mut graph = build_graph(&[1.2, 1.5, 0.5, 1.5, 1.12, 0.0]);
+        let report = validate_graph(&mut graph, &GraphValidationConfig::default()).unwrap();
+        assert_eq!(edge_ids(&report.zero_length_edges), vec![5]);
+        assert_eq!(edge_ids(&report.length_mismatches), vec![2]);
+        assert_eq!(
+            report.reverse_length_mismatches,
+            vec![(EdgeListId(0), EdgeId(0), EdgeId(1))]
+        );
+        assert_eq!(report.n_components, 2);
+        assert_eq!(edge_ids(&report.disconnected_edges), vec![4, 5]);
+        assert_eq!(report.n_dropped + report.n_fixed, 0);
+        assert_eq!(graph.out_edges(&VertexId(3)).len(), 1);
+    }
+
+    #[test]
+    fn test_validate_repair() {
+        let mut graph = build_graph(&[1.2, 1.5, 0.5, 1.5, 1.12, 0.0]);
+        let config = GraphValidationConfig {
+            zero_length_edges: Some(ValidationPolicy::Fix),
+            disconnected_components: Some(ValidationPolicy::Drop),
+            reverse_length_mismatches: Some(ValidationPolicy::Fix),
+            length_mismatches: Some(ValidationPolicy::Fix),
+            ..Default::default()
+        };
+        let report = validate_graph(&mut graph, &config).unwrap();
+        assert_eq!(report.n_fixed, 4);
+        assert_eq!(report.n_dropped, 2);
+        let distance = |e: usize| {
+            graph
+                .get_edge(&EdgeListId(0), &EdgeId(e))
+                .unwrap()
+                .distance
+                .get::<kilometer>()
+        };
+        assert!((distance(0) - 1.35).abs() < 1e-9);
+        assert!((distance(1) - 1.35).abs() < 1e-9);
+        assert!(distance(2) > 1.0);
+        assert!(distance(5) > 1.0);
+        assert!(graph.out_edges(&VertexId(3)).is_empty());
+        assert!(graph.in_edges(&VertexId(3)).is_empty());
+
+        let mut graph = build_graph(&[1.2, 1.5, 0.5, 1.5, 1.12, 0.0]);
+        let config = GraphValidationConfig {
+            zero_length_edges: Some(ValidationPolicy::Error),
+            length_mismatches: Some(ValidationPolicy::Error),
+            ..Default::default()
+        };
+        let error = validate_graph(&mut graph, &config).unwrap_err().to_string();
+        assert!(error.contains("1 zero-length edges"), "{error}");
+        assert!(error.contains("1 edges shorter"), "{error}");
+
+        let config = GraphValidationConfig {
+            disconnected_components: Some(ValidationPolicy::Fix),
+            ..Default::default()
+        };
+        assert!(validate_graph(&mut graph, &config).is_err());
+    }
+
+    #[test]
+    fn test_find_dangling_edges() {
+        let graph = build_graph(&[1.0; 6]);
+        assert!(find_dangling_edges(5, &graph.edge_lists).is_empty());
+        assert_eq!(
+            edge_ids(&find_dangling_edges(4, &graph.edge_lists)),
+            vec![4, 5]
+        );
+    }
+}
@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+
+/// default relative tolerance when comparing edge lengths
+pub const DEFAULT_LENGTH_TOLERANCE: f64 = 0.05;
+
+/// configures the topology checks run on a [`crate::model::network::Graph`] when it is
+/// loaded. each check has a [`ValidationPolicy`], defaulting to
+/// [`ValidationPolicy::Report`]. edges referencing vertices missing from the vertex list
+/// are always an error.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct GraphValidationConfig {
+    /// edges with a distance of zero
+    pub zero_length_edges: Option<ValidationPolicy>,
+    /// edges outside of the largest weakly connected component
+    pub disconnected_components: Option<ValidationPolicy>,
+    /// pairs of edges connecting the same vertices in opposite directions with different lengths
+    pub reverse_length_mismatches: Option<ValidationPolicy>,
+    /// edges shorter than the straight line distance between their endpoints
+    pub length_mismatches: Option<ValidationPolicy>,
+    /// relative tolerance when comparing edge lengths, 0.05 by default
+    pub length_tolerance: Option<f64>,
+    /// optional file to write the validation report to as JSON
+    pub report_output_file: Option<String>,
+}
+
+/// how to handle the edges found by a graph validation check.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationPolicy {
+    /// skip the check
+    Ignore,
+    /// log the edges found
+    #[default]
+    Report,
+    /// fail loading the graph if any edges are found
+    Error,
+    /// remove the edges found from the graph adjacencies so that searches do not use them.
+    /// the edges remain in their edge list so that edge ids are unchanged.
+    Drop,
+    /// correct the length of the edges found
+    Fix,
+}
+
+impl GraphValidationConfig {
+    pub fn zero_length_edges_policy(&self) -> ValidationPolicy {
+        self.zero_length_edges.unwrap_or_default()
+    }
+
+    pub fn disconnected_components_policy(&self) -> ValidationPolicy {
+        self.disconnected_components.unwrap_or_default()
+    }
+
+    pub fn reverse_length_mismatches_policy(&self) -> ValidationPolicy {
+        self.reverse_length_mismatches.unwrap_or_default()
+    }
+
+    pub fn length_mismatches_policy(&self) -> ValidationPolicy {
+        self.length_mismatches.unwrap_or_default()
+    }
+
+    pub fn get_length_tolerance(&self) -> f64 {
+        self.length_tolerance.unwrap_or(DEFAULT_LENGTH_TOLERANCE)
+    }
+
+    /// confirms each policy is supported by its check. disconnected components cannot
+    /// be fixed and reverse length mismatches cannot be dropped.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.disconnected_components_policy() == ValidationPolicy::Fix {
+            return Err(String::from(
+                "disconnected_components does not support the 'fix' policy, use 'drop' instead",
+            ));
+        }
+        if self.reverse_length_mismatches_policy() == ValidationPolicy::Drop {
+            return Err(String::from(
+                "reverse_length_mismatches does not support the 'drop' policy, use 'fix' instead",
+            ));
+        }
+        let tolerance = self.get_length_tolerance();
+        if !(0.0..1.0).contains(&tolerance) {
+            return Err(format!(
+                "length_tolerance must be in the range [0, 1), found {tolerance}"
+            ));
+        }
+        Ok(())
+    }
+}
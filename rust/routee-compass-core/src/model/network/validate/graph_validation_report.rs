@@ -0,0 +1,52 @@
+use crate::model::network::{EdgeId, EdgeListId};
+use serde::Serialize;
+
+/// edges found by each graph validation check, along with the repairs made.
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct GraphValidationReport {
+    /// edges referencing a vertex missing from the vertex list
+    pub dangling_edges: Vec<(EdgeListId, EdgeId)>,
+    /// edges with a distance of zero
+    pub zero_length_edges: Vec<(EdgeListId, EdgeId)>,
+    /// number of weakly connected components containing at least one edge
+    pub n_components: usize,
+    /// edges outside of the largest weakly connected component
+    pub disconnected_edges: Vec<(EdgeListId, EdgeId)>,
+    /// pairs of edges in the same edge list connecting the same vertices in opposite
+    /// directions whose lengths differ by more than the tolerance
+    pub reverse_length_mismatches: Vec<(EdgeListId, EdgeId, EdgeId)>,
+    /// edges shorter than the straight line distance between their endpoints by more
+    /// than the tolerance
+    pub length_mismatches: Vec<(EdgeListId, EdgeId)>,
+    /// number of edges removed from the graph adjacencies
+    pub n_dropped: usize,
+    /// number of edges whose length was corrected
+    pub n_fixed: usize,
+}
+
+impl GraphValidationReport {
+    /// true if no check found any edges
+    pub fn is_empty(&self) -> bool {
+        self.dangling_edges.is_empty()
+            && self.zero_length_edges.is_empty()
+            && self.disconnected_edges.is_empty()
+            && self.reverse_length_mismatches.is_empty()
+            && self.length_mismatches.is_empty()
+    }
+
+    /// a one-line description of the counts found by each check
+    pub fn summary(&self) -> String {
+        format!(
+            "{} dangling edges, {} zero-length edges, {} edges outside the largest of {} components, \
+            {} reverse length mismatches, {} length mismatches ({} edges dropped, {} fixed)",
+            self.dangling_edges.len(),
+            self.zero_length_edges.len(),
+            self.disconnected_edges.len(),
+            self.n_components,
+            self.reverse_length_mismatches.len(),
+            self.length_mismatches.len(),
+            self.n_dropped,
+            self.n_fixed
+        )
+    }
+}
@@ -0,0 +1,7 @@
+mod graph_validation;
+mod graph_validation_config;
+mod graph_validation_report;
+
+pub use graph_validation::{find_dangling_edges, validate_graph, write_report};
+pub use graph_validation_config::{GraphValidationConfig, ValidationPolicy};
+pub use graph_validation_report::GraphValidationReport;
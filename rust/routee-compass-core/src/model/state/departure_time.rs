@@ -0,0 +1,143 @@
+use super::{CustomVariableConfig, StateModelError, StateVariable, StateVariableConfig};
+use crate::model::traversal::default::fieldname;
+use chrono::{DateTime, TimeDelta, TimeZone, Utc};
+use ordered_float::OrderedFloat;
+use uom::si::f64::Time;
+
+/// the absolute date and time a trip departs its origin. it is stored in the state
+/// vector as seconds since the Unix epoch (UTC), and the local clock time at any point
+/// of a trip is the departure time plus the trip time. models that depend on the time
+/// of day or the day of the week, such as time-of-day speeds, conditional restrictions
+/// or transit schedules, read it from the state so that they share one representation
+/// of time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DepartureTime(pub DateTime<Utc>);
+
+impl DepartureTime {
+    /// query key with an RFC 3339 departure time, such as `"2025-06-01T08:15:00-06:00"`
+    pub const QUERY_KEY: &'static str = "departure_time";
+
+    /// the custom type name of the departure time state variable, which is serialized
+    /// as an RFC 3339 string
+    pub const CUSTOM_TYPE: &'static str = "datetime";
+
+    /// the departure time state variable, added to the state model when configured or
+    /// when a time-dependent model requires it. it is an accumulator so that it is
+    /// carried along the trip unchanged.
+    pub fn feature() -> (String, StateVariableConfig) {
+        (
+            String::from(fieldname::DEPARTURE_TIME),
+            StateVariableConfig::Custom {
+                custom_type: String::from(Self::CUSTOM_TYPE),
+                value: CustomVariableConfig::FloatingPoint {
+                    initial: OrderedFloat(0.0),
+                },
+                accumulator: true,
+            },
+        )
+    }
+
+    /// reads the departure time of a query, if present.
+    pub fn from_query(query: &serde_json::Value) -> Result<Option<DepartureTime>, String> {
+        match query.get(Self::QUERY_KEY) {
+            None => Ok(None),
+            Some(value) => {
+                let time_str = value.as_str().ok_or_else(|| {
+                    format!(
+                        "query '{}' must be an RFC 3339 string, found {value}",
+                        Self::QUERY_KEY
+                    )
+                })?;
+                let departure = DepartureTime::parse(time_str).map_err(|e| {
+                    format!(
+                        "query '{}' must be an RFC 3339 string: {e}",
+                        Self::QUERY_KEY
+                    )
+                })?;
+                Ok(Some(departure))
+            }
+        }
+    }
+
+    /// reads an RFC 3339 date and time
+    pub fn parse(value: &str) -> Result<DepartureTime, chrono::ParseError> {
+        let time = DateTime::parse_from_rfc3339(value.trim())?;
+        Ok(DepartureTime(time.with_timezone(&Utc)))
+    }
+
+    /// the departure time encoded as a state variable
+    pub fn to_state_variable(&self) -> StateVariable {
+        let seconds = self.0.timestamp() as f64 + self.0.timestamp_subsec_micros() as f64 / 1e6;
+        StateVariable(seconds)
+    }
+
+    /// decodes a departure time state variable
+    pub fn from_state_variable(value: &StateVariable) -> Result<DepartureTime, StateModelError> {
+        let seconds = value.0.floor();
+        let micros = ((value.0 - seconds) * 1e6).round() as i64;
+        DateTime::from_timestamp(seconds as i64, 0)
+            .and_then(|t| t.checked_add_signed(TimeDelta::microseconds(micros)))
+            .map(DepartureTime)
+            .ok_or_else(|| {
+                StateModelError::RuntimeError(format!(
+                    "state variable {value} is not a valid departure time"
+                ))
+            })
+    }
+
+    /// the UTC clock time after some trip time has elapsed since departure
+    pub fn clock_time(&self, trip_time: &Time) -> Result<DateTime<Utc>, StateModelError> {
+        let micros = (trip_time.get::<uom::si::time::microsecond>()).round() as i64;
+        self.0
+            .checked_add_signed(TimeDelta::microseconds(micros))
+            .ok_or_else(|| {
+                StateModelError::RuntimeError(format!(
+                    "trip time of {} seconds after departure {} is out of range",
+                    trip_time.get::<uom::si::time::second>(),
+                    self.0.to_rfc3339()
+                ))
+            })
+    }
+
+    /// the local clock time in a time zone after some trip time has elapsed since
+    /// departure, from which the time of day and the day of the week can be read
+    pub fn local_clock_time<Tz: TimeZone>(
+        &self,
+        trip_time: &Time,
+        time_zone: &Tz,
+    ) -> Result<DateTime<Tz>, StateModelError> {
+        Ok(self.clock_time(trip_time)?.with_timezone(time_zone))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::{Datelike, FixedOffset, Timelike, Weekday};
+    use serde_json::json;
+    use uom::si::time::minute;
+
+    #[test]
+    fn test_clock_time() {
+        let query = json!({"departure_time": "2025-06-01T23:30:00-06:00"});
+        let departure = DepartureTime::from_query(&query).unwrap().unwrap();
+        let roundtrip = DepartureTime::from_state_variable(&departure.to_state_variable()).unwrap();
+        assert_eq!(roundtrip, departure);
+
+        // 45 minutes later is past local midnight, on a Monday
+        let mountain = FixedOffset::west_opt(6 * 3600).unwrap();
+        let local = departure
+            .local_clock_time(&Time::new::<minute>(45.0), &mountain)
+            .unwrap();
+        assert_eq!((local.hour(), local.minute()), (0, 15));
+        assert_eq!(local.weekday(), Weekday::Mon);
+        assert_eq!(
+            departure.clock_time(&Time::new::<minute>(45.0)).unwrap(),
+            DepartureTime::parse("2025-06-02T06:15:00Z").unwrap().0
+        );
+
+        assert!(DepartureTime::from_query(&json!({})).unwrap().is_none());
+        assert!(DepartureTime::from_query(&json!({"departure_time": "08:15"})).is_err());
+        assert!(DepartureTime::from_query(&json!({"departure_time": 815})).is_err());
+    }
+}
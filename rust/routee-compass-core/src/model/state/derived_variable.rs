@@ -0,0 +1,107 @@
+use super::StateModelError;
+use crate::util::expression::{Expression, ExpressionError};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// a variable computed at output time from an arithmetic expression over state
+/// variables, such as `toll / trip_distance`. see [`Expression`] for the supported
+/// syntax. derived variables are not part of the state vector, so they cannot be
+/// used by traversal, constraint or cost models.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct DerivedVariable {
+    expression: Expression,
+}
+
+impl DerivedVariable {
+    /// the expression as written in the configuration
+    pub fn expression(&self) -> &str {
+        self.expression.source()
+    }
+
+    /// names of the state variables referenced by the expression
+    pub fn variables(&self) -> Vec<&str> {
+        self.expression.variables()
+    }
+
+    /// evaluates the expression, looking up the value of each referenced state variable.
+    /// division by zero follows floating point semantics and yields an infinite or NaN value.
+    pub fn evaluate(&self, lookup: &dyn Fn(&str) -> Option<f64>) -> Result<f64, StateModelError> {
+        self.expression.evaluate(lookup).map_err(|e| match e {
+            ExpressionError::UnknownVariable(name) => StateModelError::RuntimeError(format!(
+                "derived variable expression references '{name}', which is not a numeric state variable"
+            )),
+            other => StateModelError::RuntimeError(other.to_string()),
+        })
+    }
+}
+
+impl FromStr for DerivedVariable {
+    type Err = StateModelError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let expression = Expression::from_str(s)
+            .map_err(|e| StateModelError::BuildError(format!("invalid derived variable: {e}")))?;
+        Ok(DerivedVariable { expression })
+    }
+}
+
+impl TryFrom<String> for DerivedVariable {
+    type Error = StateModelError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        DerivedVariable::from_str(&value)
+    }
+}
+
+impl From<DerivedVariable> for String {
+    fn from(value: DerivedVariable) -> Self {
+        value.expression.source().to_string()
+    }
+}
+
+impl Display for DerivedVariable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.expression)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_evaluate() {
+        let values = HashMap::from([("toll", 3.0), ("trip_distance", 2.0)]);
+        let lookup = |name: &str| values.get(name).copied();
+        let derived = DerivedVariable::from_str("toll / trip_distance").unwrap();
+        assert_eq!(derived.evaluate(&lookup).unwrap(), 1.5);
+        assert_eq!(derived.variables(), vec!["toll", "trip_distance"]);
+        let unknown = DerivedVariable::from_str("energy / trip_distance").unwrap();
+        assert!(unknown.evaluate(&lookup).is_err());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for expression in ["", "toll /", "(toll", "toll trip_distance", "toll % 2"] {
+            assert!(
+                DerivedVariable::from_str(expression).is_err(),
+                "expected '{expression}' to fail"
+            );
+        }
+    }
+
+    #[test]
+    fn test_serde() {
+        let derived: DerivedVariable =
+            serde_json::from_value(serde_json::json!("toll / trip_distance")).unwrap();
+        assert_eq!(derived.expression(), "toll / trip_distance");
+        assert_eq!(
+            serde_json::to_value(&derived).unwrap(),
+            serde_json::json!("toll / trip_distance")
+        );
+        assert!(serde_json::from_value::<DerivedVariable>(serde_json::json!("toll /")).is_err());
+    }
+}
@@ -54,6 +54,35 @@ impl InputFeature {
     }
 }
 
+impl InputFeature {
+    /// the type of state variable expected by this input feature, named as in
+    /// [`crate::model::state::StateVariableConfig::get_feature_type`]
+    pub fn feature_type(&self) -> String {
+        match self {
+            InputFeature::Distance { .. } => "distance".to_string(),
+            InputFeature::Speed { .. } => "speed".to_string(),
+            InputFeature::Time { .. } => "time".to_string(),
+            InputFeature::Energy { .. } => "energy".to_string(),
+            InputFeature::Ratio { .. } => "ratio".to_string(),
+            InputFeature::Temperature { .. } => "temperature".to_string(),
+            InputFeature::Custom { .. } => "custom".to_string(),
+        }
+    }
+
+    /// the stringified name of the requested unit, if set
+    pub fn unit_name(&self) -> Option<String> {
+        match self {
+            InputFeature::Distance { unit, .. } => unit.map(|u| u.to_string()),
+            InputFeature::Speed { unit, .. } => unit.map(|u| u.to_string()),
+            InputFeature::Time { unit, .. } => unit.map(|u| u.to_string()),
+            InputFeature::Energy { unit, .. } => unit.map(|u| u.to_string()),
+            InputFeature::Ratio { unit, .. } => unit.map(|u| u.to_string()),
+            InputFeature::Temperature { unit, .. } => unit.map(|u| u.to_string()),
+            InputFeature::Custom { unit, .. } => Some(unit.clone()),
+        }
+    }
+}
+
 impl std::fmt::Display for InputFeature {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let s = serde_json::to_string_pretty(self).unwrap_or_default();
@@ -1,5 +1,7 @@
 mod custom_variable_config;
 mod custom_variable_type;
+mod departure_time;
+mod derived_variable;
 mod input_feature;
 mod state_model;
 mod state_model_error;
@@ -9,6 +11,8 @@ mod update_operation;
 
 pub use custom_variable_config::CustomVariableConfig;
 pub use custom_variable_type::CustomVariableType;
+pub use departure_time::DepartureTime;
+pub use derived_variable::DerivedVariable;
 pub use input_feature::InputFeature;
 pub use state_model::StateModel;
 pub use state_model_error::StateModelError;
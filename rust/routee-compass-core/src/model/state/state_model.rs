@@ -4,19 +4,22 @@ use super::{
     custom_variable_config::CustomVariableConfig, state_model_error::StateModelError,
     update_operation::UpdateOperation,
 };
-use crate::model::state::InputFeature;
+use crate::model::state::{DepartureTime, InputFeature};
+use crate::model::traversal::default::fieldname;
 use crate::model::unit::{
     DistanceUnit, EnergyUnit, RatioUnit, SpeedUnit, TemperatureUnit, TimeUnit,
 };
 use indexmap::IndexMap;
 // use crate::util::compact_ordered_hash_map::CompactOrderedHashMap;
 // use crate::util::compact_ordered_hash_map::IndexedEntry;
+use chrono::{DateTime, TimeZone, Utc};
 use itertools::Itertools;
 use serde_json::json;
 use std::collections::HashMap;
 use std::iter::Enumerate;
 
 use uom::si::f64::*;
+use uom::ConstZero;
 
 /// a state model tracks information about each feature in a search state vector.
 /// in concept, it is modeled as a mapping from a feature_name String to a StateFeature
@@ -346,6 +349,61 @@ impl StateModel {
         Ok(result)
     }
 
+    /// retrieves the departure time of a trip, stored in the
+    /// [`fieldname::DEPARTURE_TIME`] state variable.
+    ///
+    /// # Arguments
+    /// * `state` - state vector to inspect
+    ///
+    /// # Returns
+    ///
+    /// the departure time or an error
+    pub fn get_departure_time(
+        &self,
+        state: &[StateVariable],
+    ) -> Result<DepartureTime, StateModelError> {
+        let value = self.get_raw_state_variable(state, fieldname::DEPARTURE_TIME)?;
+        DepartureTime::from_state_variable(value)
+    }
+    /// retrieves the UTC clock time at a state, which is the departure time plus the
+    /// trip time. a state model without a trip time is at its departure time.
+    ///
+    /// # Arguments
+    /// * `state` - state vector to inspect
+    ///
+    /// # Returns
+    ///
+    /// the clock time or an error
+    pub fn get_clock_time(
+        &self,
+        state: &[StateVariable],
+    ) -> Result<DateTime<Utc>, StateModelError> {
+        let departure = self.get_departure_time(state)?;
+        let trip_time = if self.0.contains_key(fieldname::TRIP_TIME) {
+            self.get_time(state, fieldname::TRIP_TIME)?
+        } else {
+            Time::ZERO
+        };
+        departure.clock_time(&trip_time)
+    }
+    /// retrieves the local clock time at a state in a time zone. see
+    /// [`StateModel::get_clock_time`].
+    ///
+    /// # Arguments
+    /// * `state` - state vector to inspect
+    /// * `time_zone` - time zone of the local clock
+    ///
+    /// # Returns
+    ///
+    /// the local clock time or an error
+    pub fn get_local_clock_time<Tz: TimeZone>(
+        &self,
+        state: &[StateVariable],
+        time_zone: &Tz,
+    ) -> Result<DateTime<Tz>, StateModelError> {
+        Ok(self.get_clock_time(state)?.with_timezone(time_zone))
+    }
+
     /// internal helper function that retrieves a value as a feature vector state variable
     /// along with the custom feature's format. this is used by the four specialized get_custom
     /// methods for specific types.
@@ -511,6 +569,20 @@ impl StateModel {
         self.update_state(state, name, &value, UpdateOperation::Replace)
     }
 
+    pub fn set_departure_time(
+        &self,
+        state: &mut [StateVariable],
+        departure_time: &DepartureTime,
+    ) -> Result<(), StateModelError> {
+        let value = departure_time.to_state_variable();
+        self.update_state(
+            state,
+            fieldname::DEPARTURE_TIME,
+            &value,
+            UpdateOperation::Replace,
+        )
+    }
+
     pub fn set_custom_f64(
         &self,
         state: &mut [StateVariable],
@@ -583,6 +655,89 @@ impl StateModel {
         Ok(json![output])
     }
 
+    /// sets a state variable from a JSON value in the output unit of the variable, as
+    /// written by [`StateModel::serialize_state`]
+    ///
+    /// # Arguments
+    /// * `state` - state vector to update
+    /// * `name`  - feature name to set
+    /// * `value` - serialized feature value
+    pub fn set_serialized(
+        &self,
+        state: &mut [StateVariable],
+        name: &str,
+        value: &serde_json::Value,
+    ) -> Result<(), StateModelError> {
+        let feature = self.get_feature(name)?;
+        let value = feature.deserialize_variable(value)?;
+        self.update_state(state, name, &value, UpdateOperation::Replace)
+    }
+
+    /// cross-checks the input features declared by each model against the state
+    /// variables that produce them. every input feature must be produced, and as the
+    /// same type of state variable, since reading a variable as another type
+    /// reinterprets its value without error. units of the same type are converted by
+    /// the state model and may differ. custom variables are only checked to be custom.
+    ///
+    /// # Arguments
+    /// * `consumers` - the name of each model along with the input features it reads
+    ///
+    /// # Returns
+    ///
+    /// an error with a report of each mismatch and the feature dependency graph, if
+    /// any input feature does not match its state variable
+    pub fn audit_input_features(
+        &self,
+        consumers: &[(String, Vec<InputFeature>)],
+    ) -> Result<(), StateModelError> {
+        let describe = |feature_type: String, unit: Option<String>| match unit {
+            Some(unit) => format!("{feature_type} ({unit})"),
+            None => feature_type,
+        };
+        let mut mismatches = vec![];
+        let mut consumed_by: IndexMap<String, Vec<String>> = IndexMap::new();
+        for (model, features) in consumers.iter() {
+            for feature in features.iter() {
+                let name = feature.name();
+                let consumed = describe(feature.feature_type(), feature.unit_name());
+                consumed_by
+                    .entry(name.clone())
+                    .or_default()
+                    .push(format!("{model} as {consumed}"));
+                match self.0.get(&name) {
+                    None => mismatches.push(format!(
+                        "  '{name}' is consumed by '{model}' as {consumed} but is not produced by any model"
+                    )),
+                    Some(produced) if produced.get_feature_type() != feature.feature_type() => {
+                        let produced =
+                            describe(produced.get_feature_type(), produced.get_unit_name());
+                        mismatches.push(format!(
+                            "  '{name}' is produced as {produced} but consumed by '{model}' as {consumed}"
+                        ))
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+        if mismatches.is_empty() {
+            return Ok(());
+        }
+        let graph = consumed_by
+            .iter()
+            .map(|(name, consumers)| {
+                let produced = self.0.get(name).map_or_else(
+                    || String::from("not produced"),
+                    |f| describe(f.get_feature_type(), f.get_unit_name()),
+                );
+                format!("  {name} [{produced}] -> {}", consumers.join(", "))
+            })
+            .join("\n");
+        Err(StateModelError::FeatureMismatch(format!(
+            "{}\nfeature dependency graph:\n{graph}",
+            mismatches.join("\n")
+        )))
+    }
+
     /// uses the built-in serialization codec to output the state model representation as a JSON object
     /// stores the result as a JSON Object (Map).
     pub fn serialize_state_model(&self) -> serde_json::Value {
@@ -799,3 +954,52 @@ impl<'a> TryFrom<&'a serde_json::Value> for StateModel {
 //         }
 //     }
 // }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_audit_input_features() {
+        let state_model = StateModel::new(vec![
+            (
+                String::from("trip_distance"),
+                StateVariableConfig::Distance {
+                    initial: Length::ZERO,
+                    accumulator: true,
+                    output_unit: Some(DistanceUnit::Miles),
+                },
+            ),
+            (
+                String::from("trip_time"),
+                StateVariableConfig::Time {
+                    initial: Time::ZERO,
+                    accumulator: true,
+                    output_unit: Some(TimeUnit::Minutes),
+                },
+            ),
+        ]);
+        let time = |name: &str| InputFeature::Time {
+            name: String::from(name),
+            unit: Some(TimeUnit::Hours),
+        };
+        // units of the same type are converted
+        let valid = vec![(String::from("energy"), vec![time("trip_time")])];
+        assert!(state_model.audit_input_features(&valid).is_ok());
+
+        let invalid = vec![(
+            String::from("energy"),
+            vec![time("trip_time"), time("trip_distance"), time("trip_soc")],
+        )];
+        let report = match state_model.audit_input_features(&invalid) {
+            Err(StateModelError::FeatureMismatch(report)) => report,
+            other => panic!("expected a feature mismatch, found {other:?}"),
+        };
+        assert!(report.contains(
+            "'trip_distance' is produced as distance (miles) but consumed by 'energy' as time (hours)"
+        ));
+        assert!(report
+            .contains("'trip_soc' is consumed by 'energy' as time (hours) but is not produced"));
+        assert!(report.contains("trip_time [time (minutes)] -> energy as time (hours)"));
+    }
+}
@@ -21,6 +21,8 @@ pub enum StateModelError {
         #[from]
         source: UnitError,
     },
+    #[error("state variables are consumed as a different type than they are produced:\n{0}")]
+    FeatureMismatch(String),
     #[error("{0}")]
     BuildError(String),
     #[error("{0}")]
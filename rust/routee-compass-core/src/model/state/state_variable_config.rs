@@ -1,5 +1,5 @@
 use crate::model::{
-    state::{CustomVariableConfig, StateModelError, StateVariable},
+    state::{CustomVariableConfig, DepartureTime, StateModelError, StateVariable},
     unit::{DistanceUnit, EnergyUnit, RatioUnit, SpeedUnit, TemperatureUnit, TimeUnit},
 };
 use serde::{Deserialize, Serialize};
@@ -190,6 +190,11 @@ impl StateVariableConfig {
                     Ok(json![unit.from_uom(uom_value)])
                 })
             }
+            StateVariableConfig::Custom { custom_type, .. }
+                if custom_type == DepartureTime::CUSTOM_TYPE =>
+            {
+                DepartureTime::from_state_variable(state_variable).map(|d| json![d.0.to_rfc3339()])
+            }
             StateVariableConfig::Custom { value, .. } => match value {
                 CustomVariableConfig::FloatingPoint { .. } => {
                     value.decode_f64(state_variable).map(|v| json![v])
@@ -206,6 +211,143 @@ impl StateVariableConfig {
             },
         }
     }
+
+    /// the inverse of [`StateVariableConfig::serialize_variable`]: reads a state variable
+    /// from a JSON value in the output unit of this variable, or its default unit if
+    /// no output unit is set. a ratio may also be written as a percentage string such as
+    /// `"63%"`, a time as a clock time `"HH:MM"` or `"HH:MM:SS"` since midnight, and a
+    /// date and time as an RFC 3339 string.
+    pub fn deserialize_variable(
+        &self,
+        value: &serde_json::Value,
+    ) -> Result<StateVariable, StateModelError> {
+        let as_f64 = || {
+            value
+                .as_f64()
+                .ok_or_else(|| StateModelError::EncodeError(value.to_string(), String::from("f64")))
+        };
+        match self {
+            StateVariableConfig::Distance { output_unit, .. } => {
+                let v = as_f64()?;
+                Ok(StateVariable(output_unit.map_or(v, |unit| {
+                    DistanceUnit::default().from_uom(unit.to_uom(v))
+                })))
+            }
+            StateVariableConfig::Time { .. } if value.is_string() => {
+                let seconds = parse_clock_time(value.as_str().unwrap_or_default())?;
+                Ok(StateVariable(
+                    TimeUnit::default().from_uom(TimeUnit::Seconds.to_uom(seconds)),
+                ))
+            }
+            StateVariableConfig::Time { output_unit, .. } => {
+                let v = as_f64()?;
+                Ok(StateVariable(output_unit.map_or(v, |unit| {
+                    TimeUnit::default().from_uom(unit.to_uom(v))
+                })))
+            }
+            StateVariableConfig::Speed { output_unit, .. } => {
+                let v = as_f64()?;
+                Ok(StateVariable(output_unit.map_or(v, |unit| {
+                    SpeedUnit::default().from_uom(unit.to_uom(v))
+                })))
+            }
+            StateVariableConfig::Energy { output_unit, .. } => {
+                let v = as_f64()?;
+                Ok(StateVariable(output_unit.map_or(v, |unit| {
+                    EnergyUnit::default().from_uom(unit.to_uom(v))
+                })))
+            }
+            StateVariableConfig::Ratio { .. } if value.is_string() => {
+                let percent = parse_percent(value.as_str().unwrap_or_default())?;
+                Ok(StateVariable(
+                    RatioUnit::default().from_uom(RatioUnit::Percent.to_uom(percent)),
+                ))
+            }
+            StateVariableConfig::Ratio { output_unit, .. } => {
+                let v = as_f64()?;
+                Ok(StateVariable(output_unit.map_or(v, |unit| {
+                    RatioUnit::default().from_uom(unit.to_uom(v))
+                })))
+            }
+            StateVariableConfig::Temperature { output_unit, .. } => {
+                let v = as_f64()?;
+                Ok(StateVariable(output_unit.map_or(v, |unit| {
+                    TemperatureUnit::default().from_uom(unit.to_uom(v))
+                })))
+            }
+            StateVariableConfig::Custom { custom_type, .. }
+                if custom_type == DepartureTime::CUSTOM_TYPE && value.is_string() =>
+            {
+                let time_str = value.as_str().unwrap_or_default();
+                let departure = DepartureTime::parse(time_str).map_err(|e| {
+                    StateModelError::EncodeError(
+                        time_str.to_string(),
+                        format!("string that is not an RFC 3339 date and time ({e})"),
+                    )
+                })?;
+                Ok(departure.to_state_variable())
+            }
+            StateVariableConfig::Custom { value: codec, .. } => match codec {
+                CustomVariableConfig::FloatingPoint { .. } => codec.encode_f64(&as_f64()?),
+                CustomVariableConfig::SignedInteger { .. } => {
+                    let v = value.as_i64().ok_or_else(|| {
+                        StateModelError::EncodeError(value.to_string(), String::from("i64"))
+                    })?;
+                    codec.encode_i64(&v)
+                }
+                CustomVariableConfig::UnsignedInteger { .. } => {
+                    let v = value.as_u64().ok_or_else(|| {
+                        StateModelError::EncodeError(value.to_string(), String::from("u64"))
+                    })?;
+                    codec.encode_u64(&v)
+                }
+                CustomVariableConfig::Boolean { .. } => {
+                    let v = value.as_bool().ok_or_else(|| {
+                        StateModelError::EncodeError(value.to_string(), String::from("bool"))
+                    })?;
+                    codec.encode_bool(&v)
+                }
+            },
+        }
+    }
+}
+
+/// reads a percentage string such as "63%"
+fn parse_percent(value: &str) -> Result<f64, StateModelError> {
+    value
+        .trim()
+        .strip_suffix('%')
+        .and_then(|v| v.trim().parse::<f64>().ok())
+        .ok_or_else(|| {
+            StateModelError::EncodeError(
+                value.to_string(),
+                String::from("string that is not a percentage such as '63%'"),
+            )
+        })
+}
+
+/// reads a clock time "HH:MM" or "HH:MM:SS" as the number of seconds since midnight
+fn parse_clock_time(value: &str) -> Result<f64, StateModelError> {
+    let error = || {
+        StateModelError::EncodeError(
+            value.to_string(),
+            String::from("string that is not a clock time such as '08:15'"),
+        )
+    };
+    let parts = value
+        .trim()
+        .split(':')
+        .map(|p| p.parse::<u32>().map_err(|_| error()))
+        .collect::<Result<Vec<_>, _>>()?;
+    let (hours, minutes, seconds) = match parts.as_slice() {
+        [h, m] => (*h, *m, 0),
+        [h, m, s] => (*h, *m, *s),
+        _ => return Err(error()),
+    };
+    if hours > 23 || minutes > 59 || seconds > 59 {
+        return Err(error());
+    }
+    Ok(f64::from(hours * 3600 + minutes * 60 + seconds))
 }
 
 impl Display for StateVariableConfig {
@@ -284,3 +426,37 @@ impl Display for StateVariableConfig {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_deserialize_strings() {
+        let soc = StateVariableConfig::Ratio {
+            initial: Ratio::new::<uom::si::ratio::percent>(100.0),
+            accumulator: true,
+            output_unit: Some(RatioUnit::Percent),
+        };
+        let from_string = soc.deserialize_variable(&json!("63%")).unwrap();
+        let from_number = soc.deserialize_variable(&json!(63.0)).unwrap();
+        assert!((from_string.0 - from_number.0).abs() < 1e-9);
+        assert!(soc.deserialize_variable(&json!("63")).is_err());
+
+        let departure = StateVariableConfig::Time {
+            initial: Time::new::<uom::si::time::second>(0.0),
+            accumulator: false,
+            output_unit: Some(TimeUnit::Hours),
+        };
+        let clock = departure.deserialize_variable(&json!("08:15")).unwrap();
+        let hours = departure.deserialize_variable(&json!(8.25)).unwrap();
+        assert!((clock.0 - hours.0).abs() < 1e-9);
+        for invalid in ["8", "24:00", "08:60", "08:15:00:00", "eight"] {
+            assert!(
+                departure.deserialize_variable(&json!(invalid)).is_err(),
+                "expected '{invalid}' to fail"
+            );
+        }
+    }
+}
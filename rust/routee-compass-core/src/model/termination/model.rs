@@ -106,17 +106,7 @@ impl TerminationModel {
             } => match frequency {
                 Some(freq) if !iteration.is_multiple_of(*freq) => false,
                 _ => {
-                    let root_bytes = allocative::size_of_unique(solution) as f64;
-                    let node_bytes = solution
-                        .nodes()
-                        .map(|n| allocative::size_of_unique(n) as f64)
-                        .sum::<f64>();
-                    let label_bytes = solution
-                        .labels()
-                        .map(|l| allocative::size_of_unique(l) as f64)
-                        .sum::<f64>();
-                    let memory_bytes = root_bytes + node_bytes + label_bytes;
-                    let memory = unit.convert(memory_bytes);
+                    let memory = unit.convert(solution.estimate_memory_bytes());
                     &memory > limit
                 }
             },
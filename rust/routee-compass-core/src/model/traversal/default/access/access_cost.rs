@@ -0,0 +1,131 @@
+use crate::model::{
+    network::{EdgeId, VertexId},
+    traversal::TraversalModelError,
+    unit::TimeUnit,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uom::{si::f64::Time, ConstZero};
+
+/// a row of an access cost file, which sets the access cost of either a vertex or an edge
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AccessCostRow {
+    pub vertex_id: Option<VertexId>,
+    pub edge_id: Option<EdgeId>,
+    pub time: Option<f64>,
+    pub cost: Option<f64>,
+}
+
+/// the time and (monetary) cost of accessing a vertex or an edge
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AccessCost {
+    pub time: Time,
+    pub cost: f64,
+}
+
+impl AccessCost {
+    pub const ZERO: AccessCost = AccessCost {
+        time: Time::ZERO,
+        cost: 0.0,
+    };
+}
+
+impl std::ops::Add for AccessCost {
+    type Output = AccessCost;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        AccessCost {
+            time: self.time + rhs.time,
+            cost: self.cost + rhs.cost,
+        }
+    }
+}
+
+/// the access costs of the vertices and edges of an edge list
+#[derive(Clone, Debug, Default)]
+pub struct AccessCostTable {
+    pub vertices: HashMap<VertexId, AccessCost>,
+    pub edges: HashMap<EdgeId, AccessCost>,
+}
+
+impl AccessCostTable {
+    /// builds the table from the rows of an access cost file. rows for the same vertex
+    /// or edge are summed.
+    pub fn from_rows(
+        rows: &[AccessCostRow],
+        time_unit: &TimeUnit,
+    ) -> Result<AccessCostTable, TraversalModelError> {
+        let mut table = AccessCostTable::default();
+        for (idx, row) in rows.iter().enumerate() {
+            let access = AccessCost {
+                time: time_unit.to_uom(row.time.unwrap_or_default()),
+                cost: row.cost.unwrap_or_default(),
+            };
+            match (row.vertex_id, row.edge_id) {
+                (Some(vertex_id), None) => {
+                    let entry = table.vertices.entry(vertex_id).or_insert(AccessCost::ZERO);
+                    *entry = *entry + access;
+                }
+                (None, Some(edge_id)) => {
+                    let entry = table.edges.entry(edge_id).or_insert(AccessCost::ZERO);
+                    *entry = *entry + access;
+                }
+                _ => {
+                    return Err(TraversalModelError::BuildError(format!(
+                        "access cost row {idx} must set exactly one of vertex_id and edge_id"
+                    )))
+                }
+            }
+        }
+        Ok(table)
+    }
+
+    /// the access cost of traversing an edge: the cost of entering the edge plus the
+    /// cost of entering the vertex at its end
+    pub fn get(&self, edge_id: &EdgeId, dst_vertex_id: &VertexId) -> Option<AccessCost> {
+        match (self.edges.get(edge_id), self.vertices.get(dst_vertex_id)) {
+            (None, None) => None,
+            (edge, vertex) => Some(
+                edge.copied().unwrap_or(AccessCost::ZERO)
+                    + vertex.copied().unwrap_or(AccessCost::ZERO),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use uom::si::time::second;
+
+    #[test]
+    fn test_from_rows() {
+        let row = |vertex_id: Option<usize>, edge_id: Option<usize>, time, cost| AccessCostRow {
+            vertex_id: vertex_id.map(VertexId),
+            edge_id: edge_id.map(EdgeId),
+            time,
+            cost,
+        };
+        let rows = vec![
+            row(Some(2), None, Some(1.5), None),
+            row(None, Some(7), Some(20.0), Some(12.5)),
+            row(Some(2), None, None, Some(3.0)),
+        ];
+        let table = AccessCostTable::from_rows(&rows, &TimeUnit::Minutes).unwrap();
+
+        let ferry = table.get(&EdgeId(7), &VertexId(0)).unwrap();
+        assert_eq!(ferry.time.get::<second>(), 1200.0);
+        assert_eq!(ferry.cost, 12.5);
+        let garage = table.get(&EdgeId(1), &VertexId(2)).unwrap();
+        assert_eq!(garage.time.get::<second>(), 90.0);
+        assert_eq!(garage.cost, 3.0);
+        let both = table.get(&EdgeId(7), &VertexId(2)).unwrap();
+        assert_eq!(both.cost, 15.5);
+        assert!(table.get(&EdgeId(1), &VertexId(0)).is_none());
+
+        let invalid = vec![row(Some(1), Some(1), Some(1.0), None)];
+        assert!(AccessCostTable::from_rows(&invalid, &TimeUnit::Minutes).is_err());
+        let invalid = vec![row(None, None, Some(1.0), None)];
+        assert!(AccessCostTable::from_rows(&invalid, &TimeUnit::Minutes).is_err());
+    }
+}
@@ -0,0 +1,45 @@
+use super::{AccessCostRow, AccessCostTable, AccessTraversalConfig, AccessTraversalService};
+use crate::{
+    model::traversal::{TraversalModelBuilder, TraversalModelError, TraversalModelService},
+    util::fs::read_utils,
+};
+use kdam::Bar;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+pub struct AccessTraversalBuilder {}
+
+impl TraversalModelBuilder for AccessTraversalBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn TraversalModelService>, TraversalModelError> {
+        let config: AccessTraversalConfig =
+            serde_json::from_value(parameters.clone()).map_err(|e| {
+                TraversalModelError::BuildError(format!(
+                    "failed to read access traversal model configuration: {e}"
+                ))
+            })?;
+
+        let file_path = PathBuf::from(&config.access_cost_input_file);
+        let rows = read_utils::from_csv::<AccessCostRow>(
+            &file_path.as_path(),
+            true,
+            Some(Bar::builder().desc("access costs")),
+            None,
+        )
+        .map_err(|e| {
+            TraversalModelError::BuildError(format!(
+                "error reading access costs from file {file_path:?}: {e}"
+            ))
+        })?;
+        let table = AccessCostTable::from_rows(&rows, &config.time_unit)?;
+
+        let service = AccessTraversalService {
+            table: Arc::new(table),
+            time_unit: config.time_unit,
+            include_trip_time: config.include_trip_time.unwrap_or(true),
+        };
+        Ok(Arc::new(service))
+    }
+}
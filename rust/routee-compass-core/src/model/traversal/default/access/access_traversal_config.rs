@@ -0,0 +1,14 @@
+use crate::model::unit::TimeUnit;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct AccessTraversalConfig {
+    /// CSV file with `vertex_id`, `edge_id`, `time` and `cost` columns. each row sets
+    /// either a vertex or an edge, and either of the time and cost may be empty.
+    pub access_cost_input_file: String,
+    /// time unit of the access times in the file, and of the access time state variable
+    pub time_unit: TimeUnit,
+    /// whether access times are added to the trip time, defaults to true
+    pub include_trip_time: Option<bool>,
+}
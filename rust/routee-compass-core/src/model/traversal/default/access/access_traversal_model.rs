@@ -0,0 +1,111 @@
+use super::AccessCostTable;
+use crate::{
+    algorithm::search::SearchTree,
+    model::{
+        network::{Edge, Vertex},
+        state::{
+            CustomVariableConfig, InputFeature, StateModel, StateVariable, StateVariableConfig,
+        },
+        traversal::{default::fieldname, TraversalModel, TraversalModelError},
+        unit::TimeUnit,
+    },
+};
+use ordered_float::OrderedFloat;
+use std::sync::Arc;
+use uom::{si::f64::Time, ConstZero};
+
+pub struct AccessTraversalModel {
+    pub table: Arc<AccessCostTable>,
+    pub time_unit: TimeUnit,
+    pub include_trip_time: bool,
+}
+
+impl TraversalModel for AccessTraversalModel {
+    fn name(&self) -> String {
+        "Access Traversal Model".to_string()
+    }
+
+    fn input_features(&self) -> Vec<InputFeature> {
+        vec![]
+    }
+
+    fn output_features(&self) -> Vec<(String, StateVariableConfig)> {
+        let mut features = vec![
+            (
+                String::from(fieldname::ACCESS_TIME),
+                StateVariableConfig::Time {
+                    initial: Time::ZERO,
+                    accumulator: false,
+                    output_unit: Some(self.time_unit),
+                },
+            ),
+            (
+                String::from(fieldname::TRIP_ACCESS_COST),
+                StateVariableConfig::Custom {
+                    custom_type: String::from("currency"),
+                    value: CustomVariableConfig::FloatingPoint {
+                        initial: OrderedFloat(0.0),
+                    },
+                    accumulator: true,
+                },
+            ),
+            (
+                String::from(fieldname::EDGE_TIME),
+                StateVariableConfig::Time {
+                    initial: Time::ZERO,
+                    accumulator: false,
+                    output_unit: None,
+                },
+            ),
+        ];
+        if self.include_trip_time {
+            features.push((
+                String::from(fieldname::TRIP_TIME),
+                StateVariableConfig::Time {
+                    initial: Time::ZERO,
+                    accumulator: true,
+                    output_unit: None,
+                },
+            ));
+        }
+        features
+    }
+
+    fn traverse_edge(
+        &self,
+        traversal: (&Vertex, &Edge, &Vertex),
+        state: &mut Vec<StateVariable>,
+        _tree: &SearchTree,
+        state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        let (_, edge, dst) = traversal;
+        let Some(access) = self.table.get(&edge.edge_id, &dst.vertex_id) else {
+            return Ok(());
+        };
+        state_model.set_time(state, fieldname::ACCESS_TIME, &access.time)?;
+        state_model.add_time(state, fieldname::EDGE_TIME, &access.time)?;
+        if self.include_trip_time {
+            state_model.add_time(state, fieldname::TRIP_TIME, &access.time)?;
+        }
+        if access.cost != 0.0 {
+            let trip_cost = state_model.get_custom_f64(state, fieldname::TRIP_ACCESS_COST)?;
+            state_model.set_custom_f64(
+                state,
+                fieldname::TRIP_ACCESS_COST,
+                &(trip_cost + access.cost),
+            )?;
+        }
+        Ok(())
+    }
+
+    fn estimate_traversal(
+        &self,
+        _od: (&Vertex, &Vertex),
+        _state: &mut Vec<StateVariable>,
+        _tree: &SearchTree,
+        _state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        // access costs are not estimated, which keeps the estimate admissible
+        Ok(())
+    }
+}
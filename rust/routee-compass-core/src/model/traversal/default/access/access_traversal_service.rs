@@ -0,0 +1,26 @@
+use super::{AccessCostTable, AccessTraversalModel};
+use crate::model::{
+    traversal::{TraversalModel, TraversalModelError, TraversalModelService},
+    unit::TimeUnit,
+};
+use std::sync::Arc;
+
+pub struct AccessTraversalService {
+    pub table: Arc<AccessCostTable>,
+    pub time_unit: TimeUnit,
+    pub include_trip_time: bool,
+}
+
+impl TraversalModelService for AccessTraversalService {
+    fn build(
+        &self,
+        _query: &serde_json::Value,
+    ) -> Result<Arc<dyn TraversalModel>, TraversalModelError> {
+        let model = AccessTraversalModel {
+            table: self.table.clone(),
+            time_unit: self.time_unit,
+            include_trip_time: self.include_trip_time,
+        };
+        Ok(Arc::new(model))
+    }
+}
@@ -0,0 +1,18 @@
+//! applies the time and cost of accessing a vertex or an edge, such as boarding a ferry,
+//! entering a parking garage or a gated community, or crossing a border. costs are read
+//! from a file of vertex and edge access costs. a vertex cost applies when a route enters
+//! the vertex, and an edge cost applies when a route enters the edge. the access time is
+//! added to the edge time and trip time, and the access cost is accumulated as the trip
+//! access cost, which the cost model may price as a raw (currency) value.
+
+mod access_cost;
+mod access_traversal_builder;
+mod access_traversal_config;
+mod access_traversal_model;
+mod access_traversal_service;
+
+pub use access_cost::{AccessCost, AccessCostRow, AccessCostTable};
+pub use access_traversal_builder::AccessTraversalBuilder;
+pub use access_traversal_config::AccessTraversalConfig;
+pub use access_traversal_model::AccessTraversalModel;
+pub use access_traversal_service::AccessTraversalService;
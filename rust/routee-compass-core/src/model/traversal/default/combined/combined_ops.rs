@@ -8,6 +8,9 @@ use topological_sort;
 /// must appear earlier in the list than the time model.
 ///
 /// only confirms that the names match, ignores confirming the feature types match.
+/// a model may read a feature it also writes (such as an energy model updating the
+/// state of charge), which is not a dependency on itself. any other cycle is an error,
+/// as there is no order in which each model would read up-to-date inputs.
 ///
 /// # Arguments
 ///
@@ -15,7 +18,7 @@ use topological_sort;
 ///
 /// # Returns
 ///
-/// Sorted list of models, or an error if dependencies are missing
+/// Sorted list of models, or an error if dependencies are missing or cyclical
 pub fn topological_dependency_sort(
     models: &[Arc<dyn TraversalModel>],
 ) -> Result<Vec<Arc<dyn TraversalModel>>, TraversalModelError> {
@@ -29,21 +32,19 @@ pub fn topological_dependency_sort(
     let mut missing_parents: Vec<String> = vec![];
     let mut sort = topological_sort::TopologicalSort::<usize>::new();
     for (idx, m) in models.iter().enumerate() {
-        let input_features = m.input_features();
-        if input_features.is_empty() {
-            sort.insert(idx);
-        } else {
-            for feature in input_features.iter() {
-                match &output_features_lookup.get(&feature.name()) {
-                    None => {
-                        missing_parents.push(feature.name());
-                    }
-                    Some(ref_idxs) => {
-                        // look at all dependencies but ignore self-looping dependencies
-                        let refs_iter = ref_idxs.iter().filter(|i| &idx != *i);
-                        for ref_idx in refs_iter {
-                            sort.add_dependency(idx, *ref_idx);
-                        }
+        // every model is inserted, as a model whose only dependencies are on itself
+        // would otherwise never enter the sort
+        sort.insert(idx);
+        for feature in m.input_features().iter() {
+            match &output_features_lookup.get(&feature.name()) {
+                None => {
+                    missing_parents.push(feature.name());
+                }
+                Some(ref_idxs) => {
+                    // look at all dependencies but ignore self-looping dependencies
+                    let refs_iter = ref_idxs.iter().filter(|i| &idx != *i);
+                    for ref_idx in refs_iter {
+                        sort.add_dependency(idx, *ref_idx);
                     }
                 }
             }
@@ -60,27 +61,43 @@ pub fn topological_dependency_sort(
 
     // apply topological sort to the models.
     // the correct order we want is the opposite of the popped result
-    let mut result = vec![];
+    let mut sorted_idxs = vec![];
     while let Some(m_idx) = sort.pop() {
-        let model = models.get(m_idx).ok_or_else(|| {
-            TraversalModelError::BuildError(format!("internal error: sort has model index {m_idx} which is not found in the model collection"))
-        })?;
-        result.push(model.clone());
+        sorted_idxs.push(m_idx);
     }
-    result.reverse();
-
-    log::debug!(
-        "topological sort of traversal models: {}",
-        result.iter().map(|m| m.name()).join(", ")
-    );
+    sorted_idxs.reverse();
 
     // topological_sort crate's pop() method stops when collection is empty, unless there is a cyclical
     // dependency, in which case the collection will return None and remain non-empty.
     if !sort.is_empty() {
-        let msg = format!("cyclical dependency in traversal model features: {sort:?}");
-        return Err(TraversalModelError::BuildError(msg));
+        let cycle = models
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| !sorted_idxs.contains(idx))
+            .map(|(_, m)| {
+                let inputs = m.input_features().iter().map(|f| f.name()).join("+");
+                let outputs = m.output_features().iter().map(|(n, _)| n).join("+");
+                format!("[{}: {inputs} -> {outputs}]", m.name())
+            })
+            .join(", ");
+        let msg = format!("cyclical dependency in traversal model features between models {cycle}");
+        return Err(TraversalModelError::CyclicalDependency(msg));
     }
 
+    let result = sorted_idxs
+        .into_iter()
+        .map(|m_idx| {
+            models.get(m_idx).cloned().ok_or_else(|| {
+                TraversalModelError::BuildError(format!("internal error: sort has model index {m_idx} which is not found in the model collection"))
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    log::debug!(
+        "topological sort of traversal models: {}",
+        result.iter().map(|m| m.name()).join(", ")
+    );
+
     Ok(result)
 }
 
@@ -263,6 +280,53 @@ mod test {
         }
     }
 
+    /// tests that a model whose only inputs are its own outputs is kept in the sort
+    #[test]
+    fn test_only_self_dependency() {
+        init_test_logger();
+
+        let soc_feature = InputFeature::Ratio {
+            name: "soc".to_string(),
+            unit: None,
+        };
+        let distance = MockModel::new(vec![], vec!["distance"]);
+        let soc = MockModel::new(vec![soc_feature], vec!["soc"]);
+        let models: Vec<Arc<dyn TraversalModel>> = vec![Arc::new(soc), Arc::new(distance)];
+
+        let sorted = topological_dependency_sort(&models).expect("failure during sort function");
+        assert_eq!(sorted.len(), 2);
+    }
+
+    /// tests that models depending on each other's outputs are reported by name
+    #[test]
+    fn test_cyclical_dependency() {
+        init_test_logger();
+
+        let speed_feature = InputFeature::Speed {
+            name: "speed".to_string(),
+            unit: None,
+        };
+        let energy_feature = InputFeature::Energy {
+            name: "energy".to_string(),
+            unit: None,
+        };
+        let distance = MockModel::new(vec![], vec!["distance"]);
+        let speed = MockModel::new(vec![energy_feature], vec!["speed"]);
+        let energy = MockModel::new(vec![speed_feature], vec!["energy"]);
+        let models: Vec<Arc<dyn TraversalModel>> =
+            vec![Arc::new(energy), Arc::new(distance), Arc::new(speed)];
+
+        match topological_dependency_sort(&models) {
+            Err(TraversalModelError::CyclicalDependency(msg)) => {
+                assert!(msg.contains("speed -> energy"), "{msg}");
+                assert!(msg.contains("energy -> speed"), "{msg}");
+                assert!(!msg.contains("-> distance"), "{msg}");
+            }
+            Err(other) => panic!("unexpected error: {other}"),
+            Ok(_) => panic!("expected cyclical dependency error"),
+        }
+    }
+
     fn init_test_logger() {
         let _ = env_logger::builder()
             .is_test(true)
@@ -5,15 +5,16 @@ use crate::{
 };
 use itertools::Itertools;
 use log;
-use std::{collections::HashMap, rc::Rc, sync::Arc};
+use rayon::prelude::*;
+use std::{collections::HashMap, sync::Arc};
 
 pub struct CombinedTraversalBuilder {
-    builders: HashMap<String, Rc<dyn TraversalModelBuilder>>,
+    builders: HashMap<String, Arc<dyn TraversalModelBuilder>>,
 }
 
 impl CombinedTraversalBuilder {
     pub fn new(
-        builders: HashMap<String, Rc<dyn TraversalModelBuilder>>,
+        builders: HashMap<String, Arc<dyn TraversalModelBuilder>>,
     ) -> CombinedTraversalBuilder {
         CombinedTraversalBuilder { builders }
     }
@@ -37,7 +38,7 @@ impl TraversalModelBuilder for CombinedTraversalBuilder {
 
 fn build_selected_models(
     conf: &serde_json::Value,
-    builders: &HashMap<String, Rc<dyn TraversalModelBuilder>>,
+    builders: &HashMap<String, Arc<dyn TraversalModelBuilder>>,
 ) -> Result<Arc<dyn TraversalModelService>, TraversalModelError> {
     let models_vec = conf.as_array().ok_or_else(|| {
         TraversalModelError::BuildError(format!(
@@ -45,30 +46,45 @@ fn build_selected_models(
             serde_json::to_string(conf).unwrap_or_default()
         ))
     })?;
+    // each model reads its own input files, so the models are built concurrently
     let services: Vec<Arc<dyn TraversalModelService>> = models_vec
-        .iter()
+        .par_iter()
         .map(|conf| build_model_from_json(conf, builders))
-        .try_collect()?;
-    let service: Arc<dyn TraversalModelService> = Arc::new(CombinedTraversalService::new(services));
-    Ok(service)
+        .collect::<Result<_, _>>()?;
+    validate_dependencies(services)
 }
 
 fn build_all_models(
     conf: &serde_json::Value,
-    builders: &HashMap<String, Rc<dyn TraversalModelBuilder>>,
+    builders: &HashMap<String, Arc<dyn TraversalModelBuilder>>,
 ) -> Result<Arc<dyn TraversalModelService>, TraversalModelError> {
     let services: Vec<Arc<dyn TraversalModelService>> = builders
-        .values()
-        .map(|builder| builder.build(conf))
-        .try_collect()?;
-    let service: Arc<dyn TraversalModelService> = Arc::new(CombinedTraversalService::new(services));
-    Ok(service)
+        .par_iter()
+        .map(|(_, builder)| builder.build(conf))
+        .collect::<Result<_, _>>()?;
+    validate_dependencies(services)
+}
+
+/// combines the services and confirms that the models they build do not depend on each
+/// other cyclically. the models are sorted by their dependencies as each query is run,
+/// so their order in the configuration does not matter, but a cycle can be found here,
+/// when the models can be built without a query, instead of failing every query.
+fn validate_dependencies(
+    services: Vec<Arc<dyn TraversalModelService>>,
+) -> Result<Arc<dyn TraversalModelService>, TraversalModelError> {
+    let service = CombinedTraversalService::new(services);
+    match service.build(&serde_json::json!({})) {
+        Err(e @ TraversalModelError::CyclicalDependency(_)) => return Err(e),
+        Err(e) => log::debug!("combined traversal model dependencies checked at query time: {e}"),
+        Ok(_) => {}
+    }
+    Ok(Arc::new(service))
 }
 
 /// builds a model from its configuration within the combined traversal model
 fn build_model_from_json(
     conf: &serde_json::Value,
-    builders: &HashMap<String, Rc<dyn TraversalModelBuilder>>,
+    builders: &HashMap<String, Arc<dyn TraversalModelBuilder>>,
 ) -> Result<Arc<dyn TraversalModelService>, TraversalModelError> {
     let (conf_stripped, key) =
         strip_type_from_config(conf).map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
@@ -32,4 +32,18 @@ impl TraversalModelService for CombinedTraversalService {
         let sorted_models = topological_dependency_sort(&models)?;
         Ok(Arc::new(CombinedTraversalModel::new(sorted_models)))
     }
+
+    fn data_version(&self) -> Result<Option<String>, TraversalModelError> {
+        let versions: Vec<Option<String>> = self
+            .services
+            .iter()
+            .map(|s| s.data_version())
+            .try_collect()?;
+        let versions = versions.into_iter().flatten().collect_vec();
+        if versions.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(versions.join(",")))
+        }
+    }
 }
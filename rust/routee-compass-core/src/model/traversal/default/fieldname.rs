@@ -17,10 +17,20 @@ pub const EDGE_SPEED: &str = "edge_speed";
 
 /// state feature name for delays due to turn angles on trip
 pub const EDGE_TURN_DELAY: &str = "edge_turn_delay";
+/// state feature name for delays due to intersection controls on trip
+pub const EDGE_INTERSECTION_DELAY: &str = "edge_intersection_delay";
 /// state feature name for time required to traverse this graph edge
 pub const EDGE_TIME: &str = "edge_time";
 /// state feature name for accumulated trip time to traverse this edge
 pub const TRIP_TIME: &str = "trip_time";
+/// state feature name for the absolute departure time of a trip
+pub const DEPARTURE_TIME: &str = "departure_time";
+/// state feature name for accumulated time spent waiting during a trip
+pub const TRIP_WAIT_TIME: &str = "trip_wait_time";
+/// state feature name for the time to access a vertex or edge, such as boarding a ferry
+pub const ACCESS_TIME: &str = "access_time";
+/// state feature name for accumulated (monetary) costs of accessing vertices and edges
+pub const TRIP_ACCESS_COST: &str = "trip_access_cost";
 
 /// state feature name for grade state values for a single graph edge
 pub const EDGE_GRADE: &str = "edge_grade";
@@ -1,4 +1,5 @@
 use crate::model::unit::RatioUnit;
+use crate::util::encoded_table::TableEncoding;
 use serde::{Deserialize, Serialize};
 
 /// provides configuration for instantiating the grade engine used in grade modeling.
@@ -9,4 +10,6 @@ pub struct GradeConfiguration {
     pub grade_input_file: String,
     /// type of grade values in file
     pub grade_unit: RatioUnit,
+    /// how the grade table is stored in memory, by default as 64-bit floats
+    pub table_encoding: Option<TableEncoding>,
 }
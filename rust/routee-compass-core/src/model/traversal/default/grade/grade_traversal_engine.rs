@@ -1,21 +1,26 @@
 use super::GradeConfiguration;
 use crate::{
-    model::{network::EdgeId, traversal::TraversalModelError},
-    util::fs::{read_decoders, read_utils},
+    model::{network::EdgeId, traversal::TraversalModelError, unit::RatioUnit},
+    util::{
+        encoded_table::{EncodedTable, TableEncoding},
+        fs::{read_decoders, read_utils},
+    },
 };
 use kdam::Bar;
 use std::sync::Arc;
 use uom::{si::f64::Ratio, ConstZero};
 
 pub struct GradeTraversalEngine {
-    pub grade_by_edge_id: Option<Arc<Box<[Ratio]>>>,
+    /// grades by edge id, in the grade unit of the input file
+    pub grade_by_edge_id: Option<Arc<EncodedTable>>,
+    pub grade_unit: RatioUnit,
 }
 
 impl GradeTraversalEngine {
     /// builds a grade lookup table from the input file, or, if not provided, stubs a
     /// grade engine that always returns 0.
     pub fn new(config: &GradeConfiguration) -> Result<GradeTraversalEngine, TraversalModelError> {
-        let grade_table: Box<[Ratio]> = read_utils::read_raw_file(
+        let grades: Vec<f64> = read_utils::read_raw_file(
             config.grade_input_file.clone(),
             read_decoders::f64,
             Some(Bar::builder().desc("link grades")),
@@ -28,13 +33,24 @@ impl GradeTraversalEngine {
                 e
             ))
         })?
-        .iter()
-        .map(|&g| config.grade_unit.to_uom(g))
-        .collect::<Vec<Ratio>>()
-        .into_boxed_slice();
+        .into_vec();
+        let table_encoding = config.table_encoding.unwrap_or_default();
+        let grade_table = EncodedTable::new(grades, table_encoding).map_err(|e| {
+            TraversalModelError::BuildError(format!("cannot encode grade table: {e}"))
+        })?;
+        if table_encoding != TableEncoding::F64 {
+            log::info!(
+                "encoded grade table as {:?} using {} bytes with max error {} {}",
+                table_encoding,
+                grade_table.size_in_bytes(),
+                grade_table.max_error(),
+                config.grade_unit
+            );
+        }
 
         let engine = GradeTraversalEngine {
             grade_by_edge_id: Some(Arc::new(grade_table)),
+            grade_unit: config.grade_unit,
         };
 
         Ok(engine)
@@ -44,12 +60,12 @@ impl GradeTraversalEngine {
         match &self.grade_by_edge_id {
             None => Ok(Ratio::ZERO),
             Some(table) => {
-                let grade: &Ratio = table.get(edge_id.as_usize()).ok_or_else(|| {
+                let grade = table.get(edge_id.as_usize()).ok_or_else(|| {
                     TraversalModelError::TraversalModelFailure(format!(
                         "missing index {edge_id} from grade table"
                     ))
                 })?;
-                Ok(*grade)
+                Ok(self.grade_unit.to_uom(grade))
             }
         }
     }
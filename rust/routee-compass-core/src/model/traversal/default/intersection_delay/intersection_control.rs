@@ -0,0 +1,74 @@
+use crate::model::{network::VertexId, unit::TimeUnit};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fmt::Display};
+use uom::si::f64::Time;
+
+/// the traffic control of an intersection
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum IntersectionControl {
+    Signal,
+    Stop,
+    Roundabout,
+    None,
+}
+
+impl Display for IntersectionControl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", serde_json::to_string(self).unwrap_or_default())
+    }
+}
+
+/// a row of an intersection control file
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct IntersectionControlRow {
+    pub vertex_id: VertexId,
+    pub control: IntersectionControl,
+}
+
+impl IntersectionControl {
+    /// finds the delay at each vertex from its control type. control types missing
+    /// from the delay table, and vertices missing from the rows, have no delay.
+    pub fn vertex_delays(
+        rows: &[IntersectionControlRow],
+        delays: &HashMap<IntersectionControl, f64>,
+        time_unit: &TimeUnit,
+    ) -> HashMap<VertexId, Time> {
+        rows.iter()
+            .filter_map(|row| {
+                let delay = delays.get(&row.control).copied().unwrap_or_default();
+                (delay > 0.0).then(|| (row.vertex_id, time_unit.to_uom(delay)))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use uom::si::time::second;
+
+    #[test]
+    fn test_vertex_delays() {
+        let row = |vertex_id, control| IntersectionControlRow {
+            vertex_id: VertexId(vertex_id),
+            control,
+        };
+        let rows = vec![
+            row(0, IntersectionControl::Signal),
+            row(1, IntersectionControl::Stop),
+            row(2, IntersectionControl::Roundabout),
+            row(3, IntersectionControl::None),
+        ];
+        let delays = HashMap::from([
+            (IntersectionControl::Signal, 15.0),
+            (IntersectionControl::Roundabout, 4.5),
+            (IntersectionControl::None, 0.0),
+        ]);
+        let vertex_delays = IntersectionControl::vertex_delays(&rows, &delays, &TimeUnit::Seconds);
+        assert_eq!(vertex_delays.len(), 2);
+        assert_eq!(vertex_delays[&VertexId(0)].get::<second>(), 15.0);
+        assert_eq!(vertex_delays[&VertexId(2)].get::<second>(), 4.5);
+        assert!(!vertex_delays.contains_key(&VertexId(1)));
+    }
+}
@@ -0,0 +1,48 @@
+use super::{
+    IntersectionControl, IntersectionControlRow, IntersectionDelayTraversalConfig,
+    IntersectionDelayTraversalService,
+};
+use crate::{
+    model::traversal::{TraversalModelBuilder, TraversalModelError, TraversalModelService},
+    util::fs::read_utils,
+};
+use kdam::Bar;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+pub struct IntersectionDelayTraversalBuilder {}
+
+impl TraversalModelBuilder for IntersectionDelayTraversalBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn TraversalModelService>, TraversalModelError> {
+        let config: IntersectionDelayTraversalConfig = serde_json::from_value(parameters.clone())
+            .map_err(|e| {
+            TraversalModelError::BuildError(format!(
+                "failure reading intersection delay traversal configuration: {e}"
+            ))
+        })?;
+
+        let file_path = PathBuf::from(&config.intersection_control_input_file);
+        let rows = read_utils::from_csv::<IntersectionControlRow>(
+            &file_path.as_path(),
+            true,
+            Some(Bar::builder().desc("intersection controls")),
+            None,
+        )
+        .map_err(|e| {
+            TraversalModelError::BuildError(format!(
+                "error reading intersection controls from file {file_path:?}: {e}"
+            ))
+        })?;
+
+        let vertex_delays =
+            IntersectionControl::vertex_delays(&rows, &config.delays, &config.time_unit);
+        let service = IntersectionDelayTraversalService {
+            vertex_delays: Arc::new(vertex_delays),
+            include_trip_time: config.include_trip_time.unwrap_or(true),
+        };
+        Ok(Arc::new(service))
+    }
+}
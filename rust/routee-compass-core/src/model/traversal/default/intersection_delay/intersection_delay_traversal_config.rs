@@ -0,0 +1,17 @@
+use super::IntersectionControl;
+use crate::model::unit::TimeUnit;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct IntersectionDelayTraversalConfig {
+    /// CSV file with `vertex_id` and `control` columns, where the control is one of
+    /// `signal`, `stop`, `roundabout` or `none`. vertices not listed have no control.
+    pub intersection_control_input_file: String,
+    /// expected delay by control type, in the provided time unit
+    pub delays: HashMap<IntersectionControl, f64>,
+    /// time unit of delays
+    pub time_unit: TimeUnit,
+    pub include_trip_time: Option<bool>,
+}
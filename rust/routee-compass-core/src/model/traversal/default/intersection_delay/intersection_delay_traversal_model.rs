@@ -0,0 +1,91 @@
+use crate::{
+    algorithm::search::SearchTree,
+    model::{
+        network::{Edge, Vertex, VertexId},
+        state::{InputFeature, StateModel, StateVariable, StateVariableConfig},
+        traversal::{default::fieldname, TraversalModel, TraversalModelError},
+        unit::TimeUnit,
+    },
+};
+use std::{collections::HashMap, sync::Arc};
+use uom::{si::f64::Time, ConstZero};
+
+pub struct IntersectionDelayTraversalModel {
+    pub vertex_delays: Arc<HashMap<VertexId, Time>>,
+    pub include_trip_time: bool,
+}
+
+impl TraversalModel for IntersectionDelayTraversalModel {
+    fn name(&self) -> String {
+        "Intersection Delay Traversal Model".to_string()
+    }
+
+    fn input_features(&self) -> Vec<InputFeature> {
+        vec![]
+    }
+
+    fn output_features(&self) -> Vec<(String, StateVariableConfig)> {
+        let mut features = vec![
+            (
+                String::from(fieldname::EDGE_INTERSECTION_DELAY),
+                StateVariableConfig::Time {
+                    initial: Time::ZERO,
+                    accumulator: false,
+                    output_unit: Some(TimeUnit::Seconds),
+                },
+            ),
+            (
+                String::from(fieldname::EDGE_TIME),
+                StateVariableConfig::Time {
+                    initial: Time::ZERO,
+                    accumulator: false,
+                    output_unit: None,
+                },
+            ),
+        ];
+        if self.include_trip_time {
+            features.push((
+                String::from(fieldname::TRIP_TIME),
+                StateVariableConfig::Time {
+                    initial: Time::ZERO,
+                    accumulator: true,
+                    output_unit: None,
+                },
+            ));
+        }
+        features
+    }
+
+    fn traverse_edge(
+        &self,
+        traversal: (&Vertex, &Edge, &Vertex),
+        state: &mut Vec<StateVariable>,
+        tree: &SearchTree,
+        state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        let (src, _, _) = traversal;
+        let Some(delay) = self.vertex_delays.get(&src.vertex_id) else {
+            return Ok(());
+        };
+        if tree.get_incoming_edge(src.vertex_id).is_none() {
+            // the route starts at this vertex, so it does not pass through the intersection
+            return Ok(());
+        }
+        state_model.set_time(state, fieldname::EDGE_INTERSECTION_DELAY, delay)?;
+        state_model.add_time(state, fieldname::EDGE_TIME, delay)?;
+        if self.include_trip_time {
+            state_model.add_time(state, fieldname::TRIP_TIME, delay)?;
+        }
+        Ok(())
+    }
+
+    fn estimate_traversal(
+        &self,
+        _od: (&Vertex, &Vertex),
+        _state: &mut Vec<StateVariable>,
+        _tree: &SearchTree,
+        _state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        Ok(())
+    }
+}
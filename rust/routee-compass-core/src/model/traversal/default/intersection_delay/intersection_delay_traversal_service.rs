@@ -0,0 +1,25 @@
+use super::IntersectionDelayTraversalModel;
+use crate::model::{
+    network::VertexId,
+    traversal::{TraversalModel, TraversalModelError, TraversalModelService},
+};
+use std::{collections::HashMap, sync::Arc};
+use uom::si::f64::Time;
+
+pub struct IntersectionDelayTraversalService {
+    pub vertex_delays: Arc<HashMap<VertexId, Time>>,
+    pub include_trip_time: bool,
+}
+
+impl TraversalModelService for IntersectionDelayTraversalService {
+    fn build(
+        &self,
+        _query: &serde_json::Value,
+    ) -> Result<Arc<dyn TraversalModel>, TraversalModelError> {
+        let model = IntersectionDelayTraversalModel {
+            vertex_delays: self.vertex_delays.clone(),
+            include_trip_time: self.include_trip_time,
+        };
+        Ok(Arc::new(model))
+    }
+}
@@ -0,0 +1,16 @@
+//! adds an expected delay when a route passes through an intersection, keyed on the
+//! traffic control of the intersection vertex (signal, stop, roundabout or none). this
+//! is a coarser alternative to the angle-based turn delay model that needs only a
+//! control type for each vertex.
+
+mod intersection_control;
+mod intersection_delay_traversal_builder;
+mod intersection_delay_traversal_config;
+mod intersection_delay_traversal_model;
+mod intersection_delay_traversal_service;
+
+pub use intersection_control::{IntersectionControl, IntersectionControlRow};
+pub use intersection_delay_traversal_builder::IntersectionDelayTraversalBuilder;
+pub use intersection_delay_traversal_config::IntersectionDelayTraversalConfig;
+pub use intersection_delay_traversal_model::IntersectionDelayTraversalModel;
+pub use intersection_delay_traversal_service::IntersectionDelayTraversalService;
@@ -1,10 +1,15 @@
+pub mod access;
 pub mod combined;
 pub mod custom;
 pub mod distance;
 pub mod elevation;
 pub mod fieldname;
 pub mod grade;
+pub mod intersection_delay;
+pub mod scheduled_link;
+pub mod scripted;
 pub mod speed;
 pub mod temperature;
 pub mod time;
+pub mod time_window;
 pub mod turn_delays;
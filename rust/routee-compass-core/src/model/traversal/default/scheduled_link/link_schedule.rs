@@ -0,0 +1,146 @@
+use crate::model::{network::EdgeId, traversal::TraversalModelError, unit::TimeUnit};
+use chrono::{NaiveTime, Timelike};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uom::si::f64::Time;
+
+const SECONDS_PER_DAY: f64 = 86400.0;
+
+/// a row of a schedule file. a row with a `headway` repeats its departure at that
+/// interval until the `last_departure`, or until the end of the day.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ScheduleRow {
+    pub edge_id: EdgeId,
+    pub departure: String,
+    pub crossing_time: f64,
+    pub headway: Option<f64>,
+    pub last_departure: Option<String>,
+}
+
+/// the daily departures of a scheduled link, as seconds since local midnight in
+/// departure order, with the crossing time of each departure.
+#[derive(Clone, Debug, Default)]
+pub struct LinkSchedule {
+    departures: Vec<(f64, Time)>,
+}
+
+impl LinkSchedule {
+    /// builds the schedule of each link from the rows of a schedule file
+    pub fn from_rows(
+        rows: &[ScheduleRow],
+        time_unit: &TimeUnit,
+    ) -> Result<HashMap<EdgeId, LinkSchedule>, TraversalModelError> {
+        let mut schedules: HashMap<EdgeId, LinkSchedule> = HashMap::new();
+        for (idx, row) in rows.iter().enumerate() {
+            let first = parse_departure(&row.departure, idx)?;
+            let crossing_time = time_unit.to_uom(row.crossing_time);
+            let schedule = schedules.entry(row.edge_id).or_default();
+            match row.headway {
+                None => schedule.departures.push((first, crossing_time)),
+                Some(headway) => {
+                    let headway_seconds = time_unit.to_uom(headway).get::<uom::si::time::second>();
+                    if headway_seconds <= 0.0 {
+                        return Err(TraversalModelError::BuildError(format!(
+                            "schedule row {idx} has a headway that is not positive: {headway}"
+                        )));
+                    }
+                    let last = match &row.last_departure {
+                        Some(last) => parse_departure(last, idx)?,
+                        None => SECONDS_PER_DAY - 1.0,
+                    };
+                    let mut departure = first;
+                    while departure <= last {
+                        schedule.departures.push((departure, crossing_time));
+                        departure += headway_seconds;
+                    }
+                }
+            }
+        }
+        for schedule in schedules.values_mut() {
+            schedule.departures.sort_by(|a, b| a.0.total_cmp(&b.0));
+        }
+        Ok(schedules)
+    }
+
+    /// finds the next departure at or after a local time of day, which is on the
+    /// following day if the last departure of the day has left.
+    ///
+    /// # Arguments
+    ///
+    /// * `time_of_day` - seconds since local midnight when arriving at the link
+    ///
+    /// # Returns
+    ///
+    /// the time spent waiting for the departure and the crossing time, or None if the
+    /// link has no departures
+    pub fn next_departure(&self, time_of_day: f64) -> Option<(Time, Time)> {
+        let idx = self
+            .departures
+            .partition_point(|(departure, _)| *departure < time_of_day);
+        let (wait_seconds, crossing_time) = match self.departures.get(idx) {
+            Some((departure, crossing_time)) => (departure - time_of_day, *crossing_time),
+            None => {
+                let (departure, crossing_time) = self.departures.first()?;
+                (SECONDS_PER_DAY - time_of_day + departure, *crossing_time)
+            }
+        };
+        let wait_time = Time::new::<uom::si::time::second>(wait_seconds);
+        Some((wait_time, crossing_time))
+    }
+}
+
+/// reads a local clock time "HH:MM" or "HH:MM:SS" as seconds since midnight
+fn parse_departure(value: &str, row: usize) -> Result<f64, TraversalModelError> {
+    NaiveTime::parse_from_str(value.trim(), "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(value.trim(), "%H:%M"))
+        .map(|t| f64::from(t.num_seconds_from_midnight()))
+        .map_err(|e| {
+            TraversalModelError::BuildError(format!(
+                "schedule row {row} has departure '{value}' which is not a clock time such as '08:15': {e}"
+            ))
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use uom::si::time::minute;
+
+    #[test]
+    fn test_next_departure() {
+        let row = |edge_id, departure: &str, headway, last_departure: Option<&str>| ScheduleRow {
+            edge_id: EdgeId(edge_id),
+            departure: departure.to_string(),
+            crossing_time: 40.0,
+            headway,
+            last_departure: last_departure.map(String::from),
+        };
+        let rows = vec![
+            row(0, "18:30", None, None),
+            row(0, "06:00", Some(90.0), Some("12:00")),
+            row(1, "00:00", Some(1.5), None),
+        ];
+        let schedules = LinkSchedule::from_rows(&rows, &TimeUnit::Minutes).unwrap();
+        let next = |edge_id, time_of_day: f64| {
+            let (wait, crossing) = schedules[&EdgeId(edge_id)]
+                .next_departure(time_of_day)
+                .unwrap();
+            (wait.get::<minute>(), crossing.get::<minute>())
+        };
+
+        // ferry departs at 06:00, 07:30, 09:00, 10:30, 12:00 and 18:30
+        assert_eq!(next(0, 6.0 * 3600.0), (0.0, 40.0));
+        assert_eq!(next(0, 7.0 * 3600.0), (30.0, 40.0));
+        assert_eq!(next(0, 12.5 * 3600.0), (360.0, 40.0));
+        // the last departure has left, so the next departure is at 06:00 tomorrow
+        assert_eq!(next(0, 20.0 * 3600.0), (600.0, 40.0));
+        // a signal cycle of 90 seconds
+        let (wait, _) = next(1, 100.0);
+        assert!((wait - 80.0 / 60.0).abs() < 1e-9);
+
+        let invalid = vec![row(2, "8am", None, None)];
+        assert!(LinkSchedule::from_rows(&invalid, &TimeUnit::Minutes).is_err());
+        let invalid = vec![row(2, "08:00", Some(0.0), None)];
+        assert!(LinkSchedule::from_rows(&invalid, &TimeUnit::Minutes).is_err());
+    }
+}
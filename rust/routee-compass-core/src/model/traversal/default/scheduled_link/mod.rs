@@ -0,0 +1,17 @@
+//! models links that can only be crossed at scheduled departures, such as ferries or
+//! ramps metered by a signal cycle. a route arriving at a scheduled link waits for its
+//! next departure, found from the local clock time of the trip, and then crosses it in
+//! the scheduled crossing time. the wait and crossing time replace the edge time of the
+//! link, and the wait is recorded as trip wait time.
+
+mod link_schedule;
+mod scheduled_link_traversal_builder;
+mod scheduled_link_traversal_config;
+mod scheduled_link_traversal_model;
+mod scheduled_link_traversal_service;
+
+pub use link_schedule::{LinkSchedule, ScheduleRow};
+pub use scheduled_link_traversal_builder::ScheduledLinkTraversalBuilder;
+pub use scheduled_link_traversal_config::ScheduledLinkTraversalConfig;
+pub use scheduled_link_traversal_model::ScheduledLinkTraversalModel;
+pub use scheduled_link_traversal_service::ScheduledLinkTraversalService;
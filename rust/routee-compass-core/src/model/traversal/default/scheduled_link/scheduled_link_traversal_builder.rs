@@ -0,0 +1,56 @@
+use super::{
+    LinkSchedule, ScheduleRow, ScheduledLinkTraversalConfig, ScheduledLinkTraversalService,
+};
+use crate::{
+    model::traversal::{TraversalModelBuilder, TraversalModelError, TraversalModelService},
+    util::fs::read_utils,
+};
+use chrono::{FixedOffset, Offset, Utc};
+use kdam::Bar;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+pub struct ScheduledLinkTraversalBuilder {}
+
+impl TraversalModelBuilder for ScheduledLinkTraversalBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn TraversalModelService>, TraversalModelError> {
+        let config: ScheduledLinkTraversalConfig = serde_json::from_value(parameters.clone())
+            .map_err(|e| {
+                TraversalModelError::BuildError(format!(
+                    "failed to read scheduled link traversal model configuration: {e}"
+                ))
+            })?;
+        let utc_offset = match &config.utc_offset {
+            None => Utc.fix(),
+            Some(offset) => offset.parse::<FixedOffset>().map_err(|e| {
+                TraversalModelError::BuildError(format!(
+                    "scheduled link utc_offset '{offset}' is not an offset such as '-06:00': {e}"
+                ))
+            })?,
+        };
+
+        let file_path = PathBuf::from(&config.schedule_input_file);
+        let rows = read_utils::from_csv::<ScheduleRow>(
+            &file_path.as_path(),
+            true,
+            Some(Bar::builder().desc("link schedules")),
+            None,
+        )
+        .map_err(|e| {
+            TraversalModelError::BuildError(format!(
+                "error reading link schedules from file {file_path:?}: {e}"
+            ))
+        })?;
+        let schedules = LinkSchedule::from_rows(&rows, &config.time_unit)?;
+
+        let service = ScheduledLinkTraversalService {
+            schedules: Arc::new(schedules),
+            time_unit: config.time_unit,
+            utc_offset,
+        };
+        Ok(Arc::new(service))
+    }
+}
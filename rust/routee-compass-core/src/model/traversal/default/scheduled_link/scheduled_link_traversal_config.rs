@@ -0,0 +1,15 @@
+use crate::model::unit::TimeUnit;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ScheduledLinkTraversalConfig {
+    /// CSV file with `edge_id`, `departure`, `crossing_time`, and optional `headway` and
+    /// `last_departure` columns. departures are local clock times such as "08:15".
+    pub schedule_input_file: String,
+    /// time unit of the crossing times and headways in the file, and of the trip wait time
+    pub time_unit: TimeUnit,
+    /// offset from UTC of the local clock times of the schedule, such as "-06:00".
+    /// defaults to UTC.
+    pub utc_offset: Option<String>,
+}
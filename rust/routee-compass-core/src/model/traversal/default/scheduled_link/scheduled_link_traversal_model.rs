@@ -0,0 +1,121 @@
+use super::LinkSchedule;
+use crate::{
+    algorithm::search::SearchTree,
+    model::{
+        network::{Edge, EdgeId, Vertex},
+        state::{DepartureTime, InputFeature, StateModel, StateVariable, StateVariableConfig},
+        traversal::{default::fieldname, TraversalModel, TraversalModelError},
+        unit::TimeUnit,
+    },
+};
+use chrono::{FixedOffset, Timelike};
+use std::{collections::HashMap, sync::Arc};
+use uom::{si::f64::Time, ConstZero};
+
+pub struct ScheduledLinkTraversalModel {
+    pub schedules: Arc<HashMap<EdgeId, LinkSchedule>>,
+    pub time_unit: TimeUnit,
+    pub utc_offset: FixedOffset,
+}
+
+impl TraversalModel for ScheduledLinkTraversalModel {
+    fn name(&self) -> String {
+        "Scheduled Link Traversal Model".to_string()
+    }
+
+    /// reads the edge time after all other models have added to it, so that the trip
+    /// time on arrival at the link is known.
+    fn input_features(&self) -> Vec<InputFeature> {
+        vec![
+            InputFeature::Time {
+                name: String::from(fieldname::EDGE_TIME),
+                unit: None,
+            },
+            InputFeature::Time {
+                name: String::from(fieldname::TRIP_TIME),
+                unit: None,
+            },
+        ]
+    }
+
+    /// the departure time is registered here so that the state model has one whenever
+    /// a scheduled link model is configured.
+    fn output_features(&self) -> Vec<(String, StateVariableConfig)> {
+        vec![
+            DepartureTime::feature(),
+            (
+                String::from(fieldname::EDGE_TIME),
+                StateVariableConfig::Time {
+                    initial: Time::ZERO,
+                    accumulator: false,
+                    output_unit: None,
+                },
+            ),
+            (
+                String::from(fieldname::TRIP_TIME),
+                StateVariableConfig::Time {
+                    initial: Time::ZERO,
+                    accumulator: true,
+                    output_unit: None,
+                },
+            ),
+            (
+                String::from(fieldname::TRIP_WAIT_TIME),
+                StateVariableConfig::Time {
+                    initial: Time::ZERO,
+                    accumulator: true,
+                    output_unit: Some(self.time_unit),
+                },
+            ),
+        ]
+    }
+
+    fn traverse_edge(
+        &self,
+        traversal: (&Vertex, &Edge, &Vertex),
+        state: &mut Vec<StateVariable>,
+        _tree: &SearchTree,
+        state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        let (_, edge, _) = traversal;
+        let Some(schedule) = self.schedules.get(&edge.edge_id) else {
+            return Ok(());
+        };
+        let edge_time = state_model.get_time(state, fieldname::EDGE_TIME)?;
+        let trip_time = state_model.get_time(state, fieldname::TRIP_TIME)?;
+        let arrival_trip_time = trip_time - edge_time;
+        let arrival = state_model
+            .get_departure_time(state)?
+            .local_clock_time(&arrival_trip_time, &self.utc_offset)?;
+        let time_of_day =
+            f64::from(arrival.num_seconds_from_midnight()) + f64::from(arrival.nanosecond()) / 1e9;
+        let (wait_time, crossing_time) = schedule.next_departure(time_of_day).ok_or_else(|| {
+            TraversalModelError::TraversalModelFailure(format!(
+                "scheduled link on edge {} has no departures",
+                edge.edge_id
+            ))
+        })?;
+
+        // the wait and crossing replace the time of the link found by other models
+        let link_time = wait_time + crossing_time;
+        state_model.set_time(state, fieldname::EDGE_TIME, &link_time)?;
+        state_model.set_time(
+            state,
+            fieldname::TRIP_TIME,
+            &(arrival_trip_time + link_time),
+        )?;
+        state_model.add_time(state, fieldname::TRIP_WAIT_TIME, &wait_time)?;
+        Ok(())
+    }
+
+    /// waiting depends on the time of arrival, so estimates exclude scheduled links.
+    fn estimate_traversal(
+        &self,
+        _od: (&Vertex, &Vertex),
+        _state: &mut Vec<StateVariable>,
+        _tree: &SearchTree,
+        _state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        Ok(())
+    }
+}
@@ -0,0 +1,28 @@
+use super::{LinkSchedule, ScheduledLinkTraversalModel};
+use crate::model::{
+    network::EdgeId,
+    traversal::{TraversalModel, TraversalModelError, TraversalModelService},
+    unit::TimeUnit,
+};
+use chrono::FixedOffset;
+use std::{collections::HashMap, sync::Arc};
+
+pub struct ScheduledLinkTraversalService {
+    pub schedules: Arc<HashMap<EdgeId, LinkSchedule>>,
+    pub time_unit: TimeUnit,
+    pub utc_offset: FixedOffset,
+}
+
+impl TraversalModelService for ScheduledLinkTraversalService {
+    fn build(
+        &self,
+        _query: &serde_json::Value,
+    ) -> Result<Arc<dyn TraversalModel>, TraversalModelError> {
+        let model = ScheduledLinkTraversalModel {
+            schedules: self.schedules.clone(),
+            time_unit: self.time_unit,
+            utc_offset: self.utc_offset,
+        };
+        Ok(Arc::new(model))
+    }
+}
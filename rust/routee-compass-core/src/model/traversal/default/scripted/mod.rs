@@ -0,0 +1,13 @@
+//! a traversal model whose outputs are arithmetic expressions over declared input
+//! features, evaluated per edge. intended for prototyping models from configuration
+//! without compiling Rust. see [`crate::util::expression::Expression`] for the syntax.
+
+mod scripted_traversal_builder;
+mod scripted_traversal_config;
+mod scripted_traversal_model;
+mod scripted_traversal_service;
+
+pub use scripted_traversal_builder::ScriptedTraversalBuilder;
+pub use scripted_traversal_config::{ScriptedOutputConfig, ScriptedTraversalConfig};
+pub use scripted_traversal_model::ScriptedTraversalModel;
+pub use scripted_traversal_service::ScriptedTraversalService;
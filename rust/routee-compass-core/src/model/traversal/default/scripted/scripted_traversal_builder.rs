@@ -0,0 +1,24 @@
+use super::{ScriptedTraversalConfig, ScriptedTraversalModel, ScriptedTraversalService};
+use crate::model::traversal::{TraversalModelBuilder, TraversalModelError, TraversalModelService};
+use std::sync::Arc;
+
+pub struct ScriptedTraversalBuilder {}
+
+impl TraversalModelBuilder for ScriptedTraversalBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn TraversalModelService>, TraversalModelError> {
+        let config: ScriptedTraversalConfig =
+            serde_json::from_value(parameters.clone()).map_err(|e| {
+                TraversalModelError::BuildError(format!(
+                    "failed to read scripted traversal configuration: {e}"
+                ))
+            })?;
+        let model = ScriptedTraversalModel::new(&config)?;
+        let service = Arc::new(ScriptedTraversalService {
+            model: Arc::new(model),
+        });
+        Ok(service)
+    }
+}
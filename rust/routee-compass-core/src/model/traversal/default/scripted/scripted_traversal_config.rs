@@ -0,0 +1,33 @@
+use crate::model::state::{InputFeature, StateVariableConfig};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ScriptedTraversalConfig {
+    /// state features read by the expressions. each value is provided to the
+    /// expressions in the requested unit, or the default unit when none is given.
+    #[serde(default)]
+    pub inputs: Vec<InputFeature>,
+    /// state features written by this model, evaluated in order
+    pub outputs: Vec<ScriptedOutputConfig>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ScriptedOutputConfig {
+    /// name of the output state feature
+    pub name: String,
+    /// arithmetic expression over input names and previously-listed output names.
+    /// the result is interpreted in the output unit of the feature.
+    pub expression: String,
+    /// state variable configuration for the output feature. accumulators add the
+    /// result to the previous value, otherwise the result replaces it.
+    pub feature: StateVariableConfig,
+}
+
+impl std::fmt::Display for ScriptedTraversalConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let output = serde_json::to_string_pretty(self).unwrap_or_default();
+        write!(f, "{output}")
+    }
+}
@@ -0,0 +1,359 @@
+use super::ScriptedTraversalConfig;
+use crate::{
+    algorithm::search::SearchTree,
+    model::{
+        network::{Edge, Vertex},
+        state::{
+            CustomVariableConfig, InputFeature, StateModel, StateModelError, StateVariable,
+            StateVariableConfig,
+        },
+        traversal::{TraversalModel, TraversalModelError},
+    },
+    util::expression::Expression,
+};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// evaluates each configured output expression once per edge, reading inputs from
+/// the state vector in their requested units. outputs are written in order, so an
+/// expression may reference the outputs listed before it.
+#[derive(Clone, Debug)]
+pub struct ScriptedTraversalModel {
+    inputs: Vec<InputFeature>,
+    outputs: Vec<ScriptedOutput>,
+}
+
+#[derive(Clone, Debug)]
+struct ScriptedOutput {
+    name: String,
+    expression: Expression,
+    feature: StateVariableConfig,
+}
+
+impl ScriptedTraversalModel {
+    /// parses the output expressions and confirms that each one only references
+    /// declared inputs or outputs listed before it.
+    pub fn new(config: &ScriptedTraversalConfig) -> Result<Self, TraversalModelError> {
+        if config.outputs.is_empty() {
+            return Err(TraversalModelError::BuildError(String::from(
+                "scripted traversal model requires at least one output",
+            )));
+        }
+        let mut known: Vec<String> = config.inputs.iter().map(|i| i.name()).collect();
+        let mut outputs = Vec::with_capacity(config.outputs.len());
+        for output in config.outputs.iter() {
+            let expression = Expression::from_str(&output.expression).map_err(|e| {
+                TraversalModelError::BuildError(format!(
+                    "scripted output '{}' has invalid expression: {e}",
+                    output.name
+                ))
+            })?;
+            if let Some(missing) = expression
+                .variables()
+                .into_iter()
+                .find(|v| !known.iter().any(|k| k == v))
+            {
+                return Err(TraversalModelError::BuildError(format!(
+                    "scripted output '{}' references '{missing}', which is neither a declared input nor a previous output",
+                    output.name
+                )));
+            }
+            if let StateVariableConfig::Custom { value, .. } = &output.feature {
+                if !matches!(value, CustomVariableConfig::FloatingPoint { .. }) {
+                    return Err(TraversalModelError::BuildError(format!(
+                        "scripted output '{}' must be a floating point custom variable, found {}",
+                        output.name,
+                        value.name()
+                    )));
+                }
+            }
+            if known.contains(&output.name) {
+                return Err(TraversalModelError::BuildError(format!(
+                    "scripted output '{}' is declared more than once or shadows an input",
+                    output.name
+                )));
+            }
+            known.push(output.name.clone());
+            outputs.push(ScriptedOutput {
+                name: output.name.clone(),
+                expression,
+                feature: output.feature.clone(),
+            });
+        }
+        Ok(Self {
+            inputs: config.inputs.clone(),
+            outputs,
+        })
+    }
+}
+
+impl TraversalModel for ScriptedTraversalModel {
+    fn name(&self) -> String {
+        String::from("Scripted Traversal Model")
+    }
+
+    fn input_features(&self) -> Vec<InputFeature> {
+        self.inputs.clone()
+    }
+
+    fn output_features(&self) -> Vec<(String, StateVariableConfig)> {
+        self.outputs
+            .iter()
+            .map(|o| (o.name.clone(), o.feature.clone()))
+            .collect()
+    }
+
+    fn traverse_edge(
+        &self,
+        _trajectory: (&Vertex, &Edge, &Vertex),
+        state: &mut Vec<StateVariable>,
+        _tree: &SearchTree,
+        state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        let mut values: HashMap<&str, f64> = HashMap::new();
+        for input in self.inputs.iter() {
+            let value = read_input(input, state, state_model)?;
+            values.insert(input_name(input), value);
+        }
+        for output in self.outputs.iter() {
+            let result = output
+                .expression
+                .evaluate(&|name| values.get(name).copied())
+                .map_err(|e| {
+                    TraversalModelError::TraversalModelFailure(format!(
+                        "scripted output '{}' failed: {e}",
+                        output.name
+                    ))
+                })?;
+            let value = if output.feature.is_accumulator() {
+                read_output(&output.name, &output.feature, state, state_model)? + result
+            } else {
+                result
+            };
+            write_output(&output.name, &output.feature, value, state, state_model)?;
+            values.insert(&output.name, result);
+        }
+        Ok(())
+    }
+
+    fn estimate_traversal(
+        &self,
+        _od: (&Vertex, &Vertex),
+        _state: &mut Vec<StateVariable>,
+        _tree: &SearchTree,
+        _state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        Ok(())
+    }
+}
+
+fn input_name(input: &InputFeature) -> &str {
+    match input {
+        InputFeature::Distance { name, .. }
+        | InputFeature::Speed { name, .. }
+        | InputFeature::Time { name, .. }
+        | InputFeature::Energy { name, .. }
+        | InputFeature::Ratio { name, .. }
+        | InputFeature::Temperature { name, .. }
+        | InputFeature::Custom { name, .. } => name,
+    }
+}
+
+/// reads an input feature from the state vector in its requested unit
+fn read_input(
+    input: &InputFeature,
+    state: &[StateVariable],
+    state_model: &StateModel,
+) -> Result<f64, StateModelError> {
+    match input {
+        InputFeature::Distance { name, unit } => {
+            let value = state_model.get_distance(state, name)?;
+            Ok(unit.unwrap_or_default().from_uom(value))
+        }
+        InputFeature::Speed { name, unit } => {
+            let value = state_model.get_speed(state, name)?;
+            Ok(unit.unwrap_or_default().from_uom(value))
+        }
+        InputFeature::Time { name, unit } => {
+            let value = state_model.get_time(state, name)?;
+            Ok(unit.unwrap_or_default().from_uom(value))
+        }
+        InputFeature::Energy { name, unit } => {
+            let value = state_model.get_energy(state, name)?;
+            Ok(unit.unwrap_or_default().from_uom(value))
+        }
+        InputFeature::Ratio { name, unit } => {
+            let value = state_model.get_ratio(state, name)?;
+            Ok(unit.unwrap_or_default().from_uom(value))
+        }
+        InputFeature::Temperature { name, unit } => {
+            let value = state_model.get_temperature(state, name)?;
+            Ok(unit.unwrap_or_default().from_uom(value))
+        }
+        InputFeature::Custom { name, .. } => state_model.get_custom_f64(state, name),
+    }
+}
+
+/// reads an output feature from the state vector in its output unit
+fn read_output(
+    name: &str,
+    feature: &StateVariableConfig,
+    state: &[StateVariable],
+    state_model: &StateModel,
+) -> Result<f64, StateModelError> {
+    match feature {
+        StateVariableConfig::Distance { output_unit, .. } => {
+            let value = state_model.get_distance(state, name)?;
+            Ok(output_unit.unwrap_or_default().from_uom(value))
+        }
+        StateVariableConfig::Time { output_unit, .. } => {
+            let value = state_model.get_time(state, name)?;
+            Ok(output_unit.unwrap_or_default().from_uom(value))
+        }
+        StateVariableConfig::Speed { output_unit, .. } => {
+            let value = state_model.get_speed(state, name)?;
+            Ok(output_unit.unwrap_or_default().from_uom(value))
+        }
+        StateVariableConfig::Energy { output_unit, .. } => {
+            let value = state_model.get_energy(state, name)?;
+            Ok(output_unit.unwrap_or_default().from_uom(value))
+        }
+        StateVariableConfig::Ratio { output_unit, .. } => {
+            let value = state_model.get_ratio(state, name)?;
+            Ok(output_unit.unwrap_or_default().from_uom(value))
+        }
+        StateVariableConfig::Temperature { output_unit, .. } => {
+            let value = state_model.get_temperature(state, name)?;
+            Ok(output_unit.unwrap_or_default().from_uom(value))
+        }
+        StateVariableConfig::Custom { .. } => state_model.get_custom_f64(state, name),
+    }
+}
+
+/// writes a value given in the feature's output unit to the state vector
+fn write_output(
+    name: &str,
+    feature: &StateVariableConfig,
+    value: f64,
+    state: &mut [StateVariable],
+    state_model: &StateModel,
+) -> Result<(), StateModelError> {
+    match feature {
+        StateVariableConfig::Distance { output_unit, .. } => {
+            state_model.set_distance(state, name, &output_unit.unwrap_or_default().to_uom(value))
+        }
+        StateVariableConfig::Time { output_unit, .. } => {
+            state_model.set_time(state, name, &output_unit.unwrap_or_default().to_uom(value))
+        }
+        StateVariableConfig::Speed { output_unit, .. } => {
+            state_model.set_speed(state, name, &output_unit.unwrap_or_default().to_uom(value))
+        }
+        StateVariableConfig::Energy { output_unit, .. } => {
+            state_model.set_energy(state, name, &output_unit.unwrap_or_default().to_uom(value))
+        }
+        StateVariableConfig::Ratio { output_unit, .. } => {
+            state_model.set_ratio(state, name, &output_unit.unwrap_or_default().to_uom(value))
+        }
+        StateVariableConfig::Temperature { output_unit, .. } => {
+            state_model.set_temperature(state, name, &output_unit.unwrap_or_default().to_uom(value))
+        }
+        StateVariableConfig::Custom { .. } => state_model.set_custom_f64(state, name, &value),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::network::{EdgeId, EdgeListId, VertexId};
+    use crate::model::unit::{DistanceUnit, TimeUnit};
+    use crate::testing::mock::traversal_model::TestTraversalModel;
+    use crate::util::geo::InternalCoord;
+    use geo::coord;
+    use serde_json::json;
+    use std::sync::Arc;
+    use uom::si::f64::Length;
+
+    #[test]
+    fn test_scripted_traversal() {
+        let config: ScriptedTraversalConfig = serde_json::from_value(json!({
+            "inputs": [
+                { "type": "distance", "name": "edge_distance", "unit": "miles" },
+                { "type": "time", "name": "edge_time", "unit": "hours" }
+            ],
+            "outputs": [
+                {
+                    "name": "edge_mph",
+                    "expression": "edge_distance / edge_time",
+                    "feature": { "type": "custom", "custom_type": "mph", "value": { "type": "floating_point", "initial": 0.0 }, "accumulator": false }
+                },
+                {
+                    "name": "trip_toll",
+                    "expression": "max(0.5, 0.1 * edge_mph)",
+                    "feature": { "type": "custom", "custom_type": "dollars", "value": { "type": "floating_point", "initial": 0.0 }, "accumulator": true }
+                }
+            ]
+        }))
+        .expect("test invariant failed");
+        let model = ScriptedTraversalModel::new(&config).expect("test invariant failed");
+        let test_model = TestTraversalModel::new(Arc::new(model)).expect("test invariant failed");
+        let state_model = StateModel::empty()
+            .register(test_model.input_features(), test_model.output_features())
+            .expect("test invariant failed");
+        let mut state = state_model.initial_state(None).unwrap();
+        state_model
+            .set_distance(
+                &mut state,
+                "edge_distance",
+                &DistanceUnit::Miles.to_uom(10.0),
+            )
+            .unwrap();
+        state_model
+            .set_time(&mut state, "edge_time", &TimeUnit::Hours.to_uom(0.5))
+            .unwrap();
+
+        let v = Vertex {
+            vertex_id: VertexId(0),
+            coordinate: InternalCoord(coord! {x: -86.67, y: 36.12}),
+        };
+        let e = Edge {
+            edge_list_id: EdgeListId(0),
+            edge_id: EdgeId(0),
+            src_vertex_id: VertexId(0),
+            dst_vertex_id: VertexId(1),
+            distance: Length::new::<uom::si::length::meter>(100.0),
+        };
+        for _ in 0..2 {
+            test_model
+                .traverse_edge(
+                    (&v, &e, &v),
+                    &mut state,
+                    &SearchTree::default(),
+                    &state_model,
+                )
+                .unwrap();
+        }
+        let mph = state_model.get_custom_f64(&state, "edge_mph").unwrap();
+        let toll = state_model.get_custom_f64(&state, "trip_toll").unwrap();
+        assert!((mph - 20.0).abs() < 1e-9, "expected 20 mph, found {mph}");
+        assert!((toll - 4.0).abs() < 1e-9, "expected $4 toll, found {toll}");
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let unknown_input: ScriptedTraversalConfig = serde_json::from_value(json!({
+            "inputs": [{ "type": "distance", "name": "edge_distance", "unit": null }],
+            "outputs": [{
+                "name": "edge_cost",
+                "expression": "edge_distance * toll_rate",
+                "feature": { "type": "custom", "custom_type": "dollars", "value": { "type": "floating_point", "initial": 0.0 }, "accumulator": false }
+            }]
+        }))
+        .expect("test invariant failed");
+        assert!(ScriptedTraversalModel::new(&unknown_input).is_err());
+        let no_outputs = ScriptedTraversalConfig {
+            inputs: vec![],
+            outputs: vec![],
+        };
+        assert!(ScriptedTraversalModel::new(&no_outputs).is_err());
+    }
+}
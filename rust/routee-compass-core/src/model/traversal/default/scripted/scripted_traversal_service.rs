@@ -0,0 +1,17 @@
+use super::ScriptedTraversalModel;
+use crate::model::traversal::{TraversalModel, TraversalModelError, TraversalModelService};
+use std::sync::Arc;
+
+pub struct ScriptedTraversalService {
+    pub model: Arc<ScriptedTraversalModel>,
+}
+
+impl TraversalModelService for ScriptedTraversalService {
+    fn build(
+        &self,
+        _query: &serde_json::Value,
+    ) -> Result<Arc<dyn TraversalModel>, TraversalModelError> {
+        let model: Arc<dyn TraversalModel> = self.model.clone();
+        Ok(model)
+    }
+}
@@ -0,0 +1,149 @@
+use super::{SpeedUpdate, SpeedUpdateProvider};
+use crate::model::{network::EdgeId, traversal::TraversalModelError, unit::SpeedUnit};
+use crate::util::fs::parquet_utils::{field_as_f64, field_as_usize};
+use crate::util::fs::{fs_utils, polled_file::PolledFile};
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    fs::File,
+    path::Path,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+/// a poll-based [`SpeedUpdateProvider`] that reads edge speeds from a file that a
+/// traffic feed replaces periodically. the file is checked for changes at most once
+/// per poll interval, when a search instance is built, and is read again only when its
+/// modification time changes. the modification time is used as the update timestamp.
+///
+/// files ending in `.parquet` are read as Parquet, with an integer `edge_id` column and
+/// a floating point `speed` column. all other files are read as CSV with the same
+/// column names. if a new file cannot be read, the previous update remains in use.
+pub struct FileSpeedUpdateProvider {
+    file: PolledFile<SpeedUpdate>,
+    speed_unit: SpeedUnit,
+}
+
+#[derive(Deserialize)]
+struct SpeedUpdateRow {
+    edge_id: usize,
+    speed: f64,
+}
+
+impl FileSpeedUpdateProvider {
+    pub fn new<P: AsRef<Path>>(
+        path: P,
+        speed_unit: SpeedUnit,
+        poll_interval: Duration,
+    ) -> FileSpeedUpdateProvider {
+        FileSpeedUpdateProvider {
+            file: PolledFile::new(path, poll_interval),
+            speed_unit,
+        }
+    }
+
+    fn read_update(&self, path: &Path, modified: SystemTime) -> Result<SpeedUpdate, String> {
+        let is_parquet = path
+            .extension()
+            .map(|ext| ext.eq_ignore_ascii_case("parquet"))
+            .unwrap_or_default();
+        let rows = if is_parquet {
+            read_parquet_rows(path)
+        } else {
+            read_csv_rows(path)
+        }?;
+        let speeds: HashMap<EdgeId, _> = rows
+            .into_iter()
+            .map(|row| (EdgeId(row.edge_id), self.speed_unit.to_uom(row.speed)))
+            .collect();
+        log::info!(
+            "read {} edge speeds from speed update file {}",
+            speeds.len(),
+            path.to_string_lossy()
+        );
+        Ok(SpeedUpdate::new(speeds, modified))
+    }
+}
+
+fn read_csv_rows(path: &Path) -> Result<Vec<SpeedUpdateRow>, String> {
+    let file = fs_utils::open_reader(path).map_err(|e| e.to_string())?;
+    csv::Reader::from_reader(file)
+        .deserialize::<SpeedUpdateRow>()
+        .map(|row| row.map_err(|e| e.to_string()))
+        .collect()
+}
+
+fn read_parquet_rows(path: &Path) -> Result<Vec<SpeedUpdateRow>, String> {
+    let file = File::open(path).map_err(|e| e.to_string())?;
+    let reader = SerializedFileReader::new(file).map_err(|e| e.to_string())?;
+    let rows = reader.get_row_iter(None).map_err(|e| e.to_string())?;
+    rows.map(|row| {
+        let row = row.map_err(|e| e.to_string())?;
+        let mut edge_id = None;
+        let mut speed = None;
+        for (name, field) in row.get_column_iter() {
+            match name.as_str() {
+                "edge_id" => edge_id = field_as_usize(field),
+                "speed" => speed = field_as_f64(field),
+                _ => {}
+            }
+        }
+        match (edge_id, speed) {
+            (Some(edge_id), Some(speed)) => Ok(SpeedUpdateRow { edge_id, speed }),
+            _ => Err(format!(
+                "row must have integer 'edge_id' and numeric 'speed' columns, found {row}"
+            )),
+        }
+    })
+    .collect()
+}
+
+impl SpeedUpdateProvider for FileSpeedUpdateProvider {
+    fn current(&self) -> Result<Option<Arc<SpeedUpdate>>, TraversalModelError> {
+        let current = self
+            .file
+            .current(|path, modified| self.read_update(path, modified))
+            .map_err(TraversalModelError::TraversalModelFailure)?;
+        Ok(current.map(|(_, update)| update))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Write;
+    use uom::si::velocity::kilometer_per_hour;
+
+    #[test]
+    fn test_csv_update() {
+        let mut file = tempfile::Builder::new()
+            .suffix(".csv")
+            .tempfile()
+            .expect("failed to create temp file");
+        writeln!(file, "edge_id,speed\n0,20.0\n2,45.5").expect("failed to write temp file");
+        let provider =
+            FileSpeedUpdateProvider::new(file.path(), SpeedUnit::KPH, Duration::from_secs(60));
+        let update = provider
+            .current()
+            .expect("failed to read speed update")
+            .expect("expected a speed update");
+        assert_eq!(update.len(), 2);
+        let speed = update
+            .get(&EdgeId(2))
+            .map(|s| s.get::<kilometer_per_hour>());
+        assert_eq!(speed, Some(45.5));
+        assert_eq!(update.get(&EdgeId(1)), None);
+    }
+
+    #[test]
+    fn test_missing_file_has_no_update() {
+        let provider = FileSpeedUpdateProvider::new(
+            "does_not_exist.csv",
+            SpeedUnit::KPH,
+            Duration::from_secs(60),
+        );
+        let update = provider.current().expect("missing file should not fail");
+        assert!(update.is_none());
+    }
+}
@@ -1,11 +1,19 @@
+mod file_speed_update_provider;
 mod speed_configuration;
 mod speed_traversal_builder;
 mod speed_traversal_engine;
 mod speed_traversal_model;
 mod speed_traversal_service;
+mod speed_update;
+mod speed_update_config;
+mod speed_update_provider;
 
+pub use file_speed_update_provider::FileSpeedUpdateProvider;
 pub use speed_configuration::SpeedConfiguration;
 pub use speed_traversal_builder::SpeedTraversalBuilder;
 pub use speed_traversal_engine::SpeedTraversalEngine;
 pub use speed_traversal_model::SpeedTraversalModel;
 pub use speed_traversal_service::SpeedLookupService;
+pub use speed_update::SpeedUpdate;
+pub use speed_update_config::SpeedUpdateConfig;
+pub use speed_update_provider::{PushSpeedUpdateProvider, SpeedUpdateProvider, SpeedUpdateSource};
@@ -1,4 +1,6 @@
+use super::SpeedUpdateConfig;
 use crate::model::unit::SpeedUnit;
+use crate::util::encoded_table::TableEncoding;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -8,4 +10,9 @@ pub struct SpeedConfiguration {
     pub speed_table_input_file: String,
     /// unit the speeds were recorded in
     pub speed_unit: SpeedUnit,
+    /// if provided, current speeds from a periodically updated file override
+    /// the speed table
+    pub speed_updates: Option<SpeedUpdateConfig>,
+    /// how the speed table is stored in memory, by default as 64-bit floats
+    pub table_encoding: Option<TableEncoding>,
 }
@@ -1,11 +1,13 @@
 use super::SpeedConfiguration;
 use super::SpeedLookupService;
 use super::SpeedTraversalEngine;
+use super::{FileSpeedUpdateProvider, SpeedUpdateConfig, SpeedUpdateSource};
 use crate::model::traversal::TraversalModelBuilder;
 use crate::model::traversal::TraversalModelError;
 use crate::model::traversal::TraversalModelService;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 pub struct SpeedTraversalBuilder {}
 
@@ -19,8 +21,43 @@ impl TraversalModelBuilder for SpeedTraversalBuilder {
         })?;
 
         let filename = PathBuf::from(&config.speed_table_input_file);
-        let e = SpeedTraversalEngine::new(&filename, config.speed_unit)?;
-        let service = Arc::new(SpeedLookupService { e: Arc::new(e) });
+        let e = SpeedTraversalEngine::new(
+            &filename,
+            config.speed_unit,
+            config.table_encoding.unwrap_or_default(),
+        )?;
+        let speed_updates = match &config.speed_updates {
+            None => None,
+            Some(update_config) => {
+                let blend = update_config.blend.unwrap_or(1.0);
+                if !(0.0..=1.0).contains(&blend) {
+                    return Err(TraversalModelError::BuildError(format!(
+                        "speed update blend must be in [0, 1], found {blend}"
+                    )));
+                }
+                let poll_interval = Duration::from_secs(
+                    update_config
+                        .poll_interval_seconds
+                        .unwrap_or(SpeedUpdateConfig::DEFAULT_POLL_INTERVAL_SECONDS),
+                );
+                let provider = FileSpeedUpdateProvider::new(
+                    &update_config.speed_update_file,
+                    update_config.speed_unit.unwrap_or(config.speed_unit),
+                    poll_interval,
+                );
+                Some(SpeedUpdateSource {
+                    provider: Arc::new(provider),
+                    blend,
+                    staleness_timeout: update_config
+                        .staleness_timeout_seconds
+                        .map(Duration::from_secs),
+                })
+            }
+        };
+        let service = Arc::new(SpeedLookupService {
+            e: Arc::new(e),
+            speed_updates,
+        });
         Ok(service)
     }
 }
@@ -1,4 +1,6 @@
+use crate::model::network::EdgeId;
 use crate::model::unit::SpeedUnit;
+use crate::util::encoded_table::{EncodedTable, TableEncoding};
 use crate::util::fs::read_decoders;
 use crate::{model::traversal::TraversalModelError, util::fs::read_utils};
 use kdam::Bar;
@@ -7,7 +9,9 @@ use uom::si::f64::Velocity;
 use uom::ConstZero;
 
 pub struct SpeedTraversalEngine {
-    pub speed_table: Box<[Velocity]>,
+    /// speeds by edge id, in the speed unit of the input file
+    pub speed_table: EncodedTable,
+    pub speed_unit: SpeedUnit,
     pub max_speed: Velocity,
 }
 
@@ -15,8 +19,9 @@ impl SpeedTraversalEngine {
     pub fn new<P: AsRef<Path>>(
         speed_table_path: &P,
         speed_unit: SpeedUnit,
+        table_encoding: TableEncoding,
     ) -> Result<SpeedTraversalEngine, TraversalModelError> {
-        let speed_table: Box<[Velocity]> = read_utils::read_raw_file(
+        let speeds: Vec<f64> = read_utils::read_raw_file(
             speed_table_path,
             read_decoders::f64,
             Some(Bar::builder().desc("link speeds")),
@@ -29,17 +34,43 @@ impl SpeedTraversalEngine {
                 e,
             ))
         })?
-        .iter()
-        .map(|&s| speed_unit.to_uom(s))
-        .collect::<Vec<Velocity>>()
-        .into_boxed_slice();
-        let max_speed = get_max_speed(&speed_table)?;
+        .into_vec();
+        // the max speed bounds the heuristic, so it is taken from the exact values
+        let max_speed = get_max_speed(
+            &speeds
+                .iter()
+                .map(|&s| speed_unit.to_uom(s))
+                .collect::<Vec<Velocity>>(),
+        )?;
+        let speed_table = EncodedTable::new(speeds, table_encoding).map_err(|e| {
+            TraversalModelError::BuildError(format!("cannot encode speed table: {e}"))
+        })?;
+        if table_encoding != TableEncoding::F64 {
+            log::info!(
+                "encoded speed table as {:?} using {} bytes with max error {} {}",
+                table_encoding,
+                speed_table.size_in_bytes(),
+                speed_table.max_error(),
+                speed_unit
+            );
+        }
         let model = SpeedTraversalEngine {
             speed_table,
+            speed_unit,
             max_speed,
         };
         Ok(model)
     }
+
+    /// look up a speed from the speed table
+    pub fn get_speed(&self, edge_id: EdgeId) -> Result<Velocity, TraversalModelError> {
+        let speed = self.speed_table.get(edge_id.as_usize()).ok_or_else(|| {
+            TraversalModelError::TraversalModelFailure(format!(
+                "could not find expected index {edge_id} in speed table"
+            ))
+        })?;
+        Ok(self.speed_unit.to_uom(speed))
+    }
 }
 
 pub fn get_max_speed(speed_table: &[Velocity]) -> Result<Velocity, TraversalModelError> {
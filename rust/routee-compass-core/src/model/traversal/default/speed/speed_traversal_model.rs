@@ -2,6 +2,7 @@ use uom::si::f64::Velocity;
 use uom::ConstZero;
 
 use super::speed_traversal_engine::SpeedTraversalEngine;
+use super::SpeedUpdate;
 use crate::algorithm::search::SearchTree;
 use crate::model::network::{Edge, EdgeId, Vertex};
 use crate::model::state::StateModel;
@@ -16,6 +17,8 @@ use std::sync::Arc;
 pub struct SpeedTraversalModel {
     engine: Arc<SpeedTraversalEngine>,
     speed_limit: Option<Velocity>,
+    /// current speeds and the weight given to them when blended with historical speeds
+    speed_update: Option<(Arc<SpeedUpdate>, f64)>,
 }
 
 impl SpeedTraversalModel {
@@ -27,14 +30,35 @@ impl SpeedTraversalModel {
             Ok(SpeedTraversalModel {
                 engine,
                 speed_limit: Some(max_speed),
+                speed_update: None,
             })
         } else {
             Ok(SpeedTraversalModel {
                 engine,
                 speed_limit: None,
+                speed_update: None,
             })
         }
     }
+
+    /// blends current speeds into the historical speeds of this model. for edges in the
+    /// update, the speed is `blend * current + (1 - blend) * historical`.
+    pub fn with_speed_update(mut self, update: Arc<SpeedUpdate>, blend: f64) -> Self {
+        self.speed_update = Some((update, blend));
+        self
+    }
+
+    /// the historical speed for an edge, blended with the current speed if present
+    fn edge_speed(&self, edge_id: EdgeId) -> Result<Velocity, TraversalModelError> {
+        let historical = self.engine.get_speed(edge_id)?;
+        match &self.speed_update {
+            Some((update, blend)) => match update.get(&edge_id) {
+                Some(current) => Ok(current * *blend + historical * (1.0 - *blend)),
+                None => Ok(historical),
+            },
+            None => Ok(historical),
+        }
+    }
 }
 
 impl TraversalModel for SpeedTraversalModel {
@@ -68,7 +92,7 @@ impl TraversalModel for SpeedTraversalModel {
         state_model: &StateModel,
     ) -> Result<(), TraversalModelError> {
         let (_, edge, _) = trajectory;
-        let lookup_speed = get_speed(&self.engine.speed_table, edge.edge_id)?;
+        let lookup_speed = self.edge_speed(edge.edge_id)?;
         let speed = apply_speed_limit(lookup_speed, self.speed_limit);
         state_model.set_speed(state, fieldname::EDGE_SPEED, &speed)?;
         Ok(())
@@ -84,7 +108,13 @@ impl TraversalModel for SpeedTraversalModel {
     ) -> Result<(), TraversalModelError> {
         let speed: Velocity = match self.speed_limit {
             Some(speed_limit) => speed_limit,
-            None => self.engine.max_speed,
+            // a blended speed never exceeds the larger of the historical and current maximums
+            None => match &self.speed_update {
+                Some((update, _)) if update.max_speed() > self.engine.max_speed => {
+                    update.max_speed()
+                }
+                _ => self.engine.max_speed,
+            },
         };
         state_model.set_speed(state, fieldname::EDGE_SPEED, &speed)?;
 
@@ -92,19 +122,6 @@ impl TraversalModel for SpeedTraversalModel {
     }
 }
 
-/// look up a speed from the speed table
-pub fn get_speed(
-    speed_table: &[Velocity],
-    edge_id: EdgeId,
-) -> Result<Velocity, TraversalModelError> {
-    let speed: &Velocity = speed_table.get(edge_id.as_usize()).ok_or_else(|| {
-        TraversalModelError::TraversalModelFailure(format!(
-            "could not find expected index {edge_id} in speed table"
-        ))
-    })?;
-    Ok(*speed)
-}
-
 fn apply_speed_limit(lookup_speed: Velocity, speed_limit: Option<Velocity>) -> Velocity {
     match speed_limit {
         Some(speed_limit) => {
@@ -124,6 +141,7 @@ mod tests {
     use crate::model::network::{Edge, EdgeId, EdgeListId, Vertex, VertexId};
     use crate::model::unit::SpeedUnit;
     use crate::testing::mock::traversal_model::TestTraversalModel;
+    use crate::util::encoded_table::TableEncoding;
     use crate::util::geo::InternalCoord;
     use approx::relative_eq;
     use geo::coord;
@@ -169,8 +187,8 @@ mod tests {
     #[test]
     fn test_speed_traversal() {
         let file: PathBuf = filepath();
-        let engine =
-            SpeedTraversalEngine::new(&file, SpeedUnit::KPH).expect("test invariant failed");
+        let engine = SpeedTraversalEngine::new(&file, SpeedUnit::KPH, TableEncoding::F64)
+            .expect("test invariant failed");
         let speed_model =
             SpeedTraversalModel::new(Arc::new(engine), None).expect("test invariant failed");
         let test_model =
@@ -207,7 +225,8 @@ mod tests {
 
         let file: PathBuf = filepath();
         let engine = Arc::new(
-            SpeedTraversalEngine::new(&file, SpeedUnit::KPH).expect("test invariant failed"),
+            SpeedTraversalEngine::new(&file, SpeedUnit::KPH, TableEncoding::F64)
+                .expect("test invariant failed"),
         );
 
         let regular_model =
@@ -260,4 +279,58 @@ mod tests {
             10.0,
         );
     }
+    #[test]
+    fn test_speed_update_blending() {
+        // edge 0 has a historical speed of 10 kph
+        let file: PathBuf = filepath();
+        let engine = Arc::new(
+            SpeedTraversalEngine::new(&file, SpeedUnit::KPH, TableEncoding::F64)
+                .expect("test invariant failed"),
+        );
+        let current = Velocity::new::<uom::si::velocity::kilometer_per_hour>(30.0);
+        let update = Arc::new(SpeedUpdate::new(
+            std::collections::HashMap::from([(EdgeId(0), current)]),
+            std::time::SystemTime::now(),
+        ));
+        let speed_model = SpeedTraversalModel::new(engine, None)
+            .expect("test invariant failed")
+            .with_speed_update(update, 0.5);
+        let test_model =
+            TestTraversalModel::new(Arc::new(speed_model)).expect("test invariant failed");
+        let state_model = StateModel::empty()
+            .register(test_model.input_features(), test_model.output_features())
+            .expect("test invariant failed");
+        let mut state = state_model.initial_state(None).unwrap();
+        let v = mock_vertex();
+        test_model
+            .traverse_edge(
+                (&v, &mock_edge(0), &v),
+                &mut state,
+                &SearchTree::default(),
+                &state_model,
+            )
+            .unwrap();
+        let speed_kph = state_model
+            .get_speed(&state, "edge_speed")
+            .expect("test invariant failed")
+            .get::<uom::si::velocity::kilometer_per_hour>();
+        assert!(relative_eq!(speed_kph, 20.0));
+    }
+
+    #[test]
+    fn test_u16_speed_table() {
+        let file: PathBuf = filepath();
+        let exact = SpeedTraversalEngine::new(&file, SpeedUnit::KPH, TableEncoding::F64)
+            .expect("test invariant failed");
+        let compact = SpeedTraversalEngine::new(&file, SpeedUnit::KPH, TableEncoding::U16)
+            .expect("test invariant failed");
+        assert_eq!(compact.max_speed, exact.max_speed);
+        let bound = compact.speed_table.max_error() + 1e-9;
+        for edge_id in 0..exact.speed_table.len() {
+            let expected = exact.get_speed(EdgeId(edge_id)).unwrap();
+            let result = compact.get_speed(EdgeId(edge_id)).unwrap();
+            let error = (result - expected).get::<uom::si::velocity::kilometer_per_hour>();
+            assert!(error.abs() <= bound, "edge {edge_id} off by {error} kph");
+        }
+    }
 }
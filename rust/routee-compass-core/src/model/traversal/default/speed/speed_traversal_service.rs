@@ -1,5 +1,6 @@
 use super::{
     speed_traversal_engine::SpeedTraversalEngine, speed_traversal_model::SpeedTraversalModel,
+    SpeedUpdateSource,
 };
 use crate::model::{
     traversal::{
@@ -8,10 +9,12 @@ use crate::model::{
     },
     unit::SpeedUnit,
 };
-use std::{str::FromStr, sync::Arc};
+use std::{str::FromStr, sync::Arc, time::UNIX_EPOCH};
 
 pub struct SpeedLookupService {
     pub e: Arc<SpeedTraversalEngine>,
+    /// if provided, current speeds that override the speed table
+    pub speed_updates: Option<SpeedUpdateSource>,
 }
 
 impl TraversalModelService for SpeedLookupService {
@@ -51,7 +54,31 @@ impl TraversalModelService for SpeedLookupService {
         let speed_limit = speed_limit_tuple
             .map(|(speed_limit, max_speed_unit)| max_speed_unit.to_uom(speed_limit));
 
-        let model = SpeedTraversalModel::new(self.e.clone(), speed_limit)?;
+        let mut model = SpeedTraversalModel::new(self.e.clone(), speed_limit)?;
+        if let Some(source) = &self.speed_updates {
+            if let Some(update) = source.fresh_update()? {
+                model = model.with_speed_update(update, source.blend);
+            }
+        }
         Ok(Arc::new(model))
     }
+
+    /// the observation time of the speed update in use, in nanoseconds since the epoch,
+    /// or `historical` while the speed table is used without an update
+    fn data_version(&self) -> Result<Option<String>, TraversalModelError> {
+        let Some(source) = &self.speed_updates else {
+            return Ok(None);
+        };
+        let version = match source.fresh_update()? {
+            Some(update) => {
+                let observed = update
+                    .timestamp()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default();
+                format!("speed_update:{}", observed.as_nanos())
+            }
+            None => String::from("speed_update:historical"),
+        };
+        Ok(Some(version))
+    }
 }
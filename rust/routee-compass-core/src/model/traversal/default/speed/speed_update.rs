@@ -0,0 +1,49 @@
+use crate::model::network::EdgeId;
+use std::{collections::HashMap, time::SystemTime};
+use uom::si::f64::Velocity;
+use uom::ConstZero;
+
+/// a set of current speeds for some or all edges of a graph, such as from a
+/// real-time traffic feed, along with the time the speeds were observed.
+#[derive(Clone, Debug)]
+pub struct SpeedUpdate {
+    speeds: HashMap<EdgeId, Velocity>,
+    max_speed: Velocity,
+    timestamp: SystemTime,
+}
+
+impl SpeedUpdate {
+    pub fn new(speeds: HashMap<EdgeId, Velocity>, timestamp: SystemTime) -> SpeedUpdate {
+        let max_speed = speeds
+            .values()
+            .fold(Velocity::ZERO, |acc, s| if *s > acc { *s } else { acc });
+        SpeedUpdate {
+            speeds,
+            max_speed,
+            timestamp,
+        }
+    }
+
+    /// the current speed for an edge, if present in this update
+    pub fn get(&self, edge_id: &EdgeId) -> Option<Velocity> {
+        self.speeds.get(edge_id).copied()
+    }
+
+    /// the largest speed in this update
+    pub fn max_speed(&self) -> Velocity {
+        self.max_speed
+    }
+
+    /// the time the speeds in this update were observed
+    pub fn timestamp(&self) -> SystemTime {
+        self.timestamp
+    }
+
+    pub fn len(&self) -> usize {
+        self.speeds.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.speeds.is_empty()
+    }
+}
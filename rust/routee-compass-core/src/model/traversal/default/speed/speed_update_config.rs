@@ -0,0 +1,27 @@
+use crate::model::unit::SpeedUnit;
+use serde::{Deserialize, Serialize};
+
+/// configures current speeds read from a file that is periodically replaced by a
+/// traffic feed, such as a CSV or Parquet drop of edge speeds.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SpeedUpdateConfig {
+    /// CSV or Parquet file with columns `edge_id` and `speed`. edges missing from the
+    /// file use the historical speed table. unlike input files, this file need not exist
+    /// when the app is loaded, and a relative path is resolved from the working directory.
+    pub speed_update_file: String,
+    /// unit of the speeds in the update file. defaults to the unit of the speed table.
+    pub speed_unit: Option<SpeedUnit>,
+    /// minimum time between checks of the file for a new update. defaults to 60 seconds.
+    pub poll_interval_seconds: Option<u64>,
+    /// weight of the current speed when blended with the historical speed, in [0, 1].
+    /// defaults to 1.0, which replaces the historical speed.
+    pub blend: Option<f64>,
+    /// if provided, an update older than this (by file modification time) is ignored
+    /// in favor of historical speeds.
+    pub staleness_timeout_seconds: Option<u64>,
+}
+
+impl SpeedUpdateConfig {
+    pub const DEFAULT_POLL_INTERVAL_SECONDS: u64 = 60;
+}
@@ -0,0 +1,117 @@
+use super::SpeedUpdate;
+use crate::model::traversal::TraversalModelError;
+use std::{
+    sync::{Arc, RwLock},
+    time::{Duration, SystemTime},
+};
+
+/// a source of current edge speeds that override the historical speed table.
+///
+/// providers may be poll-based, where [`SpeedUpdateProvider::current`] checks the
+/// source for new speeds at some interval (see [`super::FileSpeedUpdateProvider`]),
+/// or push-based, where a feed client delivers speeds as they arrive (see
+/// [`PushSpeedUpdateProvider`]). the current update is read once when each search
+/// instance is built, so a query sees a consistent set of speeds.
+pub trait SpeedUpdateProvider: Send + Sync {
+    /// the most recent speed update, or None if no update has been received
+    fn current(&self) -> Result<Option<Arc<SpeedUpdate>>, TraversalModelError>;
+}
+
+/// a provider that holds the most recent update pushed by a feed client.
+#[derive(Default)]
+pub struct PushSpeedUpdateProvider {
+    latest: RwLock<Option<Arc<SpeedUpdate>>>,
+}
+
+impl PushSpeedUpdateProvider {
+    /// replaces the current update. searches that have already started are not affected.
+    pub fn push(&self, update: SpeedUpdate) -> Result<(), TraversalModelError> {
+        let mut latest = self.latest.write().map_err(|e| {
+            TraversalModelError::TraversalModelFailure(format!(
+                "speed update lock is poisoned: {e}"
+            ))
+        })?;
+        *latest = Some(Arc::new(update));
+        Ok(())
+    }
+}
+
+impl SpeedUpdateProvider for PushSpeedUpdateProvider {
+    fn current(&self) -> Result<Option<Arc<SpeedUpdate>>, TraversalModelError> {
+        let latest = self.latest.read().map_err(|e| {
+            TraversalModelError::TraversalModelFailure(format!(
+                "speed update lock is poisoned: {e}"
+            ))
+        })?;
+        Ok(latest.clone())
+    }
+}
+
+/// a [`SpeedUpdateProvider`] along with how its speeds are combined with the
+/// historical speed table.
+#[derive(Clone)]
+pub struct SpeedUpdateSource {
+    pub provider: Arc<dyn SpeedUpdateProvider>,
+    /// weight of the current speed when blended with the historical speed, in [0, 1].
+    /// a weight of 1 replaces the historical speed.
+    pub blend: f64,
+    /// if provided, updates older than this are ignored in favor of historical speeds
+    pub staleness_timeout: Option<Duration>,
+}
+
+impl SpeedUpdateSource {
+    /// the current update from the provider, if one exists and is not stale
+    pub fn fresh_update(&self) -> Result<Option<Arc<SpeedUpdate>>, TraversalModelError> {
+        let Some(update) = self.provider.current()? else {
+            return Ok(None);
+        };
+        if let Some(timeout) = self.staleness_timeout {
+            // updates with a timestamp in the future are treated as fresh
+            let age = SystemTime::now()
+                .duration_since(update.timestamp())
+                .unwrap_or(Duration::ZERO);
+            if age > timeout {
+                log::debug!(
+                    "ignoring speed update that is {}s old, exceeding the staleness timeout of {}s",
+                    age.as_secs(),
+                    timeout.as_secs()
+                );
+                return Ok(None);
+            }
+        }
+        Ok(Some(update))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::network::EdgeId;
+    use std::collections::HashMap;
+    use uom::si::f64::Velocity;
+
+    #[test]
+    fn test_stale_update_is_ignored() {
+        let provider = Arc::new(PushSpeedUpdateProvider::default());
+        let source = SpeedUpdateSource {
+            provider: provider.clone(),
+            blend: 1.0,
+            staleness_timeout: Some(Duration::from_secs(300)),
+        };
+        assert!(source.fresh_update().unwrap().is_none());
+
+        let speeds = HashMap::from([(
+            EdgeId(0),
+            Velocity::new::<uom::si::velocity::meter_per_second>(10.0),
+        )]);
+        let observed = SystemTime::now() - Duration::from_secs(60);
+        provider
+            .push(SpeedUpdate::new(speeds.clone(), observed))
+            .unwrap();
+        assert!(source.fresh_update().unwrap().is_some());
+
+        let observed = SystemTime::now() - Duration::from_secs(600);
+        provider.push(SpeedUpdate::new(speeds, observed)).unwrap();
+        assert!(source.fresh_update().unwrap().is_none());
+    }
+}
@@ -0,0 +1,49 @@
+use crate::model::unit::TimeUnit;
+use serde::{Deserialize, Serialize};
+use uom::si::f64::Time;
+
+/// a range of trip times within which a route should arrive at its destination, read
+/// from the `arrival_window` key of a query. times are measured from the start of the
+/// trip, in the same terms as the trip time state variable.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ArrivalWindow {
+    /// earliest arrival. earlier arrivals wait at the destination until this time
+    pub start: Option<f64>,
+    /// latest arrival. later arrivals are infeasible
+    pub end: Option<f64>,
+    /// unit of the start and end values, minutes by default
+    #[serde(default)]
+    pub time_unit: TimeUnit,
+}
+
+impl ArrivalWindow {
+    pub const QUERY_KEY: &'static str = "arrival_window";
+
+    /// reads the arrival window of a query, if present.
+    pub fn from_query(query: &serde_json::Value) -> Result<Option<ArrivalWindow>, String> {
+        let value = match query.get(Self::QUERY_KEY) {
+            None => return Ok(None),
+            Some(value) => value,
+        };
+        let window: ArrivalWindow = serde_json::from_value(value.clone())
+            .map_err(|e| format!("invalid query '{}' value: {e}", Self::QUERY_KEY))?;
+        if let (Some(start), Some(end)) = (window.start, window.end) {
+            if start > end {
+                return Err(format!(
+                    "query '{}' start {start} is after end {end}",
+                    Self::QUERY_KEY
+                ));
+            }
+        }
+        Ok(Some(window))
+    }
+
+    pub fn start(&self) -> Option<Time> {
+        self.start.map(|start| self.time_unit.to_uom(start))
+    }
+
+    pub fn end(&self) -> Option<Time> {
+        self.end.map(|end| self.time_unit.to_uom(end))
+    }
+}
@@ -0,0 +1,17 @@
+//! waits at the destination of a query when the route arrives before the start of the
+//! query's arrival window. the wait is added to the trip time and recorded as the
+//! trip wait time. waiting applies to queries with a destination vertex, which edge-
+//! oriented queries do not have. arrivals after the end of the window are rejected by the
+//! time window constraint model.
+
+mod arrival_window;
+mod time_window_traversal_builder;
+mod time_window_traversal_config;
+mod time_window_traversal_model;
+mod time_window_traversal_service;
+
+pub use arrival_window::ArrivalWindow;
+pub use time_window_traversal_builder::TimeWindowTraversalBuilder;
+pub use time_window_traversal_config::TimeWindowTraversalConfig;
+pub use time_window_traversal_model::TimeWindowTraversalModel;
+pub use time_window_traversal_service::TimeWindowTraversalService;
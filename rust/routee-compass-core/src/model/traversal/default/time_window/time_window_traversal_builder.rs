@@ -0,0 +1,21 @@
+use super::{TimeWindowTraversalConfig, TimeWindowTraversalService};
+use crate::model::traversal::{TraversalModelBuilder, TraversalModelError, TraversalModelService};
+use std::sync::Arc;
+
+pub struct TimeWindowTraversalBuilder {}
+
+impl TraversalModelBuilder for TimeWindowTraversalBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn TraversalModelService>, TraversalModelError> {
+        let config: TimeWindowTraversalConfig = serde_json::from_value(parameters.clone())
+            .map_err(|e| {
+                TraversalModelError::BuildError(format!(
+                    "failed to read time window traversal model configuration: {e}"
+                ))
+            })?;
+        let service = Arc::new(TimeWindowTraversalService { config });
+        Ok(service)
+    }
+}
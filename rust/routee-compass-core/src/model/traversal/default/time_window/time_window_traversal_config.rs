@@ -0,0 +1,9 @@
+use crate::model::unit::TimeUnit;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TimeWindowTraversalConfig {
+    /// time unit for state modeling of the trip wait time
+    pub time_unit: TimeUnit,
+}
@@ -0,0 +1,177 @@
+use super::{ArrivalWindow, TimeWindowTraversalConfig};
+use crate::{
+    algorithm::search::SearchTree,
+    model::{
+        network::{Edge, Vertex, VertexId},
+        state::{InputFeature, StateModel, StateVariable, StateVariableConfig},
+        traversal::{default::fieldname, TraversalModel, TraversalModelError},
+    },
+};
+use uom::{si::f64::Time, ConstZero};
+
+pub struct TimeWindowTraversalModel {
+    pub config: TimeWindowTraversalConfig,
+    pub arrival_window: Option<ArrivalWindow>,
+    pub destination: Option<VertexId>,
+}
+
+impl TraversalModel for TimeWindowTraversalModel {
+    fn name(&self) -> String {
+        String::from("Time Window Traversal Model")
+    }
+
+    fn input_features(&self) -> Vec<InputFeature> {
+        vec![InputFeature::Time {
+            name: String::from(fieldname::TRIP_TIME),
+            unit: None,
+        }]
+    }
+
+    fn output_features(&self) -> Vec<(String, StateVariableConfig)> {
+        vec![(
+            String::from(fieldname::TRIP_WAIT_TIME),
+            StateVariableConfig::Time {
+                initial: Time::ZERO,
+                accumulator: true,
+                output_unit: Some(self.config.time_unit),
+            },
+        )]
+    }
+
+    fn traverse_edge(
+        &self,
+        trajectory: (&Vertex, &Edge, &Vertex),
+        state: &mut Vec<StateVariable>,
+        _tree: &SearchTree,
+        state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        let (_, edge, _) = trajectory;
+        let start = match (&self.arrival_window, &self.destination) {
+            (Some(window), Some(destination)) if edge.dst_vertex_id == *destination => {
+                window.start()
+            }
+            _ => None,
+        };
+        if let Some(start) = start {
+            let trip_time = state_model.get_time(state, fieldname::TRIP_TIME)?;
+            if trip_time < start {
+                let wait_time = start - trip_time;
+                state_model.add_time(state, fieldname::TRIP_TIME, &wait_time)?;
+                state_model.add_time(state, fieldname::TRIP_WAIT_TIME, &wait_time)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// waiting only adds to the trip time, so estimates exclude it.
+    fn estimate_traversal(
+        &self,
+        _od: (&Vertex, &Vertex),
+        _state: &mut Vec<StateVariable>,
+        _tree: &SearchTree,
+        _state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::{
+        network::{EdgeId, EdgeListId, VertexId},
+        traversal::{default::time_window::TimeWindowTraversalService, TraversalModelService},
+        unit::TimeUnit,
+    };
+    use crate::testing::mock::traversal_model::TestTraversalModel;
+    use crate::util::geo::InternalCoord;
+    use geo::coord;
+    use uom::si::{f64::Length, time::minute};
+
+    fn mock_vertex(vertex_id: usize) -> Vertex {
+        Vertex {
+            vertex_id: VertexId(vertex_id),
+            coordinate: InternalCoord(coord! {x: -86.67, y: 36.12}),
+        }
+    }
+
+    fn mock_edge(edge_id: usize, dst_vertex_id: usize) -> Edge {
+        Edge {
+            edge_list_id: EdgeListId(0),
+            edge_id: EdgeId(edge_id),
+            src_vertex_id: VertexId(0),
+            dst_vertex_id: VertexId(dst_vertex_id),
+            distance: Length::new::<uom::si::length::meter>(100.0),
+        }
+    }
+
+    /// traverses an edge starting at the given trip time, returning the resulting trip
+    /// time and trip wait time in minutes
+    fn traverse(query: serde_json::Value, edge: &Edge, trip_time_minutes: f64) -> (f64, f64) {
+        let service = TimeWindowTraversalService {
+            config: TimeWindowTraversalConfig {
+                time_unit: TimeUnit::Minutes,
+            },
+        };
+        let model = service
+            .build(&query)
+            .expect("failed to build time window model");
+        let test_model = TestTraversalModel::new(model).expect("test invariant failed");
+        let state_model = StateModel::empty()
+            .register(test_model.input_features(), test_model.output_features())
+            .expect("failed to register state features");
+        let mut state = state_model.initial_state(None).unwrap();
+        state_model
+            .set_time(
+                &mut state,
+                fieldname::TRIP_TIME,
+                &Time::new::<minute>(trip_time_minutes),
+            )
+            .unwrap();
+        let (src, dst) = (mock_vertex(0), mock_vertex(edge.dst_vertex_id.0));
+        test_model
+            .traverse_edge(
+                (&src, edge, &dst),
+                &mut state,
+                &SearchTree::default(),
+                &state_model,
+            )
+            .unwrap();
+        let trip_time = state_model.get_time(&state, fieldname::TRIP_TIME).unwrap();
+        let wait_time = state_model
+            .get_time(&state, fieldname::TRIP_WAIT_TIME)
+            .unwrap();
+        (trip_time.get::<minute>(), wait_time.get::<minute>())
+    }
+
+    #[test]
+    fn test_early_arrival_waits() {
+        let query = serde_json::json!({
+            "destination_vertex": 1,
+            "arrival_window": {"start": 30.0, "end": 45.0}
+        });
+        let (trip_time, wait_time) = traverse(query, &mock_edge(0, 1), 20.0);
+        assert!((trip_time - 30.0).abs() < 1e-9);
+        assert!((wait_time - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_no_wait_away_from_destination_or_within_window() {
+        let query = serde_json::json!({
+            "destination_vertex": 1,
+            "arrival_window": {"start": 0.5, "time_unit": "hours"}
+        });
+        let (trip_time, wait_time) = traverse(query.clone(), &mock_edge(0, 2), 20.0);
+        assert!((trip_time - 20.0).abs() < 1e-9);
+        assert_eq!(wait_time, 0.0);
+        let (trip_time, wait_time) = traverse(query, &mock_edge(0, 1), 40.0);
+        assert!((trip_time - 40.0).abs() < 1e-9);
+        assert_eq!(wait_time, 0.0);
+    }
+
+    #[test]
+    fn test_invalid_arrival_window() {
+        let query = serde_json::json!({"arrival_window": {"start": 30.0, "end": 20.0}});
+        assert!(ArrivalWindow::from_query(&query).is_err());
+    }
+}
@@ -0,0 +1,27 @@
+use super::{ArrivalWindow, TimeWindowTraversalConfig, TimeWindowTraversalModel};
+use crate::model::map::MapJsonExtensions;
+use crate::model::traversal::{TraversalModel, TraversalModelError, TraversalModelService};
+use std::sync::Arc;
+
+pub struct TimeWindowTraversalService {
+    pub config: TimeWindowTraversalConfig,
+}
+
+impl TraversalModelService for TimeWindowTraversalService {
+    fn build(
+        &self,
+        query: &serde_json::Value,
+    ) -> Result<Arc<dyn TraversalModel>, TraversalModelError> {
+        let arrival_window =
+            ArrivalWindow::from_query(query).map_err(TraversalModelError::BuildError)?;
+        let destination = query
+            .get_destination_vertex()
+            .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+        let model = TimeWindowTraversalModel {
+            config: self.config.clone(),
+            arrival_window,
+            destination,
+        };
+        Ok(Arc::new(model))
+    }
+}
@@ -1,3 +1,4 @@
+use crate::model::network::{EdgeId, EdgeListId};
 use serde::Deserialize;
 
 /// simplifies the representation of directionality for a linestring
@@ -9,6 +10,27 @@ pub struct EdgeHeading {
     departure_heading: Option<i16>,
 }
 
+/// a row of an edge heading file. rows without an `edge_list_id` are the headings of
+/// the edges of the turn delay model's own edge list, in edge id order. rows with an
+/// `edge_list_id` are the heading of the `edge_id` of that edge list, which is used for
+/// turns from the edges of other edge lists.
+#[derive(Copy, Clone, Deserialize)]
+pub struct EdgeHeadingRow {
+    pub edge_list_id: Option<EdgeListId>,
+    pub edge_id: Option<EdgeId>,
+    pub arrival_heading: i16,
+    pub departure_heading: Option<i16>,
+}
+
+impl EdgeHeadingRow {
+    pub fn heading(&self) -> EdgeHeading {
+        EdgeHeading {
+            arrival_heading: self.arrival_heading,
+            departure_heading: self.departure_heading,
+        }
+    }
+}
+
 impl EdgeHeading {
     /// creates an EdgeHeading from a start and end heading
     pub fn new(arrival_heading: i16, departure_heading: i16) -> Self {
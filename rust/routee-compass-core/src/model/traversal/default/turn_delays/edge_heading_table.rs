@@ -0,0 +1,98 @@
+use super::{EdgeHeading, EdgeHeadingRow};
+use crate::model::{
+    network::{EdgeId, EdgeListId},
+    traversal::TraversalModelError,
+};
+use std::collections::HashMap;
+
+/// the edge headings of a turn delay model. headings are looked up by edge list and edge
+/// id, as edge ids repeat across edge lists.
+#[derive(Default)]
+pub struct EdgeHeadingTable {
+    /// headings of the edges of the model's own edge list, by edge id
+    local: Vec<EdgeHeading>,
+    /// headings of edges of any edge list
+    keyed: HashMap<(EdgeListId, EdgeId), EdgeHeading>,
+}
+
+impl EdgeHeadingTable {
+    /// builds the table from the rows of an edge heading file
+    pub fn from_rows(rows: &[EdgeHeadingRow]) -> Result<EdgeHeadingTable, TraversalModelError> {
+        let mut table = EdgeHeadingTable::default();
+        for (idx, row) in rows.iter().enumerate() {
+            match (row.edge_list_id, row.edge_id) {
+                (None, _) => table.local.push(row.heading()),
+                (Some(edge_list_id), Some(edge_id)) => {
+                    let _ = table.keyed.insert((edge_list_id, edge_id), row.heading());
+                }
+                (Some(edge_list_id), None) => {
+                    return Err(TraversalModelError::BuildError(format!(
+                        "edge heading row {idx} has edge_list_id {edge_list_id} but no edge_id"
+                    )))
+                }
+            }
+        }
+        Ok(table)
+    }
+
+    /// looks up the heading of an edge.
+    ///
+    /// # Arguments
+    ///
+    /// * `edge` - edge list and edge id of the edge
+    /// * `local_edge_list_id` - the edge list of the turn delay model, whose edges may
+    ///   be listed in edge id order
+    pub fn get(
+        &self,
+        edge: (EdgeListId, EdgeId),
+        local_edge_list_id: EdgeListId,
+    ) -> Option<EdgeHeading> {
+        let (edge_list_id, edge_id) = edge;
+        match self.keyed.get(&edge) {
+            Some(heading) => Some(*heading),
+            None if edge_list_id == local_edge_list_id => {
+                self.local.get(edge_id.as_usize()).copied()
+            }
+            None => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_multi_edge_list_headings() {
+        let row = |edge_list_id: Option<usize>, edge_id: Option<usize>, heading| EdgeHeadingRow {
+            edge_list_id: edge_list_id.map(EdgeListId),
+            edge_id: edge_id.map(EdgeId),
+            arrival_heading: heading,
+            departure_heading: None,
+        };
+        let rows = vec![
+            row(None, None, 0),
+            row(None, None, 90),
+            row(Some(1), Some(0), 180),
+        ];
+        let table = EdgeHeadingTable::from_rows(&rows).unwrap();
+        let heading = |edge_list_id, edge_id, local| {
+            table
+                .get(
+                    (EdgeListId(edge_list_id), EdgeId(edge_id)),
+                    EdgeListId(local),
+                )
+                .map(|h| h.start_heading())
+        };
+
+        // edge 0 of the model's own edge list, and edge 0 of edge list 1
+        assert_eq!(heading(0, 0, 0), Some(0));
+        assert_eq!(heading(1, 0, 0), Some(180));
+        assert_eq!(heading(0, 1, 0), Some(90));
+        assert_eq!(heading(2, 1, 0), None);
+        // a model of edge list 2 lists its own edges in edge id order
+        assert_eq!(heading(2, 1, 2), Some(90));
+
+        assert!(EdgeHeadingTable::from_rows(&[row(Some(1), None, 0)]).is_err());
+    }
+}
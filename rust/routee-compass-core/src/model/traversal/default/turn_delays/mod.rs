@@ -1,4 +1,5 @@
 mod edge_heading;
+mod edge_heading_table;
 mod turn;
 mod turn_delay_model;
 mod turn_delay_model_config;
@@ -8,7 +9,8 @@ mod turn_delay_traversal_model_builder;
 mod turn_delay_traversal_model_engine;
 mod turn_delay_traversal_model_service;
 
-pub use edge_heading::EdgeHeading;
+pub use edge_heading::{EdgeHeading, EdgeHeadingRow};
+pub use edge_heading_table::EdgeHeadingTable;
 pub use turn::Turn;
 pub use turn_delay_model::TurnDelayModel;
 pub use turn_delay_model_config::TurnDelayModelConfig;
@@ -79,11 +79,14 @@ impl TraversalModel for TurnDelayTraversalModel {
             return Ok(());
         }
         let (src, edge, _) = traversal;
-        let prev_edge_id = match tree.get_incoming_edge(src.vertex_id) {
-            Some(prev_traversal) => prev_traversal.edge_id,
+        let prev_edge = match tree.get_incoming_edge(src.vertex_id) {
+            Some(prev_traversal) => (prev_traversal.edge_list_id, prev_traversal.edge_id),
             None => return Ok(()), // no previous edge, no turn delay to apply
         };
-        let delay = self.engine.get_delay(prev_edge_id, edge.edge_id)?;
+        let next_edge = (edge.edge_list_id, edge.edge_id);
+        let Some(delay) = self.engine.get_delay(prev_edge, next_edge)? else {
+            return Ok(()); // no heading for a previous edge on another edge list
+        };
         state_model.set_time(state, fieldname::EDGE_TURN_DELAY, &delay)?;
         state_model.add_time(state, fieldname::EDGE_TIME, &delay)?;
         if self.include_trip_time {
@@ -1,5 +1,5 @@
 use super::{
-    EdgeHeading, TurnDelayTraversalConfig, TurnDelayTraversalModelEngine,
+    EdgeHeadingRow, EdgeHeadingTable, TurnDelayTraversalConfig, TurnDelayTraversalModelEngine,
     TurnDelayTraversalModelService,
 };
 use crate::{
@@ -25,7 +25,7 @@ impl TraversalModelBuilder for TurnDelayTraversalModelBuilder {
             })?;
 
         let file_path = PathBuf::from(&config.edge_heading_input_file);
-        let edge_headings = read_utils::from_csv::<EdgeHeading>(
+        let rows = read_utils::from_csv::<EdgeHeadingRow>(
             &file_path.as_path(),
             true,
             Some(Bar::builder().desc("edge headings")),
@@ -36,6 +36,7 @@ impl TraversalModelBuilder for TurnDelayTraversalModelBuilder {
                 "error reading headings from file {file_path:?}: {e}"
             ))
         })?;
+        let edge_headings = EdgeHeadingTable::from_rows(&rows)?;
 
         let engine = TurnDelayTraversalModelEngine {
             edge_headings,
@@ -1,20 +1,35 @@
 use uom::si::f64::Time;
 
-use super::edge_heading::EdgeHeading;
+use super::edge_heading_table::EdgeHeadingTable;
 use super::turn::Turn;
 use super::turn_delay_model::TurnDelayModel;
-use crate::model::network::EdgeId;
+use crate::model::network::{EdgeId, EdgeListId};
 use crate::model::traversal::TraversalModelError;
 
 pub struct TurnDelayTraversalModelEngine {
-    pub edge_headings: Box<[EdgeHeading]>,
+    pub edge_headings: EdgeHeadingTable,
     pub turn_delay_model: TurnDelayModel,
 }
 
 impl TurnDelayTraversalModelEngine {
-    pub fn get_delay(&self, prev: EdgeId, next: EdgeId) -> Result<Time, TraversalModelError> {
-        let src_heading = get_headings(&self.edge_headings, prev)?;
-        let dst_heading = get_headings(&self.edge_headings, next)?;
+    /// finds the delay of turning from the previous edge onto the next edge, which is on
+    /// the edge list of this model. a previous edge on another edge list without a
+    /// heading has no turn delay.
+    pub fn get_delay(
+        &self,
+        prev: (EdgeListId, EdgeId),
+        next: (EdgeListId, EdgeId),
+    ) -> Result<Option<Time>, TraversalModelError> {
+        let (local_edge_list_id, _) = next;
+        let src_heading = match self.edge_headings.get(prev, local_edge_list_id) {
+            Some(heading) => heading,
+            None if prev.0 != local_edge_list_id => return Ok(None),
+            None => return Err(missing_heading(prev)),
+        };
+        let dst_heading = self
+            .edge_headings
+            .get(next, local_edge_list_id)
+            .ok_or_else(|| missing_heading(next))?;
         let angle = src_heading.bearing_to_destination(&dst_heading);
         match &self.turn_delay_model {
             TurnDelayModel::TabularDiscrete { table } => {
@@ -24,19 +39,15 @@ impl TurnDelayTraversalModelEngine {
                         "table missing entry for turn {turn}"
                     ))
                 })?;
-                Ok(*delay)
+                Ok(Some(*delay))
             }
         }
     }
 }
 
-/// lookup up the edge heading from the headings table
-pub fn get_headings(
-    headings_table: &[EdgeHeading],
-    edge_id: EdgeId,
-) -> Result<EdgeHeading, TraversalModelError> {
-    let heading: &EdgeHeading = headings_table.get(edge_id.as_usize()).ok_or_else(|| {
-        TraversalModelError::TraversalModelFailure(format!("missing edge id {edge_id} "))
-    })?;
-    Ok(*heading)
+fn missing_heading(edge: (EdgeListId, EdgeId)) -> TraversalModelError {
+    let (edge_list_id, edge_id) = edge;
+    TraversalModelError::TraversalModelFailure(format!(
+        "missing heading for edge id {edge_id} of edge list {edge_list_id}"
+    ))
 }
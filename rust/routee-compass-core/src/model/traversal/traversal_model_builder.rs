@@ -7,8 +7,9 @@ use std::sync::Arc;
 /// traversal model and builds a [`TraversalModelService`].
 ///
 /// A [`TraversalModelBuilder`] instance should be an empty struct that implements
-/// this trait.
-pub trait TraversalModelBuilder {
+/// this trait. Models are built concurrently when the app loads, so builders must be
+/// thread safe.
+pub trait TraversalModelBuilder: Send + Sync {
     /// Builds a [`TraversalModelService`] from configuration.
     ///
     /// # Arguments
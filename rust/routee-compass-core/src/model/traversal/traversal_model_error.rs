@@ -6,6 +6,8 @@ use crate::model::unit::UnitError;
 pub enum TraversalModelError {
     #[error("failure building traversal model: {0}")]
     BuildError(String),
+    #[error("failure building traversal model: {0}")]
+    CyclicalDependency(String),
     #[error("{0}")]
     TraversalModelFailure(String),
     #[error("internal error: {0}")]
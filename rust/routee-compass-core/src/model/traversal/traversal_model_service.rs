@@ -29,4 +29,19 @@ pub trait TraversalModelService: Send + Sync {
         &self,
         query: &serde_json::Value,
     ) -> Result<Arc<dyn TraversalModel>, TraversalModelError>;
+
+    /// Describes the models this service can build and the query parameters they accept,
+    /// such as the vehicle models of an energy model, so that users can discover valid
+    /// query values. Services without query-time choices return None.
+    fn describe(&self) -> Option<serde_json::Value> {
+        None
+    }
+
+    /// Identifies the live data read by the models this service builds, such as the
+    /// current update of a speed feed, so that responses computed from earlier data are
+    /// not reused. Services whose models only read data loaded with the service return
+    /// None.
+    fn data_version(&self) -> Result<Option<String>, TraversalModelError> {
+        Ok(None)
+    }
 }
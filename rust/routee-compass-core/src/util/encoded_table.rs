@@ -0,0 +1,149 @@
+//! tables of per-edge values, such as speeds and grades, which can be stored in a
+//! compact encoding to reduce the memory use of large networks.
+
+use serde::{Deserialize, Serialize};
+
+/// how a table of values is stored in memory
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TableEncoding {
+    /// 64-bit floating point values, exactly as read
+    #[default]
+    F64,
+    /// 16-bit unsigned integers evenly spaced over the range of the table, using a
+    /// quarter of the memory. each value is within (max - min) / 131070 of the value
+    /// read, half of the distance between two steps.
+    U16,
+}
+
+/// a table of values indexed by edge id, stored in a [`TableEncoding`]
+#[derive(Clone, Debug)]
+pub enum EncodedTable {
+    F64(Box<[f64]>),
+    U16 {
+        values: Box<[u16]>,
+        min: f64,
+        step: f64,
+    },
+}
+
+impl EncodedTable {
+    /// encodes a table of values
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - the values by index
+    /// * `encoding` - the encoding to store them in
+    ///
+    /// # Returns
+    ///
+    /// the table, or an error if a value cannot be quantized as it is not finite
+    pub fn new(values: Vec<f64>, encoding: TableEncoding) -> Result<EncodedTable, String> {
+        match encoding {
+            TableEncoding::F64 => Ok(EncodedTable::F64(values.into_boxed_slice())),
+            TableEncoding::U16 => {
+                if let Some((idx, v)) = values.iter().enumerate().find(|(_, v)| !v.is_finite()) {
+                    return Err(format!(
+                        "cannot quantize value {v} at index {idx}, values must be finite"
+                    ));
+                }
+                let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+                let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+                let step = if values.is_empty() || max == min {
+                    0.0
+                } else {
+                    (max - min) / f64::from(u16::MAX)
+                };
+                let quantized = values
+                    .iter()
+                    .map(|v| {
+                        if step == 0.0 {
+                            0
+                        } else {
+                            ((v - min) / step).round().clamp(0.0, f64::from(u16::MAX)) as u16
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                Ok(EncodedTable::U16 {
+                    values: quantized.into_boxed_slice(),
+                    min: if values.is_empty() { 0.0 } else { min },
+                    step,
+                })
+            }
+        }
+    }
+
+    /// the value at an index, or None if the index is out of range
+    pub fn get(&self, idx: usize) -> Option<f64> {
+        match self {
+            EncodedTable::F64(values) => values.get(idx).copied(),
+            EncodedTable::U16 { values, min, step } => {
+                values.get(idx).map(|q| min + f64::from(*q) * step)
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            EncodedTable::F64(values) => values.len(),
+            EncodedTable::U16 { values, .. } => values.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// the largest difference between a value read and the value stored
+    pub fn max_error(&self) -> f64 {
+        match self {
+            EncodedTable::F64(_) => 0.0,
+            EncodedTable::U16 { step, .. } => step / 2.0,
+        }
+    }
+
+    /// the memory used by the values of the table, in bytes
+    pub fn size_in_bytes(&self) -> usize {
+        match self {
+            EncodedTable::F64(values) => std::mem::size_of_val(values.as_ref()),
+            EncodedTable::U16 { values, .. } => std::mem::size_of_val(values.as_ref()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_u16_accuracy() {
+        // speeds in kph and grades in decimal, with an irregular spread of values
+        for (lo, hi) in [(0.0, 130.0), (-0.3, 0.3)] {
+            let values = (0..10_000)
+                .map(|i| lo + (hi - lo) * ((i as f64 * 0.618_033_988_75) % 1.0))
+                .collect::<Vec<_>>();
+            let exact = EncodedTable::new(values.clone(), TableEncoding::F64).unwrap();
+            let compact = EncodedTable::new(values.clone(), TableEncoding::U16).unwrap();
+            let bound = (hi - lo) / 131070.0;
+            assert!(compact.max_error() <= bound * (1.0 + 1e-9));
+            assert_eq!(compact.len(), exact.len());
+            assert_eq!(compact.size_in_bytes() * 4, exact.size_in_bytes());
+            for (idx, value) in values.iter().enumerate() {
+                assert_eq!(exact.get(idx), Some(*value));
+                let error = (compact.get(idx).unwrap() - value).abs();
+                assert!(error <= bound * (1.0 + 1e-9), "{error} > {bound}");
+            }
+            assert_eq!(compact.get(values.len()), None);
+        }
+    }
+
+    #[test]
+    fn test_u16_edge_cases() {
+        let constant = EncodedTable::new(vec![65.0; 3], TableEncoding::U16).unwrap();
+        assert_eq!(constant.get(2), Some(65.0));
+        assert_eq!(constant.max_error(), 0.0);
+        let empty = EncodedTable::new(vec![], TableEncoding::U16).unwrap();
+        assert!(empty.is_empty());
+        assert!(EncodedTable::new(vec![1.0, f64::NAN], TableEncoding::U16).is_err());
+    }
+}
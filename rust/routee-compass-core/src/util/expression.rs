@@ -0,0 +1,359 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// an arithmetic expression over named variables, such as `toll / trip_distance`.
+/// expressions support numbers, variable names, `+`, `-`, `*`, `/`, unary minus,
+/// parentheses and the functions `min(a, b)`, `max(a, b)` and `abs(a)`, with the usual
+/// precedence. division by zero follows floating point semantics.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expression {
+    source: String,
+    ast: Node,
+}
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+pub enum ExpressionError {
+    #[error("failed parsing expression '{0}': {1}")]
+    ParseError(String, String),
+    #[error("expression references '{0}', which is not a numeric variable")]
+    UnknownVariable(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Number(f64),
+    Variable(String),
+    Negate(Box<Node>),
+    Binary(BinaryOp, Box<Node>, Box<Node>),
+    Call(Function, Vec<Node>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BinaryOp {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Function {
+    Min,
+    Max,
+    Abs,
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Function> {
+        match name {
+            "min" => Some(Function::Min),
+            "max" => Some(Function::Max),
+            "abs" => Some(Function::Abs),
+            _ => None,
+        }
+    }
+
+    fn arity(&self) -> usize {
+        match self {
+            Function::Min | Function::Max => 2,
+            Function::Abs => 1,
+        }
+    }
+}
+
+impl Expression {
+    /// the expression as written
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// names of the variables referenced by the expression, in order of first reference
+    pub fn variables(&self) -> Vec<&str> {
+        let mut names = vec![];
+        collect_variables(&self.ast, &mut names);
+        names
+    }
+
+    /// evaluates the expression, looking up the value of each referenced variable.
+    pub fn evaluate(&self, lookup: &dyn Fn(&str) -> Option<f64>) -> Result<f64, ExpressionError> {
+        evaluate(&self.ast, lookup)
+    }
+}
+
+fn collect_variables<'a>(node: &'a Node, names: &mut Vec<&'a str>) {
+    match node {
+        Node::Number(_) => {}
+        Node::Variable(name) => {
+            if !names.contains(&name.as_str()) {
+                names.push(name);
+            }
+        }
+        Node::Negate(inner) => collect_variables(inner, names),
+        Node::Binary(_, lhs, rhs) => {
+            collect_variables(lhs, names);
+            collect_variables(rhs, names);
+        }
+        Node::Call(_, args) => args.iter().for_each(|a| collect_variables(a, names)),
+    }
+}
+
+fn evaluate(node: &Node, lookup: &dyn Fn(&str) -> Option<f64>) -> Result<f64, ExpressionError> {
+    match node {
+        Node::Number(value) => Ok(*value),
+        Node::Variable(name) => {
+            lookup(name).ok_or_else(|| ExpressionError::UnknownVariable(name.clone()))
+        }
+        Node::Negate(inner) => Ok(-evaluate(inner, lookup)?),
+        Node::Binary(op, lhs, rhs) => {
+            let l = evaluate(lhs, lookup)?;
+            let r = evaluate(rhs, lookup)?;
+            match op {
+                BinaryOp::Add => Ok(l + r),
+                BinaryOp::Subtract => Ok(l - r),
+                BinaryOp::Multiply => Ok(l * r),
+                BinaryOp::Divide => Ok(l / r),
+            }
+        }
+        Node::Call(function, args) => {
+            let values = args
+                .iter()
+                .map(|a| evaluate(a, lookup))
+                .collect::<Result<Vec<_>, _>>()?;
+            match function {
+                Function::Min => Ok(values[0].min(values[1])),
+                Function::Max => Ok(values[0].max(values[1])),
+                Function::Abs => Ok(values[0].abs()),
+            }
+        }
+    }
+}
+
+impl FromStr for Expression {
+    type Err = ExpressionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_error = |msg: String| ExpressionError::ParseError(s.to_string(), msg);
+        let tokens = tokenize(s).map_err(parse_error)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let ast = parser.expression().map_err(parse_error)?;
+        if let Some(token) = parser.peek() {
+            return Err(parse_error(format!("unexpected '{token}'")));
+        }
+        Ok(Expression {
+            source: s.to_string(),
+            ast,
+        })
+    }
+}
+
+impl Display for Expression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Identifier(String),
+    Symbol(char),
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Number(n) => write!(f, "{n}"),
+            Token::Identifier(name) => write!(f, "{name}"),
+            Token::Symbol(c) => write!(f, "{c}"),
+        }
+    }
+}
+
+fn tokenize(s: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = vec![];
+    let mut chars = s.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c.is_ascii_digit() || c == '.' {
+            let mut literal = String::new();
+            while let Some(&d) = chars.peek() {
+                if d.is_ascii_digit() || d == '.' {
+                    literal.push(d);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            let value = literal
+                .parse::<f64>()
+                .map_err(|e| format!("invalid number '{literal}': {e}"))?;
+            tokens.push(Token::Number(value));
+        } else if c.is_alphabetic() || c == '_' {
+            let mut name = String::new();
+            while let Some(&d) = chars.peek() {
+                if d.is_alphanumeric() || d == '_' {
+                    name.push(d);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            tokens.push(Token::Identifier(name));
+        } else if "+-*/(),".contains(c) {
+            tokens.push(Token::Symbol(c));
+            chars.next();
+        } else {
+            return Err(format!("unexpected character '{c}'"));
+        }
+    }
+    Ok(tokens)
+}
+
+/// recursive descent parser over the grammar
+///   expression := term (('+' | '-') term)*
+///   term       := factor (('*' | '/') factor)*
+///   factor     := '-' factor | number | identifier | function '(' arguments ')' | '(' expression ')'
+///   arguments  := expression (',' expression)*
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn expect(&mut self, symbol: char) -> Result<(), String> {
+        match self.next() {
+            Some(Token::Symbol(c)) if c == symbol => Ok(()),
+            Some(token) => Err(format!("expected '{symbol}', found '{token}'")),
+            None => Err(format!("expected '{symbol}'")),
+        }
+    }
+
+    fn expression(&mut self) -> Result<Node, String> {
+        let mut lhs = self.term()?;
+        while let Some(Token::Symbol(c @ ('+' | '-'))) = self.peek() {
+            let op = if *c == '+' {
+                BinaryOp::Add
+            } else {
+                BinaryOp::Subtract
+            };
+            self.next();
+            let rhs = self.term()?;
+            lhs = Node::Binary(op, Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn term(&mut self) -> Result<Node, String> {
+        let mut lhs = self.factor()?;
+        while let Some(Token::Symbol(c @ ('*' | '/'))) = self.peek() {
+            let op = if *c == '*' {
+                BinaryOp::Multiply
+            } else {
+                BinaryOp::Divide
+            };
+            self.next();
+            let rhs = self.factor()?;
+            lhs = Node::Binary(op, Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn factor(&mut self) -> Result<Node, String> {
+        match self.next() {
+            Some(Token::Symbol('-')) => Ok(Node::Negate(Box::new(self.factor()?))),
+            Some(Token::Number(value)) => Ok(Node::Number(value)),
+            Some(Token::Identifier(name)) => {
+                if self.peek() != Some(&Token::Symbol('(')) {
+                    return Ok(Node::Variable(name));
+                }
+                let function = Function::from_name(&name)
+                    .ok_or_else(|| format!("unknown function '{name}'"))?;
+                self.next();
+                let mut args = vec![self.expression()?];
+                while self.peek() == Some(&Token::Symbol(',')) {
+                    self.next();
+                    args.push(self.expression()?);
+                }
+                self.expect(')')?;
+                if args.len() != function.arity() {
+                    return Err(format!(
+                        "function '{name}' expects {} argument(s), found {}",
+                        function.arity(),
+                        args.len()
+                    ));
+                }
+                Ok(Node::Call(function, args))
+            }
+            Some(Token::Symbol('(')) => {
+                let inner = self.expression()?;
+                self.expect(')')?;
+                Ok(inner)
+            }
+            Some(token) => Err(format!("unexpected '{token}'")),
+            None => Err(String::from("expression ended unexpectedly")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_evaluate() {
+        let values = HashMap::from([("toll", 3.0), ("trip_distance", 2.0), ("trip_time", 10.0)]);
+        let lookup = |name: &str| values.get(name).copied();
+        let cases = [
+            ("toll / trip_distance", 1.5),
+            ("1 + 2 * 3", 7.0),
+            ("(1 + 2) * 3", 9.0),
+            ("trip_time - toll - 1", 6.0),
+            ("-toll * 2", -6.0),
+            ("60 * trip_distance / trip_time", 12.0),
+            ("min(toll, trip_distance) + max(1, 2)", 4.0),
+            ("abs(trip_distance - trip_time)", 8.0),
+        ];
+        for (source, expected) in cases {
+            let expression = Expression::from_str(source).unwrap();
+            assert_eq!(expression.evaluate(&lookup).unwrap(), expected, "{source}");
+        }
+        let expression = Expression::from_str("toll / trip_distance + toll").unwrap();
+        assert_eq!(expression.variables(), vec!["toll", "trip_distance"]);
+        let unknown = Expression::from_str("energy / trip_distance").unwrap();
+        assert_eq!(
+            unknown.evaluate(&lookup),
+            Err(ExpressionError::UnknownVariable(String::from("energy")))
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let invalid = [
+            "",
+            "toll /",
+            "(toll",
+            "toll trip_distance",
+            "toll % 2",
+            "min(toll)",
+            "sqrt(toll)",
+        ];
+        for source in invalid {
+            assert!(
+                Expression::from_str(source).is_err(),
+                "expected '{source}' to fail"
+            );
+        }
+    }
+}
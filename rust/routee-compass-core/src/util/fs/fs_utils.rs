@@ -1,11 +1,14 @@
 use std::{
     fs::File,
-    io::{self, BufRead, BufReader},
+    io::{self, BufRead, BufReader, Read},
     path::Path,
 };
 
 use flate2::read::GzDecoder;
 
+/// the first four bytes of a zstd frame
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
 /// The output is wrapped in a Result to allow matching on errors
 /// Returns an Iterator to the Reader of the lines of the file.
 /// based on https://doc.rust-lang.org/rust-by-example/std_misc/file/read_lines.html
@@ -43,3 +46,41 @@ where
         }
     }
 }
+
+/// true if the file begins with the zstd magic number.
+pub fn is_zstd<P>(filepath: P) -> bool
+where
+    P: AsRef<Path>,
+{
+    let mut magic = [0u8; 4];
+    match File::open(filepath) {
+        Err(_) => false,
+        Ok(mut file) => file.read_exact(&mut magic).is_ok() && magic == ZSTD_MAGIC,
+    }
+}
+
+/// opens a file for reading, transparently decompressing gzip and zstd files, which are
+/// detected by their leading bytes rather than by their extension.
+pub fn open_reader<P>(filepath: P) -> io::Result<Box<dyn Read>>
+where
+    P: AsRef<Path>,
+{
+    let file = File::open(filepath.as_ref())?;
+    if is_gzip(filepath.as_ref()) {
+        Ok(Box::new(GzDecoder::new(BufReader::new(file))))
+    } else if is_zstd(filepath.as_ref()) {
+        Ok(Box::new(zstd::Decoder::new(file)?))
+    } else {
+        Ok(Box::new(BufReader::new(file)))
+    }
+}
+
+/// reads a file to a string, decompressing gzip and zstd files.
+pub fn read_to_string<P>(filepath: P) -> io::Result<String>
+where
+    P: AsRef<Path>,
+{
+    let mut contents = String::new();
+    open_reader(filepath)?.read_to_string(&mut contents)?;
+    Ok(contents)
+}
@@ -1,3 +1,5 @@
 pub mod fs_utils;
+pub mod parquet_utils;
+pub mod polled_file;
 pub mod read_decoders;
 pub mod read_utils;
@@ -0,0 +1,97 @@
+use geo::Geometry;
+use geo_traits::to_geo::ToGeoGeometry;
+use parquet::{
+    file::reader::SerializedFileReader,
+    record::{reader::RowIter, Field, Row},
+};
+use std::{fs::File, path::Path};
+
+/// true if the file has a `.parquet` extension
+pub fn is_parquet<P: AsRef<Path>>(path: P) -> bool {
+    has_extension(path, "parquet")
+}
+
+/// true if the file has a `.gpkg` extension
+pub fn is_geopackage<P: AsRef<Path>>(path: P) -> bool {
+    has_extension(path, "gpkg")
+}
+
+/// error message for GeoPackage inputs, which cannot be read by Compass
+pub fn geopackage_unsupported<P: AsRef<Path>>(path: P) -> String {
+    format!(
+        "GeoPackage file {} is not supported, convert the layer to GeoParquet, for example with 'ogr2ogr -f Parquet'",
+        path.as_ref().to_string_lossy()
+    )
+}
+
+/// iterates over the rows of a Parquet file
+pub fn row_iter<P: AsRef<Path>>(path: P) -> Result<RowIter<'static>, String> {
+    let filename = path.as_ref().to_string_lossy().to_string();
+    let file = File::open(path).map_err(|e| format!("failure opening {filename}: {e}"))?;
+    let reader = SerializedFileReader::new(file)
+        .map_err(|e| format!("failure reading Parquet file {filename}: {e}"))?;
+    Ok(RowIter::from_file_into(Box::new(reader)))
+}
+
+/// gets a column of a row as an unsigned integer
+pub fn get_usize(row: &Row, column: &str) -> Result<usize, String> {
+    let field = get_field(row, column)?;
+    field_as_usize(field).ok_or_else(|| {
+        format!("column '{column}' expected to be a non-negative integer, found {field}")
+    })
+}
+
+/// gets a column of a row as a floating point number
+pub fn get_f64(row: &Row, column: &str) -> Result<f64, String> {
+    let field = get_field(row, column)?;
+    field_as_f64(field)
+        .ok_or_else(|| format!("column '{column}' expected to be numeric, found {field}"))
+}
+
+/// gets a column of a row encoded as WKB, the default GeoParquet geometry encoding
+pub fn get_geometry(row: &Row, column: &str) -> Result<Geometry<f64>, String> {
+    match get_field(row, column)? {
+        Field::Bytes(bytes) => {
+            let wkb = wkb::reader::read_wkb(bytes.data())
+                .map_err(|e| format!("column '{column}' has invalid WKB: {e}"))?;
+            Ok(wkb.to_geometry())
+        }
+        other => Err(format!(
+            "column '{column}' expected to be a WKB geometry, found {other}"
+        )),
+    }
+}
+
+pub fn field_as_usize(field: &Field) -> Option<usize> {
+    match field {
+        Field::Int(v) => usize::try_from(*v).ok(),
+        Field::Long(v) => usize::try_from(*v).ok(),
+        Field::UInt(v) => usize::try_from(*v).ok(),
+        Field::ULong(v) => usize::try_from(*v).ok(),
+        _ => None,
+    }
+}
+
+pub fn field_as_f64(field: &Field) -> Option<f64> {
+    match field {
+        Field::Float(v) => Some(*v as f64),
+        Field::Double(v) => Some(*v),
+        Field::Int(v) => Some(*v as f64),
+        Field::Long(v) => Some(*v as f64),
+        _ => None,
+    }
+}
+
+fn get_field<'a>(row: &'a Row, column: &str) -> Result<&'a Field, String> {
+    row.get_column_iter()
+        .find(|(name, _)| name.as_str() == column)
+        .map(|(_, field)| field)
+        .ok_or_else(|| format!("row is missing column '{column}'"))
+}
+
+fn has_extension<P: AsRef<Path>>(path: P, extension: &str) -> bool {
+    path.as_ref()
+        .extension()
+        .map(|ext| ext.eq_ignore_ascii_case(extension))
+        .unwrap_or_default()
+}
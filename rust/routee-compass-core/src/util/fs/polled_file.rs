@@ -0,0 +1,138 @@
+//! a file that is periodically replaced by an external feed, such as a traffic or
+//! incident feed, and read again when it changes.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, RwLock},
+    time::{Duration, Instant, SystemTime},
+};
+
+/// holds the contents of a file that is periodically replaced. the file is checked for
+/// a new modification time at most once per poll interval, when [`PolledFile::current`]
+/// is called, and is read again only when its modification time changes. the new
+/// contents replace the previous contents atomically, so readers holding the previous
+/// contents are unaffected. if the file is missing or cannot be read, the previous
+/// contents remain in use.
+pub struct PolledFile<T> {
+    path: PathBuf,
+    poll_interval: Duration,
+    last_poll: Mutex<Option<Instant>>,
+    current: RwLock<Option<(SystemTime, Arc<T>)>>,
+}
+
+impl<T> PolledFile<T> {
+    pub fn new<P: AsRef<Path>>(path: P, poll_interval: Duration) -> PolledFile<T> {
+        PolledFile {
+            path: path.as_ref().to_path_buf(),
+            poll_interval,
+            last_poll: Mutex::new(None),
+            current: RwLock::new(None),
+        }
+    }
+
+    /// the current contents of the file along with its modification time, polling the
+    /// file for changes if the poll interval has elapsed.
+    ///
+    /// # Arguments
+    ///
+    /// * `read` - reads the contents of the file at the provided path, given its modification time
+    ///
+    /// # Returns
+    ///
+    /// The most recent contents read, or None if the file has never been read successfully.
+    /// An error is only returned if a lock has been poisoned.
+    pub fn current(
+        &self,
+        read: impl FnOnce(&Path, SystemTime) -> Result<T, String>,
+    ) -> Result<Option<(SystemTime, Arc<T>)>, String> {
+        if self.should_poll()? {
+            self.poll(read)?;
+        }
+        let current = self
+            .current
+            .read()
+            .map_err(|e| format!("lock for polled file is poisoned: {e}"))?;
+        Ok(current.clone())
+    }
+
+    fn should_poll(&self) -> Result<bool, String> {
+        let mut last_poll = self
+            .last_poll
+            .lock()
+            .map_err(|e| format!("lock for polled file is poisoned: {e}"))?;
+        match *last_poll {
+            Some(t) if t.elapsed() < self.poll_interval => Ok(false),
+            _ => {
+                *last_poll = Some(Instant::now());
+                Ok(true)
+            }
+        }
+    }
+
+    fn poll(
+        &self,
+        read: impl FnOnce(&Path, SystemTime) -> Result<T, String>,
+    ) -> Result<(), String> {
+        let path_str = self.path.to_string_lossy();
+        let modified = match std::fs::metadata(&self.path).and_then(|m| m.modified()) {
+            Ok(modified) => modified,
+            Err(e) => {
+                log::warn!("polled file {path_str} is unavailable: {e}");
+                return Ok(());
+            }
+        };
+        let current_modified = self
+            .current
+            .read()
+            .map_err(|e| format!("lock for polled file is poisoned: {e}"))?
+            .as_ref()
+            .map(|(t, _)| *t);
+        if current_modified == Some(modified) {
+            return Ok(());
+        }
+        match read(&self.path, modified) {
+            Ok(contents) => {
+                log::info!("read new contents of polled file {path_str}");
+                let mut current = self
+                    .current
+                    .write()
+                    .map_err(|e| format!("lock for polled file is poisoned: {e}"))?;
+                *current = Some((modified, Arc::new(contents)));
+            }
+            Err(e) => {
+                log::warn!("failure reading polled file {path_str}, keeping previous contents: {e}")
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_keeps_previous_contents_on_read_failure() {
+        let mut file = tempfile::NamedTempFile::new().expect("failed to create temp file");
+        writeln!(file, "1").expect("failed to write temp file");
+        let polled: PolledFile<String> = PolledFile::new(file.path(), Duration::ZERO);
+        let read_ok = |p: &Path, _| std::fs::read_to_string(p).map_err(|e| e.to_string());
+        let (_, first) = polled
+            .current(read_ok)
+            .expect("poll failed")
+            .expect("expected contents");
+        assert_eq!(first.trim(), "1");
+
+        // force a new modification time, then fail to read the new contents
+        let later = SystemTime::now() + Duration::from_secs(10);
+        file.as_file()
+            .set_modified(later)
+            .expect("failed to set modified time");
+        let (_, second) = polled
+            .current(|_, _| Err(String::from("malformed file")))
+            .expect("poll failed")
+            .expect("expected contents");
+        assert_eq!(second.trim(), "1");
+    }
+}
@@ -18,6 +18,10 @@ pub fn u8(_idx: usize, row: String) -> Result<u8, std::io::Error> {
     row.parse::<u8>().map_err(|e| handle_error(&row, e))
 }
 
+pub fn bool(_idx: usize, row: String) -> Result<bool, std::io::Error> {
+    row.parse::<bool>().map_err(|e| handle_error(&row, e))
+}
+
 pub fn f64(_idx: usize, row: String) -> Result<f64, std::io::Error> {
     row.parse::<f64>().map_err(|e| handle_error(&row, e))
 }
@@ -1,10 +1,8 @@
 use super::fs_utils;
 use crate::util::progress;
 use csv::ReaderBuilder;
-use flate2::read::GzDecoder;
 use kdam::{BarBuilder, BarExt};
 use std::{
-    fs::File,
     io::{self, BufRead, BufReader},
     path::Path,
 };
@@ -54,7 +52,7 @@ where
 
 /// reads in a raw file and deserializes each line of the file into a type T
 /// using the provided operation.
-/// inspects the file to determine if it should read as a raw or gzip stream.
+/// inspects the file to determine if it should read as a raw, gzip or zstd stream.
 /// the row index (starting from zero) is passed to the deserialization op
 /// as in most cases, the row number is an id.
 pub fn read_raw_file<F, T>(
@@ -81,11 +79,7 @@ where
         })),
     };
 
-    let result = if fs_utils::is_gzip(filepath.as_ref()) {
-        Ok(read_gzip(filepath, op, row_callback)?)
-    } else {
-        Ok(read_regular(filepath, op, row_callback)?)
-    };
+    let result = read_lines(filepath, op, row_callback);
     if finalize_bar {
         eprintln!();
     }
@@ -104,12 +98,7 @@ where
     F: AsRef<Path>,
     T: serde::de::DeserializeOwned + 'a,
 {
-    let f = File::open(filepath.as_ref())?;
-    let r: Box<dyn io::Read> = if fs_utils::is_gzip(filepath) {
-        Box::new(BufReader::new(GzDecoder::new(f)))
-    } else {
-        Box::new(f)
-    };
+    let r = fs_utils::open_reader(filepath)?;
     let reader = ReaderBuilder::new()
         .has_headers(has_headers)
         .trim(csv::Trim::Fields)
@@ -126,10 +115,10 @@ where
     Ok(Box::new(reader))
 }
 
-/// reads a regular file using a simple deserialization operation.
+/// reads a regular or compressed file using a simple deserialization operation.
 ///
 /// # Arguments
-/// * `filepath` - path to the regular file
+/// * `filepath` - path to the file
 /// * `op` - callback taking the line number (from zero) and the line, returning a T or read failure
 /// * `row_callback` - optional callback invoked after each row deserialization
 ///
@@ -137,7 +126,7 @@ where
 ///
 /// A Collection of T after a successful deserialization, or an error if any row read fails.
 ///
-fn read_regular<'a, F, T>(
+fn read_lines<'a, F, T>(
     filepath: F,
     op: impl Fn(usize, String) -> Result<T, io::Error>,
     mut row_callback: Option<Box<dyn FnMut() + 'a>>,
@@ -145,9 +134,8 @@ fn read_regular<'a, F, T>(
 where
     F: AsRef<Path>,
 {
-    let file = File::open(filepath)?;
-    let reader = BufReader::new(file);
-    let result: Result<Box<[T]>, std::io::Error> = reader
+    let reader = BufReader::new(fs_utils::open_reader(filepath)?);
+    reader
         .lines()
         .enumerate()
         .map(|(idx, row)| {
@@ -158,41 +146,7 @@ where
             }
             Ok(deserialized)
         })
-        .collect();
-    result
-}
-
-/// reads a GZIP'd regular file using a simple deserialization operation.
-///
-/// # Arguments
-/// * `filepath` - path to the regular file
-/// * `op` - callback taking the line number (from zero) and the line, returning a T or read failure
-/// * `row_callback` - optional callback invoked after each row deserialization
-///
-/// # Returns
-///
-/// A Collection of T after a successful deserialization, or an error if any row read fails.
-///
-fn read_gzip<'a, F, T>(
-    filepath: F,
-    op: impl Fn(usize, String) -> Result<T, io::Error>,
-    mut row_callback: Option<Box<dyn FnMut() + 'a>>,
-) -> Result<Box<[T]>, io::Error>
-where
-    F: AsRef<Path>,
-{
-    let file = File::open(filepath)?;
-    let mut result = vec![];
-    let reader = BufReader::new(GzDecoder::new(file));
-    for (idx, row) in reader.lines().enumerate() {
-        let parsed = row?;
-        let deserialized = op(idx, parsed)?;
-        if let Some(cb) = &mut row_callback {
-            cb();
-        }
-        result.push(deserialized);
-    }
-    Ok(result.into_boxed_slice())
+        .collect()
 }
 
 #[cfg(test)]
@@ -250,4 +204,29 @@ mod tests {
             "result should include each row from the source file"
         );
     }
+
+    #[test]
+    fn test_read_raw_file_zstd() {
+        let filepath = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("util")
+            .join("fs")
+            .join("test")
+            .join("test.txt.zst");
+        println!("loading file {filepath:?}");
+        let bonus_word = " yay";
+        let op = |_idx: usize, row: String| Ok(row + bonus_word);
+        let result = read_raw_file(&filepath, op, None, None).unwrap();
+        let expected = vec![
+            String::from("RouteE yay"),
+            String::from("FASTSim yay"),
+            String::from("HIVE yay"),
+            String::from("ADOPT yay"),
+        ]
+        .into_boxed_slice();
+        assert_eq!(
+            result, expected,
+            "result should include each row from the source file"
+        );
+    }
 }
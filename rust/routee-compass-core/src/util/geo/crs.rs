@@ -0,0 +1,369 @@
+//! coordinate reference systems supported for input and output coordinates.
+//! Compass works in WGS84 (EPSG:4326) internally, so coordinates in any other
+//! system are transformed to WGS84 when read and from WGS84 when written.
+use geo::{Coord, LineString};
+use serde::{Deserialize, Serialize};
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
+use std::fmt::Display;
+
+/// SRID of WGS84 longitude/latitude, the coordinate system used within Compass
+pub const WGS84_SRID: u32 = 4326;
+/// SRID of the spherical Web Mercator projection
+pub const WEB_MERCATOR_SRID: u32 = 3857;
+
+const WGS84_SEMI_MAJOR_AXIS: f64 = 6_378_137.0;
+const WGS84_FLATTENING: f64 = 1.0 / 298.257_223_563;
+const UTM_SCALE_FACTOR: f64 = 0.9996;
+const UTM_FALSE_EASTING: f64 = 500_000.0;
+const UTM_SOUTH_FALSE_NORTHING: f64 = 10_000_000.0;
+
+/// a coordinate reference system identified by its EPSG SRID. supported systems are
+/// WGS84 (4326), Web Mercator (3857) and the UTM zones on WGS84 (32601-32660 north,
+/// 32701-32760 south) and NAD83 (26901-26923), with NAD83 treated as equal to WGS84.
+/// deserializes from an SRID number or an `"EPSG:<srid>"` string.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(try_from = "CrsInput", into = "u32")]
+pub struct Crs {
+    srid: u32,
+    projection: Projection,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Projection {
+    Geographic,
+    WebMercator,
+    TransverseMercator {
+        central_meridian: f64,
+        false_northing: f64,
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CrsInput {
+    Srid(u32),
+    Name(String),
+}
+
+impl Crs {
+    pub fn wgs84() -> Crs {
+        Crs {
+            srid: WGS84_SRID,
+            projection: Projection::Geographic,
+        }
+    }
+
+    /// looks up a coordinate reference system by its EPSG SRID
+    pub fn from_srid(srid: u32) -> Result<Crs, String> {
+        let utm = |zone: u32, north: bool| Projection::TransverseMercator {
+            central_meridian: (zone as f64 - 1.0) * 6.0 - 180.0 + 3.0,
+            false_northing: if north { 0.0 } else { UTM_SOUTH_FALSE_NORTHING },
+        };
+        let projection = match srid {
+            WGS84_SRID => Projection::Geographic,
+            WEB_MERCATOR_SRID => Projection::WebMercator,
+            32601..=32660 => utm(srid - 32600, true),
+            32701..=32760 => utm(srid - 32700, false),
+            26901..=26923 => utm(srid - 26900, true),
+            _ => {
+                return Err(format!(
+                    "unsupported SRID {srid}, must be one of 4326, 3857, 32601-32660, 32701-32760 or 26901-26923"
+                ))
+            }
+        };
+        Ok(Crs { srid, projection })
+    }
+
+    pub fn srid(&self) -> u32 {
+        self.srid
+    }
+
+    pub fn is_wgs84(&self) -> bool {
+        self.projection == Projection::Geographic
+    }
+
+    /// transforms a coordinate in this system to WGS84 longitude and latitude
+    pub fn to_wgs84(&self, coord: Coord<f64>) -> Result<Coord<f64>, String> {
+        let result = match self.projection {
+            Projection::Geographic => coord,
+            Projection::WebMercator => Coord {
+                x: (coord.x / WGS84_SEMI_MAJOR_AXIS).to_degrees(),
+                y: (2.0 * (coord.y / WGS84_SEMI_MAJOR_AXIS).exp().atan() - FRAC_PI_2).to_degrees(),
+            },
+            Projection::TransverseMercator {
+                central_meridian,
+                false_northing,
+            } => transverse_mercator_inverse(coord, central_meridian, false_northing),
+        };
+        if !(-180.0..=180.0).contains(&result.x) || !(-90.0..=90.0).contains(&result.y) {
+            return Err(format!(
+                "coordinate ({}, {}) in EPSG:{} is outside of the valid WGS84 range",
+                coord.x, coord.y, self.srid
+            ));
+        }
+        Ok(result)
+    }
+
+    /// transforms a WGS84 longitude and latitude to a coordinate in this system
+    pub fn from_wgs84(&self, coord: Coord<f64>) -> Result<Coord<f64>, String> {
+        if !(-180.0..=180.0).contains(&coord.x) || !(-90.0..=90.0).contains(&coord.y) {
+            return Err(format!(
+                "coordinate ({}, {}) is not a valid WGS84 position",
+                coord.x, coord.y
+            ));
+        }
+        let result = match self.projection {
+            Projection::Geographic => coord,
+            Projection::WebMercator => Coord {
+                x: WGS84_SEMI_MAJOR_AXIS * coord.x.to_radians(),
+                y: WGS84_SEMI_MAJOR_AXIS * (FRAC_PI_4 + coord.y.to_radians() / 2.0).tan().ln(),
+            },
+            Projection::TransverseMercator {
+                central_meridian,
+                false_northing,
+            } => transverse_mercator_forward(coord, central_meridian, false_northing),
+        };
+        if !(result.x.is_finite() && result.y.is_finite()) {
+            return Err(format!(
+                "coordinate ({}, {}) cannot be projected to EPSG:{}",
+                coord.x, coord.y, self.srid
+            ));
+        }
+        Ok(result)
+    }
+
+    /// transforms a single precision coordinate in this system to WGS84
+    pub fn coord_to_wgs84(&self, coord: Coord<f32>) -> Result<Coord<f32>, String> {
+        let result = self.to_wgs84(Coord {
+            x: coord.x as f64,
+            y: coord.y as f64,
+        })?;
+        Ok(Coord {
+            x: result.x as f32,
+            y: result.y as f32,
+        })
+    }
+
+    /// transforms each coordinate of a linestring in this system to WGS84
+    pub fn linestring_to_wgs84(
+        &self,
+        linestring: &LineString<f32>,
+    ) -> Result<LineString<f32>, String> {
+        linestring
+            .coords()
+            .map(|c| self.coord_to_wgs84(*c))
+            .collect::<Result<Vec<_>, _>>()
+            .map(LineString::new)
+    }
+
+    /// transforms each coordinate of a WGS84 linestring to this system. the result is
+    /// double precision, since projected coordinates are too large to be represented to
+    /// the nearest meter in single precision.
+    pub fn linestring_from_wgs84(
+        &self,
+        linestring: &LineString<f32>,
+    ) -> Result<LineString<f64>, String> {
+        linestring
+            .coords()
+            .map(|c| {
+                self.from_wgs84(Coord {
+                    x: c.x as f64,
+                    y: c.y as f64,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(LineString::new)
+    }
+}
+
+impl Default for Crs {
+    fn default() -> Self {
+        Crs::wgs84()
+    }
+}
+
+impl Display for Crs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "EPSG:{}", self.srid)
+    }
+}
+
+impl TryFrom<CrsInput> for Crs {
+    type Error = String;
+
+    fn try_from(value: CrsInput) -> Result<Self, Self::Error> {
+        match value {
+            CrsInput::Srid(srid) => Crs::from_srid(srid),
+            CrsInput::Name(name) => {
+                let code = name
+                    .strip_prefix("EPSG:")
+                    .or_else(|| name.strip_prefix("epsg:"))
+                    .unwrap_or(&name);
+                let srid = code
+                    .parse::<u32>()
+                    .map_err(|_| format!("expected an SRID such as 'EPSG:4326', found '{name}'"))?;
+                Crs::from_srid(srid)
+            }
+        }
+    }
+}
+
+impl From<Crs> for u32 {
+    fn from(value: Crs) -> Self {
+        value.srid
+    }
+}
+
+/// series coefficients of the Krüger transverse Mercator projection, to third order in
+/// the third flattening n, which is accurate to well under a millimeter within a UTM zone.
+struct KrugerSeries {
+    /// rectifying radius scaled by the UTM scale factor
+    k0_a: f64,
+    /// second term of the conformal latitude, 2√n / (1 + n)
+    e: f64,
+    alpha: [f64; 3],
+    beta: [f64; 3],
+    delta: [f64; 3],
+}
+
+impl KrugerSeries {
+    fn wgs84() -> KrugerSeries {
+        let f = WGS84_FLATTENING;
+        let n = f / (2.0 - f);
+        let (n2, n3) = (n * n, n * n * n);
+        let a = WGS84_SEMI_MAJOR_AXIS / (1.0 + n) * (1.0 + n2 / 4.0 + n2 * n2 / 64.0);
+        KrugerSeries {
+            k0_a: UTM_SCALE_FACTOR * a,
+            e: 2.0 * n.sqrt() / (1.0 + n),
+            alpha: [
+                n / 2.0 - 2.0 * n2 / 3.0 + 5.0 * n3 / 16.0,
+                13.0 * n2 / 48.0 - 3.0 * n3 / 5.0,
+                61.0 * n3 / 240.0,
+            ],
+            beta: [
+                n / 2.0 - 2.0 * n2 / 3.0 + 37.0 * n3 / 96.0,
+                n2 / 48.0 + n3 / 15.0,
+                17.0 * n3 / 480.0,
+            ],
+            delta: [
+                2.0 * n - 2.0 * n2 / 3.0 - 2.0 * n3,
+                7.0 * n2 / 3.0 - 8.0 * n3 / 5.0,
+                56.0 * n3 / 15.0,
+            ],
+        }
+    }
+}
+
+fn transverse_mercator_forward(
+    coord: Coord<f64>,
+    central_meridian: f64,
+    false_northing: f64,
+) -> Coord<f64> {
+    let k = KrugerSeries::wgs84();
+    let lat = coord.y.to_radians();
+    let lon = (coord.x - central_meridian).to_radians();
+    let t = (lat.sin().atanh() - k.e * (k.e * lat.sin()).atanh()).sinh();
+    let xi_prime = t.atan2(lon.cos());
+    let eta_prime = (lon.sin() / (1.0 + t * t).sqrt()).atanh();
+    let (mut xi, mut eta) = (xi_prime, eta_prime);
+    for (j, alpha) in k.alpha.iter().enumerate() {
+        let m = 2.0 * (j + 1) as f64;
+        xi += alpha * (m * xi_prime).sin() * (m * eta_prime).cosh();
+        eta += alpha * (m * xi_prime).cos() * (m * eta_prime).sinh();
+    }
+    Coord {
+        x: UTM_FALSE_EASTING + k.k0_a * eta,
+        y: false_northing + k.k0_a * xi,
+    }
+}
+
+fn transverse_mercator_inverse(
+    coord: Coord<f64>,
+    central_meridian: f64,
+    false_northing: f64,
+) -> Coord<f64> {
+    let k = KrugerSeries::wgs84();
+    let xi = (coord.y - false_northing) / k.k0_a;
+    let eta = (coord.x - UTM_FALSE_EASTING) / k.k0_a;
+    let (mut xi_prime, mut eta_prime) = (xi, eta);
+    for (j, beta) in k.beta.iter().enumerate() {
+        let m = 2.0 * (j + 1) as f64;
+        xi_prime -= beta * (m * xi).sin() * (m * eta).cosh();
+        eta_prime -= beta * (m * xi).cos() * (m * eta).sinh();
+    }
+    let chi = (xi_prime.sin() / eta_prime.cosh()).asin();
+    let mut lat = chi;
+    for (j, delta) in k.delta.iter().enumerate() {
+        lat += delta * (2.0 * (j + 1) as f64 * chi).sin();
+    }
+    let lon = eta_prime.sinh().atan2(xi_prime.cos());
+    Coord {
+        x: central_meridian + lon.to_degrees(),
+        y: lat.to_degrees(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo::coord;
+
+    #[test]
+    fn test_utm_known_point() {
+        // 40°N on the central meridian of UTM zone 13N
+        let crs = Crs::from_srid(32613).unwrap();
+        let projected = crs.from_wgs84(coord! { x: -105.0, y: 40.0 }).unwrap();
+        assert!((projected.x - 500_000.0).abs() < 0.01);
+        assert!((projected.y - 4_427_757.22).abs() < 1.0, "{projected:?}");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let denver = coord! { x: -104.9903, y: 39.7392 };
+        let sydney = coord! { x: 151.2093, y: -33.8688 };
+        for (srid, point) in [
+            (4326, denver),
+            (3857, denver),
+            (32613, denver),
+            (26913, denver),
+            (32756, sydney),
+        ] {
+            let crs = Crs::from_srid(srid).unwrap();
+            let projected = crs.from_wgs84(point).unwrap();
+            let result = crs.to_wgs84(projected).unwrap();
+            assert!(
+                (result.x - point.x).abs() < 1e-8 && (result.y - point.y).abs() < 1e-8,
+                "EPSG:{srid} round trip of {point:?} returned {result:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_web_mercator_extent() {
+        let crs = Crs::from_srid(WEB_MERCATOR_SRID).unwrap();
+        let projected = crs.from_wgs84(coord! { x: 180.0, y: 0.0 }).unwrap();
+        assert!((projected.x - 20_037_508.34).abs() < 0.01);
+        assert!(projected.y.abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let from_number: Crs = serde_json::from_value(serde_json::json!(32613)).unwrap();
+        let from_name: Crs = serde_json::from_value(serde_json::json!("EPSG:32613")).unwrap();
+        assert_eq!(from_number, from_name);
+        assert_eq!(
+            serde_json::to_value(from_name).unwrap(),
+            serde_json::json!(32613)
+        );
+        let unsupported = serde_json::from_value::<Crs>(serde_json::json!(2232));
+        assert!(unsupported.is_err());
+        assert!(Crs::wgs84().is_wgs84());
+    }
+
+    #[test]
+    fn test_projected_coordinates_out_of_range() {
+        // state plane feet mistakenly declared as WGS84 are rejected
+        let result = Crs::wgs84().to_wgs84(coord! { x: 3_140_000.0, y: 1_700_000.0 });
+        assert!(result.is_err());
+    }
+}
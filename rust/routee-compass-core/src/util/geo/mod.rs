@@ -1,4 +1,5 @@
 mod coord;
+pub mod crs;
 pub mod geo_io_utils;
 pub mod haversine;
 mod polygonal_rtree;
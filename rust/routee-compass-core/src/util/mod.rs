@@ -1,5 +1,7 @@
 pub mod conversion;
 pub mod duration_extension;
+pub mod encoded_table;
+pub mod expression;
 pub mod fs;
 pub mod geo;
 pub mod io_utils;
@@ -8,3 +10,4 @@ pub mod priority_queue;
 pub mod progress;
 pub mod read_only_lock;
 pub mod serde;
+pub mod span;
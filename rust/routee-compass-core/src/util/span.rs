@@ -0,0 +1,340 @@
+//! lightweight structured spans for tracing the phases of a Compass run.
+//!
+//! a [`Span`] is opened around a unit of work (loading the graph, building a model,
+//! running a query's search, applying a plugin) and closed when the returned
+//! [`SpanGuard`] is dropped. spans opened on the same thread while another span is
+//! open record it as their parent, so a query's search and output plugin spans are
+//! nested under the query span. work handed to another thread, such as the graph
+//! loading thread or the rayon workers that run queries, is nested under the span that
+//! handed it off by running it within [`with_parent`].
+//!
+//! spans are also the clock behind the run metrics: a span given a [`StageTiming`]
+//! adds its elapsed time to it when closed, so the phase and plugin timings in a
+//! metrics report are the durations of the spans opened for those phases.
+//!
+//! these are Compass's own timing spans, not `tracing` spans, and no `tracing` or
+//! OpenTelemetry exporter is provided. by default, span events are written as `log`
+//! records at debug level to the `routee_compass::span` target. an application that
+//! exports traces can install its own [`SpanSubscriber`] once at startup via
+//! [`set_span_subscriber`] and forward the events to its tracing library.
+
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use serde_json::{json, Value};
+
+use super::duration_extension::DurationExtension;
+
+/// the log target used by the default span subscriber
+pub const SPAN_LOG_TARGET: &str = "routee_compass::span";
+
+static SUBSCRIBER: OnceLock<Box<dyn SpanSubscriber>> = OnceLock::new();
+static NEXT_SPAN_ID: AtomicU64 = AtomicU64::new(1);
+
+thread_local! {
+    static SPAN_STACK: RefCell<Vec<u64>> = const { RefCell::new(vec![]) };
+}
+
+/// receives span events. implementations must be cheap, as they are called
+/// on the hot path of every query.
+pub trait SpanSubscriber: Send + Sync {
+    /// called when a span is opened
+    fn on_enter(&self, span: &SpanRecord);
+    /// called when a span is closed, with the time elapsed since it was opened
+    fn on_exit(&self, span: &SpanRecord, elapsed: Duration);
+}
+
+/// installs the subscriber that receives all span events for the lifetime of the
+/// process. returns false if a subscriber was already installed.
+pub fn set_span_subscriber(subscriber: Box<dyn SpanSubscriber>) -> bool {
+    SUBSCRIBER.set(subscriber).is_ok()
+}
+
+/// the id of the innermost span open on this thread, if any. pass it to
+/// [`with_parent`] on another thread to nest the spans opened there under it.
+pub fn current_span_id() -> Option<u64> {
+    SPAN_STACK.with(|stack| stack.borrow().last().copied())
+}
+
+/// runs a function with the provided span as the parent of the spans it opens on this
+/// thread, for work handed off from the thread where that span is open.
+pub fn with_parent<T>(parent: Option<u64>, f: impl FnOnce() -> T) -> T {
+    let Some(parent) = parent else {
+        return f();
+    };
+    SPAN_STACK.with(|stack| stack.borrow_mut().push(parent));
+    let result = f();
+    SPAN_STACK.with(|stack| {
+        let mut stack = stack.borrow_mut();
+        if let Some(pos) = stack.iter().rposition(|s| *s == parent) {
+            stack.remove(pos);
+        }
+    });
+    result
+}
+
+/// the identity and fields of a span as sent to a [`SpanSubscriber`].
+#[derive(Clone, Debug)]
+pub struct SpanRecord {
+    /// unique identifier of this span within the process
+    pub id: u64,
+    /// the span that was open on this thread, or handed off to it with [`with_parent`],
+    /// when this span was opened, if any
+    pub parent: Option<u64>,
+    pub name: &'static str,
+    pub fields: Vec<(&'static str, String)>,
+}
+
+/// time spent in a named stage of a run, such as a single plugin, summed over the
+/// spans recorded to it. safe to update from many threads.
+pub struct StageTiming {
+    pub name: String,
+    nanos: AtomicU64,
+    calls: AtomicU64,
+}
+
+impl StageTiming {
+    pub fn new(name: &str) -> StageTiming {
+        StageTiming {
+            name: name.to_string(),
+            nanos: AtomicU64::new(0),
+            calls: AtomicU64::new(0),
+        }
+    }
+
+    /// adds the time spent processing some number of queries
+    pub fn record(&self, duration: Duration, calls: u64) {
+        let nanos = u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+        self.nanos.fetch_add(nanos, Ordering::Relaxed);
+        self.calls.fetch_add(calls, Ordering::Relaxed);
+    }
+
+    pub fn duration(&self) -> Duration {
+        Duration::from_nanos(self.nanos.load(Ordering::Relaxed))
+    }
+
+    pub fn calls(&self) -> u64 {
+        self.calls.load(Ordering::Relaxed)
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "name": self.name,
+            "duration": self.duration().hhmmss(),
+            "duration_secs": self.duration().as_secs_f64(),
+            "calls": self.calls(),
+        })
+    }
+}
+
+/// a span that has not yet been opened. add fields with [`Span::field`], a timing
+/// with [`Span::timing`] and open it with [`Span::enter`].
+pub struct Span<'a> {
+    name: &'static str,
+    fields: Vec<(&'static str, String)>,
+    timing: Option<&'a StageTiming>,
+    calls: u64,
+}
+
+impl<'a> Span<'a> {
+    pub fn new(name: &'static str) -> Span<'a> {
+        Span {
+            name,
+            fields: vec![],
+            timing: None,
+            calls: 0,
+        }
+    }
+
+    /// adds a field describing this span
+    pub fn field(mut self, key: &'static str, value: impl ToString) -> Span<'a> {
+        self.fields.push((key, value.to_string()));
+        self
+    }
+
+    /// records the elapsed time of this span, and the number of queries it processed,
+    /// to a stage timing when the span closes. has no effect if the timing is None.
+    pub fn timing(mut self, timing: Option<&'a StageTiming>, calls: u64) -> Span<'a> {
+        self.timing = timing;
+        self.calls = calls;
+        self
+    }
+
+    /// opens this span. the span closes when the returned guard is dropped.
+    pub fn enter(self) -> SpanGuard<'a> {
+        let id = NEXT_SPAN_ID.fetch_add(1, Ordering::Relaxed);
+        let parent = SPAN_STACK.with(|stack| {
+            let mut stack = stack.borrow_mut();
+            let parent = stack.last().copied();
+            stack.push(id);
+            parent
+        });
+        let record = SpanRecord {
+            id,
+            parent,
+            name: self.name,
+            fields: self.fields,
+        };
+        match SUBSCRIBER.get() {
+            Some(subscriber) => subscriber.on_enter(&record),
+            None => LogSpanSubscriber.on_enter(&record),
+        }
+        SpanGuard {
+            record,
+            start: Instant::now(),
+            timing: self.timing,
+            calls: self.calls,
+        }
+    }
+}
+
+/// an open span. closes the span when dropped.
+pub struct SpanGuard<'a> {
+    record: SpanRecord,
+    start: Instant,
+    timing: Option<&'a StageTiming>,
+    calls: u64,
+}
+
+impl SpanGuard<'_> {
+    pub fn id(&self) -> u64 {
+        self.record.id
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// sets the number of queries recorded to the stage timing, for spans that only
+    /// learn it once their work is done
+    pub fn set_calls(&mut self, calls: u64) {
+        self.calls = calls;
+    }
+
+    /// closes this span, returning its elapsed time
+    pub fn exit(self) -> Duration {
+        self.elapsed()
+    }
+}
+
+impl Drop for SpanGuard<'_> {
+    fn drop(&mut self) {
+        let id = self.record.id;
+        SPAN_STACK.with(|stack| {
+            let mut stack = stack.borrow_mut();
+            if let Some(pos) = stack.iter().rposition(|s| *s == id) {
+                stack.remove(pos);
+            }
+        });
+        let elapsed = self.start.elapsed();
+        if let Some(timing) = self.timing {
+            timing.record(elapsed, self.calls);
+        }
+        match SUBSCRIBER.get() {
+            Some(subscriber) => subscriber.on_exit(&self.record, elapsed),
+            None => LogSpanSubscriber.on_exit(&self.record, elapsed),
+        }
+    }
+}
+
+/// the default subscriber, which writes span events to the `log` facade at debug level.
+pub struct LogSpanSubscriber;
+
+impl SpanSubscriber for LogSpanSubscriber {
+    fn on_enter(&self, span: &SpanRecord) {
+        log::debug!(target: SPAN_LOG_TARGET, "enter {}", format_span(span));
+    }
+
+    fn on_exit(&self, span: &SpanRecord, elapsed: Duration) {
+        log::debug!(
+            target: SPAN_LOG_TARGET,
+            "exit {} elapsed={}",
+            format_span(span),
+            elapsed.hhmmss()
+        );
+    }
+}
+
+fn format_span(span: &SpanRecord) -> String {
+    let fields = span
+        .fields
+        .iter()
+        .map(|(k, v)| format!(" {k}={v}"))
+        .collect::<String>();
+    let parent = span
+        .parent
+        .map(|p| format!(" parent={p}"))
+        .unwrap_or_default();
+    format!("{}{{id={}{parent}{fields}}}", span.name, span.id)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_nested_spans_record_parent() {
+        let outer = Span::new("query").field("index", 0).enter();
+        let inner = Span::new("search").enter();
+        assert_eq!(inner.record.parent, Some(outer.id()));
+        drop(inner);
+        let sibling = Span::new("output_plugin").field("name", "summary").enter();
+        assert_eq!(sibling.record.parent, Some(outer.id()));
+        assert_eq!(
+            format_span(&sibling.record),
+            format!(
+                "output_plugin{{id={} parent={} name=summary}}",
+                sibling.id(),
+                outer.id()
+            )
+        );
+        drop(sibling);
+        drop(outer);
+        let root = Span::new("load").enter();
+        assert_eq!(root.record.parent, None);
+    }
+
+    #[test]
+    fn test_span_on_other_thread_records_parent() {
+        let load = Span::new("load").enter();
+        let parent = current_span_id();
+        assert_eq!(parent, Some(load.id()));
+        let child_parent = std::thread::spawn(move || {
+            with_parent(parent, || Span::new("build").enter().record.parent)
+        })
+        .join()
+        .unwrap();
+        assert_eq!(child_parent, Some(load.id()));
+        // the handed off parent is only in effect within with_parent
+        let orphan_parent = std::thread::spawn(|| Span::new("build").enter().record.parent)
+            .join()
+            .unwrap();
+        assert_eq!(orphan_parent, None);
+    }
+
+    #[test]
+    fn test_record_timing() {
+        let timing = StageTiming::new("test");
+        timing.record(Duration::from_millis(250), 1);
+        timing.record(Duration::from_millis(750), 2);
+        assert_eq!(timing.duration(), Duration::from_secs(1));
+        assert_eq!(timing.calls(), 3);
+        assert_eq!(timing.to_json()["duration_secs"], json![1.0]);
+    }
+
+    #[test]
+    fn test_span_records_timing_on_exit() {
+        let timing = StageTiming::new("search");
+        let span = Span::new("search").timing(Some(&timing), 1).enter();
+        std::thread::sleep(Duration::from_millis(5));
+        let elapsed = span.exit();
+        let mut group = Span::new("search").timing(Some(&timing), 0).enter();
+        group.set_calls(3);
+        drop(group);
+        assert_eq!(timing.calls(), 4);
+        assert!(timing.duration() >= elapsed);
+        assert!(timing.duration() >= Duration::from_millis(5));
+    }
+}
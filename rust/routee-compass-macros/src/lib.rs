@@ -5,7 +5,7 @@ extern crate syn;
 
 use proc_macro::TokenStream;
 use proc_macro_error::proc_macro_error;
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::{parse_macro_input, DeriveInput};
 
 /// A procedural macro that generates python bindings for a struct that implements the `CompassAppBindings` trait.
@@ -16,6 +16,7 @@ pub fn pybindings(_attr: TokenStream, item: TokenStream) -> TokenStream {
     let input = parse_macro_input!(item as DeriveInput);
 
     let name = &input.ident;
+    let iterator_name = format_ident!("{}ResponseIterator", name);
 
     let expanded = quote! {
         use pyo3::{exceptions::PyException, prelude::*, types::PyType, PyResult};
@@ -73,6 +74,11 @@ pub fn pybindings(_attr: TokenStream, item: TokenStream) -> TokenStream {
                 )
             }
 
+            pub fn _describe_models(&self) -> PyResult<String> {
+                CompassAppBindings::describe_models(self)
+                    .map_err(|e| PyException::new_err(format!("Error while describing models: {}", e)))
+            }
+
             #[pyo3(signature = (queries, config=None))]
             pub fn _run_queries(
                 &self,
@@ -82,6 +88,18 @@ pub fn pybindings(_attr: TokenStream, item: TokenStream) -> TokenStream {
                 CompassAppBindings::run_queries(self, queries, config)
                     .map_err(|e| PyException::new_err(format!("Error while running queries: {}", e)))
             }
+            #[pyo3(signature = (queries, config=None, chunk_size=1000))]
+            pub fn _run_queries_streaming(
+                slf: Py<Self>,
+                py: Python<'_>,
+                queries: Vec<String>,
+                config: Option<String>,
+                chunk_size: usize,
+            ) -> PyResult<#iterator_name> {
+                let stream = CompassAppBindings::run_queries_streaming(&*slf.borrow(py), queries, config, chunk_size)
+                    .map_err(|e| PyException::new_err(format!("Error while running queries: {}", e)))?;
+                Ok(#iterator_name { app: slf, stream })
+            }
             #[pyo3(signature = (queries, config=None))]
             pub fn _map_match(
                 &self,
@@ -100,6 +118,35 @@ pub fn pybindings(_attr: TokenStream, item: TokenStream) -> TokenStream {
                 CompassAppBindings::run_calculate_path(self, queries, config)
                     .map_err(|e| PyException::new_err(format!("Error while running calculate path: {}", e)))
             }
+            #[pyo3(signature = (requests, config=None))]
+            pub fn _run_replan(
+                &self,
+                requests: Vec<String>,
+                config: Option<String>,
+            ) -> PyResult<Vec<String>> {
+                CompassAppBindings::run_replan(self, requests, config)
+                    .map_err(|e| PyException::new_err(format!("Error while running replan: {}", e)))
+            }
+        }
+
+        /// iterator over the json string responses of a streaming run, see `_run_queries_streaming`.
+        #[pyclass]
+        pub struct #iterator_name {
+            app: Py<#name>,
+            stream: routee_compass::app::compass::QueryStream,
+        }
+
+        #[pymethods]
+        impl #iterator_name {
+            fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+                slf
+            }
+            fn __next__(&mut self, py: Python<'_>) -> PyResult<Option<String>> {
+                let app = self.app.borrow(py);
+                CompassAppBindings::next_streaming_response(&*app, &mut self.stream)
+                    .transpose()
+                    .map_err(|e| PyException::new_err(format!("Error while running queries: {}", e)))
+            }
         }
     };
 
@@ -3,11 +3,14 @@ use super::{
     prediction::{PredictionModelConfig, PredictionModelRecord},
 };
 use crate::model::fieldname;
+use ordered_float::OrderedFloat;
 use routee_compass_core::{
     algorithm::search::SearchTree,
     model::{
         network::{Edge, Vertex},
-        state::{InputFeature, StateModel, StateVariable, StateVariableConfig},
+        state::{
+            CustomVariableConfig, InputFeature, StateModel, StateVariable, StateVariableConfig,
+        },
         traversal::{TraversalModel, TraversalModelError, TraversalModelService},
         unit::{EnergyRateUnit, EnergyUnit, RatioUnit},
     },
@@ -16,7 +19,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::sync::Arc;
 use uom::{
-    si::f64::{Energy, Ratio},
+    si::f64::{Energy, Power, Ratio, Time},
     ConstZero,
 };
 
@@ -28,6 +31,65 @@ pub struct BevEnergyModelConfig {
     pub battery_capacity: f64,
     pub battery_capacity_unit: EnergyUnit,
     pub include_trip_energy: Option<bool>,
+    /// if true, adds a `trip_battery_throughput` accumulator of the energy cycled through
+    /// the battery. defaults to false.
+    pub include_battery_throughput: Option<bool>,
+    /// if provided, adds a `trip_battery_degradation_cost` accumulator charging this cost
+    /// per kWh of battery throughput, which can be used as a cost feature.
+    pub battery_degradation_cost_per_kwh: Option<f64>,
+    /// default HVAC and auxiliary load in kW, overridden by the query `auxiliary_load_kw` key.
+    pub auxiliary_load_kw: Option<f64>,
+    /// default cap on regenerative braking power in kW, overridden by the query
+    /// `regen_power_cap_kw` key.
+    pub regen_power_cap_kw: Option<f64>,
+}
+
+/// adjustments to the predicted energy of an edge traversal from the power drawn by
+/// auxiliary loads and the power limit of regenerative braking. both scale with the
+/// time spent on the edge, so applying either requires an `edge_time` state variable.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BevPowerAdjustments {
+    /// constant power drawn by HVAC and other auxiliary loads
+    pub auxiliary_load: Option<Power>,
+    /// maximum power recovered by regenerative braking
+    pub regen_power_cap: Option<Power>,
+}
+
+impl BevPowerAdjustments {
+    pub const AUXILIARY_LOAD_KEY: &'static str = "auxiliary_load_kw";
+    pub const REGEN_POWER_CAP_KEY: &'static str = "regen_power_cap_kw";
+
+    pub fn is_empty(&self) -> bool {
+        self.auxiliary_load.is_none() && self.regen_power_cap.is_none()
+    }
+
+    /// overrides these adjustments with any provided by the query
+    pub fn with_query(self, query: &serde_json::Value) -> Result<Self, TraversalModelError> {
+        let auxiliary_load = energy_model_ops::get_query_power_kw(query, Self::AUXILIARY_LOAD_KEY)?;
+        let regen_power_cap =
+            energy_model_ops::get_query_power_kw(query, Self::REGEN_POWER_CAP_KEY)?;
+        Ok(BevPowerAdjustments {
+            auxiliary_load: auxiliary_load.or(self.auxiliary_load),
+            regen_power_cap: regen_power_cap.or(self.regen_power_cap),
+        })
+    }
+
+    /// limits the energy recovered by regenerative braking and adds the auxiliary load
+    /// for an edge traversed over some time.
+    pub fn adjust(&self, energy: Energy, time: Time) -> Energy {
+        let mut adjusted = energy;
+        if let Some(cap) = self.regen_power_cap {
+            let max_regen: Energy = cap * time;
+            if adjusted < -max_regen {
+                adjusted = -max_regen;
+            }
+        }
+        if let Some(load) = self.auxiliary_load {
+            let auxiliary_energy: Energy = load * time;
+            adjusted += auxiliary_energy;
+        }
+        adjusted
+    }
 }
 
 // CONSTANT: Energy (kWh) required to lift 1 kg by 1 meter.
@@ -41,6 +103,9 @@ pub struct BevEnergyModel {
     battery_capacity: Energy,
     starting_soc: Ratio,
     include_trip_energy: bool,
+    include_battery_throughput: bool,
+    battery_degradation_cost_per_kwh: Option<f64>,
+    power_adjustments: BevPowerAdjustments,
 }
 
 impl BevEnergyModel {
@@ -62,8 +127,29 @@ impl BevEnergyModel {
             battery_capacity,
             starting_soc,
             include_trip_energy,
+            include_battery_throughput: false,
+            battery_degradation_cost_per_kwh: None,
+            power_adjustments: BevPowerAdjustments::default(),
         })
     }
+
+    /// adds battery wear state variables: the energy cycled through the battery and,
+    /// if a cost per kWh is provided, the cost of battery degradation.
+    pub fn with_battery_wear(
+        mut self,
+        include_battery_throughput: bool,
+        battery_degradation_cost_per_kwh: Option<f64>,
+    ) -> Self {
+        self.include_battery_throughput = include_battery_throughput;
+        self.battery_degradation_cost_per_kwh = battery_degradation_cost_per_kwh;
+        self
+    }
+
+    /// sets the default auxiliary load and regenerative braking power cap
+    pub fn with_power_adjustments(mut self, power_adjustments: BevPowerAdjustments) -> Self {
+        self.power_adjustments = power_adjustments;
+        self
+    }
 }
 
 impl TraversalModelService for BevEnergyModel {
@@ -71,19 +157,41 @@ impl TraversalModelService for BevEnergyModel {
         &self,
         query: &serde_json::Value,
     ) -> Result<Arc<dyn TraversalModel>, TraversalModelError> {
+        let power_adjustments = self.power_adjustments.with_query(query)?;
         match energy_model_ops::get_query_start_energy(query, self.battery_capacity)? {
-            None => Ok(Arc::new(self.clone())),
+            None => Ok(Arc::new(
+                self.clone().with_power_adjustments(power_adjustments),
+            )),
             Some(starting_energy) => {
                 let updated = Self::new(
                     self.prediction_model_record.clone(),
                     self.battery_capacity,
                     starting_energy,
                     self.include_trip_energy,
-                )?;
+                )?
+                .with_battery_wear(
+                    self.include_battery_throughput,
+                    self.battery_degradation_cost_per_kwh,
+                )
+                .with_power_adjustments(power_adjustments);
                 Ok(Arc::new(updated))
             }
         }
     }
+
+    fn describe(&self) -> Option<serde_json::Value> {
+        let power_schema = serde_json::json!({ "type": "number", "minimum": 0.0 });
+        Some(serde_json::json!({
+            "type": "bev",
+            "prediction_model": self.prediction_model_record.describe(),
+            "battery_capacity_kwh": self.battery_capacity.get::<uom::si::energy::kilowatt_hour>(),
+            "query_parameters": {
+                "starting_soc_percent": energy_model_ops::starting_soc_schema(),
+                BevPowerAdjustments::AUXILIARY_LOAD_KEY: power_schema,
+                BevPowerAdjustments::REGEN_POWER_CAP_KEY: power_schema,
+            },
+        }))
+    }
 }
 
 impl TryFrom<&Value> for BevEnergyModel {
@@ -104,7 +212,19 @@ impl TryFrom<&Value> for BevEnergyModel {
             battery_capacity,
             battery_capacity,
             config.include_trip_energy.unwrap_or(true),
-        )?;
+        )?
+        .with_battery_wear(
+            config.include_battery_throughput.unwrap_or_default(),
+            config.battery_degradation_cost_per_kwh,
+        )
+        .with_power_adjustments(BevPowerAdjustments {
+            auxiliary_load: config
+                .auxiliary_load_kw
+                .map(Power::new::<uom::si::power::kilowatt>),
+            regen_power_cap: config
+                .regen_power_cap_kw
+                .map(Power::new::<uom::si::power::kilowatt>),
+        });
         Ok(bev)
     }
 }
@@ -129,6 +249,12 @@ impl TraversalModel for BevEnergyModel {
             //     unit: None,
             // },
         ];
+        if !self.power_adjustments.is_empty() {
+            input_features.push(InputFeature::Time {
+                name: String::from(fieldname::EDGE_TIME),
+                unit: None,
+            });
+        }
         input_features.extend(self.prediction_model_record.input_features.clone());
         input_features
     }
@@ -182,6 +308,32 @@ impl TraversalModel for BevEnergyModel {
                 },
             ));
         }
+        if self.include_battery_throughput {
+            features.push((
+                String::from(fieldname::TRIP_BATTERY_THROUGHPUT),
+                StateVariableConfig::Energy {
+                    initial: Energy::ZERO,
+                    accumulator: true,
+                    output_unit: Some(
+                        self.prediction_model_record
+                            .energy_rate_unit
+                            .associated_energy_unit(),
+                    ),
+                },
+            ));
+        }
+        if self.battery_degradation_cost_per_kwh.is_some() {
+            features.push((
+                String::from(fieldname::TRIP_BATTERY_DEGRADATION_COST),
+                StateVariableConfig::Custom {
+                    custom_type: String::from("currency"),
+                    value: CustomVariableConfig::FloatingPoint {
+                        initial: OrderedFloat(0.0),
+                    },
+                    accumulator: true,
+                },
+            ));
+        }
         features
     }
 
@@ -198,7 +350,9 @@ impl TraversalModel for BevEnergyModel {
             self.prediction_model_record.clone(),
             self.battery_capacity,
             self.include_trip_energy,
-        )
+            &self.power_adjustments,
+        )?;
+        self.add_battery_wear(state, state_model)
     }
 
     fn estimate_traversal(
@@ -214,7 +368,38 @@ impl TraversalModel for BevEnergyModel {
             self.prediction_model_record.clone(),
             self.battery_capacity,
             self.include_trip_energy,
-        )
+        )?;
+        self.add_battery_wear(state, state_model)
+    }
+}
+
+impl BevEnergyModel {
+    /// accumulates the battery throughput and degradation cost of the edge energy, which
+    /// counts both discharge and regenerative charging as energy cycled through the battery.
+    fn add_battery_wear(
+        &self,
+        state: &mut [StateVariable],
+        state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        if !self.include_battery_throughput && self.battery_degradation_cost_per_kwh.is_none() {
+            return Ok(());
+        }
+        let energy = state_model.get_energy(state, fieldname::EDGE_ENERGY_ELECTRIC)?;
+        let throughput = energy.abs();
+        if self.include_battery_throughput {
+            state_model.add_energy(state, fieldname::TRIP_BATTERY_THROUGHPUT, &throughput)?;
+        }
+        if let Some(cost_per_kwh) = self.battery_degradation_cost_per_kwh {
+            let kwh = throughput.get::<uom::si::energy::kilowatt_hour>();
+            let trip_cost =
+                state_model.get_custom_f64(state, fieldname::TRIP_BATTERY_DEGRADATION_COST)?;
+            state_model.set_custom_f64(
+                state,
+                fieldname::TRIP_BATTERY_DEGRADATION_COST,
+                &(trip_cost + kwh * cost_per_kwh),
+            )?;
+        }
+        Ok(())
     }
 }
 
@@ -282,12 +467,19 @@ fn bev_traversal(
     record: Arc<PredictionModelRecord>,
     battery_capacity: Energy,
     include_trip_energy: bool,
+    power_adjustments: &BevPowerAdjustments,
 ) -> Result<(), TraversalModelError> {
     // gather state variables
     let start_soc = state_model.get_ratio(state, fieldname::TRIP_SOC)?;
 
     // generate energy for link traversal
-    let energy = record.predict(state, state_model)?;
+    let predicted_energy = record.predict(state, state_model)?;
+    let energy = if power_adjustments.is_empty() {
+        predicted_energy
+    } else {
+        let time = state_model.get_time(state, fieldname::EDGE_TIME)?;
+        power_adjustments.adjust(predicted_energy, time)
+    };
 
     if include_trip_energy {
         state_model.add_energy(state, fieldname::TRIP_ENERGY_ELECTRIC, &energy)?;
@@ -326,7 +518,15 @@ mod tests {
         let grade = Ratio::new::<uom::si::ratio::percent>(0.0);
         let mut state = state_vector(&state_model, distance, speed, grade);
 
-        bev_traversal(&mut state, &state_model, record.clone(), bat_cap, true).unwrap();
+        bev_traversal(
+            &mut state,
+            &state_model,
+            record.clone(),
+            bat_cap,
+            true,
+            &BevPowerAdjustments::default(),
+        )
+        .unwrap();
 
         let elec = state_model
             .get_energy(&state, fieldname::TRIP_ENERGY_ELECTRIC)
@@ -357,7 +557,15 @@ mod tests {
         let grade = Ratio::new::<uom::si::ratio::percent>(-5.0);
         let mut state = state_vector(&state_model, distance, speed, grade);
 
-        bev_traversal(&mut state, &state_model, record.clone(), bat_cap, true).unwrap();
+        bev_traversal(
+            &mut state,
+            &state_model,
+            record.clone(),
+            bat_cap,
+            true,
+            &BevPowerAdjustments::default(),
+        )
+        .unwrap();
 
         let elec = state_model
             .get_energy(&state, fieldname::TRIP_ENERGY_ELECTRIC)
@@ -388,7 +596,15 @@ mod tests {
         let grade = Ratio::new::<uom::si::ratio::percent>(-5.0);
         let mut state = state_vector(&state_model, distance, speed, grade);
 
-        bev_traversal(&mut state, &state_model, record.clone(), bat_cap, true).unwrap();
+        bev_traversal(
+            &mut state,
+            &state_model,
+            record.clone(),
+            bat_cap,
+            true,
+            &BevPowerAdjustments::default(),
+        )
+        .unwrap();
 
         let battery_percent_soc = state_model.get_ratio(&state, fieldname::TRIP_SOC).unwrap();
         assert!(battery_percent_soc <= Ratio::new::<uom::si::ratio::percent>(100.0));
@@ -408,12 +624,87 @@ mod tests {
         let grade = Ratio::new::<uom::si::ratio::percent>(5.0);
         let mut state = state_vector(&state_model, distance, speed, grade);
 
-        bev_traversal(&mut state, &state_model, record.clone(), bat_cap, true).unwrap();
+        bev_traversal(
+            &mut state,
+            &state_model,
+            record.clone(),
+            bat_cap,
+            true,
+            &BevPowerAdjustments::default(),
+        )
+        .unwrap();
 
         let battery_percent_soc = state_model.get_ratio(&state, fieldname::TRIP_SOC).unwrap();
         assert!(battery_percent_soc >= Ratio::ZERO);
     }
 
+    #[test]
+    fn test_bev_battery_wear() {
+        let bat_cap = Energy::new::<uom::si::energy::kilowatt_hour>(60.0);
+        let record = mock_prediction_model();
+        let bev = BevEnergyModel::new(record.clone(), bat_cap, bat_cap * 0.5, true)
+            .expect("test invariant failed")
+            .with_battery_wear(true, Some(0.1));
+        let model = TestTraversalModel::new(Arc::new(bev.clone())).expect("test invariant failed");
+        let state_model = state_model(model);
+
+        // regen braking still cycles energy through the battery
+        let distance = Length::new::<uom::si::length::mile>(10.0);
+        let speed = Velocity::new::<uom::si::velocity::mile_per_hour>(55.0);
+        let grade = Ratio::new::<uom::si::ratio::percent>(-5.0);
+        let mut state = state_vector(&state_model, distance, speed, grade);
+        bev_traversal(
+            &mut state,
+            &state_model,
+            record,
+            bat_cap,
+            true,
+            &BevPowerAdjustments::default(),
+        )
+        .unwrap();
+        bev.add_battery_wear(&mut state, &state_model).unwrap();
+
+        let energy = state_model
+            .get_energy(&state, fieldname::EDGE_ENERGY_ELECTRIC)
+            .expect("test invariant failed");
+        let throughput = state_model
+            .get_energy(&state, fieldname::TRIP_BATTERY_THROUGHPUT)
+            .expect("test invariant failed");
+        assert!(
+            energy < Energy::ZERO,
+            "edge energy {energy:?} should be regen"
+        );
+        assert_eq!(throughput, energy.abs());
+        let cost = state_model
+            .get_custom_f64(&state, fieldname::TRIP_BATTERY_DEGRADATION_COST)
+            .expect("test invariant failed");
+        let expected = throughput.get::<uom::si::energy::kilowatt_hour>() * 0.1;
+        assert!((cost - expected).abs() < 1e-9, "{cost} != {expected}");
+    }
+
+    #[test]
+    fn test_bev_power_adjustments() {
+        let kw = Power::new::<uom::si::power::kilowatt>;
+        let kwh = Energy::new::<uom::si::energy::kilowatt_hour>;
+        let half_hour = Time::new::<uom::si::time::hour>(0.5);
+        let defaults = BevPowerAdjustments {
+            auxiliary_load: Some(kw(2.0)),
+            regen_power_cap: None,
+        };
+        let query = serde_json::json!({ "regen_power_cap_kw": 10.0 });
+        let adjustments = defaults.with_query(&query).expect("test invariant failed");
+
+        // regen of 8 kWh over half an hour is capped at 5 kWh, then 1 kWh of aux load is added
+        let adjusted = adjustments.adjust(kwh(-8.0), half_hour);
+        assert!((adjusted - kwh(-4.0)).abs() < kwh(1e-9), "{adjusted:?}");
+        // consumption is not capped
+        let adjusted = adjustments.adjust(kwh(8.0), half_hour);
+        assert!((adjusted - kwh(9.0)).abs() < kwh(1e-9), "{adjusted:?}");
+
+        let invalid = serde_json::json!({ "auxiliary_load_kw": -1.0 });
+        assert!(defaults.with_query(&invalid).is_err());
+    }
+
     fn mock_prediction_model() -> Arc<PredictionModelRecord> {
         // let bat_cap = *battery_capacity.0;
         // let bat_unit = *battery_capacity.1;
@@ -1,3 +1,4 @@
+use routee_compass_core::algorithm::search::{Direction, SearchTree};
 use routee_compass_core::model::{
     constraint::{ConstraintModel, ConstraintModelError},
     network::Edge,
@@ -17,6 +18,8 @@ impl ConstraintModel for BatteryFilter {
         &self,
         _edge: &Edge,
         _previous_edge: Option<&Edge>,
+        _tree: &SearchTree,
+        _direction: &Direction,
         state: &[StateVariable],
         state_model: &StateModel,
     ) -> Result<bool, ConstraintModelError> {
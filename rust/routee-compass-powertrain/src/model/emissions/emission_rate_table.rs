@@ -0,0 +1,321 @@
+use itertools::Itertools;
+use kdam::Bar;
+use ndarray::{Array1, Array2};
+use ninterp::prelude::*;
+use ordered_float::OrderedFloat;
+use routee_compass_core::{model::traversal::TraversalModelError, util::fs::read_utils};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// emission rates by powertrain type and pollutant, linearly interpolated over speed
+/// and (optionally) grade. queries outside of the table bounds are clamped to the bounds.
+pub struct EmissionRateTable {
+    /// pollutant names in the order of the rates returned by [`EmissionRateTable::rates`]
+    pub pollutants: Vec<String>,
+    /// true if rates vary by grade
+    pub has_grade: bool,
+    rates: HashMap<String, Vec<RateInterpolator>>,
+}
+
+enum RateInterpolator {
+    Speed(Interp1DOwned<f64, strategy::Linear>),
+    SpeedGrade(Interp2DOwned<f64, strategy::Linear>),
+}
+
+impl EmissionRateTable {
+    pub const POWERTRAIN_TYPE_COLUMN: &'static str = "powertrain_type";
+    pub const SPEED_COLUMN: &'static str = "speed";
+    pub const GRADE_COLUMN: &'static str = "grade";
+
+    /// reads a table of emission rates from a CSV file.
+    pub fn from_file<P: AsRef<Path>>(
+        path: &P,
+        pollutants: Option<Vec<String>>,
+    ) -> Result<EmissionRateTable, TraversalModelError> {
+        let rows: Box<[BTreeMap<String, String>]> = read_utils::from_csv(
+            path,
+            true,
+            Some(Bar::builder().desc("emission rates")),
+            None,
+        )
+        .map_err(|e| {
+            TraversalModelError::BuildError(format!(
+                "failure reading emission rates file {}: {e}",
+                path.as_ref().to_string_lossy()
+            ))
+        })?;
+        EmissionRateTable::new(&rows, pollutants)
+    }
+
+    /// builds a table of emission rates from rows of column name to value.
+    pub fn new(
+        rows: &[BTreeMap<String, String>],
+        pollutants: Option<Vec<String>>,
+    ) -> Result<EmissionRateTable, TraversalModelError> {
+        let first = rows.first().ok_or_else(|| {
+            TraversalModelError::BuildError(String::from("emission rates table is empty"))
+        })?;
+        let has_grade = first.contains_key(Self::GRADE_COLUMN);
+        let pollutants = match pollutants {
+            Some(p) => p,
+            None => first
+                .keys()
+                .filter(|k| {
+                    ![
+                        Self::POWERTRAIN_TYPE_COLUMN,
+                        Self::SPEED_COLUMN,
+                        Self::GRADE_COLUMN,
+                    ]
+                    .contains(&k.as_str())
+                })
+                .cloned()
+                .collect(),
+        };
+        if pollutants.is_empty() {
+            return Err(TraversalModelError::BuildError(String::from(
+                "emission rates table has no pollutant columns",
+            )));
+        }
+
+        let mut rates = HashMap::new();
+        let by_powertrain = rows
+            .iter()
+            .map(|row| Ok((get(row, Self::POWERTRAIN_TYPE_COLUMN)?.to_string(), row)))
+            .collect::<Result<Vec<_>, TraversalModelError>>()?
+            .into_iter()
+            .into_group_map();
+        for (powertrain_type, rows) in by_powertrain.into_iter() {
+            let interpolators = pollutants
+                .iter()
+                .map(|pollutant| build_interpolator(&rows, pollutant, has_grade))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| {
+                    TraversalModelError::BuildError(format!(
+                        "failure building emission rates for powertrain type '{powertrain_type}': {e}"
+                    ))
+                })?;
+            rates.insert(powertrain_type, interpolators);
+        }
+
+        Ok(EmissionRateTable {
+            pollutants,
+            has_grade,
+            rates,
+        })
+    }
+
+    /// powertrain types found in the table
+    pub fn powertrain_types(&self) -> Vec<&String> {
+        self.rates.keys().sorted().collect()
+    }
+
+    pub fn contains_powertrain_type(&self, powertrain_type: &str) -> bool {
+        self.rates.contains_key(powertrain_type)
+    }
+
+    /// emission rate of each pollutant, in the table units, for a powertrain type at
+    /// some speed and grade. grade is ignored if the table does not vary by grade.
+    pub fn rates(
+        &self,
+        powertrain_type: &str,
+        speed: f64,
+        grade: f64,
+    ) -> Result<Vec<f64>, TraversalModelError> {
+        let interpolators = self.rates.get(powertrain_type).ok_or_else(|| {
+            TraversalModelError::TraversalModelFailure(format!(
+                "no emission rates for powertrain type '{powertrain_type}'"
+            ))
+        })?;
+        interpolators
+            .iter()
+            .map(|interp| {
+                let result = match interp {
+                    RateInterpolator::Speed(i) => i.interpolate(&[speed]),
+                    RateInterpolator::SpeedGrade(i) => i.interpolate(&[speed, grade]),
+                };
+                result.map_err(|e| {
+                    TraversalModelError::TraversalModelFailure(format!(
+                        "failure interpolating emission rate: {e}"
+                    ))
+                })
+            })
+            .collect()
+    }
+}
+
+fn get<'a>(
+    row: &'a BTreeMap<String, String>,
+    column: &str,
+) -> Result<&'a str, TraversalModelError> {
+    row.get(column)
+        .map(|v| v.as_str())
+        .ok_or_else(|| TraversalModelError::BuildError(format!("missing column '{column}'")))
+}
+
+fn get_f64(row: &BTreeMap<String, String>, column: &str) -> Result<f64, TraversalModelError> {
+    let value = get(row, column)?;
+    value.trim().parse::<f64>().map_err(|e| {
+        TraversalModelError::BuildError(format!(
+            "invalid value '{value}' in column '{column}': {e}"
+        ))
+    })
+}
+
+fn build_interpolator(
+    rows: &[&BTreeMap<String, String>],
+    pollutant: &str,
+    has_grade: bool,
+) -> Result<RateInterpolator, TraversalModelError> {
+    let mut values: BTreeMap<(OrderedFloat<f64>, OrderedFloat<f64>), f64> = BTreeMap::new();
+    for row in rows.iter() {
+        let speed = get_f64(row, EmissionRateTable::SPEED_COLUMN)?;
+        let grade = if has_grade {
+            get_f64(row, EmissionRateTable::GRADE_COLUMN)?
+        } else {
+            0.0
+        };
+        let rate = get_f64(row, pollutant)?;
+        values.insert((OrderedFloat(speed), OrderedFloat(grade)), rate);
+    }
+    let speeds = values.keys().map(|(s, _)| *s).unique().collect_vec();
+    let grades = values
+        .keys()
+        .map(|(_, g)| *g)
+        .sorted()
+        .unique()
+        .collect_vec();
+    if values.len() != speeds.len() * grades.len() {
+        return Err(TraversalModelError::BuildError(format!(
+            "'{pollutant}' rates must cover every combination of speed and grade"
+        )));
+    }
+    let speed_grid = Array1::from_iter(speeds.iter().map(|s| s.0));
+    let validation_error = |e: ninterp::error::ValidateError| {
+        TraversalModelError::BuildError(format!("invalid '{pollutant}' rates: {e}"))
+    };
+    if has_grade {
+        let grade_grid = Array1::from_iter(grades.iter().map(|g| g.0));
+        // BTreeMap iteration is sorted by speed then grade, which is row-major order
+        let rates =
+            Array2::from_shape_vec((speeds.len(), grades.len()), values.into_values().collect())
+                .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+        let interp = Interp2D::new(
+            speed_grid,
+            grade_grid,
+            rates,
+            strategy::Linear,
+            Extrapolate::Clamp,
+        )
+        .map_err(validation_error)?;
+        Ok(RateInterpolator::SpeedGrade(interp))
+    } else {
+        let rates = Array1::from_iter(values.into_values());
+        let interp = Interp1D::new(speed_grid, rates, strategy::Linear, Extrapolate::Clamp)
+            .map_err(validation_error)?;
+        Ok(RateInterpolator::Speed(interp))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn row(values: &[(&str, &str)]) -> BTreeMap<String, String> {
+        values
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_rates_by_speed_and_grade() {
+        let rows = [
+            row(&[
+                ("powertrain_type", "ice"),
+                ("speed", "10"),
+                ("grade", "0"),
+                ("co2", "400"),
+                ("nox", "1.0"),
+            ]),
+            row(&[
+                ("powertrain_type", "ice"),
+                ("speed", "10"),
+                ("grade", "5"),
+                ("co2", "600"),
+                ("nox", "2.0"),
+            ]),
+            row(&[
+                ("powertrain_type", "ice"),
+                ("speed", "30"),
+                ("grade", "0"),
+                ("co2", "300"),
+                ("nox", "0.5"),
+            ]),
+            row(&[
+                ("powertrain_type", "ice"),
+                ("speed", "30"),
+                ("grade", "5"),
+                ("co2", "500"),
+                ("nox", "1.5"),
+            ]),
+            row(&[
+                ("powertrain_type", "bev"),
+                ("speed", "10"),
+                ("grade", "0"),
+                ("co2", "0"),
+                ("nox", "0"),
+            ]),
+            row(&[
+                ("powertrain_type", "bev"),
+                ("speed", "10"),
+                ("grade", "5"),
+                ("co2", "0"),
+                ("nox", "0"),
+            ]),
+            row(&[
+                ("powertrain_type", "bev"),
+                ("speed", "30"),
+                ("grade", "0"),
+                ("co2", "0"),
+                ("nox", "0"),
+            ]),
+            row(&[
+                ("powertrain_type", "bev"),
+                ("speed", "30"),
+                ("grade", "5"),
+                ("co2", "0"),
+                ("nox", "0"),
+            ]),
+        ];
+        let table = EmissionRateTable::new(&rows, None).expect("failed to build table");
+        assert_eq!(table.pollutants, vec!["co2", "nox"]);
+        assert_eq!(table.powertrain_types(), vec!["bev", "ice"]);
+
+        let rates = table.rates("ice", 20.0, 2.5).expect("rates failed");
+        assert!((rates[0] - 450.0).abs() < 1e-9);
+        assert!((rates[1] - 1.25).abs() < 1e-9);
+        // clamped to the table bounds
+        let clamped = table.rates("ice", 60.0, 0.0).expect("rates failed");
+        assert!((clamped[0] - 300.0).abs() < 1e-9);
+        assert!(table.rates("phev", 20.0, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_incomplete_grid() {
+        let rows = [
+            row(&[
+                ("powertrain_type", "ice"),
+                ("speed", "10"),
+                ("grade", "0"),
+                ("co2", "400"),
+            ]),
+            row(&[
+                ("powertrain_type", "ice"),
+                ("speed", "30"),
+                ("grade", "5"),
+                ("co2", "500"),
+            ]),
+        ];
+        assert!(EmissionRateTable::new(&rows, None).is_err());
+    }
+}
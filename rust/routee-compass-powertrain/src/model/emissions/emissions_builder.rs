@@ -0,0 +1,26 @@
+use super::{EmissionRateTable, EmissionsModelConfig, EmissionsModelService};
+use routee_compass_core::model::traversal::{
+    TraversalModelBuilder, TraversalModelError, TraversalModelService,
+};
+use std::sync::Arc;
+
+pub struct EmissionsModelBuilder {}
+
+impl TraversalModelBuilder for EmissionsModelBuilder {
+    fn build(
+        &self,
+        params: &serde_json::Value,
+    ) -> Result<Arc<dyn TraversalModelService>, TraversalModelError> {
+        let config: EmissionsModelConfig = serde_json::from_value(params.clone()).map_err(|e| {
+            TraversalModelError::BuildError(format!(
+                "failure reading emissions traversal model configuration: {e}"
+            ))
+        })?;
+        let table = EmissionRateTable::from_file(
+            &config.emission_rates_input_file,
+            config.pollutants.clone(),
+        )?;
+        let service = EmissionsModelService::new(table, &config)?;
+        Ok(Arc::new(service))
+    }
+}
@@ -0,0 +1,24 @@
+use routee_compass_core::model::unit::{DistanceUnit, RatioUnit, SpeedUnit};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct EmissionsModelConfig {
+    /// CSV file of emission rates with columns `powertrain_type`, `speed`, an optional
+    /// `grade`, and one column per pollutant with the rate in grams per `distance_unit`.
+    /// rows must cover every combination of the speed and grade values of a powertrain type.
+    pub emission_rates_input_file: String,
+    /// pollutant columns to model. defaults to all pollutant columns in the file.
+    pub pollutants: Option<Vec<String>>,
+    /// powertrain type used when a query does not provide a `powertrain_type`.
+    /// may be omitted when the file has a single powertrain type.
+    pub default_powertrain_type: Option<String>,
+    /// unit of the `speed` column. defaults to mph.
+    pub speed_unit: Option<SpeedUnit>,
+    /// unit of the `grade` column. defaults to percent.
+    pub grade_unit: Option<RatioUnit>,
+    /// distance unit of the emission rates. defaults to miles.
+    pub distance_unit: Option<DistanceUnit>,
+    /// if true, adds `trip_<pollutant>` accumulators. defaults to true.
+    pub include_trip_emissions: Option<bool>,
+}
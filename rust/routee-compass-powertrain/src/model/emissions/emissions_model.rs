@@ -0,0 +1,128 @@
+use super::EmissionRateTable;
+use crate::model::fieldname;
+use ordered_float::OrderedFloat;
+use routee_compass_core::{
+    algorithm::search::SearchTree,
+    model::{
+        network::{Edge, Vertex},
+        state::{
+            CustomVariableConfig, InputFeature, StateModel, StateVariable, StateVariableConfig,
+        },
+        traversal::{TraversalModel, TraversalModelError},
+        unit::{DistanceUnit, RatioUnit, SpeedUnit},
+    },
+};
+use std::sync::Arc;
+
+/// assigns the mass of each pollutant emitted while traversing an edge, using emission
+/// rates looked up by the powertrain type and the edge speed and grade. writes
+/// `edge_<pollutant>` and, optionally, `trip_<pollutant>` state variables in grams.
+pub struct EmissionsModel {
+    pub table: Arc<EmissionRateTable>,
+    pub powertrain_type: String,
+    pub speed_unit: SpeedUnit,
+    pub grade_unit: RatioUnit,
+    pub distance_unit: DistanceUnit,
+    pub include_trip_emissions: bool,
+}
+
+impl EmissionsModel {
+    /// unit name of the emission state variables
+    pub const EMISSIONS_UNIT: &'static str = "grams";
+
+    pub fn edge_feature_name(pollutant: &str) -> String {
+        format!("edge_{pollutant}")
+    }
+
+    pub fn trip_feature_name(pollutant: &str) -> String {
+        format!("trip_{pollutant}")
+    }
+}
+
+impl TraversalModel for EmissionsModel {
+    fn name(&self) -> String {
+        format!("Emissions Model: {}", self.powertrain_type)
+    }
+
+    fn input_features(&self) -> Vec<InputFeature> {
+        let mut features = vec![
+            InputFeature::Distance {
+                name: String::from(fieldname::EDGE_DISTANCE),
+                unit: None,
+            },
+            InputFeature::Speed {
+                name: String::from(fieldname::EDGE_SPEED),
+                unit: None,
+            },
+        ];
+        if self.table.has_grade {
+            features.push(InputFeature::Ratio {
+                name: String::from(fieldname::EDGE_GRADE),
+                unit: None,
+            });
+        }
+        features
+    }
+
+    fn output_features(&self) -> Vec<(String, StateVariableConfig)> {
+        let feature = |accumulator: bool| StateVariableConfig::Custom {
+            custom_type: String::from(Self::EMISSIONS_UNIT),
+            value: CustomVariableConfig::FloatingPoint {
+                initial: OrderedFloat(0.0),
+            },
+            accumulator,
+        };
+        let mut features = vec![];
+        for pollutant in self.table.pollutants.iter() {
+            features.push((Self::edge_feature_name(pollutant), feature(false)));
+            if self.include_trip_emissions {
+                features.push((Self::trip_feature_name(pollutant), feature(true)));
+            }
+        }
+        features
+    }
+
+    fn traverse_edge(
+        &self,
+        _trajectory: (&Vertex, &Edge, &Vertex),
+        state: &mut Vec<StateVariable>,
+        _tree: &SearchTree,
+        state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        let distance = state_model.get_distance(state, fieldname::EDGE_DISTANCE)?;
+        let speed = state_model.get_speed(state, fieldname::EDGE_SPEED)?;
+        let grade = if self.table.has_grade {
+            let grade = state_model.get_ratio(state, fieldname::EDGE_GRADE)?;
+            self.grade_unit.from_uom(grade)
+        } else {
+            0.0
+        };
+        let rates = self.table.rates(
+            &self.powertrain_type,
+            self.speed_unit.from_uom(speed),
+            grade,
+        )?;
+        let distance = self.distance_unit.from_uom(distance);
+        for (pollutant, rate) in self.table.pollutants.iter().zip(rates) {
+            let grams = rate * distance;
+            state_model.set_custom_f64(state, &Self::edge_feature_name(pollutant), &grams)?;
+            if self.include_trip_emissions {
+                let trip_name = Self::trip_feature_name(pollutant);
+                let trip_grams = state_model.get_custom_f64(state, &trip_name)? + grams;
+                state_model.set_custom_f64(state, &trip_name, &trip_grams)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// emissions are not estimated, which keeps any cost on emissions admissible.
+    fn estimate_traversal(
+        &self,
+        _od: (&Vertex, &Vertex),
+        _state: &mut Vec<StateVariable>,
+        _tree: &SearchTree,
+        _state_model: &StateModel,
+    ) -> Result<(), TraversalModelError> {
+        Ok(())
+    }
+}
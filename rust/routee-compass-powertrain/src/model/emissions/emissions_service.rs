@@ -0,0 +1,96 @@
+use super::{EmissionRateTable, EmissionsModel, EmissionsModelConfig};
+use routee_compass_core::model::{
+    traversal::{TraversalModel, TraversalModelError, TraversalModelService},
+    unit::{DistanceUnit, RatioUnit, SpeedUnit},
+};
+use std::sync::Arc;
+
+pub struct EmissionsModelService {
+    pub table: Arc<EmissionRateTable>,
+    pub default_powertrain_type: Option<String>,
+    pub speed_unit: SpeedUnit,
+    pub grade_unit: RatioUnit,
+    pub distance_unit: DistanceUnit,
+    pub include_trip_emissions: bool,
+}
+
+impl EmissionsModelService {
+    /// query key selecting the powertrain type of the emission rates
+    pub const POWERTRAIN_TYPE_KEY: &'static str = "powertrain_type";
+
+    pub fn new(
+        table: EmissionRateTable,
+        config: &EmissionsModelConfig,
+    ) -> Result<EmissionsModelService, TraversalModelError> {
+        let default_powertrain_type = match (
+            &config.default_powertrain_type,
+            table.powertrain_types().as_slice(),
+        ) {
+            (Some(p), _) if !table.contains_powertrain_type(p) => {
+                return Err(TraversalModelError::BuildError(format!(
+                    "default powertrain type '{p}' not found in emission rates, found: {:?}",
+                    table.powertrain_types()
+                )))
+            }
+            (Some(p), _) => Some(p.clone()),
+            (None, [only]) => Some(only.to_string()),
+            (None, _) => None,
+        };
+        Ok(EmissionsModelService {
+            table: Arc::new(table),
+            default_powertrain_type,
+            speed_unit: config.speed_unit.unwrap_or(SpeedUnit::MPH),
+            grade_unit: config.grade_unit.unwrap_or(RatioUnit::Percent),
+            distance_unit: config.distance_unit.unwrap_or(DistanceUnit::Miles),
+            include_trip_emissions: config.include_trip_emissions.unwrap_or(true),
+        })
+    }
+}
+
+impl TraversalModelService for EmissionsModelService {
+    fn build(
+        &self,
+        query: &serde_json::Value,
+    ) -> Result<Arc<dyn TraversalModel>, TraversalModelError> {
+        let powertrain_type = match query.get(Self::POWERTRAIN_TYPE_KEY) {
+            Some(value) => value.as_str().map(String::from).ok_or_else(|| {
+                TraversalModelError::BuildError(format!(
+                    "query '{}' must be a string, found '{value}'",
+                    Self::POWERTRAIN_TYPE_KEY
+                ))
+            })?,
+            None => self.default_powertrain_type.clone().ok_or_else(|| {
+                TraversalModelError::BuildError(format!(
+                    "query must provide '{}', one of {:?}",
+                    Self::POWERTRAIN_TYPE_KEY,
+                    self.table.powertrain_types()
+                ))
+            })?,
+        };
+        if !self.table.contains_powertrain_type(&powertrain_type) {
+            return Err(TraversalModelError::BuildError(format!(
+                "no emission rates for powertrain type '{powertrain_type}', found: {:?}",
+                self.table.powertrain_types()
+            )));
+        }
+        let model = EmissionsModel {
+            table: self.table.clone(),
+            powertrain_type,
+            speed_unit: self.speed_unit,
+            grade_unit: self.grade_unit,
+            distance_unit: self.distance_unit,
+            include_trip_emissions: self.include_trip_emissions,
+        };
+        Ok(Arc::new(model))
+    }
+
+    fn describe(&self) -> Option<serde_json::Value> {
+        Some(serde_json::json!({
+            "type": "emissions",
+            "query_key": Self::POWERTRAIN_TYPE_KEY,
+            "powertrain_types": self.table.powertrain_types(),
+            "default_powertrain_type": self.default_powertrain_type,
+            "pollutants": self.table.pollutants,
+        }))
+    }
+}
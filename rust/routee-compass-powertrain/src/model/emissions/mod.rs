@@ -0,0 +1,11 @@
+mod emission_rate_table;
+mod emissions_builder;
+mod emissions_config;
+mod emissions_model;
+mod emissions_service;
+
+pub use emission_rate_table::EmissionRateTable;
+pub use emissions_builder::EmissionsModelBuilder;
+pub use emissions_config::EmissionsModelConfig;
+pub use emissions_model::EmissionsModel;
+pub use emissions_service::EmissionsModelService;
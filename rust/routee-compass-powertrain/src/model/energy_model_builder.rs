@@ -3,6 +3,7 @@ use crate::model::BevEnergyModel;
 use crate::model::IceEnergyModel;
 use crate::model::PhevEnergyModel;
 use config::Config;
+use rayon::prelude::*;
 use routee_compass_core::config::ops::strip_type_from_config;
 use routee_compass_core::config::ConfigJsonExtensions;
 use routee_compass_core::model::traversal::{
@@ -34,71 +35,82 @@ impl TraversalModelBuilder for EnergyModelBuilder {
                 ))
             })?;
 
-        // read all vehicle configurations from files
-        let mut vehicle_library = HashMap::new();
-        for vehicle_file in &config.vehicle_input_files {
-            let vehicle_config = Config::builder()
-                .add_source(config::File::with_name(vehicle_file))
-                .build()
-                .map_err(|e| {
-                    TraversalModelError::BuildError(format!(
-                        "failed to read vehicle config file '{}': {}",
-                        vehicle_file, e
-                    ))
-                })?;
+        // vehicle model files are independent, so they are read concurrently. the
+        // library is collected in file order, so a later file with the same name wins.
+        let vehicles = config
+            .vehicle_input_files
+            .par_iter()
+            .map(|vehicle_file| read_vehicle(vehicle_file, config.include_trip_energy))
+            .collect::<Result<Vec<_>, _>>()?;
+        let vehicle_library: HashMap<_, _> = vehicles.into_iter().collect();
 
-            let mut vehicle_json = vehicle_config
-                .try_deserialize::<serde_json::Value>()
-                .map_err(|e| {
-                    TraversalModelError::BuildError(format!(
-                        "failed to parse vehicle config file '{}': {}",
-                        vehicle_file, e
-                    ))
-                })?
-                .normalize_file_paths(Path::new(vehicle_file), None)
-                .map_err(|e| {
-                    TraversalModelError::BuildError(format!(
-                        "failed to normalize file paths in vehicle config file '{}': {}",
-                        vehicle_file, e
-                    ))
-                })?;
+        let service = EnergyModelService::new(vehicle_library)?;
 
-            // inject include_trip_energy if specified at the model level
-            if let Some(include_trip_energy) = config.include_trip_energy {
-                vehicle_json["include_trip_energy"] = serde_json::Value::Bool(include_trip_energy);
-            }
+        Ok(Arc::new(service))
+    }
+}
 
-            let model_name = vehicle_json
-                .get("name")
-                .and_then(|v| v.as_str())
-                .ok_or_else(|| {
-                    TraversalModelError::BuildError(format!(
-                        "vehicle model missing 'name' field in '{}'",
-                        vehicle_file
-                    ))
-                })?
-                .to_string();
+/// reads a vehicle model file, returning the name of the vehicle and its service
+fn read_vehicle(
+    vehicle_file: &str,
+    include_trip_energy: Option<bool>,
+) -> Result<(String, Arc<dyn TraversalModelService>), TraversalModelError> {
+    let vehicle_config = Config::builder()
+        .add_source(config::File::with_name(vehicle_file))
+        .build()
+        .map_err(|e| {
+            TraversalModelError::BuildError(format!(
+                "failed to read vehicle config file '{}': {}",
+                vehicle_file, e
+            ))
+        })?;
 
-            let (vehicle_json_stripped, vehicle_type) = strip_type_from_config(&vehicle_json)
-                .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
+    let mut vehicle_json = vehicle_config
+        .try_deserialize::<serde_json::Value>()
+        .map_err(|e| {
+            TraversalModelError::BuildError(format!(
+                "failed to parse vehicle config file '{}': {}",
+                vehicle_file, e
+            ))
+        })?
+        .normalize_file_paths(Path::new(vehicle_file), None)
+        .map_err(|e| {
+            TraversalModelError::BuildError(format!(
+                "failed to normalize file paths in vehicle config file '{}': {}",
+                vehicle_file, e
+            ))
+        })?;
 
-            let service: Arc<dyn TraversalModelService> = match vehicle_type.as_str() {
-                "ice" => Arc::new(IceEnergyModel::try_from(&vehicle_json_stripped)?),
-                "bev" => Arc::new(BevEnergyModel::try_from(&vehicle_json_stripped)?),
-                "phev" => Arc::new(PhevEnergyModel::try_from(&vehicle_json_stripped)?),
-                _ => {
-                    return Err(TraversalModelError::BuildError(format!(
-                        "unknown vehicle model type in '{}': {}",
-                        vehicle_file, vehicle_type
-                    )));
-                }
-            };
+    // inject include_trip_energy if specified at the model level
+    if let Some(include_trip_energy) = include_trip_energy {
+        vehicle_json["include_trip_energy"] = serde_json::Value::Bool(include_trip_energy);
+    }
 
-            vehicle_library.insert(model_name, service);
-        }
+    let model_name = vehicle_json
+        .get("name")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| {
+            TraversalModelError::BuildError(format!(
+                "vehicle model missing 'name' field in '{}'",
+                vehicle_file
+            ))
+        })?
+        .to_string();
 
-        let service = EnergyModelService::new(vehicle_library)?;
+    let (vehicle_json_stripped, vehicle_type) = strip_type_from_config(&vehicle_json)
+        .map_err(|e| TraversalModelError::BuildError(e.to_string()))?;
 
-        Ok(Arc::new(service))
-    }
+    let service: Arc<dyn TraversalModelService> = match vehicle_type.as_str() {
+        "ice" => Arc::new(IceEnergyModel::try_from(&vehicle_json_stripped)?),
+        "bev" => Arc::new(BevEnergyModel::try_from(&vehicle_json_stripped)?),
+        "phev" => Arc::new(PhevEnergyModel::try_from(&vehicle_json_stripped)?),
+        _ => {
+            return Err(TraversalModelError::BuildError(format!(
+                "unknown vehicle model type in '{}': {}",
+                vehicle_file, vehicle_type
+            )));
+        }
+    };
+
+    Ok((model_name, service))
 }
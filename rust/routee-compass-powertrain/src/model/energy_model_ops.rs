@@ -4,7 +4,7 @@ use routee_compass_core::model::{
     unit::UnitError,
 };
 use uom::{
-    si::f64::{Energy, Ratio},
+    si::f64::{Energy, Power, Ratio},
     ConstZero,
 };
 
@@ -86,6 +86,31 @@ pub fn get_query_start_energy(
     Ok(Some(starting_battery_energy))
 }
 
+/// schema of the query `starting_soc_percent` parameter accepted by vehicles with a battery
+pub fn starting_soc_schema() -> serde_json::Value {
+    serde_json::json!({ "type": "number", "minimum": 0.0, "maximum": 100.0 })
+}
+
+/// inspect the user query for a non-negative power value in kilowatts at the given key.
+/// if not provided, return None.
+pub fn get_query_power_kw(
+    query: &serde_json::Value,
+    key: &str,
+) -> Result<Option<Power>, TraversalModelError> {
+    let kw = match query.get(key) {
+        Some(value) => value.as_f64().ok_or_else(|| {
+            TraversalModelError::BuildError(format!("Expected '{key}' value to be numeric"))
+        })?,
+        None => return Ok(None),
+    };
+    if kw < 0.0 {
+        return Err(TraversalModelError::BuildError(format!(
+            "Expected '{key}' value to be non-negative"
+        )));
+    }
+    Ok(Some(Power::new::<uom::si::power::kilowatt>(kw)))
+}
+
 /// look up the grade from the grade table
 pub fn get_grade(
     grade_table: &Option<Box<[Ratio]>>,
@@ -1,6 +1,8 @@
+use itertools::Itertools;
 use routee_compass_core::model::traversal::{
     TraversalModel, TraversalModelError, TraversalModelService,
 };
+use serde_json::json;
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -17,6 +19,18 @@ impl EnergyModelService {
     ) -> Result<Self, TraversalModelError> {
         Ok(EnergyModelService { vehicle_library })
     }
+
+    /// names of the vehicle models in the library, sorted
+    pub fn vehicle_names(&self) -> Vec<&String> {
+        self.vehicle_library.keys().sorted().collect()
+    }
+
+    fn vehicle_names_message(&self) -> String {
+        format!(
+            "must be one of [{}]",
+            self.vehicle_names().into_iter().join(", ")
+        )
+    }
 }
 
 impl TraversalModelService for EnergyModelService {
@@ -27,25 +41,98 @@ impl TraversalModelService for EnergyModelService {
         let model_name = parameters
             .get("model_name")
             .ok_or_else(|| {
-                TraversalModelError::BuildError("query missing 'model_name' field".to_string())
+                TraversalModelError::BuildError(format!(
+                    "query missing 'model_name' field, {}",
+                    self.vehicle_names_message()
+                ))
             })?
             .as_str()
             .ok_or_else(|| {
-                TraversalModelError::BuildError("query 'model_name' is not a string".to_string())
+                TraversalModelError::BuildError(format!(
+                    "query 'model_name' is not a string, {}",
+                    self.vehicle_names_message()
+                ))
             })?;
 
         let service = self.vehicle_library.get(model_name).ok_or_else(|| {
             TraversalModelError::BuildError(format!(
-                "unknown vehicle model {}, must be one of [{}]",
+                "unknown vehicle model '{}', {}",
                 model_name,
-                self.vehicle_library
-                    .keys()
-                    .cloned()
-                    .collect::<Vec<_>>()
-                    .join(",")
+                self.vehicle_names_message()
             ))
         })?;
         let model = service.build(parameters)?;
         Ok(model)
     }
+
+    fn describe(&self) -> Option<serde_json::Value> {
+        let vehicles = self
+            .vehicle_names()
+            .into_iter()
+            .map(|name| {
+                let description = self.vehicle_library[name]
+                    .describe()
+                    .unwrap_or(serde_json::Value::Null);
+                (name.clone(), description)
+            })
+            .collect::<serde_json::Map<_, _>>();
+        Some(json!({
+            "type": "energy",
+            "query_key": "model_name",
+            "vehicles": vehicles,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct MockVehicle;
+
+    impl TraversalModelService for MockVehicle {
+        fn build(
+            &self,
+            _query: &serde_json::Value,
+        ) -> Result<Arc<dyn TraversalModel>, TraversalModelError> {
+            Err(TraversalModelError::BuildError(String::from("mock")))
+        }
+
+        fn describe(&self) -> Option<serde_json::Value> {
+            Some(json!({ "type": "mock" }))
+        }
+    }
+
+    #[test]
+    fn test_describe_and_unknown_vehicle() {
+        let vehicle_library: HashMap<String, Arc<dyn TraversalModelService>> = HashMap::from([
+            (
+                String::from("truck"),
+                Arc::new(MockVehicle) as Arc<dyn TraversalModelService>,
+            ),
+            (
+                String::from("car"),
+                Arc::new(MockVehicle) as Arc<dyn TraversalModelService>,
+            ),
+        ]);
+        let service = EnergyModelService::new(vehicle_library).expect("test invariant failed");
+
+        let description = service
+            .describe()
+            .expect("energy service should describe itself");
+        let names = description["vehicles"]
+            .as_object()
+            .expect("vehicles should be an object")
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["car", "truck"]);
+        assert_eq!(description["vehicles"]["car"]["type"], json!("mock"));
+
+        let error = service
+            .build(&json!({ "model_name": "bus" }))
+            .err()
+            .expect("unknown vehicle should fail");
+        assert!(error.to_string().contains("[car, truck]"), "{error}");
+    }
 }
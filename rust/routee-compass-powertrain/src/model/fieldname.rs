@@ -16,8 +16,11 @@
 //! - BEV
 //!   - uses "trip_energy_electric" and "edge_energy_electric" for energy consumption
 //!   - adds "trip_soc" for state of charge percentage
+//!   - optionally adds "trip_battery_throughput" and "trip_battery_degradation_cost"
 //! - PHEV
 //!   - uses all of the above
+//! - Emissions
+//!   - uses "edge_<pollutant>" and "trip_<pollutant>" for the grams of each pollutant, such as "edge_co2"
 
 /// state feature name for liquid fuel state values for a single graph edge
 pub const EDGE_ENERGY_LIQUID: &str = "edge_energy_liquid";
@@ -31,4 +34,9 @@ pub const TRIP_ENERGY_ELECTRIC: &str = "trip_energy_electric";
 pub const TRIP_SOC: &str = "trip_soc";
 
 pub const BATTERY_CAPACITY: &str = "battery_capacity";
+/// accumulated energy cycled through the battery over a trip, counting both
+/// discharge and regenerative charging
+pub const TRIP_BATTERY_THROUGHPUT: &str = "trip_battery_throughput";
+/// accumulated cost of battery wear over a trip, from the battery throughput
+pub const TRIP_BATTERY_DEGRADATION_COST: &str = "trip_battery_degradation_cost";
 pub use routee_compass_core::model::traversal::default::fieldname::*;
@@ -47,6 +47,14 @@ impl TraversalModelService for IceEnergyModel {
     ) -> Result<Arc<dyn TraversalModel>, TraversalModelError> {
         Ok(Arc::new(self.clone()))
     }
+
+    fn describe(&self) -> Option<serde_json::Value> {
+        Some(serde_json::json!({
+            "type": "ice",
+            "prediction_model": self.prediction_model_record.describe(),
+            "query_parameters": {},
+        }))
+    }
 }
 
 impl TryFrom<&Value> for IceEnergyModel {
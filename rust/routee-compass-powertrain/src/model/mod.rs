@@ -1,5 +1,6 @@
 mod bev_energy_model;
 pub mod charging;
+pub mod emissions;
 mod energy_model_builder;
 pub mod energy_model_ops;
 pub mod energy_model_service;
@@ -8,7 +9,7 @@ mod ice_energy_model;
 mod phev_energy_model;
 pub mod prediction;
 
-pub use bev_energy_model::BevEnergyModel;
+pub use bev_energy_model::{BevEnergyModel, BevPowerAdjustments};
 pub use energy_model_builder::EnergyModelBuilder;
 pub use ice_energy_model::IceEnergyModel;
 pub use phev_energy_model::PhevEnergyModel;
@@ -112,6 +112,18 @@ impl TraversalModelService for PhevEnergyModel {
             }
         }
     }
+
+    fn describe(&self) -> Option<serde_json::Value> {
+        Some(serde_json::json!({
+            "type": "phev",
+            "charge_sustaining_model": self.charge_sustain_model.describe(),
+            "charge_depleting_model": self.charge_depleting_model.describe(),
+            "battery_capacity_kwh": self.battery_capacity.get::<uom::si::energy::kilowatt_hour>(),
+            "query_parameters": {
+                "starting_soc_percent": energy_model_ops::starting_soc_schema(),
+            },
+        }))
+    }
 }
 
 impl TraversalModel for PhevEnergyModel {
@@ -81,6 +81,15 @@ impl TryFrom<&PredictionModelConfig> for PredictionModelRecord {
 }
 
 impl PredictionModelRecord {
+    /// describes the model name, input features and energy rate unit
+    pub fn describe(&self) -> serde_json::Value {
+        serde_json::json!({
+            "name": self.name,
+            "input_features": self.input_features,
+            "energy_rate_unit": self.energy_rate_unit,
+        })
+    }
+
     pub fn predict(
         &self,
         state: &mut [StateVariable],
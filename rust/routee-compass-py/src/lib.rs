@@ -2,12 +2,28 @@
 
 pub mod app_wrapper;
 
-use app_wrapper::CompassAppWrapper;
-use pyo3::prelude::*;
+use app_wrapper::{CompassAppWrapper, CompassAppWrapperResponseIterator};
+use pyo3::{exceptions::PyException, prelude::*};
+use routee_compass::app::compass::dataset_manifest::DatasetManifest;
+use std::path::Path;
+
+/// writes a manifest of the files and content hashes of a dataset directory, returning
+/// the dataset version recorded in the manifest
+#[pyfunction]
+#[pyo3(signature = (directory, dataset_version=None))]
+fn write_dataset_manifest(directory: String, dataset_version: Option<String>) -> PyResult<String> {
+    let directory = Path::new(&directory);
+    let manifest = DatasetManifest::build(directory, dataset_version.as_deref())
+        .and_then(|m| m.write(directory).map(|_| m))
+        .map_err(|e| PyException::new_err(format!("failure writing dataset manifest: {e}")))?;
+    Ok(manifest.dataset_version)
+}
 
 #[pymodule]
 fn routee_compass_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<CompassAppWrapper>()?;
+    m.add_class::<CompassAppWrapperResponseIterator>()?;
+    m.add_function(wrap_pyfunction!(write_dataset_manifest, m)?)?;
 
     Ok(())
 }
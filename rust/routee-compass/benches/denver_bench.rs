@@ -9,22 +9,40 @@ use std::{hint::black_box, io::Write};
 
 use routee_compass::app::cli::cli_args::CliArgs;
 use routee_compass::app::cli::run;
-use routee_compass::app::compass::CompassBuilderInventory;
+use routee_compass::app::compass::{CompassApp, CompassAppConfig, CompassBuilderInventory};
 
 use criterion::{criterion_group, criterion_main, Criterion};
 use tempfile::NamedTempFile;
 
+const DENVER_CONFIG: &str =
+    "../../python/nrel/routee/compass/resources/downtown_denver_example/osm_default_speed.toml";
+
+const DENVER_QUERY: &str = "{
+    \"origin_name\": \"NREL\",
+    \"destination_name\": \"Comrade Brewing Company\",
+    \"destination_y\": 39.62627481432341,
+    \"destination_x\": -104.99460207519721,
+    \"origin_y\": 39.798311884359094,
+    \"origin_x\": -104.86796368632217
+}";
+
 /// Run the query on the downtown denver example config file
 fn downtown_denver_example(query_file: String) {
     let args = CliArgs {
-        config_file: String::from(
-            "../../python/nrel/routee/compass/resources/downtown_denver_example/osm_default_speed.toml",
-        ),
-        query_file,
+        config_file: Some(String::from(DENVER_CONFIG)),
+        query_file: Some(query_file),
+        query_template: None,
         chunksize: None,
         newline_delimited: false,
         parallelism: Some(1),
         output_directory: None,
+        checkpoint_file: None,
+        resume: false,
+        validate_config: false,
+        no_load: false,
+        export_schema: None,
+        write_manifest: None,
+        dataset_version: None,
     };
     let builder = CompassBuilderInventory::new().expect("failed to load compass app builder");
     match run::command_line_runner(&args, Some(builder), None) {
@@ -39,17 +57,8 @@ fn downtown_denver_example(query_file: String) {
 fn bench_example(c: &mut Criterion) {
     let mut group = c.benchmark_group("routee-compass");
 
-    let query = "{
-        \"origin_name\": \"NREL\",
-        \"destination_name\": \"Comrade Brewing Company\",
-        \"destination_y\": 39.62627481432341,
-        \"destination_x\": -104.99460207519721,
-        \"origin_y\": 39.798311884359094,
-        \"origin_x\": -104.86796368632217
-    }";
-
     let mut tmp_file = NamedTempFile::new().unwrap();
-    tmp_file.write_all(query.as_bytes()).unwrap();
+    tmp_file.write_all(DENVER_QUERY.as_bytes()).unwrap();
     let tmp_path = tmp_file.into_temp_path();
 
     group.bench_with_input("downtown denver example", &tmp_path, |b, input| {
@@ -60,5 +69,40 @@ fn bench_example(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, bench_example);
+/// Benchmark the downtown denver query with each search tree storage, loading the app
+/// outside of the timed loop so that only the searches are measured
+fn bench_tree_storage(c: &mut Criterion) {
+    let mut group = c.benchmark_group("search-tree-storage");
+    let conf_str = std::fs::read_to_string(DENVER_CONFIG).expect("failed to read denver config");
+    // vertices 0 and 400 of the downtown denver network
+    let query = serde_json::json!({
+        "origin_x": -104.9848634,
+        "origin_y": 39.7400593,
+        "destination_x": -104.9960105,
+        "destination_y": 39.7462733,
+        "weights": {"trip_distance": 1, "trip_time": 1}
+    });
+
+    for storage in ["nodes", "arena"] {
+        let conf_str =
+            format!("{conf_str}\n[algorithm]\ntype = \"a*\"\ntree_storage = \"{storage}\"\n");
+        let config = CompassAppConfig::from_str(&conf_str, DENVER_CONFIG, config::FileFormat::Toml)
+            .expect("failed to read denver config");
+        let builder = CompassBuilderInventory::new().expect("failed to load compass app builder");
+        let app = CompassApp::new(&config, &builder).expect("failed to build compass app");
+        let response = app
+            .run(&mut vec![query.clone()], None)
+            .expect("query failed");
+        assert!(response[0].get("error").is_none(), "{}", response[0]);
+
+        group.bench_function(storage, |b| {
+            b.iter(|| {
+                let mut queries = vec![query.clone()];
+                black_box(app.run(&mut queries, None).expect("query failed"))
+            })
+        });
+    }
+}
+
+criterion_group!(benches, bench_example, bench_tree_storage);
 criterion_main!(benches);
@@ -1,4 +1,4 @@
-use super::compass::{CompassApp, CompassAppError};
+use super::compass::{CompassApp, CompassAppError, QueryStream};
 use crate::app::search::SearchAppGraphOps;
 use itertools::Itertools;
 use routee_compass_core::{
@@ -48,7 +48,7 @@ use std::str::FromStr;
 ///         // inject custom traversal model here like:
 ///
 ///         // my_custom_traversal_model_builder = MyCustomTraversalModelBuilder::new();
-///         // builder.add_traversal_model("my_custom_model", Rc::new(my_custom_traversal_model));
+///         // builder.add_traversal_model("my_custom_model", Arc::new(my_custom_traversal_model));
 ///
 ///         let config = CompassAppConfig::from_str(&config_string, &original_file_path, FileFormat::Toml)?;
 ///         let app = CompassApp::new(&config, &builder)?;
@@ -202,6 +202,15 @@ pub trait CompassAppBindings {
             .collect_vec()
     }
 
+    /// Describes the query-time choices of the loaded traversal models, such as vehicle model names
+    ///
+    /// # Returns
+    /// * a json string with the description of each traversal model
+    fn describe_models(&self) -> Result<String, CompassAppError> {
+        let description = self.app().describe_models()?;
+        Ok(description.to_string())
+    }
+
     /// Runs a set of queries and returns the results
     ///
     /// # Arguments
@@ -233,6 +242,50 @@ pub trait CompassAppBindings {
         Ok(string_results)
     }
 
+    /// Creates a stream that runs a set of queries incrementally as its responses are taken
+    ///
+    /// # Arguments
+    /// * `queries` - a list of queries to run as json strings
+    /// * `config` - optional configuration for parallelism, etc.
+    /// * `chunk_size` - number of queries run at a time
+    ///
+    /// # Returns
+    /// * a stream to pass to [`CompassAppBindings::next_streaming_response`]
+    fn run_queries_streaming(
+        &self,
+        queries: Vec<String>,
+        config: Option<String>,
+        chunk_size: usize,
+    ) -> Result<QueryStream, CompassAppError> {
+        let config_inner: Option<serde_json::Value> = match config {
+            Some(c) => {
+                let c_serde: serde_json::Value = serde_json::from_str(&c)?;
+                Some(c_serde)
+            }
+            None => None,
+        };
+
+        let json_queries = queries
+            .iter()
+            .map(|q| serde_json::from_str(q))
+            .collect::<Result<Vec<serde_json::Value>, serde_json::Error>>()?;
+
+        Ok(QueryStream::new(json_queries, config_inner, chunk_size))
+    }
+
+    /// Takes the next response from a stream created by [`CompassAppBindings::run_queries_streaming`]
+    ///
+    /// # Returns
+    /// * the next result as a json string, or None once the stream is exhausted
+    fn next_streaming_response(
+        &self,
+        stream: &mut QueryStream,
+    ) -> Option<Result<String, CompassAppError>> {
+        stream
+            .next_response(self.app())
+            .map(|r| r.map(|response| response.to_string()))
+    }
+
     /// Runs map matching on a batch of requests
     ///
     /// # Arguments
@@ -298,4 +351,38 @@ pub trait CompassAppBindings {
         let string_results: Vec<String> = results.iter().map(|r| r.to_string()).collect();
         Ok(string_results)
     }
+
+    /// Runs a set of re-planning requests and returns the results
+    ///
+    /// # Arguments
+    /// * `requests` - a list of re-planning requests as json strings
+    /// * `config` - optional configuration for parallelism, etc.
+    ///
+    /// # Returns
+    /// * a list of json strings containing the results of the requests
+    fn run_replan(
+        &self,
+        requests: Vec<String>,
+        config: Option<String>,
+    ) -> Result<Vec<String>, CompassAppError> {
+        let config_inner: Option<serde_json::Value> = match config {
+            Some(c) => {
+                let c_serde: serde_json::Value = serde_json::from_str(&c)?;
+                Some(c_serde)
+            }
+            None => None,
+        };
+
+        let json_requests = requests
+            .iter()
+            .map(|q| serde_json::from_str(q))
+            .collect::<Result<Vec<serde_json::Value>, serde_json::Error>>()?;
+
+        let results = self
+            .app()
+            .run_replan(&json_requests, config_inner.as_ref())?;
+
+        let string_results: Vec<String> = results.iter().map(|r| r.to_string()).collect();
+        Ok(string_results)
+    }
 }
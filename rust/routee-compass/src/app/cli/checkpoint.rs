@@ -0,0 +1,141 @@
+use crate::app::compass::CompassAppError;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+/// records the ids of queries whose responses have been written to the response sink,
+/// one id per line, so that an interrupted run can be resumed without re-running them.
+///
+/// a query's id is the value of its `id` field if present, otherwise its index in the
+/// query file. ids are recorded once a chunk of queries has completed and the response
+/// sink has been closed, so a crash loses at most the chunk in progress.
+pub struct Checkpoint {
+    path: PathBuf,
+    completed: HashSet<String>,
+    file: File,
+}
+
+impl Checkpoint {
+    /// query key used as the query id, when present
+    pub const QUERY_ID_KEY: &'static str = "id";
+
+    /// opens a checkpoint file. when resuming, the ids already recorded in the file
+    /// are loaded and new ids are appended. otherwise, any existing file is replaced.
+    pub fn open(path: &Path, resume: bool) -> Result<Checkpoint, CompassAppError> {
+        let completed = if resume && path.exists() {
+            let file = File::open(path).map_err(|e| {
+                CompassAppError::BuildFailure(format!(
+                    "could not open checkpoint file {}: {e}",
+                    path.to_string_lossy()
+                ))
+            })?;
+            BufReader::new(file)
+                .lines()
+                .filter(|line| line.as_ref().map(|l| !l.is_empty()).unwrap_or(true))
+                .collect::<Result<HashSet<_>, _>>()
+                .map_err(|e| {
+                    CompassAppError::BuildFailure(format!(
+                        "failure reading checkpoint file {}: {e}",
+                        path.to_string_lossy()
+                    ))
+                })?
+        } else {
+            HashSet::new()
+        };
+        let file = OpenOptions::new()
+            .create(true)
+            .append(resume)
+            .write(true)
+            .truncate(!resume)
+            .open(path)
+            .map_err(|e| {
+                CompassAppError::BuildFailure(format!(
+                    "could not open checkpoint file {} for writing: {e}",
+                    path.to_string_lossy()
+                ))
+            })?;
+        Ok(Checkpoint {
+            path: path.to_path_buf(),
+            completed,
+            file,
+        })
+    }
+
+    /// the id of a query: its `id` field if present, otherwise its index in the query file
+    pub fn query_id(query: &Value, index: usize) -> String {
+        match query.get(Self::QUERY_ID_KEY) {
+            Some(Value::String(id)) => id.clone(),
+            Some(id) => id.to_string(),
+            None => index.to_string(),
+        }
+    }
+
+    /// true if the query with this id was completed in a previous run
+    pub fn is_completed(&self, id: &str) -> bool {
+        self.completed.contains(id)
+    }
+
+    /// number of completed query ids
+    pub fn n_completed(&self) -> usize {
+        self.completed.len()
+    }
+
+    /// records a set of query ids as completed, syncing the checkpoint file to disk.
+    pub fn record(&mut self, ids: &[String]) -> Result<(), CompassAppError> {
+        let mut lines = String::new();
+        for id in ids.iter() {
+            lines.push_str(id);
+            lines.push('\n');
+        }
+        self.file
+            .write_all(lines.as_bytes())
+            .and_then(|_| self.file.sync_data())
+            .map_err(|e| {
+                CompassAppError::InternalError(format!(
+                    "failure writing checkpoint file {}: {e}",
+                    self.path.to_string_lossy()
+                ))
+            })?;
+        self.completed.extend(ids.iter().cloned());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_checkpoint_resume() {
+        let dir = tempfile::tempdir().expect("test invariant failed: could not create tempdir");
+        let path = dir.path().join("checkpoint.txt");
+
+        let mut checkpoint = Checkpoint::open(&path, false).expect("failed to open checkpoint");
+        let ids = vec![
+            Checkpoint::query_id(&json!({"id": "a"}), 0),
+            Checkpoint::query_id(&json!({"id": 7}), 1),
+            Checkpoint::query_id(&json!({}), 2),
+        ];
+        assert_eq!(ids, vec!["a", "7", "2"]);
+        checkpoint.record(&ids).expect("failed to record ids");
+        drop(checkpoint);
+
+        let mut resumed = Checkpoint::open(&path, true).expect("failed to resume checkpoint");
+        assert_eq!(resumed.n_completed(), 3);
+        assert!(resumed.is_completed("a"));
+        assert!(!resumed.is_completed("3"));
+        resumed
+            .record(&[String::from("3")])
+            .expect("failed to record ids");
+        drop(resumed);
+
+        let resumed = Checkpoint::open(&path, true).expect("failed to resume checkpoint");
+        assert_eq!(resumed.n_completed(), 4);
+
+        let restarted = Checkpoint::open(&path, false).expect("failed to open checkpoint");
+        assert_eq!(restarted.n_completed(), 0);
+    }
+}
@@ -5,12 +5,27 @@ use routee_compass_core::config::CompassConfigurationError;
 #[command(author, version, about, long_about = None)]
 pub struct CliArgs {
     /// RouteE Compass service configuration TOML file
-    #[arg(short, long, value_name = "*.toml")]
-    pub config_file: String,
+    #[arg(
+        short,
+        long,
+        value_name = "*.toml",
+        required_unless_present_any = ["export_schema", "write_manifest"]
+    )]
+    pub config_file: Option<String>,
 
-    /// JSON file containing queries. Should be newline-delimited if chunksize is set
-    #[arg(short, long, value_name = "*.json")]
-    pub query_file: String,
+    /// JSON file containing queries. Should be newline-delimited if chunksize is set.
+    /// A CSV file is also accepted, where each row is expanded into a query using the query template
+    #[arg(
+        short,
+        long,
+        value_name = "*.json",
+        required_unless_present_any = ["validate_config", "export_schema", "write_manifest"]
+    )]
+    pub query_file: Option<String>,
+
+    /// JSON file containing a query template that each row of a CSV query file is merged into
+    #[arg(long, value_name = "*.json")]
+    pub query_template: Option<String>,
 
     /// Size of batches to load into memory at a time
     #[arg(long)]
@@ -28,16 +43,88 @@ pub struct CliArgs {
     /// treated as filenames only and written to this directory.
     #[arg(short, long, value_name = "DIR")]
     pub output_directory: Option<String>,
+
+    /// File recording the ids of completed queries, one per line. A query's id is its `id`
+    /// field if present, otherwise its index in the query file.
+    #[arg(long, value_name = "FILE")]
+    pub checkpoint_file: Option<String>,
+
+    /// Skip queries already recorded as completed in the checkpoint file
+    #[arg(long)]
+    pub resume: bool,
+
+    /// Build every component from the config file without running queries, reporting all
+    /// configuration errors found
+    #[arg(long)]
+    pub validate_config: bool,
+
+    /// With validate_config, skip building the graph and map model, which read the largest files
+    #[arg(long)]
+    pub no_load: bool,
+
+    /// Write JSON Schemas for the config file and queries, including all registered
+    /// components, to this directory and exit
+    #[arg(long, value_name = "DIR")]
+    pub export_schema: Option<String>,
+
+    /// Write a manifest of the files and content hashes of the dataset in this directory
+    /// and exit
+    #[arg(long, value_name = "DIR")]
+    pub write_manifest: Option<String>,
+
+    /// With write_manifest, the version of the dataset recorded in the manifest. by
+    /// default, the version is derived from the file hashes
+    #[arg(long)]
+    pub dataset_version: Option<String>,
 }
 
 impl CliArgs {
+    /// true if the query file should be read as CSV rows expanded by the query template
+    pub fn is_csv_query_file(&self) -> bool {
+        self.query_file.as_ref().is_some_and(|f| {
+            std::path::Path::new(f)
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"))
+        })
+    }
+
     pub fn validate(&self) -> Result<(), CompassAppError> {
-        match (self.chunksize, self.newline_delimited) {
-            (Some(_), false) => Err(CompassAppError::CompassConfigurationError(
+        if self.query_template.is_some() && !self.is_csv_query_file() {
+            return Err(CompassAppError::CompassConfigurationError(
                 CompassConfigurationError::UserConfigurationError(String::from(
-                    "chunksize must be set if newline_delimited_queries is true",
+                    "query_template can only be used with a CSV query file",
                 )),
-            )),
+            ));
+        }
+        if self.no_load && !self.validate_config {
+            return Err(CompassAppError::CompassConfigurationError(
+                CompassConfigurationError::UserConfigurationError(String::from(
+                    "no_load can only be used with validate_config",
+                )),
+            ));
+        }
+        if self.dataset_version.is_some() && self.write_manifest.is_none() {
+            return Err(CompassAppError::CompassConfigurationError(
+                CompassConfigurationError::UserConfigurationError(String::from(
+                    "dataset_version can only be used with write_manifest",
+                )),
+            ));
+        }
+        if self.resume && self.checkpoint_file.is_none() {
+            return Err(CompassAppError::CompassConfigurationError(
+                CompassConfigurationError::UserConfigurationError(String::from(
+                    "resume requires a checkpoint_file",
+                )),
+            ));
+        }
+        match (self.chunksize, self.newline_delimited) {
+            (Some(_), false) if !self.is_csv_query_file() => {
+                Err(CompassAppError::CompassConfigurationError(
+                    CompassConfigurationError::UserConfigurationError(String::from(
+                        "chunksize must be set if newline_delimited_queries is true",
+                    )),
+                ))
+            }
             (Some(chunksize), _) if chunksize < 1 => {
                 Err(CompassAppError::CompassConfigurationError(
                     CompassConfigurationError::UserConfigurationError(format!(
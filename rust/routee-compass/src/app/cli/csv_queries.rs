@@ -0,0 +1,187 @@
+use crate::app::compass::CompassAppError;
+use serde_json::{Map, Value};
+use std::{fs::File, path::Path};
+
+/// expands rows of a CSV file into queries by merging each row into a JSON query template.
+///
+/// the template is a JSON object. any string value in the template of the form `"{column}"`
+/// is replaced by the value of that column in the row. any column not referenced by a
+/// placeholder is added as a top-level key of the query, overwriting the template value.
+/// CSV values that parse as numbers or booleans are written as JSON numbers or booleans,
+/// and empty values are omitted.
+///
+/// # Example
+///
+/// with the template `{"model_name": "2017_CHEVROLET_Bolt", "weights": {"trip_time": "{time_weight}"}}`,
+/// the row `origin_x,origin_y,destination_x,destination_y,time_weight` becomes a query with
+/// the coordinates as top-level keys and `time_weight` written to `weights.trip_time`.
+pub struct CsvQueryTemplate {
+    template: Map<String, Value>,
+}
+
+impl CsvQueryTemplate {
+    pub fn new(template: Value) -> Result<CsvQueryTemplate, CompassAppError> {
+        match template {
+            Value::Object(template) => Ok(CsvQueryTemplate { template }),
+            other => Err(CompassAppError::BuildFailure(format!(
+                "query template must be a JSON object, found {other}"
+            ))),
+        }
+    }
+
+    /// reads a query template from a JSON file
+    pub fn from_file(path: &Path) -> Result<CsvQueryTemplate, CompassAppError> {
+        let file = File::open(path).map_err(|e| {
+            CompassAppError::BuildFailure(format!(
+                "could not open query template file {}: {e}",
+                path.to_string_lossy()
+            ))
+        })?;
+        let template: Value = serde_json::from_reader(std::io::BufReader::new(file))?;
+        CsvQueryTemplate::new(template)
+    }
+
+    /// builds a query from a single CSV row
+    pub fn expand_row(
+        &self,
+        headers: &csv::StringRecord,
+        row: &csv::StringRecord,
+    ) -> Result<Value, CompassAppError> {
+        if headers.len() != row.len() {
+            return Err(CompassAppError::CompassFailure(format!(
+                "CSV row has {} values but the header has {} columns",
+                row.len(),
+                headers.len()
+            )));
+        }
+        let values = headers
+            .iter()
+            .zip(row.iter())
+            .filter(|(_, v)| !v.trim().is_empty())
+            .map(|(k, v)| (k.to_string(), parse_csv_value(v)))
+            .collect::<Map<_, _>>();
+
+        let mut placeholders_used = vec![];
+        let mut query = self.template.clone();
+        for value in query.values_mut() {
+            substitute_placeholders(value, &values, &mut placeholders_used);
+        }
+        for (column, value) in values.into_iter() {
+            if !placeholders_used.contains(&column) {
+                query.insert(column, value);
+            }
+        }
+        Ok(Value::Object(query))
+    }
+}
+
+/// replaces `"{column}"` string values with the matching row value, recursively.
+/// placeholders for columns missing from the row are removed from objects.
+fn substitute_placeholders(value: &mut Value, row: &Map<String, Value>, used: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            let mut missing = vec![];
+            for (key, v) in map.iter_mut() {
+                if placeholder_column(v).is_some_and(|c| !row.contains_key(c)) {
+                    missing.push(key.clone());
+                } else {
+                    substitute_placeholders(v, row, used);
+                }
+            }
+            for key in missing.iter() {
+                map.remove(key);
+            }
+        }
+        Value::Array(values) => {
+            for v in values.iter_mut() {
+                substitute_placeholders(v, row, used);
+            }
+        }
+        Value::String(_) => {
+            if let Some(column) = placeholder_column(value).map(String::from) {
+                if let Some(row_value) = row.get(&column) {
+                    *value = row_value.clone();
+                    used.push(column);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// if this value is a string of the form `"{column}"`, returns the column name
+fn placeholder_column(value: &Value) -> Option<&str> {
+    value
+        .as_str()
+        .and_then(|s| s.strip_prefix('{'))
+        .and_then(|s| s.strip_suffix('}'))
+        .filter(|s| !s.is_empty() && !s.contains(['{', '}']))
+}
+
+fn parse_csv_value(value: &str) -> Value {
+    let trimmed = value.trim();
+    if let Ok(i) = trimmed.parse::<i64>() {
+        Value::from(i)
+    } else if let Ok(f) = trimmed.parse::<f64>() {
+        serde_json::Number::from_f64(f)
+            .map(Value::Number)
+            .unwrap_or_else(|| Value::String(trimmed.to_string()))
+    } else if let Ok(b) = trimmed.parse::<bool>() {
+        Value::Bool(b)
+    } else {
+        Value::String(trimmed.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    fn expand(template: Value, headers: &[&str], row: &[&str]) -> Value {
+        let template = CsvQueryTemplate::new(template).expect("invalid template");
+        template
+            .expand_row(
+                &csv::StringRecord::from(headers.to_vec()),
+                &csv::StringRecord::from(row.to_vec()),
+            )
+            .expect("expand failed")
+    }
+
+    #[test]
+    fn test_expand_row_top_level() {
+        let result = expand(
+            json!({"model_name": "leaf"}),
+            &["origin_x", "origin_y", "label"],
+            &["-105.1", "39.7", "home"],
+        );
+        assert_eq!(
+            result,
+            json!({"model_name": "leaf", "origin_x": -105.1, "origin_y": 39.7, "label": "home"})
+        );
+    }
+
+    #[test]
+    fn test_expand_row_placeholders() {
+        let result = expand(
+            json!({"weights": {"trip_time": "{w}", "trip_distance": "{missing}"}, "ids": ["{id}"]}),
+            &["w", "id", "flag"],
+            &["2", "abc", "true"],
+        );
+        assert_eq!(
+            result,
+            json!({"weights": {"trip_time": 2}, "ids": ["abc"], "flag": true})
+        );
+    }
+
+    #[test]
+    fn test_expand_row_empty_values_omitted() {
+        let result = expand(json!({"a": 1}), &["a", "b"], &["", "x"]);
+        assert_eq!(result, json!({"a": 1, "b": "x"}));
+    }
+
+    #[test]
+    fn test_template_must_be_object() {
+        assert!(CsvQueryTemplate::new(json!([1, 2])).is_err());
+    }
+}
@@ -1,2 +1,4 @@
+pub mod checkpoint;
 pub mod cli_args;
+pub mod csv_queries;
 pub mod run;
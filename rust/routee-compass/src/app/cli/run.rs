@@ -1,11 +1,17 @@
+use super::checkpoint::Checkpoint;
 use super::cli_args::CliArgs;
+use super::csv_queries::CsvQueryTemplate;
+use crate::app::compass::compass_app_schema;
+use crate::app::compass::dataset_manifest::DatasetManifest;
 use crate::app::compass::response::response_output_policy::ResponseOutputPolicy;
+use crate::app::compass::response::write_mode::WriteMode;
 use crate::app::compass::CompassAppConfig;
 use crate::app::compass::{
     CompassApp, CompassAppError, CompassBuilderInventory, CompassJsonExtensions,
 };
 use itertools::{Either, Itertools};
 use log::{debug, error, info, warn};
+use routee_compass_core::config::CompassConfigurationError;
 use serde_json::{json, Value};
 use std::io::BufRead;
 use std::time::Instant;
@@ -29,6 +35,19 @@ pub fn command_line_runner(
 ) -> Result<(), CompassAppError> {
     args.validate()?;
 
+    if let Some(directory) = &args.write_manifest {
+        let manifest =
+            DatasetManifest::build(Path::new(directory), args.dataset_version.as_deref())?;
+        let path = manifest.write(Path::new(directory))?;
+        info!(
+            "wrote manifest of {} files with dataset version {} to {}",
+            manifest.files.len(),
+            manifest.dataset_version,
+            path.display()
+        );
+        return Ok(());
+    }
+
     // Start timing the load phase
     let load_start = Instant::now();
 
@@ -37,7 +56,22 @@ pub fn command_line_runner(
         Some(b) => b,
         None => CompassBuilderInventory::new()?,
     };
-    let config_path = Path::new(&args.config_file);
+    if let Some(directory) = &args.export_schema {
+        let paths = compass_app_schema::write_schemas(&builder_or_default, Path::new(directory))?;
+        for path in paths {
+            info!("wrote schema file {}", path.display());
+        }
+        return Ok(());
+    }
+
+    let config_filename = args.config_file.as_ref().ok_or_else(|| {
+        CompassAppError::CompassConfigurationError(
+            CompassConfigurationError::UserConfigurationError(String::from(
+                "config_file is required unless export_schema or write_manifest is set",
+            )),
+        )
+    })?;
+    let config_path = Path::new(config_filename);
     let mut config = CompassAppConfig::try_from(config_path)?;
 
     // Apply CLI overrides to config
@@ -75,10 +109,19 @@ pub fn command_line_runner(
         }
     }
 
+    if args.resume {
+        if let Some(ref response_policy) = config.system.response_output_policy {
+            validate_resume_write_mode(response_policy)?;
+        }
+    }
+
     info!(
         "Loaded the following Compass configuration:\n{}",
         config.to_pretty_string()?
     );
+    if args.validate_config {
+        return validate_config(&config, &builder_or_default, !args.no_load);
+    }
     let compass_app = match CompassApp::new(&config, &builder_or_default) {
         Ok(app) => app,
         Err(e) => {
@@ -95,23 +138,65 @@ pub fn command_line_runner(
     );
 
     // read user file containing JSON query/queries
-    info!("reading queries from {}", &args.query_file);
-    let query_file = File::open(args.query_file.clone()).map_err(|_e| {
-        CompassAppError::BuildFailure(format!("Could not find query file {}", args.query_file))
+    let query_filename = args.query_file.as_ref().ok_or_else(|| {
+        CompassAppError::CompassConfigurationError(
+            CompassConfigurationError::UserConfigurationError(String::from(
+                "query_file is required unless validate_config is set",
+            )),
+        )
+    })?;
+    info!("reading queries from {query_filename}");
+    let query_file = File::open(query_filename).map_err(|_e| {
+        CompassAppError::BuildFailure(format!("Could not find query file {query_filename}"))
     })?;
 
+    let mut checkpoint = match &args.checkpoint_file {
+        Some(path) => {
+            let checkpoint = Checkpoint::open(Path::new(path), args.resume)?;
+            if args.resume {
+                info!(
+                    "resuming from checkpoint file {path}, skipping {} completed queries",
+                    checkpoint.n_completed()
+                );
+            }
+            Some(checkpoint)
+        }
+        None => None,
+    };
+
     // Start timing the run phase
     let run_start = Instant::now();
 
     // execute queries on app
     let result = match (args.chunksize, args.newline_delimited) {
-        (None, false) => run_json(&query_file, &compass_app, run_config),
+        _ if args.is_csv_query_file() => {
+            let template = match &args.query_template {
+                Some(path) => CsvQueryTemplate::from_file(Path::new(path))?,
+                None => CsvQueryTemplate::new(json!({}))?,
+            };
+            let chunksize = args.get_chunksize_option()?;
+            run_csv(
+                &query_file,
+                &template,
+                chunksize,
+                &compass_app,
+                run_config,
+                &mut checkpoint,
+            )
+        }
+        (None, false) => run_json(&query_file, &compass_app, run_config, &mut checkpoint),
         (Some(_), false) => Err(CompassAppError::InternalError(String::from(
             "not yet implemented",
         ))),
         (_, true) => {
             let chunksize = args.get_chunksize_option()?;
-            run_newline_json(&query_file, chunksize, &compass_app, run_config)
+            run_newline_json(
+                &query_file,
+                chunksize,
+                &compass_app,
+                run_config,
+                &mut checkpoint,
+            )
         }
     };
 
@@ -138,15 +223,17 @@ fn run_json(
     query_file: &File,
     compass_app: &CompassApp,
     run_config: Option<&Value>,
+    checkpoint: &mut Option<Checkpoint>,
 ) -> Result<(), CompassAppError> {
     let reader = BufReader::new(query_file);
     let user_json: serde_json::Value = serde_json::from_reader(reader)?;
-    let mut user_queries = user_json.get_queries()?;
-    let results = compass_app.run(&mut user_queries, run_config)?;
-    for result in results.iter() {
-        log_error(result);
-    }
-    Ok(())
+    let user_queries = user_json
+        .get_queries()?
+        .into_iter()
+        .enumerate()
+        .map(|(idx, query)| (Checkpoint::query_id(&query, idx), query))
+        .collect();
+    run_chunk(user_queries, compass_app, run_config, checkpoint)
 }
 
 /// parses a file as newline-delimited JSON which can be optionally chunked into sub-batches
@@ -158,9 +245,10 @@ fn run_newline_json(
     chunksize_option: Option<usize>,
     compass_app: &CompassApp,
     run_config: Option<&Value>,
+    checkpoint: &mut Option<Checkpoint>,
 ) -> Result<(), CompassAppError> {
     let reader = BufReader::new(query_file);
-    let iterator = reader.lines();
+    let iterator = reader.lines().enumerate();
     let chunksize = chunksize_option.unwrap_or(usize::MAX);
     let chunks = iterator.chunks(chunksize);
     info!("reading {chunksize} queries at-a-time from newline-delimited JSON file");
@@ -169,10 +257,10 @@ fn run_newline_json(
         debug!("executing batch {}", iteration + 1);
 
         // parse JSON output
-        let (mut chunk_queries, errors): (Vec<Value>, Vec<CompassAppError>) =
-            chunk.enumerate().partition_map(|(idx, row)| match row {
+        let (chunk_queries, errors): (Vec<(String, Value)>, Vec<CompassAppError>) = chunk
+            .partition_map(|(idx, row)| match row {
                 Ok(string) => match serde_json::from_str(&string) {
-                    Ok(query) => Either::Left(query),
+                    Ok(query) => Either::Left((Checkpoint::query_id(&query, idx), query)),
                     Err(e) => Either::Right(CompassAppError::CompassFailure(format!(
                         "while reading chunk {iteration} row {idx}, failed to read JSON: {e}"
                     ))),
@@ -182,9 +270,7 @@ fn run_newline_json(
                 ))),
             });
         // run Compass on this chunk of queries
-        for result in compass_app.run(&mut chunk_queries, run_config)?.iter() {
-            log_error(result)
-        }
+        run_chunk(chunk_queries, compass_app, run_config, checkpoint)?;
 
         // report JSON parsing errors
         for error in errors {
@@ -199,6 +285,87 @@ fn run_newline_json(
     Ok(())
 }
 
+/// reads a CSV file where each row is expanded into a query using the query template,
+/// optionally chunked into sub-batches which are each run against the CompassApp.run command.
+fn run_csv(
+    query_file: &File,
+    template: &CsvQueryTemplate,
+    chunksize_option: Option<usize>,
+    compass_app: &CompassApp,
+    run_config: Option<&Value>,
+    checkpoint: &mut Option<Checkpoint>,
+) -> Result<(), CompassAppError> {
+    let mut reader = csv::Reader::from_reader(BufReader::new(query_file));
+    let headers = reader.headers().cloned().map_err(|e| {
+        CompassAppError::BuildFailure(format!("failed to read CSV query file header: {e}"))
+    })?;
+    let chunksize = chunksize_option.unwrap_or(usize::MAX);
+    let chunks = reader.records().enumerate().chunks(chunksize);
+    info!("reading {chunksize} queries at-a-time from CSV file");
+
+    for (iteration, chunk) in chunks.into_iter().enumerate() {
+        debug!("executing batch {}", iteration + 1);
+
+        let (chunk_queries, errors): (Vec<(String, Value)>, Vec<CompassAppError>) = chunk
+            .partition_map(|(idx, row)| {
+                let query = row
+                    .map_err(|e| {
+                        CompassAppError::CompassFailure(format!(
+                            "while reading chunk {iteration} row {idx}, failed to read CSV: {e}"
+                        ))
+                    })
+                    .and_then(|r| template.expand_row(&headers, &r));
+                match query {
+                    Ok(q) => Either::Left((Checkpoint::query_id(&q, idx), q)),
+                    Err(e) => Either::Right(e),
+                }
+            });
+        run_chunk(chunk_queries, compass_app, run_config, checkpoint)?;
+        for error in errors {
+            let error_json = json!({
+                "request": "failed to parse",
+                "error": error.to_string()
+            });
+            log_error(&error_json)
+        }
+    }
+
+    Ok(())
+}
+
+/// runs a chunk of queries paired with their ids, skipping any query already completed
+/// according to the checkpoint. once the chunk has run, the response sink has been closed,
+/// so the ids of the chunk are recorded as completed.
+fn run_chunk(
+    queries: Vec<(String, Value)>,
+    compass_app: &CompassApp,
+    run_config: Option<&Value>,
+    checkpoint: &mut Option<Checkpoint>,
+) -> Result<(), CompassAppError> {
+    let (ids, mut chunk_queries): (Vec<String>, Vec<Value>) = match checkpoint {
+        Some(c) => queries
+            .into_iter()
+            .filter(|(id, _)| !c.is_completed(id))
+            .unzip(),
+        None => queries.into_iter().unzip(),
+    };
+    if let Some(c) = checkpoint {
+        if chunk_queries.is_empty() {
+            debug!("all queries in chunk already completed, skipping");
+            return Ok(());
+        }
+        for result in compass_app.run(&mut chunk_queries, run_config)?.iter() {
+            log_error(result)
+        }
+        c.record(&ids)
+    } else {
+        for result in compass_app.run(&mut chunk_queries, run_config)?.iter() {
+            log_error(result)
+        }
+        Ok(())
+    }
+}
+
 fn log_error(result: &Value) {
     if let Some(error) = result.get("error") {
         let error_string = error.to_string().replace("\\n", "\n");
@@ -206,6 +373,49 @@ fn log_error(result: &Value) {
     }
 }
 
+/// builds each component of the app without running queries, logging every configuration
+/// error found. fails if any component could not be built.
+fn validate_config(
+    config: &CompassAppConfig,
+    builder: &CompassBuilderInventory,
+    load_network: bool,
+) -> Result<(), CompassAppError> {
+    let errors = CompassApp::validate_config(config, builder, load_network);
+    if errors.is_empty() {
+        info!("configuration is valid");
+        return Ok(());
+    }
+    for error in errors.iter() {
+        error!("{error}");
+    }
+    Err(CompassAppError::CompassConfigurationError(
+        CompassConfigurationError::UserConfigurationError(format!(
+            "found {} configuration error(s):\n{}",
+            errors.len(),
+            errors.join("\n")
+        )),
+    ))
+}
+
+/// resuming appends to the output files of the interrupted run, so a write mode that
+/// replaces or rejects existing files would lose or refuse the earlier responses.
+fn validate_resume_write_mode(policy: &ResponseOutputPolicy) -> Result<(), CompassAppError> {
+    match policy {
+        ResponseOutputPolicy::File { filename, write_mode, .. } => match write_mode {
+            Some(WriteMode::Overwrite) | Some(WriteMode::Error) => {
+                Err(CompassAppError::BuildFailure(format!(
+                    "cannot resume with output file {filename}, write_mode must be 'append' when resuming"
+                )))
+            }
+            _ => Ok(()),
+        },
+        ResponseOutputPolicy::Combined { policies } => policies
+            .iter()
+            .try_for_each(|p| validate_resume_write_mode(p)),
+        ResponseOutputPolicy::None => Ok(()),
+    }
+}
+
 /// Recursively applies output directory override to a ResponseOutputPolicy
 /// Any existing filename in the config is treated as a filename only, and re-rooted
 /// to the provided output directory.
@@ -1,26 +1,48 @@
 use super::compass_app_system::CompassAppSystemParameters;
-
 use super::{
-    compass_app_ops as ops, compass_map_matching as map_matching_ops, CompassBuilderInventory,
+    compass_app_ops as ops, compass_map_matching as map_matching_ops, compass_replan, trip_chain,
+    CompassBuilderInventory,
 };
+use super::{QueryScheduler, QueryStream, ResponseStream, RunMetrics};
 use crate::app::compass::compass_app_config::CompassAppConfig;
+use crate::app::compass::response::response_metadata::ResponseMetadataConfig;
+use crate::app::compass::response::response_ordering::{ResponseOrdering, ResponseOrderingConfig};
 use crate::app::compass::response::response_persistence_policy::ResponsePersistencePolicy;
+use crate::app::compass::response::response_sink::ResponseSink;
+use crate::app::compass::response_cache::ResponseCache;
 use crate::{
-    app::{compass::CompassAppError, search::SearchApp},
-    plugin::{input::InputPlugin, output::OutputPlugin},
+    app::{
+        compass::CompassAppError,
+        search::{EdgeModelServices, SearchApp, SearchInstanceCache},
+    },
+    plugin::{
+        input::{default::load_balancer::LoadBalancerPlugin, InputPlugin},
+        output::{
+            default::{
+                dataset_version::DatasetVersionOutputPlugin,
+                response_metadata::ResponseMetadataOutputPlugin,
+            },
+            OutputPlugin,
+        },
+    },
 };
 
 use kdam::Bar;
 use rayon::current_num_threads;
-use routee_compass_core::algorithm::search::SearchAlgorithm;
+use routee_compass_core::algorithm::search::{
+    a_star::{a_star_ops, FreeFlowHeuristic},
+    SearchAlgorithm, SearchError,
+};
 use routee_compass_core::model::cost::cost_model_service::CostModelService;
 use routee_compass_core::model::map::MapModel;
 use routee_compass_core::model::network::Graph;
-use routee_compass_core::model::state::StateModel;
+use routee_compass_core::model::state::{DepartureTime, StateModel, StateModelError};
+use routee_compass_core::util::span::{self, Span};
 use serde_json::Value;
 use std::{
     path::Path,
     sync::{Arc, Mutex},
+    time::Duration,
 };
 
 use routee_compass_core::algorithm::map_matching::MapMatchingAlgorithm;
@@ -39,6 +61,10 @@ pub struct CompassApp {
     pub output_plugins: Vec<Arc<dyn OutputPlugin>>,
     pub system_parameters: CompassAppSystemParameters,
     pub map_matching_algorithm: Arc<dyn MapMatchingAlgorithm>,
+    /// if provided, the persistent cache of responses consulted before search
+    pub response_cache: Option<Arc<ResponseCache>>,
+    /// time taken to build this app, reported in run metrics
+    pub load_duration: Duration,
 }
 
 impl TryFrom<&Path> for CompassApp {
@@ -86,36 +112,75 @@ impl CompassApp {
         config: &CompassAppConfig,
         builder: &CompassBuilderInventory,
     ) -> Result<Self, CompassAppError> {
-        let state_model = match &config.state {
-            Some(state_config) => Arc::new(StateModel::new(state_config.clone())),
-            None => Arc::new(StateModel::empty()),
+        let load_span = Span::new("load").enter();
+        // hashed before clipping, which replaces the graph files of the config
+        let config_hash = match (
+            &config.system.response_metadata,
+            &config.system.response_cache,
+        ) {
+            (None, None) => None,
+            _ => Some(config.hash()?),
+        };
+        let dataset_manifest = match &config.system.dataset_manifest {
+            Some(manifest_config) => Some(ops::with_timing("dataset manifest", || {
+                manifest_config.load()
+            })?),
+            None => None,
+        };
+        let clipped_config;
+        let config = match &config.graph.clip {
+            Some(_) => {
+                clipped_config = ops::with_timing("graph clip", || config.with_clipped_graph())?;
+                &clipped_config
+            }
+            None => config,
         };
+        let departure_time_features = match &config.system.departure_time {
+            Some(_) => vec![DepartureTime::feature()],
+            None => vec![],
+        };
+        let state_features = departure_time_features
+            .into_iter()
+            .chain(config.state.iter().flatten().cloned())
+            .collect();
+        let state_model = Arc::new(StateModel::new(state_features));
         let cost_model_service = CostModelService::try_from(&config.cost)?;
         let label_model_service = builder.build_label_model_service(&config.label)?;
         log::info!("app termination model: {:?}", config.termination);
 
-        // build selected components for search behaviors
-        let traversal_model_services = ops::with_timing("traversal models", || {
-            config.build_traversal_model_services(builder)
-        })?;
-        let constraint_model_services = ops::with_timing("constraint models", || {
-            config.build_constraint_model_services(builder)
-        })?;
-
-        // build graph
-        let graph = ops::with_timing("graph", || Ok(Arc::new(Graph::try_from(&config.graph)?)))?;
-
-        let map_model = ops::with_timing("map model", || {
-            let mm = MapModel::new(graph.clone(), &config.mapping).map_err(|e| {
-                CompassAppError::BuildFailure(format!("unable to load MapModel from config: {e}"))
+        // the graph, and the spatial index and heuristic built from it, are loaded on
+        // another thread while the traversal and constraint models read their input files.
+        // if a model fails to build, its error is returned without waiting for the graph,
+        // which is left to finish loading in the background and then dropped.
+        let load_span_id = Some(load_span.id());
+        let graph_config = config.clone();
+        let graph_config_hash = config_hash.clone();
+        let graph_thread = std::thread::Builder::new()
+            .name(String::from("compass-graph-loader"))
+            .spawn(move || {
+                span::with_parent(load_span_id, || {
+                    load_graph(&graph_config, graph_config_hash)
+                })
+            })
+            .map_err(|e| {
+                CompassAppError::InternalError(format!("could not start graph loading thread: {e}"))
             })?;
-            Ok(Arc::new(mm))
-        })?;
-
+        let EdgeModelServices {
+            traversal_model_services,
+            constraint_model_services,
+        } = build_edge_model_services(config, builder)?;
+        let LoadedGraph {
+            graph,
+            map_model,
+            response_cache,
+            heuristic,
+        } = graph_thread.join().map_err(|_| {
+            CompassAppError::InternalError(String::from("graph loading thread panicked"))
+        })??;
         let search_algorithm = SearchAlgorithm::from(&config.algorithm);
 
         // build search app
-        let search_app = Arc::new(SearchApp::new(
+        let mut search_app = SearchApp::new(
             search_algorithm,
             graph,
             map_model,
@@ -126,14 +191,76 @@ impl CompassApp {
             config.termination.clone(),
             label_model_service,
             config.system.default_edge_list,
-        ));
+        );
+        if let Some(heuristic) = heuristic {
+            search_app = search_app.with_heuristic(heuristic);
+        }
+        if let Some(departure_time_config) = &config.system.departure_time {
+            if let Some(departure) = departure_time_config.default_departure_time()? {
+                search_app = search_app.with_default_departure_time(departure);
+            }
+        }
+        if let Some(weight) = config.algorithm.heuristic_weight() {
+            let weight = a_star_ops::validate_heuristic_weight(weight)?;
+            search_app = search_app.with_heuristic_weight(weight);
+        }
+        // models that can be built without a query have their state variable dependencies
+        // audited at load. the audit is repeated as each search instance is built. it runs
+        // before the search instance cache is attached, so that the instance built for the
+        // empty audit query is not cached.
+        match search_app.build_search_instance(&serde_json::json!({})) {
+            Err(SearchError::StateFailure {
+                source: e @ StateModelError::FeatureMismatch(_),
+            }) => return Err(CompassAppError::BuildFailure(e.to_string())),
+            Err(e) => log::debug!("state variable audit deferred to query time: {e}"),
+            Ok(_) => log::debug!("state variable audit passed"),
+        }
+        if let Some(cache_config) = &config.system.search_instance_cache {
+            search_app =
+                search_app.with_search_instance_cache(SearchInstanceCache::new(cache_config));
+        }
+        let search_app = Arc::new(search_app);
 
-        let input_plugins = ops::with_timing("input plugins", || {
+        let mut input_plugins = ops::with_timing("input plugins", || {
             Ok(builder.build_input_plugins(&config.plugin.input_plugins)?)
         })?;
-        let output_plugins = ops::with_timing("output plugins", || {
+        let default_load_balancer = config.system.default_load_balancer.unwrap_or_default();
+        if default_load_balancer && !input_plugins.iter().any(|p| p.name() == "load_balancer") {
+            input_plugins.push(Arc::new(LoadBalancerPlugin::default_estimator()));
+        }
+        let mut output_plugins = ops::with_timing("output plugins", || {
             Ok(builder.build_output_plugins(&config.plugin.output_plugins)?)
         })?;
+        // the fields added by the app are also added to responses read from the cache
+        let mut cache_annotations = serde_json::Map::new();
+        let dataset_version = dataset_manifest.map(|m| m.dataset_version);
+        if let Some(dataset_version) = &dataset_version {
+            cache_annotations.insert(
+                DatasetVersionOutputPlugin::DATASET_VERSION_KEY.to_string(),
+                Value::String(dataset_version.clone()),
+            );
+            output_plugins.push(Arc::new(DatasetVersionOutputPlugin {
+                dataset_version: dataset_version.clone(),
+            }));
+        }
+        if let (Some(metadata_config), Some(config_hash)) =
+            (&config.system.response_metadata, config_hash)
+        {
+            let search_algorithm = serde_json::to_value(&config.algorithm)?;
+            let plugin = ResponseMetadataOutputPlugin::new(
+                metadata_config,
+                config_hash,
+                dataset_version,
+                search_algorithm,
+            );
+            cache_annotations.insert(
+                ResponseMetadataConfig::METADATA_KEY.to_string(),
+                plugin.metadata.clone(),
+            );
+            output_plugins.push(Arc::new(plugin));
+        }
+        let response_cache =
+            response_cache.map(|cache| Arc::new(cache.with_annotations(cache_annotations)));
 
         let map_matching_algorithm = ops::with_timing("map matching algorithm", || {
             Ok(builder.build_map_matching_algorithm(&config.map_matching)?)
@@ -145,10 +272,194 @@ impl CompassApp {
             output_plugins,
             system_parameters: config.system.clone(),
             map_matching_algorithm,
+            response_cache,
+            load_duration: load_span.elapsed(),
         };
         Ok(app)
     }
 
+    /// builds each component of a [`CompassApp`] from configuration without running any
+    /// queries, collecting the error of every component that fails instead of stopping at
+    /// the first. when `load_network` is false, the graph and map model are not built,
+    /// which skips the heaviest file reads.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - deserialized TOML file contents
+    /// * `builder` - inventory of Compass components that can be built from the config object
+    /// * `load_network` - if true, also build the graph and map model
+    ///
+    /// # Returns
+    ///
+    /// a description of each configuration error, prefixed by the failing component.
+    /// empty if every component was built.
+    pub fn validate_config(
+        config: &CompassAppConfig,
+        builder: &CompassBuilderInventory,
+        load_network: bool,
+    ) -> Vec<String> {
+        let mut errors: Vec<String> = vec![];
+        let mut check = |component: String, result: Result<(), String>| {
+            if let Err(e) = result {
+                errors.push(format!("{component}: {e}"));
+            }
+        };
+
+        check(
+            String::from("cost model"),
+            CostModelService::try_from(&config.cost)
+                .map(|_| ())
+                .map_err(|e| e.to_string()),
+        );
+        check(
+            String::from("label model"),
+            builder
+                .build_label_model_service(&config.label)
+                .map(|_| ())
+                .map_err(|e| e.to_string()),
+        );
+        for (idx, search) in config.search.iter().enumerate() {
+            check(
+                format!("traversal model for edge list {idx}"),
+                builder
+                    .build_traversal_model_service(&search.traversal)
+                    .map(|_| ())
+                    .map_err(|e| e.to_string()),
+            );
+            check(
+                format!("constraint model for edge list {idx}"),
+                builder
+                    .build_constraint_model_service(&search.constraint)
+                    .map(|_| ())
+                    .map_err(|e| e.to_string()),
+            );
+        }
+        if load_network {
+            match Graph::try_from(&config.graph) {
+                Ok(graph) => {
+                    if let Some(heuristic) = config.algorithm.heuristic() {
+                        check(
+                            String::from("heuristic"),
+                            FreeFlowHeuristic::from_config(heuristic, &graph)
+                                .map(|_| ())
+                                .map_err(|e| e.to_string()),
+                        );
+                    }
+                    check(
+                        String::from("map model"),
+                        MapModel::new(Arc::new(graph), &config.mapping)
+                            .map(|_| ())
+                            .map_err(|e| e.to_string()),
+                    )
+                }
+                Err(e) => check(String::from("graph"), Err(e.to_string())),
+            }
+        }
+        for (idx, plugin) in config.plugin.input_plugins.iter().enumerate() {
+            check(
+                format!("input plugin {idx}"),
+                builder
+                    .build_input_plugins(std::slice::from_ref(plugin))
+                    .map(|_| ())
+                    .map_err(|e| e.to_string()),
+            );
+        }
+        for (idx, plugin) in config.plugin.output_plugins.iter().enumerate() {
+            check(
+                format!("output plugin {idx}"),
+                builder
+                    .build_output_plugins(std::slice::from_ref(plugin))
+                    .map(|_| ())
+                    .map_err(|e| e.to_string()),
+            );
+        }
+        check(
+            String::from("map matching algorithm"),
+            builder
+                .build_map_matching_algorithm(&config.map_matching)
+                .map(|_| ())
+                .map_err(|e| e.to_string()),
+        );
+        errors
+    }
+
+    /// runs a set of queries incrementally, returning an iterator over the responses.
+    /// queries are run via [`CompassApp::run`] in chunks of `chunk_size` queries, where the
+    /// next chunk only runs once every response of the previous chunk has been consumed.
+    /// this bounds the number of responses held in memory, and dropping the iterator
+    /// cancels the queries that have not yet run.
+    ///
+    /// each chunk is a separate run, so a response output policy that writes to a file
+    /// should append rather than overwrite, and a metrics report covers only the last chunk.
+    ///
+    /// # Arguments
+    ///
+    /// * `queries` - list of search queries to execute
+    /// * `config` - configuration for each chunk run which may override default configurations
+    /// * `chunk_size` - number of queries to run at a time, which should be at least the parallelism
+    ///
+    /// # Result
+    ///
+    /// an iterator over each response, or the error of a chunk that failed to run
+    pub fn run_streaming(
+        &self,
+        queries: Vec<Value>,
+        config: Option<Value>,
+        chunk_size: usize,
+    ) -> ResponseStream<'_> {
+        ResponseStream::new(self, QueryStream::new(queries, config, chunk_size))
+    }
+
+    /// reloads the edge attribute tables read by the traversal and constraint models,
+    /// such as edge speeds, grades and restrictions, without rebuilding the graph or
+    /// restarting the app. the traversal and constraint model services are rebuilt from
+    /// the `[search]` section of the provided configuration, which may reference updated
+    /// files, and then swapped in as a unit. all other sections of the configuration,
+    /// including the graph, are ignored, so the reloaded tables must describe the same
+    /// edges as the loaded graph.
+    ///
+    /// queries that are already running continue with the previous tables, while queries
+    /// that begin after the swap use the reloaded tables. if the reload fails, the current
+    /// tables remain in use. the response cache, if any, is cleared, and stores new
+    /// responses under the hash of the provided configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - configuration with the `[search]` section to reload
+    /// * `builder` - inventory of Compass components used to build the model services
+    pub fn reload_edge_attributes(
+        &self,
+        config: &CompassAppConfig,
+        builder: &CompassBuilderInventory,
+    ) -> Result<(), CompassAppError> {
+        let _span = Span::new("reload_edge_attributes").enter();
+        let services = build_edge_model_services(config, builder)?;
+        self.search_app.replace_edge_model_services(services)?;
+        if let Some(cache) = &self.response_cache {
+            cache.reset(config.hash()?)?;
+        }
+        log::info!("reloaded edge attributes");
+        Ok(())
+    }
+
+    /// describes the query-time choices of the loaded traversal models, such as the
+    /// names of the vehicle models in an energy model library along with the query
+    /// parameters each accepts.
+    ///
+    /// # Result
+    ///
+    /// a JSON object with a `traversal_models` array holding the description of each
+    /// traversal model that offers query-time choices
+    pub fn describe_models(&self) -> Result<Value, CompassAppError> {
+        let services = self.search_app.edge_model_services()?;
+        let traversal_models = services
+            .traversal_model_services
+            .iter()
+            .filter_map(|s| s.describe())
+            .collect::<Vec<_>>();
+        Ok(serde_json::json!({ "traversal_models": traversal_models }))
+    }
+
     /// runs a set of queries via this instance of CompassApp. this
     ///   1. processes each input query based on the InputPlugins
     ///   2. runs the search algorithm with each query via SearchApp
@@ -172,6 +483,7 @@ impl CompassApp {
         queries: &mut Vec<Value>,
         config: Option<&Value>,
     ) -> Result<Vec<Value>, CompassAppError> {
+        let run_span = Span::new("run").field("queries", queries.len()).enter();
         let override_config_opt: Option<CompassAppSystemParameters> = match config {
             Some(c) => serde_json::from_value(c.clone())?,
             None => None,
@@ -196,37 +508,80 @@ impl CompassApp {
             .unwrap_or_default();
         let response_writer = response_output_policy.build()?;
 
+        let metrics_config = override_config_opt
+            .as_ref()
+            .and_then(|c| c.metrics.clone())
+            .or(self.system_parameters.metrics.clone());
+        let run_metrics = RunMetrics::new(
+            self.load_duration,
+            &self.input_plugins,
+            &self.output_plugins,
+            metrics_config,
+        );
+
+        let response_ordering = override_config_opt
+            .as_ref()
+            .and_then(|c| c.response_ordering.clone())
+            .or(self.system_parameters.response_ordering.clone())
+            .unwrap_or_default();
+        response_ordering.attach_input_index(queries);
+
         // INPUT PROCESSING
 
+        let input_span = Span::new("input_plugins")
+            .timing(Some(&run_metrics.input_plugins), queries.len() as u64)
+            .enter();
         let input_plugin_result = ops::apply_input_plugins(
             queries,
             &self.input_plugins,
             self.search_app.clone(),
             parallelism,
-        )?;
-        let (processed_inputs, input_errors) = input_plugin_result;
-        let mut load_balanced_inputs =
-            ops::apply_load_balancing_policy(processed_inputs, parallelism, 1.0)?;
-
-        log::info!(
-            "creating {} parallel batches across {} threads to run queries",
-            parallelism,
-            current_num_threads(),
+            Some(&run_metrics.plugins),
         );
-        let proc_batch_sizes = load_balanced_inputs
-            .iter()
-            .map(|qs| qs.len())
+        drop(input_span);
+        let (processed_inputs, input_errors) = input_plugin_result?;
+
+        // queries that will share a one-to-many search are load balanced as a group
+        let one_to_many = override_config_opt
+            .as_ref()
+            .and_then(|c| c.one_to_many.clone())
+            .or(self.system_parameters.one_to_many.clone());
+        ops::detect_shareable_searches(&processed_inputs, one_to_many.as_ref());
+
+        // when writing in input order, the batch is run in windows whose responses
+        // are reordered before they are written
+        let in_input_order = response_ordering.ordering == ResponseOrdering::InputOrder;
+        let windows = if in_input_order {
+            let mut sorted = processed_inputs;
+            sorted.sort_by_key(ResponseOrderingConfig::query_input_index);
+            let window_size = response_ordering.window_size();
+            let mut windows = vec![];
+            while sorted.len() > window_size {
+                let rest = sorted.split_off(window_size);
+                windows.push(sorted);
+                sorted = rest;
+            }
+            windows.push(sorted);
+            windows
+        } else {
+            vec![processed_inputs]
+        };
+
+        // identical queries are run once per window, so that the responses copied to
+        // their duplicates are written with the window
+        let deduplication = override_config_opt
+            .as_ref()
+            .and_then(|c| c.deduplication.clone())
+            .or(self.system_parameters.deduplication.clone());
+        let windows = windows
+            .into_iter()
+            .map(|window| ops::apply_deduplication(window, deduplication.as_ref()))
             .collect::<Vec<_>>();
-        log::info!("queries assigned per executor: {proc_batch_sizes:?}");
 
         // set up search progress bar
-        let num_balanced_inputs = load_balanced_inputs
-            .iter()
-            .flatten()
-            .collect::<Vec<_>>()
-            .len();
+        let num_inputs = windows.iter().map(|(w, _)| w.len()).sum::<usize>();
         let search_pb = Bar::builder()
-            .total(num_balanced_inputs)
+            .total(num_inputs)
             .animation("fillup")
             .desc("search")
             .build()
@@ -235,34 +590,154 @@ impl CompassApp {
             })?;
         let search_pb_shared = Arc::new(Mutex::new(search_pb));
 
-        // run parallel searches as organized by the (optional) load balancing policy
-        // across a thread pool managed by rayon
-        let run_query_result = match response_persistence_policy {
-            ResponsePersistencePolicy::PersistResponseInMemory => ops::run_batch_with_responses(
-                &mut load_balanced_inputs,
-                &self.output_plugins,
-                &self.search_app,
-                &response_writer,
-                search_pb_shared,
-            )?,
-            ResponsePersistencePolicy::DiscardResponseFromMemory => {
-                ops::run_batch_without_responses(
-                    &mut load_balanced_inputs,
-                    &self.output_plugins,
-                    &self.search_app,
-                    &response_writer,
-                    search_pb_shared,
-                )?
-            }
+        // with work stealing, queries run on a dedicated pool of `parallelism` threads
+        let scheduler = override_config_opt
+            .as_ref()
+            .and_then(|c| c.scheduler)
+            .or(self.system_parameters.scheduler)
+            .unwrap_or_default();
+        let pool = match scheduler {
+            QueryScheduler::Static => None,
+            QueryScheduler::WorkStealing => Some(
+                rayon::ThreadPoolBuilder::new()
+                    .num_threads(parallelism)
+                    .build()
+                    .map_err(|e| {
+                        CompassAppError::InternalError(format!(
+                            "could not build work-stealing thread pool: {e}"
+                        ))
+                    })?,
+            ),
         };
+
+        let run_query_result = ops::install(pool.as_ref(), || {
+            let mut run_query_result: Vec<Value> = vec![];
+            for (window, duplicates) in windows.into_iter() {
+                // the legs of each trip chain run in order as a single query group
+                let (chains, window) = trip_chain::group_chains(window);
+                if !chains.is_empty() {
+                    log::info!("trip chaining: running {} chains of legs", chains.len());
+                }
+                let query_groups = match &one_to_many {
+                    Some(one_to_many_config) => {
+                        let groups = one_to_many_config.group_queries(window);
+                        let n_shared = groups.iter().filter(|g| g.len() > 1).count();
+                        log::info!(
+                            "one-to-many: {n_shared} of {} query groups share a search",
+                            groups.len()
+                        );
+                        groups
+                    }
+                    None => window.into_iter().map(|q| vec![q]).collect(),
+                };
+                let query_groups = chains.into_iter().chain(query_groups).collect();
+                let mut load_balanced_inputs = match scheduler {
+                    QueryScheduler::Static => {
+                        log::info!(
+                            "creating {} parallel batches across {} threads to run queries",
+                            parallelism,
+                            current_num_threads(),
+                        );
+                        ops::apply_group_load_balancing_policy(query_groups, parallelism, 1.0)?
+                    }
+                    QueryScheduler::WorkStealing => {
+                        log::info!(
+                            "scheduling {} query groups across {} work-stealing threads",
+                            query_groups.len(),
+                            parallelism,
+                        );
+                        ops::apply_work_stealing_policy(query_groups, 1.0)?
+                    }
+                };
+                let proc_batch_sizes = load_balanced_inputs
+                    .iter()
+                    .map(|groups| groups.iter().map(|g| g.len()).sum::<usize>())
+                    .collect::<Vec<_>>();
+                if scheduler == QueryScheduler::Static {
+                    log::info!("queries assigned per executor: {proc_batch_sizes:?}");
+                }
+
+                // run parallel searches as organized by the (optional) load balancing policy
+                // across a thread pool managed by rayon
+                if in_input_order {
+                    let window_responses = ops::run_batch_with_responses(
+                        &mut load_balanced_inputs,
+                        &self.output_plugins,
+                        &self.search_app,
+                        self.response_cache.as_deref(),
+                        &ResponseSink::None,
+                        &duplicates,
+                        search_pb_shared.clone(),
+                        Some(&run_metrics),
+                    )?;
+                    let ordered = ops::write_responses_in_input_order(
+                        window_responses.collect(),
+                        &response_writer,
+                        Some(&run_metrics),
+                    )?;
+                    if let ResponsePersistencePolicy::PersistResponseInMemory =
+                        response_persistence_policy
+                    {
+                        run_query_result.extend(ordered);
+                    }
+                    continue;
+                }
+                let window_result = match response_persistence_policy {
+                    ResponsePersistencePolicy::PersistResponseInMemory => {
+                        ops::run_batch_with_responses(
+                            &mut load_balanced_inputs,
+                            &self.output_plugins,
+                            &self.search_app,
+                            self.response_cache.as_deref(),
+                            &response_writer,
+                            &duplicates,
+                            search_pb_shared.clone(),
+                            Some(&run_metrics),
+                        )?
+                    }
+                    ResponsePersistencePolicy::DiscardResponseFromMemory => {
+                        ops::run_batch_without_responses(
+                            &mut load_balanced_inputs,
+                            &self.output_plugins,
+                            &self.search_app,
+                            self.response_cache.as_deref(),
+                            &response_writer,
+                            &duplicates,
+                            search_pb_shared.clone(),
+                            Some(&run_metrics),
+                        )?
+                    }
+                };
+                run_query_result.extend(window_result);
+            }
+            Ok::<_, CompassAppError>(run_query_result)
+        })?;
         eprintln!();
+        let close_span = Span::new("write")
+            .timing(Some(&run_metrics.write), 0)
+            .enter();
         response_writer.close()?;
+        drop(close_span);
+        run_metrics.plugins.log_report();
+        if let Some(cache) = &self.search_app.search_instance_cache {
+            let (hits, misses) = cache.stats();
+            log::info!("search instance cache: {hits} hits, {misses} misses");
+        }
+        if let Some(cache) = &self.response_cache {
+            let (hits, misses) = cache.stats();
+            log::info!("response cache: {hits} hits, {misses} misses");
+        }
+        run_metrics.write_report(run_span.elapsed())?;
 
         // combine successful runs along with any error rows for response
-        let run_result = run_query_result
+        let mut run_result: Vec<Value> = run_query_result
+            .into_iter()
             // .chain(mapped_errors)
             .chain(input_errors)
             .collect();
+        if in_input_order {
+            run_result.sort_by_key(ResponseOrderingConfig::response_input_index);
+        }
         Ok(run_result)
     }
 }
@@ -274,6 +749,7 @@ impl CompassApp {
         config: Option<&Value>,
     ) -> Result<Vec<Value>, CompassAppError> {
         let parallelism = self.get_parallelism(config)?;
+        let seed = self.get_seed(config)?;
         log::info!(
             "running {} map match queries with parallelism {} across {} threads",
             queries.len(),
@@ -281,7 +757,7 @@ impl CompassApp {
             current_num_threads(),
         );
         ops::run_batch(queries, parallelism, "map matching", |q| {
-            self.run_single_map_match(q)
+            self.run_single_map_match(q, seed)
         })
     }
 
@@ -297,12 +773,28 @@ impl CompassApp {
         })
     }
 
+    /// Runs a batch of re-planning requests in parallel. each request holds a previous
+    /// response, the current position of the vehicle and any observed state values, and
+    /// is re-planned to the destination of the previous response (see
+    /// [`compass_replan::run_single_replan`]).
+    pub fn run_replan(
+        &self,
+        requests: &[Value],
+        config: Option<&Value>,
+    ) -> Result<Vec<Value>, CompassAppError> {
+        let parallelism = self.get_parallelism(config)?;
+        ops::run_batch(requests, parallelism, "re-planning", |r| {
+            self.run_single_replan(r)
+        })
+    }
+
     /// Helper function that runs map matching on a single query and returns a JSON response.
-    fn run_single_map_match(&self, query: &Value) -> Value {
+    fn run_single_map_match(&self, query: &Value, seed: Option<u64>) -> Value {
         match map_matching_ops::run_single_map_match(
             query,
             &self.search_app,
             &self.map_matching_algorithm,
+            seed,
         ) {
             Ok(response) => response,
             Err(e) => serde_json::json!({
@@ -323,6 +815,17 @@ impl CompassApp {
         }
     }
 
+    /// Helper function that re-plans a single request and returns a JSON response.
+    fn run_single_replan(&self, request: &Value) -> Value {
+        match compass_replan::run_single_replan(request, &self.search_app, &self.output_plugins) {
+            Ok(response) => response,
+            Err(e) => serde_json::json!({
+                "request": request,
+                "error": e.to_string()
+            }),
+        }
+    }
+
     /// Helper to get parallelism from config or system parameters
     fn get_parallelism(&self, config: Option<&Value>) -> Result<usize, CompassAppError> {
         let override_config_opt: Option<CompassAppSystemParameters> = match config {
@@ -336,12 +839,120 @@ impl CompassApp {
             .unwrap_or(1);
         Ok(parallelism)
     }
+
+    /// Helper to get the global seed from config or system parameters
+    fn get_seed(&self, config: Option<&Value>) -> Result<Option<u64>, CompassAppError> {
+        let override_config_opt: Option<CompassAppSystemParameters> = match config {
+            Some(c) => serde_json::from_value(c.clone())?,
+            None => None,
+        };
+        let seed = override_config_opt
+            .as_ref()
+            .and_then(|c| c.seed)
+            .or(self.system_parameters.seed);
+        Ok(seed)
+    }
+}
+
+/// the graph and the components built from it
+struct LoadedGraph {
+    graph: Arc<Graph>,
+    map_model: Arc<MapModel>,
+    response_cache: Option<ResponseCache>,
+    heuristic: Option<FreeFlowHeuristic>,
+}
+
+/// builds the traversal and constraint models of each edge list concurrently
+fn build_edge_model_services(
+    config: &CompassAppConfig,
+    builder: &CompassBuilderInventory,
+) -> Result<EdgeModelServices, CompassAppError> {
+    let parent = span::current_span_id();
+    let (traversal_model_services, constraint_model_services) = rayon::join(
+        || {
+            span::with_parent(parent, || {
+                ops::with_timing("traversal models", || {
+                    config.build_traversal_model_services(builder)
+                })
+            })
+        },
+        || {
+            span::with_parent(parent, || {
+                ops::with_timing("constraint models", || {
+                    config.build_constraint_model_services(builder)
+                })
+            })
+        },
+    );
+    Ok(EdgeModelServices::new(
+        traversal_model_services?,
+        constraint_model_services?,
+    ))
+}
+
+/// loads the graph and the components built from it: the map model with its spatial
+/// index, the response cache and the A* heuristic. the components only read the graph,
+/// so they are built concurrently once it is loaded.
+fn load_graph(
+    config: &CompassAppConfig,
+    config_hash: Option<String>,
+) -> Result<LoadedGraph, CompassAppError> {
+    let graph = ops::with_timing("graph", || Ok(Arc::new(Graph::try_from(&config.graph)?)))?;
+
+    let parent = span::current_span_id();
+    let (map_model, (response_cache, heuristic)) = rayon::join(
+        || {
+            span::with_parent(parent, || {
+                ops::with_timing("map model", || {
+                    let mm = MapModel::new(graph.clone(), &config.mapping).map_err(|e| {
+                        CompassAppError::BuildFailure(format!(
+                            "unable to load MapModel from config: {e}"
+                        ))
+                    })?;
+                    Ok(Arc::new(mm))
+                })
+            })
+        },
+        || {
+            rayon::join(
+                || match &config.system.response_cache {
+                    Some(cache_config) => span::with_parent(parent, || {
+                        ops::with_timing("response cache", || {
+                            let config_hash = config_hash.clone().unwrap_or_default();
+                            ResponseCache::new(cache_config, &graph, config_hash)
+                        })
+                    })
+                    .map(Some),
+                    None => Ok(None),
+                },
+                || match config.algorithm.heuristic() {
+                    Some(heuristic_config) => span::with_parent(parent, || {
+                        ops::with_timing("heuristic", || {
+                            Ok(FreeFlowHeuristic::from_config(heuristic_config, &graph)?)
+                        })
+                    })
+                    .map(Some),
+                    None => Ok(None),
+                },
+            )
+        },
+    );
+    Ok(LoadedGraph {
+        graph,
+        map_model: map_model?,
+        response_cache: response_cache?,
+        heuristic: heuristic?,
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::CompassApp;
-    use crate::app::compass::CompassAppError;
+    use crate::app::compass::compass_app_test_ops::{
+        build_app, speeds_test_app, speeds_test_config, speeds_test_config_from_toml,
+        speeds_test_toml,
+    };
+    use crate::app::compass::{CompassAppError, CompassBuilderInventory};
     use routee_compass_core::config::CompassConfigurationError;
     use std::path::PathBuf;
 
@@ -419,6 +1030,23 @@ mod tests {
         assert_eq!(path_0, &expected_path);
     }
 
+    #[test]
+    fn test_free_flow_heuristic() {
+        let app = speeds_test_app(
+            "[algorithm]\ntype = \"a*\"\n[algorithm.heuristic]\ntype = \"free_flow\"\nspeed_table_input_file = \"src/app/compass/test/speeds_test/test_edge_speeds.csv\"\nspeed_unit = \"kph\"",
+        );
+        assert!(app.search_app.heuristic.is_some());
+
+        let mut queries = vec![serde_json::json!({
+            "origin_vertex": 0,
+            "destination_vertex": 2
+        })];
+        let result = app.run(&mut queries, None).expect("run failed");
+        // the heuristic is a lower bound, so the time-optimal route is still found
+        let path = &result[0]["route"]["path"];
+        assert_eq!(path, &serde_json::json!(vec![0, 2]));
+    }
+
     #[test]
     fn test_run_calculate_path() {
         let conf_file_test = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -462,4 +1090,365 @@ mod tests {
             "summary should have edge_time"
         );
     }
+    #[test]
+    fn test_reload_edge_attributes() {
+        let app = speeds_test_app("");
+        let run_path = |app: &CompassApp| {
+            let mut queries = vec![serde_json::json!({
+                "origin_vertex": 0,
+                "destination_vertex": 2
+            })];
+            let result = app.run(&mut queries, None).expect("run failed");
+            result[0]["route"]["path"].clone()
+        };
+        // path [0, 2] is time-optimal with the historical speeds
+        assert_eq!(run_path(&app), serde_json::json!([0, 2]));
+
+        // a traffic update where edge 1 becomes much faster makes path [1] time-optimal
+        let speeds_file = std::env::temp_dir().join("test_reload_edge_attributes_speeds.csv");
+        std::fs::write(&speeds_file, "112.0\n500.0\n112.0\n").expect("failed to write speeds");
+        let updated = speeds_test_config_from_toml(&speeds_test_toml().replace(
+            "src/app/compass/test/speeds_test/test_edge_speeds.csv",
+            &speeds_file.to_string_lossy(),
+        ));
+        let builder = CompassBuilderInventory::new().unwrap();
+        app.reload_edge_attributes(&updated, &builder)
+            .expect("reload failed");
+        let _ = std::fs::remove_file(&speeds_file);
+        assert_eq!(run_path(&app), serde_json::json!([1]));
+    }
+    #[test]
+    fn test_default_load_balancer() {
+        let n_load_balancers = |app: &CompassApp| {
+            app.input_plugins
+                .iter()
+                .filter(|p| p.name() == "load_balancer")
+                .count()
+        };
+        let app = speeds_test_app("");
+        assert_eq!(n_load_balancers(&app), 0);
+
+        let app = speeds_test_app("[system]\ndefault_load_balancer = true\n");
+        assert_eq!(n_load_balancers(&app), 1);
+    }
+
+    #[test]
+    fn test_validate_config() {
+        let builder = CompassBuilderInventory::new().unwrap();
+        let mut config = speeds_test_config("");
+        let errors = CompassApp::validate_config(&config, &builder, true);
+        assert!(errors.is_empty(), "unexpected errors: {errors:?}");
+
+        // every invalid component is reported, not just the first
+        config
+            .plugin
+            .input_plugins
+            .push(serde_json::json!({"type": "bogus_input"}));
+        config
+            .plugin
+            .output_plugins
+            .push(serde_json::json!({"type": "bogus_output"}));
+        config.map_matching = serde_json::json!({"type": "bogus_map_matching"});
+        let errors = CompassApp::validate_config(&config, &builder, false);
+        assert_eq!(errors.len(), 3, "unexpected errors: {errors:?}");
+        assert!(errors[0].starts_with("input plugin"));
+        assert!(errors[1].starts_with("output plugin"));
+        assert!(errors[2].starts_with("map matching algorithm"));
+    }
+
+    #[test]
+    fn test_clip_graph() {
+        let clip_dir = tempfile::tempdir().unwrap();
+        // keeps vertices 0 and 1 and the edge between them, dropping vertex 2
+        let conf_str_with_clip = format!(
+            "{}\n[graph.clip]\nbbox = [-105.5, 39.0, -104.5, 42.0]\noutput_directory = {:?}",
+            speeds_test_toml().replace(
+                "[graph.edge_list]\n",
+                "[graph.edge_list]\nattribute_input_files = [\"src/app/compass/test/speeds_test/test_edge_speeds.csv\"]\n",
+            ),
+            clip_dir.path().to_str().unwrap(),
+        );
+        let app = build_app(&speeds_test_config_from_toml(&conf_str_with_clip));
+
+        let read_lines = |filename: &str| {
+            std::fs::read_to_string(clip_dir.path().join(filename))
+                .unwrap()
+                .lines()
+                .map(String::from)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(read_lines("test_vertices.csv").len(), 3);
+        assert_eq!(
+            read_lines("edge_list_0_test_edges.csv"),
+            vec![
+                "edge_id,src_vertex_id,dst_vertex_id,road_class,distance,grade",
+                "0,0,1,3,175381,0"
+            ]
+        );
+        assert_eq!(
+            read_lines("edge_list_0_test_edge_speeds.csv"),
+            vec!["112.0"]
+        );
+
+        let mut queries = vec![
+            serde_json::json!({"origin_vertex": 0, "destination_vertex": 1}),
+            serde_json::json!({"origin_vertex": 0, "destination_vertex": 2}),
+        ];
+        let responses = app.run(&mut queries, None).expect("run failed");
+        let find = |destination: i64| {
+            responses
+                .iter()
+                .find(|r| r["request"]["destination_vertex"] == destination)
+                .unwrap()
+        };
+        assert_eq!(find(1)["route"]["path"], serde_json::json!([0]));
+        assert!(find(2).get("error").is_some());
+    }
+
+    #[test]
+    fn test_geoparquet_graph() {
+        use arrow::array::{ArrayRef, BinaryArray, Float64Array, Int64Array};
+        use arrow::record_batch::RecordBatch;
+        use geo::{line_string, point, Geometry};
+        use parquet::arrow::ArrowWriter;
+        use std::sync::Arc;
+
+        let write_parquet = |path: &std::path::Path, columns: Vec<(&str, ArrayRef)>| {
+            let batch = RecordBatch::try_from_iter(columns).unwrap();
+            let file = std::fs::File::create(path).unwrap();
+            let mut writer = ArrowWriter::try_new(file, batch.schema(), None).unwrap();
+            writer.write(&batch).unwrap();
+            writer.close().unwrap();
+        };
+        let to_wkb = |geometries: Vec<Geometry<f64>>| -> ArrayRef {
+            let wkb = geometries
+                .iter()
+                .map(|g| {
+                    let mut bytes = vec![];
+                    wkb::writer::write_geometry(&mut bytes, g, &Default::default()).unwrap();
+                    bytes
+                })
+                .collect::<Vec<_>>();
+            Arc::new(BinaryArray::from_iter_values(wkb))
+        };
+
+        // the vertices and edges of the speeds test graph
+        let coords = [
+            (-105.1683038, 39.7379033),
+            (-104.8086039, 41.1475252),
+            (-111.9095014, 40.7607176),
+        ];
+        let edges = [(0, 1, 175381.0), (0, 2, 772320.0), (1, 2, 707960.0)];
+        let data_dir = tempfile::tempdir().unwrap();
+        let vertex_file = data_dir.path().join("vertices.parquet");
+        let edge_file = data_dir.path().join("edges.parquet");
+        write_parquet(
+            &vertex_file,
+            vec![
+                (
+                    "vertex_id",
+                    Arc::new(Int64Array::from(vec![0, 1, 2])) as ArrayRef,
+                ),
+                (
+                    "geometry",
+                    to_wkb(
+                        coords
+                            .iter()
+                            .map(|(x, y)| Geometry::Point(point!(x: *x, y: *y)))
+                            .collect(),
+                    ),
+                ),
+            ],
+        );
+        write_parquet(
+            &edge_file,
+            vec![
+                (
+                    "u",
+                    Arc::new(Int64Array::from_iter_values(edges.iter().map(|e| e.0))) as ArrayRef,
+                ),
+                (
+                    "v",
+                    Arc::new(Int64Array::from_iter_values(edges.iter().map(|e| e.1))),
+                ),
+                (
+                    "length",
+                    Arc::new(Float64Array::from_iter_values(edges.iter().map(|e| e.2))),
+                ),
+                (
+                    "geometry",
+                    to_wkb(
+                        edges
+                            .iter()
+                            .map(|(u, v, _)| {
+                                let ((x0, y0), (x1, y1)) =
+                                    (coords[*u as usize], coords[*v as usize]);
+                                Geometry::LineString(line_string![(x: x0, y: y0), (x: x1, y: y1)])
+                            })
+                            .collect(),
+                    ),
+                ),
+            ],
+        );
+
+        let conf_str = speeds_test_toml();
+        let conf_str_with_geoparquet = format!(
+            "{}\n[mapping]\ngeometry = {{ type = \"from_linestrings\", geometry_input_file = {:?} }}",
+            conf_str
+                .replace(
+                    "vertex_list_input_file = \"src/app/compass/test/speeds_test/test_vertices.csv\"",
+                    &format!(
+                        "vertex_list_input_file = {:?}\nvertex_list_columns = {{ vertex_id = \"vertex_id\" }}",
+                        vertex_file.to_str().unwrap()
+                    ),
+                )
+                .replace(
+                    "input_file = \"src/app/compass/test/speeds_test/test_edges.csv\"",
+                    &format!(
+                        "input_file = {:?}\ncolumns = {{ src_vertex_id = \"u\", dst_vertex_id = \"v\", distance = \"length\" }}",
+                        edge_file.to_str().unwrap()
+                    ),
+                ),
+            edge_file.to_str().unwrap(),
+        );
+        let run = |conf_str: &str| {
+            let app = build_app(&speeds_test_config_from_toml(conf_str));
+            let mut queries =
+                vec![serde_json::json!({"origin_vertex": 0, "destination_vertex": 2})];
+            app.run(&mut queries, None).expect("run failed").remove(0)
+        };
+        let csv_response = run(&conf_str);
+        let geoparquet_response = run(&conf_str_with_geoparquet);
+        assert_eq!(
+            geoparquet_response["route"]["path"],
+            serde_json::json!([0, 2])
+        );
+        assert_eq!(
+            geoparquet_response["route"]["path"],
+            csv_response["route"]["path"]
+        );
+        assert_eq!(
+            geoparquet_response["route"]["traversal_summary"],
+            csv_response["route"]["traversal_summary"]
+        );
+    }
+
+    #[test]
+    fn test_state_variable_audit() {
+        let conf_str = speeds_test_toml();
+        let builder = CompassBuilderInventory::new().unwrap();
+
+        // a time rate on the trip distance fails at load
+        let mismatched = conf_str.replace(
+            "[cost.vehicle_rates.trip_distance]\ntype = \"raw\"",
+            "[cost.vehicle_rates.trip_distance]\ntype = \"time\"\nfactor = 1.0\nunit = \"minutes\"",
+        );
+        assert_ne!(mismatched, conf_str);
+        let config = speeds_test_config_from_toml(&mismatched);
+        let error = CompassApp::new(&config, &builder)
+            .err()
+            .expect("expected the audit to fail")
+            .to_string();
+        assert!(
+            error.contains("'trip_distance' is produced as distance (miles) but consumed by 'cost model' as time (minutes)"),
+            "{error}"
+        );
+        assert!(error.contains("feature dependency graph"), "{error}");
+
+        // and at query time when set by a query
+        let app = speeds_test_app("");
+        let mut queries = vec![serde_json::json!({
+            "origin_vertex": 0,
+            "destination_vertex": 2,
+            "vehicle_rates": {"trip_distance": {"type": "time", "factor": 1.0, "unit": "hours"}}
+        })];
+        let responses = app.run(&mut queries, None).expect("run failed");
+        let error = responses[0]["error"].as_str().unwrap_or_default();
+        assert!(
+            error.contains("'trip_distance' is produced as distance"),
+            "{}",
+            responses[0]
+        );
+    }
+
+    #[test]
+    fn test_state_variable_audit_is_not_cached() {
+        let app = speeds_test_app("[system.search_instance_cache]\ncapacity = 4");
+        let cache = app
+            .search_app
+            .search_instance_cache
+            .as_ref()
+            .expect("cache should be enabled");
+        assert!(cache.is_empty());
+        assert_eq!(cache.stats(), (0, 0));
+
+        let mut queries = vec![serde_json::json!({"origin_vertex": 0, "destination_vertex": 2})];
+        let responses = app.run(&mut queries, None).expect("run failed");
+        assert!(responses[0].get("error").is_none(), "{}", responses[0]);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.stats(), (0, 1));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_model_failure_does_not_wait_for_graph() {
+        // the vertex list is a named pipe without a writer, so the graph never finishes
+        // loading until the pipe is opened below
+        let dir = tempfile::tempdir().unwrap();
+        let vertices = dir.path().join("vertices.csv");
+        let mkfifo = std::process::Command::new("mkfifo")
+            .arg(&vertices)
+            .status()
+            .expect("failed to run mkfifo");
+        assert!(mkfifo.success());
+        let speeds = dir.path().join("speeds.csv");
+        std::fs::write(&speeds, "not a speed\n").unwrap();
+        let mut config = speeds_test_config_from_toml(&speeds_test_toml().replace(
+            "src/app/compass/test/speeds_test/test_edge_speeds.csv",
+            &speeds.to_string_lossy(),
+        ));
+        // set after reading the config, which only accepts regular files
+        config.graph.vertex_list_input_file = vertices.to_string_lossy().to_string();
+        // the models are built on their own pool, as the graph blocks a thread of the
+        // global pool while it reads the pipe
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(2)
+            .build()
+            .unwrap();
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let builder = CompassBuilderInventory::new().unwrap();
+            let result = pool.install(|| CompassApp::new(&config, &builder).map(|_| ()));
+            let _ = tx.send(result);
+        });
+        let result = rx.recv_timeout(std::time::Duration::from_secs(30));
+
+        // closing the pipe ends the vertex list, so the graph loading thread finishes
+        drop(
+            std::fs::OpenOptions::new()
+                .write(true)
+                .open(&vertices)
+                .unwrap(),
+        );
+        let result = result.expect("app waited for the graph after a model failed");
+        let error = result.expect_err("app should fail").to_string();
+        assert!(error.contains("not a speed"), "{error}");
+    }
+
+    #[test]
+    fn test_graph_failure_is_returned() {
+        let dir = tempfile::tempdir().unwrap();
+        let vertices = dir.path().join("vertices.csv");
+        std::fs::write(&vertices, "vertex_id,x,y\n0,not a coordinate,0.0\n").unwrap();
+        let config = speeds_test_config_from_toml(&speeds_test_toml().replace(
+            "src/app/compass/test/speeds_test/test_vertices.csv",
+            &vertices.to_string_lossy(),
+        ));
+        let builder = CompassBuilderInventory::new().unwrap();
+        let error = CompassApp::new(&config, &builder)
+            .err()
+            .expect("app should fail")
+            .to_string();
+        assert!(error.contains("not a coordinate"), "{error}");
+    }
 }
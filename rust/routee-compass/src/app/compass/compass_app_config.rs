@@ -1,20 +1,36 @@
-use std::{path::Path, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+    sync::Arc,
+};
 
 use config::Config;
+use rayon::prelude::*;
 use routee_compass_core::{
     algorithm::search::SearchAlgorithmConfig,
     config::{ConfigJsonExtensions, OneOrMany},
     model::{
-        constraint::ConstraintModelService, cost::CostModelConfig, map::MapModelConfig,
-        network::GraphConfig, state::StateVariableConfig, termination::TerminationModel,
+        constraint::ConstraintModelService,
+        cost::CostModelConfig,
+        map::MapModelConfig,
+        network::{clip::clip_graph, GraphConfig},
+        state::StateVariableConfig,
+        termination::TerminationModel,
         traversal::TraversalModelService,
     },
+    util::span,
 };
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::Value;
 
 use crate::{
-    app::compass::{CompassAppError, CompassAppSystemParameters, CompassBuilderInventory},
+    app::{
+        compass::{
+            compass_app_config_ops, compass_app_ops as ops, remote_files, CompassAppError,
+            CompassAppSystemParameters, CompassBuilderInventory,
+        },
+        search::query_parameters_key,
+    },
     plugin::PluginConfig,
 };
 
@@ -46,7 +62,9 @@ pub struct SearchConfig {
 }
 
 impl CompassAppConfig {
-    /// reads a stringified configuration file with provided format and constructs a [`CompassAppConfig`]
+    /// reads a stringified configuration file with provided format and constructs a [`CompassAppConfig`].
+    /// `${ENV_VAR}` references are interpolated and files listed under `include` are merged
+    /// beneath it, resolved relative to `config_path`.
     pub fn from_str(
         config: &str,
         config_path: &str,
@@ -57,41 +75,61 @@ impl CompassAppConfig {
             config::FileFormat::Toml,
         );
 
-        let user_config = config::File::from_str(config, format);
+        let user_json =
+            compass_app_config_ops::read_user_config(config, format, Path::new(config_path))?;
+        let user_config = config::File::from_str(&user_json.to_string(), config::FileFormat::Json);
 
         let config = Config::builder()
             .add_source(default_config)
             .add_source(user_config)
             .build()?;
 
-        let config_json = config
-            .clone()
-            .try_deserialize::<serde_json::Value>()?
-            .normalize_file_paths(Path::new(config_path), None)?;
+        let config_json = remote_files::resolve_remote_files(
+            config.clone().try_deserialize::<serde_json::Value>()?,
+        )?
+        .normalize_file_paths(Path::new(config_path), None)?;
         let compass_config: CompassAppConfig = serde_json::from_value(config_json)?;
 
         Ok(compass_config)
     }
 }
 
+/// replaces the config values naming a file with the path of the file it was replaced by
+fn replace_files<T: Serialize + DeserializeOwned>(
+    section: &T,
+    replaced_files: &HashMap<String, String>,
+) -> Result<T, CompassAppError> {
+    let mut json = serde_json::to_value(section).map_err(|e| {
+        CompassAppError::BuildFailure(format!("failure serializing config section: {e}"))
+    })?;
+    compass_app_config_ops::replace_strings(&mut json, replaced_files);
+    serde_json::from_value(json).map_err(|e| {
+        CompassAppError::BuildFailure(format!("failure reading clipped config section: {e}"))
+    })
+}
+
 impl TryFrom<&Path> for CompassAppConfig {
     type Error = CompassAppError;
 
+    /// reads a configuration file, interpolating `${ENV_VAR}` references and merging any
+    /// files listed under `include` beneath it.
     fn try_from(config_path: &Path) -> Result<Self, Self::Error> {
         let default_config = config::File::from_str(
             include_str!("config.default.toml"),
             config::FileFormat::Toml,
         );
+        let user_json = compass_app_config_ops::read_user_config_file(config_path)?;
+        let user_config = config::File::from_str(&user_json.to_string(), config::FileFormat::Json);
 
         let config = Config::builder()
             .add_source(default_config)
-            .add_source(config::File::from(config_path))
+            .add_source(user_config)
             .build()?;
 
-        let config_json = config
-            .clone()
-            .try_deserialize::<serde_json::Value>()?
-            .normalize_file_paths(config_path, None)?;
+        let config_json = remote_files::resolve_remote_files(
+            config.clone().try_deserialize::<serde_json::Value>()?,
+        )?
+        .normalize_file_paths(config_path, None)?;
         let compass_config: CompassAppConfig =
             serde_json::from_value(config_json).map_err(|e| {
                 let filename = config_path.to_str().unwrap_or("<config path>");
@@ -103,6 +141,18 @@ impl TryFrom<&Path> for CompassAppConfig {
 }
 
 impl CompassAppConfig {
+    /// hash of the JSON representation of this config with object keys sorted,
+    /// hex-encoded. two apps built from configs with the same hash read the same files
+    /// with the same parameters.
+    pub fn hash(&self) -> Result<String, CompassAppError> {
+        let json = serde_json::to_value(self).map_err(|e| {
+            CompassAppError::BuildFailure(format!("Failed to serialize config: {}", e))
+        })?;
+        let key = query_parameters_key(&json, &HashSet::new());
+        let hash = twox_hash::xxhash3_128::Hasher::oneshot(key.as_bytes());
+        Ok(format!("{hash:032x}"))
+    }
+
     /// Returns a pretty-printed JSON representation of this config.
     /// Useful for debugging and logging the exact configuration being used.
     pub fn to_pretty_string(&self) -> Result<String, CompassAppError> {
@@ -111,26 +161,73 @@ impl CompassAppConfig {
         })
     }
 
+    /// if the graph has a clip region, writes the clipped graph files and returns a copy
+    /// of this config that reads them. every value of the search, cost, label, mapping,
+    /// plugin and map matching sections naming one of the clipped files, such as the
+    /// edge-indexed tables of the traversal models, is replaced with the path of its
+    /// clipped copy. otherwise, returns a copy of this config.
+    pub fn with_clipped_graph(&self) -> Result<CompassAppConfig, CompassAppError> {
+        let Some(clip) = &self.graph.clip else {
+            return Ok(self.clone());
+        };
+        let clipped = clip_graph(&self.graph, clip)?;
+        let files = &clipped.replaced_files;
+        Ok(CompassAppConfig {
+            graph: clipped.config.clone(),
+            search: replace_files(&self.search, files)?,
+            cost: replace_files(&self.cost, files)?,
+            label: replace_files(&self.label, files)?,
+            mapping: replace_files(&self.mapping, files)?,
+            plugin: replace_files(&self.plugin, files)?,
+            map_matching: replace_files(&self.map_matching, files)?,
+            ..self.clone()
+        })
+    }
+
+    /// builds the traversal model of each edge list. the models of the edge lists are
+    /// built concurrently.
     pub fn build_traversal_model_services(
         &self,
         builders: &CompassBuilderInventory,
     ) -> Result<Vec<Arc<dyn TraversalModelService>>, CompassAppError> {
+        let parent = span::current_span_id();
         let result = self
             .search
             .iter()
-            .map(|el| builders.build_traversal_model_service(&el.traversal))
+            .collect::<Vec<_>>()
+            .into_par_iter()
+            .enumerate()
+            .map(|(idx, el)| {
+                span::with_parent(parent, || {
+                    ops::with_timing(&format!("traversal model for edge list {idx}"), || {
+                        Ok(builders.build_traversal_model_service(&el.traversal)?)
+                    })
+                })
+            })
             .collect::<Result<Vec<_>, _>>()?;
         Ok(result)
     }
 
+    /// builds the constraint model of each edge list. the models of the edge lists are
+    /// built concurrently.
     pub fn build_constraint_model_services(
         &self,
         builders: &CompassBuilderInventory,
     ) -> Result<Vec<Arc<dyn ConstraintModelService>>, CompassAppError> {
+        let parent = span::current_span_id();
         let result = self
             .search
             .iter()
-            .map(|el| builders.build_constraint_model_service(&el.constraint))
+            .collect::<Vec<_>>()
+            .into_par_iter()
+            .enumerate()
+            .map(|(idx, el)| {
+                span::with_parent(parent, || {
+                    ops::with_timing(&format!("constraint model for edge list {idx}"), || {
+                        Ok(builders.build_constraint_model_service(&el.constraint)?)
+                    })
+                })
+            })
             .collect::<Result<Vec<_>, _>>()?;
         Ok(result)
     }
@@ -0,0 +1,307 @@
+use super::CompassAppError;
+use config::{Config, FileFormat};
+use routee_compass_core::config::{CompassConfigurationError, ConfigJsonExtensions};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// top-level key listing other configuration files to merge beneath this one
+pub const INCLUDE_KEY: &str = "include";
+
+/// reads a user configuration, interpolating `${ENV_VAR}` references in string values and
+/// deep merging any files listed under the top-level `include` key beneath it. included files
+/// are merged in order, so later includes override earlier ones, and the including file
+/// overrides them all. tables are merged key by key while arrays and values are replaced.
+///
+/// file paths in an included file are resolved relative to that file. paths in the root
+/// configuration are left for the caller to normalize.
+///
+/// # Arguments
+///
+/// * `config` - contents of the configuration
+/// * `format` - file format of the configuration
+/// * `config_path` - location of the configuration, used to resolve relative includes
+pub fn read_user_config(
+    config: &str,
+    format: FileFormat,
+    config_path: &Path,
+) -> Result<Value, CompassAppError> {
+    let mut visited = vec![canonical(config_path)];
+    read_config(config, format, config_path, &mut visited)
+}
+
+/// reads the configuration file at the provided path. see [`read_user_config`].
+pub fn read_user_config_file(config_path: &Path) -> Result<Value, CompassAppError> {
+    let contents = std::fs::read_to_string(config_path).map_err(|e| {
+        CompassConfigurationError::UserConfigurationError(format!(
+            "failed reading config file '{}': {e}",
+            config_path.display()
+        ))
+    })?;
+    read_user_config(&contents, file_format(config_path), config_path)
+}
+
+fn read_config(
+    config: &str,
+    format: FileFormat,
+    config_path: &Path,
+    visited: &mut Vec<PathBuf>,
+) -> Result<Value, CompassAppError> {
+    let value = Config::builder()
+        .add_source(config::File::from_str(config, format))
+        .build()?
+        .try_deserialize::<Value>()?;
+    let mut value = interpolate_env_vars(value, &|name| std::env::var(name).ok())?;
+    let includes = match value.as_object_mut() {
+        Some(obj) => obj.remove(INCLUDE_KEY),
+        None => None,
+    };
+    let include_paths = match includes {
+        None => vec![],
+        Some(Value::String(path)) => vec![path],
+        Some(Value::Array(paths)) => paths
+            .into_iter()
+            .map(|p| match p {
+                Value::String(path) => Ok(path),
+                other => Err(include_type_error(&other)),
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+        Some(other) => return Err(include_type_error(&other)),
+    };
+
+    let parent = config_path.parent().unwrap_or(Path::new(""));
+    let mut merged = Value::Object(serde_json::Map::new());
+    for include in include_paths {
+        let include_path = parent.join(&include);
+        let canonical_path = canonical(&include_path);
+        if visited.contains(&canonical_path) {
+            return Err(CompassConfigurationError::UserConfigurationError(format!(
+                "config file '{}' includes itself through '{include}'",
+                config_path.display()
+            ))
+            .into());
+        }
+        let contents = std::fs::read_to_string(&include_path).map_err(|e| {
+            CompassConfigurationError::UserConfigurationError(format!(
+                "failed reading config file '{}' included by '{}': {e}",
+                include_path.display(),
+                config_path.display()
+            ))
+        })?;
+        visited.push(canonical_path);
+        let included = read_config(
+            &contents,
+            file_format(&include_path),
+            &include_path,
+            visited,
+        )?
+        .normalize_file_paths(&include_path, None)?;
+        visited.pop();
+        deep_merge(&mut merged, included);
+    }
+    deep_merge(&mut merged, value);
+    Ok(merged)
+}
+
+/// merges overlay into base. objects are merged key by key, recursively, while all
+/// other values (including arrays) in the overlay replace those in the base.
+pub fn deep_merge(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Object(base_obj), Value::Object(overlay_obj)) => {
+            for (key, value) in overlay_obj {
+                match base_obj.get_mut(&key) {
+                    Some(existing) => deep_merge(existing, value),
+                    None => {
+                        base_obj.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// replaces `${NAME}` references in every string value with the value of the variable
+/// returned by the lookup. a reference may provide a fallback as `${NAME:-default}`.
+/// referencing a variable that is not set and has no fallback is an error.
+pub fn interpolate_env_vars(
+    value: Value,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) -> Result<Value, CompassConfigurationError> {
+    match value {
+        Value::String(s) => Ok(Value::String(interpolate_string(&s, lookup)?)),
+        Value::Array(values) => values
+            .into_iter()
+            .map(|v| interpolate_env_vars(v, lookup))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::Array),
+        Value::Object(obj) => obj
+            .into_iter()
+            .map(|(k, v)| interpolate_env_vars(v, lookup).map(|v| (k, v)))
+            .collect::<Result<serde_json::Map<_, _>, _>>()
+            .map(Value::Object),
+        other => Ok(other),
+    }
+}
+
+/// replaces every string value that is a key of the replacements with its value.
+pub fn replace_strings(value: &mut Value, replacements: &HashMap<String, String>) {
+    match value {
+        Value::String(s) => {
+            if let Some(replacement) = replacements.get(s) {
+                *s = replacement.clone();
+            }
+        }
+        Value::Array(values) => values
+            .iter_mut()
+            .for_each(|v| replace_strings(v, replacements)),
+        Value::Object(obj) => obj
+            .values_mut()
+            .for_each(|v| replace_strings(v, replacements)),
+        _ => {}
+    }
+}
+
+fn interpolate_string(
+    s: &str,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) -> Result<String, CompassConfigurationError> {
+    let mut result = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find("${") {
+        result.push_str(&rest[..start]);
+        let end = rest[start..].find('}').ok_or_else(|| {
+            CompassConfigurationError::UserConfigurationError(format!(
+                "unterminated environment variable reference in config value '{s}'"
+            ))
+        })?;
+        let reference = &rest[start + 2..start + end];
+        let (name, fallback) = match reference.split_once(":-") {
+            Some((name, fallback)) => (name, Some(fallback)),
+            None => (reference, None),
+        };
+        let replacement = match (lookup(name), fallback) {
+            (Some(value), _) => value,
+            (None, Some(fallback)) => fallback.to_string(),
+            (None, None) => {
+                return Err(CompassConfigurationError::UserConfigurationError(format!(
+                    "environment variable '{name}' referenced in config value '{s}' is not set"
+                )))
+            }
+        };
+        result.push_str(&replacement);
+        rest = &rest[start + end + 1..];
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+fn include_type_error(value: &Value) -> CompassAppError {
+    CompassConfigurationError::UserConfigurationError(format!(
+        "'{INCLUDE_KEY}' must be a file path or a list of file paths, found {value}"
+    ))
+    .into()
+}
+
+fn file_format(path: &Path) -> FileFormat {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("json") => FileFormat::Json,
+        Some("yaml") | Some("yml") => FileFormat::Yaml,
+        _ => FileFormat::Toml,
+    }
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+    use std::fs;
+
+    #[test]
+    fn test_deep_merge() {
+        let mut base = json!({
+            "graph": { "vertex_list_input_file": "v.csv", "verbose": false },
+            "cost": { "weights": { "trip_time": 1 } },
+            "plugin": { "output_plugins": [{ "type": "summary" }, { "type": "traversal" }] }
+        });
+        let overlay = json!({
+            "graph": { "verbose": true },
+            "cost": { "weights": { "trip_energy": 2 } },
+            "plugin": { "output_plugins": [{ "type": "traversal" }] }
+        });
+        deep_merge(&mut base, overlay);
+        assert_eq!(
+            base,
+            json!({
+                "graph": { "vertex_list_input_file": "v.csv", "verbose": true },
+                "cost": { "weights": { "trip_time": 1, "trip_energy": 2 } },
+                "plugin": { "output_plugins": [{ "type": "traversal" }] }
+            })
+        );
+    }
+
+    #[test]
+    fn test_interpolate_env_vars() {
+        let lookup = |name: &str| match name {
+            "DATA_DIR" => Some(String::from("/data")),
+            _ => None,
+        };
+        let value = json!({
+            "input_file": "${DATA_DIR}/edges.csv",
+            "list": ["${REGION:-denver}", 3],
+            "plain": "no references"
+        });
+        let result = interpolate_env_vars(value, &lookup).unwrap();
+        assert_eq!(
+            result,
+            json!({
+                "input_file": "/data/edges.csv",
+                "list": ["denver", 3],
+                "plain": "no references"
+            })
+        );
+        assert!(interpolate_env_vars(json!("${MISSING}"), &lookup).is_err());
+        assert!(interpolate_env_vars(json!("${DATA_DIR"), &lookup).is_err());
+    }
+
+    #[test]
+    fn test_read_user_config_with_includes() {
+        let dir = tempfile::tempdir().unwrap();
+        let base_dir = dir.path().join("base");
+        fs::create_dir_all(&base_dir).unwrap();
+        fs::write(base_dir.join("edges.csv"), "").unwrap();
+        fs::write(
+            base_dir.join("network.toml"),
+            r#"
+            [graph.edge_list]
+            input_file = "edges.csv"
+            [cost.weights]
+            trip_distance = 1
+            trip_time = 1
+            "#,
+        )
+        .unwrap();
+        let config_path = dir.path().join("app.toml");
+        let config = r#"
+            include = ["base/network.toml"]
+            [cost.weights]
+            trip_time = 5
+        "#;
+        let result = read_user_config(config, FileFormat::Toml, &config_path).unwrap();
+        assert_eq!(
+            result["cost"]["weights"],
+            json!({ "trip_distance": 1, "trip_time": 5 })
+        );
+        assert!(result.get(INCLUDE_KEY).is_none());
+        let edges = result["graph"]["edge_list"]["input_file"].as_str().unwrap();
+        assert_eq!(Path::new(edges), base_dir.join("edges.csv"));
+
+        // a file that includes itself is rejected
+        fs::write(&config_path, "include = \"app.toml\"").unwrap();
+        assert!(read_user_config_file(&config_path).is_err());
+    }
+}
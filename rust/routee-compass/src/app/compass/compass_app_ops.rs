@@ -1,10 +1,13 @@
-use crate::app::compass::CompassAppError;
+use crate::app::compass::{
+    response_cache::ResponseCache, trip_chain, CompassAppError, PluginMetrics,
+    QueryDeduplicationConfig, QueryDuplicates, RunMetrics,
+};
 use crate::app::{
-    compass::response::response_sink::ResponseSink,
-    search::{SearchApp, SearchAppResult},
+    compass::response::{response_ordering::ResponseOrderingConfig, response_sink::ResponseSink},
+    search::{OneToManyConfig, SearchApp, SearchAppResult},
 };
 use crate::plugin::{
-    input::{input_plugin_ops as in_ops, InputJsonExtensions, InputPlugin},
+    input::{input_plugin_ops as in_ops, InputField, InputJsonExtensions, InputPlugin},
     output::{output_plugin_ops as out_ops, OutputPlugin},
     PluginError,
 };
@@ -18,7 +21,8 @@ use routee_compass_core::config::ConfigJsonExtensions;
 use routee_compass_core::model::network::{EdgeId, EdgeListId};
 use routee_compass_core::util::duration_extension::DurationExtension;
 use routee_compass_core::util::progress;
-use serde_json::Value;
+use routee_compass_core::util::span::{self, Span};
+use serde_json::{json, Value};
 use std::sync::{Arc, Mutex};
 
 /// Creates a shared progress bar wrapped in Arc<Mutex<>> for parallel processing.
@@ -60,27 +64,99 @@ pub fn apply_load_balancing_policy(
     parallelism: usize,
     default: f64,
 ) -> Result<Vec<Vec<serde_json::Value>>, CompassAppError> {
-    if queries.is_empty() {
+    let groups = queries.into_iter().map(|q| vec![q]).collect();
+    let assignments = apply_group_load_balancing_policy(groups, parallelism, default)?
+        .into_iter()
+        .map(|bin| bin.into_iter().flatten().collect())
+        .collect();
+    Ok(assignments)
+}
+
+/// orders groups of queries for a work-stealing scheduler, placing each group in its own
+/// bin so that threads take groups one at a time. groups are sorted by descending weight
+/// so that the slowest searches start first.
+///
+/// # Arguments
+///
+/// * `groups` - groups of user queries, such as queries sharing a one-to-many search
+/// * `default` - weight value if weight heuristic fails to produce an estimate
+///
+/// # Returns
+///
+/// One bin per group, heaviest first
+pub fn apply_work_stealing_policy(
+    groups: Vec<Vec<serde_json::Value>>,
+    default: f64,
+) -> Result<Vec<Vec<Vec<serde_json::Value>>>, CompassAppError> {
+    let mut weighted = groups
+        .into_iter()
+        .map(|group| {
+            let mut w = 0.0;
+            for q in group.iter() {
+                w += q.get_query_weight_estimate()?.unwrap_or(default);
+            }
+            Ok((OrderedFloat(w), group))
+        })
+        .collect::<Result<Vec<_>, CompassAppError>>()?;
+    weighted.sort_by_key(|(w, _)| std::cmp::Reverse(*w));
+    Ok(weighted.into_iter().map(|(_, g)| vec![g]).collect())
+}
+
+/// runs a function within a thread pool, if provided, or else on the current
+/// (global) rayon pool.
+pub fn install<T, F>(pool: Option<&rayon::ThreadPool>, f: F) -> T
+where
+    T: Send,
+    F: FnOnce() -> T + Send,
+{
+    match pool {
+        Some(p) => p.install(f),
+        None => f(),
+    }
+}
+
+/// applies the weight balancing policy to groups of queries that must be run together,
+/// such as queries sharing a one-to-many search. the weight of a group is the sum of
+/// the weights of its queries.
+///
+/// # Arguments
+///
+/// * `groups` - groups of user queries to load balance based on a query weight heuristic.
+/// * `parallelism` - number of chunks to split inputs into, set by user
+/// * `default` - weight value if weight heuristic fails to produce an estimate
+///
+/// # Returns
+///
+/// The groups assigned to each chunk. the resulting batches are not equal-sized
+pub fn apply_group_load_balancing_policy(
+    groups: Vec<Vec<serde_json::Value>>,
+    parallelism: usize,
+    default: f64,
+) -> Result<Vec<Vec<Vec<serde_json::Value>>>, CompassAppError> {
+    if groups.is_empty() {
         return Ok(vec![]);
     }
 
     let mut bin_totals = vec![0.0; parallelism];
-    let mut assignments: Vec<Vec<serde_json::Value>> = vec![vec![]; parallelism];
-    let n_queries = queries.len();
+    let mut assignments: Vec<Vec<Vec<serde_json::Value>>> = vec![vec![]; parallelism];
+    let n_queries = groups.iter().map(|g| g.len()).sum();
 
     let bar_builder = Bar::builder()
         .total(n_queries)
         .desc("load balancing")
         .animation("fillup");
     let mut bar_opt = progress::build_progress_bar(bar_builder);
-    for q in queries.into_iter() {
-        let w = q.get_query_weight_estimate()?.unwrap_or(default);
+    for group in groups.into_iter() {
+        let mut w = 0.0;
+        for q in group.iter() {
+            w += q.get_query_weight_estimate()?.unwrap_or(default);
+        }
         let min_bin = min_bin(&bin_totals)?;
         bin_totals[min_bin] += w;
-        assignments[min_bin].push(q);
         if let Some(ref mut bar) = bar_opt {
-            let _ = bar.update(1);
+            let _ = bar.update(group.len());
         }
+        assignments[min_bin].push(group);
     }
     Ok(assignments)
 }
@@ -97,12 +173,14 @@ fn min_bin(bins: &[f64]) -> Result<usize, PluginError> {
 
 /// executes the input plugins on each query, returning all
 /// successful mappings (left) and mapping errors (right) as the pair
-/// (left, right). errors are already serialized into JSON.
+/// (left, right). errors are already serialized into JSON. the wall time
+/// of each plugin is recorded to the metrics, if provided.
 pub fn apply_input_plugins(
     queries: &mut Vec<Value>,
     input_plugins: &[Arc<dyn InputPlugin>],
     search_app: Arc<SearchApp>,
     parallelism: usize,
+    metrics: Option<&PluginMetrics>,
 ) -> Result<(Vec<Value>, Vec<Value>), CompassAppError> {
     // result of each iteration of plugin updates is stored here
     let mut queries_processed = queries.drain(..).collect_vec();
@@ -114,17 +192,30 @@ pub fn apply_input_plugins(
         .position(0)
         .build()
         .map_err(CompassAppError::InternalError)?;
-    outer_bar.set_description("input plugins"); // until we have named plugins
+    outer_bar.set_description("input plugins");
 
     for (idx, plugin) in input_plugins.iter().enumerate() {
         // nested progress bar running for each query
-        // outer_bar.set_description(format!("{}", plugin.name));  // placeholder for named plugins
+        outer_bar.set_description(format!("input plugin '{}'", plugin.name()));
+        let n_queries = queries_processed.len();
+        let _span = Span::new("input_plugin")
+            .field("name", plugin.name())
+            .field("queries", n_queries)
+            .timing(
+                metrics.and_then(|m| m.input_plugins.get(idx)),
+                n_queries as u64,
+            )
+            .enter();
         let inner_bar = Arc::new(Mutex::new(
             Bar::builder()
                 .total(queries_processed.len())
                 .position(1)
                 .animation("fillup")
-                .desc(format!("applying input plugin {}", idx + 1))
+                .desc(format!(
+                    "applying input plugin {} '{}'",
+                    idx + 1,
+                    plugin.name()
+                ))
                 .build()
                 .map_err(|e| {
                     CompassAppError::InternalError(format!(
@@ -138,29 +229,42 @@ pub fn apply_input_plugins(
 
         // apply this input plugin in parallel, assigning the result back to `queries_processed`
         // and tracking any errors along the way.
+        let parent = span::current_span_id();
         let (good, bad): (Vec<Value>, Vec<Value>) = queries_processed
             .par_chunks_mut(chunk_size)
             .flat_map(|qs| {
-                qs.iter_mut()
-                    .flat_map(|q| {
-                        if let Ok(mut pb_local) = inner_bar.lock() {
-                            let _ = pb_local.update(1);
-                        }
-                        // run the input plugin and flatten the result if it is a JSON array
-                        let p = plugin.clone();
-                        match p.process(q, search_app.clone()) {
-                            Err(e) => vec![in_ops::package_error(&mut q.clone(), e)],
-                            Ok(_) => in_ops::unpack_json_array_as_vec(q),
-                        }
-                    })
-                    .collect_vec()
+                span::with_parent(parent, || {
+                    qs.iter_mut()
+                        .flat_map(|q| {
+                            if let Ok(mut pb_local) = inner_bar.lock() {
+                                let _ = pb_local.update(1);
+                            }
+                            // queries marked complete by an earlier plugin pass through
+                            if q.is_input_plugins_complete() {
+                                return vec![q.clone()];
+                            }
+                            // run the input plugin and flatten the result if it is a JSON array
+                            let p = plugin.clone();
+                            match p.process(q, search_app.clone()) {
+                                Err(e) => vec![in_ops::package_error(&mut q.clone(), e)],
+                                Ok(_) => in_ops::unpack_json_array_as_vec(q),
+                            }
+                        })
+                        .collect_vec()
+                })
             })
             .partition(|row| !matches!(row.as_object(), Some(obj) if obj.contains_key("error")));
         queries_processed = good;
         query_errors.extend(bad);
+        let _ = outer_bar.update(1);
     }
     eprintln!();
     eprintln!();
+    for query in queries_processed.iter_mut() {
+        if let Some(obj) = query.as_object_mut() {
+            obj.remove(InputField::InputPluginsComplete.to_str());
+        }
+    }
 
     Ok((queries_processed, query_errors))
 }
@@ -192,6 +296,7 @@ where
 /// # Arguments
 ///
 /// * `query` - a single search query that has been processed by InputPlugins
+/// * `metrics` - if provided, records the search metrics and the time spent in each output plugin
 ///
 /// # Returns
 ///
@@ -200,35 +305,322 @@ pub fn run_single_query(
     query: &mut serde_json::Value,
     output_plugins: &[Arc<dyn OutputPlugin>],
     search_app: &SearchApp,
+    metrics: Option<&RunMetrics>,
 ) -> Result<serde_json::Value, CompassAppError> {
+    let mut query_span = Span::new("query");
+    if let Some(request_id) = query.get("request_id") {
+        query_span = query_span.field("request_id", request_id);
+    }
+    let _query_span = query_span.enter();
+    let search_span = Span::new("search")
+        .timing(metrics.map(|m| &m.search), 1)
+        .enter();
     let search_result = search_app.run(query);
-    let output = apply_output_processing(query, search_result, search_app, output_plugins);
+    drop(search_span);
+    if let Some(m) = metrics {
+        if let Ok((result, _)) = &search_result {
+            m.record_query(result);
+        }
+    }
+    let plugin_metrics = metrics.map(|m| &m.plugins);
+    let output = apply_output_processing(
+        query,
+        search_result,
+        search_app,
+        output_plugins,
+        plugin_metrics,
+    );
     Ok(output)
 }
 
-/// runs a query batch which has been sorted into parallel chunks
-/// and retains the responses from each search in memory.
+/// Helper function that runs CompassApp on a group of queries, sharing a single
+/// one-to-many search across the group when possible (see [`SearchApp::run_group`]).
+/// groups with a single query are run via [`run_single_query`], and trip chains via
+/// [`run_trip_chain`]. if a response cache is provided, queries found in the cache are
+/// not run, and the responses of the queries that are run are stored in the cache.
+/// trip chains bypass the cache, as each leg depends on the legs before it. responses
+/// are only stored if the live data read by the models, such as a speed feed, did not
+/// change while the queries ran.
+///
+/// # Arguments
+///
+/// * `queries` - a group of search queries that have been processed by InputPlugins
+/// * `response_cache` - if provided, the cache of responses consulted before search
+/// * `metrics` - if provided, records the search metrics and the time spent in each output plugin
+///
+/// # Returns
+///
+/// * The result of the search and post-processing for each query as a JSON object, or, an error
+pub fn run_query_group(
+    queries: &mut [serde_json::Value],
+    output_plugins: &[Arc<dyn OutputPlugin>],
+    search_app: &SearchApp,
+    response_cache: Option<&ResponseCache>,
+    metrics: Option<&RunMetrics>,
+) -> Result<Vec<serde_json::Value>, CompassAppError> {
+    if trip_chain::is_chain(queries) {
+        return run_trip_chain(queries, output_plugins, search_app, metrics);
+    }
+    let Some(cache) = response_cache else {
+        return run_uncached_query_group(queries, output_plugins, search_app, metrics);
+    };
+    let data_version = search_app.edge_model_services()?.data_version()?;
+    cache.sync_data_version(&data_version)?;
+    // keys are computed before search, which adds fields to the queries
+    let mut responses = vec![];
+    let mut uncached_keys = vec![];
+    let mut uncached = vec![];
+    for query in queries.iter() {
+        let key = cache.key(query, data_version.as_deref())?;
+        match cache.get(&key, query)? {
+            Some(response) => responses.push(response),
+            None => {
+                uncached_keys.push(key);
+                uncached.push(query.clone());
+            }
+        }
+    }
+    if !uncached.is_empty() {
+        let uncached_responses =
+            run_uncached_query_group(&mut uncached, output_plugins, search_app, metrics)?;
+        let unchanged = search_app.edge_model_services()?.data_version()? == data_version;
+        for (key, response) in uncached_keys.iter().zip(uncached_responses) {
+            if unchanged {
+                cache.put(key, &response)?;
+            }
+            responses.push(response);
+        }
+    }
+    Ok(responses)
+}
+
+/// runs a group of queries as described in [`run_query_group`], without a response cache.
+fn run_uncached_query_group(
+    queries: &mut [serde_json::Value],
+    output_plugins: &[Arc<dyn OutputPlugin>],
+    search_app: &SearchApp,
+    metrics: Option<&RunMetrics>,
+) -> Result<Vec<serde_json::Value>, CompassAppError> {
+    if let [query] = queries {
+        let response = run_single_query(query, output_plugins, search_app, metrics)?;
+        return Ok(vec![response]);
+    }
+    let _group_span = Span::new("query_group")
+        .field("queries", queries.len())
+        .enter();
+    let mut search_span = Span::new("search")
+        .timing(metrics.map(|m| &m.search), 0)
+        .enter();
+    let search_results = search_app.run_group(queries);
+    search_span.set_calls(search_results.len() as u64);
+    drop(search_span);
+    if let Some(m) = metrics {
+        for (result, _) in search_results.iter().flatten() {
+            m.record_query(result);
+        }
+    }
+    let plugin_metrics = metrics.map(|m| &m.plugins);
+    let responses = queries
+        .iter()
+        .zip(search_results)
+        .map(|(query, search_result)| {
+            let mut query_span = Span::new("query");
+            if let Some(request_id) = query.get("request_id") {
+                query_span = query_span.field("request_id", request_id);
+            }
+            let _query_span = query_span.enter();
+            apply_output_processing(
+                query,
+                search_result,
+                search_app,
+                output_plugins,
+                plugin_metrics,
+            )
+        })
+        .collect();
+    Ok(responses)
+}
+
+/// runs the legs of a trip chain in order, starting each leg from the state at the end
+/// of the leg before it, so that the trip time, distance and state of charge accumulate
+/// over a multi-leg tour. only accumulated state variables are carried over, and each
+/// must exist in the state model of the following leg. if a leg fails or finds no
+/// route, the legs after it are not run and respond with an error.
+///
+/// # Arguments
+///
+/// * `queries` - the legs of a trip chain, sorted by leg index, that have been processed by InputPlugins
+/// * `metrics` - if provided, records the search metrics and the time spent in each output plugin
+///
+/// # Returns
+///
+/// * The result of the search and post-processing for each leg as a JSON object, with a
+///   `chain` object holding the state the leg started from, or, an error
+pub fn run_trip_chain(
+    queries: &mut [serde_json::Value],
+    output_plugins: &[Arc<dyn OutputPlugin>],
+    search_app: &SearchApp,
+    metrics: Option<&RunMetrics>,
+) -> Result<Vec<serde_json::Value>, CompassAppError> {
+    let _chain_span = Span::new("trip_chain").field("legs", queries.len()).enter();
+    let plugin_metrics = metrics.map(|m| &m.plugins);
+    let mut initial_state: Option<serde_json::Map<String, Value>> = None;
+    let mut failed_leg: Option<Value> = None;
+    let mut responses = vec![];
+    for (position, query) in queries.iter_mut().enumerate() {
+        let mut query_span = Span::new("query");
+        if let Some(request_id) = query.get("request_id") {
+            query_span = query_span.field("request_id", request_id);
+        }
+        let _query_span = query_span.enter();
+        let leg_index = query
+            .get(trip_chain::LEG_INDEX_KEY)
+            .cloned()
+            .unwrap_or_else(|| json!(position));
+        let search_result = match &failed_leg {
+            Some(failed) => Err(CompassAppError::CompassFailure(format!(
+                "leg {failed} of this trip chain failed, so the state at the start of this leg is unknown"
+            ))),
+            None => {
+                let search_span = Span::new("search")
+                    .timing(metrics.map(|m| &m.search), 1)
+                    .enter();
+                let result = search_app.run_from_state(query, initial_state.as_ref());
+                drop(search_span);
+                if let Some(m) = metrics {
+                    if let Ok((result, _)) = &result {
+                        m.record_query(result);
+                    }
+                }
+                result
+            }
+        };
+        let next_state = match &search_result {
+            Ok((result, si)) => trip_chain::final_state(result, si).ok().flatten(),
+            Err(_) => None,
+        };
+        let mut response = apply_output_processing(
+            query,
+            search_result,
+            search_app,
+            output_plugins,
+            plugin_metrics,
+        );
+        response[trip_chain::CHAIN_KEY] = json!({
+            "chain_id": query.get(trip_chain::CHAIN_ID_KEY),
+            "leg_index": leg_index,
+            "initial_state": initial_state,
+        });
+        responses.push(response);
+        if next_state.is_none() && failed_leg.is_none() {
+            failed_leg = Some(leg_index);
+        }
+        initial_state = next_state;
+    }
+    Ok(responses)
+}
+
+/// removes the queries of a batch that are identical to an earlier query, ignoring the
+/// keys that only identify a query, and logs the fraction of queries removed.
+///
+/// # Arguments
+///
+/// * `queries` - search queries that have been processed by InputPlugins
+/// * `config` - the deduplication configuration, or None to keep every query
+///
+/// # Returns
+///
+/// The unique queries along with the duplicates of each, which receive a copy of its response
+pub fn apply_deduplication(
+    queries: Vec<Value>,
+    config: Option<&QueryDeduplicationConfig>,
+) -> (Vec<Value>, QueryDuplicates) {
+    let Some(config) = config else {
+        return (queries, QueryDuplicates::default());
+    };
+    let (unique, duplicates) = QueryDuplicates::deduplicate(queries, &config.ignore_keys());
+    log::info!(
+        "deduplication: {} of {} queries are duplicates (dedup ratio {:.4}), running {} unique queries",
+        duplicates.n_duplicates(),
+        duplicates.n_queries(),
+        duplicates.dedup_ratio(),
+        unique.len()
+    );
+    (unique, duplicates)
+}
+
+/// detects a batch of queries which share an origin (or a destination) and identical
+/// search parameters when the one-to-many mode is not configured, and logs how many
+/// queries could instead share a single search.
+///
+/// # Arguments
+///
+/// * `queries` - the queries of the batch
+/// * `one_to_many` - the one-to-many configuration of this run, if any
+///
+/// # Returns
+///
+/// The number of queries that could share a search, or zero if the mode is configured
+pub fn detect_shareable_searches(
+    queries: &[Value],
+    one_to_many: Option<&OneToManyConfig>,
+) -> usize {
+    if one_to_many.is_some() || queries.len() < 2 {
+        return 0;
+    }
+    let n_shareable = OneToManyConfig::count_shareable_queries(queries);
+    if n_shareable > 0 {
+        log::info!(
+            "{n_shareable} of {} queries share an origin or destination with identical parameters. \
+            configure [system.one_to_many] to answer each group with a single shared search",
+            queries.len()
+        );
+    }
+    n_shareable
+}
+
+/// runs a query batch which has been sorted into parallel chunks of query groups
+/// and retains the responses from each search in memory. responses are copied
+/// to the duplicates of their query.
+#[allow(clippy::too_many_arguments)]
 pub fn run_batch_with_responses(
-    load_balanced_inputs: &mut Vec<Vec<Value>>,
+    load_balanced_inputs: &mut Vec<Vec<Vec<Value>>>,
     output_plugins: &[Arc<dyn OutputPlugin>],
     search_app: &SearchApp,
+    response_cache: Option<&ResponseCache>,
     response_writer: &ResponseSink,
+    duplicates: &QueryDuplicates,
     pb: Arc<Mutex<Bar>>,
+    metrics: Option<&RunMetrics>,
 ) -> Result<Box<dyn Iterator<Item = Value>>, CompassAppError> {
+    // spans of the queries run on the rayon threads are nested under the caller's span
+    let parent = span::current_span_id();
     let run_query_result = load_balanced_inputs
         .par_iter_mut()
-        .map(|queries| {
-            queries
-                .iter_mut()
-                .map(|q| {
-                    let mut response = run_single_query(q, output_plugins, search_app)?;
-                    if let Ok(mut pb_local) = pb.lock() {
-                        let _ = pb_local.update(1);
+        .with_max_len(1)
+        .map(|groups| {
+            span::with_parent(parent, || {
+                let mut responses = vec![];
+                for group in groups.iter_mut() {
+                    let group_responses = run_query_group(
+                        group,
+                        output_plugins,
+                        search_app,
+                        response_cache,
+                        metrics,
+                    )?;
+                    for response in group_responses.into_iter() {
+                        if let Ok(mut pb_local) = pb.lock() {
+                            let _ = pb_local.update(1);
+                        }
+                        for mut response in duplicates.fan_out(response) {
+                            write_response(response_writer, &mut response, metrics)?;
+                            responses.push(response);
+                        }
                     }
-                    response_writer.write_response(&mut response)?;
-                    Ok(response)
-                })
-                .collect::<Result<Vec<serde_json::Value>, CompassAppError>>()
+                }
+                Ok(responses)
+            })
         })
         .collect::<Result<Vec<Vec<serde_json::Value>>, CompassAppError>>()?;
 
@@ -237,26 +629,45 @@ pub fn run_batch_with_responses(
     Ok(Box::new(run_result))
 }
 
-/// runs a query batch which has been sorted into parallel chunks.
-/// the search result is not persisted in memory.
+/// runs a query batch which has been sorted into parallel chunks of query groups.
+/// the search result is not persisted in memory. responses are copied to the
+/// duplicates of their query.
+#[allow(clippy::too_many_arguments)]
 pub fn run_batch_without_responses(
-    load_balanced_inputs: &mut Vec<Vec<Value>>,
+    load_balanced_inputs: &mut Vec<Vec<Vec<Value>>>,
     output_plugins: &[Arc<dyn OutputPlugin>],
     search_app: &SearchApp,
+    response_cache: Option<&ResponseCache>,
     response_writer: &ResponseSink,
+    duplicates: &QueryDuplicates,
     pb: Arc<Mutex<Bar>>,
+    metrics: Option<&RunMetrics>,
 ) -> Result<Box<dyn Iterator<Item = Value>>, CompassAppError> {
     // run the computations, discard values that do not trigger an error
+    let parent = span::current_span_id();
     let _ = load_balanced_inputs
         .par_iter_mut()
-        .map(|queries| {
-            queries.iter_mut().try_for_each(|q| {
-                let mut response = run_single_query(q, output_plugins, search_app)?;
-                if let Ok(mut pb_local) = pb.lock() {
-                    let _ = pb_local.update(1);
-                }
-                response_writer.write_response(&mut response)?;
-                Ok(())
+        .with_max_len(1)
+        .map(|groups| {
+            span::with_parent(parent, || {
+                groups.iter_mut().try_for_each(|group| {
+                    let responses = run_query_group(
+                        group,
+                        output_plugins,
+                        search_app,
+                        response_cache,
+                        metrics,
+                    )?;
+                    for response in responses.into_iter() {
+                        if let Ok(mut pb_local) = pb.lock() {
+                            let _ = pb_local.update(1);
+                        }
+                        for mut response in duplicates.fan_out(response) {
+                            write_response(response_writer, &mut response, metrics)?;
+                        }
+                    }
+                    Ok(())
+                })
             })
         })
         .collect::<Result<Vec<_>, CompassAppError>>()?;
@@ -264,24 +675,57 @@ pub fn run_batch_without_responses(
     Ok(Box::new(std::iter::empty::<Value>()))
 }
 
+/// sorts a set of responses by their input index and writes them to the response sink,
+/// returning the sorted responses. responses without an input index are written last.
+pub fn write_responses_in_input_order(
+    mut responses: Vec<Value>,
+    response_writer: &ResponseSink,
+    metrics: Option<&RunMetrics>,
+) -> Result<Vec<Value>, CompassAppError> {
+    responses.sort_by_key(|r| ResponseOrderingConfig::response_input_index(r).unwrap_or(u64::MAX));
+    for response in responses.iter_mut() {
+        write_response(response_writer, response, metrics)?;
+    }
+    Ok(responses)
+}
+
+/// writes a response, recording the time spent writing to the metrics if provided
+fn write_response(
+    response_writer: &ResponseSink,
+    response: &mut Value,
+    metrics: Option<&RunMetrics>,
+) -> Result<(), CompassAppError> {
+    let _span = Span::new("write")
+        .timing(metrics.map(|m| &m.write), 1)
+        .enter();
+    response_writer.write_response(response)?;
+    Ok(())
+}
+
 // helper that applies the output processing. this includes
 // 1. summarizing from the TraversalModel
-// 2. applying the output plugins
+// 2. applying the output plugins, recording the time spent in each to the metrics if provided
 pub fn apply_output_processing(
     request_json: &serde_json::Value,
     result: Result<(SearchAppResult, SearchInstance), CompassAppError>,
     search_app: &SearchApp,
     output_plugins: &[Arc<dyn OutputPlugin>],
+    metrics: Option<&PluginMetrics>,
 ) -> serde_json::Value {
     let mut initial: Value = match out_ops::create_initial_output(request_json, &result, search_app)
     {
         Ok(value) => value,
         Err(error_value) => return error_value,
     };
-    for output_plugin in output_plugins.iter() {
-        match output_plugin.process(&mut initial, &result) {
-            Ok(()) => {}
-            Err(e) => return out_ops::package_error(request_json, e),
+    for (idx, output_plugin) in output_plugins.iter().enumerate() {
+        let span = Span::new("output_plugin")
+            .field("name", output_plugin.name())
+            .timing(metrics.and_then(|m| m.output_plugins.get(idx)), 1)
+            .enter();
+        let plugin_result = output_plugin.process(&mut initial, &result);
+        drop(span);
+        if let Err(e) = plugin_result {
+            return out_ops::package_error(request_json, e);
         }
     }
 
@@ -338,19 +782,16 @@ where
     Ok(results)
 }
 
-/// helper function to wrap some lambda with runtime logging
+/// helper function to wrap some lambda with runtime logging and a `build` span
 pub fn with_timing<T>(
     name: &str,
     thunk: impl Fn() -> Result<T, CompassAppError>,
 ) -> Result<T, CompassAppError> {
-    let start = Local::now();
+    let span = Span::new("build").field("component", name).enter();
     let result = thunk();
-    let duration = (Local::now() - start)
-        .to_std()
-        .map_err(|e| CompassAppError::InternalError(e.to_string()))?;
     log::info!(
         "finished reading {name} with duration {}",
-        duration.hhmmss()
+        span.exit().hhmmss()
     );
     result
 }
@@ -391,7 +832,7 @@ pub fn run_single_calculate_path(
         })
         .collect::<Result<Vec<_>, CompassAppError>>()?;
 
-    let si = search_app.build_search_instance(query)?;
+    let si = search_app.get_search_instance(query)?;
     let start_time = Local::now();
 
     let edge_traversals = si
@@ -409,7 +850,10 @@ pub fn run_single_calculate_path(
         search_executed_time: start_time.to_rfc3339(),
         search_runtime: runtime,
         iterations: 0,
+        max_frontier_size: 0,
+        peak_tree_size: 0,
         terminated: None,
+        settled: vec![],
     };
 
     let response = apply_output_processing(
@@ -417,13 +861,14 @@ pub fn run_single_calculate_path(
         Ok((search_app_result, si)),
         search_app,
         output_plugins,
+        None,
     );
     Ok(response)
 }
 
 #[cfg(test)]
 mod test {
-    use super::apply_load_balancing_policy;
+    use super::{apply_load_balancing_policy, apply_work_stealing_policy};
     use crate::plugin::input::InputField;
     use serde_json::json;
 
@@ -513,4 +958,30 @@ mod test {
         let expected = vec![vec![0], vec![1, 4, 7, 10], vec![2, 5, 8, 11], vec![3, 6, 9]];
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_work_stealing_policy() {
+        let groups: Vec<Vec<serde_json::Value>> = vec![
+            vec![json!({"index": 0, InputField::QueryWeightEstimate.to_str(): 1})],
+            vec![
+                json!({"index": 1, InputField::QueryWeightEstimate.to_str(): 2}),
+                json!({"index": 2, InputField::QueryWeightEstimate.to_str(): 2}),
+            ],
+            vec![json!({"index": 3, InputField::QueryWeightEstimate.to_str(): 10})],
+            vec![json!({"index": 4})],
+        ];
+        let result = apply_work_stealing_policy(groups, 1.0)
+            .unwrap()
+            .iter()
+            .map(|bin| {
+                bin.iter()
+                    .flatten()
+                    .map(|q| q.get("index").unwrap().as_i64().unwrap())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        // one group per bin, heaviest first, ties kept in input order
+        let expected: Vec<Vec<i64>> = vec![vec![3], vec![1, 2], vec![0], vec![4]];
+        assert_eq!(result, expected);
+    }
 }
@@ -0,0 +1,315 @@
+use super::{compass_app_config_ops::INCLUDE_KEY, CompassAppError, CompassBuilderInventory};
+use crate::plugin::input::default::validate::default_query_schema;
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+
+const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// filename of the configuration schema written by [`write_schemas`]
+pub const CONFIG_SCHEMA_FILENAME: &str = "compass_config.schema.json";
+
+/// filename of the query schema written by [`write_schemas`]
+pub const QUERY_SCHEMA_FILENAME: &str = "compass_query.schema.json";
+
+/// JSON Schema (draft 2020-12) describing a [`super::CompassAppConfig`] file. the `type`
+/// of each component (traversal, constraint and label models, plugins and map matching
+/// algorithms) is restricted to the names registered with the builder, so components
+/// added by other crates through [`inventory`] are included.
+///
+/// sections are not required, since they may be provided by the default configuration or
+/// an included file, and component parameters beyond `type` are not described.
+pub fn config_schema(builder: &CompassBuilderInventory) -> Value {
+    let string_list = json!({"type": "array", "items": {"type": "string"}});
+    let vertex_columns = json!({
+        "type": "object",
+        "properties": {
+            "vertex_id": {"type": "string"},
+            "x": {"type": "string"},
+            "y": {"type": "string"},
+            "geometry": {"type": "string"}
+        },
+        "additionalProperties": false
+    });
+    let edge_columns = json!({
+        "type": "object",
+        "properties": {
+            "edge_id": {"type": "string"},
+            "src_vertex_id": {"type": "string"},
+            "dst_vertex_id": {"type": "string"},
+            "distance": {"type": "string"}
+        },
+        "additionalProperties": false
+    });
+    let edge_list = json!({
+        "type": "object",
+        "properties": {
+            "input_file": {"type": "string"},
+            "attribute_input_files": string_list,
+            "columns": edge_columns
+        },
+        "required": ["input_file"]
+    });
+    let srid = json!({"anyOf": [{"type": "integer"}, {"type": "string"}]});
+    let geometry = json!({
+        "type": "object",
+        "properties": {
+            "type": {"enum": ["from_vertices", "from_linestrings"]},
+            "geometry_input_file": {"type": "string"},
+            "geometry_column": {"type": "string"},
+            "srid": srid
+        },
+        "required": ["type"]
+    });
+    let search = json!({
+        "type": "object",
+        "properties": {
+            "traversal": component(&builder.traversal_model_names(), true),
+            "constraint": component(&builder.constraint_model_names(), true)
+        }
+    });
+    json!({
+        "$schema": JSON_SCHEMA_DIALECT,
+        "title": "RouteE Compass configuration",
+        "type": "object",
+        "properties": {
+            INCLUDE_KEY: {"anyOf": [{"type": "string"}, string_list]},
+            "algorithm": {
+                "type": "object",
+                "properties": {
+                    "type": {"enum": ["dijkstras", "a*", "svp", "yens", "penalty"]},
+                    "penalty_factor": {"type": "number", "exclusiveMinimum": 1.0},
+                    "max_searches": {"type": "integer", "minimum": 1},
+                    "admissibility": {
+                        "type": "object",
+                        "properties": {
+                            "max_sharing": {"type": "number", "minimum": 0.0, "maximum": 1.0},
+                            "max_stretch": {"type": "number", "minimum": 0.0},
+                            "local_optimality": {"type": "number", "minimum": 0.0, "maximum": 1.0}
+                        },
+                        "additionalProperties": false
+                    },
+                    "heuristic_weight": {"type": "number", "minimum": 1.0},
+                    "heuristic": {
+                        "type": "object",
+                        "properties": {
+                            "type": {"enum": ["free_flow"]},
+                            "speed_table_input_file": {"type": "string"},
+                            "speed_unit": {"type": "string"},
+                            "edge_list_id": {"type": "integer", "minimum": 0}
+                        },
+                        "required": ["type", "speed_table_input_file", "speed_unit"]
+                    }
+                }
+            },
+            "state": {"type": "array"},
+            "cost": {
+                "type": "object",
+                "properties": {
+                    "displayed_costs": string_list,
+                    "vehicle_rates": {"type": "object"},
+                    "network_rates": {"type": "object"},
+                    "weights": {
+                        "type": "object",
+                        "additionalProperties": {"type": "number", "minimum": 0.0}
+                    },
+                    "cost_aggregation": {"enum": ["sum", "mul"]},
+                    "ignore_unknown_user_provided_weights": {"type": "boolean"},
+                    "road_class_input_file": {"type": "string"}
+                }
+            },
+            "label": component(&builder.label_model_names(), false),
+            "mapping": {
+                "type": "object",
+                "properties": {
+                    "tolerance": {
+                        "type": "object",
+                        "properties": {
+                            "distance": {"type": "number", "minimum": 0.0},
+                            "unit": {"type": "string"}
+                        },
+                        "required": ["distance", "unit"]
+                    },
+                    "spatial_index_type": {"enum": ["vertex", "edge"]},
+                    "matching_type": string_list,
+                    "geometry": one_or_many(geometry),
+                    "queries_without_destinations": {"type": "boolean"},
+                    "endpoint_candidates": {"type": "integer", "minimum": 1}
+                }
+            },
+            "graph": {
+                "type": "object",
+                "properties": {
+                    "vertex_list_input_file": {"type": "string"},
+                    "vertex_list_columns": vertex_columns,
+                    "srid": srid,
+                    "edge_list": one_or_many(edge_list),
+                    "prevent_u_turns": {"type": "boolean"},
+                    "validation": {"type": "object"},
+                    "strongly_connected_components": {
+                        "type": "object",
+                        "properties": {"type": {"enum": ["tag", "largest", "min_size"]}},
+                        "required": ["type"]
+                    },
+                    "clip": {
+                        "type": "object",
+                        "properties": {
+                            "bbox": {"type": "array", "items": {"type": "number"}, "minItems": 4, "maxItems": 4},
+                            "polygon": {"type": "string"},
+                            "output_directory": {"type": "string"}
+                        },
+                        "required": ["output_directory"]
+                    }
+                }
+            },
+            "search": one_or_many(search),
+            "plugin": {
+                "type": "object",
+                "properties": {
+                    "input_plugins": {
+                        "type": "array",
+                        "items": input_plugin(&builder.input_plugin_names())
+                    },
+                    "output_plugins": {
+                        "type": "array",
+                        "items": component(&builder.output_plugin_names(), true)
+                    }
+                }
+            },
+            "termination": {
+                "type": "object",
+                "properties": {
+                    "type": {"enum": ["query_runtime", "solution_size", "iterations", "ram", "combined"]}
+                }
+            },
+            "system": {"type": "object"},
+            "map_matching": component(&builder.map_matching_names(), false)
+        },
+        "additionalProperties": false
+    })
+}
+
+/// JSON Schema (draft 2020-12) describing a query, covering the fields read by the core
+/// search application. see [`default_query_schema`].
+pub fn query_schema() -> Value {
+    let mut schema = default_query_schema();
+    if let Value::Object(map) = &mut schema {
+        map.insert(String::from("$schema"), json!(JSON_SCHEMA_DIALECT));
+        map.insert(String::from("title"), json!("RouteE Compass query"));
+    }
+    schema
+}
+
+/// writes the configuration and query schemas to [`CONFIG_SCHEMA_FILENAME`] and
+/// [`QUERY_SCHEMA_FILENAME`] in the provided directory, creating it if needed.
+///
+/// # Returns
+///
+/// the paths of the files written
+pub fn write_schemas(
+    builder: &CompassBuilderInventory,
+    directory: &Path,
+) -> Result<Vec<PathBuf>, CompassAppError> {
+    std::fs::create_dir_all(directory).map_err(|e| {
+        CompassAppError::BuildFailure(format!(
+            "failed to create schema directory '{}': {e}",
+            directory.display()
+        ))
+    })?;
+    let schemas = [
+        (CONFIG_SCHEMA_FILENAME, config_schema(builder)),
+        (QUERY_SCHEMA_FILENAME, query_schema()),
+    ];
+    let mut paths = vec![];
+    for (filename, schema) in schemas {
+        let path = directory.join(filename);
+        let contents = serde_json::to_string_pretty(&schema)?;
+        std::fs::write(&path, contents).map_err(|e| {
+            CompassAppError::BuildFailure(format!(
+                "failed to write schema file '{}': {e}",
+                path.display()
+            ))
+        })?;
+        paths.push(path);
+    }
+    Ok(paths)
+}
+
+/// schema of a component configured by a registered builder, selected by its `type`
+fn component(names: &[String], type_required: bool) -> Value {
+    let mut schema = json!({
+        "type": "object",
+        "properties": {"type": {"enum": names}}
+    });
+    if type_required {
+        schema["required"] = json!(["type"]);
+    }
+    schema
+}
+
+/// schema of an input plugin, which may set a condition on the queries it runs on
+fn input_plugin(names: &[String]) -> Value {
+    let mut schema = component(names, true);
+    let keys = json!({"type": "array", "items": {"type": "string"}});
+    schema["properties"]["when"] = json!({
+        "type": "object",
+        "properties": {
+            "present": keys,
+            "absent": keys,
+            "equals": {"type": "object"}
+        },
+        "additionalProperties": false
+    });
+    schema["properties"]["complete"] = json!({"type": "boolean"});
+    schema
+}
+
+/// schema accepting either a single value or an array of values, see [`routee_compass_core::config::OneOrMany`]
+fn one_or_many(schema: Value) -> Value {
+    json!({"anyOf": [schema.clone(), {"type": "array", "items": schema}]})
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::compass::compass_app_config_ops::read_user_config_file;
+    use crate::plugin::input::default::validate::QuerySchema;
+
+    #[test]
+    fn test_config_schema() {
+        let builder = CompassBuilderInventory::new().unwrap();
+        let schema = config_schema(&builder);
+        let traversal_types = &schema["properties"]["search"]["anyOf"][0]["properties"]
+            ["traversal"]["properties"]["type"]["enum"];
+        assert!(traversal_types
+            .as_array()
+            .unwrap()
+            .contains(&json!("combined")));
+
+        // the bundled example configuration satisfies the schema
+        let validator = QuerySchema::new(schema.clone()).unwrap();
+        let config_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("src/app/compass/test/speeds_test/speeds_test.toml");
+        let config = read_user_config_file(&config_path).unwrap();
+        let errors = validator.validate(&config);
+        assert!(errors.is_empty(), "unexpected errors: {errors:?}");
+
+        let invalid = json!({
+            "serach": {},
+            "plugin": {"output_plugins": [{"type": "bogus"}]}
+        });
+        let errors = validator.validate(&invalid);
+        assert_eq!(errors.len(), 2, "unexpected errors: {errors:?}");
+    }
+
+    #[test]
+    fn test_write_schemas() {
+        let builder = CompassBuilderInventory::new().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let paths = write_schemas(&builder, &dir.path().join("schemas")).unwrap();
+        assert_eq!(paths.len(), 2);
+        let query: Value =
+            serde_json::from_str(&std::fs::read_to_string(&paths[1]).unwrap()).unwrap();
+        assert_eq!(query["title"], json!("RouteE Compass query"));
+        QuerySchema::new(query).unwrap();
+    }
+}
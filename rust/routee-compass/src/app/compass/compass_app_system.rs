@@ -1,13 +1,53 @@
+use super::dataset_manifest::DatasetManifestConfig;
+use super::departure_time_config::DepartureTimeConfig;
+use super::metrics_config::MetricsConfig;
+use super::query_deduplication::QueryDeduplicationConfig;
+use super::query_scheduler::QueryScheduler;
+use super::remote_files::RemoteFilesConfig;
 use super::response::{
+    response_metadata::ResponseMetadataConfig, response_ordering::ResponseOrderingConfig,
     response_output_policy::ResponseOutputPolicy,
     response_persistence_policy::ResponsePersistencePolicy,
 };
+use super::response_cache::ResponseCacheConfig;
+use crate::app::search::{OneToManyConfig, SearchInstanceCacheConfig};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct CompassAppSystemParameters {
     pub parallelism: Option<usize>,
+    /// how queries are scheduled across the `parallelism` threads
+    pub scheduler: Option<QueryScheduler>,
     pub default_edge_list: Option<usize>,
     pub response_persistence_policy: Option<ResponsePersistencePolicy>,
     pub response_output_policy: Option<ResponseOutputPolicy>,
+    /// if provided, writes a report of run metrics at the end of each batch
+    pub metrics: Option<MetricsConfig>,
+    /// if provided, queries with matching model parameters share search instances
+    pub search_instance_cache: Option<SearchInstanceCacheConfig>,
+    /// if provided, queries sharing an origin or destination share a single search
+    pub one_to_many: Option<OneToManyConfig>,
+    /// if provided, identical queries in a batch are run once and share a response
+    pub deduplication: Option<QueryDeduplicationConfig>,
+    /// if provided, responses are stored and re-used by identical queries in later runs.
+    /// only read when the app is built.
+    pub response_cache: Option<ResponseCacheConfig>,
+    /// if provided, tags queries with their input index or writes responses in input order
+    pub response_ordering: Option<ResponseOrderingConfig>,
+    /// if provided, seeds stochastic components so that results are reproducible
+    pub seed: Option<u64>,
+    /// if provided, adds a `departure_time` variable to the state model, read from each query
+    pub departure_time: Option<DepartureTimeConfig>,
+    /// if true, a haversine load balancer is added when no load_balancer input plugin is
+    /// configured. false by default
+    pub default_load_balancer: Option<bool>,
+    /// if provided, how input files given as URLs are downloaded. only read when the
+    /// config is read.
+    pub remote_files: Option<RemoteFilesConfig>,
+    /// if provided, the manifest of the dataset, verified when the app is built. its
+    /// dataset version is added to every response.
+    pub dataset_manifest: Option<DatasetManifestConfig>,
+    /// if provided, adds a `metadata` object describing the app and its configuration to
+    /// every response. only read when the app is built.
+    pub response_metadata: Option<ResponseMetadataConfig>,
 }
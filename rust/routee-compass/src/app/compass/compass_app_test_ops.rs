@@ -0,0 +1,48 @@
+//! helpers for end-to-end tests that build a [`CompassApp`] from the speeds test
+//! configuration, a network of three vertices where the path [0, 2] from vertex 0 to
+//! vertex 2 is time-optimal and the path [1] is distance-optimal.
+
+use super::{CompassApp, CompassAppConfig, CompassBuilderInventory};
+use std::path::PathBuf;
+
+/// path to the speeds test configuration file
+pub fn speeds_test_config_file() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src")
+        .join("app")
+        .join("compass")
+        .join("test")
+        .join("speeds_test")
+        .join("speeds_test.toml")
+}
+
+/// contents of the speeds test configuration file
+pub fn speeds_test_toml() -> String {
+    std::fs::read_to_string(speeds_test_config_file()).unwrap()
+}
+
+/// reads a TOML configuration whose file paths are relative to the speeds test directory
+pub fn speeds_test_config_from_toml(conf_str: &str) -> CompassAppConfig {
+    CompassAppConfig::from_str(
+        conf_str,
+        speeds_test_config_file().to_str().unwrap(),
+        config::FileFormat::Toml,
+    )
+    .unwrap()
+}
+
+/// reads the speeds test configuration with extra TOML appended to it
+pub fn speeds_test_config(extra_toml: &str) -> CompassAppConfig {
+    speeds_test_config_from_toml(&format!("{}\n{extra_toml}", speeds_test_toml()))
+}
+
+/// builds an app with the default builders
+pub fn build_app(config: &CompassAppConfig) -> CompassApp {
+    let builder = CompassBuilderInventory::new().unwrap();
+    CompassApp::new(config, &builder).unwrap()
+}
+
+/// builds an app from the speeds test configuration with extra TOML appended to it
+pub fn speeds_test_app(extra_toml: &str) -> CompassApp {
+    build_app(&speeds_test_config(extra_toml))
+}
@@ -2,15 +2,24 @@ use super::CompassComponentError;
 use crate::plugin::{
     input::{
         default::{
-            debug::DebugInputPluginBuilder, grid_search::GridSearchBuilder,
-            inject::InjectPluginBuilder, load_balancer::LoadBalancerBuilder,
+            debug::DebugInputPluginBuilder, geocode::GeocodeInputPluginBuilder,
+            grid_search::GridSearchBuilder, inject::InjectPluginBuilder,
+            load_balancer::LoadBalancerBuilder, sweep::SweepBuilder,
+            validate::ValidateInputPluginBuilder,
         },
-        InputPlugin, InputPluginBuilder,
+        ConditionalInputPlugin, InputPlugin, InputPluginBuilder, InputPluginCondition,
     },
     output::{
         default::{
+            accessibility::AccessibilityOutputPluginBuilder,
+            cost_surface::CostSurfaceOutputPluginBuilder,
+            elevation_profile::ElevationProfileOutputPluginBuilder,
+            explored::ExploredOutputPluginBuilder, rest_stops::RestStopsOutputPluginBuilder,
+            route_explanation::RouteExplanationOutputPluginBuilder,
+            route_facilities::RouteFacilitiesOutputPluginBuilder,
+            segment_summary::SegmentSummaryOutputPluginBuilder,
             summary::SummaryOutputPluginBuilder, traversal::TraversalPluginBuilder,
-            uuid::UUIDOutputPluginBuilder,
+            uuid::UUIDOutputPluginBuilder, vector_tile::VectorTileOutputPluginBuilder,
         },
         OutputPlugin, OutputPluginBuilder,
     },
@@ -27,23 +36,36 @@ use routee_compass_core::{
     model::{
         constraint::{
             default::{
+                avoid_edges::avoid_edges_builder::AvoidEdgesBuilder,
+                closures::closures_builder::ClosuresBuilder,
                 combined::combined_builder::CombinedConstraintModelBuilder,
+                geofence::geofence_builder::GeofenceBuilder, hov::hov_builder::HovBuilder,
                 no_restriction_builder::NoRestrictionBuilder,
                 road_class::road_class_builder::RoadClassBuilder,
+                time_window::time_window_builder::TimeWindowBuilder,
                 turn_restrictions::turn_restriction_builder::TurnRestrictionBuilder,
                 vehicle_restrictions::VehicleRestrictionBuilder,
             },
             ConstraintModelBuilder, ConstraintModelService,
         },
         label::{
-            default::vertex_label_model::VertexLabelModelBuilder,
-            label_model_builder::LabelModelBuilder, label_model_service::LabelModelService,
+            default::{
+                soc_bucket_label_model::SocBucketLabelModelBuilder,
+                time_bucket_label_model::TimeBucketLabelModelBuilder,
+                vertex_label_model::VertexLabelModelBuilder,
+            },
+            label_model_builder::LabelModelBuilder,
+            label_model_service::LabelModelService,
         },
         traversal::{
             default::{
-                combined::CombinedTraversalBuilder, custom::CustomTraversalBuilder,
-                elevation::ElevationTraversalBuilder, grade::GradeTraversalBuilder,
+                access::AccessTraversalBuilder, combined::CombinedTraversalBuilder,
+                custom::CustomTraversalBuilder, elevation::ElevationTraversalBuilder,
+                grade::GradeTraversalBuilder,
+                intersection_delay::IntersectionDelayTraversalBuilder,
+                scheduled_link::ScheduledLinkTraversalBuilder, scripted::ScriptedTraversalBuilder,
                 temperature::TemperatureTraversalBuilder, time::TimeTraversalBuilder,
+                time_window::TimeWindowTraversalBuilder,
                 turn_delays::TurnDelayTraversalModelBuilder,
             },
             TraversalModelBuilder, TraversalModelService,
@@ -55,9 +77,10 @@ use routee_compass_powertrain::model::{
         battery::BatteryFilterBuilder, simple_charging_builder::SimpleChargingBuilder,
         soc_label_builder::SOCLabelModelBuilder,
     },
+    emissions::EmissionsModelBuilder,
     EnergyModelBuilder,
 };
-use std::{collections::HashMap, rc::Rc, sync::Arc};
+use std::{collections::HashMap, sync::Arc};
 
 /// provides a plugin API for downstream libraries to inject values into the CompassBuilderInventory.
 /// for details, see the [`inventory`] crate. must be a "type" defined in this crate in order to
@@ -71,31 +94,56 @@ inventory::collect!(BuilderRegistration);
 // along with any plugins registered by downstream libraries.
 inventory::submit! {
     BuilderRegistration(|builder| {
-        builder.add_traversal_model("distance".to_string(),  Rc::new(DistanceTraversalBuilder {}));
-        builder.add_traversal_model("speed".to_string(), Rc::new(SpeedTraversalBuilder {}));
-        builder.add_traversal_model("time".to_string(), Rc::new(TimeTraversalBuilder {}));
-        builder.add_traversal_model("grade".to_string(), Rc::new(GradeTraversalBuilder {}));
-        builder.add_traversal_model("elevation".to_string(), Rc::new(ElevationTraversalBuilder {}));
-        builder.add_traversal_model("energy".to_string(), Rc::new(EnergyModelBuilder {}));
-        builder.add_traversal_model("simple_charging".to_string(), Rc::new(SimpleChargingBuilder::default()));
-        builder.add_traversal_model("temperature".to_string(), Rc::new(TemperatureTraversalBuilder {}));
-        builder.add_traversal_model("turn_delay".to_string(), Rc::new(TurnDelayTraversalModelBuilder {}));
-        builder.add_traversal_model("custom".to_string(), Rc::new(CustomTraversalBuilder {}));
-        builder.add_constraint_model("no_restriction".to_string(), Rc::new(NoRestrictionBuilder {}));
-        builder.add_constraint_model("road_class".to_string(), Rc::new(RoadClassBuilder {}));
-        builder.add_constraint_model("turn_restriction".to_string(), Rc::new(TurnRestrictionBuilder {}));
-        builder.add_constraint_model("battery".to_string(), Rc::new(BatteryFilterBuilder::default()));
-        builder.add_constraint_model("vehicle_restriction".to_string(), Rc::new(VehicleRestrictionBuilder {}));
-        builder.add_label_model("vertex".to_string(), Rc::new(VertexLabelModelBuilder));
-        builder.add_label_model("soc".to_string(), Rc::new(SOCLabelModelBuilder));
-        builder.add_input_plugin("grid_search".to_string(), Rc::new(GridSearchBuilder {}));
-        builder.add_input_plugin("load_balancer".to_string(), Rc::new(LoadBalancerBuilder {}));
-        builder.add_input_plugin("inject".to_string(), Rc::new(InjectPluginBuilder {}));
-        builder.add_input_plugin("debug".to_string(), Rc::new(DebugInputPluginBuilder {}));
-        builder.add_output_plugin("traversal".to_string(), Rc::new(TraversalPluginBuilder {}));
-        builder.add_output_plugin("summary".to_string(), Rc::new(SummaryOutputPluginBuilder {}));
-        builder.add_output_plugin("uuid".to_string(), Rc::new(UUIDOutputPluginBuilder {}));
-        builder.add_map_matching_model("lcss".to_string(), Rc::new(LcssMapMatchingBuilder {}));
+        builder.add_traversal_model("distance".to_string(),  Arc::new(DistanceTraversalBuilder {}));
+        builder.add_traversal_model("speed".to_string(), Arc::new(SpeedTraversalBuilder {}));
+        builder.add_traversal_model("time".to_string(), Arc::new(TimeTraversalBuilder {}));
+        builder.add_traversal_model("time_window".to_string(), Arc::new(TimeWindowTraversalBuilder {}));
+        builder.add_traversal_model("grade".to_string(), Arc::new(GradeTraversalBuilder {}));
+        builder.add_traversal_model("elevation".to_string(), Arc::new(ElevationTraversalBuilder {}));
+        builder.add_traversal_model("energy".to_string(), Arc::new(EnergyModelBuilder {}));
+        builder.add_traversal_model("emissions".to_string(), Arc::new(EmissionsModelBuilder {}));
+        builder.add_traversal_model("simple_charging".to_string(), Arc::new(SimpleChargingBuilder::default()));
+        builder.add_traversal_model("temperature".to_string(), Arc::new(TemperatureTraversalBuilder {}));
+        builder.add_traversal_model("turn_delay".to_string(), Arc::new(TurnDelayTraversalModelBuilder {}));
+        builder.add_traversal_model("intersection_delay".to_string(), Arc::new(IntersectionDelayTraversalBuilder {}));
+        builder.add_traversal_model("access".to_string(), Arc::new(AccessTraversalBuilder {}));
+        builder.add_traversal_model("scheduled_link".to_string(), Arc::new(ScheduledLinkTraversalBuilder {}));
+        builder.add_traversal_model("custom".to_string(), Arc::new(CustomTraversalBuilder {}));
+        builder.add_traversal_model("scripted".to_string(), Arc::new(ScriptedTraversalBuilder {}));
+        builder.add_constraint_model("no_restriction".to_string(), Arc::new(NoRestrictionBuilder {}));
+        builder.add_constraint_model("road_class".to_string(), Arc::new(RoadClassBuilder {}));
+        builder.add_constraint_model("turn_restriction".to_string(), Arc::new(TurnRestrictionBuilder {}));
+        builder.add_constraint_model("battery".to_string(), Arc::new(BatteryFilterBuilder::default()));
+        builder.add_constraint_model("vehicle_restriction".to_string(), Arc::new(VehicleRestrictionBuilder {}));
+        builder.add_constraint_model("hov".to_string(), Arc::new(HovBuilder {}));
+        builder.add_constraint_model("geofence".to_string(), Arc::new(GeofenceBuilder {}));
+        builder.add_constraint_model("avoid_edges".to_string(), Arc::new(AvoidEdgesBuilder {}));
+        builder.add_constraint_model("closures".to_string(), Arc::new(ClosuresBuilder {}));
+        builder.add_constraint_model("time_window".to_string(), Arc::new(TimeWindowBuilder {}));
+        builder.add_label_model("vertex".to_string(), Arc::new(VertexLabelModelBuilder));
+        builder.add_label_model("soc".to_string(), Arc::new(SOCLabelModelBuilder));
+        builder.add_label_model("time_bucket".to_string(), Arc::new(TimeBucketLabelModelBuilder));
+        builder.add_label_model("soc_bucket".to_string(), Arc::new(SocBucketLabelModelBuilder));
+        builder.add_input_plugin("grid_search".to_string(), Arc::new(GridSearchBuilder {}));
+        builder.add_input_plugin("load_balancer".to_string(), Arc::new(LoadBalancerBuilder {}));
+        builder.add_input_plugin("inject".to_string(), Arc::new(InjectPluginBuilder {}));
+        builder.add_input_plugin("debug".to_string(), Arc::new(DebugInputPluginBuilder {}));
+        builder.add_input_plugin("geocode".to_string(), Arc::new(GeocodeInputPluginBuilder {}));
+        builder.add_input_plugin("validate".to_string(), Arc::new(ValidateInputPluginBuilder {}));
+        builder.add_input_plugin("sweep".to_string(), Arc::new(SweepBuilder {}));
+        builder.add_output_plugin("traversal".to_string(), Arc::new(TraversalPluginBuilder {}));
+        builder.add_output_plugin("summary".to_string(), Arc::new(SummaryOutputPluginBuilder {}));
+        builder.add_output_plugin("uuid".to_string(), Arc::new(UUIDOutputPluginBuilder {}));
+        builder.add_output_plugin("vector_tile".to_string(), Arc::new(VectorTileOutputPluginBuilder {}));
+        builder.add_output_plugin("elevation_profile".to_string(), Arc::new(ElevationProfileOutputPluginBuilder {}));
+        builder.add_output_plugin("explored".to_string(), Arc::new(ExploredOutputPluginBuilder {}));
+        builder.add_output_plugin("segment_summary".to_string(), Arc::new(SegmentSummaryOutputPluginBuilder {}));
+        builder.add_output_plugin("rest_stops".to_string(), Arc::new(RestStopsOutputPluginBuilder {}));
+        builder.add_output_plugin("route_facilities".to_string(), Arc::new(RouteFacilitiesOutputPluginBuilder {}));
+        builder.add_output_plugin("route_explanation".to_string(), Arc::new(RouteExplanationOutputPluginBuilder {}));
+        builder.add_output_plugin("cost_surface".to_string(), Arc::new(CostSurfaceOutputPluginBuilder {}));
+        builder.add_output_plugin("accessibility".to_string(), Arc::new(AccessibilityOutputPluginBuilder {}));
+        builder.add_map_matching_model("lcss".to_string(), Arc::new(LcssMapMatchingBuilder {}));
         Ok(())
     })
 }
@@ -116,12 +164,12 @@ inventory::submit! {
 /// will be loaded and CPU/RAM impacted.
 ///
 pub struct CompassBuilderInventory {
-    traversal_model_builders: HashMap<String, Rc<dyn TraversalModelBuilder>>,
-    constraint_model_builders: HashMap<String, Rc<dyn ConstraintModelBuilder>>,
-    label_model_builders: HashMap<String, Rc<dyn LabelModelBuilder>>,
-    input_plugin_builders: HashMap<String, Rc<dyn InputPluginBuilder>>,
-    output_plugin_builders: HashMap<String, Rc<dyn OutputPluginBuilder>>,
-    map_matching_builders: HashMap<String, Rc<dyn MapMatchingBuilder>>,
+    traversal_model_builders: HashMap<String, Arc<dyn TraversalModelBuilder>>,
+    constraint_model_builders: HashMap<String, Arc<dyn ConstraintModelBuilder>>,
+    label_model_builders: HashMap<String, Arc<dyn LabelModelBuilder>>,
+    input_plugin_builders: HashMap<String, Arc<dyn InputPluginBuilder>>,
+    output_plugin_builders: HashMap<String, Arc<dyn OutputPluginBuilder>>,
+    map_matching_builders: HashMap<String, Arc<dyn MapMatchingBuilder>>,
 }
 
 impl CompassBuilderInventory {
@@ -163,30 +211,70 @@ impl CompassBuilderInventory {
         Ok(builder)
     }
 
-    pub fn add_traversal_model(&mut self, name: String, builder: Rc<dyn TraversalModelBuilder>) {
+    pub fn add_traversal_model(&mut self, name: String, builder: Arc<dyn TraversalModelBuilder>) {
         let _ = self.traversal_model_builders.insert(name, builder);
     }
 
-    pub fn add_constraint_model(&mut self, name: String, builder: Rc<dyn ConstraintModelBuilder>) {
+    pub fn add_constraint_model(&mut self, name: String, builder: Arc<dyn ConstraintModelBuilder>) {
         let _ = self.constraint_model_builders.insert(name, builder);
     }
 
-    pub fn add_label_model(&mut self, name: String, builder: Rc<dyn LabelModelBuilder>) {
+    pub fn add_label_model(&mut self, name: String, builder: Arc<dyn LabelModelBuilder>) {
         let _ = self.label_model_builders.insert(name, builder);
     }
 
-    pub fn add_input_plugin(&mut self, name: String, builder: Rc<dyn InputPluginBuilder>) {
+    pub fn add_input_plugin(&mut self, name: String, builder: Arc<dyn InputPluginBuilder>) {
         let _ = self.input_plugin_builders.insert(name, builder);
     }
 
-    pub fn add_output_plugin(&mut self, name: String, builder: Rc<dyn OutputPluginBuilder>) {
+    pub fn add_output_plugin(&mut self, name: String, builder: Arc<dyn OutputPluginBuilder>) {
         let _ = self.output_plugin_builders.insert(name, builder);
     }
 
-    pub fn add_map_matching_model(&mut self, name: String, builder: Rc<dyn MapMatchingBuilder>) {
+    pub fn add_map_matching_model(&mut self, name: String, builder: Arc<dyn MapMatchingBuilder>) {
         let _ = self.map_matching_builders.insert(name, builder);
     }
 
+    /// sorted names accepted as the `type` of a traversal model, including `combined`
+    pub fn traversal_model_names(&self) -> Vec<String> {
+        sorted_names(
+            self.traversal_model_builders
+                .keys()
+                .map(String::as_str)
+                .chain(["combined"]),
+        )
+    }
+
+    /// sorted names accepted as the `type` of a constraint model, including `combined`
+    pub fn constraint_model_names(&self) -> Vec<String> {
+        sorted_names(
+            self.constraint_model_builders
+                .keys()
+                .map(String::as_str)
+                .chain(["combined"]),
+        )
+    }
+
+    /// sorted names accepted as the `type` of a label model
+    pub fn label_model_names(&self) -> Vec<String> {
+        sorted_names(self.label_model_builders.keys().map(String::as_str))
+    }
+
+    /// sorted names accepted as the `type` of an input plugin
+    pub fn input_plugin_names(&self) -> Vec<String> {
+        sorted_names(self.input_plugin_builders.keys().map(String::as_str))
+    }
+
+    /// sorted names accepted as the `type` of an output plugin
+    pub fn output_plugin_names(&self) -> Vec<String> {
+        sorted_names(self.output_plugin_builders.keys().map(String::as_str))
+    }
+
+    /// sorted names accepted as the `type` of a map matching algorithm
+    pub fn map_matching_names(&self) -> Vec<String> {
+        sorted_names(self.map_matching_builders.keys().map(String::as_str))
+    }
+
     /// builds a traversal model with the specified type name with the provided
     /// traversal model configuration JSON
     pub fn build_traversal_model_service(
@@ -197,7 +285,7 @@ impl CompassBuilderInventory {
         let mut builders = self.traversal_model_builders.clone();
         builders.insert(
             String::from("combined"),
-            Rc::new(CombinedTraversalBuilder::new(builders.clone())),
+            Arc::new(CombinedTraversalBuilder::new(builders.clone())),
         );
         let (conf_stripped, tm_type) = strip_type_from_config(config)?;
 
@@ -228,7 +316,7 @@ impl CompassBuilderInventory {
         let mut builders = self.constraint_model_builders.clone();
         builders.insert(
             String::from("combined"),
-            Rc::new(CombinedConstraintModelBuilder::new(builders.clone())),
+            Arc::new(CombinedConstraintModelBuilder::new(builders.clone())),
         );
         let (conf_stripped, fm_type) = strip_type_from_config(config)?;
 
@@ -278,6 +366,33 @@ impl CompassBuilderInventory {
             let plugin_type =
                 plugin_json.get_config_string(&"type", &format!("input plugin {idx}"))?;
             log::info!("loading input plugin '{plugin_type}'");
+            // the condition keys apply to any plugin, so they are removed before the
+            // plugin parameters are read by its builder
+            let mut plugin_json = plugin_json.clone();
+            let (when, complete) = match plugin_json.as_object_mut() {
+                Some(obj) => (
+                    obj.remove(ConditionalInputPlugin::WHEN_KEY),
+                    obj.remove(ConditionalInputPlugin::COMPLETE_KEY),
+                ),
+                None => (None, None),
+            };
+            let when = when
+                .map(serde_json::from_value::<InputPluginCondition>)
+                .transpose()
+                .map_err(|e| {
+                    CompassConfigurationError::UserConfigurationError(format!(
+                        "input plugin {idx} '{plugin_type}' has invalid 'when' condition: {e}"
+                    ))
+                })?;
+            let complete = match complete {
+                None => false,
+                Some(value) => value.as_bool().ok_or_else(|| {
+                    CompassConfigurationError::ExpectedFieldWithType(
+                        String::from(ConditionalInputPlugin::COMPLETE_KEY),
+                        String::from("boolean"),
+                    )
+                })?,
+            };
             let builder = self
                 .input_plugin_builders
                 .get(&plugin_type)
@@ -288,8 +403,16 @@ impl CompassBuilderInventory {
                         self.input_plugin_builders.keys().join(", "),
                     )
                 })?;
-            let input_plugin = builder.build(plugin_json)?;
-            plugins.push(input_plugin);
+            let input_plugin = builder.build(&plugin_json)?;
+            if when.is_some() || complete {
+                plugins.push(Arc::new(ConditionalInputPlugin {
+                    plugin: input_plugin,
+                    when,
+                    complete,
+                }));
+            } else {
+                plugins.push(input_plugin);
+            }
         }
         Ok(plugins)
     }
@@ -342,3 +465,149 @@ impl CompassBuilderInventory {
             .map_err(CompassConfigurationError::MapMatchingError)
     }
 }
+
+fn sorted_names<'a>(names: impl Iterator<Item = &'a str>) -> Vec<String> {
+    names.map(String::from).sorted().dedup().collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::compass::compass_app_test_ops::speeds_test_app;
+
+    #[test]
+    fn test_arrival_window() {
+        let app = speeds_test_app(
+            "[[search.traversal.models]]\ntype = \"time_window\"\ntime_unit = \"minutes\"\n[search.constraint]\ntype = \"time_window\"",
+        );
+
+        let query = |window: serde_json::Value| serde_json::json!({"origin_vertex": 0, "destination_vertex": 2, "arrival_window": window});
+        let mut queries = vec![
+            serde_json::json!({"origin_vertex": 0, "destination_vertex": 2}),
+            query(serde_json::json!({"end": 400.0})),
+            query(serde_json::json!({"start": 10.0, "time_unit": "hours"})),
+        ];
+        let mut responses = app.run(&mut queries, None).expect("run failed");
+        responses.sort_by_key(|r| r["request"]["arrival_window"].to_string());
+        let summary = |response: &serde_json::Value, feature: &str| {
+            response["route"]["traversal_summary"][feature]["value"]
+                .as_f64()
+                .unwrap_or_else(|| panic!("missing {feature} in {response}"))
+        };
+        let trip_time = summary(&responses[0], "trip_time");
+        assert!(trip_time < 600.0, "{trip_time}");
+        assert_eq!(summary(&responses[0], "trip_wait_time"), 0.0);
+
+        // an early arrival waits at the destination until the window opens
+        assert!((summary(&responses[2], "trip_time") - 600.0).abs() < 1e-6);
+        let wait_time = summary(&responses[2], "trip_wait_time");
+        assert!((wait_time - (600.0 - trip_time)).abs() < 1e-6);
+
+        // the window closes before the fastest route arrives
+        assert!(responses[1].get("error").is_some(), "{}", responses[1]);
+    }
+
+    #[test]
+    fn test_access_costs() {
+        let dir = tempfile::tempdir().unwrap();
+        let access_file = dir.path().join("access.csv");
+        // a ferry on edge 2 delays the fastest route, and entering vertex 2 has a fee
+        std::fs::write(
+            &access_file,
+            "vertex_id,edge_id,time,cost\n,2,1000.0,12.5\n2,,5.0,3.0\n",
+        )
+        .unwrap();
+        let app = speeds_test_app(&format!(
+            "[[search.traversal.models]]\ntype = \"access\"\naccess_cost_input_file = {:?}\ntime_unit = \"minutes\"",
+            access_file.to_str().unwrap(),
+        ));
+        let mut queries = vec![serde_json::json!({"origin_vertex": 0, "destination_vertex": 2})];
+        let responses = app.run(&mut queries, None).expect("run failed");
+        let route = &responses[0]["route"];
+        assert_eq!(route["path"], serde_json::json!([1]), "{}", responses[0]);
+        let summary = &route["traversal_summary"];
+        assert_eq!(summary["trip_access_cost"]["value"], serde_json::json!(3.0));
+        assert_eq!(summary["access_time"]["value"], serde_json::json!(5.0));
+        assert!(summary["trip_time"]["value"].as_f64().unwrap() > 5.0);
+    }
+
+    #[test]
+    fn test_scheduled_link() {
+        let dir = tempfile::tempdir().unwrap();
+        let schedule_file = dir.path().join("schedule.csv");
+        // a ferry on edge 2 departs daily at noon and crosses in an hour
+        std::fs::write(
+            &schedule_file,
+            "edge_id,departure,crossing_time,headway,last_departure\n2,12:00,60.0,,\n",
+        )
+        .unwrap();
+        let app = speeds_test_app(&format!(
+            "[[search.traversal.models]]\ntype = \"scheduled_link\"\nschedule_input_file = {:?}\ntime_unit = \"minutes\"\nutc_offset = \"-06:00\"",
+            schedule_file.to_str().unwrap(),
+        ));
+        let query = |departure_time: &str| {
+            serde_json::json!({
+                "origin_vertex": 0,
+                "destination_vertex": 2,
+                "departure_time": departure_time
+            })
+        };
+        let mut queries = vec![
+            query("2025-06-01T08:00:00-06:00"),
+            query("2025-06-01T11:00:00-06:00"),
+        ];
+        let mut responses = app.run(&mut queries, None).expect("run failed");
+        responses.sort_by_key(|r| r["request"]["departure_time"].to_string());
+        let summary = |response: &serde_json::Value, feature: &str| {
+            response["route"]["traversal_summary"][feature]["value"]
+                .as_f64()
+                .unwrap_or_else(|| panic!("missing {feature} in {response}"))
+        };
+
+        // edge 0 takes about 94 minutes, so the ferry is reached before noon and the
+        // trip arrives at 13:00 local time
+        let early = &responses[0];
+        assert_eq!(early["route"]["path"], serde_json::json!([0, 2]), "{early}");
+        assert!((summary(early, "trip_time") - 300.0).abs() < 1e-6);
+        assert!((summary(early, "trip_wait_time") - 146.05).abs() < 0.01);
+
+        // the noon ferry has left, so the direct road is faster than waiting a day
+        let late = &responses[1];
+        assert_eq!(late["route"]["path"], serde_json::json!([1]), "{late}");
+        assert_eq!(summary(late, "trip_wait_time"), 0.0);
+    }
+
+    #[test]
+    fn test_intersection_delay() {
+        let dir = tempfile::tempdir().unwrap();
+        let controls_file = dir.path().join("controls.csv");
+        // the fastest route passes through vertex 1. the origin and destination are
+        // never delayed.
+        std::fs::write(
+            &controls_file,
+            "vertex_id,control\n0,signal\n1,roundabout\n2,signal\n",
+        )
+        .unwrap();
+        let run = |roundabout_delay: f64| {
+            let app = speeds_test_app(&format!(
+                "[[search.traversal.models]]\ntype = \"intersection_delay\"\nintersection_control_input_file = {:?}\ntime_unit = \"minutes\"\ndelays = {{ signal = 1000.0, roundabout = {roundabout_delay:.1} }}",
+                controls_file.to_str().unwrap(),
+            ));
+            let mut queries =
+                vec![serde_json::json!({"origin_vertex": 0, "destination_vertex": 2})];
+            let responses = app.run(&mut queries, None).expect("run failed");
+            responses[0]["route"].clone()
+        };
+
+        let route = run(10.0);
+        assert_eq!(route["path"], serde_json::json!([0, 2]), "{route}");
+        let summary = &route["traversal_summary"];
+        assert_eq!(
+            summary["edge_intersection_delay"]["value"],
+            serde_json::json!(600.0)
+        );
+
+        // a long enough delay at the roundabout makes the direct road faster
+        let route = run(300.0);
+        assert_eq!(route["path"], serde_json::json!([1]), "{route}");
+    }
+}
@@ -4,7 +4,9 @@ use crate::app::map_matching::{
 };
 use crate::app::search::generate_route_output;
 use crate::app::search::SearchApp;
-use crate::plugin::output::default::traversal::TraversalOutputFormat;
+use crate::plugin::output::default::traversal::{
+    GeoJsonProperties, GeometryOutputOptions, TraversalOutputFormat,
+};
 use geo::Point;
 use routee_compass_core::algorithm::map_matching::MapMatchingAlgorithm;
 use routee_compass_core::algorithm::map_matching::{
@@ -14,10 +16,18 @@ use routee_compass_core::algorithm::search::{EdgeTraversal, SearchInstance};
 use serde_json::Value;
 use std::sync::Arc;
 
-/// Converts a JSON request to the internal trace format.
-pub fn convert_request_to_trace(request: &MapMatchingRequest) -> MapMatchingTrace {
+/// Converts a JSON request to the internal trace format. The trace is seeded with
+/// the request seed, if provided, or else with a seed derived from the global seed.
+pub fn convert_request_to_trace(
+    request: &MapMatchingRequest,
+    global_seed: Option<u64>,
+) -> MapMatchingTrace {
     let points: Vec<MapMatchingPoint> = request.trace.iter().map(convert_trace_point).collect();
-    MapMatchingTrace::new(points)
+    let trace = MapMatchingTrace::new(points);
+    let seed = request
+        .seed
+        .or_else(|| global_seed.map(|s| trace.derive_seed(s)));
+    trace.with_seed(seed)
 }
 
 /// Converts a single trace point from the request format.
@@ -48,24 +58,33 @@ pub fn convert_result_to_response(
     let output_format = request.output_format;
     let summary_ops = &request.summary_ops;
 
-    let (mut path_json, traversal_summary) =
-        match generate_route_output(&matched_path, si, &output_format, summary_ops) {
-            Ok(output) => {
-                let path = output
-                    .get("path")
-                    .cloned()
-                    .unwrap_or(serde_json::Value::Null);
-                let summary = output.get("traversal_summary").cloned();
-                (path, summary)
-            }
-            Err(e) => {
-                log::error!("failed to generate route output for map matching: {}", e);
-                (
-                    serde_json::to_value(&matched_path).unwrap_or(serde_json::Value::Null),
-                    None,
-                )
-            }
-        };
+    let geojson_properties = GeoJsonProperties::default();
+    let (mut path_json, traversal_summary) = match generate_route_output(
+        &matched_path,
+        si,
+        &output_format,
+        summary_ops,
+        &request.derived_variables,
+        &geojson_properties,
+        &GeometryOutputOptions::default(),
+        false,
+    ) {
+        Ok(output) => {
+            let path = output
+                .get("path")
+                .cloned()
+                .unwrap_or(serde_json::Value::Null);
+            let summary = output.get("traversal_summary").cloned();
+            (path, summary)
+        }
+        Err(e) => {
+            log::error!("failed to generate route output for map matching: {}", e);
+            (
+                serde_json::to_value(&matched_path).unwrap_or(serde_json::Value::Null),
+                None,
+            )
+        }
+    };
 
     // If format is JSON, we need to add geometry manually since TraversalOutputFormat::Json doesn't include it by default
     // and map matching expects it.
@@ -91,6 +110,7 @@ pub fn run_single_map_match(
     query: &Value,
     search_app: &SearchApp,
     map_matching_algorithm: &Arc<dyn MapMatchingAlgorithm>,
+    global_seed: Option<u64>,
 ) -> Result<Value, CompassAppError> {
     let request: MapMatchingRequest = serde_json::from_value(query.clone())?;
 
@@ -100,7 +120,7 @@ pub fn run_single_map_match(
         .map_err(MapMatchingAppError::InvalidRequest)?;
 
     // Convert request to internal trace format
-    let trace = convert_request_to_trace(&request);
+    let trace = convert_request_to_trace(&request, global_seed);
 
     // Build a search instance for this query
     let mut query_config = map_matching_algorithm.search_parameters();
@@ -0,0 +1,322 @@
+use crate::app::compass::{compass_app_ops, CompassAppError};
+use crate::app::search::SearchApp;
+use crate::plugin::output::OutputPlugin;
+use geo::Point;
+use routee_compass_core::algorithm::search::SearchInstance;
+use routee_compass_core::model::map::{spatial_index_ops, MapJsonKey};
+use routee_compass_core::model::network::{EdgeId, EdgeListId, Graph};
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use std::sync::Arc;
+
+/// the response key describing how a re-planned query was started
+pub const REPLAN_KEY: &str = "replan";
+
+/// query keys that locate the origin of a query, which are replaced by the current
+/// position of the vehicle when re-planning
+const ORIGIN_KEYS: [MapJsonKey; 7] = [
+    MapJsonKey::OriginX,
+    MapJsonKey::OriginY,
+    MapJsonKey::OriginVertex,
+    MapJsonKey::OriginEdgeList,
+    MapJsonKey::OriginEdge,
+    MapJsonKey::OriginSnap,
+    MapJsonKey::OriginCandidates,
+];
+
+/// a request to re-plan a trip that is underway from the current position of the
+/// vehicle to the destination of a previous response.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ReplanRequest {
+    /// the previous response, holding the `request` and `route` of the trip. the route
+    /// path must be written in the `edge_id`, `json` or `geojson` traversal format.
+    pub response: Value,
+    /// the current matched position of the vehicle
+    pub position: ReplanPosition,
+    /// observed values of state variables at the current position, such as the state of
+    /// charge or the elapsed trip time, in the units of the previous response. these
+    /// replace the values found by evaluating the previous route up to the position.
+    #[serde(default)]
+    pub state: Map<String, Value>,
+}
+
+/// the current position of a vehicle, as the edge it is traveling on or as a WGS84
+/// coordinate that is matched to the nearest edge.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(untagged)]
+pub enum ReplanPosition {
+    Edge {
+        edge_id: EdgeId,
+        #[serde(default)]
+        edge_list_id: EdgeListId,
+    },
+    Coordinate {
+        x: f64,
+        y: f64,
+    },
+}
+
+impl ReplanRequest {
+    /// the request of the previous response
+    pub fn previous_request(&self) -> Result<&Value, CompassAppError> {
+        self.response.get("request").ok_or_else(|| {
+            CompassAppError::CompassFailure(String::from(
+                "replan response is missing the 'request' of the previous query",
+            ))
+        })
+    }
+
+    /// the edges of the (first) route of the previous response
+    pub fn previous_route(&self) -> Result<Vec<(EdgeListId, EdgeId)>, CompassAppError> {
+        let route = match self.response.get("route") {
+            Some(Value::Array(routes)) => routes.first(),
+            other => other,
+        };
+        let path = route.and_then(|r| r.get("path")).ok_or_else(|| {
+            CompassAppError::CompassFailure(String::from(
+                "replan response is missing the 'route' of the previous query",
+            ))
+        })?;
+        let edges = match path {
+            Value::Array(edges) => edges.iter().collect::<Vec<_>>(),
+            Value::Object(collection) => collection
+                .get("features")
+                .and_then(Value::as_array)
+                .map(|features| {
+                    features
+                        .iter()
+                        .map(|f| f.get("properties").unwrap_or(&Value::Null))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            _ => vec![],
+        };
+        let route = edges
+            .into_iter()
+            .map(route_edge)
+            .collect::<Option<Vec<_>>>()
+            .unwrap_or_default();
+        if route.is_empty() {
+            return Err(CompassAppError::CompassFailure(String::from(
+                "replan response route path must list the route edge ids, as written by the 'edge_id', 'json' or 'geojson' traversal formats",
+            )));
+        }
+        Ok(route)
+    }
+
+    /// the edge of the current position of the vehicle
+    pub fn position_edge(
+        &self,
+        search_app: &SearchApp,
+    ) -> Result<(EdgeListId, EdgeId), CompassAppError> {
+        match &self.position {
+            ReplanPosition::Edge {
+                edge_id,
+                edge_list_id,
+            } => Ok((*edge_list_id, *edge_id)),
+            ReplanPosition::Coordinate { x, y } => {
+                let point = Point::new(*x as f32, *y as f32);
+                let nearest = search_app.map_model.k_nearest(&point, 1, None)?;
+                let candidate = nearest.first().ok_or_else(|| {
+                    CompassAppError::CompassFailure(format!(
+                        "replan position ({x}, {y}) does not match any edge"
+                    ))
+                })?;
+                Ok((candidate.edge_list_id, candidate.edge_id))
+            }
+        }
+    }
+
+    /// the query to the original destination from the current position of the vehicle.
+    /// the origin is located the same way as in the previous request: by the position
+    /// edge if the previous request had an origin edge, and otherwise by the vertex at
+    /// the end of the position edge.
+    pub fn replan_query(
+        &self,
+        position: (EdgeListId, EdgeId),
+        graph: &Graph,
+    ) -> Result<Value, CompassAppError> {
+        let mut query = self.previous_request()?.clone();
+        let map = query.as_object_mut().ok_or_else(|| {
+            CompassAppError::CompassFailure(String::from(
+                "replan response 'request' must be a JSON object",
+            ))
+        })?;
+        let edge_oriented = map.contains_key(MapJsonKey::OriginEdge.as_str());
+        for key in ORIGIN_KEYS.iter() {
+            map.remove(key.as_str());
+        }
+        let (edge_list_id, edge_id) = position;
+        if edge_oriented {
+            map.insert(MapJsonKey::OriginEdgeList.to_string(), json![edge_list_id]);
+            map.insert(MapJsonKey::OriginEdge.to_string(), json![edge_id]);
+        } else {
+            let vertex_id = graph.dst_vertex_id(&edge_list_id, &edge_id)?;
+            map.insert(MapJsonKey::OriginVertex.to_string(), json![vertex_id]);
+        }
+        Ok(query)
+    }
+}
+
+/// reads an edge of a route path, written as an edge id or as an object with an
+/// `edge_id` and an optional `edge_list_id`
+fn route_edge(value: &Value) -> Option<(EdgeListId, EdgeId)> {
+    match value {
+        Value::Number(n) => n.as_u64().map(|e| (EdgeListId(0), EdgeId(e as usize))),
+        Value::Object(edge) => {
+            let edge_id = edge.get("edge_id")?.as_u64()?;
+            let edge_list_id = match edge.get("edge_list_id") {
+                Some(el) => el.as_u64()?,
+                None => 0,
+            };
+            Some((EdgeListId(edge_list_id as usize), EdgeId(edge_id as usize)))
+        }
+        _ => None,
+    }
+}
+
+/// the index of the route edge where the vehicle is: the position edge if it is on the
+/// route, and otherwise the route edge nearest to the start of the position edge, where
+/// the vehicle is assumed to have left the route.
+///
+/// # Returns
+///
+/// the index of the route edge, and true if the position edge is on the route
+fn route_index(
+    route: &[(EdgeListId, EdgeId)],
+    position: (EdgeListId, EdgeId),
+    si: &SearchInstance,
+) -> Result<(usize, bool), CompassAppError> {
+    if let Some(index) = route.iter().position(|e| *e == position) {
+        return Ok((index, true));
+    }
+    let (edge_list_id, edge_id) = position;
+    let linestring = si.map_model.get_linestring(&edge_list_id, &edge_id)?;
+    let point = linestring
+        .points()
+        .next()
+        .ok_or_else(|| CompassAppError::InternalError(format!("edge {edge_id} has no geometry")))?;
+    let mut nearest: Option<(usize, f64)> = None;
+    for (index, (el, e)) in route.iter().enumerate() {
+        let route_linestring = si.map_model.get_linestring(el, e)?;
+        if let Some((_, distance)) =
+            spatial_index_ops::nearest_point_on_linestring(route_linestring, &point)?
+        {
+            let meters = distance.get::<uom::si::length::meter>();
+            if nearest.is_none_or(|(_, d)| meters < d) {
+                nearest = Some((index, meters));
+            }
+        }
+    }
+    let (index, _) = nearest.unwrap_or_default();
+    Ok((index, false))
+}
+
+/// re-plans a trip that is underway to the destination of a previous response. the
+/// state vector at the current position is found by evaluating the previous route up
+/// to the position, then updated with any observed state values of the request, and
+/// the search to the original destination starts from this state rather than the
+/// initial values of the state model. the search instance is built as for any other
+/// query, so the loaded components are reused.
+///
+/// # Returns
+///
+/// the response of the re-planned query, with a `replan` object describing where the
+/// vehicle was found on the previous route and the state it started from
+pub fn run_single_replan(
+    request: &Value,
+    search_app: &SearchApp,
+    output_plugins: &[Arc<dyn OutputPlugin>],
+) -> Result<Value, CompassAppError> {
+    let replan: ReplanRequest = serde_json::from_value(request.clone())?;
+    let previous_route = replan.previous_route()?;
+    let position = replan.position_edge(search_app)?;
+    let previous_si = search_app.get_search_instance(replan.previous_request()?)?;
+
+    let (index, on_route) = route_index(&previous_route, position, &previous_si)?;
+    let traversals = previous_si.compute_path(&previous_route[..=index])?;
+    let mut initial_state = match traversals.last() {
+        Some(last) => last.result_state.clone(),
+        None => previous_si.initial_state()?,
+    };
+    for (name, value) in replan.state.iter() {
+        previous_si
+            .state_model
+            .set_serialized(&mut initial_state, name, value)?;
+    }
+    // state vectors are read by name, as the state model of the re-planned query may
+    // order its state variables differently
+    let serialized_state = previous_si
+        .state_model
+        .serialize_state(&initial_state, false)?;
+    let state_values = serialized_state.as_object().cloned().unwrap_or_default();
+
+    let mut query = replan.replan_query(position, &search_app.graph)?;
+    let result = search_app.run_from_state(&mut query, Some(&state_values));
+    let mut response =
+        compass_app_ops::apply_output_processing(&query, result, search_app, output_plugins, None);
+    let (edge_list_id, edge_id) = previous_route[index];
+    response[REPLAN_KEY] = json!({
+        "on_route": on_route,
+        "route_index": index,
+        "route_edge_list_id": edge_list_id,
+        "route_edge_id": edge_id,
+        "initial_state": serialized_state,
+    });
+    Ok(response)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::compass::compass_app_test_ops::speeds_test_app;
+
+    #[test]
+    fn test_run_replan() {
+        let app = speeds_test_app("");
+        let mut queries = vec![serde_json::json!({
+            "origin_vertex": 0,
+            "destination_vertex": 2
+        })];
+        let responses = app.run(&mut queries, None).expect("run failed");
+        let previous = responses[0].clone();
+        assert_eq!(previous["route"]["path"], serde_json::json!([0, 2]));
+        let previous_time = previous["route"]["final_state"]["trip_time"]
+            .as_f64()
+            .unwrap();
+
+        // on edge 0 of the route, 10 minutes later than planned
+        let planned = app
+            .run_calculate_path(&[serde_json::json!({"path": [{"edge_id": 0}]})], None)
+            .expect("run_calculate_path failed");
+        let planned_time = planned[0]["route"]["final_state"]["trip_time"]
+            .as_f64()
+            .unwrap();
+        let requests = vec![
+            serde_json::json!({
+                "response": previous,
+                "position": { "edge_id": 0 },
+                "state": { "trip_time": planned_time + 10.0 }
+            }),
+            serde_json::json!({
+                "response": previous,
+                "position": { "edge_id": 1 }
+            }),
+        ];
+        let results = app.run_replan(&requests, None).expect("run_replan failed");
+        let on_route = &results[0];
+        assert_eq!(on_route["replan"]["on_route"], true, "{on_route}");
+        assert_eq!(on_route["replan"]["route_index"], 0);
+        assert_eq!(on_route["request"]["origin_vertex"], 1);
+        assert_eq!(on_route["route"]["path"], serde_json::json!([2]));
+        let replanned_time = on_route["route"]["final_state"]["trip_time"]
+            .as_f64()
+            .unwrap();
+        assert!((replanned_time - (previous_time + 10.0)).abs() < 1e-6);
+
+        // edge 1 leaves vertex 0, nearest to the start of the route
+        let off_route = &results[1];
+        assert_eq!(off_route["replan"]["on_route"], false, "{off_route}");
+        assert_eq!(off_route["replan"]["route_index"], 0);
+    }
+}
@@ -0,0 +1,326 @@
+use super::CompassAppError;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+
+/// name of the manifest file written to a dataset directory
+pub const MANIFEST_FILENAME: &str = "compass-manifest.json";
+
+/// version of the manifest format written by this version of Compass
+pub const MANIFEST_SCHEMA_VERSION: u32 = 1;
+
+/// hash algorithm of the file hashes in a manifest
+pub const HASH_ALGORITHM: &str = "xxh3_128";
+
+/// configures the dataset manifest read when the app is built. the dataset version of the
+/// manifest is added to every response as `dataset_version`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct DatasetManifestConfig {
+    /// manifest file written with the dataset, listing files relative to its directory
+    pub manifest_input_file: String,
+    /// if true (the default), the size and hash of each file listed in the manifest are
+    /// checked when the app is built, failing if any file is missing or has changed
+    pub verify: Option<bool>,
+}
+
+/// lists the files of a network dataset along with their content hashes, so that the
+/// results of a run can be traced back to the exact dataset that produced them.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DatasetManifest {
+    /// version of the manifest format
+    pub schema_version: u32,
+    /// version of the dataset, as given when the manifest was written or derived from
+    /// the file hashes
+    pub dataset_version: String,
+    /// hash algorithm of the file hashes
+    pub hash_algorithm: String,
+    /// each file of the dataset, sorted by path
+    pub files: Vec<DatasetFile>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DatasetFile {
+    /// path of the file relative to the manifest directory, with `/` separators
+    pub path: String,
+    pub size_bytes: u64,
+    /// hex-encoded hash of the file contents
+    pub hash: String,
+}
+
+impl DatasetManifest {
+    /// builds the manifest of every file in a directory and its subdirectories, other than
+    /// an existing manifest file.
+    ///
+    /// # Arguments
+    ///
+    /// * `directory` - dataset directory
+    /// * `dataset_version` - version of the dataset. if not provided, the version is the
+    ///   first 16 characters of a hash over the file paths and hashes.
+    pub fn build(
+        directory: &Path,
+        dataset_version: Option<&str>,
+    ) -> Result<DatasetManifest, CompassAppError> {
+        let mut paths = vec![];
+        list_files(directory, &mut paths)?;
+        paths.retain(|p| p.as_path() != Path::new(MANIFEST_FILENAME));
+        paths.sort();
+        let files = paths
+            .iter()
+            .map(|relative| {
+                let (size_bytes, hash) = hash_file(&directory.join(relative))?;
+                let path = relative
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                Ok(DatasetFile {
+                    path,
+                    size_bytes,
+                    hash,
+                })
+            })
+            .collect::<Result<Vec<_>, CompassAppError>>()?;
+        let dataset_version = match dataset_version {
+            Some(version) => version.to_string(),
+            None => {
+                let mut hasher = twox_hash::xxhash3_128::Hasher::new();
+                for file in files.iter() {
+                    hasher.write(file.path.as_bytes());
+                    hasher.write(file.hash.as_bytes());
+                }
+                format!("{:032x}", hasher.finish_128())[..16].to_string()
+            }
+        };
+        Ok(DatasetManifest {
+            schema_version: MANIFEST_SCHEMA_VERSION,
+            dataset_version,
+            hash_algorithm: String::from(HASH_ALGORITHM),
+            files,
+        })
+    }
+
+    /// writes this manifest as [`MANIFEST_FILENAME`] in a directory, returning its path
+    pub fn write(&self, directory: &Path) -> Result<PathBuf, CompassAppError> {
+        let path = directory.join(MANIFEST_FILENAME);
+        let contents = serde_json::to_string_pretty(self)?;
+        std::fs::write(&path, contents).map_err(|e| {
+            CompassAppError::InternalError(format!(
+                "failure writing dataset manifest {}: {e}",
+                path.display()
+            ))
+        })?;
+        Ok(path)
+    }
+
+    pub fn read(path: &Path) -> Result<DatasetManifest, CompassAppError> {
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            CompassAppError::BuildFailure(format!(
+                "failure reading dataset manifest {}: {e}",
+                path.display()
+            ))
+        })?;
+        let manifest: DatasetManifest = serde_json::from_str(&contents).map_err(|e| {
+            CompassAppError::BuildFailure(format!(
+                "invalid dataset manifest {}: {e}",
+                path.display()
+            ))
+        })?;
+        if manifest.schema_version > MANIFEST_SCHEMA_VERSION {
+            return Err(CompassAppError::BuildFailure(format!(
+                "dataset manifest {} has schema version {}, but this version of Compass reads up to version {MANIFEST_SCHEMA_VERSION}",
+                path.display(),
+                manifest.schema_version
+            )));
+        }
+        if manifest.hash_algorithm != HASH_ALGORITHM {
+            return Err(CompassAppError::BuildFailure(format!(
+                "dataset manifest {} uses unsupported hash algorithm '{}'",
+                path.display(),
+                manifest.hash_algorithm
+            )));
+        }
+        Ok(manifest)
+    }
+
+    /// checks that each file listed in this manifest exists in the directory with the
+    /// listed size and hash. files in the directory that are not listed are ignored.
+    ///
+    /// # Returns
+    ///
+    /// a description of each file that is missing or has changed. empty if all match.
+    pub fn verify(&self, directory: &Path) -> Result<Vec<String>, CompassAppError> {
+        let mut mismatches = vec![];
+        for file in self.files.iter() {
+            let path = directory.join(&file.path);
+            if !path.is_file() {
+                mismatches.push(format!("{} is missing", file.path));
+                continue;
+            }
+            let (size_bytes, hash) = hash_file(&path)?;
+            if size_bytes != file.size_bytes {
+                mismatches.push(format!(
+                    "{} has size {size_bytes} bytes, expected {}",
+                    file.path, file.size_bytes
+                ));
+            } else if hash != file.hash {
+                mismatches.push(format!(
+                    "{} has hash {hash}, expected {}",
+                    file.path, file.hash
+                ));
+            }
+        }
+        Ok(mismatches)
+    }
+}
+
+impl DatasetManifestConfig {
+    /// reads the configured manifest, verifying the dataset files unless `verify` is false
+    pub fn load(&self) -> Result<DatasetManifest, CompassAppError> {
+        let path = Path::new(&self.manifest_input_file);
+        let manifest = DatasetManifest::read(path)?;
+        if self.verify.unwrap_or(true) {
+            let directory = path.parent().unwrap_or(Path::new(""));
+            let mismatches = manifest.verify(directory)?;
+            if !mismatches.is_empty() {
+                return Err(CompassAppError::BuildFailure(format!(
+                    "dataset does not match manifest {} (version {}): {}",
+                    path.display(),
+                    manifest.dataset_version,
+                    mismatches.join("; ")
+                )));
+            }
+        }
+        log::info!(
+            "dataset version {} with {} files in manifest {}",
+            manifest.dataset_version,
+            manifest.files.len(),
+            path.display()
+        );
+        Ok(manifest)
+    }
+}
+
+/// reads a file, returning its size and hex-encoded hash
+pub fn hash_file(path: &Path) -> Result<(u64, String), CompassAppError> {
+    let failed = |e: std::io::Error| {
+        CompassAppError::BuildFailure(format!("failure hashing {}: {e}", path.display()))
+    };
+    let mut reader = BufReader::new(File::open(path).map_err(failed)?);
+    let mut hasher = twox_hash::xxhash3_128::Hasher::new();
+    let mut buffer = vec![0u8; 1 << 16];
+    let mut size_bytes = 0;
+    loop {
+        let n = reader.read(&mut buffer).map_err(failed)?;
+        if n == 0 {
+            break;
+        }
+        hasher.write(&buffer[..n]);
+        size_bytes += n as u64;
+    }
+    Ok((size_bytes, format!("{:032x}", hasher.finish_128())))
+}
+
+/// collects the paths, relative to `root`, of the files in a directory and its subdirectories
+fn list_files(root: &Path, paths: &mut Vec<PathBuf>) -> Result<(), CompassAppError> {
+    let mut directories = vec![PathBuf::new()];
+    while let Some(relative) = directories.pop() {
+        let directory = root.join(&relative);
+        let entries = std::fs::read_dir(&directory).map_err(|e| {
+            CompassAppError::BuildFailure(format!(
+                "failure reading dataset directory {}: {e}",
+                directory.display()
+            ))
+        })?;
+        for entry in entries {
+            let entry = entry.map_err(|e| CompassAppError::BuildFailure(e.to_string()))?;
+            let path = relative.join(entry.file_name());
+            if entry.path().is_dir() {
+                directories.push(path);
+            } else {
+                paths.push(path);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::compass::compass_app_test_ops::{
+        build_app, speeds_test_config, speeds_test_config_file,
+    };
+    use crate::app::compass::{CompassApp, CompassBuilderInventory};
+
+    #[test]
+    fn test_build_write_and_verify() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("edges")).unwrap();
+        std::fs::write(dir.path().join("vertices.csv"), "vertex_id,x,y\n").unwrap();
+        std::fs::write(dir.path().join("edges/edges.csv"), "edge_id\n").unwrap();
+
+        let manifest = DatasetManifest::build(dir.path(), None).unwrap();
+        let paths = manifest
+            .files
+            .iter()
+            .map(|f| f.path.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(paths, vec!["edges/edges.csv", "vertices.csv"]);
+        assert_eq!(manifest.dataset_version.len(), 16);
+
+        // writing the manifest does not change the manifest of the directory
+        let path = manifest.write(dir.path()).unwrap();
+        assert_eq!(DatasetManifest::build(dir.path(), None).unwrap(), manifest);
+        let read = DatasetManifest::read(&path).unwrap();
+        assert_eq!(read, manifest);
+        assert!(read.verify(dir.path()).unwrap().is_empty());
+
+        std::fs::write(dir.path().join("vertices.csv"), "vertex_id,x,z\n").unwrap();
+        std::fs::remove_file(dir.path().join("edges/edges.csv")).unwrap();
+        let mismatches = read.verify(dir.path()).unwrap();
+        assert_eq!(mismatches.len(), 2, "{mismatches:?}");
+        assert!(mismatches[0].starts_with("edges/edges.csv is missing"));
+        assert!(mismatches[1].starts_with("vertices.csv has hash"));
+
+        let config = DatasetManifestConfig {
+            manifest_input_file: path.to_string_lossy().to_string(),
+            verify: None,
+        };
+        assert!(config.load().is_err());
+        let unverified = DatasetManifestConfig {
+            verify: Some(false),
+            ..config
+        };
+        assert_eq!(unverified.load().unwrap(), manifest);
+    }
+
+    #[test]
+    fn test_dataset_manifest() {
+        let test_dir = speeds_test_config_file().parent().unwrap().to_path_buf();
+        let dataset_dir = tempfile::tempdir().unwrap();
+        let vertices = dataset_dir.path().join("test_vertices.csv");
+        std::fs::copy(test_dir.join("test_vertices.csv"), &vertices).unwrap();
+        let manifest = DatasetManifest::build(dataset_dir.path(), Some("speeds-v1")).unwrap();
+        let manifest_file = manifest.write(dataset_dir.path()).unwrap();
+
+        let config = speeds_test_config(&format!(
+            "[system.dataset_manifest]\nmanifest_input_file = {:?}",
+            manifest_file.to_str().unwrap(),
+        ));
+        let app = build_app(&config);
+        let mut queries = vec![serde_json::json!({"origin_vertex": 0, "destination_vertex": 2})];
+        let responses = app.run(&mut queries, None).expect("run failed");
+        assert_eq!(responses[0]["dataset_version"], "speeds-v1");
+
+        // a changed dataset file fails verification
+        std::fs::write(&vertices, "vertex_id,x,y\n").unwrap();
+        let builder = CompassBuilderInventory::new().unwrap();
+        let error = CompassApp::new(&config, &builder).err().unwrap();
+        assert!(
+            error.to_string().contains("test_vertices.csv has size"),
+            "{error}"
+        );
+    }
+}
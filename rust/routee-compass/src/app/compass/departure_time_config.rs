@@ -0,0 +1,92 @@
+use super::CompassAppError;
+use routee_compass_core::model::state::DepartureTime;
+use serde::{Deserialize, Serialize};
+
+/// configures the optional `[system.departure_time]` state variable, holding the absolute
+/// date and time each trip departs its origin. time-dependent traversal models add this
+/// variable to the state model themselves.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct DepartureTimeConfig {
+    /// RFC 3339 departure time of queries without a `departure_time` key. if omitted,
+    /// those queries fail, so that responses never depend on when they were run.
+    pub default: Option<String>,
+}
+
+impl DepartureTimeConfig {
+    /// reads the configured default departure time, if any
+    pub fn default_departure_time(&self) -> Result<Option<DepartureTime>, CompassAppError> {
+        self.default
+            .as_ref()
+            .map(|time_str| {
+                DepartureTime::parse(time_str).map_err(|e| {
+                    CompassAppError::BuildFailure(format!(
+                        "system.departure_time.default must be an RFC 3339 string, found '{time_str}': {e}"
+                    ))
+                })
+            })
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::compass::compass_app_test_ops::speeds_test_app;
+
+    #[test]
+    fn test_departure_time() {
+        let query = || serde_json::json!({"origin_vertex": 0, "destination_vertex": 2});
+
+        // without the system config, the state model has no departure time
+        let app = speeds_test_app("");
+        let responses = app.run(&mut vec![query()], None).expect("run failed");
+        assert!(
+            responses[0]["route"]["final_state"]
+                .get("departure_time")
+                .is_none(),
+            "{}",
+            responses[0]
+        );
+
+        // queries without a departure time fail unless a default is configured
+        let app = speeds_test_app("[system.departure_time]\n");
+        let mut queries = vec![
+            query(),
+            serde_json::json!({
+                "origin_vertex": 0,
+                "destination_vertex": 2,
+                "departure_time": "2025-06-01T08:00:00-06:00"
+            }),
+            serde_json::json!({
+                "origin_vertex": 0,
+                "destination_vertex": 2,
+                "departure_time": "08:00"
+            }),
+        ];
+        let responses = app.run(&mut queries, None).expect("run failed");
+        let response = |departure_time: Option<&str>| {
+            responses
+                .iter()
+                .find(|r| r["request"]["departure_time"].as_str() == departure_time)
+                .expect("missing response")
+        };
+        assert!(response(None).get("error").is_some(), "{}", response(None));
+        let valid = response(Some("2025-06-01T08:00:00-06:00"));
+        assert_eq!(
+            valid["route"]["final_state"]["departure_time"],
+            serde_json::json!("2025-06-01T14:00:00+00:00"),
+            "{valid}"
+        );
+        let invalid = response(Some("08:00"));
+        assert!(invalid.get("error").is_some(), "{invalid}");
+
+        let app = speeds_test_app("[system.departure_time]\ndefault = \"2025-06-01T06:00:00Z\"\n");
+        let responses = app.run(&mut vec![query()], None).expect("run failed");
+        assert_eq!(
+            responses[0]["route"]["final_state"]["departure_time"],
+            serde_json::json!("2025-06-01T06:00:00+00:00"),
+            "{}",
+            responses[0]
+        );
+    }
+}
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+/// configures the optional `[system.metrics]` report written at the end of each
+/// batch run.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct MetricsConfig {
+    /// destination file for the metrics report. overwritten by each run.
+    pub filename: String,
+    /// format of the report file
+    #[serde(default)]
+    pub format: MetricsFormat,
+    /// if true, the size of each search result is estimated with allocative. this
+    /// adds a traversal of each search tree to every query.
+    #[serde(default)]
+    pub estimate_memory: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MetricsFormat {
+    /// a single JSON object with run-level metrics and an array of per-query metrics
+    #[default]
+    Json,
+    /// a long-format table with columns `metric,query,value`, where run-level
+    /// metrics have an empty `query` column
+    Csv,
+}
@@ -1,14 +1,30 @@
 mod compass_app;
 mod compass_app_config;
+pub mod compass_app_config_ops;
 mod compass_app_error;
 pub mod compass_app_ops;
+pub mod compass_app_schema;
 mod compass_app_system;
+#[cfg(test)]
+pub(crate) mod compass_app_test_ops;
 mod compass_builder_inventory;
 mod compass_component_error;
 mod compass_input_field;
 mod compass_json_extensions;
 pub mod compass_map_matching;
+pub mod compass_replan;
+pub mod dataset_manifest;
+mod departure_time_config;
+mod metrics_config;
+mod plugin_metrics;
+mod query_deduplication;
+mod query_scheduler;
+mod query_stream;
+pub mod remote_files;
 pub mod response;
+pub mod response_cache;
+mod run_metrics;
+pub mod trip_chain;
 
 pub use compass_app::CompassApp;
 pub use compass_app_config::{CompassAppConfig, SearchConfig};
@@ -19,3 +35,10 @@ pub use compass_builder_inventory::CompassBuilderInventory;
 pub use compass_component_error::CompassComponentError;
 pub use compass_input_field::CompassInputField;
 pub use compass_json_extensions::CompassJsonExtensions;
+pub use departure_time_config::DepartureTimeConfig;
+pub use metrics_config::{MetricsConfig, MetricsFormat};
+pub use plugin_metrics::{PluginMetrics, StageTiming};
+pub use query_deduplication::{QueryDeduplicationConfig, QueryDuplicates};
+pub use query_scheduler::QueryScheduler;
+pub use query_stream::{QueryStream, ResponseStream};
+pub use run_metrics::{QueryMetrics, RunMetrics};
@@ -0,0 +1,62 @@
+use crate::plugin::{input::InputPlugin, output::OutputPlugin};
+use routee_compass_core::util::duration_extension::DurationExtension;
+pub use routee_compass_core::util::span::StageTiming;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// per-plugin timings for a run of a CompassApp. input plugins are timed by the wall
+/// time of each plugin stage. output plugins run inside each parallel search, so their
+/// times are summed across threads.
+pub struct PluginMetrics {
+    pub input_plugins: Vec<StageTiming>,
+    pub output_plugins: Vec<StageTiming>,
+}
+
+impl PluginMetrics {
+    pub fn new(
+        input_plugins: &[Arc<dyn InputPlugin>],
+        output_plugins: &[Arc<dyn OutputPlugin>],
+    ) -> PluginMetrics {
+        PluginMetrics {
+            input_plugins: input_plugins
+                .iter()
+                .map(|p| StageTiming::new(p.name()))
+                .collect(),
+            output_plugins: output_plugins
+                .iter()
+                .map(|p| StageTiming::new(p.name()))
+                .collect(),
+        }
+    }
+
+    /// total time spent across all output plugins
+    pub fn output_plugins_duration(&self) -> Duration {
+        self.output_plugins.iter().map(StageTiming::duration).sum()
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "input_plugins": self.input_plugins.iter().map(StageTiming::to_json).collect::<Vec<_>>(),
+            "output_plugins": self.output_plugins.iter().map(StageTiming::to_json).collect::<Vec<_>>(),
+        })
+    }
+
+    /// logs the time spent in each plugin, in order of execution
+    pub fn log_report(&self) {
+        for (kind, timings) in [
+            ("input", &self.input_plugins),
+            ("output", &self.output_plugins),
+        ] {
+            for (idx, timing) in timings.iter().enumerate() {
+                log::info!(
+                    "{kind} plugin {} '{}': {} over {} queries",
+                    idx + 1,
+                    timing.name,
+                    timing.duration().hhmmss(),
+                    timing.calls()
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,199 @@
+use crate::app::compass::response::response_ordering::ResponseOrderingConfig;
+use crate::app::search::query_parameters_key;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+
+/// configures the optional `[system.deduplication]` pass, where identical queries in a
+/// batch are run once and the response is copied to each duplicate.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct QueryDeduplicationConfig {
+    /// additional query keys that only identify a query, such as custom identifiers,
+    /// and should be ignored when matching queries.
+    #[serde(default)]
+    pub ignore_keys: Vec<String>,
+}
+
+impl QueryDeduplicationConfig {
+    /// query keys that only identify a query and never affect its response
+    pub const IGNORED_QUERY_KEYS: [&'static str; 4] = [
+        "request_id",
+        "group_id",
+        ResponseOrderingConfig::INPUT_INDEX_KEY,
+        QueryDuplicates::DEDUP_ID_KEY,
+    ];
+
+    /// the keys ignored when matching queries
+    pub fn ignore_keys(&self) -> HashSet<String> {
+        Self::IGNORED_QUERY_KEYS
+            .iter()
+            .map(|k| k.to_string())
+            .chain(self.ignore_keys.iter().cloned())
+            .collect()
+    }
+}
+
+/// the duplicate queries removed from a batch, by the dedup id of the query that is
+/// run in their place.
+#[derive(Debug, Default)]
+pub struct QueryDuplicates {
+    duplicates: HashMap<u64, Vec<Value>>,
+    n_queries: usize,
+}
+
+impl QueryDuplicates {
+    /// query key tagging a query that is run on behalf of its duplicates. it is removed
+    /// from the request of the response.
+    pub const DEDUP_ID_KEY: &'static str = "dedup_id";
+
+    /// removes the queries that are identical to an earlier query, ignoring the
+    /// keys in `ignore_keys`. the order of the remaining queries is preserved.
+    pub fn deduplicate(
+        queries: Vec<Value>,
+        ignore_keys: &HashSet<String>,
+    ) -> (Vec<Value>, QueryDuplicates) {
+        let n_queries = queries.len();
+        let mut unique: Vec<Value> = vec![];
+        let mut unique_index: HashMap<String, usize> = HashMap::new();
+        let mut duplicates: HashMap<u64, Vec<Value>> = HashMap::new();
+        for query in queries.into_iter() {
+            let key = query_parameters_key(&query, ignore_keys);
+            match unique_index.get(&key) {
+                Some(idx) => {
+                    let dedup_id = *idx as u64;
+                    if let Some(obj) = unique[*idx].as_object_mut() {
+                        obj.insert(Self::DEDUP_ID_KEY.to_string(), Value::from(dedup_id));
+                    }
+                    duplicates.entry(dedup_id).or_default().push(query);
+                }
+                None => {
+                    unique_index.insert(key, unique.len());
+                    unique.push(query);
+                }
+            }
+        }
+        (
+            unique,
+            QueryDuplicates {
+                duplicates,
+                n_queries,
+            },
+        )
+    }
+
+    /// number of queries before deduplication
+    pub fn n_queries(&self) -> usize {
+        self.n_queries
+    }
+
+    /// number of queries removed as duplicates
+    pub fn n_duplicates(&self) -> usize {
+        self.duplicates.values().map(|d| d.len()).sum()
+    }
+
+    /// fraction of the queries that were removed as duplicates
+    pub fn dedup_ratio(&self) -> f64 {
+        if self.n_queries == 0 {
+            0.0
+        } else {
+            self.n_duplicates() as f64 / self.n_queries as f64
+        }
+    }
+
+    /// copies a response to each duplicate of its query, echoing the original request
+    /// of the duplicate. returns the response followed by the copies.
+    pub fn fan_out(&self, mut response: Value) -> Vec<Value> {
+        let dedup_id = response
+            .get_mut("request")
+            .and_then(Value::as_object_mut)
+            .and_then(|request| request.remove(Self::DEDUP_ID_KEY))
+            .and_then(|id| id.as_u64());
+        let duplicates = match dedup_id.and_then(|id| self.duplicates.get(&id)) {
+            Some(duplicates) => duplicates,
+            None => return vec![response],
+        };
+        let copies = duplicates
+            .iter()
+            .map(|request| {
+                let mut copy = response.clone();
+                copy["request"] = request.clone();
+                copy
+            })
+            .collect::<Vec<_>>();
+        std::iter::once(response).chain(copies).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::compass::compass_app_test_ops::speeds_test_app;
+    use crate::app::compass::QueryDuplicates;
+    use serde_json::json;
+
+    #[test]
+    fn test_deduplicate_and_fan_out() {
+        let config = QueryDeduplicationConfig {
+            ignore_keys: vec![String::from("name")],
+        };
+        let queries = vec![
+            json!({"request_id": "a", "origin_vertex": 0, "destination_vertex": 5}),
+            json!({"request_id": "b", "origin_vertex": 0, "destination_vertex": 6}),
+            json!({"destination_vertex": 5, "origin_vertex": 0, "request_id": "c", "name": "x"}),
+        ];
+        let (unique, duplicates) = QueryDuplicates::deduplicate(queries, &config.ignore_keys());
+        assert_eq!(unique.len(), 2);
+        assert_eq!(duplicates.n_queries(), 3);
+        assert_eq!(duplicates.n_duplicates(), 1);
+        assert!((duplicates.dedup_ratio() - 1.0 / 3.0).abs() < 1e-9);
+
+        let response = json!({"request": unique[0].clone(), "route": [1, 2]});
+        let responses = duplicates.fan_out(response);
+        assert_eq!(responses.len(), 2);
+        assert_eq!(responses[0]["request"]["request_id"], "a");
+        assert!(responses[0]["request"]
+            .get(QueryDuplicates::DEDUP_ID_KEY)
+            .is_none());
+        assert_eq!(responses[1]["request"]["request_id"], "c");
+        assert_eq!(responses[1]["route"], json!([1, 2]));
+
+        let unduplicated = json!({"request": unique[1].clone()});
+        assert_eq!(duplicates.fan_out(unduplicated).len(), 1);
+    }
+
+    #[test]
+    fn test_query_deduplication() {
+        let app = speeds_test_app("");
+        // queries 0, 2 and 4 are duplicates, as are queries 1 and 3
+        let mut queries = (0..5)
+            .map(|idx| {
+                serde_json::json!({
+                    "origin_vertex": idx % 2,
+                    "destination_vertex": 2,
+                    "request_id": idx
+                })
+            })
+            .collect::<Vec<_>>();
+        let config = serde_json::json!({
+            "parallelism": 2,
+            "deduplication": {},
+            "response_ordering": { "ordering": "input_order", "window_size": 3 }
+        });
+        let responses = app.run(&mut queries, Some(&config)).expect("run failed");
+        assert_eq!(responses.len(), 5);
+        for (idx, response) in responses.iter().enumerate() {
+            let request = &response["request"];
+            assert_eq!(request["request_id"], serde_json::json!(idx), "{response}");
+            assert_eq!(request["origin_vertex"], serde_json::json!(idx % 2));
+            assert!(request.get(QueryDuplicates::DEDUP_ID_KEY).is_none());
+            assert!(response.get("error").is_none(), "{response}");
+            assert_eq!(
+                response["route"]["path"],
+                responses[idx % 2]["route"]["path"]
+            );
+        }
+        // query 2 shares the window, and so the search, of query 0
+        assert_eq!(responses[2]["route"], responses[0]["route"]);
+    }
+}
@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+/// declares how the queries of a batch are scheduled across threads.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryScheduler {
+    /// queries are assigned to `parallelism` bins up front, balanced by their query
+    /// weight estimates, and each bin is run by one thread.
+    #[default]
+    Static,
+    /// queries are run by a pool of `parallelism` threads that each take the next
+    /// query (or query group) as they become free, heaviest estimates first, so that
+    /// a slow query does not hold up the queries behind it.
+    WorkStealing,
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::compass::compass_app_test_ops::speeds_test_app;
+
+    #[test]
+    fn test_run_work_stealing() {
+        let app = speeds_test_app("");
+        let mut queries = (0..5)
+            .map(|idx| {
+                serde_json::json!({
+                    "origin_vertex": 0,
+                    "destination_vertex": 2,
+                    "request_id": idx
+                })
+            })
+            .collect::<Vec<_>>();
+        let config = serde_json::json!({ "parallelism": 2, "scheduler": "work_stealing" });
+        let responses = app.run(&mut queries, Some(&config)).expect("run failed");
+        assert_eq!(responses.len(), 5);
+        assert!(responses.iter().all(|r| r.get("error").is_none()));
+    }
+}
@@ -0,0 +1,115 @@
+use super::{CompassApp, CompassAppError};
+use serde_json::Value;
+use std::collections::VecDeque;
+
+/// the state of a batch of queries that is run incrementally, one chunk at a time, as
+/// responses are consumed. a new chunk is only run once every response of the previous
+/// chunk has been taken, so at most one chunk of responses is held in memory, and
+/// dropping the stream cancels the queries that have not yet run.
+///
+/// the stream does not borrow the app that runs it, so that language bindings can hold
+/// it between calls. see [`ResponseStream`] for an iterator over a stream.
+pub struct QueryStream {
+    queries: VecDeque<Value>,
+    config: Option<Value>,
+    chunk_size: usize,
+    responses: VecDeque<Value>,
+}
+
+impl QueryStream {
+    /// creates a stream over a batch of queries.
+    ///
+    /// # Arguments
+    ///
+    /// * `queries` - the queries to run
+    /// * `config` - configuration for each chunk run, as in [`CompassApp::run`]
+    /// * `chunk_size` - number of queries run at a time. queries within a chunk run in
+    ///   parallel, so this should be at least the configured parallelism.
+    pub fn new(queries: Vec<Value>, config: Option<Value>, chunk_size: usize) -> QueryStream {
+        QueryStream {
+            queries: queries.into(),
+            config,
+            chunk_size: chunk_size.max(1),
+            responses: VecDeque::new(),
+        }
+    }
+
+    /// number of queries that have not yet been run
+    pub fn remaining_queries(&self) -> usize {
+        self.queries.len()
+    }
+
+    /// takes the next response, running the next chunk of queries with the app if all
+    /// buffered responses have been taken. returns None once every query has been run
+    /// and every response taken. a chunk that fails to run returns its error, and the
+    /// stream continues with the following chunk.
+    pub fn next_response(&mut self, app: &CompassApp) -> Option<Result<Value, CompassAppError>> {
+        while self.responses.is_empty() {
+            if self.queries.is_empty() {
+                return None;
+            }
+            let n = self.chunk_size.min(self.queries.len());
+            let mut chunk = self.queries.drain(..n).collect::<Vec<_>>();
+            match app.run(&mut chunk, self.config.as_ref()) {
+                Ok(responses) => self.responses.extend(responses),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+        self.responses.pop_front().map(Ok)
+    }
+}
+
+/// an iterator over the responses of a [`QueryStream`] run by a [`CompassApp`].
+/// see [`CompassApp::run_streaming`].
+pub struct ResponseStream<'a> {
+    app: &'a CompassApp,
+    stream: QueryStream,
+}
+
+impl<'a> ResponseStream<'a> {
+    pub fn new(app: &'a CompassApp, stream: QueryStream) -> ResponseStream<'a> {
+        ResponseStream { app, stream }
+    }
+
+    /// number of queries that have not yet been run
+    pub fn remaining_queries(&self) -> usize {
+        self.stream.remaining_queries()
+    }
+}
+
+impl Iterator for ResponseStream<'_> {
+    type Item = Result<Value, CompassAppError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.stream.next_response(self.app)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::compass::compass_app_test_ops::speeds_test_app;
+
+    #[test]
+    fn test_run_streaming() {
+        let app = speeds_test_app("");
+        let queries = (0..3)
+            .map(|idx| {
+                serde_json::json!({
+                    "origin_vertex": 0,
+                    "destination_vertex": 2,
+                    "request_id": idx
+                })
+            })
+            .collect::<Vec<_>>();
+        let mut stream = app.run_streaming(queries, None, 2);
+        let first = stream
+            .next()
+            .expect("expected a response")
+            .expect("run failed");
+        assert_eq!(first["request"]["request_id"], serde_json::json!(0));
+        // only the first chunk of two queries has run
+        assert_eq!(stream.remaining_queries(), 1);
+        let rest = stream.collect::<Result<Vec<_>, _>>().expect("run failed");
+        assert_eq!(rest.len(), 2);
+    }
+}
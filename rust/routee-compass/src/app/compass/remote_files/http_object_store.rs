@@ -0,0 +1,46 @@
+use super::{CacheValidators, ObjectStore, ObjectStoreError, ObjectStoreResponse};
+use crate::app::http::HttpClient;
+use std::io::Write;
+
+/// reads objects from a plain HTTP mirror through the shared [`HttpClient`]. cached
+/// copies are revalidated with conditional requests, so an object is only downloaded
+/// again when the mirror has a new version.
+#[derive(Clone, Debug)]
+pub struct HttpObjectStore {
+    pub client: HttpClient,
+}
+
+impl ObjectStore for HttpObjectStore {
+    fn get(
+        &self,
+        url: &str,
+        validators: &CacheValidators,
+        writer: &mut dyn Write,
+    ) -> Result<ObjectStoreResponse, ObjectStoreError> {
+        let mut request_headers = vec![];
+        if let Some(etag) = &validators.etag {
+            request_headers.push(("If-None-Match", etag.clone()));
+        }
+        if let Some(last_modified) = &validators.last_modified {
+            request_headers.push(("If-Modified-Since", last_modified.clone()));
+        }
+        let response = self.client.get(url, &request_headers, writer)?;
+        match response.status {
+            200 => {
+                let validators = CacheValidators {
+                    etag: response.headers.get("etag").cloned(),
+                    last_modified: response.headers.get("last-modified").cloned(),
+                };
+                Ok(ObjectStoreResponse::Downloaded {
+                    bytes: response.bytes,
+                    validators,
+                })
+            }
+            304 if !validators.is_empty() => Ok(ObjectStoreResponse::NotModified),
+            status => Err(ObjectStoreError::RequestFailed(
+                url.to_string(),
+                format!("request returned status {status}"),
+            )),
+        }
+    }
+}
@@ -0,0 +1,11 @@
+#[cfg(feature = "http_mirror")]
+mod http_object_store;
+mod object_store;
+mod remote_files_config;
+mod remote_files_ops;
+
+#[cfg(feature = "http_mirror")]
+pub use http_object_store::HttpObjectStore;
+pub use object_store::{CacheValidators, ObjectStore, ObjectStoreError, ObjectStoreResponse};
+pub use remote_files_config::{RemoteFilesConfig, DEFAULT_CACHE_DIRECTORY};
+pub use remote_files_ops::{is_remote, resolve_remote_files};
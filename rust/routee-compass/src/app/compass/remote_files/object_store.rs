@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+
+/// a store of remote input files addressed by URL, such as a plain HTTP mirror.
+/// objects are streamed to a writer so that large network files are never held
+/// in memory.
+pub trait ObjectStore {
+    /// streams the object at a URL to a writer. when the validators of a cached copy
+    /// are provided and the object has not changed since they were issued, nothing is
+    /// written and [`ObjectStoreResponse::NotModified`] is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - location of the object
+    /// * `validators` - validators of a cached copy of the object, possibly empty
+    /// * `writer` - destination of the object contents
+    ///
+    /// # Returns
+    ///
+    /// whether the object was written, or an error
+    fn get(
+        &self,
+        url: &str,
+        validators: &CacheValidators,
+        writer: &mut dyn Write,
+    ) -> Result<ObjectStoreResponse, ObjectStoreError>;
+}
+
+/// identifies a version of an object, so that a cached copy is only downloaded again
+/// when the object has changed.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CacheValidators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+impl CacheValidators {
+    pub fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
+}
+
+#[derive(Debug)]
+pub enum ObjectStoreResponse {
+    /// the object has not changed since the provided validators were issued
+    NotModified,
+    /// the object was written, along with the validators of this version
+    Downloaded {
+        bytes: u64,
+        validators: CacheValidators,
+    },
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ObjectStoreError {
+    #[error("unsupported URL '{0}': {1}")]
+    UnsupportedUrl(String, String),
+    #[error("request for '{0}' failed: {1}")]
+    RequestFailed(String, String),
+    #[cfg(feature = "http_mirror")]
+    #[error(transparent)]
+    Http(#[from] crate::app::http::HttpError),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, path::PathBuf, time::Duration};
+
+/// default directory name, within the system temp directory, for downloaded input files
+pub const DEFAULT_CACHE_DIRECTORY: &str = "routee-compass-remote-files";
+
+/// configures how input files are fetched from a plain HTTP mirror, which requires the
+/// `http_mirror` feature. any config value with a key ending in `input_file` or
+/// `input_files` may be an `http://` URL instead of a local path. each file is streamed
+/// to the cache directory when the config is read. later reads of the same URL
+/// revalidate the cached copy with the mirror and only download it again if it changed.
+///
+/// requests are sent over plain HTTP with the shared `HttpClient` of the `http` module,
+/// as no TLS implementation is a dependency of this crate, so `https://` URLs are
+/// rejected. object storage such as S3 is not read directly: there is no request
+/// signing, so a bucket must be served through a plain HTTP mirror or proxy.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct RemoteFilesConfig {
+    /// directory for downloaded files, by default a directory in the system temp directory
+    pub cache_directory: Option<String>,
+    /// timeout for connecting to and reading from a host, 60 seconds by default
+    pub timeout_seconds: Option<u64>,
+    /// headers added to each request, such as an `Authorization` header
+    pub headers: Option<HashMap<String, String>>,
+    /// if true (the default), cached files are revalidated with the host on each read
+    pub revalidate: Option<bool>,
+}
+
+impl RemoteFilesConfig {
+    pub fn cache_directory(&self) -> PathBuf {
+        match &self.cache_directory {
+            Some(dir) => PathBuf::from(dir),
+            None => std::env::temp_dir().join(DEFAULT_CACHE_DIRECTORY),
+        }
+    }
+
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_seconds.unwrap_or(60))
+    }
+
+    pub fn revalidate(&self) -> bool {
+        self.revalidate.unwrap_or(true)
+    }
+}
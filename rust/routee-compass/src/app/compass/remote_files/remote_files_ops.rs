@@ -0,0 +1,297 @@
+use super::RemoteFilesConfig;
+use crate::app::compass::CompassAppError;
+use serde_json::Value;
+
+/// true if an input file path is a URL. only `http://` URLs can be fetched, but other
+/// URLs are detected so that they are rejected with a clear error.
+pub fn is_remote(path: &str) -> bool {
+    ["http://", "https://", "s3://"]
+        .iter()
+        .any(|scheme| path.starts_with(scheme))
+}
+
+/// replaces the URLs of remote input files in a config with the paths of local copies,
+/// downloading the files that are not yet cached from a plain HTTP mirror. the download
+/// settings are read from the `system.remote_files` section of the config.
+pub fn resolve_remote_files(mut config: Value) -> Result<Value, CompassAppError> {
+    let remote_files: RemoteFilesConfig = match config.pointer("/system/remote_files") {
+        Some(value) => serde_json::from_value(value.clone()).map_err(|e| {
+            CompassAppError::BuildFailure(format!("invalid system.remote_files config: {e}"))
+        })?,
+        None => RemoteFilesConfig::default(),
+    };
+    resolve_value(&mut config, None, &remote_files)?;
+    Ok(config)
+}
+
+fn resolve_value(
+    value: &mut Value,
+    key: Option<&str>,
+    remote_files: &RemoteFilesConfig,
+) -> Result<(), CompassAppError> {
+    let is_input_file_key = key
+        .map(|k| k.ends_with("input_file") || k.ends_with("input_files"))
+        .unwrap_or_default();
+    match value {
+        Value::String(path) if is_input_file_key && is_remote(path) => {
+            validate_url(path)?;
+            *path = fetch(path, remote_files)?;
+            Ok(())
+        }
+        Value::Array(values) => values
+            .iter_mut()
+            .try_for_each(|v| resolve_value(v, key, remote_files)),
+        Value::Object(obj) => obj
+            .iter_mut()
+            .try_for_each(|(k, v)| resolve_value(v, Some(k), remote_files)),
+        _ => Ok(()),
+    }
+}
+
+/// rejects the remote input files that could never be downloaded, before any request is sent
+fn validate_url(url: &str) -> Result<(), CompassAppError> {
+    if url.starts_with("https://") {
+        return Err(CompassAppError::BuildFailure(format!(
+            "input file '{url}' is an HTTPS URL, which is not supported as files are fetched over plain HTTP without TLS. serve the file from a plain http:// mirror"
+        )));
+    }
+    if url.starts_with("s3://") {
+        return Err(CompassAppError::BuildFailure(format!(
+            "input file '{url}' is an s3:// URL, which is not supported as requests are not signed. serve the bucket through a plain http:// mirror"
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "http_mirror"))]
+fn fetch(url: &str, _remote_files: &RemoteFilesConfig) -> Result<String, CompassAppError> {
+    Err(CompassAppError::BuildFailure(format!(
+        "input file '{url}' is a URL, which requires routee-compass to be built with the 'http_mirror' feature"
+    )))
+}
+
+/// returns the path of the cached copy of a remote file. the file is downloaded if not
+/// yet cached, or if the cached copy is revalidated and the host has a newer version.
+#[cfg(feature = "http_mirror")]
+fn fetch(url: &str, remote_files: &RemoteFilesConfig) -> Result<String, CompassAppError> {
+    use super::{CacheValidators, ObjectStoreResponse};
+    use std::io::Write;
+
+    let failed = |e: String| {
+        CompassAppError::BuildFailure(format!("failure downloading input file '{url}': {e}"))
+    };
+    let cache_directory = remote_files.cache_directory();
+    let filename = cache_filename(url);
+    let path = cache_directory.join(&filename);
+    let validators_path = cache_directory.join(format!("{filename}.validators.json"));
+    let path_string = path.to_string_lossy().to_string();
+    let cached = path.is_file();
+    if cached && !remote_files.revalidate() {
+        log::info!("using cached copy of {url} at {path_string}");
+        return Ok(path_string);
+    }
+    let validators: CacheValidators = match cached {
+        true => std::fs::read_to_string(&validators_path)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default(),
+        false => CacheValidators::default(),
+    };
+
+    std::fs::create_dir_all(&cache_directory).map_err(|e| failed(e.to_string()))?;
+    // stream to a temporary file first so that an interrupted download is not cached
+    let partial = cache_directory.join(format!("{filename}.{}.part", std::process::id()));
+    let store = object_store(remote_files);
+    let result = std::fs::File::create(&partial)
+        .map_err(|e| e.to_string())
+        .and_then(|file| {
+            let mut writer = std::io::BufWriter::new(file);
+            let response = store
+                .get(url, &validators, &mut writer)
+                .map_err(|e| e.to_string())?;
+            writer.flush().map_err(|e| e.to_string())?;
+            Ok(response)
+        });
+    if !matches!(result, Ok(ObjectStoreResponse::Downloaded { .. })) {
+        let _ = std::fs::remove_file(&partial);
+    }
+    match result {
+        Ok(ObjectStoreResponse::NotModified) => {
+            log::info!("cached copy of {url} at {path_string} is up to date");
+            Ok(path_string)
+        }
+        Ok(ObjectStoreResponse::Downloaded { bytes, validators }) => {
+            // the validators of the previous copy must not outlive it
+            let _ = std::fs::remove_file(&validators_path);
+            std::fs::rename(&partial, &path).map_err(|e| failed(e.to_string()))?;
+            if !validators.is_empty() {
+                let json = serde_json::to_string(&validators).map_err(|e| failed(e.to_string()))?;
+                std::fs::write(&validators_path, json).map_err(|e| failed(e.to_string()))?;
+            }
+            log::info!("downloaded {bytes} bytes from {url} to {path_string}");
+            Ok(path_string)
+        }
+        Err(e) if cached => {
+            log::warn!("unable to revalidate {url}, using cached copy at {path_string}: {e}");
+            Ok(path_string)
+        }
+        Err(e) => Err(failed(e)),
+    }
+}
+
+/// the object store that reads remote files from a plain HTTP mirror
+#[cfg(feature = "http_mirror")]
+fn object_store(remote_files: &RemoteFilesConfig) -> Box<dyn super::ObjectStore> {
+    let mut client = crate::app::http::HttpClient::new(remote_files.timeout());
+    client.headers = remote_files.headers.clone().unwrap_or_default();
+    Box::new(super::HttpObjectStore { client })
+}
+
+/// the name of the cached copy of a remote file. the name starts with a hash of the full
+/// URL so that distinct URLs never share a cached copy, and ends with the file name of the
+/// URL so that the compression of the file is still detected by its extension.
+#[cfg(feature = "http_mirror")]
+fn cache_filename(url: &str) -> String {
+    let hash = twox_hash::xxhash3_128::Hasher::oneshot(url.as_bytes());
+    let without_query = url.split(['?', '#']).next().unwrap_or(url);
+    let basename = without_query
+        .rsplit('/')
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' | '.' => c,
+            _ => '_',
+        })
+        .collect::<String>();
+    format!("{hash:032x}-{basename}")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[cfg(feature = "http_mirror")]
+    use crate::app::http::test_server::serve;
+    use serde_json::json;
+
+    #[test]
+    fn test_local_paths_unchanged() {
+        let config = json!({"graph": {"vertex_list_input_file": "vertices.csv"}, "label": "http://example.com"});
+        let resolved = resolve_remote_files(config.clone()).unwrap();
+        assert_eq!(resolved, config);
+    }
+
+    #[cfg(not(feature = "http_mirror"))]
+    #[test]
+    fn test_remote_files_require_feature() {
+        let config = json!({"graph": {"vertex_list_input_file": "http://mirror/vertices.csv"}});
+        let error = resolve_remote_files(config).unwrap_err().to_string();
+        assert!(error.contains("'http_mirror' feature"), "{error}");
+    }
+
+    #[test]
+    fn test_unsupported_urls_rejected() {
+        let https =
+            json!({"graph": {"vertex_list_input_file": "https://example.com/vertices.csv"}});
+        let error = resolve_remote_files(https).unwrap_err().to_string();
+        assert!(error.contains("HTTPS URL"), "{error}");
+
+        let s3 = json!({"graph": {"vertex_list_input_file": "s3://bucket/vertices.csv"}});
+        let error = resolve_remote_files(s3).unwrap_err().to_string();
+        assert!(error.contains("plain http:// mirror"), "{error}");
+    }
+
+    #[cfg(feature = "http_mirror")]
+    #[test]
+    fn test_cache_filenames_distinct() {
+        let a = cache_filename("http://host/a/b_c.csv.gz");
+        let b = cache_filename("http://host/a_b/c.csv.gz");
+        assert_ne!(a, b);
+        assert!(a.ends_with("-b_c.csv.gz"), "{a}");
+        assert!(cache_filename("http://host/edges.csv?v=2").ends_with("-edges.csv"));
+    }
+
+    #[cfg(feature = "http_mirror")]
+    #[test]
+    fn test_download_and_revalidate() {
+        let body = "vertex_id,x,y\n0,1.0,2.0\n";
+        let (port, server) = serve(vec![
+            format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nETag: \"v1\"\r\n\r\n{body}",
+                body.len()
+            ),
+            String::from("HTTP/1.1 304 Not Modified\r\n\r\n"),
+        ]);
+        let cache_dir = tempfile::tempdir().unwrap();
+        let config = json!({
+            "graph": {"edge_list": [{"input_file": format!("http://127.0.0.1:{port}/bucket/edges.csv")}]},
+            "system": {"remote_files": {
+                "cache_directory": cache_dir.path().to_str().unwrap()
+            }}
+        });
+        let resolved = resolve_remote_files(config.clone()).unwrap();
+        let path = resolved["graph"]["edge_list"][0]["input_file"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), body);
+
+        // the second read revalidates the cached copy with the ETag of the first response
+        let revalidated = resolve_remote_files(config.clone()).unwrap();
+        assert_eq!(revalidated, resolved);
+        let requests = server.join().unwrap();
+        assert!(
+            requests[0].starts_with("GET /bucket/edges.csv HTTP/1.1"),
+            "{}",
+            requests[0]
+        );
+        assert!(
+            requests[1].contains("If-None-Match: \"v1\"\r\n"),
+            "{}",
+            requests[1]
+        );
+
+        // the server has stopped, so the third read falls back to the cached copy
+        let cached = resolve_remote_files(config).unwrap();
+        assert_eq!(cached, resolved);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), body);
+        let partial_files = std::fs::read_dir(cache_dir.path())
+            .unwrap()
+            .filter(|e| {
+                e.as_ref()
+                    .unwrap()
+                    .path()
+                    .to_string_lossy()
+                    .ends_with(".part")
+            })
+            .count();
+        assert_eq!(partial_files, 0);
+    }
+
+    #[cfg(feature = "http_mirror")]
+    #[test]
+    fn test_redirect_and_headers() {
+        let (port, server) = serve(vec![
+            String::from("HTTP/1.1 302 Found\r\nLocation: /moved/edges.csv\r\n\r\n"),
+            String::from(
+                "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n6\r\nedge_i\r\n4\r\nd\n0\n\r\n0\r\n\r\n",
+            ),
+        ]);
+        let cache_dir = tempfile::tempdir().unwrap();
+        let config = json!({
+            "graph": {"edge_list_input_file": format!("http://127.0.0.1:{port}/edges.csv")},
+            "system": {"remote_files": {
+                "cache_directory": cache_dir.path().to_str().unwrap(),
+                "headers": {"Authorization": "Bearer token"}
+            }}
+        });
+        let resolved = resolve_remote_files(config).unwrap();
+        let path = resolved["graph"]["edge_list_input_file"].as_str().unwrap();
+        assert_eq!(std::fs::read_to_string(path).unwrap(), "edge_id\n0\n");
+        let requests = server.join().unwrap();
+        assert!(requests[1].starts_with("GET /moved/edges.csv HTTP/1.1"));
+        assert!(requests
+            .iter()
+            .all(|r| r.contains("Authorization: Bearer token\r\n")));
+    }
+}
@@ -1,6 +1,8 @@
 pub mod internal_writer;
 pub mod mapping;
 pub mod parquet_writer;
+pub mod response_metadata;
+pub mod response_ordering;
 pub mod response_output_format;
 pub mod response_output_format_json;
 pub mod response_output_policy;
@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+/// configures the optional `[system.response_metadata]` section. when set, each response
+/// carries a `metadata` object recording the Compass version, a hash of the loaded
+/// configuration, the dataset version (if a dataset manifest is configured), the search
+/// algorithm and, optionally, a breakdown of the time spent on the query.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ResponseMetadataConfig {
+    /// if true (the default), includes the search and output plugin runtimes of the query
+    pub timing: Option<bool>,
+}
+
+impl ResponseMetadataConfig {
+    /// response key holding the metadata object
+    pub const METADATA_KEY: &'static str = "metadata";
+
+    pub fn include_timing(&self) -> bool {
+        self.timing.unwrap_or(true)
+    }
+}
@@ -0,0 +1,115 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// declares the order in which responses of a batch are written to the response sink.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseOrdering {
+    /// responses are written in the order their searches complete
+    #[default]
+    Completion,
+    /// responses are written in the order their searches complete, and each query is
+    /// tagged with its `input_index`, which is echoed in the response request.
+    InputIndex,
+    /// queries are tagged with their `input_index` and responses are written (and
+    /// returned) in input order. the batch is run in windows of queries, where the
+    /// responses of a window are buffered and reordered before they are written.
+    InputOrder,
+}
+
+/// configures the optional `[system.response_ordering]` of a batch.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ResponseOrderingConfig {
+    #[serde(default)]
+    pub ordering: ResponseOrdering,
+    /// number of queries run at a time when writing in input order, bounding the
+    /// number of responses buffered. defaults to [`ResponseOrderingConfig::DEFAULT_WINDOW_SIZE`].
+    pub window_size: Option<usize>,
+}
+
+impl ResponseOrderingConfig {
+    /// query key holding the position of the query in the batch
+    pub const INPUT_INDEX_KEY: &'static str = "input_index";
+    pub const DEFAULT_WINDOW_SIZE: usize = 10_000;
+
+    pub fn window_size(&self) -> usize {
+        self.window_size.unwrap_or(Self::DEFAULT_WINDOW_SIZE).max(1)
+    }
+
+    /// tags each query with its position in the batch, unless ordering by completion.
+    /// queries that already carry an `input_index`, such as those assigned by a caller
+    /// splitting a larger batch, keep it.
+    pub fn attach_input_index(&self, queries: &mut [Value]) {
+        if self.ordering == ResponseOrdering::Completion {
+            return;
+        }
+        for (idx, query) in queries.iter_mut().enumerate() {
+            if let Some(obj) = query.as_object_mut() {
+                obj.entry(Self::INPUT_INDEX_KEY)
+                    .or_insert_with(|| Value::from(idx));
+            }
+        }
+    }
+
+    /// the input index of a query
+    pub fn query_input_index(query: &Value) -> Option<u64> {
+        query.get(Self::INPUT_INDEX_KEY).and_then(Value::as_u64)
+    }
+
+    /// the input index of a response, found on its request
+    pub fn response_input_index(response: &Value) -> Option<u64> {
+        response.get("request").and_then(Self::query_input_index)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::compass::compass_app_test_ops::speeds_test_app;
+    use serde_json::json;
+
+    #[test]
+    fn test_attach_input_index() {
+        let config = ResponseOrderingConfig {
+            ordering: ResponseOrdering::InputOrder,
+            window_size: None,
+        };
+        let mut queries = vec![json!({}), json!({"input_index": 7}), json!({})];
+        config.attach_input_index(&mut queries);
+        let indices = queries
+            .iter()
+            .map(ResponseOrderingConfig::query_input_index)
+            .collect::<Vec<_>>();
+        assert_eq!(indices, vec![Some(0), Some(7), Some(2)]);
+
+        let mut untagged = vec![json!({})];
+        ResponseOrderingConfig::default().attach_input_index(&mut untagged);
+        assert_eq!(untagged, vec![json!({})]);
+    }
+
+    #[test]
+    fn test_run_in_input_order() {
+        let app = speeds_test_app("");
+        let mut queries = (0..7)
+            .map(|idx| {
+                serde_json::json!({
+                    "origin_vertex": idx % 2,
+                    "destination_vertex": 2,
+                    "request_id": idx
+                })
+            })
+            .collect::<Vec<_>>();
+        let config = serde_json::json!({
+            "parallelism": 3,
+            "response_ordering": { "ordering": "input_order", "window_size": 3 }
+        });
+        let responses = app.run(&mut queries, Some(&config)).expect("run failed");
+        let indices = responses
+            .iter()
+            .map(|r| r["request"]["input_index"].clone())
+            .collect::<Vec<_>>();
+        let expected = (0..7).map(|idx| serde_json::json!(idx)).collect::<Vec<_>>();
+        assert_eq!(indices, expected);
+    }
+}
@@ -107,7 +107,9 @@ fn get_or_create_file_writer(
     if filename.ends_with(".gz") {
         let file = write_mode.open_file(&output_file_path)?;
         let encoder = GzEncoder::new(file, Compression::default());
-        Ok(InternalWriter::GzippedFile { encoder })
+        Ok(InternalWriter::GzippedFile {
+            encoder: Box::new(encoder),
+        })
     } else {
         let file = write_mode.open_file(&output_file_path)?;
         Ok(InternalWriter::File { file })
@@ -0,0 +1,431 @@
+use super::{ResponseCacheConfig, ResponseStore};
+use crate::app::compass::response::response_metadata::ResponseMetadataConfig;
+use crate::app::compass::{CompassAppError, QueryDeduplicationConfig};
+use crate::app::search::query_parameters_key;
+use crate::plugin::output::default::dataset_version::DatasetVersionOutputPlugin;
+use routee_compass_core::model::network::Graph;
+use serde_json::{json, Map, Value};
+use std::{
+    collections::HashSet,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, RwLock,
+    },
+};
+use twox_hash::xxhash3_128::Hasher;
+
+/// the key of a query in a [`ResponseCache`]. keys are computed before a query is run,
+/// since search adds fields to the query, such as the vertices its locations map to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponseCacheKey {
+    /// hash of the graph, and of the config, data version and normalized query, naming
+    /// the entry in the store
+    pub id: String,
+    /// the normalized query, stored with the entry to rule out hash collisions
+    pub query: String,
+    /// the number of times the cache had been cleared when this key was computed.
+    /// responses are not stored if the cache was cleared while their query ran.
+    pub generation: u64,
+}
+
+/// stores the responses of successful queries so that re-running a query, in this
+/// run or a later one, reads its response instead of searching. queries are matched
+/// on all of their keys except those that only identify the query, after the input
+/// plugins have run. entries are only shared between apps with identical graphs and
+/// configurations, and only while the live data read by the models, such as speed and
+/// incident feeds, is unchanged. the cache is cleared when that data changes or the
+/// edge attributes of the app are reloaded.
+///
+/// model inputs that are read once when the app is built, such as speed tables, are
+/// not part of the key, so a cache must be cleared (or moved to a new location) when
+/// they change on disk.
+pub struct ResponseCache {
+    store: Arc<dyn ResponseStore>,
+    graph_hash: u128,
+    /// hash of the configuration the models were built from
+    config_hash: RwLock<String>,
+    /// the data version seen by the most recent lookup, or None before the first lookup
+    data_version: Mutex<Option<Option<String>>>,
+    /// fields describing this app added to each response read from the cache, such as
+    /// the response metadata, replacing those of the app that stored the response
+    annotations: Map<String, Value>,
+    ignore_keys: HashSet<String>,
+    generation: AtomicU64,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl ResponseCache {
+    /// response fields that describe the app that produced a response rather than the
+    /// response itself. they are not stored, as they may differ for the app reading it.
+    pub const APP_FIELDS: [&'static str; 2] = [
+        ResponseMetadataConfig::METADATA_KEY,
+        DatasetVersionOutputPlugin::DATASET_VERSION_KEY,
+    ];
+
+    /// # Arguments
+    ///
+    /// * `config` - response cache configuration
+    /// * `graph` - the graph of the app
+    /// * `config_hash` - hash of the configuration of the app, see [`crate::app::compass::CompassAppConfig::hash`]
+    pub fn new(
+        config: &ResponseCacheConfig,
+        graph: &Graph,
+        config_hash: String,
+    ) -> Result<ResponseCache, CompassAppError> {
+        let store = config.store.build()?;
+        let ignore_keys = QueryDeduplicationConfig::IGNORED_QUERY_KEYS
+            .iter()
+            .map(|k| k.to_string())
+            .chain(config.ignore_keys.iter().cloned())
+            .collect();
+        Ok(ResponseCache {
+            store,
+            graph_hash: graph_content_hash(graph),
+            config_hash: RwLock::new(config_hash),
+            data_version: Mutex::new(None),
+            annotations: Map::new(),
+            ignore_keys,
+            generation: AtomicU64::new(0),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        })
+    }
+
+    /// adds these fields to each response read from the cache. see [`Self::APP_FIELDS`].
+    pub fn with_annotations(mut self, annotations: Map<String, Value>) -> ResponseCache {
+        self.annotations = annotations;
+        self
+    }
+
+    /// the key of this query in the cache
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - the query, after the input plugins have run
+    /// * `data_version` - version of the live data read by the models, see [`Self::sync_data_version`]
+    pub fn key(
+        &self,
+        query: &Value,
+        data_version: Option<&str>,
+    ) -> Result<ResponseCacheKey, CompassAppError> {
+        let generation = self.generation.load(Ordering::Acquire);
+        let query = query_parameters_key(query, &self.ignore_keys);
+        let mut hasher = Hasher::new();
+        hasher.write(self.read_config_hash()?.as_bytes());
+        hasher.write(&[0]);
+        if let Some(version) = data_version {
+            hasher.write(&[1]);
+            hasher.write(version.as_bytes());
+        }
+        hasher.write(&[0]);
+        hasher.write(query.as_bytes());
+        Ok(ResponseCacheKey {
+            id: format!("{:032x}-{:032x}", self.graph_hash, hasher.finish_128()),
+            query,
+            generation,
+        })
+    }
+
+    /// records the version of the live data read by the models of the app, clearing the
+    /// cache if it differs from the version seen by the previous lookup. called before
+    /// the keys of a group of queries are computed.
+    pub fn sync_data_version(&self, data_version: &Option<String>) -> Result<(), CompassAppError> {
+        let mut current = self.data_version.lock().map_err(|e| {
+            CompassAppError::InternalError(format!("response cache lock is poisoned: {e}"))
+        })?;
+        match current.as_ref() {
+            Some(previous) if previous != data_version => {
+                log::info!(
+                    "model data changed from version {} to {}, clearing the response cache",
+                    previous.as_deref().unwrap_or("none"),
+                    data_version.as_deref().unwrap_or("none")
+                );
+                self.clear()?;
+            }
+            _ => {}
+        }
+        *current = Some(data_version.clone());
+        Ok(())
+    }
+
+    /// discards all stored responses and keys new entries with the hash of the
+    /// configuration the models were rebuilt from, such as after the edge attributes of
+    /// the app are reloaded
+    pub fn reset(&self, config_hash: String) -> Result<(), CompassAppError> {
+        let mut current = self.config_hash.write().map_err(|e| {
+            CompassAppError::InternalError(format!("response cache lock is poisoned: {e}"))
+        })?;
+        self.clear()?;
+        *current = config_hash;
+        Ok(())
+    }
+
+    /// discards all stored responses
+    pub fn clear(&self) -> Result<(), CompassAppError> {
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        self.store.clear()
+    }
+
+    /// reads the cached response for a query, echoing this query as the request.
+    pub fn get(
+        &self,
+        key: &ResponseCacheKey,
+        query: &Value,
+    ) -> Result<Option<Value>, CompassAppError> {
+        let entry = self.store.get(&key.id)?;
+        let response = entry.and_then(|mut entry| {
+            if entry.get("query").and_then(Value::as_str) != Some(key.query.as_str()) {
+                return None;
+            }
+            let mut response = entry.get_mut("response")?.take();
+            let obj = response.as_object_mut()?;
+            for field in Self::APP_FIELDS.iter() {
+                obj.remove(*field);
+            }
+            obj.extend(self.annotations.clone());
+            obj.insert(String::from("request"), query.clone());
+            Some(response)
+        });
+        let counter = if response.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        Ok(response)
+    }
+
+    /// stores the response of a query. error responses are not stored, so that failed
+    /// queries are retried, and neither are responses whose query ran while the cache
+    /// was cleared.
+    pub fn put(&self, key: &ResponseCacheKey, response: &Value) -> Result<(), CompassAppError> {
+        if response.get("error").is_some()
+            || self.generation.load(Ordering::Acquire) != key.generation
+        {
+            return Ok(());
+        }
+        let mut response = response.clone();
+        if let Some(obj) = response.as_object_mut() {
+            obj.remove("request");
+            for field in Self::APP_FIELDS.iter() {
+                obj.remove(*field);
+            }
+        }
+        let entry = json!({"query": key.query, "response": response});
+        self.store.put(&key.id, &entry)
+    }
+
+    /// number of queries read from the cache and number of queries not found in the cache
+    pub fn stats(&self) -> (u64, u64) {
+        (
+            self.hits.load(Ordering::Relaxed),
+            self.misses.load(Ordering::Relaxed),
+        )
+    }
+
+    fn read_config_hash(&self) -> Result<String, CompassAppError> {
+        let config_hash = self.config_hash.read().map_err(|e| {
+            CompassAppError::InternalError(format!("response cache lock is poisoned: {e}"))
+        })?;
+        Ok(config_hash.clone())
+    }
+}
+
+/// hashes the vertex coordinates and the endpoints and distances of the edges of a
+/// graph with xxh3. the hash is stable across runs and platforms, so that a persistent
+/// cache can tell whether it was written for the same graph.
+pub fn graph_content_hash(graph: &Graph) -> u128 {
+    let mut hasher = Hasher::new();
+    let mut write_u64 = |value: u64| hasher.write(&value.to_le_bytes());
+    write_u64(graph.vertices.len() as u64);
+    for vertex in graph.vertices.iter() {
+        write_u64(vertex.x().to_bits() as u64);
+        write_u64(vertex.y().to_bits() as u64);
+    }
+    write_u64(graph.edge_lists.len() as u64);
+    for edge_list in graph.edge_lists.iter() {
+        write_u64(edge_list.len() as u64);
+        for edge in edge_list.edges() {
+            write_u64(edge.src_vertex_id.0 as u64);
+            write_u64(edge.dst_vertex_id.0 as u64);
+            write_u64(edge.distance.value.to_bits());
+        }
+    }
+    hasher.finish_128()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::compass::compass_app_test_ops::{build_app, speeds_test_config};
+    use crate::app::compass::response_cache::ResponseStoreConfig;
+    use crate::app::compass::CompassBuilderInventory;
+    use routee_compass_core::model::network::{Edge, EdgeList, Vertex};
+    use uom::si::{f64::Length, length::meter};
+
+    fn graph(distance: f64) -> Graph {
+        let vertices = vec![Vertex::new(0, 0.0, 0.0), Vertex::new(1, 0.01, 0.0)];
+        let edges = vec![Edge::new(0, 0, 0, 1, Length::new::<meter>(distance))];
+        // the adjacency lists do not contribute to the content hash
+        Graph {
+            adj: vec![Default::default(); vertices.len()].into_boxed_slice(),
+            rev: vec![Default::default(); vertices.len()].into_boxed_slice(),
+            vertices: vertices.into_boxed_slice(),
+            edge_lists: vec![EdgeList(edges.into_boxed_slice())],
+            reverse_edges: None,
+            components: None,
+        }
+    }
+
+    fn file_cache_config(dir: &tempfile::TempDir) -> ResponseCacheConfig {
+        ResponseCacheConfig {
+            store: ResponseStoreConfig::File {
+                directory: dir.path().to_str().unwrap().to_string(),
+            },
+            ignore_keys: vec![],
+        }
+    }
+
+    fn new_cache(config: &ResponseCacheConfig, distance: f64, config_hash: &str) -> ResponseCache {
+        ResponseCache::new(config, &graph(distance), config_hash.to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_cache_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = file_cache_config(&dir);
+        let cache = new_cache(&config, 1100.0, "config");
+        let query = json!({"origin_vertex": 0, "destination_vertex": 1, "request_id": "a"});
+        let key = cache.key(&query, None).unwrap();
+        assert_eq!(cache.get(&key, &query).unwrap(), None);
+
+        let response = json!({"request": query, "route": {"path": [0]}});
+        cache.put(&key, &response).unwrap();
+        let error = json!({"request": {"origin_vertex": 1}, "error": "no path"});
+        let error_key = cache.key(&error["request"], None).unwrap();
+        cache.put(&error_key, &error).unwrap();
+        assert_eq!(cache.get(&error_key, &error["request"]).unwrap(), None);
+
+        // a duplicate query with a new id reads the response, echoing its own request
+        let duplicate = json!({"request_id": "b", "destination_vertex": 1, "origin_vertex": 0});
+        let cached = cache
+            .get(&cache.key(&duplicate, None).unwrap(), &duplicate)
+            .unwrap()
+            .expect("expected a cached response");
+        assert_eq!(cached["request"]["request_id"], "b");
+        assert_eq!(cached["route"], json!({"path": [0]}));
+        assert_eq!(cache.stats(), (1, 2));
+
+        // the entry is persisted, but not shared with an app on a different graph or
+        // with a different configuration
+        let reopened = new_cache(&config, 1100.0, "config");
+        assert!(reopened.get(&key, &query).unwrap().is_some());
+        for other in [
+            new_cache(&config, 1200.0, "config"),
+            new_cache(&config, 1100.0, "other config"),
+        ] {
+            let other_key = other.key(&query, None).unwrap();
+            assert_ne!(other_key, key);
+            assert!(other.get(&other_key, &query).unwrap().is_none());
+        }
+    }
+
+    #[test]
+    fn test_data_version_change_clears_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = new_cache(&file_cache_config(&dir), 1100.0, "config");
+        let query = json!({"origin_vertex": 0, "destination_vertex": 1});
+        let response = json!({"route": {"path": [0]}});
+        let version = Some(String::from("speed_update:1"));
+        cache.sync_data_version(&version).unwrap();
+        let key = cache.key(&query, version.as_deref()).unwrap();
+        cache.put(&key, &response).unwrap();
+        assert!(cache.get(&key, &query).unwrap().is_some());
+        assert_ne!(cache.key(&query, None).unwrap().id, key.id);
+
+        // a new feed update clears the entries read from the previous update
+        let update = Some(String::from("speed_update:2"));
+        cache.sync_data_version(&update).unwrap();
+        assert!(cache.get(&key, &query).unwrap().is_none());
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+
+        // and responses of queries that ran while the cache was cleared are not stored
+        cache.put(&key, &response).unwrap();
+        assert!(cache.get(&key, &query).unwrap().is_none());
+
+        // resetting the cache keys new entries with the new config hash
+        let key = cache.key(&query, update.as_deref()).unwrap();
+        cache.put(&key, &response).unwrap();
+        cache.reset(String::from("reloaded config")).unwrap();
+        assert!(cache.get(&key, &query).unwrap().is_none());
+        assert_ne!(cache.key(&query, update.as_deref()).unwrap().id, key.id);
+    }
+
+    #[test]
+    fn test_app_fields_are_not_replayed() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = file_cache_config(&dir);
+        let cache = new_cache(&config, 1100.0, "config");
+        let query = json!({"origin_vertex": 0, "destination_vertex": 1});
+        let key = cache.key(&query, None).unwrap();
+        let response = json!({
+            "route": {"path": [0]},
+            "metadata": {"compass_version": "0.0.1"},
+            "dataset_version": "old"
+        });
+        cache.put(&key, &response).unwrap();
+
+        // an app without metadata reads a response without the fields of the writer
+        let cached = cache.get(&key, &query).unwrap().unwrap();
+        assert_eq!(cached, json!({"route": {"path": [0]}, "request": query}));
+
+        // an app with metadata reads its own
+        let annotations =
+            json!({"metadata": {"compass_version": "0.0.2"}, "dataset_version": "new"});
+        let cache = new_cache(&config, 1100.0, "config")
+            .with_annotations(annotations.as_object().unwrap().clone());
+        let cached = cache.get(&key, &query).unwrap().unwrap();
+        assert_eq!(cached["metadata"], annotations["metadata"]);
+        assert_eq!(cached["dataset_version"], "new");
+    }
+
+    #[test]
+    fn test_response_cache() {
+        let cache_dir = tempfile::tempdir().unwrap();
+        let config = speeds_test_config(&format!(
+            "[system.response_cache]\ntype = \"file\"\ndirectory = {:?}",
+            cache_dir.path().to_str().unwrap()
+        ));
+        let query = |request_id: &str| {
+            serde_json::json!({
+                "origin_vertex": 0,
+                "destination_vertex": 2,
+                "request_id": request_id
+            })
+        };
+
+        let app = build_app(&config);
+        let first = app
+            .run(&mut vec![query("first")], None)
+            .expect("run failed");
+        assert_eq!(app.response_cache.as_ref().unwrap().stats(), (0, 1));
+
+        // a new app reads the response stored by the previous one
+        let app = build_app(&config);
+        let second = app
+            .run(&mut vec![query("second")], None)
+            .expect("run failed");
+        assert_eq!(app.response_cache.as_ref().unwrap().stats(), (1, 0));
+        assert_eq!(second[0]["request"]["request_id"], "second");
+        assert_eq!(second[0]["route"]["path"], first[0]["route"]["path"]);
+
+        // reloading the edge attributes clears the cache
+        let builder = CompassBuilderInventory::new().unwrap();
+        app.reload_edge_attributes(&config, &builder)
+            .expect("reload failed");
+        app.run(&mut vec![query("third")], None)
+            .expect("run failed");
+        assert_eq!(app.response_cache.as_ref().unwrap().stats(), (1, 1));
+    }
+}
@@ -0,0 +1,92 @@
+use super::ResponseStore;
+use crate::app::compass::CompassAppError;
+use serde_json::Value;
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, ErrorKind},
+    path::PathBuf,
+};
+
+/// stores each entry as a JSON file named by its key in a directory. entries are
+/// written to a temporary file and then renamed, so that a run that is interrupted,
+/// or a concurrent run sharing the directory, never reads a partially-written entry.
+pub struct FileResponseStore {
+    pub directory: PathBuf,
+}
+
+impl FileResponseStore {
+    /// opens a store in this directory, creating the directory if it does not exist.
+    pub fn new(directory: &str) -> Result<FileResponseStore, CompassAppError> {
+        let directory = PathBuf::from(directory);
+        std::fs::create_dir_all(&directory).map_err(|e| {
+            CompassAppError::BuildFailure(format!(
+                "failure creating response cache directory {}: {e}",
+                directory.display()
+            ))
+        })?;
+        Ok(FileResponseStore { directory })
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.directory.join(format!("{key}.json"))
+    }
+}
+
+impl ResponseStore for FileResponseStore {
+    fn get(&self, key: &str) -> Result<Option<Value>, CompassAppError> {
+        let path = self.entry_path(key);
+        let file = match File::open(&path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(CompassAppError::InternalError(format!(
+                    "failure reading response cache entry {}: {e}",
+                    path.display()
+                )))
+            }
+        };
+        let entry = serde_json::from_reader(BufReader::new(file))?;
+        Ok(Some(entry))
+    }
+
+    fn put(&self, key: &str, entry: &Value) -> Result<(), CompassAppError> {
+        let path = self.entry_path(key);
+        let tmp_path = self
+            .directory
+            .join(format!("{key}.{:?}.tmp", std::thread::current().id()));
+        let write_result = File::create(&tmp_path)
+            .map_err(|e| e.to_string())
+            .and_then(|file| {
+                serde_json::to_writer(BufWriter::new(file), entry).map_err(|e| e.to_string())
+            })
+            .and_then(|_| std::fs::rename(&tmp_path, &path).map_err(|e| e.to_string()));
+        write_result.map_err(|e| {
+            let _ = std::fs::remove_file(&tmp_path);
+            CompassAppError::InternalError(format!(
+                "failure writing response cache entry {}: {e}",
+                path.display()
+            ))
+        })
+    }
+
+    fn clear(&self) -> Result<(), CompassAppError> {
+        let failed = |e: std::io::Error| {
+            CompassAppError::InternalError(format!(
+                "failure clearing response cache directory {}: {e}",
+                self.directory.display()
+            ))
+        };
+        for entry in std::fs::read_dir(&self.directory).map_err(failed)? {
+            let path = entry.map_err(failed)?.path();
+            let is_entry = path.extension().is_some_and(|ext| ext == "json");
+            if is_entry {
+                match std::fs::remove_file(&path) {
+                    // removed by a concurrent run sharing the directory
+                    Err(e) if e.kind() == ErrorKind::NotFound => {}
+                    result => result.map_err(failed)?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
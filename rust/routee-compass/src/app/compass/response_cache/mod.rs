@@ -0,0 +1,9 @@
+mod cache;
+mod file_response_store;
+mod response_cache_config;
+mod response_store;
+
+pub use cache::{graph_content_hash, ResponseCache, ResponseCacheKey};
+pub use file_response_store::FileResponseStore;
+pub use response_cache_config::{ResponseCacheConfig, ResponseStoreConfig};
+pub use response_store::ResponseStore;
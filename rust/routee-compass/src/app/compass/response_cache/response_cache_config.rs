@@ -0,0 +1,34 @@
+use super::{FileResponseStore, ResponseStore};
+use crate::app::compass::CompassAppError;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// configures the optional `[system.response_cache]`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ResponseCacheConfig {
+    /// where cached responses are stored
+    #[serde(flatten)]
+    pub store: ResponseStoreConfig,
+    /// additional query keys that do not affect the response, such as custom
+    /// identifiers, and should be ignored when matching queries.
+    #[serde(default)]
+    pub ignore_keys: Vec<String>,
+}
+
+/// the storage of a response cache.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum ResponseStoreConfig {
+    /// one JSON file per response in a directory, which is created if it does not exist
+    File { directory: String },
+}
+
+impl ResponseStoreConfig {
+    pub fn build(&self) -> Result<Arc<dyn ResponseStore>, CompassAppError> {
+        match self {
+            ResponseStoreConfig::File { directory } => {
+                Ok(Arc::new(FileResponseStore::new(directory)?))
+            }
+        }
+    }
+}
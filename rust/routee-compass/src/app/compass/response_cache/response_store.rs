@@ -0,0 +1,15 @@
+use crate::app::compass::CompassAppError;
+use serde_json::Value;
+
+/// persistent storage for the entries of a [`super::ResponseCache`], by key. stores are
+/// shared across the threads of a batch.
+pub trait ResponseStore: Send + Sync {
+    /// reads the entry stored at this key, if any
+    fn get(&self, key: &str) -> Result<Option<Value>, CompassAppError>;
+
+    /// stores an entry at this key, replacing any previous entry
+    fn put(&self, key: &str, entry: &Value) -> Result<(), CompassAppError>;
+
+    /// removes all entries
+    fn clear(&self) -> Result<(), CompassAppError>;
+}
@@ -0,0 +1,333 @@
+use super::{MetricsConfig, MetricsFormat, PluginMetrics, StageTiming};
+use crate::{
+    app::{compass::CompassAppError, search::SearchAppResult},
+    plugin::{input::InputPlugin, output::OutputPlugin},
+};
+use routee_compass_core::util::duration_extension::DurationExtension;
+use serde_json::{json, Map, Value};
+use std::{
+    fs::File,
+    io::BufWriter,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// metrics collected while running a batch of queries via a CompassApp.
+/// plugin and phase timings are always collected. when a [`MetricsConfig`] is
+/// provided, per-query search metrics are also collected and a report is written
+/// to the configured file at the end of the batch.
+///
+/// phase timings for input plugins and the total run are wall times. the search,
+/// output plugin and write phases run on many threads, so their times are summed
+/// across threads.
+pub struct RunMetrics {
+    pub plugins: PluginMetrics,
+    pub input_plugins: StageTiming,
+    pub search: StageTiming,
+    pub write: StageTiming,
+    load: Duration,
+    config: Option<MetricsConfig>,
+    queries: Mutex<Vec<QueryMetrics>>,
+}
+
+/// search metrics for a single query.
+#[derive(Clone, Debug)]
+pub struct QueryMetrics {
+    pub iterations: u64,
+    pub max_frontier_size: usize,
+    pub tree_size: usize,
+    pub peak_tree_size: usize,
+    pub route_edges: usize,
+    pub search_runtime: Duration,
+    /// estimated size of the search result, if memory estimation is enabled
+    pub result_size_bytes: Option<usize>,
+}
+
+impl QueryMetrics {
+    fn to_json(&self) -> Value {
+        json!({
+            "iterations": self.iterations,
+            "max_frontier_size": self.max_frontier_size,
+            "tree_size": self.tree_size,
+            "peak_tree_size": self.peak_tree_size,
+            "route_edges": self.route_edges,
+            "search_runtime_secs": self.search_runtime.as_secs_f64(),
+            "result_size_bytes": self.result_size_bytes,
+        })
+    }
+}
+
+impl RunMetrics {
+    /// creates the metrics for a run.
+    ///
+    /// # Arguments
+    ///
+    /// * `load` - time taken to build the CompassApp that is running this batch
+    /// * `input_plugins` - input plugins of the app, in order
+    /// * `output_plugins` - output plugins of the app, in order
+    /// * `config` - if provided, enables the per-query metrics and report file
+    pub fn new(
+        load: Duration,
+        input_plugins: &[Arc<dyn InputPlugin>],
+        output_plugins: &[Arc<dyn OutputPlugin>],
+        config: Option<MetricsConfig>,
+    ) -> RunMetrics {
+        RunMetrics {
+            plugins: PluginMetrics::new(input_plugins, output_plugins),
+            input_plugins: StageTiming::new("input_plugins"),
+            search: StageTiming::new("search"),
+            write: StageTiming::new("write"),
+            load,
+            config,
+            queries: Mutex::new(vec![]),
+        }
+    }
+
+    /// true if a metrics report was requested for this run
+    pub fn enabled(&self) -> bool {
+        self.config.is_some()
+    }
+
+    /// records the search metrics for a single query. has no effect unless
+    /// a metrics report was requested.
+    pub fn record_query(&self, result: &SearchAppResult) {
+        let Some(config) = &self.config else {
+            return;
+        };
+        let result_size_bytes = if config.estimate_memory {
+            Some(allocative::size_of_unique(result))
+        } else {
+            None
+        };
+        let query_metrics = QueryMetrics {
+            iterations: result.iterations,
+            max_frontier_size: result.max_frontier_size,
+            tree_size: result.trees.iter().map(|t| t.len()).sum(),
+            peak_tree_size: result.peak_tree_size,
+            route_edges: result.routes.iter().map(|r| r.len()).sum(),
+            search_runtime: result.search_runtime,
+            result_size_bytes,
+        };
+        if let Ok(mut queries) = self.queries.lock() {
+            queries.push(query_metrics);
+        }
+    }
+
+    /// builds the metrics report as a JSON object.
+    ///
+    /// # Arguments
+    ///
+    /// * `total` - wall time of the complete batch run
+    pub fn to_json(&self, total: Duration) -> Value {
+        let queries = match self.queries.lock() {
+            Ok(queries) => queries.clone(),
+            Err(_) => vec![],
+        };
+        let phases = [
+            ("load", self.load),
+            ("input_plugins", self.input_plugins.duration()),
+            ("search", self.search.duration()),
+            ("output_plugins", self.plugins.output_plugins_duration()),
+            ("write", self.write.duration()),
+            ("total", total),
+        ]
+        .into_iter()
+        .map(|(name, duration)| (name.to_string(), json![duration.as_secs_f64()]))
+        .collect::<Map<_, _>>();
+
+        let max_result_size = queries.iter().filter_map(|q| q.result_size_bytes).max();
+        json!({
+            "queries": queries.len(),
+            "phase_duration_secs": phases,
+            "plugins": self.plugins.to_json(),
+            "search": {
+                "iterations": summary_stats(queries.iter().map(|q| q.iterations as f64)),
+                "max_frontier_size": summary_stats(queries.iter().map(|q| q.max_frontier_size as f64)),
+                "tree_size": summary_stats(queries.iter().map(|q| q.tree_size as f64)),
+                "peak_tree_size": summary_stats(queries.iter().map(|q| q.peak_tree_size as f64)),
+                "search_runtime_secs": summary_stats(queries.iter().map(|q| q.search_runtime.as_secs_f64())),
+            },
+            "memory": {
+                "max_search_result_bytes": max_result_size,
+                "peak_process_bytes": peak_process_memory_bytes(),
+            },
+            "per_query": queries.iter().map(QueryMetrics::to_json).collect::<Vec<_>>(),
+        })
+    }
+
+    /// writes the metrics report to the configured file. has no effect unless
+    /// a metrics report was requested.
+    pub fn write_report(&self, total: Duration) -> Result<(), CompassAppError> {
+        let Some(config) = &self.config else {
+            return Ok(());
+        };
+        let report = self.to_json(total);
+        let file = File::create(&config.filename).map_err(|e| {
+            CompassAppError::InternalError(format!(
+                "failure creating metrics file {}: {e}",
+                config.filename
+            ))
+        })?;
+        let writer = BufWriter::new(file);
+        match config.format {
+            MetricsFormat::Json => serde_json::to_writer_pretty(writer, &report)?,
+            MetricsFormat::Csv => write_csv_report(writer, &report).map_err(|e| {
+                CompassAppError::InternalError(format!(
+                    "failure writing metrics file {}: {e}",
+                    config.filename
+                ))
+            })?,
+        }
+        log::info!(
+            "wrote run metrics to {} after {}",
+            config.filename,
+            total.hhmmss()
+        );
+        Ok(())
+    }
+}
+
+/// min, max, mean and total of a set of observations, or null if there are none.
+fn summary_stats(values: impl Iterator<Item = f64>) -> Value {
+    let values = values.collect::<Vec<_>>();
+    if values.is_empty() {
+        return Value::Null;
+    }
+    let total = values.iter().sum::<f64>();
+    let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    json!({
+        "min": min,
+        "max": max,
+        "mean": total / values.len() as f64,
+        "total": total,
+    })
+}
+
+/// writes the report in long format. run-level metrics are flattened to dot-delimited
+/// names with an empty `query` column, while each per-query metric is written with the
+/// index of the query in the order it completed.
+fn write_csv_report<W: std::io::Write>(writer: W, report: &Value) -> Result<(), csv::Error> {
+    let mut csv_writer = csv::Writer::from_writer(writer);
+    csv_writer.write_record(["metric", "query", "value"])?;
+    let mut rows = vec![];
+    if let Value::Object(map) = report {
+        for (key, value) in map.iter().filter(|(k, _)| *k != "per_query") {
+            flatten_json(key, value, &mut rows);
+        }
+    }
+    for (metric, value) in rows.into_iter() {
+        csv_writer.write_record([metric.as_str(), "", value.as_str()])?;
+    }
+    let per_query = report.get("per_query").and_then(Value::as_array);
+    for (idx, query) in per_query.into_iter().flatten().enumerate() {
+        let query_idx = idx.to_string();
+        let fields = query.as_object().into_iter().flatten();
+        for (metric, value) in fields.filter(|(_, v)| !v.is_null()) {
+            csv_writer.write_record([metric.as_str(), query_idx.as_str(), &value.to_string()])?;
+        }
+    }
+    csv_writer.flush()?;
+    Ok(())
+}
+
+fn flatten_json(prefix: &str, value: &Value, rows: &mut Vec<(String, String)>) {
+    match value {
+        Value::Object(map) => {
+            for (key, v) in map.iter() {
+                flatten_json(&format!("{prefix}.{key}"), v, rows);
+            }
+        }
+        Value::Array(values) => {
+            for (idx, v) in values.iter().enumerate() {
+                flatten_json(&format!("{prefix}.{idx}"), v, rows);
+            }
+        }
+        Value::Null => {}
+        Value::String(s) => rows.push((prefix.to_string(), s.clone())),
+        other => rows.push((prefix.to_string(), other.to_string())),
+    }
+}
+
+/// the peak resident set size of this process, where supported (linux only).
+fn peak_process_memory_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|l| l.starts_with("VmHWM:"))?;
+    let kib = line.split_whitespace().nth(1)?.parse::<u64>().ok()?;
+    Some(kib * 1024)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use routee_compass_core::algorithm::search::{Direction, SearchTree};
+
+    fn mock_metrics(format: MetricsFormat) -> RunMetrics {
+        let config = MetricsConfig {
+            filename: String::from("metrics.out"),
+            format,
+            estimate_memory: true,
+        };
+        let metrics = RunMetrics::new(Duration::from_secs(2), &[], &[], Some(config));
+        for iterations in [10, 30] {
+            let result = SearchAppResult {
+                routes: vec![],
+                trees: vec![SearchTree::new(Direction::Forward)],
+                search_executed_time: String::from(""),
+                search_runtime: Duration::from_millis(500),
+                iterations,
+                max_frontier_size: iterations as usize / 2,
+                peak_tree_size: 1,
+                terminated: None,
+                settled: vec![],
+            };
+            metrics.record_query(&result);
+        }
+        metrics
+    }
+
+    #[test]
+    fn test_report_json() {
+        let metrics = mock_metrics(MetricsFormat::Json);
+        let report = metrics.to_json(Duration::from_secs(5));
+        assert_eq!(report["queries"], json![2]);
+        assert_eq!(report["phase_duration_secs"]["load"], json![2.0]);
+        assert_eq!(report["phase_duration_secs"]["total"], json![5.0]);
+        assert_eq!(report["search"]["iterations"]["mean"], json![20.0]);
+        assert_eq!(report["search"]["max_frontier_size"]["max"], json![15.0]);
+        assert!(report["memory"]["max_search_result_bytes"].is_u64());
+        assert_eq!(report["per_query"].as_array().map(|a| a.len()), Some(2));
+    }
+
+    #[test]
+    fn test_report_csv() {
+        let metrics = mock_metrics(MetricsFormat::Csv);
+        let report = metrics.to_json(Duration::from_secs(5));
+        let mut buffer = vec![];
+        write_csv_report(&mut buffer, &report).expect("failed to write csv");
+        let csv = String::from_utf8(buffer).expect("invalid utf8");
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], "metric,query,value");
+        assert!(lines.contains(&"phase_duration_secs.load,,2.0"));
+        assert!(lines.contains(&"search.iterations.total,,40.0"));
+        assert!(lines.contains(&"iterations,1,30"));
+    }
+
+    #[test]
+    fn test_disabled_records_nothing() {
+        let metrics = RunMetrics::new(Duration::ZERO, &[], &[], None);
+        metrics.record_query(&SearchAppResult {
+            routes: vec![],
+            trees: vec![],
+            search_executed_time: String::from(""),
+            search_runtime: Duration::ZERO,
+            iterations: 1,
+            max_frontier_size: 1,
+            peak_tree_size: 1,
+            terminated: None,
+            settled: vec![],
+        });
+        assert!(!metrics.enabled());
+        assert_eq!(metrics.to_json(Duration::ZERO)["queries"], json![0]);
+    }
+}
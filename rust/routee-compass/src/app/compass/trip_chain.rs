@@ -0,0 +1,155 @@
+use crate::app::compass::CompassAppError;
+use crate::app::search::SearchAppResult;
+use routee_compass_core::algorithm::search::SearchInstance;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// the query key naming the trip chain a query is a leg of
+pub const CHAIN_ID_KEY: &str = "chain_id";
+
+/// the query key ordering the legs of a trip chain
+pub const LEG_INDEX_KEY: &str = "leg_index";
+
+/// the response key describing the leg of a trip chain
+pub const CHAIN_KEY: &str = "chain";
+
+/// true if this query group is a trip chain, whose legs run in order with the state
+/// at the end of each leg carried over to the start of the next.
+pub fn is_chain(group: &[Value]) -> bool {
+    group.first().is_some_and(|q| q.get(CHAIN_ID_KEY).is_some())
+}
+
+/// separates the legs of trip chains from the other queries of a batch. the legs of a
+/// chain are grouped by their `chain_id` and sorted by their `leg_index`. legs without
+/// a `leg_index` follow the indexed legs in input order.
+///
+/// # Returns
+///
+/// the legs of each chain, in the order their chains first appear, and the queries that
+/// are not part of any chain
+pub fn group_chains(queries: Vec<Value>) -> (Vec<Vec<Value>>, Vec<Value>) {
+    let mut chains: Vec<Vec<Value>> = vec![];
+    let mut chain_index: HashMap<String, usize> = HashMap::new();
+    let mut unchained = vec![];
+    for query in queries.into_iter() {
+        let Some(chain_id) = query.get(CHAIN_ID_KEY).map(|c| c.to_string()) else {
+            unchained.push(query);
+            continue;
+        };
+        match chain_index.get(&chain_id) {
+            Some(idx) => chains[*idx].push(query),
+            None => {
+                chain_index.insert(chain_id, chains.len());
+                chains.push(vec![query]);
+            }
+        }
+    }
+    for chain in chains.iter_mut() {
+        chain.sort_by_key(|q| {
+            q.get(LEG_INDEX_KEY)
+                .and_then(Value::as_u64)
+                .unwrap_or(u64::MAX)
+        });
+    }
+    (chains, unchained)
+}
+
+/// the state at the end of the (first) route of a leg, which starts the next leg of its
+/// chain. only accumulated state variables, such as the trip time, distance and state of
+/// charge, are carried over. a route without edges ends in the initial state of the leg.
+///
+/// # Returns
+///
+/// the accumulated state variables by name in their output units, or None if no route
+/// was found
+pub fn final_state(
+    result: &SearchAppResult,
+    si: &SearchInstance,
+) -> Result<Option<Map<String, Value>>, CompassAppError> {
+    let Some(route) = result.routes.first() else {
+        return Ok(None);
+    };
+    let state = match route.last() {
+        Some(last) => last.result_state.clone(),
+        None => si.initial_state()?,
+    };
+    let serialized = si.state_model.serialize_state(&state, true)?;
+    Ok(serialized.as_object().cloned())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::compass::compass_app_test_ops::speeds_test_app;
+    use serde_json::json;
+
+    #[test]
+    fn test_group_chains() {
+        let queries = vec![
+            json!({"id": 0, "chain_id": "a", "leg_index": 1}),
+            json!({"id": 1}),
+            json!({"id": 2, "chain_id": 7, "leg_index": 0}),
+            json!({"id": 3, "chain_id": "a", "leg_index": 0}),
+            json!({"id": 4, "chain_id": "a"}),
+            json!({"id": 5, "chain_id": "7", "leg_index": 0}),
+        ];
+        let (chains, unchained) = group_chains(queries);
+        let ids = |qs: &[Value]| qs.iter().map(|q| q["id"].clone()).collect::<Vec<_>>();
+        let chain_ids = chains.iter().map(|c| ids(c)).collect::<Vec<_>>();
+        assert_eq!(
+            chain_ids,
+            vec![
+                vec![json!(3), json!(0), json!(4)],
+                vec![json!(2)],
+                vec![json!(5)]
+            ]
+        );
+        assert_eq!(ids(&unchained), vec![json!(1)]);
+        assert!(chains.iter().all(|c| is_chain(c)));
+        assert!(!is_chain(&unchained));
+    }
+
+    #[test]
+    fn test_trip_chain() {
+        let app = speeds_test_app("");
+        let trip_time =
+            |r: &serde_json::Value| r["route"]["final_state"]["trip_time"].as_f64().unwrap();
+        let mut single_legs = vec![
+            serde_json::json!({"origin_vertex": 0, "destination_vertex": 1}),
+            serde_json::json!({"origin_vertex": 1, "destination_vertex": 2}),
+        ];
+        let single = app.run(&mut single_legs, None).expect("run failed");
+        let (first_time, second_time) = (trip_time(&single[0]), trip_time(&single[1]));
+
+        // legs are listed out of order, and the second chain fails on its first leg
+        let mut queries = vec![
+            serde_json::json!({"chain_id": "a", "leg_index": 1, "origin_vertex": 1, "destination_vertex": 2}),
+            serde_json::json!({"chain_id": "a", "leg_index": 0, "origin_vertex": 0, "destination_vertex": 1}),
+            serde_json::json!({"chain_id": "b", "leg_index": 0, "origin_vertex": 2, "destination_vertex": 0}),
+            serde_json::json!({"chain_id": "b", "leg_index": 1, "origin_vertex": 0, "destination_vertex": 1}),
+        ];
+        let responses = app.run(&mut queries, None).expect("run failed");
+        let leg = |chain_id: &str, leg_index: u64| {
+            responses
+                .iter()
+                .find(|r| {
+                    r["chain"]["chain_id"] == chain_id && r["chain"]["leg_index"] == leg_index
+                })
+                .unwrap_or_else(|| panic!("missing leg {leg_index} of chain {chain_id}"))
+        };
+
+        let first = leg("a", 0);
+        assert!(first["chain"]["initial_state"].is_null());
+        assert!((trip_time(first) - first_time).abs() < 1e-6);
+        let second = leg("a", 1);
+        assert!(second.get("error").is_none(), "{second}");
+        let carried_time = second["chain"]["initial_state"]["trip_time"]
+            .as_f64()
+            .unwrap();
+        assert!((carried_time - first_time).abs() < 1e-6);
+        assert!((trip_time(second) - (first_time + second_time)).abs() < 1e-6);
+
+        assert!(leg("b", 0).get("error").is_some());
+        assert!(leg("b", 1).get("error").is_some());
+    }
+}
@@ -0,0 +1,329 @@
+use super::{HttpError, HttpUrl};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+/// number of redirects followed before a request fails
+pub const MAX_REDIRECTS: usize = 5;
+
+/// a minimal HTTP/1.1 client for `http://` URLs, shared by the components that read
+/// from the network: the HTTP geocoder and the input files fetched from an HTTP mirror.
+/// requests are sent over plain HTTP, as no TLS implementation is a dependency of this
+/// crate, so HTTPS servers must be reached through a plain HTTP proxy or mirror. responses may be sent with a
+/// Content-Length, chunked or closed at the end of the body.
+#[derive(Clone, Debug)]
+pub struct HttpClient {
+    /// timeout for connecting to and reading from a host
+    pub timeout: Duration,
+    /// headers added to each request, such as an `Authorization` header. they are only
+    /// sent to the host of the original URL, so that credentials are not leaked to the
+    /// target of a redirect.
+    pub headers: HashMap<String, String>,
+    /// if provided, responses with a larger body fail instead of being read
+    pub max_response_bytes: Option<u64>,
+}
+
+/// the status and headers of a response, with header names in lowercase
+#[derive(Debug)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub headers: HashMap<String, String>,
+    /// number of body bytes written
+    pub bytes: u64,
+}
+
+impl HttpClient {
+    pub fn new(timeout: Duration) -> HttpClient {
+        HttpClient {
+            timeout,
+            headers: HashMap::new(),
+            max_response_bytes: None,
+        }
+    }
+
+    /// sends a GET request to a URL, following redirects. the body of a successful (2xx)
+    /// response is streamed to the writer. the bodies of other responses are not read.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - an `http://` URL
+    /// * `request_headers` - headers for this request, sent to every host
+    /// * `writer` - destination of the response body
+    ///
+    /// # Returns
+    ///
+    /// the final response after any redirects, or an error if the request failed or the
+    /// body exceeds the size limit of this client
+    pub fn get(
+        &self,
+        url: &str,
+        request_headers: &[(&str, String)],
+        writer: &mut dyn Write,
+    ) -> Result<HttpResponse, HttpError> {
+        let original = HttpUrl::parse(url)?;
+        let mut current = original.clone();
+        for _ in 0..=MAX_REDIRECTS {
+            let send_headers = current.same_origin(&original);
+            let mut reader = self.send(&current, request_headers, send_headers)?;
+            let (status, headers) = read_head(&mut reader, url)?;
+            match status {
+                301 | 302 | 303 | 307 | 308 => {
+                    let location = headers.get("location").ok_or_else(|| {
+                        HttpError::RequestFailed(
+                            url.to_string(),
+                            format!("redirect {status} has no Location header"),
+                        )
+                    })?;
+                    log::debug!("{url} redirected ({status}) to {location}");
+                    current = current.join(location)?;
+                }
+                200..=299 => {
+                    let bytes = self.copy_body(&mut reader, &headers, writer, url)?;
+                    return Ok(HttpResponse {
+                        status,
+                        headers,
+                        bytes,
+                    });
+                }
+                _ => {
+                    return Ok(HttpResponse {
+                        status,
+                        headers,
+                        bytes: 0,
+                    })
+                }
+            }
+        }
+        Err(HttpError::RequestFailed(
+            url.to_string(),
+            format!("more than {MAX_REDIRECTS} redirects"),
+        ))
+    }
+
+    /// connects to the host of a URL and sends a GET request, returning the response stream
+    fn send(
+        &self,
+        url: &HttpUrl,
+        request_headers: &[(&str, String)],
+        send_headers: bool,
+    ) -> Result<BufReader<TcpStream>, HttpError> {
+        let addr = (url.host.as_str(), url.port)
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| {
+                HttpError::RequestFailed(
+                    url.url(),
+                    format!("unable to resolve host {}", url.host_header()),
+                )
+            })?;
+        let mut stream = TcpStream::connect_timeout(&addr, self.timeout)?;
+        stream.set_read_timeout(Some(self.timeout))?;
+        stream.set_write_timeout(Some(self.timeout))?;
+
+        let mut request = format!(
+            "GET {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: routee-compass\r\nConnection: close\r\n",
+            url.path,
+            url.host_header()
+        );
+        for (name, value) in request_headers.iter() {
+            request.push_str(&format!("{name}: {value}\r\n"));
+        }
+        if send_headers {
+            for (name, value) in self.headers.iter() {
+                request.push_str(&format!("{name}: {value}\r\n"));
+            }
+        }
+        request.push_str("\r\n");
+        stream.write_all(request.as_bytes())?;
+        Ok(BufReader::new(stream))
+    }
+
+    /// streams the body of a response to a writer, returning the number of bytes written
+    fn copy_body(
+        &self,
+        reader: &mut impl BufRead,
+        headers: &HashMap<String, String>,
+        writer: &mut dyn Write,
+        url: &str,
+    ) -> Result<u64, HttpError> {
+        let limit = self.max_response_bytes.unwrap_or(u64::MAX);
+        let chunked = headers
+            .get("transfer-encoding")
+            .is_some_and(|te| te.to_lowercase().contains("chunked"));
+        if chunked {
+            return copy_chunked(reader, writer, limit, url);
+        }
+        match headers.get("content-length") {
+            Some(len) => {
+                let expected = len.parse::<u64>().map_err(|e| {
+                    HttpError::RequestFailed(
+                        url.to_string(),
+                        format!("invalid Content-Length '{len}': {e}"),
+                    )
+                })?;
+                if expected > limit {
+                    return Err(HttpError::ResponseTooLarge(url.to_string(), limit));
+                }
+                let bytes = std::io::copy(&mut reader.take(expected), writer)?;
+                if bytes != expected {
+                    return Err(HttpError::RequestFailed(
+                        url.to_string(),
+                        format!("response ended after {bytes} of {expected} bytes"),
+                    ));
+                }
+                Ok(bytes)
+            }
+            None => copy_limited(reader, writer, limit, url),
+        }
+    }
+}
+
+/// reads the status code and the headers of a response, with header names in lowercase
+fn read_head(
+    reader: &mut impl BufRead,
+    url: &str,
+) -> Result<(u16, HashMap<String, String>), HttpError> {
+    let malformed = |msg: &str| {
+        HttpError::RequestFailed(url.to_string(), format!("malformed HTTP response: {msg}"))
+    };
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let status = line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|s| s.parse::<u16>().ok())
+        .ok_or_else(|| malformed(&format!("invalid status line '{}'", line.trim_end())))?;
+    let mut headers = HashMap::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Err(malformed("response ended before the end of the headers"));
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            return Ok((status, headers));
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            headers.insert(name.trim().to_lowercase(), value.trim().to_string());
+        }
+    }
+}
+
+/// copies a reader to its end, failing if it has more than `limit` bytes
+fn copy_limited(
+    reader: &mut impl Read,
+    writer: &mut dyn Write,
+    limit: u64,
+    url: &str,
+) -> Result<u64, HttpError> {
+    let bytes = std::io::copy(&mut reader.take(limit.saturating_add(1)), writer)?;
+    if bytes > limit {
+        return Err(HttpError::ResponseTooLarge(url.to_string(), limit));
+    }
+    Ok(bytes)
+}
+
+/// decodes a body sent with `Transfer-Encoding: chunked`, failing if it has more than
+/// `limit` bytes
+fn copy_chunked(
+    reader: &mut impl BufRead,
+    writer: &mut dyn Write,
+    limit: u64,
+    url: &str,
+) -> Result<u64, HttpError> {
+    let malformed = |msg: String| {
+        HttpError::RequestFailed(url.to_string(), format!("malformed chunked body: {msg}"))
+    };
+    let mut total: u64 = 0;
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Err(malformed(String::from("body ended before the last chunk")));
+        }
+        // chunk extensions follow the size after a ';'
+        let size_str = line.split(';').next().unwrap_or_default().trim();
+        let size = u64::from_str_radix(size_str, 16)
+            .map_err(|e| malformed(format!("invalid chunk size '{size_str}': {e}")))?;
+        if size == 0 {
+            // skip the trailer headers, which end with an empty line
+            loop {
+                line.clear();
+                if reader.read_line(&mut line)? == 0 || line.trim_end().is_empty() {
+                    return Ok(total);
+                }
+            }
+        }
+        total = total.saturating_add(size);
+        if total > limit {
+            return Err(HttpError::ResponseTooLarge(url.to_string(), limit));
+        }
+        let copied = std::io::copy(&mut reader.take(size), writer)?;
+        if copied != size {
+            return Err(malformed(format!(
+                "chunk ended after {copied} of {size} bytes"
+            )));
+        }
+        line.clear();
+        reader.read_line(&mut line)?;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test_server::serve;
+    use super::*;
+
+    #[test]
+    fn test_chunked_response() {
+        let (port, server) = serve(vec![String::from(
+            "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n4;ext=1\r\nedge\r\n6\r\n_id\n0\n\r\n0\r\nX-Trailer: 1\r\n\r\n",
+        )]);
+        let client = HttpClient::new(Duration::from_secs(5));
+        let mut body = vec![];
+        let response = client
+            .get(
+                &format!("http://127.0.0.1:{port}/edges.csv"),
+                &[],
+                &mut body,
+            )
+            .unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(response.bytes, 10);
+        assert_eq!(String::from_utf8(body).unwrap(), "edge_id\n0\n");
+        let requests = server.join().unwrap();
+        assert!(
+            requests[0].starts_with(&format!(
+                "GET /edges.csv HTTP/1.1\r\nHost: 127.0.0.1:{port}\r\n"
+            )),
+            "{}",
+            requests[0]
+        );
+    }
+
+    #[test]
+    fn test_response_size_limit() {
+        let body = "x".repeat(100);
+        let (port, server) = serve(vec![
+            format!("HTTP/1.1 200 OK\r\nContent-Length: 100\r\n\r\n{body}"),
+            format!("HTTP/1.1 200 OK\r\n\r\n{body}"),
+            format!(
+                "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n64\r\n{body}\r\n0\r\n\r\n"
+            ),
+        ]);
+        let client = HttpClient {
+            max_response_bytes: Some(99),
+            ..HttpClient::new(Duration::from_secs(5))
+        };
+        let url = format!("http://127.0.0.1:{port}/");
+        for _ in 0..3 {
+            let result = client.get(&url, &[], &mut vec![]);
+            assert!(
+                matches!(result, Err(HttpError::ResponseTooLarge(_, 99))),
+                "{result:?}"
+            );
+        }
+        server.join().unwrap();
+    }
+}
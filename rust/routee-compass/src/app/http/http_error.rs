@@ -0,0 +1,11 @@
+#[derive(thiserror::Error, Debug)]
+pub enum HttpError {
+    #[error("unsupported URL '{0}': {1}")]
+    UnsupportedUrl(String, String),
+    #[error("request for '{0}' failed: {1}")]
+    RequestFailed(String, String),
+    #[error("response from '{0}' is larger than the limit of {1} bytes")]
+    ResponseTooLarge(String, u64),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
@@ -0,0 +1,133 @@
+use super::HttpError;
+
+/// the parts of an `http://` URL used to send a request. `https://` URLs are rejected,
+/// as no TLS implementation is a dependency of this crate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HttpUrl {
+    pub host: String,
+    pub port: u16,
+    /// the path and query of the URL, starting with `/`
+    pub path: String,
+}
+
+impl HttpUrl {
+    pub fn parse(url: &str) -> Result<HttpUrl, HttpError> {
+        let unsupported = |msg: &str| HttpError::UnsupportedUrl(url.to_string(), msg.to_string());
+        let rest = match url.strip_prefix("http://") {
+            Some(rest) => rest,
+            None if url.starts_with("https://") => {
+                return Err(unsupported(
+                    "HTTPS is not supported, requests are sent over plain HTTP",
+                ))
+            }
+            None => return Err(unsupported("only http:// URLs are supported")),
+        };
+        let (authority, path) = match rest.find(['/', '?']) {
+            Some(idx) if rest[idx..].starts_with('?') => {
+                (&rest[..idx], format!("/{}", &rest[idx..]))
+            }
+            Some(idx) => (&rest[..idx], rest[idx..].to_string()),
+            None => (rest, String::from("/")),
+        };
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => {
+                let port = port
+                    .parse::<u16>()
+                    .map_err(|e| unsupported(&format!("invalid port: {e}")))?;
+                (host, port)
+            }
+            None => (authority, 80),
+        };
+        if host.is_empty() {
+            return Err(unsupported("missing host"));
+        }
+        Ok(HttpUrl {
+            host: host.to_string(),
+            port,
+            path,
+        })
+    }
+
+    /// resolves the Location of a redirect against this URL
+    pub fn join(&self, location: &str) -> Result<HttpUrl, HttpError> {
+        if location.contains("://") {
+            HttpUrl::parse(location)
+        } else if let Some(rest) = location.strip_prefix("//") {
+            HttpUrl::parse(&format!("http://{rest}"))
+        } else if location.starts_with('/') {
+            Ok(HttpUrl {
+                path: location.to_string(),
+                ..self.clone()
+            })
+        } else {
+            let without_query = self.path.split('?').next().unwrap_or_default();
+            let directory = match without_query.rfind('/') {
+                Some(idx) => &without_query[..=idx],
+                None => "/",
+            };
+            Ok(HttpUrl {
+                path: format!("{directory}{location}"),
+                ..self.clone()
+            })
+        }
+    }
+
+    /// the value of the Host header of a request to this URL, which always names the port
+    pub fn host_header(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+
+    /// true if both URLs are served by the same host and port
+    pub fn same_origin(&self, other: &HttpUrl) -> bool {
+        self.host == other.host && self.port == other.port
+    }
+
+    pub fn url(&self) -> String {
+        match self.port {
+            80 => format!("http://{}{}", self.host, self.path),
+            port => format!("http://{}:{port}{}", self.host, self.path),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let url = HttpUrl::parse("http://localhost:8080/search?format=json").unwrap();
+        assert_eq!(url.host, "localhost");
+        assert_eq!(url.port, 8080);
+        assert_eq!(url.path, "/search?format=json");
+        assert_eq!(url.host_header(), "localhost:8080");
+        let url = HttpUrl::parse("http://example.com").unwrap();
+        assert_eq!((url.port, url.path.as_str()), (80, "/"));
+        assert_eq!(url.host_header(), "example.com:80");
+        assert!(matches!(
+            HttpUrl::parse("https://example.com/search"),
+            Err(HttpError::UnsupportedUrl(..))
+        ));
+    }
+
+    #[test]
+    fn test_join_redirect_location() {
+        let url = HttpUrl::parse("http://host:8080/data/edges.csv").unwrap();
+        assert_eq!(
+            url.join("/other/edges.csv").unwrap().url(),
+            "http://host:8080/other/edges.csv"
+        );
+        assert_eq!(
+            url.join("edges-v2.csv").unwrap().url(),
+            "http://host:8080/data/edges-v2.csv"
+        );
+        assert_eq!(
+            url.join("http://mirror/edges.csv").unwrap().url(),
+            "http://mirror/edges.csv"
+        );
+        assert!(matches!(
+            url.join("https://mirror/edges.csv"),
+            Err(HttpError::UnsupportedUrl(..))
+        ));
+    }
+}
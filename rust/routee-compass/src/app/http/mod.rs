@@ -0,0 +1,9 @@
+mod http_client;
+mod http_error;
+mod http_url;
+
+pub use http_client::{HttpClient, HttpResponse, MAX_REDIRECTS};
+pub use http_error::HttpError;
+pub use http_url::HttpUrl;
+#[cfg(test)]
+pub(crate) mod test_server;
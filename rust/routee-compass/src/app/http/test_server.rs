@@ -0,0 +1,26 @@
+use std::io::{Read, Write};
+use std::net::TcpListener;
+
+/// serves one response per connection on a local port, returning the port and a
+/// handle that joins to the requests received
+pub fn serve(responses: Vec<String>) -> (u16, std::thread::JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let server = std::thread::spawn(move || {
+        responses
+            .into_iter()
+            .map(|response| {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = vec![];
+                let mut buf = [0u8; 1024];
+                while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                    let n = stream.read(&mut buf).unwrap();
+                    request.extend_from_slice(&buf[..n]);
+                }
+                stream.write_all(response.as_bytes()).unwrap();
+                String::from_utf8_lossy(&request).to_string()
+            })
+            .collect()
+    });
+    (port, server)
+}
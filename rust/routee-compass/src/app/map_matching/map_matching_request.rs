@@ -1,5 +1,6 @@
 use crate::app::search::SummaryOp;
 use crate::plugin::output::default::traversal::TraversalOutputFormat;
+use routee_compass_core::model::state::DerivedVariable;
 use serde::Deserialize;
 use std::collections::HashMap;
 
@@ -17,6 +18,13 @@ pub struct MapMatchingRequest {
     /// Operations to perform on the search state for the final summary.
     #[serde(default = "default_summary_ops")]
     pub summary_ops: HashMap<String, SummaryOp>,
+    /// Named arithmetic expressions over state variables to add to the final summary.
+    #[serde(default)]
+    pub derived_variables: HashMap<String, DerivedVariable>,
+    /// Optional seed for any randomness used while matching this trace. Overrides the
+    /// seed derived from the `[system]` seed.
+    #[serde(default)]
+    pub seed: Option<u64>,
 }
 
 fn default_output_format() -> TraversalOutputFormat {
@@ -64,6 +72,20 @@ mod tests {
         assert_eq!(request.trace.len(), 2);
     }
 
+    #[test]
+    fn test_deserialize_encoded_polyline_format() {
+        let json = r#"{
+            "trace": [{"x": -105.0, "y": 40.0}],
+            "output_format": "encoded_polyline6"
+        }"#;
+
+        let request: MapMatchingRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            request.output_format,
+            TraversalOutputFormat::EncodedPolyline6
+        );
+    }
+
     #[test]
     fn test_empty_trace_validation() {
         let request = MapMatchingRequest {
@@ -71,6 +93,8 @@ mod tests {
             search_parameters: None,
             output_format: TraversalOutputFormat::Json,
             summary_ops: HashMap::new(),
+            derived_variables: HashMap::new(),
+            seed: None,
         };
         assert!(request.validate().is_err());
     }
@@ -12,7 +12,7 @@
 //! - Row 0: vertices 0-8 have 2 edges each, vertex 9 has 1 (only vertical) = 19 edges (0-18)
 //! - Row 1: starts at edge 19, etc.
 
-use crate::app::compass::CompassApp;
+use crate::app::compass::{CompassApp, CompassAppConfig, CompassBuilderInventory};
 use std::path::PathBuf;
 
 // =============================================================================
@@ -458,6 +458,47 @@ fn test_lcss_noisy_trace() {
     let trace = TestTrace::noisy_eastward_horizontal(0, 5);
     run_map_match_test(&app, trace, "LCSS noisy horizontal");
 }
+#[test]
+fn test_lcss_random_cuts_reproducible() {
+    let conf_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src")
+        .join("app")
+        .join("compass")
+        .join("test")
+        .join("map_matching_test")
+        .join("compass_lcss.toml");
+    let config_string = std::fs::read_to_string(&conf_file)
+        .expect("failed to read LCSS config")
+        .replace(
+            "similarity_cutoff = 0.5",
+            "similarity_cutoff = 0.5\nrandom_cuts = 3",
+        );
+    let config = CompassAppConfig::from_str(
+        &config_string,
+        &conf_file.to_string_lossy(),
+        config::FileFormat::Toml,
+    )
+    .expect("failed to parse LCSS config");
+    let builder = CompassBuilderInventory::new().expect("failed to build inventory");
+    let app = CompassApp::new(&config, &builder).expect("failed to load LCSS app");
+
+    let trace = TestTrace::noisy_eastward_horizontal(0, 5);
+    let queries = (0..4)
+        .map(|_| serde_json::json!({ "trace": trace.points }))
+        .collect::<Vec<_>>();
+    let run = |parallelism: usize| {
+        let config = serde_json::json!({ "parallelism": parallelism, "seed": 42 });
+        app.map_match(&queries, Some(&config))
+            .expect("map matching failed")
+            .iter()
+            .map(|r| r["point_matches"].clone())
+            .collect::<Vec<_>>()
+    };
+    let single_threaded = run(1);
+    assert_eq!(single_threaded, run(4));
+    assert_eq!(single_threaded, run(1));
+}
+
 #[test]
 fn test_map_matching_with_geometry() {
     let app = load_lcss_app();
@@ -602,3 +643,41 @@ fn test_map_matching_formats_and_summaries() {
         "FeatureCollection"
     );
 }
+
+#[test]
+fn test_map_match_with_vertex_index() {
+    // origins and destinations map match to vertices while LCSS uses an edge index built on demand
+    let conf_file_test = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src")
+        .join("app")
+        .join("compass")
+        .join("test")
+        .join("speeds_test")
+        .join("speeds_test.toml");
+
+    let conf_str = std::fs::read_to_string(&conf_file_test).unwrap();
+    let conf_str_with_mm = format!(
+        "{}\n[map_matching]\ntype = \"lcss\"\n[mapping]\nspatial_index_type = \"vertex\"",
+        conf_str
+    );
+
+    let config = crate::app::compass::CompassAppConfig::from_str(
+        &conf_str_with_mm,
+        conf_file_test.to_str().unwrap(),
+        config::FileFormat::Toml,
+    )
+    .unwrap();
+    let builder = crate::app::compass::CompassBuilderInventory::new().unwrap();
+    let app = CompassApp::new(&config, &builder).unwrap();
+
+    let query = serde_json::json!({
+        "trace": [
+            {"x": -105.1683, "y": 39.7379},
+            {"x": -105.1683, "y": 39.7379}
+        ]
+    });
+    let result = app.map_match(&[query], None).unwrap();
+    assert_eq!(result.len(), 1);
+    assert!(result[0].get("error").is_none(), "{}", result[0]);
+    assert!(result[0].get("matched_path").is_some());
+}
@@ -27,3 +27,107 @@ impl MappingApp {
             .map_err(MappingAppError::MapError)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::app::compass::compass_app_test_ops::speeds_test_app;
+    use crate::app::compass::{CompassApp, CompassAppConfig, CompassBuilderInventory};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_snapping_report() {
+        let app =
+            speeds_test_app("[mapping]\nmatching_type = [\"point\", \"vertex_id\", \"edge_id\"]");
+
+        // the origin is 0.0002 degrees of latitude, about 22 meters, north of vertex 0
+        let query = serde_json::json!({
+            "origin_x": -105.1683038,
+            "origin_y": 39.7381033,
+            "destination_x": -104.8086039,
+            "destination_y": 41.1475252
+        });
+        let too_far = serde_json::json!({
+            "origin_x": -105.1683038,
+            "origin_y": 39.7381033,
+            "destination_x": -104.8086039,
+            "destination_y": 41.1475252,
+            "max_snap_distance": {"distance": 10.0, "unit": "meters"}
+        });
+        let mut queries = vec![query, too_far];
+        let result = app.run(&mut queries, None).expect("run failed");
+        // responses are returned in the order their searches complete
+        let (snapped, too_far): (Vec<_>, Vec<_>) = result
+            .iter()
+            .partition(|r| r["request"].get("max_snap_distance").is_none());
+
+        let snapping = snapped[0].get("snapping").expect("result has no snapping");
+        assert_eq!(snapping["origin"]["vertex_id"], serde_json::json!(0));
+        let origin_distance = snapping["origin"]["distance_meters"].as_f64().unwrap();
+        assert!((origin_distance - 22.2).abs() < 0.5);
+        assert_eq!(snapping["destination"]["vertex_id"], serde_json::json!(1));
+        assert!(snapped[0]["request"].get("origin_snap").is_none());
+
+        assert!(
+            too_far[0].get("error").is_some(),
+            "origin is farther than the query max_snap_distance"
+        );
+    }
+
+    #[test]
+    fn test_endpoint_candidates() {
+        // a directed grid with edges running east and north
+        let conf_file_test = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("app")
+            .join("compass")
+            .join("test")
+            .join("map_matching_test")
+            .join("compass_lcss.toml");
+        let conf_str = std::fs::read_to_string(&conf_file_test).unwrap();
+        let conf_str_with_matching = conf_str.replace(
+            "[mapping]\n",
+            "[mapping]\nmatching_type = [\"point\", \"vertex_id\", \"edge_id\"]\n",
+        );
+        let config = CompassAppConfig::from_str(
+            &conf_str_with_matching,
+            conf_file_test.to_str().unwrap(),
+            config::FileFormat::Toml,
+        )
+        .unwrap();
+        let builder = CompassBuilderInventory::new().unwrap();
+        let app = CompassApp::new(&config, &builder).unwrap();
+
+        // the origin snaps to the eastbound edge 4 -> 5, which cannot reach the destination
+        // edge 14 -> 15 to its north. the northbound edge 4 -> 14 is the second or third
+        // candidate.
+        let query = |k: usize| {
+            serde_json::json!({
+                "origin_x": -104.955,
+                "origin_y": 40.0001,
+                "destination_edge_list": 0,
+                "destination_edge": 27,
+                "endpoint_candidates": k
+            })
+        };
+        let mut queries = vec![query(1), query(3)];
+        let result = app.run(&mut queries, None).expect("run failed");
+        // responses are returned in the order their searches complete
+        let by_k = |k: u64| {
+            result
+                .iter()
+                .find(|r| r["request"]["endpoint_candidates"].as_u64() == Some(k))
+                .expect("missing result")
+        };
+
+        assert!(by_k(1).get("error").is_some(), "{}", by_k(1));
+        assert!(by_k(3).get("error").is_none(), "{}", by_k(3));
+        let snapping = &by_k(3)["snapping"];
+        assert_eq!(snapping["origin_candidates"].as_array().unwrap().len(), 3);
+        assert_eq!(
+            snapping["origin_candidates"][0]["edge_id"],
+            serde_json::json!(8)
+        );
+        assert_eq!(snapping["origin"]["edge_id"], serde_json::json!(9));
+        assert_eq!(by_k(3)["request"]["origin_edge"], serde_json::json!(9));
+    }
+}
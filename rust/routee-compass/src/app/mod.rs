@@ -2,6 +2,8 @@ pub mod bindings;
 pub mod cli;
 pub mod compass;
 pub mod geom;
+#[cfg(any(feature = "http_geocoder", feature = "http_mirror"))]
+pub mod http;
 pub mod map_matching;
 pub mod mapping;
 pub mod search;
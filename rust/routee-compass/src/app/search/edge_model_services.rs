@@ -0,0 +1,45 @@
+use routee_compass_core::{
+    algorithm::search::SearchError,
+    model::{constraint::ConstraintModelService, traversal::TraversalModelService},
+};
+use std::sync::Arc;
+
+/// the traversal and constraint model services of a [`super::SearchApp`]. these hold
+/// the edge attribute tables (such as speeds, grades and restrictions) that are read
+/// when building each search instance, and may be replaced as a unit while the app
+/// is running without rebuilding the graph.
+#[derive(Clone)]
+pub struct EdgeModelServices {
+    pub traversal_model_services: Vec<Arc<dyn TraversalModelService>>,
+    pub constraint_model_services: Vec<Arc<dyn ConstraintModelService>>,
+}
+
+impl EdgeModelServices {
+    pub fn new(
+        traversal_model_services: Vec<Arc<dyn TraversalModelService>>,
+        constraint_model_services: Vec<Arc<dyn ConstraintModelService>>,
+    ) -> EdgeModelServices {
+        EdgeModelServices {
+            traversal_model_services,
+            constraint_model_services,
+        }
+    }
+
+    /// the versions of the live data read by the services, such as speed or incident
+    /// feeds, or None if every service only reads data loaded with it. see
+    /// [`TraversalModelService::data_version`].
+    pub fn data_version(&self) -> Result<Option<String>, SearchError> {
+        let mut versions = vec![];
+        for service in self.traversal_model_services.iter() {
+            versions.extend(service.data_version()?);
+        }
+        for service in self.constraint_model_services.iter() {
+            versions.extend(service.data_version()?);
+        }
+        if versions.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(versions.join(",")))
+        }
+    }
+}
@@ -1,10 +1,20 @@
+mod edge_model_services;
+mod one_to_many_config;
 mod route_output;
 mod search_app;
 mod search_app_graph_ops;
 pub mod search_app_ops;
 mod search_app_result;
+mod search_instance_cache;
+mod tradeoff_query;
 
+pub use edge_model_services::EdgeModelServices;
+pub use one_to_many_config::{OneToManyConfig, OneToManyGrouping};
 pub use route_output::{generate_route_output, RouteOutputError, SummaryOp};
 pub use search_app::SearchApp;
 pub use search_app_graph_ops::SearchAppGraphOps;
 pub use search_app_result::SearchAppResult;
+pub use search_instance_cache::{
+    query_parameters_key, SearchInstanceCache, SearchInstanceCacheConfig,
+};
+pub use tradeoff_query::TradeoffQuery;
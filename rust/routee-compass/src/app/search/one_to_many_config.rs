@@ -0,0 +1,267 @@
+use super::search_instance_cache::{query_parameters_key, SearchInstanceCache};
+use routee_compass_core::model::map::MapJsonKey;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+
+/// configures the optional `[system.one_to_many]` mode, where queries that share an
+/// origin (or a destination) are answered by a single search whose tree is shared
+/// across the group. see [`super::SearchApp::run_group`].
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct OneToManyConfig {
+    /// how queries are assigned to groups
+    #[serde(default)]
+    pub grouping: OneToManyGrouping,
+    /// if provided, groups larger than this are split, so that large groups can
+    /// still be spread across threads.
+    pub max_group_size: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OneToManyGrouping {
+    /// queries with identical model parameters and an identical origin location are
+    /// grouped. queries left ungrouped are then grouped by identical destination location.
+    #[default]
+    Auto,
+    /// queries with identical model parameters and an identical `group_id` value are
+    /// grouped. queries without a `group_id` are run individually.
+    GroupId,
+}
+
+impl OneToManyConfig {
+    /// query key assigning a query to a group when grouping by `group_id`
+    pub const GROUP_ID_KEY: &'static str = "group_id";
+
+    /// partitions queries into groups that may share a search. the order of the groups
+    /// and of the queries within each group follows the order of the input queries.
+    pub fn group_queries(&self, queries: Vec<Value>) -> Vec<Vec<Value>> {
+        let ignore_keys = SearchInstanceCache::default_ignore_keys();
+        let groups = match self.grouping {
+            OneToManyGrouping::GroupId => group_by(queries, |q| {
+                let group_id = q.get(Self::GROUP_ID_KEY)?;
+                Some(format!(
+                    "{}|{group_id}",
+                    query_parameters_key(q, &ignore_keys)
+                ))
+            }),
+            OneToManyGrouping::Auto => {
+                let by_origin = group_by(queries, |q| {
+                    location_group_key(q, &ORIGIN_KEYS, &ignore_keys)
+                });
+                let (grouped, ungrouped): (Vec<_>, Vec<_>) =
+                    by_origin.into_iter().partition(|g| g.len() > 1);
+                let by_destination = group_by(ungrouped.into_iter().flatten().collect(), |q| {
+                    location_group_key(q, &DESTINATION_KEYS, &ignore_keys)
+                });
+                grouped.into_iter().chain(by_destination).collect()
+            }
+        };
+        match self.max_group_size {
+            Some(max) if max > 0 => groups
+                .into_iter()
+                .flat_map(|g| {
+                    let mut chunks = vec![];
+                    let mut remaining = g;
+                    while remaining.len() > max {
+                        let rest = remaining.split_off(max);
+                        chunks.push(remaining);
+                        remaining = rest;
+                    }
+                    chunks.push(remaining);
+                    chunks
+                })
+                .collect(),
+            _ => groups,
+        }
+    }
+
+    /// counts the queries that `auto` grouping would answer with a search shared with
+    /// another query, without grouping them. used to detect batches that would benefit
+    /// from the one-to-many mode when it is not configured.
+    pub fn count_shareable_queries(queries: &[Value]) -> usize {
+        let ignore_keys = SearchInstanceCache::default_ignore_keys();
+        let origin_keys = queries
+            .iter()
+            .map(|q| location_group_key(q, &ORIGIN_KEYS, &ignore_keys))
+            .collect::<Vec<_>>();
+        let origin_counts = key_counts(origin_keys.iter().flatten());
+        let shares_origin = |key: &Option<String>| {
+            key.as_ref()
+                .is_some_and(|k| origin_counts.get(k).is_some_and(|c| *c > 1))
+        };
+        let destination_keys = queries
+            .iter()
+            .zip(origin_keys.iter())
+            .filter(|(_, origin_key)| !shares_origin(origin_key))
+            .filter_map(|(q, _)| location_group_key(q, &DESTINATION_KEYS, &ignore_keys))
+            .collect::<Vec<_>>();
+        let destination_counts = key_counts(destination_keys.iter());
+        let n_origin_shared = origin_keys.iter().filter(|k| shares_origin(k)).count();
+        let n_destination_shared = destination_counts
+            .values()
+            .filter(|c| **c > 1)
+            .sum::<usize>();
+        n_origin_shared + n_destination_shared
+    }
+}
+
+const ORIGIN_KEYS: [MapJsonKey; 5] = [
+    MapJsonKey::OriginVertex,
+    MapJsonKey::OriginEdgeList,
+    MapJsonKey::OriginEdge,
+    MapJsonKey::OriginX,
+    MapJsonKey::OriginY,
+];
+
+const DESTINATION_KEYS: [MapJsonKey; 5] = [
+    MapJsonKey::DestinationVertex,
+    MapJsonKey::DestinationEdgeList,
+    MapJsonKey::DestinationEdge,
+    MapJsonKey::DestinationX,
+    MapJsonKey::DestinationY,
+];
+
+/// the key of the `auto` group of a query, combining its model parameters with the values
+/// of the location keys present on it, or None if none are present.
+fn location_group_key(
+    query: &Value,
+    keys: &[MapJsonKey],
+    ignore_keys: &HashSet<String>,
+) -> Option<String> {
+    let location = location_key(query, keys)?;
+    Some(format!(
+        "{}|{location}",
+        query_parameters_key(query, ignore_keys)
+    ))
+}
+
+fn key_counts<'a>(keys: impl Iterator<Item = &'a String>) -> HashMap<&'a String, usize> {
+    let mut counts = HashMap::new();
+    for key in keys {
+        *counts.entry(key).or_default() += 1;
+    }
+    counts
+}
+
+/// groups queries by the provided key, where queries without a key are each placed
+/// in their own group.
+fn group_by(queries: Vec<Value>, key_fn: impl Fn(&Value) -> Option<String>) -> Vec<Vec<Value>> {
+    let mut groups: Vec<Vec<Value>> = vec![];
+    let mut group_index: HashMap<String, usize> = HashMap::new();
+    for query in queries.into_iter() {
+        match key_fn(&query) {
+            None => groups.push(vec![query]),
+            Some(key) => match group_index.get(&key) {
+                Some(idx) => groups[*idx].push(query),
+                None => {
+                    group_index.insert(key, groups.len());
+                    groups.push(vec![query]);
+                }
+            },
+        }
+    }
+    groups
+}
+
+/// the values of the location keys present on a query, or None if none are present.
+fn location_key(query: &Value, keys: &[MapJsonKey]) -> Option<String> {
+    let location = keys
+        .iter()
+        .filter_map(|k| {
+            let value = query.get(k.as_str())?;
+            Some((k.as_str().to_string(), value.clone()))
+        })
+        .collect::<Map<_, _>>();
+    if location.is_empty() {
+        None
+    } else {
+        Some(Value::Object(location).to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_auto_grouping() {
+        let config = OneToManyConfig::default();
+        let queries = vec![
+            json!({"origin_x": 1.0, "origin_y": 2.0, "destination_x": 3.0, "destination_y": 4.0, "request_id": 0}),
+            json!({"origin_x": 5.0, "origin_y": 6.0, "destination_x": 3.0, "destination_y": 4.0, "request_id": 1}),
+            json!({"origin_x": 1.0, "origin_y": 2.0, "destination_x": 7.0, "destination_y": 8.0, "request_id": 2}),
+            json!({"origin_x": 9.0, "origin_y": 9.0, "destination_x": 3.0, "destination_y": 4.0, "request_id": 3}),
+            json!({"origin_x": 1.0, "origin_y": 2.0, "destination_x": 7.0, "destination_y": 8.0, "model_name": "leaf", "request_id": 4}),
+        ];
+        let groups = config
+            .group_queries(queries)
+            .into_iter()
+            .map(|g| {
+                g.iter()
+                    .map(|q| q["request_id"].clone())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        // queries 0 and 2 share an origin, queries 1 and 3 share a destination, and
+        // query 4 has different model parameters
+        assert_eq!(
+            groups,
+            vec![
+                vec![json![0], json![2]],
+                vec![json![1], json![3]],
+                vec![json![4]]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_count_shareable_queries() {
+        let queries = vec![
+            json!({"origin_vertex": 0, "destination_vertex": 1}),
+            json!({"origin_vertex": 0, "destination_vertex": 2}),
+            json!({"origin_vertex": 3, "destination_vertex": 4}),
+            json!({"origin_vertex": 5, "destination_vertex": 4}),
+            json!({"origin_vertex": 6, "destination_vertex": 7}),
+            json!({"origin_vertex": 0, "destination_vertex": 8, "model_name": "leaf"}),
+        ];
+        // queries 0 and 1 share an origin and queries 2 and 3 share a destination
+        assert_eq!(OneToManyConfig::count_shareable_queries(&queries), 4);
+        assert_eq!(OneToManyConfig::count_shareable_queries(&queries[4..]), 0);
+    }
+
+    #[test]
+    fn test_group_id_grouping() {
+        let config = OneToManyConfig {
+            grouping: OneToManyGrouping::GroupId,
+            max_group_size: Some(2),
+        };
+        let queries = vec![
+            json!({"group_id": "a", "request_id": 0}),
+            json!({"group_id": "b", "request_id": 1}),
+            json!({"group_id": "a", "request_id": 2}),
+            json!({"request_id": 3}),
+            json!({"group_id": "a", "request_id": 4}),
+        ];
+        let groups = config
+            .group_queries(queries)
+            .into_iter()
+            .map(|g| {
+                g.iter()
+                    .map(|q| q["request_id"].clone())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            groups,
+            vec![
+                vec![json![0], json![2]],
+                vec![json![4]],
+                vec![json![1]],
+                vec![json![3]]
+            ]
+        );
+    }
+}
@@ -1,8 +1,14 @@
 use super::summary_op::SummaryOp;
-use crate::plugin::output::default::traversal::TraversalOutputFormat;
+use crate::plugin::output::default::traversal::{
+    GeoJsonProperties, GeometryOutputOptions, TraversalOutputFormat,
+};
+#[cfg(feature = "detailed_costs")]
+use itertools::Itertools;
 use routee_compass_core::algorithm::search::EdgeTraversal;
 use routee_compass_core::algorithm::search::SearchInstance;
 use routee_compass_core::model::cost::TraversalCost;
+use routee_compass_core::model::state::DerivedVariable;
+use routee_compass_core::model::traversal::default::fieldname;
 use serde_json::json;
 use std::collections::HashMap;
 
@@ -18,17 +24,24 @@ pub enum RouteOutputError {
     CostSerialization(String),
     #[error("failed serializing state variable: {0}")]
     StateVariableSerialization(String),
+    #[error("failed evaluating derived variable '{0}': {1}")]
+    DerivedVariableEvaluation(String, String),
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn generate_route_output(
     route: &Vec<EdgeTraversal>,
     si: &SearchInstance,
     output_format: &TraversalOutputFormat,
     summary_ops: &HashMap<String, SummaryOp>,
+    derived_variables: &HashMap<String, DerivedVariable>,
+    geojson_properties: &GeoJsonProperties,
+    geometry_options: &GeometryOutputOptions,
+    cost_breakdown: bool,
 ) -> Result<serde_json::Value, RouteOutputError> {
     if route.is_empty() {
         return Ok(serde_json::json!({
-            "path": output_format.generate_route_output(route, si.map_model.clone(), si.state_model.clone()).map_err(|e| RouteOutputError::OutputGenerationFailed(e.to_string()))?,
+            "path": output_format.generate_route_output(route, si.map_model.clone(), si.state_model.clone(), geojson_properties, geometry_options).map_err(|e| RouteOutputError::OutputGenerationFailed(e.to_string()))?,
             "traversal_summary": serde_json::Map::new(),
             "final_state": serde_json::Value::Null,
             "cost": serde_json::Value::Null,
@@ -36,7 +49,13 @@ pub fn generate_route_output(
     }
     let last_edge = route.last().ok_or(RouteOutputError::EmptyRoute)?;
     let path_json = output_format
-        .generate_route_output(route, si.map_model.clone(), si.state_model.clone())
+        .generate_route_output(
+            route,
+            si.map_model.clone(),
+            si.state_model.clone(),
+            geojson_properties,
+            geometry_options,
+        )
         .map_err(|e| RouteOutputError::OutputGenerationFailed(e.to_string()))?;
     let final_state = si
         .state_model
@@ -60,6 +79,11 @@ pub fn generate_route_output(
         .serialize_cost_info()
         .map_err(|e| RouteOutputError::CostSerialization(e.to_string()))?;
 
+    let distance_index = si
+        .state_model
+        .indexed_iter()
+        .find(|(_, (name, _))| name.as_str() == fieldname::EDGE_DISTANCE)
+        .map(|(i, _)| i);
     let mut traversal_summary = serde_json::Map::new();
     for (i, (name, feature)) in si.state_model.indexed_iter() {
         let op = summary_ops.get(name).cloned().unwrap_or_else(|| {
@@ -70,7 +94,7 @@ pub fn generate_route_output(
             }
         });
 
-        let value = op.summarize_route(route, i);
+        let value = op.summarize_route(route, i, distance_index)?;
 
         let serialized = feature
             .serialize_variable(&value)
@@ -84,7 +108,29 @@ pub fn generate_route_output(
         traversal_summary.insert(name.clone(), summary_entry);
     }
 
-    let result = serde_json::json![{
+    // derived variables are computed from the summarized state variables, in their output units.
+    // non-finite results, such as from a division by zero, are written as null.
+    let mut derived_summary = serde_json::Map::new();
+    for (name, derived) in derived_variables.iter() {
+        let lookup = |variable: &str| {
+            traversal_summary
+                .get(variable)
+                .and_then(|entry| entry.get("value"))
+                .and_then(serde_json::Value::as_f64)
+        };
+        let value = derived.evaluate(&lookup).map_err(|e| {
+            RouteOutputError::DerivedVariableEvaluation(name.clone(), e.to_string())
+        })?;
+        let summary_entry = json!({
+            "value": if value.is_finite() { json![value] } else { serde_json::Value::Null },
+            "unit": serde_json::Value::Null,
+            "expression": derived.expression()
+        });
+        derived_summary.insert(name.clone(), summary_entry);
+    }
+    traversal_summary.extend(derived_summary);
+
+    let mut result = serde_json::json![{
         "final_state": final_state,
         "state_model": state_model,
         "cost_model": cost_model,
@@ -92,5 +138,117 @@ pub fn generate_route_output(
         "path": path_json,
         "traversal_summary": traversal_summary
     }];
+    if cost_breakdown {
+        result["cost_breakdown"] = generate_cost_breakdown(route)?;
+    }
     Ok(result)
 }
+
+/// the cost contributed by each cost feature for each edge in the route, as recorded on
+/// each edge traversal during the search. each entry in the resulting array has the edge's
+/// total and objective cost along with the unweighted cost of each feature, which sum to
+/// the total cost.
+#[cfg(feature = "detailed_costs")]
+fn generate_cost_breakdown(route: &[EdgeTraversal]) -> Result<serde_json::Value, RouteOutputError> {
+    let breakdown = route
+        .iter()
+        .map(|edge_traversal| {
+            let components = edge_traversal
+                .cost
+                .cost_component
+                .iter()
+                .sorted_by(|(a, _), (b, _)| a.cmp(b))
+                .map(|(name, cost)| (name.clone(), json![cost]))
+                .collect::<serde_json::Map<_, _>>();
+            json!({
+                "edge_list_id": edge_traversal.edge_list_id,
+                "edge_id": edge_traversal.edge_id,
+                "total_cost": edge_traversal.cost.total_cost,
+                "objective_cost": edge_traversal.cost.objective_cost,
+                "components": components,
+            })
+        })
+        .collect_vec();
+    Ok(json![breakdown])
+}
+
+/// cost components are only recorded on edge traversals with the `detailed_costs` feature.
+#[cfg(not(feature = "detailed_costs"))]
+fn generate_cost_breakdown(
+    _route: &[EdgeTraversal],
+) -> Result<serde_json::Value, RouteOutputError> {
+    Err(RouteOutputError::CostSerialization(String::from(
+        "cost_breakdown requires routee-compass to be built with the detailed_costs feature",
+    )))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::compass::compass_app_test_ops::speeds_test_app;
+    use routee_compass_core::model::{
+        network::{EdgeId, EdgeListId},
+        unit::Cost,
+    };
+
+    fn mock_route() -> Vec<EdgeTraversal> {
+        let mut cost = TraversalCost::default();
+        cost.insert("trip_distance", Cost::new(3.0), 1.0);
+        cost.insert("trip_time", Cost::new(2.0), 2.0);
+        vec![EdgeTraversal {
+            edge_list_id: EdgeListId(0),
+            edge_id: EdgeId(7),
+            cost,
+            result_state: vec![],
+        }]
+    }
+
+    #[test]
+    #[cfg(feature = "detailed_costs")]
+    fn test_cost_breakdown_reads_cost_components() {
+        let breakdown = generate_cost_breakdown(&mock_route()).unwrap();
+        assert_eq!(
+            breakdown,
+            json!([{
+                "edge_list_id": 0,
+                "edge_id": 7,
+                "total_cost": 5.0,
+                "objective_cost": 7.0,
+                "components": {"trip_distance": 3.0, "trip_time": 2.0},
+            }])
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "detailed_costs"))]
+    fn test_cost_breakdown_requires_detailed_costs() {
+        assert!(generate_cost_breakdown(&mock_route()).is_err());
+    }
+
+    #[test]
+    fn test_derived_variables() {
+        let app = speeds_test_app("");
+        let mut queries = vec![serde_json::json!({
+            "origin_vertex": 0,
+            "destination_vertex": 2,
+            "derived_variables": {
+                "distance_per_time": "edge_distance / edge_time",
+                "double_distance": "2 * edge_distance"
+            }
+        })];
+        let responses = app.run(&mut queries, None).expect("run failed");
+        let summary = &responses[0]["route"]["traversal_summary"];
+        let distance = summary["edge_distance"]["value"].as_f64().unwrap();
+        let time = summary["edge_time"]["value"].as_f64().unwrap();
+        let derived = summary["distance_per_time"]["value"].as_f64().unwrap();
+        assert!((derived - distance / time).abs() < 1e-9);
+        assert_eq!(
+            summary["double_distance"]["value"].as_f64().unwrap(),
+            2.0 * distance
+        );
+        assert_eq!(
+            summary["double_distance"]["expression"],
+            serde_json::json!("2 * edge_distance")
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use super::output_generator::RouteOutputError;
 use routee_compass_core::algorithm::search::EdgeTraversal;
 use routee_compass_core::model::state::StateVariable;
 use serde::{Deserialize, Serialize};
@@ -11,24 +12,33 @@ pub enum SummaryOp {
     First,
     Min,
     Max,
+    /// median of the per-edge values
+    P50,
+    /// 95th percentile of the per-edge values
+    P95,
+    /// average of the per-edge values weighted by `edge_distance`, such as
+    /// the average speed over a route
+    DistanceWeightedAvg,
+    /// population standard deviation of the per-edge values
+    StdDev,
+    /// number of edges with a non-zero value
+    NonzeroCount,
 }
 
 impl SummaryOp {
+    /// summarizes a state variable over a route. `distance_index` is the index of the
+    /// `edge_distance` state variable, which is required by [`SummaryOp::DistanceWeightedAvg`].
     pub fn summarize_route(
         &self,
         route: &[EdgeTraversal],
         state_variable_index: usize,
-    ) -> StateVariable {
-        match self {
-            SummaryOp::Sum => route
-                .iter()
-                .map(|e| e.result_state[state_variable_index])
-                .sum(),
+        distance_index: Option<usize>,
+    ) -> Result<StateVariable, RouteOutputError> {
+        let values = || route.iter().map(|e| e.result_state[state_variable_index]);
+        let summary = match self {
+            SummaryOp::Sum => values().sum(),
             SummaryOp::Avg => {
-                let sum = route
-                    .iter()
-                    .map(|e| e.result_state[state_variable_index])
-                    .sum::<StateVariable>();
+                let sum = values().sum::<StateVariable>();
                 let count = route.len() as f64;
                 StateVariable(sum.0 / count)
             }
@@ -40,16 +50,97 @@ impl SummaryOp {
                 .first()
                 .map(|e| e.result_state[state_variable_index])
                 .unwrap_or(StateVariable::ZERO),
-            SummaryOp::Min => route
-                .iter()
-                .map(|e| e.result_state[state_variable_index])
+            SummaryOp::Min => values()
                 .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal))
                 .unwrap_or(StateVariable::ZERO),
-            SummaryOp::Max => route
-                .iter()
-                .map(|e| e.result_state[state_variable_index])
+            SummaryOp::Max => values()
                 .max_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal))
                 .unwrap_or(StateVariable::ZERO),
-        }
+            SummaryOp::P50 => percentile(values().map(|v| v.0).collect(), 0.5),
+            SummaryOp::P95 => percentile(values().map(|v| v.0).collect(), 0.95),
+            SummaryOp::DistanceWeightedAvg => {
+                let distance_index = distance_index.ok_or_else(|| {
+                    RouteOutputError::OutputGenerationFailed(String::from(
+                        "summary op 'distance_weighted_avg' requires an 'edge_distance' state variable",
+                    ))
+                })?;
+                let (weighted_sum, total_distance) =
+                    route.iter().fold((0.0, 0.0), |(sum, total), e| {
+                        let distance = e.result_state[distance_index].0;
+                        (
+                            sum + e.result_state[state_variable_index].0 * distance,
+                            total + distance,
+                        )
+                    });
+                if total_distance == 0.0 {
+                    StateVariable::ZERO
+                } else {
+                    StateVariable(weighted_sum / total_distance)
+                }
+            }
+            SummaryOp::StdDev => {
+                if route.is_empty() {
+                    StateVariable::ZERO
+                } else {
+                    let count = route.len() as f64;
+                    let mean = values().map(|v| v.0).sum::<f64>() / count;
+                    let variance = values().map(|v| (v.0 - mean).powi(2)).sum::<f64>() / count;
+                    StateVariable(variance.sqrt())
+                }
+            }
+            SummaryOp::NonzeroCount => {
+                StateVariable(values().filter(|v| v.0 != 0.0).count() as f64)
+            }
+        };
+        Ok(summary)
+    }
+}
+
+/// percentile of a set of values using linear interpolation between the closest ranks.
+fn percentile(mut values: Vec<f64>, q: f64) -> StateVariable {
+    if values.is_empty() {
+        return StateVariable::ZERO;
+    }
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let rank = q * (values.len() - 1) as f64;
+    let lo = rank.floor() as usize;
+    let hi = rank.ceil() as usize;
+    let value = values[lo] + (values[hi] - values[lo]) * (rank - lo as f64);
+    StateVariable(value)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use routee_compass_core::model::cost::TraversalCost;
+    use routee_compass_core::model::network::{EdgeId, EdgeListId};
+
+    /// builds a route where each edge state is [edge_distance, edge_speed]
+    fn mock_route(states: &[(f64, f64)]) -> Vec<EdgeTraversal> {
+        states
+            .iter()
+            .enumerate()
+            .map(|(idx, (distance, speed))| EdgeTraversal {
+                edge_list_id: EdgeListId(0),
+                edge_id: EdgeId(idx),
+                cost: TraversalCost::default(),
+                result_state: vec![StateVariable(*distance), StateVariable(*speed)],
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_summary_ops() {
+        let route = mock_route(&[(1.0, 10.0), (3.0, 30.0), (0.0, 0.0), (4.0, 20.0)]);
+        let summarize = |op: SummaryOp| op.summarize_route(&route, 1, Some(0)).unwrap().0;
+        assert_eq!(summarize(SummaryOp::Avg), 15.0);
+        assert_eq!(summarize(SummaryOp::P50), 15.0);
+        assert!((summarize(SummaryOp::P95) - 28.5).abs() < 1e-9);
+        assert_eq!(summarize(SummaryOp::DistanceWeightedAvg), 22.5);
+        assert!((summarize(SummaryOp::StdDev) - 125.0_f64.sqrt()).abs() < 1e-9);
+        assert_eq!(summarize(SummaryOp::NonzeroCount), 3.0);
+        assert!(SummaryOp::DistanceWeightedAvg
+            .summarize_route(&route, 1, None)
+            .is_err());
     }
 }
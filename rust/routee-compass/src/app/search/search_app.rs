@@ -1,20 +1,31 @@
-use super::{search_app_ops, search_app_result::SearchAppResult};
+use super::{
+    search_app_ops, search_app_result::SearchAppResult, EdgeModelServices, SearchInstanceCache,
+    TradeoffQuery,
+};
 use crate::{app::compass::CompassAppError, plugin::PluginError};
 use chrono::Local;
 use routee_compass_core::{
-    algorithm::search::{Direction, SearchAlgorithm, SearchError, SearchInstance},
+    algorithm::search::{
+        a_star::{a_star_ops, FreeFlowHeuristic},
+        util::UTurnConstraintModel,
+        Corridor, Direction, SearchAlgorithm, SearchAlgorithmResult, SearchError, SearchInstance,
+    },
     model::{
-        constraint::ConstraintModelService,
+        constraint::{ConstraintModel, ConstraintModelService},
         cost::cost_model_service::CostModelService,
         label::label_model_service::LabelModelService,
-        map::{MapJsonExtensions, MapModel},
+        map::{MapJsonExtensions, MapJsonKey, MapModel, NearestSearchResult, SnapReport},
         network::Graph,
-        state::StateModel,
+        state::{DepartureTime, StateModel, StateVariable},
         termination::TerminationModel,
-        traversal::TraversalModelService,
+        traversal::{
+            default::{fieldname, time_window::ArrivalWindow},
+            TraversalModelService,
+        },
+        unit::Cost,
     },
 };
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use std::time;
 
 /// a configured and loaded application to execute searches.
@@ -23,15 +34,29 @@ pub struct SearchApp {
     pub graph: Arc<Graph>,
     pub map_model: Arc<MapModel>,
     pub state_model: Arc<StateModel>,
-    pub traversal_model_services: Vec<Arc<dyn TraversalModelService>>,
-    pub constraint_model_services: Vec<Arc<dyn ConstraintModelService>>,
+    /// the traversal and constraint model services, which may be replaced while
+    /// queries run. each search reads the services that were current when its search
+    /// instance was built.
+    edge_model_services: RwLock<Arc<EdgeModelServices>>,
     pub cost_model_service: Arc<CostModelService>,
     pub termination_model: Arc<TerminationModel>,
     pub label_model_service: Arc<dyn LabelModelService>,
     pub default_edge_list: Option<usize>,
+    /// if present, search instances are shared across queries with matching parameters
+    pub search_instance_cache: Option<SearchInstanceCache>,
+    /// if present, replaces the traversal model cost estimates used by A*
+    pub heuristic: Option<FreeFlowHeuristic>,
+    /// default weight on the A* cost estimates, which queries may override
+    pub heuristic_weight: f64,
+    /// departure time of queries without a `departure_time` key, when the state model
+    /// has a departure time
+    pub default_departure_time: Option<DepartureTime>,
 }
 
 impl SearchApp {
+    /// the query key mapping state variable names to their values at the origin
+    pub const INITIAL_STATE_KEY: &'static str = "initial_state";
+
     /// builds a new SearchApp from the required components.
     /// handles all of the specialized boxing that allows for simple parallelization.
     #[allow(clippy::too_many_arguments)]
@@ -52,15 +77,73 @@ impl SearchApp {
             graph,
             map_model,
             state_model,
-            traversal_model_services,
+            edge_model_services: RwLock::new(Arc::new(EdgeModelServices::new(
+                traversal_model_services,
+                constraint_model_services,
+            ))),
             cost_model_service: Arc::new(cost_model_service),
-            constraint_model_services,
             termination_model: Arc::new(termination_model),
             label_model_service,
             default_edge_list,
+            search_instance_cache: None,
+            heuristic: None,
+            heuristic_weight: 1.0,
+            default_departure_time: None,
         }
     }
 
+    /// enables sharing search instances across queries with matching parameters
+    pub fn with_search_instance_cache(mut self, cache: SearchInstanceCache) -> Self {
+        self.search_instance_cache = Some(cache);
+        self
+    }
+
+    /// replaces the traversal model cost estimates used by A* with a precomputed heuristic
+    pub fn with_heuristic(mut self, heuristic: FreeFlowHeuristic) -> Self {
+        self.heuristic = Some(heuristic);
+        self
+    }
+
+    /// runs weighted A* with this default heuristic weight, no less than 1
+    pub fn with_heuristic_weight(mut self, heuristic_weight: f64) -> Self {
+        self.heuristic_weight = heuristic_weight;
+        self
+    }
+
+    /// departs queries without a `departure_time` key at this time
+    pub fn with_default_departure_time(mut self, departure_time: DepartureTime) -> Self {
+        self.default_departure_time = Some(departure_time);
+        self
+    }
+
+    /// the current traversal and constraint model services
+    pub fn edge_model_services(&self) -> Result<Arc<EdgeModelServices>, SearchError> {
+        let services = self
+            .edge_model_services
+            .read()
+            .map_err(|e| SearchError::ReadOnlyPoisonError(e.to_string()))?;
+        Ok(services.clone())
+    }
+
+    /// replaces the traversal and constraint model services used by subsequent searches.
+    /// searches that have already built their search instance continue with the previous
+    /// services. any cached search instances are discarded, as they were built from the
+    /// previous services.
+    pub fn replace_edge_model_services(
+        &self,
+        services: EdgeModelServices,
+    ) -> Result<(), SearchError> {
+        let mut current = self
+            .edge_model_services
+            .write()
+            .map_err(|e| SearchError::ReadOnlyPoisonError(e.to_string()))?;
+        *current = Arc::new(services);
+        if let Some(cache) = &self.search_instance_cache {
+            cache.clear()?;
+        }
+        Ok(())
+    }
+
     /// main interface for running search. takes a user query and builds the instance of the
     /// search assets and then executes a search. if a destination is set on the query, then the
     /// route is computed. if the algorithm produces more than one route, then the result contains
@@ -76,13 +159,89 @@ impl SearchApp {
     pub fn run(
         &self,
         query: &mut serde_json::Value,
+    ) -> Result<(SearchAppResult, SearchInstance), CompassAppError> {
+        self.run_from_state(query, None)
+    }
+
+    /// runs a search as in [`SearchApp::run`], starting from the given state values
+    /// instead of the initial values of the state model, such as the state of a vehicle
+    /// that is re-planning a trip underway.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - a JSON search query provided by the user
+    /// * `initial_state` - if provided, state variable values at the origin of the search
+    ///   by name, in their output units as written by [`StateModel::serialize_state`].
+    ///   state variables that are not listed keep their initial values.
+    ///
+    /// # Results
+    ///
+    /// The complete set of trees, routes, and search assets for this run.
+    pub fn run_from_state(
+        &self,
+        query: &mut serde_json::Value,
+        initial_state: Option<&serde_json::Map<String, serde_json::Value>>,
     ) -> Result<(SearchAppResult, SearchInstance), CompassAppError> {
         let search_start_time = Local::now();
-        let si = self.build_search_instance(query)?;
+        let mut si = self.get_search_instance(query)?;
         self.map_model.map_match(query, &si)?;
+        if query.get(ArrivalWindow::QUERY_KEY).is_some() {
+            // arrival windows depend on the destination vertex, which may be set by map
+            // matching and is ignored when matching cached search instances
+            si = self.build_search_instance(query)?;
+        }
+        if let Some(values) = initial_state {
+            let mut state = si.initial_state()?;
+            for (name, value) in values.iter() {
+                si.state_model.set_serialized(&mut state, name, value)?;
+            }
+            si.initial_state = Some(state);
+        }
+        let results = match TradeoffQuery::from_query(query)? {
+            None => self.run_search(query, &si),
+            Some(tradeoff) => self.run_tradeoff(query, &tradeoff, &si),
+        }?;
+
+        let search_end_time = Local::now();
+        let search_runtime = (search_end_time - search_start_time)
+            .to_std()
+            .unwrap_or(time::Duration::ZERO);
 
-        // depending on the presence of an origin edge or origin vertex, we run each type of query
-        let results = if query.get_origin_edge().is_ok() {
+        log::debug!(
+            "Search Completed in {:?} miliseconds",
+            search_runtime.as_millis()
+        );
+
+        let result = SearchAppResult {
+            routes: results.routes,
+            trees: results.trees,
+            search_executed_time: search_start_time.to_rfc3339(),
+            search_runtime,
+            iterations: results.iterations,
+            max_frontier_size: results.max_frontier_size,
+            peak_tree_size: results.peak_tree_size,
+            terminated: results.terminated,
+            settled: results.settled,
+        };
+
+        Ok((result, si))
+    }
+
+    /// runs the search for a map-matched query with the given search instance.
+    fn run_search(
+        &self,
+        query: &mut serde_json::Value,
+        si: &SearchInstance,
+    ) -> Result<SearchAlgorithmResult, CompassAppError> {
+        let origin_candidates = query.get_snap_candidates(MapJsonKey::OriginCandidates)?;
+        let destination_candidates =
+            query.get_snap_candidates(MapJsonKey::DestinationCandidates)?;
+
+        // depending on the presence of endpoint candidates, an origin edge or origin vertex,
+        // we run each type of query
+        if origin_candidates.is_some() || destination_candidates.is_some() {
+            self.run_candidates(query, origin_candidates, destination_candidates, si)
+        } else if query.get_origin_edge().is_ok() {
             let o = query.get_origin_edge().map_err(|e| {
                 CompassAppError::PluginError(PluginError::BuildFailed(format!("attempting to run search app with query that has an invalid origin_edge value: {e}")))
             })?;
@@ -90,7 +249,7 @@ impl SearchApp {
                 CompassAppError::PluginError(PluginError::BuildFailed(format!("attempting to run search app with query that has an invalid destination_edge value: {e}")))
             })?;
             self.search_algorithm
-                .run_edge_oriented(o, d_opt, query, &Direction::Forward, &si)
+                .run_edge_oriented(o, d_opt, query, &Direction::Forward, si)
                 .map_err(CompassAppError::SearchFailure)
         } else if query.get_origin_vertex().is_ok() {
             let o = query.get_origin_vertex().map_err(|e| {
@@ -101,32 +260,263 @@ impl SearchApp {
             })?;
 
             self.search_algorithm
-                .run_vertex_oriented(o, d, query, &Direction::Forward, &si)
+                .run_vertex_oriented(o, d, query, &Direction::Forward, si)
                 .map_err(CompassAppError::SearchFailure)
         } else {
             Err(CompassAppError::CompassFailure(String::from("SearchApp.run called with query that lacks origin_edge and origin_vertex, at least one required")))
-        }?;
+        }
+    }
 
-        let search_end_time = Local::now();
-        let search_runtime = (search_end_time - search_start_time)
+    /// runs the search once for each weighting of a tradeoff query, reusing the search
+    /// instance with a cost model built for each weighting. the result has one route
+    /// per weighting, in the order of [`TradeoffQuery::second_objective_weights`].
+    fn run_tradeoff(
+        &self,
+        query: &mut serde_json::Value,
+        tradeoff: &TradeoffQuery,
+        si: &SearchInstance,
+    ) -> Result<SearchAlgorithmResult, CompassAppError> {
+        let mut merged = SearchAlgorithmResult::default();
+        for weight in tradeoff.second_objective_weights() {
+            let weighted_query = tradeoff.weighted_query(query, weight);
+            let cost_model = self
+                .cost_model_service
+                .build(&weighted_query, si.state_model.clone())
+                .map_err(|e| {
+                    CompassAppError::SearchFailure(SearchError::BuildError(e.to_string()))
+                })?;
+            let weighted_si = SearchInstance {
+                cost_model: Arc::new(cost_model),
+                ..si.clone()
+            };
+            let result = self.run_search(query, &weighted_si)?;
+            merged
+                .routes
+                .push(result.routes.into_iter().next().unwrap_or_default());
+            merged.trees.extend(result.trees);
+            merged.iterations += result.iterations;
+            merged.max_frontier_size = merged.max_frontier_size.max(result.max_frontier_size);
+            merged.peak_tree_size = merged.peak_tree_size.max(result.peak_tree_size);
+            merged.terminated = merged.terminated.or(result.terminated);
+            merged.settled.extend(result.settled);
+        }
+        Ok(merged)
+    }
+
+    /// runs a single search over the origin and destination candidates retained by map
+    /// matching, using the mapped origin or destination for an endpoint without candidates.
+    /// the snap reports of the query are updated to the candidates used by the route.
+    fn run_candidates(
+        &self,
+        query: &mut serde_json::Value,
+        origin_candidates: Option<Vec<SnapReport>>,
+        destination_candidates: Option<Vec<SnapReport>>,
+        si: &SearchInstance,
+    ) -> Result<SearchAlgorithmResult, CompassAppError> {
+        let origins = match &origin_candidates {
+            Some(candidates) => candidates
+                .iter()
+                .filter_map(|c| c.nearest_search_result())
+                .collect::<Vec<_>>(),
+            None => match query.get_origin_edge() {
+                Ok((edge_list_id, edge_id)) => {
+                    vec![NearestSearchResult::NearestEdge(edge_list_id, edge_id)]
+                }
+                Err(_) => vec![NearestSearchResult::NearestVertex(
+                    query.get_origin_vertex()?,
+                )],
+            },
+        };
+        let destinations = match &destination_candidates {
+            Some(candidates) => candidates
+                .iter()
+                .filter_map(|c| c.nearest_search_result())
+                .collect::<Vec<_>>(),
+            None => match (
+                query.get_destination_edge()?,
+                query.get_destination_vertex()?,
+            ) {
+                (Some((edge_list_id, edge_id)), _) => {
+                    vec![NearestSearchResult::NearestEdge(edge_list_id, edge_id)]
+                }
+                (None, Some(vertex_id)) => vec![NearestSearchResult::NearestVertex(vertex_id)],
+                (None, None) => vec![],
+            },
+        };
+
+        let (result, selection) = self.search_algorithm.run_candidates(
+            &origins,
+            &destinations,
+            &Direction::Forward,
+            si,
+        )?;
+
+        let selected_origin = selection
+            .origin
+            .zip(origin_candidates.as_ref())
+            .and_then(|(i, candidates)| candidates.get(i));
+        if let Some(report) = selected_origin {
+            match report.nearest_search_result() {
+                Some(NearestSearchResult::NearestVertex(v)) => query.add_origin_vertex(v)?,
+                Some(NearestSearchResult::NearestEdge(el, e)) => query.add_origin_edge(el, e)?,
+                None => {}
+            }
+            query.add_snap_report(MapJsonKey::OriginSnap, report)?;
+        }
+        let selected_destination = selection
+            .destination
+            .zip(destination_candidates.as_ref())
+            .and_then(|(i, candidates)| candidates.get(i));
+        if let Some(report) = selected_destination {
+            match report.nearest_search_result() {
+                Some(NearestSearchResult::NearestVertex(v)) => query.add_destination_vertex(v)?,
+                Some(NearestSearchResult::NearestEdge(el, e)) => {
+                    query.add_destination_edge(el, e)?
+                }
+                None => {}
+            }
+            query.add_snap_report(MapJsonKey::DestinationSnap, report)?;
+        }
+        Ok(result)
+    }
+
+    /// runs a group of queries that share an origin (or a destination) with a single
+    /// one-to-many search, backtracking the route for each query from the shared tree.
+    /// queries sharing an origin run a forward search from the origin that ends once
+    /// every destination is settled. queries sharing a destination run a reverse search
+    /// from the destination.
+    ///
+    /// the group shares the search instance built for its first query, so all queries
+    /// in the group should have the same model parameters. the shared tree is not
+    /// included in the results, and each result reports the iterations and runtime of
+    /// the shared search. if the group cannot share a search, such as when the queries
+    /// are edge-oriented, lack destinations, have distinct origins and destinations,
+    /// or the search algorithm does not support one-to-many search, then each query is
+    /// run individually via [`SearchApp::run`].
+    ///
+    /// # Arguments
+    ///
+    /// * `queries` - JSON search queries provided by the user
+    ///
+    /// # Results
+    ///
+    /// The result for each query, in the order provided.
+    pub fn run_group(
+        &self,
+        queries: &mut [serde_json::Value],
+    ) -> Vec<Result<(SearchAppResult, SearchInstance), CompassAppError>> {
+        match self.run_shared_search(queries) {
+            Some(results) => results,
+            None => queries.iter_mut().map(|q| self.run(q)).collect(),
+        }
+    }
+
+    /// attempts a shared search for a group of queries, returning None if the
+    /// queries cannot share a search tree.
+    fn run_shared_search(
+        &self,
+        queries: &mut [serde_json::Value],
+    ) -> Option<Vec<Result<(SearchAppResult, SearchInstance), CompassAppError>>> {
+        let search_start_time = Local::now();
+        // tradeoff queries run a search for each weighting
+        if queries
+            .iter()
+            .any(|q| q.get(TradeoffQuery::QUERY_KEY).is_some())
+        {
+            return None;
+        }
+        let si = self.get_search_instance(queries.first()?).ok()?;
+        let mut endpoints = vec![];
+        for query in queries.iter_mut() {
+            self.map_model.map_match(query, &si).ok()?;
+            if query.get_origin_edge().is_ok() {
+                return None;
+            }
+            let o = query.get_origin_vertex().ok()?;
+            let d = query.get_destination_vertex().ok()??;
+            endpoints.push((o, d));
+        }
+        let (first_o, first_d) = *endpoints.first()?;
+        let (root, leaves, direction) = if endpoints.iter().all(|(o, _)| *o == first_o) {
+            let leaves = endpoints.iter().map(|(_, d)| *d).collect::<Vec<_>>();
+            (first_o, leaves, Direction::Forward)
+        } else if endpoints.iter().all(|(_, d)| *d == first_d) {
+            let leaves = endpoints.iter().map(|(o, _)| *o).collect::<Vec<_>>();
+            (first_d, leaves, Direction::Reverse)
+        } else {
+            return None;
+        };
+        let results = match self
+            .search_algorithm
+            .run_vertex_oriented_many(root, &leaves, &direction, &si)
+        {
+            Err(SearchError::BuildError(msg)) => {
+                log::debug!("running queries individually: {msg}");
+                return None;
+            }
+            Err(e) => {
+                let msg = e.to_string();
+                return Some(
+                    queries
+                        .iter()
+                        .map(|_| {
+                            Err(CompassAppError::SearchFailure(SearchError::InternalError(
+                                msg.clone(),
+                            )))
+                        })
+                        .collect(),
+                );
+            }
+            Ok(results) => results,
+        };
+
+        let search_runtime = (Local::now() - search_start_time)
             .to_std()
             .unwrap_or(time::Duration::ZERO);
-
         log::debug!(
-            "Search Completed in {:?} miliseconds",
+            "shared search for {} queries completed in {:?} miliseconds",
+            queries.len(),
             search_runtime.as_millis()
         );
+        let app_results = results
+            .into_iter()
+            .map(|result| {
+                let result = result.map_err(CompassAppError::SearchFailure)?;
+                let app_result = SearchAppResult {
+                    routes: result.routes,
+                    trees: result.trees,
+                    search_executed_time: search_start_time.to_rfc3339(),
+                    search_runtime,
+                    iterations: result.iterations,
+                    max_frontier_size: result.max_frontier_size,
+                    peak_tree_size: result.peak_tree_size,
+                    terminated: result.terminated,
+                    settled: result.settled,
+                };
+                Ok((app_result, si.clone()))
+            })
+            .collect();
+        Some(app_results)
+    }
 
-        let result = SearchAppResult {
-            routes: results.routes,
-            trees: results.trees,
-            search_executed_time: search_start_time.to_rfc3339(),
-            search_runtime,
-            iterations: results.iterations,
-            terminated: results.terminated,
-        };
-
-        Ok((result, si))
+    /// gets the assets that will run the search for this query instance, reusing a
+    /// previously-built instance if a search instance cache is enabled.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - the user query initiating this search
+    ///
+    /// # Results
+    ///
+    /// The SearchInstance which runs this search query.
+    pub fn get_search_instance(
+        &self,
+        query: &serde_json::Value,
+    ) -> Result<SearchInstance, SearchError> {
+        match &self.search_instance_cache {
+            Some(cache) => cache.get_or_build(query, |q| self.build_search_instance(q)),
+            None => self.build_search_instance(query),
+        }
     }
 
     /// builds the assets that will run the search for this query instance.
@@ -142,7 +532,8 @@ impl SearchApp {
         &self,
         query: &serde_json::Value,
     ) -> Result<SearchInstance, SearchError> {
-        let traversal_models = self
+        let edge_model_services = self.edge_model_services()?;
+        let traversal_models = edge_model_services
             .traversal_model_services
             .iter()
             .map(|m| m.build(query))
@@ -156,13 +547,35 @@ impl SearchApp {
             .cost_model_service
             .build(query, state_model.clone())
             .map_err(|e| SearchError::BuildError(e.to_string()))?;
-        let constraint_models = self
+        search_app_ops::audit_input_features(&traversal_models, &cost_model, &state_model)?;
+        let constraint_models = edge_model_services
             .constraint_model_services
             .iter()
             .map(|m| m.build(query, state_model.clone()))
             .collect::<Result<Vec<_>, _>>()?;
+        let constraint_models = if self.prevent_u_turns(query)? {
+            constraint_models
+                .into_iter()
+                .map(|m| {
+                    let wrapped: Arc<dyn ConstraintModel> =
+                        Arc::new(UTurnConstraintModel::new(m, self.graph.clone()));
+                    wrapped
+                })
+                .collect()
+        } else {
+            constraint_models
+        };
 
         let label_model = self.label_model_service.build(query, state_model.clone())?;
+        let heuristic_weight = self.heuristic_weight(query)?;
+        let record_settled = match query.get("record_settled") {
+            None => false,
+            Some(value) => value.as_bool().ok_or_else(|| {
+                SearchError::BuildError(format!(
+                    "query 'record_settled' must be a boolean, found '{value}'"
+                ))
+            })?,
+        };
 
         let search_assets = SearchInstance {
             graph: self.graph.clone(),
@@ -174,8 +587,353 @@ impl SearchApp {
             termination_model: self.termination_model.clone(),
             label_model,
             default_edge_list: self.default_edge_list,
+            heuristic: self.heuristic,
+            heuristic_weight,
+            record_settled,
+            max_cost: self.max_cost(query)?,
+            corridor: Corridor::from_query(query, &self.graph, &self.map_model)?.map(Arc::new),
+            initial_state: None,
+        };
+        let initial_state = self.initial_state(query, &search_assets)?;
+
+        Ok(SearchInstance {
+            initial_state,
+            ..search_assets
+        })
+    }
+
+    /// the state at the origin of a query. if the state model has a departure time, it is
+    /// read from the query `departure_time` key or is the default departure time. the
+    /// initial values of the state model may be overridden with an optional `initial_state` object mapping state
+    /// variable names to values in their output units, such as `{"trip_soc": "63%"}`.
+    fn initial_state(
+        &self,
+        query: &serde_json::Value,
+        si: &SearchInstance,
+    ) -> Result<Option<Vec<StateVariable>>, SearchError> {
+        let has_departure_time = si
+            .state_model
+            .contains_key(&String::from(fieldname::DEPARTURE_TIME));
+        let key = Self::INITIAL_STATE_KEY;
+        let values = match query.get(key) {
+            None if !has_departure_time => return Ok(None),
+            None => None,
+            Some(value) => Some(value.as_object().ok_or_else(|| {
+                SearchError::BuildError(format!(
+                    "query '{key}' must be an object of state variable values, found '{value}'"
+                ))
+            })?),
+        };
+        let mut state = si.initial_state()?;
+        if has_departure_time {
+            let departure = DepartureTime::from_query(query)
+                .map_err(SearchError::BuildError)?
+                .or(self.default_departure_time)
+                .ok_or_else(|| {
+                    SearchError::BuildError(format!(
+                        "query has no '{}', which is required by the state model",
+                        DepartureTime::QUERY_KEY
+                    ))
+                })?;
+            si.state_model.set_departure_time(&mut state, &departure)?;
+        }
+        for (name, value) in values.into_iter().flatten() {
+            si.state_model
+                .set_serialized(&mut state, name, value)
+                .map_err(|e| {
+                    SearchError::BuildError(format!(
+                        "query '{key}' has an invalid value for '{name}': {e}"
+                    ))
+                })?;
+        }
+        Ok(Some(state))
+    }
+
+    /// the weighted A* heuristic weight for a query, which may override the configured
+    /// weight with an optional `heuristic_weight` key.
+    fn heuristic_weight(&self, query: &serde_json::Value) -> Result<f64, SearchError> {
+        match query.get("heuristic_weight") {
+            None => Ok(self.heuristic_weight),
+            Some(value) => {
+                let weight = value.as_f64().ok_or_else(|| {
+                    SearchError::BuildError(format!(
+                        "query 'heuristic_weight' must be a number, found '{value}'"
+                    ))
+                })?;
+                if weight != 1.0 && !self.search_algorithm.is_a_star() {
+                    return Err(SearchError::BuildError(String::from(
+                        "query has 'heuristic_weight' but the search algorithm does not use A*",
+                    )));
+                }
+                a_star_ops::validate_heuristic_weight(weight)
+            }
+        }
+    }
+
+    /// the optional `max_cost` of a query, which bounds the objective cost of the search
+    /// tree from its root, such as for reachability searches without a destination.
+    fn max_cost(&self, query: &serde_json::Value) -> Result<Option<Cost>, SearchError> {
+        match query.get("max_cost") {
+            None => Ok(None),
+            Some(value) => match value.as_f64() {
+                Some(max_cost) if max_cost >= 0.0 => Ok(Some(Cost::new(max_cost))),
+                _ => Err(SearchError::BuildError(format!(
+                    "query 'max_cost' must be a non-negative number, found '{value}'"
+                ))),
+            },
+        }
+    }
+
+    /// U-turn prevention is enabled by default when the graph was built with reverse
+    /// edge pairings. queries may toggle it via the optional `prevent_u_turns` key.
+    fn prevent_u_turns(&self, query: &serde_json::Value) -> Result<bool, SearchError> {
+        let has_reverse_edges = self.graph.reverse_edges.is_some();
+        match query.get("prevent_u_turns") {
+            None => Ok(has_reverse_edges),
+            Some(value) => match value.as_bool() {
+                Some(true) if !has_reverse_edges => Err(SearchError::BuildError(String::from(
+                    "query has 'prevent_u_turns' but the graph was not built with prevent_u_turns = true",
+                ))),
+                Some(prevent) => Ok(prevent),
+                None => Err(SearchError::BuildError(format!(
+                    "query 'prevent_u_turns' must be a boolean, found '{value}'"
+                ))),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::compass::compass_app_test_ops::{
+        build_app, speeds_test_app, speeds_test_config_from_toml, speeds_test_toml,
+    };
+
+    #[test]
+    fn test_weighted_a_star() {
+        let app = speeds_test_app("");
+        let mut queries = vec![
+            serde_json::json!({
+                "origin_vertex": 0,
+                "destination_vertex": 2,
+                "heuristic_weight": 2.0
+            }),
+            serde_json::json!({
+                "origin_vertex": 0,
+                "destination_vertex": 2
+            }),
+            serde_json::json!({
+                "origin_vertex": 0,
+                "destination_vertex": 2,
+                "heuristic_weight": 0.5
+            }),
+        ];
+        let result = app.run(&mut queries, None).expect("run failed");
+        let by_weight = |weight: Option<f64>| {
+            result
+                .iter()
+                .find(|r| r["request"]["heuristic_weight"].as_f64() == weight)
+                .expect("missing result")
+        };
+
+        let report = &by_weight(Some(2.0))["weighted_search"];
+        assert_eq!(report["heuristic_weight"], serde_json::json!(2.0));
+        let route_cost = report["route_cost"].as_f64().unwrap();
+        let gap = report["optimality_gap_bound"].as_f64().unwrap();
+        assert!((gap - route_cost / 2.0).abs() < 1e-9);
+        assert!(by_weight(None).get("weighted_search").is_none());
+        assert!(
+            by_weight(Some(0.5)).get("error").is_some(),
+            "heuristic_weight must be at least 1"
+        );
+    }
+
+    #[test]
+    fn test_reference_route() {
+        let app = speeds_test_app("");
+        // the fastest route from 0 to 2 is via vertex 1, while the reference route is edge 1
+        let mut queries = vec![
+            serde_json::json!({
+                "origin_vertex": 0,
+                "destination_vertex": 2,
+                "request_id": "free"
+            }),
+            serde_json::json!({
+                "origin_vertex": 0,
+                "destination_vertex": 2,
+                "request_id": "reference",
+                "reference_edges": [1],
+                "reference_deviation_penalty": 1.0
+            }),
+        ];
+        let responses = app.run(&mut queries, None).expect("run failed");
+        let path = |request_id: &str| {
+            let response = responses
+                .iter()
+                .find(|r| r["request"]["request_id"] == request_id)
+                .unwrap();
+            response["route"]["path"].clone()
+        };
+        assert_eq!(path("free"), serde_json::json!([0, 2]));
+        assert_eq!(path("reference"), serde_json::json!([1]));
+    }
+
+    #[test]
+    fn test_penalty_method_alternatives() {
+        let app = speeds_test_app(
+            "[algorithm]\ntype = \"penalty\"\nk = 2\npenalty_factor = 10.0\n[algorithm.underlying]\ntype = \"dijkstras\"",
+        );
+        let mut queries = vec![serde_json::json!({
+            "origin_vertex": 0,
+            "destination_vertex": 2
+        })];
+        let responses = app.run(&mut queries, None).expect("run failed");
+        let response = &responses[0];
+        let routes = response["route"].as_array().expect("expected two routes");
+        assert_eq!(routes[0]["path"], serde_json::json!([0, 2]));
+        assert_eq!(routes[1]["path"], serde_json::json!([1]));
+
+        // the alternative shares no edges with the fastest route
+        let alternatives = &response["alternatives"];
+        assert_eq!(alternatives["route_count"], 2);
+        assert_eq!(alternatives["routes"][0]["overlap_with_shortest"], 1.0);
+        assert_eq!(alternatives["routes"][0]["max_overlap"], 0.0);
+        assert_eq!(alternatives["routes"][1]["overlap_with_shortest"], 0.0);
+    }
+
+    #[test]
+    fn test_alternative_admissibility() {
+        // runs the penalty method, which finds the alternative [1] to the fastest route [0, 2]
+        let paths = |admissibility: &str| {
+            let app = speeds_test_app(&format!(
+                "[algorithm]\ntype = \"penalty\"\nk = 2\npenalty_factor = 10.0\nadmissibility = {admissibility}\n[algorithm.underlying]\ntype = \"dijkstras\""
+            ));
+            let mut queries = vec![serde_json::json!({
+                "origin_vertex": 0,
+                "destination_vertex": 2
+            })];
+            let responses = app.run(&mut queries, None).expect("run failed");
+            match &responses[0]["route"] {
+                serde_json::Value::Array(routes) => {
+                    routes.iter().map(|r| r["path"].clone()).collect()
+                }
+                route => vec![route["path"].clone()],
+            }
+        };
+
+        // the alternative shares no edges with the fastest route
+        let admissible = paths("{ max_sharing = 0.0, max_stretch = 100.0 }");
+        assert_eq!(
+            admissible,
+            vec![serde_json::json!([0, 2]), serde_json::json!([1])]
+        );
+
+        // the alternative costs more than the fastest route between its endpoints
+        let stretched = paths("{ max_stretch = 0.0 }");
+        assert_eq!(stretched, vec![serde_json::json!([0, 2])]);
+
+        // the subpath around the detour, here the whole alternative, is not a shortest path
+        let not_locally_optimal = paths("{ local_optimality = 0.5 }");
+        assert_eq!(not_locally_optimal, vec![serde_json::json!([0, 2])]);
+    }
+
+    #[test]
+    fn test_initial_state() {
+        let app = speeds_test_app("");
+        let mut queries = vec![
+            serde_json::json!({"request_id": 0, "origin_vertex": 0, "destination_vertex": 2}),
+            serde_json::json!({
+                "request_id": 1,
+                "origin_vertex": 0,
+                "destination_vertex": 2,
+                "initial_state": {"trip_time": 10.0, "trip_distance": 2.5}
+            }),
+            serde_json::json!({
+                "request_id": 2,
+                "origin_vertex": 0,
+                "destination_vertex": 2,
+                "initial_state": {"trip_soc": "63%"}
+            }),
+            serde_json::json!({
+                "request_id": 3,
+                "origin_vertex": 0,
+                "destination_vertex": 2,
+                "initial_state": {"trip_time": "ten minutes"}
+            }),
+        ];
+        let responses = app.run(&mut queries, None).expect("run failed");
+        let final_state = |r: &serde_json::Value, name: &str| {
+            r["route"]["final_state"][name]
+                .as_f64()
+                .unwrap_or_else(|| panic!("missing {name} in {r}"))
+        };
+        let response = |request_id: u64| {
+            responses
+                .iter()
+                .find(|r| r["request"]["request_id"] == request_id)
+                .unwrap_or_else(|| panic!("missing response {request_id}"))
         };
+        let base = response(0);
+        let offset = response(1);
+        assert!(
+            (final_state(offset, "trip_time") - (final_state(base, "trip_time") + 10.0)).abs()
+                < 1e-6
+        );
+        assert!(
+            (final_state(offset, "trip_distance") - (final_state(base, "trip_distance") + 2.5))
+                .abs()
+                < 1e-6
+        );
+
+        // unknown state variables and unreadable values are reported as errors
+        assert!(response(2).get("error").is_some(), "{}", response(2));
+        assert!(response(3).get("error").is_some(), "{}", response(3));
+    }
 
-        Ok(search_assets)
+    #[test]
+    fn test_tree_storage() {
+        let conf_str = speeds_test_toml().replace(
+            "route = \"edge_id\"",
+            "route = \"edge_id\"\ntree = \"json\"",
+        );
+        let run = |storage: &str| {
+            let app = build_app(&speeds_test_config_from_toml(&format!(
+                "{conf_str}\n[algorithm]\ntype = \"dijkstras\"\ntree_storage = \"{storage}\""
+            )));
+            let mut queries = vec![serde_json::json!({
+                "origin_vertex": 0,
+                "destination_vertex": 2
+            })];
+            let mut result = app.run(&mut queries, None).expect("run failed");
+            result.remove(0)
+        };
+        let nodes = run("nodes");
+        let arena = run("arena");
+        assert!(arena.get("error").is_none(), "{arena}");
+        assert_eq!(arena["route"]["path"], serde_json::json!(vec![0, 2]));
+        assert_eq!(
+            arena["route"]["cost"]["objective_cost"],
+            nodes["route"]["cost"]["objective_cost"]
+        );
+        for feature in ["edge_distance", "trip_distance", "edge_time", "trip_time"] {
+            let summary = &arena["route"]["traversal_summary"][feature]["value"];
+            assert_eq!(
+                summary,
+                &nodes["route"]["traversal_summary"][feature]["value"]
+            );
+        }
+        // tree branches hold their states, which are stored in the arena
+        let n_features = arena["route"]["state_model"].as_object().unwrap().len();
+        let tree = arena["tree"]
+            .as_array()
+            .expect("tree should be a json array");
+        assert_eq!(tree.len(), nodes["tree"].as_array().unwrap().len());
+        assert!(!tree.is_empty());
+        for branch in tree.iter() {
+            let state = branch["Branch"]["incoming_edge"]["result_state"]
+                .as_array()
+                .unwrap_or_else(|| panic!("tree branch should have a result state: {branch}"));
+            assert_eq!(state.len(), n_features, "{branch}");
+        }
     }
 }
@@ -1,7 +1,8 @@
 use itertools::Itertools;
 use routee_compass_core::config::ConfigJsonExtensions;
 use routee_compass_core::model::{
-    state::{StateModelError, StateVariableConfig},
+    cost::CostModel,
+    state::{StateModel, StateModelError, StateVariableConfig},
     traversal::TraversalModel,
 };
 use std::{collections::HashMap, sync::Arc};
@@ -53,3 +54,23 @@ pub fn collect_features(
     added_features.extend(user_features);
     Ok(added_features)
 }
+
+/// cross-checks the input features of the traversal models and the vehicle cost rates
+/// of the cost model against the state variables that produce them, failing with a
+/// dependency graph report if a model reads a state variable as a different type than
+/// it is produced. see [`StateModel::audit_input_features`].
+pub fn audit_input_features(
+    traversal_models: &[Arc<dyn TraversalModel>],
+    cost_model: &CostModel,
+    state_model: &StateModel,
+) -> Result<(), StateModelError> {
+    let consumers = traversal_models
+        .iter()
+        .map(|m| (m.name(), m.input_features()))
+        .chain(std::iter::once((
+            String::from("cost model"),
+            cost_model.input_features(),
+        )))
+        .collect::<Vec<_>>();
+    state_model.audit_input_features(&consumers)
+}
@@ -1,6 +1,6 @@
 use allocative::Allocative;
 
-use routee_compass_core::algorithm::search::{EdgeTraversal, SearchTree};
+use routee_compass_core::algorithm::search::{EdgeTraversal, SearchTree, SettledLabel};
 
 use std::time::Duration;
 
@@ -11,5 +11,9 @@ pub struct SearchAppResult {
     pub search_executed_time: String,
     pub search_runtime: Duration,
     pub iterations: u64,
+    pub max_frontier_size: usize,
+    pub peak_tree_size: usize,
     pub terminated: Option<String>,
+    /// labels in the order they were settled, if the query set `record_settled`
+    pub settled: Vec<SettledLabel>,
 }
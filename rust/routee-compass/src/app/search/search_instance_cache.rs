@@ -0,0 +1,235 @@
+use routee_compass_core::{
+    algorithm::search::{SearchError, SearchInstance},
+    model::map::MapJsonKey,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex, MutexGuard,
+    },
+};
+
+/// cached instances by key, along with the keys in insertion order for eviction
+type CacheEntries = (HashMap<String, SearchInstance>, VecDeque<String>);
+
+/// configures the optional `[system.search_instance_cache]`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SearchInstanceCacheConfig {
+    /// maximum number of distinct search instances to retain. defaults to 16.
+    pub capacity: Option<usize>,
+    /// additional query keys that do not affect how models are built, such as
+    /// custom identifiers, and should be ignored when matching queries.
+    #[serde(default)]
+    pub ignore_keys: Vec<String>,
+}
+
+/// retains [`SearchInstance`]s built for previous queries so that queries which
+/// build identical models share them. queries are matched on all of their keys
+/// except those that only locate the search (origin and destination keys) or
+/// identify the query, so any query parameter read by a model service produces
+/// a new instance. when full, the oldest instance is evicted.
+pub struct SearchInstanceCache {
+    capacity: usize,
+    ignore_keys: HashSet<String>,
+    instances: Mutex<CacheEntries>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    /// incremented when the cache is cleared, so that instances built before
+    /// the cache was cleared are not stored
+    generation: AtomicU64,
+}
+
+impl SearchInstanceCache {
+    pub const DEFAULT_CAPACITY: usize = 16;
+
+    /// query keys that never affect how models are built
+    pub const IGNORED_QUERY_KEYS: [&'static str; 9] = [
+        "origin_address",
+        "destination_address",
+        "query_weight_estimate",
+        "request_id",
+        "group_id",
+        "input_index",
+        "dedup_id",
+        "chain_id",
+        "leg_index",
+    ];
+
+    pub fn new(config: &SearchInstanceCacheConfig) -> SearchInstanceCache {
+        let ignore_keys = Self::default_ignore_keys()
+            .into_iter()
+            .chain(config.ignore_keys.iter().cloned())
+            .collect();
+        SearchInstanceCache {
+            capacity: config.capacity.unwrap_or(Self::DEFAULT_CAPACITY).max(1),
+            ignore_keys,
+            instances: Mutex::new((HashMap::new(), VecDeque::new())),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            generation: AtomicU64::new(0),
+        }
+    }
+
+    /// the location and identity keys that are always ignored when matching queries
+    pub fn default_ignore_keys() -> HashSet<String> {
+        let map_keys = [
+            MapJsonKey::OriginX,
+            MapJsonKey::OriginY,
+            MapJsonKey::DestinationX,
+            MapJsonKey::DestinationY,
+            MapJsonKey::OriginVertex,
+            MapJsonKey::DestinationVertex,
+            MapJsonKey::OriginEdgeList,
+            MapJsonKey::OriginEdge,
+            MapJsonKey::DestinationEdgeList,
+            MapJsonKey::DestinationEdge,
+        ]
+        .map(|k| k.as_str().to_string());
+        map_keys
+            .into_iter()
+            .chain(Self::IGNORED_QUERY_KEYS.iter().map(|k| k.to_string()))
+            .collect()
+    }
+
+    /// the normalized query-parameter subset used to match queries. object keys are
+    /// sorted so that key order does not affect the match.
+    pub fn cache_key(&self, query: &Value) -> String {
+        query_parameters_key(query, &self.ignore_keys)
+    }
+
+    /// returns the search instance for queries matching this query, building and
+    /// storing it if none exists. instances are built outside of the cache lock, so
+    /// concurrent misses for the same key may each build an instance.
+    pub fn get_or_build(
+        &self,
+        query: &Value,
+        build: impl FnOnce(&Value) -> Result<SearchInstance, SearchError>,
+    ) -> Result<SearchInstance, SearchError> {
+        let key = self.cache_key(query);
+        if let Some(si) = self.lock()?.0.get(&key) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(si.clone());
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let generation = self.generation.load(Ordering::Acquire);
+        let si = build(query)?;
+        let mut guard = self.lock()?;
+        let (instances, order) = &mut *guard;
+        let cleared = self.generation.load(Ordering::Acquire) != generation;
+        if !cleared && !instances.contains_key(&key) {
+            if instances.len() >= self.capacity {
+                if let Some(oldest) = order.pop_front() {
+                    instances.remove(&oldest);
+                }
+            }
+            order.push_back(key.clone());
+            instances.insert(key, si.clone());
+        }
+        Ok(si)
+    }
+
+    /// discards all cached instances
+    pub fn clear(&self) -> Result<(), SearchError> {
+        let mut guard = self.lock()?;
+        let (instances, order) = &mut *guard;
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        instances.clear();
+        order.clear();
+        Ok(())
+    }
+
+    /// number of instances in the cache
+    pub fn len(&self) -> usize {
+        self.instances
+            .lock()
+            .map(|guard| guard.0.len())
+            .unwrap_or_default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// number of queries served from the cache and number of queries that built a new instance
+    pub fn stats(&self) -> (u64, u64) {
+        (
+            self.hits.load(Ordering::Relaxed),
+            self.misses.load(Ordering::Relaxed),
+        )
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, CacheEntries>, SearchError> {
+        self.instances
+            .lock()
+            .map_err(|e| SearchError::ReadOnlyPoisonError(e.to_string()))
+    }
+}
+
+/// the normalized query with the ignored keys removed. object keys are sorted so
+/// that key order does not affect the result.
+pub fn query_parameters_key(query: &Value, ignore_keys: &HashSet<String>) -> String {
+    match query {
+        Value::Object(map) => {
+            let filtered = map
+                .iter()
+                .filter(|(k, _)| !ignore_keys.contains(*k))
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect::<Map<_, _>>();
+            normalize(&Value::Object(filtered)).to_string()
+        }
+        other => normalize(other).to_string(),
+    }
+}
+
+/// copies a JSON value with all object keys sorted
+fn normalize(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries = map.iter().collect::<Vec<_>>();
+            entries.sort_by_key(|(k, _)| *k);
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(k, v)| (k.clone(), normalize(v)))
+                    .collect(),
+            )
+        }
+        Value::Array(values) => Value::Array(values.iter().map(normalize).collect()),
+        other => other.clone(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    fn mock_cache() -> SearchInstanceCache {
+        SearchInstanceCache::new(&SearchInstanceCacheConfig {
+            capacity: None,
+            ignore_keys: vec![String::from("name")],
+        })
+    }
+
+    #[test]
+    fn test_cache_key_ignores_location_keys() {
+        let cache = mock_cache();
+        let a = json!({"origin_x": -105.1, "origin_y": 39.7, "name": "a", "model_name": "leaf"});
+        let b = json!({"model_name": "leaf", "origin_vertex": 5, "request_id": 7});
+        assert_eq!(cache.cache_key(&a), cache.cache_key(&b));
+    }
+
+    #[test]
+    fn test_cache_key_is_order_independent() {
+        let cache = mock_cache();
+        let a = json!({"weights": {"trip_time": 1, "trip_distance": 0}, "model_name": "leaf"});
+        let b = json!({"model_name": "leaf", "weights": {"trip_distance": 0, "trip_time": 1}});
+        let c = json!({"model_name": "leaf", "weights": {"trip_distance": 1, "trip_time": 0}});
+        assert_eq!(cache.cache_key(&a), cache.cache_key(&b));
+        assert_ne!(cache.cache_key(&a), cache.cache_key(&c));
+    }
+}
@@ -0,0 +1,129 @@
+use crate::app::compass::CompassAppError;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// the optional `tradeoff` key of a query, which searches the same origin and
+/// destination once for each of a sweep of cost weightings between two objectives,
+/// from all of the weight on the first objective to all of the weight on the second.
+/// the search instance is built once and only its cost model is rebuilt for each
+/// weighting. the query weights of other cost features are kept for every weighting.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TradeoffQuery {
+    /// the two weighted cost features, such as `["trip_time", "trip_energy_electric"]`
+    pub objectives: [String; 2],
+    /// number of evenly spaced weightings, including both endpoints. defaults to 5,
+    /// giving weights of 0%, 25%, 50%, 75% and 100% on the second objective.
+    pub steps: Option<usize>,
+}
+
+impl TradeoffQuery {
+    pub const QUERY_KEY: &'static str = "tradeoff";
+    pub const DEFAULT_STEPS: usize = 5;
+
+    /// reads the tradeoff of a query, if present
+    pub fn from_query(query: &Value) -> Result<Option<TradeoffQuery>, CompassAppError> {
+        let value = match query.get(Self::QUERY_KEY) {
+            None => return Ok(None),
+            Some(value) => value,
+        };
+        let tradeoff: TradeoffQuery = serde_json::from_value(value.clone()).map_err(|e| {
+            CompassAppError::CompassFailure(format!(
+                "invalid query '{}' value: {e}",
+                Self::QUERY_KEY
+            ))
+        })?;
+        if tradeoff.objectives[0] == tradeoff.objectives[1] {
+            return Err(CompassAppError::CompassFailure(format!(
+                "query '{}' objectives must be different, found '{}' twice",
+                Self::QUERY_KEY,
+                tradeoff.objectives[0]
+            )));
+        }
+        if tradeoff.steps() < 2 {
+            return Err(CompassAppError::CompassFailure(format!(
+                "query '{}' steps must be at least 2, found {}",
+                Self::QUERY_KEY,
+                tradeoff.steps()
+            )));
+        }
+        Ok(Some(tradeoff))
+    }
+
+    pub fn steps(&self) -> usize {
+        self.steps.unwrap_or(Self::DEFAULT_STEPS)
+    }
+
+    /// the weight of the second objective in each weighting, from 0 to 1. the first
+    /// objective has the remaining weight.
+    pub fn second_objective_weights(&self) -> Vec<f64> {
+        let intervals = (self.steps() - 1) as f64;
+        (0..self.steps()).map(|i| i as f64 / intervals).collect()
+    }
+
+    /// copies a query, setting the cost weights of the two objectives for one weighting
+    pub fn weighted_query(&self, query: &Value, second_objective_weight: f64) -> Value {
+        let mut weighted = query.clone();
+        if !weighted["weights"].is_object() {
+            weighted["weights"] = json!({});
+        }
+        let [first, second] = &self.objectives;
+        weighted["weights"][first] = json!(1.0 - second_objective_weight);
+        weighted["weights"][second] = json!(second_objective_weight);
+        weighted
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::compass::compass_app_test_ops::speeds_test_app;
+
+    #[test]
+    fn test_weightings() {
+        let query = json!({
+            "origin_vertex": 0,
+            "weights": {"trip_distance": 0.5},
+            "tradeoff": {"objectives": ["trip_time", "trip_energy"]}
+        });
+        let tradeoff = TradeoffQuery::from_query(&query).unwrap().unwrap();
+        assert_eq!(
+            tradeoff.second_objective_weights(),
+            vec![0.0, 0.25, 0.5, 0.75, 1.0]
+        );
+        let weighted = tradeoff.weighted_query(&query, 0.25);
+        assert_eq!(
+            weighted["weights"],
+            json!({"trip_distance": 0.5, "trip_time": 0.75, "trip_energy": 0.25})
+        );
+
+        assert!(TradeoffQuery::from_query(&json!({})).unwrap().is_none());
+        let same = json!({"tradeoff": {"objectives": ["trip_time", "trip_time"]}});
+        assert!(TradeoffQuery::from_query(&same).is_err());
+        let one_step =
+            json!({"tradeoff": {"objectives": ["trip_time", "trip_energy"], "steps": 1}});
+        assert!(TradeoffQuery::from_query(&one_step).is_err());
+    }
+
+    #[test]
+    fn test_tradeoff_query() {
+        let app = speeds_test_app("");
+        let mut queries = vec![serde_json::json!({
+            "origin_vertex": 0,
+            "destination_vertex": 2,
+            "tradeoff": { "objectives": ["trip_time", "trip_distance"], "steps": 3 }
+        })];
+        let responses = app.run(&mut queries, None).expect("run failed");
+        let tradeoff = &responses[0]["tradeoff"];
+        let points = tradeoff["points"].as_array().expect("missing points");
+        assert_eq!(points.len(), 3, "{tradeoff}");
+        let value = |p: &serde_json::Value, name: &str| p[name]["value"].as_f64().unwrap();
+        // all time uses the fast route through vertex 1, all distance the direct edge 1
+        let (fastest, shortest) = (&points[0], &points[2]);
+        assert_eq!(fastest["weights"]["trip_distance"], 0.0);
+        assert_eq!(shortest["weights"]["trip_distance"], 1.0);
+        assert!(value(fastest, "trip_time") < value(shortest, "trip_time"));
+        assert!(value(fastest, "trip_distance") > value(shortest, "trip_distance"));
+        assert_eq!(points[1]["weights"]["trip_time"], 0.5);
+    }
+}
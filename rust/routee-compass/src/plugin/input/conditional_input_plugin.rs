@@ -0,0 +1,137 @@
+use super::{InputJsonExtensions, InputPlugin, InputPluginError};
+use crate::app::search::SearchApp;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::sync::Arc;
+
+/// predicate on a query deciding whether an input plugin runs, configured as the `when`
+/// key of an input plugin. a query matches if it meets every condition that is set.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct InputPluginCondition {
+    /// keys that must all be present in the query
+    pub present: Option<Vec<String>>,
+    /// keys that must all be absent from the query
+    pub absent: Option<Vec<String>>,
+    /// values that the query must have at each key
+    pub equals: Option<Map<String, Value>>,
+}
+
+impl InputPluginCondition {
+    pub fn matches(&self, query: &Value) -> bool {
+        let present = self
+            .present
+            .iter()
+            .flatten()
+            .all(|key| query.get(key).is_some());
+        let absent = self
+            .absent
+            .iter()
+            .flatten()
+            .all(|key| query.get(key).is_none());
+        let equals = self
+            .equals
+            .iter()
+            .flatten()
+            .all(|(key, value)| query.get(key) == Some(value));
+        present && absent && equals
+    }
+}
+
+/// runs an input plugin only on the queries matching a condition, and optionally marks
+/// the queries it processes as complete, so that they skip the remaining input plugins.
+/// built from the `when` and `complete` keys of an input plugin configuration.
+pub struct ConditionalInputPlugin {
+    pub plugin: Arc<dyn InputPlugin>,
+    pub when: Option<InputPluginCondition>,
+    pub complete: bool,
+}
+
+impl ConditionalInputPlugin {
+    /// configuration key holding the [`InputPluginCondition`]
+    pub const WHEN_KEY: &'static str = "when";
+    /// configuration key that, if true, marks the processed queries as complete
+    pub const COMPLETE_KEY: &'static str = "complete";
+}
+
+impl InputPlugin for ConditionalInputPlugin {
+    fn name(&self) -> &str {
+        self.plugin.name()
+    }
+
+    fn process(
+        &self,
+        input: &mut Value,
+        search_app: Arc<SearchApp>,
+    ) -> Result<(), InputPluginError> {
+        if let Some(when) = &self.when {
+            if !when.matches(input) {
+                return Ok(());
+            }
+        }
+        self.plugin.process(input, search_app)?;
+        if self.complete {
+            // plugins may replace a query with an array of queries
+            match input {
+                Value::Array(queries) => queries
+                    .iter_mut()
+                    .try_for_each(|q| q.mark_input_plugins_complete())?,
+                query => query.mark_input_plugins_complete()?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::compass::compass_app_test_ops::{
+        build_app, speeds_test_config_from_toml, speeds_test_toml,
+    };
+    use serde_json::json;
+
+    #[test]
+    fn test_condition_matches() {
+        let condition: InputPluginCondition = serde_json::from_value(json!({
+            "present": ["origin_vertex"],
+            "absent": ["origin_x", "origin_y"],
+            "equals": {"model_name": "2017_CHEVROLET_Bolt"}
+        }))
+        .unwrap();
+        let query = json!({"origin_vertex": 0, "model_name": "2017_CHEVROLET_Bolt"});
+        assert!(condition.matches(&query));
+        assert!(!condition.matches(
+            &json!({"origin_vertex": 0, "origin_x": 1.0, "model_name": "2017_CHEVROLET_Bolt"})
+        ));
+        assert!(!condition.matches(&json!({"origin_vertex": 0, "model_name": "other"})));
+        assert!(!condition.matches(&json!({"model_name": "2017_CHEVROLET_Bolt"})));
+        assert!(InputPluginCondition::default().matches(&json!({})));
+    }
+
+    #[test]
+    fn test_conditional_input_plugins() {
+        // the first plugin tags and completes high priority queries, which skip the second
+        let input_plugins = r#"input_plugins = [
+            { type = "inject", format = "key_value", key = "tag", value = "high", write_mode = "overwrite", when = { equals = { priority = "high" } }, complete = true },
+            { type = "inject", format = "key_value", key = "tag", value = "default", write_mode = "overwrite" },
+        ]"#;
+        let app = build_app(&speeds_test_config_from_toml(
+            &speeds_test_toml().replace("input_plugins = []", input_plugins),
+        ));
+        let mut queries = vec![
+            serde_json::json!({"origin_vertex": 0, "destination_vertex": 2, "priority": "high"}),
+            serde_json::json!({"origin_vertex": 0, "destination_vertex": 1}),
+        ];
+        let responses = app.run(&mut queries, None).expect("run failed");
+        let find = |destination: i64| {
+            &responses
+                .iter()
+                .find(|r| r["request"]["destination_vertex"] == destination)
+                .unwrap()["request"]
+        };
+        assert_eq!(find(2)["tag"], "high");
+        assert_eq!(find(1)["tag"], "default");
+        assert!(find(2).get("input_plugins_complete").is_none());
+    }
+}
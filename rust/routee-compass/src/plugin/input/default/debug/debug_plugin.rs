@@ -9,6 +9,10 @@ use std::sync::Arc;
 pub struct DebugInputPlugin {}
 
 impl InputPlugin for DebugInputPlugin {
+    fn name(&self) -> &str {
+        "debug"
+    }
+
     fn process(
         &self,
         input: &mut serde_json::Value,
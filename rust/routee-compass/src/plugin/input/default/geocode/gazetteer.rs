@@ -0,0 +1,90 @@
+use super::geocoder::Geocoder;
+use crate::plugin::input::InputPluginError;
+use routee_compass_core::util::fs::fs_utils;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// a file-backed geocoder that looks up addresses in a CSV file with columns
+/// `address`, `x` and `y`. addresses are matched ignoring case and repeated whitespace.
+pub struct Gazetteer {
+    entries: HashMap<String, (f64, f64)>,
+}
+
+#[derive(Deserialize)]
+struct GazetteerRow {
+    address: String,
+    x: f64,
+    y: f64,
+}
+
+impl Gazetteer {
+    pub fn new(entries: HashMap<String, (f64, f64)>) -> Gazetteer {
+        let entries = entries
+            .into_iter()
+            .map(|(address, coord)| (normalize(&address), coord))
+            .collect();
+        Gazetteer { entries }
+    }
+
+    pub fn from_file(path: &Path) -> Result<Gazetteer, InputPluginError> {
+        let file = fs_utils::open_reader(path).map_err(|e| {
+            InputPluginError::BuildFailed(format!(
+                "failure opening gazetteer file {}: {e}",
+                path.to_string_lossy()
+            ))
+        })?;
+        let entries = csv::Reader::from_reader(file)
+            .deserialize::<GazetteerRow>()
+            .enumerate()
+            .map(|(idx, row)| {
+                let row = row.map_err(|e| {
+                    InputPluginError::BuildFailed(format!(
+                        "failure reading row {idx} of gazetteer file {}: {e}",
+                        path.to_string_lossy()
+                    ))
+                })?;
+                Ok((row.address, (row.x, row.y)))
+            })
+            .collect::<Result<HashMap<_, _>, InputPluginError>>()?;
+        Ok(Gazetteer::new(entries))
+    }
+}
+
+impl Geocoder for Gazetteer {
+    fn geocode(&self, address: &str) -> Result<Option<(f64, f64)>, InputPluginError> {
+        Ok(self.entries.get(&normalize(address)).cloned())
+    }
+}
+
+fn normalize(address: &str) -> String {
+    address
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_gazetteer_from_file() {
+        let mut file = tempfile::NamedTempFile::new().expect("failed to create temp file");
+        writeln!(file, "address,x,y").unwrap();
+        writeln!(
+            file,
+            "\"15013 Denver West Pkwy, Golden, CO\",-105.1686,39.7405"
+        )
+        .unwrap();
+        let gazetteer = Gazetteer::from_file(file.path()).expect("failed to read gazetteer");
+        let result = gazetteer
+            .geocode("15013  denver west pkwy, GOLDEN, co")
+            .expect("geocode failed");
+        assert_eq!(result, Some((-105.1686, 39.7405)));
+        let missing = gazetteer.geocode("unknown").expect("geocode failed");
+        assert_eq!(missing, None);
+    }
+}
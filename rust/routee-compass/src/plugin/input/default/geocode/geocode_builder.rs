@@ -0,0 +1,31 @@
+use super::geocode_config::GeocodeInputPluginConfig;
+use crate::plugin::input::{InputPlugin, InputPluginBuilder};
+use routee_compass_core::config::CompassConfigurationError;
+use std::sync::Arc;
+
+/// Builds a plugin that resolves `origin_address` and `destination_address` query
+/// fields into coordinates.
+///
+/// # Example Configuration
+///
+/// ```toml
+/// [[plugin.input_plugins]]
+/// type = "geocode"
+/// geocoder = { type = "gazetteer", input_file = "addresses.csv" }
+/// ```
+pub struct GeocodeInputPluginBuilder {}
+
+impl InputPluginBuilder for GeocodeInputPluginBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn InputPlugin>, CompassConfigurationError> {
+        let config: GeocodeInputPluginConfig = serde_json::from_value(parameters.clone())?;
+        let plugin = config.build().map_err(|e| {
+            CompassConfigurationError::UserConfigurationError(format!(
+                "failed to build geocode plugin from configuration: {e}"
+            ))
+        })?;
+        Ok(Arc::new(plugin))
+    }
+}
@@ -0,0 +1,58 @@
+use super::{gazetteer::Gazetteer, geocode_plugin::GeocodeInputPlugin, geocoder::Geocoder};
+use crate::plugin::input::InputPluginError;
+use serde::{Deserialize, Serialize};
+use std::{path::Path, sync::Arc};
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct GeocodeInputPluginConfig {
+    /// the geocoder used to resolve addresses
+    pub geocoder: GeocoderConfig,
+    /// if true, replaces coordinates already present in the query. defaults to false.
+    pub overwrite: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum GeocoderConfig {
+    /// looks up addresses in a CSV file with columns `address`, `x` and `y`
+    Gazetteer { input_file: String },
+    /// queries a Nominatim-compatible search endpoint over plain HTTP.
+    /// requires the `http_geocoder` feature.
+    Http {
+        url: String,
+        timeout_seconds: Option<u64>,
+    },
+}
+
+impl GeocodeInputPluginConfig {
+    pub fn build(&self) -> Result<GeocodeInputPlugin, InputPluginError> {
+        let geocoder = self.geocoder.build()?;
+        Ok(GeocodeInputPlugin::new(
+            geocoder,
+            self.overwrite.unwrap_or_default(),
+        ))
+    }
+}
+
+impl GeocoderConfig {
+    pub fn build(&self) -> Result<Arc<dyn Geocoder>, InputPluginError> {
+        match self {
+            GeocoderConfig::Gazetteer { input_file } => {
+                let gazetteer = Gazetteer::from_file(Path::new(input_file))?;
+                Ok(Arc::new(gazetteer))
+            }
+            #[cfg(feature = "http_geocoder")]
+            GeocoderConfig::Http {
+                url,
+                timeout_seconds,
+            } => {
+                let geocoder = super::http_geocoder::HttpGeocoder::new(url, *timeout_seconds)?;
+                Ok(Arc::new(geocoder))
+            }
+            #[cfg(not(feature = "http_geocoder"))]
+            GeocoderConfig::Http { .. } => Err(InputPluginError::BuildFailed(String::from(
+                "http geocoder requires routee-compass to be built with the 'http_geocoder' feature",
+            ))),
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use super::geocoder::Geocoder;
+use crate::{
+    app::search::SearchApp,
+    plugin::input::{input_plugin::InputPlugin, InputField, InputPluginError},
+};
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+/// resolves the `origin_address` and `destination_address` fields of a query into
+/// coordinates using a [`Geocoder`], writing them to the `origin_x`/`origin_y` and
+/// `destination_x`/`destination_y` fields.
+pub struct GeocodeInputPlugin {
+    geocoder: Arc<dyn Geocoder>,
+    overwrite: bool,
+}
+
+impl GeocodeInputPlugin {
+    pub fn new(geocoder: Arc<dyn Geocoder>, overwrite: bool) -> GeocodeInputPlugin {
+        GeocodeInputPlugin {
+            geocoder,
+            overwrite,
+        }
+    }
+}
+
+impl InputPlugin for GeocodeInputPlugin {
+    fn name(&self) -> &str {
+        "geocode"
+    }
+
+    fn process(
+        &self,
+        input: &mut serde_json::Value,
+        _search_app: Arc<SearchApp>,
+    ) -> Result<(), InputPluginError> {
+        process_geocode(self, input)
+    }
+}
+
+pub fn process_geocode(
+    plugin: &GeocodeInputPlugin,
+    input: &mut serde_json::Value,
+) -> Result<(), InputPluginError> {
+    let fields = [
+        (
+            InputField::OriginAddress,
+            InputField::OriginX,
+            InputField::OriginY,
+        ),
+        (
+            InputField::DestinationAddress,
+            InputField::DestinationX,
+            InputField::DestinationY,
+        ),
+    ];
+    for (address_field, x_field, y_field) in fields {
+        let query = input.as_object_mut().ok_or_else(|| {
+            InputPluginError::UnexpectedQueryStructure(String::from(
+                "InputQuery is not a JSON object",
+            ))
+        })?;
+        let address = match query.get(address_field.to_str()) {
+            None => continue,
+            Some(Value::String(address)) => address.clone(),
+            Some(_) => {
+                return Err(InputPluginError::QueryFieldHasInvalidType(
+                    address_field,
+                    String::from("string"),
+                ))
+            }
+        };
+        let has_coordinate =
+            query.contains_key(x_field.to_str()) || query.contains_key(y_field.to_str());
+        if has_coordinate && !plugin.overwrite {
+            continue;
+        }
+        let (x, y) = plugin.geocoder.geocode(&address)?.ok_or_else(|| {
+            InputPluginError::InputPluginFailed(format!(
+                "unable to geocode {address_field} '{address}'"
+            ))
+        })?;
+        query.insert(x_field.to_string(), json![x]);
+        query.insert(y_field.to_string(), json![y]);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::plugin::input::default::geocode::Gazetteer;
+    use std::collections::HashMap;
+
+    fn mock_plugin(overwrite: bool) -> GeocodeInputPlugin {
+        let entries = HashMap::from([
+            (String::from("home"), (-105.0, 39.7)),
+            (String::from("work"), (-105.2, 39.8)),
+        ]);
+        GeocodeInputPlugin::new(Arc::new(Gazetteer::new(entries)), overwrite)
+    }
+
+    #[test]
+    fn test_geocode_origin_and_destination() {
+        let mut query = json!({"origin_address": "Home", "destination_address": "work"});
+        process_geocode(&mock_plugin(false), &mut query).expect("geocode failed");
+        assert_eq!(query["origin_x"], json![-105.0]);
+        assert_eq!(query["origin_y"], json![39.7]);
+        assert_eq!(query["destination_x"], json![-105.2]);
+        assert_eq!(query["destination_y"], json![39.8]);
+    }
+
+    #[test]
+    fn test_geocode_does_not_overwrite() {
+        let mut query = json!({"origin_address": "home", "origin_x": 1.0, "origin_y": 2.0});
+        process_geocode(&mock_plugin(false), &mut query).expect("geocode failed");
+        assert_eq!(query["origin_x"], json![1.0]);
+        process_geocode(&mock_plugin(true), &mut query).expect("geocode failed");
+        assert_eq!(query["origin_x"], json![-105.0]);
+    }
+
+    #[test]
+    fn test_geocode_not_found() {
+        let mut query = json!({"origin_address": "nowhere"});
+        assert!(process_geocode(&mock_plugin(false), &mut query).is_err());
+    }
+}
@@ -0,0 +1,8 @@
+use crate::plugin::input::InputPluginError;
+
+/// resolves an address string into a coordinate. implementations are shared across
+/// the threads of a batch run.
+pub trait Geocoder: Send + Sync {
+    /// finds the (x, y) WGS84 coordinate for an address, or None if the address is not found.
+    fn geocode(&self, address: &str) -> Result<Option<(f64, f64)>, InputPluginError>;
+}
@@ -0,0 +1,137 @@
+use super::geocoder::Geocoder;
+use crate::app::http::{HttpClient, HttpUrl};
+use crate::plugin::input::InputPluginError;
+use serde_json::Value;
+use std::time::Duration;
+
+/// largest geocoder response read, in bytes. a response holds a single result, so
+/// anything larger is not a geocoder response.
+pub const MAX_RESPONSE_BYTES: u64 = 1024 * 1024;
+
+/// a geocoder that queries a Nominatim-compatible search endpoint, such as a
+/// self-hosted Nominatim or Pelias instance, with the shared [`HttpClient`]. requests
+/// are sent over plain HTTP, so HTTPS endpoints are not supported.
+pub struct HttpGeocoder {
+    url: HttpUrl,
+    client: HttpClient,
+}
+
+impl HttpGeocoder {
+    pub fn new(url: &str, timeout_seconds: Option<u64>) -> Result<HttpGeocoder, InputPluginError> {
+        let url = HttpUrl::parse(url).map_err(|e| {
+            InputPluginError::BuildFailed(format!("invalid http geocoder url: {e}"))
+        })?;
+        let client = HttpClient {
+            max_response_bytes: Some(MAX_RESPONSE_BYTES),
+            ..HttpClient::new(Duration::from_secs(timeout_seconds.unwrap_or(10)))
+        };
+        Ok(HttpGeocoder { url, client })
+    }
+
+    fn request(&self, address: &str) -> Result<Vec<u8>, InputPluginError> {
+        let separator = if self.url.path.contains('?') {
+            '&'
+        } else {
+            '?'
+        };
+        let url = format!(
+            "{}{separator}q={}&format=json&limit=1",
+            self.url.url(),
+            url_encode(address)
+        );
+        let accept = [("Accept", String::from("application/json"))];
+        let mut body = vec![];
+        let response = self.client.get(&url, &accept, &mut body).map_err(|e| {
+            InputPluginError::InputPluginFailed(format!("geocoder request failed: {e}"))
+        })?;
+        if response.status != 200 {
+            return Err(InputPluginError::InputPluginFailed(format!(
+                "geocoder request for '{address}' failed with status {}",
+                response.status
+            )));
+        }
+        Ok(body)
+    }
+}
+
+impl Geocoder for HttpGeocoder {
+    fn geocode(&self, address: &str) -> Result<Option<(f64, f64)>, InputPluginError> {
+        let body = self.request(address)?;
+        let results: Value = serde_json::from_slice(&body)?;
+        let first = match results.as_array().and_then(|r| r.first()) {
+            Some(first) => first,
+            None => return Ok(None),
+        };
+        let x = parse_coordinate(first.get("lon"));
+        let y = parse_coordinate(first.get("lat"));
+        match (x, y) {
+            (Some(x), Some(y)) => Ok(Some((x, y))),
+            _ => Err(InputPluginError::InputPluginFailed(format!(
+                "geocoder result for '{address}' is missing 'lat' or 'lon': {first}"
+            ))),
+        }
+    }
+}
+
+/// Nominatim returns coordinates as strings, other implementations as numbers
+fn parse_coordinate(value: Option<&Value>) -> Option<f64> {
+    match value? {
+        Value::String(s) => s.parse::<f64>().ok(),
+        other => other.as_f64(),
+    }
+}
+
+fn url_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            b' ' => String::from("+"),
+            _ => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::http::test_server::serve;
+
+    #[test]
+    fn test_parse_url() {
+        let geocoder =
+            HttpGeocoder::new("http://localhost:8080/search", None).expect("invalid url");
+        assert_eq!(geocoder.url.host, "localhost");
+        assert_eq!(geocoder.url.port, 8080);
+        assert_eq!(geocoder.url.path, "/search");
+        assert!(HttpGeocoder::new("https://example.com", None).is_err());
+    }
+
+    #[test]
+    fn test_geocode() {
+        let body = r#"[{"lat": "39.75", "lon": "-105.22"}]"#;
+        let (port, server) = serve(vec![format!(
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        )]);
+        let geocoder = HttpGeocoder::new(&format!("http://127.0.0.1:{port}/search"), None)
+            .expect("invalid url");
+        let coord = geocoder.geocode("1 Main St").expect("geocode failed");
+        assert_eq!(coord, Some((-105.22, 39.75)));
+        let requests = server.join().unwrap();
+        assert!(
+            requests[0].starts_with(&format!(
+                "GET /search?q=1+Main+St&format=json&limit=1 HTTP/1.1\r\nHost: 127.0.0.1:{port}\r\n"
+            )),
+            "{}",
+            requests[0]
+        );
+    }
+
+    #[test]
+    fn test_url_encode() {
+        assert_eq!(url_encode("1 Main St, Golden"), "1+Main+St%2C+Golden");
+    }
+}
@@ -0,0 +1,15 @@
+mod gazetteer;
+mod geocode_builder;
+mod geocode_config;
+mod geocode_plugin;
+mod geocoder;
+#[cfg(feature = "http_geocoder")]
+mod http_geocoder;
+
+pub use gazetteer::Gazetteer;
+pub use geocode_builder::GeocodeInputPluginBuilder;
+pub use geocode_config::{GeocodeInputPluginConfig, GeocoderConfig};
+pub use geocode_plugin::GeocodeInputPlugin;
+pub use geocoder::Geocoder;
+#[cfg(feature = "http_geocoder")]
+pub use http_geocoder::HttpGeocoder;
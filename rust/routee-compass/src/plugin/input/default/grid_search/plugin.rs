@@ -11,6 +11,10 @@ use std::sync::Arc;
 pub struct GridSearchPlugin {}
 
 impl InputPlugin for GridSearchPlugin {
+    fn name(&self) -> &str {
+        "grid_search"
+    }
+
     fn process(
         &self,
         input: &mut serde_json::Value,
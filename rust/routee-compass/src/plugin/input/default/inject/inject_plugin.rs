@@ -23,6 +23,10 @@ pub enum InjectInputPlugin {
 }
 
 impl InputPlugin for InjectInputPlugin {
+    fn name(&self) -> &str {
+        "inject"
+    }
+
     fn process(
         &self,
         input: &mut serde_json::Value,
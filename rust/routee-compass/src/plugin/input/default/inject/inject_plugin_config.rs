@@ -3,7 +3,10 @@
 use super::{inject_plugin::InjectInputPlugin, CoordinateOrientation, WriteMode};
 use crate::plugin::input::InputPluginError;
 use geojson::{Feature, FeatureCollection, GeoJson};
-use routee_compass_core::util::geo::{geo_io_utils, PolygonalRTree};
+use routee_compass_core::util::{
+    fs::fs_utils,
+    geo::{geo_io_utils, PolygonalRTree},
+};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -44,7 +47,7 @@ impl InjectPluginConfig {
             }),
             InjectPluginConfig::SpatialKeyValue(spatial) => {
                 let contents =
-                    std::fs::read_to_string(&spatial.spatial_input_file).map_err(|e| {
+                    fs_utils::read_to_string(&spatial.spatial_input_file).map_err(|e| {
                         InputPluginError::BuildFailed(format!(
                             "file read failed for file '{}': {}",
                             spatial.spatial_input_file, e
@@ -12,6 +12,6 @@ impl InputPluginBuilder for LoadBalancerBuilder {
     ) -> Result<Arc<dyn InputPlugin>, CompassConfigurationError> {
         let heuristic =
             params.get_config_serde::<WeightHeuristic>(&"weight_heuristic", &"load_balancer")?;
-        Ok(Arc::new(LoadBalancerPlugin { heuristic }))
+        Ok(Arc::new(LoadBalancerPlugin::new(heuristic)))
     }
 }
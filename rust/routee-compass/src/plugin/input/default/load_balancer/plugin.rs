@@ -8,17 +8,51 @@ use crate::plugin::input::InputPluginError;
 
 pub struct LoadBalancerPlugin {
     pub heuristic: WeightHeuristic,
+    /// if true, queries that already have a query weight estimate keep it, and queries
+    /// whose weight cannot be estimated are left without one instead of failing.
+    pub lenient: bool,
+}
+
+impl LoadBalancerPlugin {
+    pub fn new(heuristic: WeightHeuristic) -> LoadBalancerPlugin {
+        LoadBalancerPlugin {
+            heuristic,
+            lenient: false,
+        }
+    }
+
+    /// the load balancer added to a CompassApp that has none configured. estimates
+    /// weights by haversine distance for queries with origin and destination
+    /// coordinates, without overwriting user-provided estimates.
+    pub fn default_estimator() -> LoadBalancerPlugin {
+        LoadBalancerPlugin {
+            heuristic: WeightHeuristic::Haversine { multiplier: None },
+            lenient: true,
+        }
+    }
 }
 
 impl InputPlugin for LoadBalancerPlugin {
+    fn name(&self) -> &str {
+        "load_balancer"
+    }
+
     fn process(
         &self,
         query: &mut serde_json::Value,
         _search_app: Arc<SearchApp>,
     ) -> Result<(), InputPluginError> {
-        let w = self.heuristic.estimate_weight(query)?;
-        let _updated = query.clone();
-        query.add_query_weight_estimate(w)?;
-        Ok(())
+        if !self.lenient {
+            let w = self.heuristic.estimate_weight(query)?;
+            query.add_query_weight_estimate(w)?;
+            return Ok(());
+        }
+        if query.get_query_weight_estimate().ok().flatten().is_some() {
+            return Ok(());
+        }
+        match self.heuristic.estimate_weight(query) {
+            Ok(w) => query.add_query_weight_estimate(w),
+            Err(_) => Ok(()),
+        }
     }
 }
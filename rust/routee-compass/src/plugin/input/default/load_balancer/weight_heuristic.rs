@@ -7,8 +7,13 @@ use serde::{Deserialize, Serialize};
 #[serde(rename_all = "snake_case", tag = "type")]
 pub enum WeightHeuristic {
     /// computes a weight directly as the haversine distance estimation between
-    /// trip origin and destination, in kilometers.
-    Haversine,
+    /// trip origin and destination, in kilometers. if a multiplier is provided, the
+    /// distance is scaled by the multiplier found for each query, such as a value
+    /// mapped from a travel mode field.
+    Haversine {
+        #[serde(default)]
+        multiplier: Option<CustomWeightType>,
+    },
     /// user provides a field of some custom weight type that is used directly
     /// for weight estimates.
     Custom {
@@ -19,10 +24,10 @@ pub enum WeightHeuristic {
 impl WeightHeuristic {
     pub fn estimate_weight(&self, query: &serde_json::Value) -> Result<f64, InputPluginError> {
         match self {
-            WeightHeuristic::Haversine => {
+            WeightHeuristic::Haversine { multiplier } => {
                 let o = query.get_origin_coordinate()?;
                 let d_option = query.get_destination_coordinate()?;
-                match d_option {
+                let distance = match d_option {
                     None => Err(InputPluginError::InputPluginFailed(String::from(
                         "cannot estimate search size without destination coordinate",
                     ))),
@@ -33,9 +38,48 @@ impl WeightHeuristic {
                                 "failed calculating load balancing weight value due to {s}"
                             ))
                         }),
+                }?;
+                match multiplier {
+                    Some(m) => Ok(distance * m.get_weight(query)?),
+                    None => Ok(distance),
                 }
             }
             WeightHeuristic::Custom { custom_weight_type } => custom_weight_type.get_weight(query),
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_haversine_with_mode_multiplier() {
+        let heuristic: WeightHeuristic = serde_json::from_value(json!({
+            "type": "haversine",
+            "multiplier": {
+                "type": "categorical",
+                "column_name": "mode",
+                "mapping": { "walk": 1.0, "drive": 10.0 },
+                "default": 1.0
+            }
+        }))
+        .expect("failed to deserialize heuristic");
+        let query = |mode: &str| {
+            json!({
+                "origin_x": -105.0, "origin_y": 39.7,
+                "destination_x": -104.9, "destination_y": 39.7,
+                "mode": mode
+            })
+        };
+        let walk = heuristic.estimate_weight(&query("walk")).unwrap();
+        let drive = heuristic.estimate_weight(&query("drive")).unwrap();
+        assert!(walk > 8.0 && walk < 9.0, "walk weight was {walk}");
+        assert!((drive - walk * 10.0).abs() < 1e-9);
+
+        let plain: WeightHeuristic = serde_json::from_value(json!({ "type": "haversine" }))
+            .expect("failed to deserialize heuristic");
+        assert_eq!(plain.estimate_weight(&query("drive")).unwrap(), walk);
+    }
+}
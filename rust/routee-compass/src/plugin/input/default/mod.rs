@@ -1,4 +1,7 @@
 pub mod debug;
+pub mod geocode;
 pub mod grid_search;
 pub mod inject;
 pub mod load_balancer;
+pub mod sweep;
+pub mod validate;
@@ -0,0 +1,15 @@
+use super::plugin::SweepPlugin;
+use crate::plugin::input::{InputPlugin, InputPluginBuilder};
+use routee_compass_core::config::CompassConfigurationError;
+use std::sync::Arc;
+
+pub struct SweepBuilder {}
+
+impl InputPluginBuilder for SweepBuilder {
+    fn build(
+        &self,
+        _parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn InputPlugin>, CompassConfigurationError> {
+        Ok(Arc::new(SweepPlugin {}))
+    }
+}
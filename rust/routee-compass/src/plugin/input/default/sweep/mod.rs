@@ -0,0 +1,5 @@
+mod builder;
+mod plugin;
+
+pub use builder::SweepBuilder;
+pub use plugin::SweepPlugin;
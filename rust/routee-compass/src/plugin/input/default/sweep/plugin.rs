@@ -0,0 +1,133 @@
+use crate::app::search::SearchApp;
+use crate::plugin::input::input_field::InputField;
+use crate::plugin::input::input_plugin::InputPlugin;
+use crate::plugin::input::InputPluginError;
+use routee_compass_core::util::multiset::MultiSet;
+use serde_json::{json, Map, Value};
+use std::sync::Arc;
+
+/// expands a query with a `sweep` object of arrays into one query per combination of the
+/// array values, for parameter sweeps over scenarios. each sweep key is set to one of its
+/// values, replacing any value already in the query, and each expanded query is tagged
+/// with the `sweep_parameters` it was given and its `sweep_index` in the expansion.
+///
+/// unlike the grid search plugin, object values are not merged into the query, so a
+/// sweep over whole objects, such as sets of cost weights, replaces the object at its key.
+pub struct SweepPlugin {}
+
+impl InputPlugin for SweepPlugin {
+    fn name(&self) -> &str {
+        "sweep"
+    }
+
+    fn process(
+        &self,
+        input: &mut Value,
+        _search_app: Arc<SearchApp>,
+    ) -> Result<(), InputPluginError> {
+        if let Some(expansion) = expand_sweep(input)? {
+            *input = Value::Array(expansion);
+        }
+        Ok(())
+    }
+}
+
+fn expand_sweep(input: &Value) -> Result<Option<Vec<Value>>, InputPluginError> {
+    let sweep = match input.get(InputField::Sweep.to_str()) {
+        Some(sweep) => sweep.as_object().ok_or_else(|| {
+            InputPluginError::QueryFieldHasInvalidType(InputField::Sweep, String::from("object"))
+        })?,
+        None => return Ok(None),
+    };
+    let mut keys: Vec<&String> = vec![];
+    let mut values: Vec<&Vec<Value>> = vec![];
+    for (key, value) in sweep.iter() {
+        match value.as_array() {
+            Some(array) if !array.is_empty() => {
+                keys.push(key);
+                values.push(array);
+            }
+            _ => {
+                return Err(InputPluginError::InputPluginFailed(format!(
+                    "sweep parameter '{key}' must be a non-empty array, found {value}"
+                )))
+            }
+        }
+    }
+    let mut initial = input
+        .as_object()
+        .ok_or_else(|| InputPluginError::UnexpectedQueryStructure(format!("{input:?}")))?
+        .clone();
+    initial.remove(InputField::Sweep.to_str());
+
+    let indices = values
+        .iter()
+        .map(|v| (0..v.len()).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let expansion = MultiSet::from(&indices)
+        .enumerate()
+        .map(|(sweep_index, combination)| {
+            let mut query = initial.clone();
+            let mut parameters = Map::new();
+            for (set_idx, val_idx) in combination.iter().enumerate() {
+                let value = values[set_idx][*val_idx].clone();
+                query.insert(keys[set_idx].clone(), value.clone());
+                parameters.insert(keys[set_idx].clone(), value);
+            }
+            query.insert(
+                InputField::SweepParameters.to_string(),
+                Value::Object(parameters),
+            );
+            query.insert(InputField::SweepIndex.to_string(), json!(sweep_index));
+            Value::Object(query)
+        })
+        .collect();
+    Ok(Some(expansion))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_sweep_cross_product() {
+        let input = json!({
+            "origin_vertex": 0,
+            "weights": {"trip_time": 1},
+            "sweep": {
+                "model_name": ["a", "b", "c"],
+                "weights": [{"trip_time": 1}, {"trip_distance": 1}]
+            }
+        });
+        let result = expand_sweep(&input).unwrap().unwrap();
+        assert_eq!(result.len(), 6);
+        assert_eq!(
+            result[4],
+            json!({
+                "origin_vertex": 0,
+                "weights": {"trip_distance": 1},
+                "model_name": "b",
+                "sweep_parameters": {"model_name": "b", "weights": {"trip_distance": 1}},
+                "sweep_index": 4
+            })
+        );
+        let combinations = result
+            .iter()
+            .map(|q| (q["model_name"].clone(), q["weights"].clone()))
+            .collect::<std::collections::HashSet<_>>();
+        assert_eq!(combinations.len(), 6);
+    }
+
+    #[test]
+    fn test_no_sweep() {
+        assert!(expand_sweep(&json!({"origin_vertex": 0}))
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_empty_sweep_parameter() {
+        let input = json!({"sweep": {"model_name": []}});
+        assert!(expand_sweep(&input).is_err());
+    }
+}
@@ -0,0 +1,7 @@
+mod query_schema;
+mod validate_builder;
+mod validate_plugin;
+
+pub use query_schema::{default_query_schema, QuerySchema};
+pub use validate_builder::ValidateInputPluginBuilder;
+pub use validate_plugin::ValidateInputPlugin;
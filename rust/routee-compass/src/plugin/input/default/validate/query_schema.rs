@@ -0,0 +1,485 @@
+use serde_json::{json, Map, Value};
+
+/// keywords of the JSON Schema specification supported by [`QuerySchema`]. annotation
+/// keywords such as `title` and `description` are also accepted and ignored.
+const SUPPORTED_KEYWORDS: [&str; 22] = [
+    "type",
+    "properties",
+    "required",
+    "additionalProperties",
+    "dependentRequired",
+    "items",
+    "enum",
+    "const",
+    "minimum",
+    "maximum",
+    "exclusiveMinimum",
+    "exclusiveMaximum",
+    "minLength",
+    "maxLength",
+    "minItems",
+    "maxItems",
+    "allOf",
+    "anyOf",
+    "oneOf",
+    "not",
+    "$schema",
+    "$id",
+];
+const ANNOTATION_KEYWORDS: [&str; 4] = ["title", "description", "default", "examples"];
+
+/// validates queries against a subset of JSON Schema (draft 2020-12), reporting each
+/// violation with the JSON path where it was found, for example `$.origin_x`.
+/// references (`$ref`) and format validation are not supported.
+#[derive(Clone, Debug)]
+pub struct QuerySchema {
+    schema: Value,
+}
+
+impl QuerySchema {
+    pub fn new(schema: Value) -> Result<QuerySchema, String> {
+        check_keywords(&schema, "#")?;
+        Ok(QuerySchema { schema })
+    }
+
+    /// validates a query, returning all violations found, or an empty vector if valid
+    pub fn validate(&self, query: &Value) -> Vec<String> {
+        let mut errors = vec![];
+        validate_value(&self.schema, query, "$", &mut errors);
+        errors
+    }
+}
+
+/// the default schema for queries, covering the fields read by the core search application
+pub fn default_query_schema() -> Value {
+    let longitude = json!({"type": "number", "minimum": -180.0, "maximum": 180.0});
+    let latitude = json!({"type": "number", "minimum": -90.0, "maximum": 90.0});
+    let id = json!({"type": "integer", "minimum": 0});
+    json!({
+        "type": "object",
+        "properties": {
+            "origin_x": longitude,
+            "origin_y": latitude,
+            "destination_x": longitude,
+            "destination_y": latitude,
+            "origin_vertex": id,
+            "destination_vertex": id,
+            "origin_edge": id,
+            "destination_edge": id,
+            "origin_address": {"type": "string", "minLength": 1},
+            "input_srid": {"anyOf": [{"type": "integer"}, {"type": "string"}]},
+            "destination_address": {"type": "string", "minLength": 1},
+            "endpoint_candidates": {"type": "integer", "minimum": 1},
+            "heuristic_weight": {"type": "number", "minimum": 1.0},
+            "record_settled": {"type": "boolean"},
+            "max_cost": {"type": "number", "minimum": 0.0},
+            "reference_edges": {"type": "array", "items": id},
+            "reference_deviation_penalty": {"type": "number", "minimum": 0.0},
+            "corridor": {
+                "type": "object",
+                "properties": {
+                    "type": {"type": "string", "enum": ["spatial", "network"]},
+                    "edges": {"type": "array", "items": id},
+                    "edge_list_id": id,
+                    "path": {
+                        "type": "array",
+                        "items": {
+                            "type": "array",
+                            "items": {"type": "number"},
+                            "minItems": 2,
+                            "maxItems": 2
+                        }
+                    },
+                    "width": {"type": "number", "minimum": 0.0},
+                    "distance_unit": {"type": "string"}
+                },
+                "required": ["width"]
+            },
+            "weights": {
+                "type": "object",
+                "additionalProperties": {"type": "number", "minimum": 0.0}
+            },
+            "departure_time": {"type": "string", "minLength": 1},
+            "initial_state": {
+                "type": "object",
+                "additionalProperties": {"anyOf": [{"type": "number"}, {"type": "string"}, {"type": "boolean"}]}
+            },
+            "chain_id": {"anyOf": [{"type": "integer"}, {"type": "string"}]},
+            "leg_index": {"type": "integer", "minimum": 0}
+        },
+        "dependentRequired": {
+            "origin_x": ["origin_y"],
+            "origin_y": ["origin_x"],
+            "destination_x": ["destination_y"],
+            "destination_y": ["destination_x"],
+            "leg_index": ["chain_id"]
+        },
+        "anyOf": [
+            {"required": ["origin_x", "origin_y"]},
+            {"required": ["origin_vertex"]},
+            {"required": ["origin_edge"]},
+            {"required": ["origin_address"]}
+        ]
+    })
+}
+
+/// confirms the schema only uses supported keywords, so that unsupported constraints
+/// are not silently ignored.
+fn check_keywords(schema: &Value, path: &str) -> Result<(), String> {
+    let map = match schema {
+        Value::Bool(_) => return Ok(()),
+        Value::Object(map) => map,
+        other => return Err(format!("schema at {path} must be an object, found {other}")),
+    };
+    for (keyword, value) in map.iter() {
+        if ANNOTATION_KEYWORDS.contains(&keyword.as_str()) {
+            continue;
+        }
+        if !SUPPORTED_KEYWORDS.contains(&keyword.as_str()) {
+            return Err(format!(
+                "schema keyword '{keyword}' at {path} is not supported, must be one of: {}",
+                SUPPORTED_KEYWORDS.join(", ")
+            ));
+        }
+        let child_path = format!("{path}/{keyword}");
+        match (keyword.as_str(), value) {
+            ("properties", Value::Object(properties)) => {
+                for (name, property) in properties.iter() {
+                    check_keywords(property, &format!("{child_path}/{name}"))?;
+                }
+            }
+            ("additionalProperties" | "items" | "not", sub) => check_keywords(sub, &child_path)?,
+            ("allOf" | "anyOf" | "oneOf", Value::Array(subs)) => {
+                for (idx, sub) in subs.iter().enumerate() {
+                    check_keywords(sub, &format!("{child_path}/{idx}"))?;
+                }
+            }
+            ("properties" | "allOf" | "anyOf" | "oneOf", other) => {
+                return Err(format!("schema at {child_path} has invalid value {other}"));
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn validate_value(schema: &Value, value: &Value, path: &str, errors: &mut Vec<String>) {
+    let schema = match schema {
+        Value::Bool(true) => return,
+        Value::Bool(false) => {
+            errors.push(format!("{path}: no value is allowed here"));
+            return;
+        }
+        Value::Object(map) => map,
+        _ => return,
+    };
+
+    if let Some(expected) = schema.get("type") {
+        if !matches_type(expected, value) {
+            errors.push(format!(
+                "{path}: expected type {}, found {}",
+                type_description(expected),
+                type_name(value)
+            ));
+            // further keywords would only report the same problem
+            return;
+        }
+    }
+    if let Some(Value::Array(options)) = schema.get("enum") {
+        if !options.contains(value) {
+            errors.push(format!(
+                "{path}: value {value} must be one of {}",
+                Value::Array(options.clone())
+            ));
+        }
+    }
+    if let Some(expected) = schema.get("const") {
+        if expected != value {
+            errors.push(format!("{path}: value {value} must equal {expected}"));
+        }
+    }
+    if let Some(n) = value.as_f64() {
+        validate_number(schema, n, path, errors);
+    }
+    if let Value::String(s) = value {
+        let len = s.chars().count() as u64;
+        if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+            if len < min {
+                errors.push(format!(
+                    "{path}: string must have at least {min} characters"
+                ));
+            }
+        }
+        if let Some(max) = schema.get("maxLength").and_then(Value::as_u64) {
+            if len > max {
+                errors.push(format!("{path}: string must have at most {max} characters"));
+            }
+        }
+    }
+    if let Value::Array(items) = value {
+        validate_array(schema, items, path, errors);
+    }
+    if let Value::Object(object) = value {
+        validate_object(schema, object, path, errors);
+    }
+    validate_combinators(schema, value, path, errors);
+}
+
+fn validate_number(schema: &Map<String, Value>, n: f64, path: &str, errors: &mut Vec<String>) {
+    let bound = |k: &str| schema.get(k).and_then(Value::as_f64);
+    if let Some(min) = bound("minimum") {
+        if n < min {
+            errors.push(format!("{path}: value {n} is less than the minimum {min}"));
+        }
+    }
+    if let Some(max) = bound("maximum") {
+        if n > max {
+            errors.push(format!(
+                "{path}: value {n} is greater than the maximum {max}"
+            ));
+        }
+    }
+    if let Some(min) = bound("exclusiveMinimum") {
+        if n <= min {
+            errors.push(format!("{path}: value {n} must be greater than {min}"));
+        }
+    }
+    if let Some(max) = bound("exclusiveMaximum") {
+        if n >= max {
+            errors.push(format!("{path}: value {n} must be less than {max}"));
+        }
+    }
+}
+
+fn validate_array(
+    schema: &Map<String, Value>,
+    items: &[Value],
+    path: &str,
+    errors: &mut Vec<String>,
+) {
+    let len = items.len() as u64;
+    if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+        if len < min {
+            errors.push(format!("{path}: array must have at least {min} items"));
+        }
+    }
+    if let Some(max) = schema.get("maxItems").and_then(Value::as_u64) {
+        if len > max {
+            errors.push(format!("{path}: array must have at most {max} items"));
+        }
+    }
+    if let Some(item_schema) = schema.get("items") {
+        for (idx, item) in items.iter().enumerate() {
+            validate_value(item_schema, item, &format!("{path}[{idx}]"), errors);
+        }
+    }
+}
+
+fn validate_object(
+    schema: &Map<String, Value>,
+    object: &Map<String, Value>,
+    path: &str,
+    errors: &mut Vec<String>,
+) {
+    if let Some(Value::Array(required)) = schema.get("required") {
+        for key in required.iter().filter_map(Value::as_str) {
+            if !object.contains_key(key) {
+                errors.push(format!("{path}.{key}: required field is missing"));
+            }
+        }
+    }
+    if let Some(Value::Object(dependencies)) = schema.get("dependentRequired") {
+        for (key, dependents) in dependencies.iter() {
+            if !object.contains_key(key) {
+                continue;
+            }
+            for dependent in dependents.as_array().into_iter().flatten() {
+                if let Some(d) = dependent.as_str() {
+                    if !object.contains_key(d) {
+                        errors.push(format!("{path}.{d}: required when '{key}' is provided"));
+                    }
+                }
+            }
+        }
+    }
+    let properties = schema.get("properties").and_then(Value::as_object);
+    for (key, child) in object.iter() {
+        let child_path = format!("{path}.{key}");
+        match properties.and_then(|p| p.get(key)) {
+            Some(property_schema) => validate_value(property_schema, child, &child_path, errors),
+            None => {
+                if let Some(additional) = schema.get("additionalProperties") {
+                    if additional == &Value::Bool(false) {
+                        errors.push(format!("{child_path}: unexpected field"));
+                    } else {
+                        validate_value(additional, child, &child_path, errors);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn validate_combinators(
+    schema: &Map<String, Value>,
+    value: &Value,
+    path: &str,
+    errors: &mut Vec<String>,
+) {
+    if let Some(Value::Array(subs)) = schema.get("allOf") {
+        for sub in subs.iter() {
+            validate_value(sub, value, path, errors);
+        }
+    }
+    if let Some(Value::Array(subs)) = schema.get("anyOf") {
+        let results = sub_results(subs, value, path);
+        if !results.iter().any(|r| r.is_empty()) {
+            errors.push(format!(
+                "{path}: must match at least one of {} alternatives: [{}]",
+                subs.len(),
+                results
+                    .iter()
+                    .map(|r| r.join(", "))
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            ));
+        }
+    }
+    if let Some(Value::Array(subs)) = schema.get("oneOf") {
+        let results = sub_results(subs, value, path);
+        let matches = results.iter().filter(|r| r.is_empty()).count();
+        if matches != 1 {
+            errors.push(format!(
+                "{path}: must match exactly one of {} alternatives, matched {matches}",
+                subs.len()
+            ));
+        }
+    }
+    if let Some(sub) = schema.get("not") {
+        let mut sub_errors = vec![];
+        validate_value(sub, value, path, &mut sub_errors);
+        if sub_errors.is_empty() {
+            errors.push(format!("{path}: must not match schema {sub}"));
+        }
+    }
+}
+
+fn sub_results(subs: &[Value], value: &Value, path: &str) -> Vec<Vec<String>> {
+    subs.iter()
+        .map(|sub| {
+            let mut sub_errors = vec![];
+            validate_value(sub, value, path, &mut sub_errors);
+            sub_errors
+        })
+        .collect()
+}
+
+fn matches_type(expected: &Value, value: &Value) -> bool {
+    match expected {
+        Value::String(t) => matches_type_name(t, value),
+        Value::Array(ts) => ts
+            .iter()
+            .filter_map(Value::as_str)
+            .any(|t| matches_type_name(t, value)),
+        _ => true,
+    }
+}
+
+fn matches_type_name(expected: &str, value: &Value) -> bool {
+    match expected {
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        other => type_name(value) == other,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn type_description(expected: &Value) -> String {
+    match expected {
+        Value::String(t) => t.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn default_schema() -> QuerySchema {
+        QuerySchema::new(default_query_schema()).expect("default schema should be valid")
+    }
+
+    #[test]
+    fn test_valid_query() {
+        let query = json!({
+            "origin_x": -105.1, "origin_y": 39.7,
+            "destination_x": -104.9, "destination_y": 39.6,
+            "weights": {"trip_time": 1.0}
+        });
+        assert!(default_schema().validate(&query).is_empty());
+    }
+
+    #[test]
+    fn test_type_and_range_errors_report_paths() {
+        let query = json!({
+            "origin_x": "-105.1", "origin_y": 139.7,
+            "weights": {"trip_time": -1.0}
+        });
+        let errors = default_schema().validate(&query);
+        assert!(errors.contains(&String::from(
+            "$.origin_x: expected type number, found string"
+        )));
+        assert!(errors
+            .iter()
+            .any(|e| e.starts_with("$.origin_y: value 139.7 is greater than the maximum")));
+        assert!(errors
+            .iter()
+            .any(|e| e.starts_with("$.weights.trip_time: value -1 is less than the minimum")));
+    }
+
+    #[test]
+    fn test_missing_pair_and_origin() {
+        let query = json!({"destination_x": -104.9});
+        let errors = default_schema().validate(&query);
+        assert!(errors.contains(&String::from(
+            "$.destination_y: required when 'destination_x' is provided"
+        )));
+        assert!(errors
+            .iter()
+            .any(|e| e.starts_with("$: must match at least one of 4 alternatives")));
+    }
+
+    #[test]
+    fn test_unsupported_keyword() {
+        let schema = json!({"type": "object", "properties": {"a": {"$ref": "#/defs/a"}}});
+        assert!(QuerySchema::new(schema).is_err());
+    }
+
+    #[test]
+    fn test_items_and_additional_properties() {
+        let schema = QuerySchema::new(json!({
+            "type": "object",
+            "properties": {"ids": {"type": "array", "items": {"type": "integer"}}},
+            "additionalProperties": false
+        }))
+        .expect("invalid schema");
+        let errors = schema.validate(&json!({"ids": [1, "two"], "extra": true}));
+        assert_eq!(
+            errors,
+            vec![
+                String::from("$.ids[1]: expected type integer, found string"),
+                String::from("$.extra: unexpected field"),
+            ]
+        );
+    }
+}
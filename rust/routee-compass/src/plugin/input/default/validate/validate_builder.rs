@@ -0,0 +1,63 @@
+use super::{
+    query_schema::{default_query_schema, QuerySchema},
+    validate_plugin::ValidateInputPlugin,
+};
+use crate::plugin::input::{InputPlugin, InputPluginBuilder};
+use routee_compass_core::config::{CompassConfigurationError, ConfigJsonExtensions};
+use std::sync::Arc;
+
+/// Builds a plugin that rejects queries that do not match a JSON Schema.
+///
+/// # Configuration
+///
+/// * `schema_file` (optional) - a JSON file containing the schema
+/// * `schema` (optional) - the schema written inline in the configuration
+///
+/// if neither is provided, the schema for the core query fields from
+/// [`default_query_schema`] is used.
+///
+/// # Example Configuration
+///
+/// ```toml
+/// [[plugin.input_plugins]]
+/// type = "validate"
+/// schema_file = "query_schema.json"
+/// ```
+pub struct ValidateInputPluginBuilder {}
+
+impl InputPluginBuilder for ValidateInputPluginBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn InputPlugin>, CompassConfigurationError> {
+        let parent_key = String::from("validate");
+        let schema_file = parameters.get_config_path_optional(&"schema_file", &parent_key)?;
+        let inline_schema = parameters.get("schema").cloned();
+        let schema_json = match (schema_file, inline_schema) {
+            (Some(_), Some(_)) => {
+                return Err(CompassConfigurationError::UserConfigurationError(
+                    String::from(
+                        "validate plugin accepts either 'schema_file' or 'schema', not both",
+                    ),
+                ))
+            }
+            (Some(path), None) => {
+                let contents = std::fs::read_to_string(&path).map_err(|e| {
+                    CompassConfigurationError::UserConfigurationError(format!(
+                        "failure reading query schema file {}: {e}",
+                        path.to_string_lossy()
+                    ))
+                })?;
+                serde_json::from_str(&contents)?
+            }
+            (None, Some(schema)) => schema,
+            (None, None) => default_query_schema(),
+        };
+        let schema = QuerySchema::new(schema_json).map_err(|e| {
+            CompassConfigurationError::UserConfigurationError(format!(
+                "invalid query schema for validate plugin: {e}"
+            ))
+        })?;
+        Ok(Arc::new(ValidateInputPlugin::new(schema)))
+    }
+}
@@ -0,0 +1,53 @@
+use super::query_schema::QuerySchema;
+use crate::{
+    app::search::SearchApp,
+    plugin::input::{input_plugin::InputPlugin, InputPluginError},
+};
+use routee_compass_core::model::map::{MapJsonExtensions, MapJsonKey};
+use std::sync::Arc;
+
+/// rejects queries that do not match a [`QuerySchema`], listing each violation by its
+/// JSON path, before any other input plugins or search models process the query.
+/// coordinates of queries with an `input_srid` are validated after transformation to WGS84.
+pub struct ValidateInputPlugin {
+    schema: QuerySchema,
+}
+
+impl ValidateInputPlugin {
+    pub fn new(schema: QuerySchema) -> ValidateInputPlugin {
+        ValidateInputPlugin { schema }
+    }
+}
+
+impl InputPlugin for ValidateInputPlugin {
+    fn name(&self) -> &str {
+        "validate"
+    }
+
+    fn process(
+        &self,
+        input: &mut serde_json::Value,
+        _search_app: Arc<SearchApp>,
+    ) -> Result<(), InputPluginError> {
+        // coordinates declared in another coordinate reference system are checked
+        // after transformation to WGS84
+        let errors = if input.get(MapJsonKey::InputSrid.as_str()).is_some() {
+            let mut transformed = input.clone();
+            transformed
+                .apply_input_srid()
+                .map_err(|e| InputPluginError::InputPluginFailed(e.to_string()))?;
+            self.schema.validate(&transformed)
+        } else {
+            self.schema.validate(input)
+        };
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(InputPluginError::InputPluginFailed(format!(
+                "query failed schema validation with {} error(s): {}",
+                errors.len(),
+                errors.join("; ")
+            )))
+        }
+    }
+}
@@ -7,12 +7,18 @@ pub enum InputField {
     OriginY,
     DestinationX,
     DestinationY,
+    OriginAddress,
+    DestinationAddress,
     OriginVertex,
     DestinationVertex,
     OriginEdge,
     DestinationEdge,
     GridSearch,
+    Sweep,
+    SweepParameters,
+    SweepIndex,
     QueryWeightEstimate,
+    InputPluginsComplete,
     Custom(String),
 }
 
@@ -24,12 +30,18 @@ impl InputField {
             I::OriginY => "origin_y",
             I::DestinationX => "destination_x",
             I::DestinationY => "destination_y",
+            I::OriginAddress => "origin_address",
+            I::DestinationAddress => "destination_address",
             I::OriginVertex => "origin_vertex",
             I::DestinationVertex => "destination_vertex",
             I::OriginEdge => "origin_edge",
             I::DestinationEdge => "destination_edge",
             I::GridSearch => "grid_search",
+            I::Sweep => "sweep",
+            I::SweepParameters => "sweep_parameters",
+            I::SweepIndex => "sweep_index",
             I::QueryWeightEstimate => "query_weight_estimate",
+            I::InputPluginsComplete => "input_plugins_complete",
             I::Custom(field) => field,
         }
     }
@@ -19,6 +19,9 @@ pub trait InputJsonExtensions {
     fn get_grid_search(&self) -> Option<&serde_json::Value>;
     fn add_query_weight_estimate(&mut self, weight: f64) -> Result<(), InputPluginError>;
     fn get_query_weight_estimate(&self) -> Result<Option<f64>, CompassAppError>;
+    /// marks a query as complete, so that it is skipped by the remaining input plugins
+    fn mark_input_plugins_complete(&mut self) -> Result<(), InputPluginError>;
+    fn is_input_plugins_complete(&self) -> bool;
 }
 
 impl InputJsonExtensions for serde_json::Value {
@@ -207,6 +210,24 @@ impl InputJsonExtensions for serde_json::Value {
             }),
         }
     }
+
+    fn mark_input_plugins_complete(&mut self) -> Result<(), InputPluginError> {
+        match self {
+            serde_json::Value::Object(map) => {
+                map.insert(InputField::InputPluginsComplete.to_string(), json!(true));
+                Ok(())
+            }
+            _ => Err(InputPluginError::UnexpectedQueryStructure(String::from(
+                "InputQuery is not a JSON object",
+            ))),
+        }
+    }
+
+    fn is_input_plugins_complete(&self) -> bool {
+        self.get(InputField::InputPluginsComplete.to_str())
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or_default()
+    }
 }
 
 // pub type DecodeOp<T> = Box<dyn Fn(&serde_json::Value) -> Option<T>>;
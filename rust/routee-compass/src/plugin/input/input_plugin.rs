@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use crate::app::search::SearchApp;
+use crate::plugin::short_type_name;
 
 use super::InputPluginError;
 
@@ -15,16 +16,28 @@ use super::InputPluginError;
 /// The following default set of input plugin builders are found in the [`super::default`] module:
 ///
 /// * [debug] - logs the (current) status of each query to the logging system
+/// * [geocode] - resolves origin and destination addresses into coordinates
 /// * [grid search] - duplicates a query based on a list of user-defined values
 /// * [inject] - mechanism to inject values into the queries
 /// * [load balancer] - uses weighting heuristics to balance query loads across threads
+/// * [sweep] - expands a query into the cross product of its sweep parameters
+/// * [validate] - rejects queries that do not match a JSON Schema
 ///
 /// [debug]: super::default::debug::debug_builder::DebugInputPluginBuilder
+/// [geocode]: super::default::geocode::GeocodeInputPluginBuilder
 /// [grid search]: super::default::grid_search::GridSearchBuilder
 /// [inject]: super::default::inject::inject_builder::InjectPluginBuilder
 /// [load balancer]: super::default::load_balancer::builder::LoadBalancerBuilder
+/// [sweep]: super::default::sweep::SweepBuilder
+/// [validate]: super::default::validate::ValidateInputPluginBuilder
 ///
 pub trait InputPlugin: Send + Sync {
+    /// a name for this plugin used in progress bars and run metrics. by default,
+    /// the name of the type implementing [`InputPlugin`].
+    fn name(&self) -> &str {
+        short_type_name(std::any::type_name::<Self>())
+    }
+
     /// Applies this [`InputPlugin`] to a user query input, passing along a `Vec` of input
     /// queries as a result which will replace the input.
     ///
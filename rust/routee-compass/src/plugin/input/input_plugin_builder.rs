@@ -9,7 +9,7 @@ use std::sync::Arc;
 /// this trait.
 ///
 /// [InputPlugin]: compass_app::plugin::input::input_plugin::InputPlugin
-pub trait InputPluginBuilder {
+pub trait InputPluginBuilder: Send + Sync {
     /// Builds a [InputPlugin] from JSON configuration.
     ///
     /// # Arguments
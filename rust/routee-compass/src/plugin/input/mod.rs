@@ -1,3 +1,4 @@
+mod conditional_input_plugin;
 pub mod default;
 mod input_field;
 mod input_json_extensions;
@@ -6,6 +7,7 @@ mod input_plugin_builder;
 mod input_plugin_error;
 pub mod input_plugin_ops;
 
+pub use conditional_input_plugin::{ConditionalInputPlugin, InputPluginCondition};
 pub use input_field::InputField;
 pub use input_json_extensions::InputJsonExtensions;
 pub use input_plugin::InputPlugin;
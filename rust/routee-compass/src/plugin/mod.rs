@@ -5,3 +5,9 @@ mod plugin_error;
 
 pub use plugin_config::PluginConfig;
 pub use plugin_error::PluginError;
+
+/// removes the module path from a type name, for example `my_crate::plugin::MyPlugin`
+/// becomes `MyPlugin`. used for default plugin names.
+pub(crate) fn short_type_name(type_name: &str) -> &str {
+    type_name.rsplit("::").next().unwrap_or(type_name)
+}
@@ -0,0 +1,40 @@
+use super::plugin::AccessibilityOutputPlugin;
+use crate::{
+    app::compass::CompassComponentError,
+    plugin::{
+        output::{default::accessibility::AccessibilityConfig, OutputPlugin, OutputPluginBuilder},
+        PluginError,
+    },
+};
+use std::sync::Arc;
+
+/// Builds a plugin that joins the costs of searches without a destination against a
+/// file of opportunities at each vertex to compute accessibility metrics per origin.
+///
+/// # Example Configuration
+///
+/// ```toml
+/// [[plugin.output_plugins]]
+/// type = "accessibility"
+/// opportunities_input_file = "jobs.csv"
+/// cumulative_thresholds = [15.0, 30.0, 45.0]
+/// gravity = { type = "exponential", beta = 0.1 }
+/// ```
+pub struct AccessibilityOutputPluginBuilder {}
+
+impl OutputPluginBuilder for AccessibilityOutputPluginBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn OutputPlugin>, CompassComponentError> {
+        let conf: AccessibilityConfig =
+            serde_json::from_value(parameters.clone()).map_err(|e| {
+                PluginError::BuildFailed(format!(
+                    "failure reading accessibility output plugin config: {e}"
+                ))
+            })?;
+        let plugin = AccessibilityOutputPlugin::new(conf)
+            .map_err(|e| PluginError::OutputPluginFailed { source: e })?;
+        Ok(Arc::new(plugin))
+    }
+}
@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AccessibilityConfig {
+    /// CSV file with a `vertex_id` and `opportunities` column, such as the number of
+    /// jobs at each vertex. vertices may be repeated, and their opportunities are summed.
+    pub opportunities_input_file: String,
+    /// objective costs at which the cumulative opportunities are counted
+    #[serde(default)]
+    pub cumulative_thresholds: Vec<f64>,
+    /// if provided, computes a gravity-based accessibility metric with this decay function
+    pub gravity: Option<GravityDecay>,
+}
+
+/// decay functions that weigh the opportunities at a vertex by the cost to reach it
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum GravityDecay {
+    /// exp(-beta * cost)
+    Exponential { beta: f64 },
+    /// cost^-beta, where costs below 1 are treated as 1
+    Power { beta: f64 },
+    /// exp(-cost^2 / (2 * sigma^2))
+    Gaussian { sigma: f64 },
+}
+
+impl GravityDecay {
+    /// the weight of an opportunity reached at some cost
+    pub fn weight(&self, cost: f64) -> f64 {
+        match self {
+            GravityDecay::Exponential { beta } => (-beta * cost).exp(),
+            GravityDecay::Power { beta } => cost.max(1.0).powf(-beta),
+            GravityDecay::Gaussian { sigma } => (-(cost * cost) / (2.0 * sigma * sigma)).exp(),
+        }
+    }
+
+    /// validates the decay parameters
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            GravityDecay::Exponential { beta } | GravityDecay::Power { beta }
+                if !(beta.is_finite() && *beta >= 0.0) =>
+            {
+                Err(format!(
+                    "gravity decay beta must be non-negative, found {beta}"
+                ))
+            }
+            GravityDecay::Gaussian { sigma } if !(sigma.is_finite() && *sigma > 0.0) => Err(
+                format!("gravity decay sigma must be positive, found {sigma}"),
+            ),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_gravity_decay_weights() {
+        let exponential = GravityDecay::Exponential { beta: 0.1 };
+        assert_eq!(exponential.weight(0.0), 1.0);
+        assert!((exponential.weight(10.0) - (-1.0f64).exp()).abs() < 1e-12);
+
+        let power = GravityDecay::Power { beta: 2.0 };
+        assert_eq!(power.weight(0.5), 1.0);
+        assert_eq!(power.weight(4.0), 1.0 / 16.0);
+
+        let gaussian = GravityDecay::Gaussian { sigma: 10.0 };
+        assert!((gaussian.weight(10.0) - (-0.5f64).exp()).abs() < 1e-12);
+
+        assert!(GravityDecay::Power { beta: -1.0 }.validate().is_err());
+        assert!(GravityDecay::Gaussian { sigma: 0.0 }.validate().is_err());
+    }
+}
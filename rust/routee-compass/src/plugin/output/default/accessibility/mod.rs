@@ -0,0 +1,7 @@
+mod builder;
+mod config;
+mod plugin;
+
+pub use builder::AccessibilityOutputPluginBuilder;
+pub use config::{AccessibilityConfig, GravityDecay};
+pub use plugin::AccessibilityOutputPlugin;
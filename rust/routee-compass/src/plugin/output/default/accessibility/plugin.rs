@@ -0,0 +1,149 @@
+use crate::app::{compass::CompassAppError, search::SearchAppResult};
+use crate::plugin::output::default::accessibility::{AccessibilityConfig, GravityDecay};
+use crate::plugin::output::default::cost_surface::reached_vertices;
+use crate::plugin::output::output_plugin::OutputPlugin;
+use crate::plugin::output::OutputPluginError;
+use routee_compass_core::algorithm::search::SearchInstance;
+use routee_compass_core::model::map::MapJsonExtensions;
+use routee_compass_core::model::network::VertexId;
+use routee_compass_core::util::fs::read_utils;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// computes accessibility metrics for each query without a destination by joining the
+/// lowest cost to reach each vertex against the opportunities at that vertex.
+///
+/// each response gets an `accessibility` object with the `reachable_opportunities` of
+/// every vertex reached, the `cumulative` opportunities reached within each cost
+/// threshold and, if a decay function is configured, the `gravity` metric, which sums
+/// the opportunities weighted by the decay of the cost to reach them.
+pub struct AccessibilityOutputPlugin {
+    pub opportunities: HashMap<VertexId, f64>,
+    pub cumulative_thresholds: Vec<f64>,
+    pub gravity: Option<GravityDecay>,
+}
+
+/// a row of the opportunities input file
+#[derive(Deserialize)]
+struct OpportunityRow {
+    vertex_id: usize,
+    opportunities: f64,
+}
+
+impl AccessibilityOutputPlugin {
+    pub fn new(conf: AccessibilityConfig) -> Result<AccessibilityOutputPlugin, OutputPluginError> {
+        if let Some(t) = conf
+            .cumulative_thresholds
+            .iter()
+            .find(|t| !(t.is_finite() && **t >= 0.0))
+        {
+            return Err(OutputPluginError::BuildFailed(format!(
+                "accessibility cumulative thresholds must be non-negative, found {t}"
+            )));
+        }
+        if let Some(gravity) = &conf.gravity {
+            gravity.validate().map_err(OutputPluginError::BuildFailed)?;
+        }
+        let rows: Box<[OpportunityRow]> =
+            read_utils::from_csv(&conf.opportunities_input_file, true, None, None).map_err(
+                |e| {
+                    OutputPluginError::BuildFailed(format!(
+                        "failure reading opportunities file {}: {e}",
+                        conf.opportunities_input_file
+                    ))
+                },
+            )?;
+        let mut opportunities: HashMap<VertexId, f64> = HashMap::new();
+        for row in rows.iter() {
+            *opportunities.entry(VertexId(row.vertex_id)).or_default() += row.opportunities;
+        }
+        Ok(AccessibilityOutputPlugin {
+            opportunities,
+            cumulative_thresholds: conf.cumulative_thresholds,
+            gravity: conf.gravity,
+        })
+    }
+}
+
+impl OutputPlugin for AccessibilityOutputPlugin {
+    fn name(&self) -> &str {
+        "accessibility"
+    }
+
+    fn process(
+        &self,
+        output: &mut Value,
+        search_result: &Result<(SearchAppResult, SearchInstance), CompassAppError>,
+    ) -> Result<(), OutputPluginError> {
+        let result = match search_result {
+            Ok((result, _)) if result.routes.is_empty() => result,
+            _ => return Ok(()),
+        };
+        let Some(tree) = result.trees.first() else {
+            return Ok(());
+        };
+        let origin = output["request"].get_origin_vertex().ok();
+        let reached = reached_vertices(tree, origin)?
+            .into_iter()
+            .filter_map(|r| {
+                self.opportunities
+                    .get(&r.vertex_id)
+                    .map(|opportunities| (r.cost, *opportunities))
+            })
+            .collect::<Vec<_>>();
+
+        let reachable: f64 = reached.iter().map(|(_, o)| o).sum();
+        let cumulative = self
+            .cumulative_thresholds
+            .iter()
+            .map(|threshold| {
+                let opportunities: f64 = reached
+                    .iter()
+                    .filter(|(cost, _)| cost <= threshold)
+                    .map(|(_, o)| o)
+                    .sum();
+                json!({"threshold": threshold, "opportunities": opportunities})
+            })
+            .collect::<Vec<_>>();
+        let mut accessibility = json!({
+            "reachable_opportunities": reachable,
+            "cumulative": cumulative
+        });
+        if let Some(gravity) = &self.gravity {
+            let value: f64 = reached.iter().map(|(c, o)| o * gravity.weight(*c)).sum();
+            accessibility["gravity"] = json!(value);
+        }
+        output["accessibility"] = accessibility;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::compass::compass_app_test_ops::speeds_test_app;
+
+    #[test]
+    fn test_accessibility() {
+        let opportunities_file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(
+            opportunities_file.path(),
+            "vertex_id,opportunities\n0,5\n1,10\n2,15\n2,5\n",
+        )
+        .unwrap();
+        let app = speeds_test_app(&format!(
+            "[mapping]\nqueries_without_destinations = true\n[[plugin.output_plugins]]\ntype = \"accessibility\"\nopportunities_input_file = {:?}\ncumulative_thresholds = [0.0, 1000000.0]\ngravity = {{ type = \"exponential\", beta = 0.0 }}",
+            opportunities_file.path().to_str().unwrap(),
+        ));
+
+        let mut queries = vec![serde_json::json!({"origin_vertex": 0})];
+        let responses = app.run(&mut queries, None).expect("run failed");
+        let accessibility = &responses[0]["accessibility"];
+        assert_eq!(accessibility["reachable_opportunities"], 35.0);
+        // only the opportunities at the origin are reached at no cost
+        assert_eq!(accessibility["cumulative"][0]["opportunities"], 5.0);
+        assert_eq!(accessibility["cumulative"][1]["opportunities"], 35.0);
+        // without decay, every reachable opportunity counts fully
+        assert_eq!(accessibility["gravity"], 35.0);
+    }
+}
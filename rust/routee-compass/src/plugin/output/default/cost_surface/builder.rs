@@ -0,0 +1,39 @@
+use super::plugin::CostSurfaceOutputPlugin;
+use crate::{
+    app::compass::CompassComponentError,
+    plugin::{
+        output::{default::cost_surface::CostSurfaceConfig, OutputPlugin, OutputPluginBuilder},
+        PluginError,
+    },
+};
+use std::sync::Arc;
+
+/// Builds a plugin that writes the cost to reach every vertex of a search without a
+/// destination to a table, one file per query, for accessibility analysis.
+///
+/// # Example Configuration
+///
+/// ```toml
+/// [[plugin.output_plugins]]
+/// type = "cost_surface"
+/// output_directory = "cost_surfaces"
+/// format = "parquet"
+/// state_features = ["trip_time", "trip_distance"]
+/// ```
+pub struct CostSurfaceOutputPluginBuilder {}
+
+impl OutputPluginBuilder for CostSurfaceOutputPluginBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn OutputPlugin>, CompassComponentError> {
+        let conf: CostSurfaceConfig = serde_json::from_value(parameters.clone()).map_err(|e| {
+            PluginError::BuildFailed(format!(
+                "failure reading cost surface output plugin config: {e}"
+            ))
+        })?;
+        let plugin = CostSurfaceOutputPlugin::new(conf)
+            .map_err(|e| PluginError::OutputPluginFailed { source: e })?;
+        Ok(Arc::new(plugin))
+    }
+}
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CostSurfaceConfig {
+    /// directory where a cost table is written for each query without a destination
+    pub output_directory: String,
+    /// file format of the cost tables. defaults to csv.
+    #[serde(default)]
+    pub format: CostSurfaceFormat,
+    /// state features reported for each vertex, such as `trip_time`
+    #[serde(default)]
+    pub state_features: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CostSurfaceFormat {
+    #[default]
+    Csv,
+    Parquet,
+}
+
+impl CostSurfaceFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            CostSurfaceFormat::Csv => "csv",
+            CostSurfaceFormat::Parquet => "parquet",
+        }
+    }
+}
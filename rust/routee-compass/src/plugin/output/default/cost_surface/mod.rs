@@ -0,0 +1,7 @@
+mod builder;
+mod config;
+mod plugin;
+
+pub use builder::CostSurfaceOutputPluginBuilder;
+pub use config::{CostSurfaceConfig, CostSurfaceFormat};
+pub use plugin::{reached_vertices, CostSurfaceOutputPlugin, ReachedVertex};
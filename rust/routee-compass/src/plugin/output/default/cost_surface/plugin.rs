@@ -0,0 +1,333 @@
+use crate::app::{compass::CompassAppError, search::SearchAppResult};
+use crate::plugin::output::default::cost_surface::{CostSurfaceConfig, CostSurfaceFormat};
+use crate::plugin::output::default::traversal::TreeOutputLimits;
+use crate::plugin::output::output_plugin::OutputPlugin;
+use crate::plugin::output::OutputPluginError;
+use arrow::array::{ArrayRef, Float64Array, UInt64Array};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use parquet::arrow::ArrowWriter;
+use routee_compass_core::algorithm::search::{SearchInstance, SearchTree};
+use routee_compass_core::model::map::MapJsonExtensions;
+use routee_compass_core::model::network::VertexId;
+use routee_compass_core::model::state::StateVariable;
+use routee_compass_core::model::unit::AsF64;
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// writes a vertex-indexed cost table for each query without a destination, such as a
+/// reachability search bounded by the query `max_cost`. each row holds a vertex reached
+/// by the search, its coordinates, the lowest objective cost to reach it from the origin
+/// and the configured state features at that cost.
+///
+/// tables are named by the `request_id` or `input_index` of the request, if present,
+/// and otherwise numbered in the order they are written. the response gets a
+/// `cost_surface` object with the number of vertices and the path of the table.
+pub struct CostSurfaceOutputPlugin {
+    pub output_directory: PathBuf,
+    pub format: CostSurfaceFormat,
+    pub state_features: Vec<String>,
+    table_count: AtomicUsize,
+}
+
+/// a vertex reached by a search, with the cost and state features to reach it
+struct CostSurfaceRow {
+    vertex_id: VertexId,
+    x: f64,
+    y: f64,
+    cost: f64,
+    features: Vec<f64>,
+}
+
+/// a vertex reached by a search tree with the lowest objective cost to reach it from the
+/// root, and the state at that cost. the state of the root is the initial state (None).
+pub struct ReachedVertex<'a> {
+    pub vertex_id: VertexId,
+    pub cost: f64,
+    pub state: Option<&'a [StateVariable]>,
+}
+
+/// collects the vertices of a search tree with the lowest cost to reach each of them,
+/// ordered by cost. the origin is used as the root of a tree without any branches.
+pub fn reached_vertices(
+    tree: &SearchTree,
+    origin: Option<VertexId>,
+) -> Result<Vec<ReachedVertex<'_>>, OutputPluginError> {
+    let mut reached = vec![];
+    let mut visited: HashSet<VertexId> = HashSet::new();
+    if let Some(root) = tree.root().map(|r| *r.vertex_id()).or(origin) {
+        visited.insert(root);
+        reached.push(ReachedVertex {
+            vertex_id: root,
+            cost: 0.0,
+            state: None,
+        });
+    }
+    for branch in TreeOutputLimits::default().select(tree)? {
+        let vertex_id = *branch.label.vertex_id();
+        if visited.insert(vertex_id) {
+            reached.push(ReachedVertex {
+                vertex_id,
+                cost: branch.cost.as_f64(),
+                state: tree.get_state(branch.label),
+            });
+        }
+    }
+    Ok(reached)
+}
+
+impl CostSurfaceOutputPlugin {
+    pub fn new(conf: CostSurfaceConfig) -> Result<CostSurfaceOutputPlugin, OutputPluginError> {
+        let output_directory = PathBuf::from(&conf.output_directory);
+        std::fs::create_dir_all(&output_directory).map_err(|e| {
+            OutputPluginError::BuildFailed(format!(
+                "failure creating cost surface output directory {}: {e}",
+                conf.output_directory
+            ))
+        })?;
+        Ok(CostSurfaceOutputPlugin {
+            output_directory,
+            format: conf.format,
+            state_features: conf.state_features,
+            table_count: AtomicUsize::new(0),
+        })
+    }
+
+    /// the path of the table for a request
+    fn table_path(&self, request: &Value) -> PathBuf {
+        let table_id = ["request_id", "input_index"]
+            .iter()
+            .find_map(|key| request.get(key))
+            .map(|v| match v {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            })
+            .unwrap_or_else(|| {
+                let count = self.table_count.fetch_add(1, Ordering::SeqCst);
+                format!("cost_surface_{count}")
+            });
+        let filename = table_id
+            .chars()
+            .map(|c| match c {
+                'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' | '.' => c,
+                _ => '_',
+            })
+            .collect::<String>();
+        self.output_directory
+            .join(format!("{filename}.{}", self.format.extension()))
+    }
+
+    /// builds a row for each vertex reached by a search tree
+    fn rows(
+        &self,
+        tree: &SearchTree,
+        origin: Option<VertexId>,
+        si: &SearchInstance,
+    ) -> Result<Vec<CostSurfaceRow>, OutputPluginError> {
+        let initial_state = si.initial_state().map_err(|e| {
+            OutputPluginError::OutputPluginFailed(format!("failure building initial state: {e}"))
+        })?;
+        reached_vertices(tree, origin)?
+            .into_iter()
+            .map(|reached| {
+                let state = reached.state.unwrap_or(&initial_state);
+                self.row(reached.vertex_id, reached.cost, state, si)
+            })
+            .collect()
+    }
+
+    fn row(
+        &self,
+        vertex_id: VertexId,
+        cost: f64,
+        state: &[StateVariable],
+        si: &SearchInstance,
+    ) -> Result<CostSurfaceRow, OutputPluginError> {
+        let vertex = si.graph.get_vertex(&vertex_id).map_err(|e| {
+            OutputPluginError::OutputPluginFailed(format!("cost surface vertex not found: {e}"))
+        })?;
+        let features = if self.state_features.is_empty() {
+            vec![]
+        } else {
+            let state_json = si.state_model.serialize_state(state, false).map_err(|e| {
+                OutputPluginError::OutputPluginFailed(format!(
+                    "failure serializing cost surface state: {e}"
+                ))
+            })?;
+            self.state_features
+                .iter()
+                .map(|name| {
+                    state_json
+                        .get(name)
+                        .and_then(Value::as_f64)
+                        .ok_or_else(|| {
+                            OutputPluginError::OutputPluginFailed(format!(
+                                "cost surface state feature '{name}' is not a numeric feature of the state model"
+                            ))
+                        })
+                })
+                .collect::<Result<Vec<_>, _>>()?
+        };
+        Ok(CostSurfaceRow {
+            vertex_id,
+            x: vertex.x().into(),
+            y: vertex.y().into(),
+            cost,
+            features,
+        })
+    }
+
+    fn write_csv(&self, path: &Path, rows: &[CostSurfaceRow]) -> Result<(), OutputPluginError> {
+        let failed = |e: csv::Error| {
+            OutputPluginError::OutputPluginFailed(format!(
+                "failure writing cost surface file {}: {e}",
+                path.display()
+            ))
+        };
+        let mut writer = csv::Writer::from_path(path).map_err(failed)?;
+        let header = ["vertex_id", "x", "y", "cost"]
+            .into_iter()
+            .map(String::from)
+            .chain(self.state_features.iter().cloned());
+        writer.write_record(header).map_err(failed)?;
+        for row in rows.iter() {
+            let record = [
+                row.vertex_id.to_string(),
+                row.x.to_string(),
+                row.y.to_string(),
+                row.cost.to_string(),
+            ]
+            .into_iter()
+            .chain(row.features.iter().map(|f| f.to_string()));
+            writer.write_record(record).map_err(failed)?;
+        }
+        writer.flush().map_err(|e| failed(csv::Error::from(e)))
+    }
+
+    fn write_parquet(&self, path: &Path, rows: &[CostSurfaceRow]) -> Result<(), OutputPluginError> {
+        let failed = |e: String| {
+            OutputPluginError::OutputPluginFailed(format!(
+                "failure writing cost surface file {}: {e}",
+                path.display()
+            ))
+        };
+        let float_column = |f: &dyn Fn(&CostSurfaceRow) -> f64| -> ArrayRef {
+            Arc::new(Float64Array::from_iter_values(rows.iter().map(f)))
+        };
+        let mut fields = vec![
+            Field::new("vertex_id", DataType::UInt64, false),
+            Field::new("x", DataType::Float64, false),
+            Field::new("y", DataType::Float64, false),
+            Field::new("cost", DataType::Float64, false),
+        ];
+        let mut columns: Vec<ArrayRef> = vec![
+            Arc::new(UInt64Array::from_iter_values(
+                rows.iter().map(|r| r.vertex_id.0 as u64),
+            )),
+            float_column(&|r| r.x),
+            float_column(&|r| r.y),
+            float_column(&|r| r.cost),
+        ];
+        for (idx, name) in self.state_features.iter().enumerate() {
+            fields.push(Field::new(name, DataType::Float64, false));
+            columns.push(float_column(&|r| r.features[idx]));
+        }
+        let schema = Arc::new(Schema::new(fields));
+        let batch =
+            RecordBatch::try_new(schema.clone(), columns).map_err(|e| failed(e.to_string()))?;
+        let file = File::create(path).map_err(|e| failed(e.to_string()))?;
+        let mut writer =
+            ArrowWriter::try_new(file, schema, None).map_err(|e| failed(e.to_string()))?;
+        writer.write(&batch).map_err(|e| failed(e.to_string()))?;
+        writer.close().map_err(|e| failed(e.to_string()))?;
+        Ok(())
+    }
+}
+
+impl OutputPlugin for CostSurfaceOutputPlugin {
+    fn name(&self) -> &str {
+        "cost_surface"
+    }
+
+    fn process(
+        &self,
+        output: &mut Value,
+        search_result: &Result<(SearchAppResult, SearchInstance), CompassAppError>,
+    ) -> Result<(), OutputPluginError> {
+        let (result, si) = match search_result {
+            Err(_) => return Ok(()),
+            Ok((result, si)) if result.routes.is_empty() => (result, si),
+            Ok(_) => return Ok(()),
+        };
+        let Some(tree) = result.trees.first() else {
+            return Ok(());
+        };
+        let origin = output["request"].get_origin_vertex().ok();
+        let rows = self.rows(tree, origin, si)?;
+        let path = self.table_path(&output["request"]);
+        match self.format {
+            CostSurfaceFormat::Csv => self.write_csv(&path, &rows)?,
+            CostSurfaceFormat::Parquet => self.write_parquet(&path, &rows)?,
+        }
+        let max_cost = rows.iter().map(|r| r.cost).fold(0.0, f64::max);
+        output["cost_surface"] = json!({
+            "vertex_count": rows.len(),
+            "max_cost": max_cost,
+            "output_file": path.to_string_lossy()
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::compass::compass_app_test_ops::speeds_test_app;
+
+    #[test]
+    fn test_cost_surface() {
+        let csv_dir = tempfile::tempdir().unwrap();
+        let parquet_dir = tempfile::tempdir().unwrap();
+        let app = speeds_test_app(&format!(
+            "[mapping]\nqueries_without_destinations = true\n[[plugin.output_plugins]]\ntype = \"cost_surface\"\noutput_directory = {:?}\nstate_features = [\"trip_time\"]\n[[plugin.output_plugins]]\ntype = \"cost_surface\"\noutput_directory = {:?}\nformat = \"parquet\"",
+            csv_dir.path().to_str().unwrap(),
+            parquet_dir.path().to_str().unwrap(),
+        ));
+
+        // without a bound, the search from vertex 0 reaches every vertex
+        let mut queries = vec![serde_json::json!({"origin_vertex": 0, "request_id": "all"})];
+        let responses = app.run(&mut queries, None).expect("run failed");
+        assert_eq!(responses[0]["cost_surface"]["vertex_count"], 3);
+        let mut reader = csv::Reader::from_path(csv_dir.path().join("all.csv")).unwrap();
+        let rows = reader
+            .records()
+            .map(|r| r.unwrap().iter().map(String::from).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let vertices = rows.iter().map(|r| r[0].as_str()).collect::<Vec<_>>();
+        assert_eq!(vertices, vec!["0", "1", "2"]);
+        let vertex_1_cost: f64 = rows[1][3].parse().unwrap();
+        assert!(vertex_1_cost > 0.0);
+        // with a time-optimal objective, the cost is the trip time
+        assert_eq!(rows[1][3], rows[1][4]);
+
+        let parquet_file = std::fs::File::open(parquet_dir.path().join("all.parquet")).unwrap();
+        let parquet_reader =
+            parquet::file::reader::SerializedFileReader::new(parquet_file).unwrap();
+        let n_rows = parquet::file::reader::FileReader::metadata(&parquet_reader)
+            .file_metadata()
+            .num_rows();
+        assert_eq!(n_rows, 3);
+
+        // a max_cost below the cost of vertex 1 leaves only the origin
+        let mut queries = vec![serde_json::json!({
+            "origin_vertex": 0,
+            "request_id": "bounded",
+            "max_cost": vertex_1_cost / 2.0
+        })];
+        let responses = app.run(&mut queries, None).expect("run failed");
+        assert_eq!(responses[0]["cost_surface"]["vertex_count"], 1);
+    }
+}
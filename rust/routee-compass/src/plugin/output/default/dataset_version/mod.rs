@@ -0,0 +1,3 @@
+mod plugin;
+
+pub use plugin::DatasetVersionOutputPlugin;
@@ -0,0 +1,27 @@
+use crate::app::compass::CompassAppError;
+use crate::app::search::SearchAppResult;
+use crate::plugin::output::{OutputPlugin, OutputPluginError};
+use routee_compass_core::algorithm::search::SearchInstance;
+use serde_json::Value;
+
+/// adds the version of the loaded dataset to each response as `dataset_version`. added
+/// by the app when a dataset manifest is configured in the `system` section.
+pub struct DatasetVersionOutputPlugin {
+    pub dataset_version: String,
+}
+
+impl DatasetVersionOutputPlugin {
+    /// response key holding the dataset version
+    pub const DATASET_VERSION_KEY: &'static str = "dataset_version";
+}
+
+impl OutputPlugin for DatasetVersionOutputPlugin {
+    fn process(
+        &self,
+        output: &mut Value,
+        _result: &Result<(SearchAppResult, SearchInstance), CompassAppError>,
+    ) -> Result<(), OutputPluginError> {
+        output[Self::DATASET_VERSION_KEY] = Value::String(self.dataset_version.clone());
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+use super::plugin::ElevationProfileOutputPlugin;
+use crate::{
+    app::compass::CompassComponentError,
+    plugin::{
+        output::{
+            default::elevation_profile::ElevationProfileConfig, OutputPlugin, OutputPluginBuilder,
+        },
+        PluginError,
+    },
+};
+use std::sync::Arc;
+
+/// Builds a plugin that appends the elevation profile of each route to the result.
+/// Requires a traversal model that provides `edge_distance` and `edge_grade`.
+///
+/// # Example Configuration
+///
+/// ```toml
+/// [[plugin.output_plugins]]
+/// type = "elevation_profile"
+/// distance_unit = "miles"
+/// elevation_unit = "feet"
+/// ```
+pub struct ElevationProfileOutputPluginBuilder {}
+
+impl OutputPluginBuilder for ElevationProfileOutputPluginBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn OutputPlugin>, CompassComponentError> {
+        let conf: ElevationProfileConfig =
+            serde_json::from_value(parameters.clone()).map_err(|e| {
+                PluginError::BuildFailed(format!(
+                    "failure reading elevation profile output plugin config: {e}"
+                ))
+            })?;
+        Ok(Arc::new(ElevationProfileOutputPlugin::new(conf)))
+    }
+}
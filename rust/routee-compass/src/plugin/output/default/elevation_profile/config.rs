@@ -0,0 +1,10 @@
+use routee_compass_core::model::unit::DistanceUnit;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ElevationProfileConfig {
+    /// unit of the cumulative distance of each sample. defaults to miles.
+    pub distance_unit: Option<DistanceUnit>,
+    /// unit of the elevation of each sample. defaults to feet.
+    pub elevation_unit: Option<DistanceUnit>,
+}
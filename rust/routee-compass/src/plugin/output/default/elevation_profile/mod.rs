@@ -0,0 +1,7 @@
+mod builder;
+mod config;
+mod plugin;
+
+pub use builder::ElevationProfileOutputPluginBuilder;
+pub use config::ElevationProfileConfig;
+pub use plugin::ElevationProfileOutputPlugin;
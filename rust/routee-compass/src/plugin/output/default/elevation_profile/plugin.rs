@@ -0,0 +1,207 @@
+use crate::app::{compass::CompassAppError, search::SearchAppResult};
+use crate::plugin::output::default::elevation_profile::ElevationProfileConfig;
+use crate::plugin::output::output_plugin::OutputPlugin;
+use crate::plugin::output::OutputPluginError;
+use routee_compass_core::algorithm::search::{EdgeTraversal, SearchInstance};
+use routee_compass_core::model::state::StateModel;
+use routee_compass_core::model::traversal::default::fieldname;
+use routee_compass_core::model::unit::DistanceUnit;
+use serde_json::json;
+use uom::si::f64::Length;
+use uom::ConstZero;
+
+/// appends a cumulative distance vs. elevation profile for each route, with one
+/// (distance, elevation) sample at the start of the route and at the end of each edge.
+/// elevations are computed from the distance and grade of each edge, starting from the
+/// query `start_elevation` (in the configured elevation unit) or zero if not provided.
+pub struct ElevationProfileOutputPlugin {
+    pub distance_unit: DistanceUnit,
+    pub elevation_unit: DistanceUnit,
+}
+
+impl ElevationProfileOutputPlugin {
+    pub fn new(conf: ElevationProfileConfig) -> ElevationProfileOutputPlugin {
+        ElevationProfileOutputPlugin {
+            distance_unit: conf.distance_unit.unwrap_or_default(),
+            elevation_unit: conf.elevation_unit.unwrap_or(DistanceUnit::Feet),
+        }
+    }
+
+    /// computes the (distance, elevation) samples along a route in the configured units.
+    pub fn create_profile(
+        &self,
+        route: &[EdgeTraversal],
+        state_model: &StateModel,
+        start_elevation: f64,
+    ) -> Result<Vec<(f64, f64)>, OutputPluginError> {
+        let mut distance = Length::ZERO;
+        let mut elevation = self.elevation_unit.to_uom(start_elevation);
+        let mut profile = Vec::with_capacity(route.len() + 1);
+        profile.push((0.0, start_elevation));
+        for et in route.iter() {
+            let edge_distance = state_model
+                .get_distance(&et.result_state, fieldname::EDGE_DISTANCE)
+                .map_err(|e| profile_error(fieldname::EDGE_DISTANCE, e))?;
+            let edge_grade = state_model
+                .get_ratio(&et.result_state, fieldname::EDGE_GRADE)
+                .map_err(|e| profile_error(fieldname::EDGE_GRADE, e))?;
+            distance += edge_distance;
+            elevation += edge_distance * edge_grade;
+            profile.push((
+                self.distance_unit.from_uom(distance),
+                self.elevation_unit.from_uom(elevation),
+            ));
+        }
+        Ok(profile)
+    }
+}
+
+impl OutputPlugin for ElevationProfileOutputPlugin {
+    fn name(&self) -> &str {
+        "elevation_profile"
+    }
+
+    fn process(
+        &self,
+        output: &mut serde_json::Value,
+        search_result: &Result<(SearchAppResult, SearchInstance), CompassAppError>,
+    ) -> Result<(), OutputPluginError> {
+        let (result, si) = match search_result {
+            Err(_) => return Ok(()),
+            Ok((result, si)) => (result, si),
+        };
+        let start_elevation = output
+            .get("request")
+            .and_then(|r| r.get("start_elevation"))
+            .and_then(|e| e.as_f64())
+            .unwrap_or_default();
+
+        let profiles = result
+            .routes
+            .iter()
+            .map(|route| {
+                let profile = self.create_profile(route, &si.state_model, start_elevation)?;
+                Ok(json!({
+                    "distance_unit": self.distance_unit,
+                    "elevation_unit": self.elevation_unit,
+                    "profile": profile,
+                }))
+            })
+            .collect::<Result<Vec<_>, OutputPluginError>>()?;
+
+        output["elevation_profile"] = match profiles.as_slice() {
+            [] => serde_json::Value::Null,
+            [profile] => profile.to_owned(),
+            _ => json![profiles],
+        };
+        Ok(())
+    }
+}
+
+fn profile_error(feature: &str, error: impl std::fmt::Display) -> OutputPluginError {
+    OutputPluginError::OutputPluginFailed(format!(
+        "elevation profile requires state variable '{feature}', is a grade traversal model configured? {error}"
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use routee_compass_core::model::{
+        cost::TraversalCost,
+        network::{EdgeId, EdgeListId},
+        state::{StateVariable, StateVariableConfig},
+        unit::RatioUnit,
+    };
+    use uom::si::f64::Ratio;
+
+    fn mock_state_model() -> StateModel {
+        StateModel::new(vec![
+            (
+                String::from(fieldname::EDGE_DISTANCE),
+                StateVariableConfig::Distance {
+                    initial: Length::ZERO,
+                    accumulator: false,
+                    output_unit: None,
+                },
+            ),
+            (
+                String::from(fieldname::EDGE_GRADE),
+                StateVariableConfig::Ratio {
+                    initial: Ratio::ZERO,
+                    accumulator: false,
+                    output_unit: None,
+                },
+            ),
+        ])
+    }
+
+    fn mock_edge(
+        state_model: &StateModel,
+        edge_id: usize,
+        meters: f64,
+        grade: f64,
+    ) -> EdgeTraversal {
+        let mut state = state_model
+            .initial_state(None)
+            .expect("test invariant failed");
+        state_model
+            .set_distance(
+                &mut state,
+                fieldname::EDGE_DISTANCE,
+                &DistanceUnit::Meters.to_uom(meters),
+            )
+            .expect("test invariant failed");
+        state_model
+            .set_ratio(
+                &mut state,
+                fieldname::EDGE_GRADE,
+                &RatioUnit::Decimal.to_uom(grade),
+            )
+            .expect("test invariant failed");
+        EdgeTraversal {
+            edge_list_id: EdgeListId(0),
+            edge_id: EdgeId(edge_id),
+            cost: TraversalCost::default(),
+            result_state: state,
+        }
+    }
+
+    #[test]
+    fn test_create_profile() {
+        let state_model = mock_state_model();
+        let route = vec![
+            mock_edge(&state_model, 0, 100.0, 0.05),
+            mock_edge(&state_model, 1, 200.0, -0.02),
+        ];
+        let plugin = ElevationProfileOutputPlugin {
+            distance_unit: DistanceUnit::Meters,
+            elevation_unit: DistanceUnit::Meters,
+        };
+        let profile = plugin
+            .create_profile(&route, &state_model, 1000.0)
+            .expect("profile failed");
+        let expected = [(0.0, 1000.0), (100.0, 1005.0), (300.0, 1001.0)];
+        assert_eq!(profile.len(), expected.len());
+        for ((d, e), (d_exp, e_exp)) in profile.iter().zip(expected.iter()) {
+            assert!((d - d_exp).abs() < 1e-6, "distance {d} != {d_exp}");
+            assert!((e - e_exp).abs() < 1e-6, "elevation {e} != {e_exp}");
+        }
+    }
+
+    #[test]
+    fn test_create_profile_missing_grade() {
+        let state_model = StateModel::new(vec![]);
+        let route = vec![EdgeTraversal {
+            edge_list_id: EdgeListId(0),
+            edge_id: EdgeId(0),
+            cost: TraversalCost::default(),
+            result_state: vec![StateVariable::ZERO],
+        }];
+        let plugin = ElevationProfileOutputPlugin::new(ElevationProfileConfig {
+            distance_unit: None,
+            elevation_unit: None,
+        });
+        assert!(plugin.create_profile(&route, &state_model, 0.0).is_err());
+    }
+}
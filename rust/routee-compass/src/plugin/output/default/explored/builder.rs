@@ -0,0 +1,38 @@
+use super::plugin::ExploredOutputPlugin;
+use crate::{
+    app::compass::CompassComponentError,
+    plugin::{
+        output::{default::explored::ExploredConfig, OutputPlugin, OutputPluginBuilder},
+        PluginError,
+    },
+};
+use std::sync::Arc;
+
+/// Builds a plugin that reports the labels settled by each search, for queries that
+/// set `record_settled = true`, as a GeoJSON heatmap and optionally as rows of a CSV file.
+///
+/// # Example Configuration
+///
+/// ```toml
+/// [[plugin.output_plugins]]
+/// type = "explored"
+/// output_file = "explored.csv"
+/// heatmap = true
+/// ```
+pub struct ExploredOutputPluginBuilder {}
+
+impl OutputPluginBuilder for ExploredOutputPluginBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn OutputPlugin>, CompassComponentError> {
+        let conf: ExploredConfig = serde_json::from_value(parameters.clone()).map_err(|e| {
+            PluginError::BuildFailed(format!(
+                "failure reading explored output plugin config: {e}"
+            ))
+        })?;
+        let plugin = ExploredOutputPlugin::new(conf)
+            .map_err(|e| PluginError::OutputPluginFailed { source: e })?;
+        Ok(Arc::new(plugin))
+    }
+}
@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct ExploredConfig {
+    /// if provided, settled labels of every query are appended to this CSV file
+    pub output_file: Option<String>,
+    /// include a GeoJSON heatmap of the settled vertices in each response. defaults to true.
+    pub heatmap: Option<bool>,
+}
@@ -0,0 +1,7 @@
+mod builder;
+mod config;
+mod plugin;
+
+pub use builder::ExploredOutputPluginBuilder;
+pub use config::ExploredConfig;
+pub use plugin::ExploredOutputPlugin;
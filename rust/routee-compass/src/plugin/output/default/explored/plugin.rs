@@ -0,0 +1,218 @@
+use crate::app::{compass::CompassAppError, search::SearchAppResult};
+use crate::plugin::output::default::explored::ExploredConfig;
+use crate::plugin::output::output_plugin::OutputPlugin;
+use crate::plugin::output::OutputPluginError;
+use routee_compass_core::algorithm::search::{SearchInstance, SettledLabel};
+use routee_compass_core::model::network::VertexId;
+use routee_compass_core::model::unit::AsF64;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fs::File;
+use std::sync::Mutex;
+
+/// reports the labels settled by a search, in the order they were settled, for
+/// debugging heuristic quality and constraint behavior. only queries that set
+/// `record_settled = true` record their settled labels.
+///
+/// each response gets an `explored` object with the number of labels settled and
+/// a GeoJSON heatmap with one point per settled vertex. if an output file is
+/// configured, every settled label is also appended to it as a CSV row.
+pub struct ExploredOutputPlugin {
+    pub heatmap: bool,
+    pub writer: Option<Mutex<csv::Writer<File>>>,
+}
+
+impl ExploredOutputPlugin {
+    pub fn new(conf: ExploredConfig) -> Result<ExploredOutputPlugin, OutputPluginError> {
+        let writer = match &conf.output_file {
+            None => None,
+            Some(output_file) => {
+                let mut writer = csv::Writer::from_path(output_file).map_err(|e| {
+                    OutputPluginError::BuildFailed(format!(
+                        "failure creating explored output file {output_file}: {e}"
+                    ))
+                })?;
+                writer
+                    .write_record([
+                        "query_id",
+                        "order",
+                        "vertex_id",
+                        "x",
+                        "y",
+                        "cost",
+                        "priority",
+                    ])
+                    .map_err(|e| OutputPluginError::BuildFailed(e.to_string()))?;
+                Some(Mutex::new(writer))
+            }
+        };
+        Ok(ExploredOutputPlugin {
+            heatmap: conf.heatmap.unwrap_or(true),
+            writer,
+        })
+    }
+
+    /// appends the settled labels of a query to the output file. rows are tagged with
+    /// the `request_id` or `input_index` of the request, if present.
+    fn write_rows(
+        &self,
+        writer: &Mutex<csv::Writer<File>>,
+        request: &Value,
+        settled: &[SettledLabel],
+        si: &SearchInstance,
+    ) -> Result<(), OutputPluginError> {
+        let query_id = ["request_id", "input_index"]
+            .iter()
+            .find_map(|key| request.get(key))
+            .map(|v| match v {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            })
+            .unwrap_or_default();
+        let mut writer = writer.lock().map_err(|e| {
+            OutputPluginError::InternalError(format!("explored output file lock poisoned: {e}"))
+        })?;
+        for (order, label) in settled.iter().enumerate() {
+            let vertex = si.graph.get_vertex(&label.vertex_id).map_err(|e| {
+                OutputPluginError::OutputPluginFailed(format!("explored vertex not found: {e}"))
+            })?;
+            writer
+                .write_record([
+                    query_id.clone(),
+                    order.to_string(),
+                    label.vertex_id.to_string(),
+                    vertex.x().to_string(),
+                    vertex.y().to_string(),
+                    label.cost.as_f64().to_string(),
+                    label.priority.as_f64().to_string(),
+                ])
+                .map_err(|e| OutputPluginError::OutputPluginFailed(e.to_string()))?;
+        }
+        writer
+            .flush()
+            .map_err(|e| OutputPluginError::OutputPluginFailed(e.to_string()))
+    }
+}
+
+/// builds a GeoJSON FeatureCollection with one point per settled vertex. each point has
+/// the `order` the vertex was first settled, its lowest settled `cost` and the `count`
+/// of labels settled at the vertex, which may be more than one with a state-dependent
+/// label model.
+pub fn heatmap(settled: &[SettledLabel], si: &SearchInstance) -> Result<Value, OutputPluginError> {
+    let mut vertices: Vec<(VertexId, usize, f64, usize)> = vec![];
+    let mut index: HashMap<VertexId, usize> = HashMap::new();
+    for (order, label) in settled.iter().enumerate() {
+        let cost = label.cost.as_f64();
+        match index.get(&label.vertex_id) {
+            Some(idx) => {
+                let (_, _, min_cost, count) = &mut vertices[*idx];
+                *min_cost = min_cost.min(cost);
+                *count += 1;
+            }
+            None => {
+                index.insert(label.vertex_id, vertices.len());
+                vertices.push((label.vertex_id, order, cost, 1));
+            }
+        }
+    }
+    let features = vertices
+        .into_iter()
+        .map(|(vertex_id, order, cost, count)| {
+            let vertex = si.graph.get_vertex(&vertex_id).map_err(|e| {
+                OutputPluginError::OutputPluginFailed(format!("explored vertex not found: {e}"))
+            })?;
+            Ok(json!({
+                "type": "Feature",
+                "geometry": {"type": "Point", "coordinates": [vertex.x(), vertex.y()]},
+                "properties": {
+                    "vertex_id": vertex_id,
+                    "order": order,
+                    "cost": cost,
+                    "count": count
+                }
+            }))
+        })
+        .collect::<Result<Vec<_>, OutputPluginError>>()?;
+    Ok(json!({"type": "FeatureCollection", "features": features}))
+}
+
+impl OutputPlugin for ExploredOutputPlugin {
+    fn name(&self) -> &str {
+        "explored"
+    }
+
+    fn process(
+        &self,
+        output: &mut Value,
+        search_result: &Result<(SearchAppResult, SearchInstance), CompassAppError>,
+    ) -> Result<(), OutputPluginError> {
+        let (result, si) = match search_result {
+            Err(_) => return Ok(()),
+            Ok((result, si)) if si.record_settled => (result, si),
+            Ok(_) => return Ok(()),
+        };
+        if let Some(writer) = &self.writer {
+            self.write_rows(writer, &output["request"], &result.settled, si)?;
+        }
+        let mut explored = json!({"settled_count": result.settled.len()});
+        if self.heatmap {
+            explored["heatmap"] = heatmap(&result.settled, si)?;
+        }
+        output["explored"] = explored;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::compass::compass_app_test_ops::speeds_test_app;
+
+    #[test]
+    fn test_record_settled() {
+        let output_dir = tempfile::tempdir().unwrap();
+        let output_file = output_dir.path().join("explored.csv");
+        let app = speeds_test_app(&format!(
+            "[[plugin.output_plugins]]\ntype = \"explored\"\noutput_file = {:?}",
+            output_file.to_str().unwrap()
+        ));
+
+        let mut queries = vec![
+            serde_json::json!({
+                "origin_vertex": 0,
+                "destination_vertex": 2,
+                "request_id": "recorded",
+                "record_settled": true
+            }),
+            serde_json::json!({
+                "origin_vertex": 0,
+                "destination_vertex": 2,
+                "request_id": "not_recorded"
+            }),
+        ];
+        let result = app.run(&mut queries, None).expect("run failed");
+        let recorded = result
+            .iter()
+            .find(|r| r["request"]["request_id"] == "recorded")
+            .unwrap();
+        let not_recorded = result
+            .iter()
+            .find(|r| r["request"]["request_id"] == "not_recorded")
+            .unwrap();
+        assert!(not_recorded.get("explored").is_none());
+
+        let explored = &recorded["explored"];
+        let settled_count = explored["settled_count"].as_u64().unwrap();
+        assert!(settled_count > 0, "{explored}");
+        let features = explored["heatmap"]["features"].as_array().unwrap();
+        assert_eq!(features[0]["properties"]["vertex_id"], serde_json::json!(0));
+        assert_eq!(features[0]["properties"]["order"], serde_json::json!(0));
+
+        let csv = std::fs::read_to_string(&output_file).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("query_id,order,vertex_id,x,y,cost,priority")
+        );
+        assert_eq!(lines.count() as u64, settled_count);
+    }
+}
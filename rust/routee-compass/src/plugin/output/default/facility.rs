@@ -0,0 +1,44 @@
+use crate::plugin::output::OutputPluginError;
+use routee_compass_core::util::fs::read_utils;
+use serde::Deserialize;
+
+/// a point of interest read from a facilities file, such as a rest area or charger
+#[derive(Deserialize, Clone, Debug)]
+pub struct Facility {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub category: Option<String>,
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Facility {
+    /// reads a CSV of facilities with WGS84 `x` and `y` columns and optional `name` and
+    /// `category` columns. if categories are provided, only facilities with one of these
+    /// categories are kept.
+    pub fn read_file(
+        facilities_input_file: &str,
+        categories: Option<&[String]>,
+    ) -> Result<Vec<Facility>, OutputPluginError> {
+        let rows: Box<[Facility]> = read_utils::from_csv(&facilities_input_file, true, None, None)
+            .map_err(|e| {
+                OutputPluginError::BuildFailed(format!(
+                    "failure reading facilities file {facilities_input_file}: {e}"
+                ))
+            })?;
+        let facilities = rows
+            .into_vec()
+            .into_iter()
+            .filter(|f| match (categories, &f.category) {
+                (None, _) => true,
+                (Some(categories), Some(category)) => categories.contains(category),
+                (Some(_), None) => false,
+            })
+            .collect::<Vec<_>>();
+        if facilities.is_empty() {
+            log::warn!("no facilities found in {facilities_input_file}");
+        }
+        Ok(facilities)
+    }
+}
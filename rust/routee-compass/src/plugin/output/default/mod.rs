@@ -1,3 +1,15 @@
+pub mod accessibility;
+pub mod cost_surface;
+pub mod dataset_version;
+pub mod elevation_profile;
+pub mod explored;
+pub mod facility;
+pub mod response_metadata;
+pub mod rest_stops;
+pub mod route_explanation;
+pub mod route_facilities;
+pub mod segment_summary;
 pub mod summary;
 pub mod traversal;
 pub mod uuid;
+pub mod vector_tile;
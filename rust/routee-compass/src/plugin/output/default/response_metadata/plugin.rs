@@ -0,0 +1,97 @@
+use crate::app::compass::response::response_metadata::ResponseMetadataConfig;
+use crate::app::compass::CompassAppError;
+use crate::app::search::SearchAppResult;
+use crate::plugin::output::{OutputPlugin, OutputPluginError};
+use routee_compass_core::algorithm::search::SearchInstance;
+use serde_json::{json, Value};
+
+/// adds a `metadata` object to each response describing the app that produced it. added
+/// by the app, after all other output plugins, when `[system.response_metadata]` is set.
+pub struct ResponseMetadataOutputPlugin {
+    /// metadata shared by every response of the app
+    pub metadata: Value,
+    pub include_timing: bool,
+}
+
+impl ResponseMetadataOutputPlugin {
+    /// # Arguments
+    ///
+    /// * `config` - response metadata configuration
+    /// * `config_hash` - hash of the configuration the app was built from
+    /// * `dataset_version` - version of the dataset manifest, if configured
+    /// * `search_algorithm` - search algorithm configuration
+    pub fn new(
+        config: &ResponseMetadataConfig,
+        config_hash: String,
+        dataset_version: Option<String>,
+        search_algorithm: Value,
+    ) -> ResponseMetadataOutputPlugin {
+        let metadata = json!({
+            "compass_version": env!("CARGO_PKG_VERSION"),
+            "config_hash": config_hash,
+            "dataset_version": dataset_version,
+            "search_algorithm": search_algorithm,
+        });
+        ResponseMetadataOutputPlugin {
+            metadata,
+            include_timing: config.include_timing(),
+        }
+    }
+}
+
+impl OutputPlugin for ResponseMetadataOutputPlugin {
+    fn process(
+        &self,
+        output: &mut Value,
+        result: &Result<(SearchAppResult, SearchInstance), CompassAppError>,
+    ) -> Result<(), OutputPluginError> {
+        let mut metadata = self.metadata.clone();
+        if let (true, Ok((result, _))) = (self.include_timing, result) {
+            // the output plugins began when the initial output was created
+            let output_plugins_secs = output
+                .get("output_plugin_executed_time")
+                .and_then(Value::as_str)
+                .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+                .map(|start| {
+                    let elapsed = chrono::Local::now().fixed_offset() - start;
+                    elapsed.num_microseconds().unwrap_or_default() as f64 / 1e6
+                });
+            metadata["timing"] = json!({
+                "search_runtime_secs": result.search_runtime.as_secs_f64(),
+                "output_plugins_secs": output_plugins_secs,
+            });
+        }
+        output[ResponseMetadataConfig::METADATA_KEY] = metadata;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::compass::compass_app_test_ops::{build_app, speeds_test_config};
+
+    #[test]
+    fn test_response_metadata() {
+        let read_config = || speeds_test_config("[system.response_metadata]\ntiming = true");
+        let config = read_config();
+        // the cost weights are read into a HashMap, whose order varies between instances
+        assert_eq!(config.hash().unwrap(), read_config().hash().unwrap());
+
+        let app = build_app(&config);
+        let mut queries = vec![serde_json::json!({"origin_vertex": 0, "destination_vertex": 2})];
+        let responses = app.run(&mut queries, None).expect("run failed");
+        let metadata = &responses[0]["metadata"];
+        assert_eq!(metadata["compass_version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(metadata["config_hash"], config.hash().unwrap());
+        assert_eq!(metadata["dataset_version"], serde_json::Value::Null);
+        assert_eq!(metadata["search_algorithm"]["type"], "a*");
+        assert!(
+            metadata["timing"]["search_runtime_secs"].is_f64(),
+            "{metadata}"
+        );
+        assert!(
+            metadata["timing"]["output_plugins_secs"].is_f64(),
+            "{metadata}"
+        );
+    }
+}
@@ -0,0 +1,42 @@
+use super::plugin::RestStopsOutputPlugin;
+use crate::{
+    app::compass::CompassComponentError,
+    plugin::{
+        output::{default::rest_stops::RestStopsConfig, OutputPlugin, OutputPluginBuilder},
+        PluginError,
+    },
+};
+use std::sync::Arc;
+
+/// Builds a plugin that suggests stops, such as breaks or charging, at the nearest
+/// facility each time a route passes a trip time or trip distance interval.
+///
+/// # Example Configuration
+///
+/// ```toml
+/// [[plugin.output_plugins]]
+/// type = "rest_stops"
+/// name = "break"
+/// facilities_input_file = "rest-areas.csv"
+/// time_interval = 4.0
+/// time_unit = "hours"
+/// max_facility_distance = 10.0
+/// distance_unit = "miles"
+/// ```
+pub struct RestStopsOutputPluginBuilder {}
+
+impl OutputPluginBuilder for RestStopsOutputPluginBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn OutputPlugin>, CompassComponentError> {
+        let conf: RestStopsConfig = serde_json::from_value(parameters.clone()).map_err(|e| {
+            PluginError::BuildFailed(format!(
+                "failure reading rest stops output plugin config: {e}"
+            ))
+        })?;
+        let plugin = RestStopsOutputPlugin::new(conf)
+            .map_err(|e| PluginError::OutputPluginFailed { source: e })?;
+        Ok(Arc::new(plugin))
+    }
+}
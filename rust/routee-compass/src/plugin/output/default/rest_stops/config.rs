@@ -0,0 +1,23 @@
+use routee_compass_core::model::unit::{DistanceUnit, TimeUnit};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RestStopsConfig {
+    /// name of this kind of stop, used as the output key, such as `break` or `charging`
+    pub name: String,
+    /// CSV file of facilities with WGS84 `x` and `y` columns and optional `name` and
+    /// `category` columns
+    pub facilities_input_file: String,
+    /// if provided, only facilities with one of these categories are suggested
+    pub categories: Option<Vec<String>>,
+    /// suggests a stop before this much trip time has passed since the last stop
+    pub time_interval: Option<f64>,
+    /// unit of the time interval and the times in the output. defaults to hours.
+    pub time_unit: Option<TimeUnit>,
+    /// suggests a stop before this much trip distance has passed since the last stop
+    pub distance_interval: Option<f64>,
+    /// unit of the distance interval and the distances in the output. defaults to miles.
+    pub distance_unit: Option<DistanceUnit>,
+    /// facilities farther than this from the route are not suggested. defaults to no limit.
+    pub max_facility_distance: Option<f64>,
+}
@@ -0,0 +1,7 @@
+mod builder;
+mod config;
+mod plugin;
+
+pub use builder::RestStopsOutputPluginBuilder;
+pub use config::RestStopsConfig;
+pub use plugin::RestStopsOutputPlugin;
@@ -0,0 +1,439 @@
+use crate::app::{compass::CompassAppError, search::SearchAppResult};
+use crate::plugin::output::default::{facility::Facility, rest_stops::RestStopsConfig};
+use crate::plugin::output::output_plugin::OutputPlugin;
+use crate::plugin::output::OutputPluginError;
+use geo::{coord, Coord};
+use routee_compass_core::algorithm::search::{EdgeTraversal, SearchInstance};
+use routee_compass_core::model::{
+    state::StateModel,
+    traversal::default::fieldname,
+    unit::{DistanceUnit, TimeUnit},
+};
+use routee_compass_core::util::geo::haversine;
+use serde_json::{json, Value};
+use uom::si::f64::{Length, Time};
+use uom::ConstZero;
+
+/// suggests stops along each route, such as breaks or charging, each time the route
+/// passes a trip time or trip distance interval since the previous stop. each stop is
+/// placed at the last vertex before the interval is exceeded and is matched to the
+/// nearest facility from a file of points of interest. the detour to a facility is
+/// estimated as the straight-line distance there and back, and its time from the
+/// average speed of the route up to the stop. stops are written under
+/// `rest_stops.<name>`.
+pub struct RestStopsOutputPlugin {
+    pub name: String,
+    pub facilities: Vec<Facility>,
+    pub time_interval: Option<Time>,
+    pub distance_interval: Option<Length>,
+    pub time_unit: TimeUnit,
+    pub distance_unit: DistanceUnit,
+    pub max_facility_distance: Option<Length>,
+}
+
+/// the trip time and trip distance at the end of an edge of a route, for the trip
+/// state variables found in the state model
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RouteProgress {
+    pub trip_time: Option<Time>,
+    pub trip_distance: Option<Length>,
+}
+
+impl RestStopsOutputPlugin {
+    pub fn new(conf: RestStopsConfig) -> Result<RestStopsOutputPlugin, OutputPluginError> {
+        let time_unit = conf.time_unit.unwrap_or(TimeUnit::Hours);
+        let distance_unit = conf.distance_unit.unwrap_or(DistanceUnit::Miles);
+        let positive = |name: &str, value: Option<f64>| match value {
+            Some(v) if !(v.is_finite() && v > 0.0) => Err(OutputPluginError::BuildFailed(format!(
+                "rest stops {name} must be positive, found {v}"
+            ))),
+            _ => Ok(value),
+        };
+        let time_interval = positive("time_interval", conf.time_interval)?;
+        let distance_interval = positive("distance_interval", conf.distance_interval)?;
+        if time_interval.is_none() && distance_interval.is_none() {
+            return Err(OutputPluginError::BuildFailed(format!(
+                "rest stops '{}' requires a time_interval, a distance_interval, or both",
+                conf.name
+            )));
+        }
+        let max_facility_distance = positive("max_facility_distance", conf.max_facility_distance)?;
+
+        let facilities =
+            Facility::read_file(&conf.facilities_input_file, conf.categories.as_deref())?;
+        Ok(RestStopsOutputPlugin {
+            name: conf.name,
+            facilities,
+            time_interval: time_interval.map(|t| time_unit.to_uom(t)),
+            distance_interval: distance_interval.map(|d| distance_unit.to_uom(d)),
+            time_unit,
+            distance_unit,
+            max_facility_distance: max_facility_distance.map(|d| distance_unit.to_uom(d)),
+        })
+    }
+
+    /// finds the route edges at the end of which a stop is suggested. a stop goes at the
+    /// end of the last edge that stays within the intervals since the previous stop, or
+    /// at the end of an edge that exceeds an interval by itself, unless that edge ends
+    /// the route.
+    pub fn plan_stops(
+        &self,
+        route: &[EdgeTraversal],
+        state_model: &StateModel,
+    ) -> Result<Vec<(usize, RouteProgress)>, OutputPluginError> {
+        let trip_time = String::from(fieldname::TRIP_TIME);
+        let trip_distance = String::from(fieldname::TRIP_DISTANCE);
+        let has_time = state_model.contains_key(&trip_time);
+        let has_distance = state_model.contains_key(&trip_distance);
+        for (interval, has_feature, feature) in [
+            (self.time_interval.is_some(), has_time, &trip_time),
+            (
+                self.distance_interval.is_some(),
+                has_distance,
+                &trip_distance,
+            ),
+        ] {
+            if interval && !has_feature {
+                return Err(OutputPluginError::OutputPluginFailed(format!(
+                    "rest stops '{}' interval requires the '{feature}' state variable",
+                    self.name
+                )));
+            }
+        }
+        let progress = |edge: &EdgeTraversal| -> Result<RouteProgress, OutputPluginError> {
+            let failed = |e: routee_compass_core::model::state::StateModelError| {
+                OutputPluginError::OutputPluginFailed(e.to_string())
+            };
+            Ok(RouteProgress {
+                trip_time: match has_time {
+                    true => Some(
+                        state_model
+                            .get_time(&edge.result_state, &trip_time)
+                            .map_err(failed)?,
+                    ),
+                    false => None,
+                },
+                trip_distance: match has_distance {
+                    true => Some(
+                        state_model
+                            .get_distance(&edge.result_state, &trip_distance)
+                            .map_err(failed)?,
+                    ),
+                    false => None,
+                },
+            })
+        };
+        let exceeds = |at: &RouteProgress, since: &RouteProgress| {
+            let time = match (self.time_interval, at.trip_time, since.trip_time) {
+                (Some(interval), Some(a), Some(s)) => a - s > interval,
+                _ => false,
+            };
+            let distance = match (
+                self.distance_interval,
+                at.trip_distance,
+                since.trip_distance,
+            ) {
+                (Some(interval), Some(a), Some(s)) => a - s > interval,
+                _ => false,
+            };
+            time || distance
+        };
+
+        let mut stops = vec![];
+        let mut last_stop = RouteProgress {
+            trip_time: has_time.then_some(Time::ZERO),
+            trip_distance: has_distance.then_some(Length::ZERO),
+        };
+        // the end of the previous edge, if it comes after the last stop
+        let mut previous: Option<(usize, RouteProgress)> = None;
+        for (idx, edge) in route.iter().enumerate() {
+            let at = progress(edge)?;
+            if exceeds(&at, &last_stop) {
+                if let Some((previous_idx, previous_progress)) = previous.take() {
+                    stops.push((previous_idx, previous_progress));
+                    last_stop = previous_progress;
+                }
+                if exceeds(&at, &last_stop) && idx + 1 < route.len() {
+                    stops.push((idx, at));
+                    last_stop = at;
+                    continue;
+                }
+            }
+            previous = Some((idx, at));
+        }
+        Ok(stops)
+    }
+
+    /// the nearest facility to a location, within the maximum facility distance, along
+    /// with its distance
+    pub fn nearest_facility(
+        &self,
+        location: &Coord<f32>,
+    ) -> Result<Option<(&Facility, Length)>, OutputPluginError> {
+        let mut nearest: Option<(&Facility, Length)> = None;
+        for facility in self.facilities.iter() {
+            let distance =
+                haversine::coord_distance(location, &coord! {x: facility.x, y: facility.y})
+                    .map_err(OutputPluginError::OutputPluginFailed)?;
+            if self.max_facility_distance.is_some_and(|max| distance > max) {
+                continue;
+            }
+            if nearest.is_none_or(|(_, d)| distance < d) {
+                nearest = Some((facility, distance));
+            }
+        }
+        Ok(nearest)
+    }
+
+    /// describes each suggested stop along a route
+    fn suggest_stops(
+        &self,
+        route: &[EdgeTraversal],
+        si: &SearchInstance,
+    ) -> Result<Value, OutputPluginError> {
+        let stops = self
+            .plan_stops(route, &si.state_model)?
+            .into_iter()
+            .map(|(idx, progress)| {
+                let edge = &route[idx];
+                let vertex_id = si
+                    .graph
+                    .dst_vertex_id(&edge.edge_list_id, &edge.edge_id)
+                    .map_err(|e| OutputPluginError::OutputPluginFailed(e.to_string()))?;
+                let vertex = si
+                    .graph
+                    .get_vertex(&vertex_id)
+                    .map_err(|e| OutputPluginError::OutputPluginFailed(e.to_string()))?;
+                let mut stop = json!({
+                    "edge_index": idx,
+                    "vertex_id": vertex_id,
+                    "x": vertex.x(),
+                    "y": vertex.y(),
+                    "trip_time": progress.trip_time.map(|t| self.time_unit.from_uom(t)),
+                    "trip_distance": progress.trip_distance.map(|d| self.distance_unit.from_uom(d)),
+                    "facility": Value::Null,
+                });
+                if let Some((facility, distance)) = self.nearest_facility(&vertex.coordinate)? {
+                    let detour_distance = distance * 2.0;
+                    let detour_time = match (progress.trip_time, progress.trip_distance) {
+                        (Some(t), Some(d)) if t > Time::ZERO && d > Length::ZERO => {
+                            Some(self.time_unit.from_uom(detour_distance / (d / t)))
+                        }
+                        _ => None,
+                    };
+                    stop["facility"] = json!({
+                        "name": facility.name,
+                        "category": facility.category,
+                        "x": facility.x,
+                        "y": facility.y,
+                    });
+                    stop["detour_distance"] = json!(self.distance_unit.from_uom(detour_distance));
+                    stop["detour_time"] = json!(detour_time);
+                }
+                Ok(stop)
+            })
+            .collect::<Result<Vec<_>, OutputPluginError>>()?;
+        Ok(json!({
+            "time_unit": self.time_unit,
+            "distance_unit": self.distance_unit,
+            "stops": stops
+        }))
+    }
+}
+
+impl OutputPlugin for RestStopsOutputPlugin {
+    fn name(&self) -> &str {
+        "rest_stops"
+    }
+
+    fn process(
+        &self,
+        output: &mut Value,
+        search_result: &Result<(SearchAppResult, SearchInstance), CompassAppError>,
+    ) -> Result<(), OutputPluginError> {
+        let (result, si) = match search_result {
+            Err(_) => return Ok(()),
+            Ok((result, si)) => (result, si),
+        };
+        let suggestions = result
+            .routes
+            .iter()
+            .map(|route| self.suggest_stops(route, si))
+            .collect::<Result<Vec<_>, OutputPluginError>>()?;
+        let suggestion = match suggestions.as_slice() {
+            [] => Value::Null,
+            [suggestion] => suggestion.to_owned(),
+            _ => json![suggestions],
+        };
+        if !output["rest_stops"].is_object() {
+            output["rest_stops"] = json!({});
+        }
+        output["rest_stops"][&self.name] = suggestion;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::compass::compass_app_test_ops::speeds_test_app;
+    use routee_compass_core::model::{
+        cost::TraversalCost,
+        network::{EdgeId, EdgeListId},
+        state::StateVariableConfig,
+    };
+
+    fn mock_plugin(
+        time_interval: Option<f64>,
+        distance_interval: Option<f64>,
+    ) -> RestStopsOutputPlugin {
+        RestStopsOutputPlugin {
+            name: String::from("break"),
+            facilities: vec![
+                Facility {
+                    name: Some(String::from("near")),
+                    category: None,
+                    x: -105.0,
+                    y: 40.01,
+                },
+                Facility {
+                    name: Some(String::from("far")),
+                    category: None,
+                    x: -105.0,
+                    y: 40.5,
+                },
+            ],
+            time_interval: time_interval.map(|t| TimeUnit::Hours.to_uom(t)),
+            distance_interval: distance_interval.map(|d| DistanceUnit::Miles.to_uom(d)),
+            time_unit: TimeUnit::Hours,
+            distance_unit: DistanceUnit::Miles,
+            max_facility_distance: Some(DistanceUnit::Miles.to_uom(10.0)),
+        }
+    }
+
+    fn mock_state_model() -> StateModel {
+        StateModel::new(vec![
+            (
+                String::from(fieldname::TRIP_TIME),
+                StateVariableConfig::Time {
+                    initial: Time::ZERO,
+                    accumulator: true,
+                    output_unit: Some(TimeUnit::Hours),
+                },
+            ),
+            (
+                String::from(fieldname::TRIP_DISTANCE),
+                StateVariableConfig::Distance {
+                    initial: Length::ZERO,
+                    accumulator: true,
+                    output_unit: Some(DistanceUnit::Miles),
+                },
+            ),
+        ])
+    }
+
+    /// a route with edges ending at each of the given trip hours, at 50 miles per hour
+    fn mock_route(state_model: &StateModel, hours: &[f64]) -> Vec<EdgeTraversal> {
+        hours
+            .iter()
+            .enumerate()
+            .map(|(edge_id, h)| {
+                let mut state = state_model
+                    .initial_state(None)
+                    .expect("test invariant failed");
+                state_model
+                    .set_time(
+                        &mut state,
+                        fieldname::TRIP_TIME,
+                        &TimeUnit::Hours.to_uom(*h),
+                    )
+                    .expect("test invariant failed");
+                state_model
+                    .set_distance(
+                        &mut state,
+                        fieldname::TRIP_DISTANCE,
+                        &DistanceUnit::Miles.to_uom(h * 50.0),
+                    )
+                    .expect("test invariant failed");
+                EdgeTraversal {
+                    edge_list_id: EdgeListId(0),
+                    edge_id: EdgeId(edge_id),
+                    cost: TraversalCost::default(),
+                    result_state: state,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_plan_stops() {
+        let state_model = mock_state_model();
+        let route = mock_route(&state_model, &[1.0, 3.5, 4.5, 7.0, 12.0, 13.0, 18.0]);
+        let stop_edges = |plugin: &RestStopsOutputPlugin| {
+            plugin
+                .plan_stops(&route, &state_model)
+                .unwrap()
+                .into_iter()
+                .map(|(idx, _)| idx)
+                .collect::<Vec<_>>()
+        };
+        // stops before 4 hours pass, or at the end of an edge longer than 4 hours,
+        // but not at the end of the route
+        assert_eq!(stop_edges(&mock_plugin(Some(4.0), None)), vec![1, 3, 4, 5]);
+        // 150 miles is reached at 3 hours, before 4 hours
+        assert_eq!(
+            stop_edges(&mock_plugin(Some(4.0), Some(150.0))),
+            vec![0, 1, 2, 3, 4, 5]
+        );
+        let (_, progress) = mock_plugin(Some(4.0), None)
+            .plan_stops(&route, &state_model)
+            .unwrap()[0];
+        assert_eq!(progress.trip_time, Some(TimeUnit::Hours.to_uom(3.5)));
+
+        let no_distance = StateModel::new(vec![]);
+        assert!(mock_plugin(None, Some(150.0))
+            .plan_stops(&[], &no_distance)
+            .is_err());
+    }
+
+    #[test]
+    fn test_nearest_facility() {
+        let plugin = mock_plugin(Some(4.0), None);
+        let (facility, distance) = plugin
+            .nearest_facility(&coord! {x: -105.0, y: 40.0})
+            .unwrap()
+            .expect("should find a facility");
+        assert_eq!(facility.name.as_deref(), Some("near"));
+        assert!(DistanceUnit::Miles.from_uom(distance) < 1.0);
+        // both facilities are beyond the maximum facility distance
+        assert!(plugin
+            .nearest_facility(&coord! {x: -105.0, y: 41.0})
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_rest_stops() {
+        let dir = tempfile::tempdir().unwrap();
+        let facilities_file = dir.path().join("facilities.csv");
+        std::fs::write(
+            &facilities_file,
+            "name,x,y\nnear vertex 1,-104.8,41.1\nnear vertex 2,-111.9,40.7\n",
+        )
+        .unwrap();
+        let app = speeds_test_app(&format!(
+            "[[plugin.output_plugins]]\ntype = \"rest_stops\"\nname = \"break\"\nfacilities_input_file = {:?}\ntime_interval = 4.0",
+            facilities_file.to_str().unwrap(),
+        ));
+        let mut queries = vec![serde_json::json!({"origin_vertex": 0, "destination_vertex": 2})];
+        let responses = app.run(&mut queries, None).expect("run failed");
+        let rest_stops = &responses[0]["rest_stops"]["break"];
+        let stops = rest_stops["stops"].as_array().expect("missing stops");
+        // the route passes vertex 1 before 4 hours and arrives after 4 hours
+        assert_eq!(stops.len(), 1, "{rest_stops}");
+        assert_eq!(stops[0]["vertex_id"], 1);
+        assert_eq!(stops[0]["facility"]["name"], "near vertex 1");
+        assert!(stops[0]["detour_distance"].as_f64().unwrap() > 0.0);
+        assert!(stops[0]["detour_time"].as_f64().unwrap() > 0.0);
+    }
+}
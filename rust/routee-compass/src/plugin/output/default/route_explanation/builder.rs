@@ -0,0 +1,41 @@
+use super::plugin::RouteExplanationOutputPlugin;
+use crate::{
+    app::compass::CompassComponentError,
+    plugin::{
+        output::{
+            default::route_explanation::RouteExplanationConfig, OutputPlugin, OutputPluginBuilder,
+        },
+        PluginError,
+    },
+};
+use std::sync::Arc;
+
+/// Builds a plugin that explains each route by comparing it against baseline routes that
+/// each minimize a single state variable, such as the fastest and the shortest route.
+///
+/// # Example Configuration
+///
+/// ```toml
+/// [[plugin.output_plugins]]
+/// type = "route_explanation"
+/// baselines = ["trip_time", "trip_distance"]
+/// features = ["trip_time", "trip_distance", "trip_energy_electric"]
+/// ```
+pub struct RouteExplanationOutputPluginBuilder {}
+
+impl OutputPluginBuilder for RouteExplanationOutputPluginBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn OutputPlugin>, CompassComponentError> {
+        let conf: RouteExplanationConfig =
+            serde_json::from_value(parameters.clone()).map_err(|e| {
+                PluginError::BuildFailed(format!(
+                    "failure reading route explanation output plugin config: {e}"
+                ))
+            })?;
+        let plugin = RouteExplanationOutputPlugin::new(conf)
+            .map_err(|e| PluginError::OutputPluginFailed { source: e })?;
+        Ok(Arc::new(plugin))
+    }
+}
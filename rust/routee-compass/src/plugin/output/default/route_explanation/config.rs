@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RouteExplanationConfig {
+    /// state variables minimized by the baseline routes, one baseline route per state
+    /// variable. defaults to `trip_time` and `trip_distance`.
+    pub baselines: Option<Vec<String>>,
+    /// state variables compared between each route and the baseline routes. defaults to
+    /// every accumulator state variable, such as `trip_time` or `trip_energy`.
+    pub features: Option<Vec<String>>,
+}
@@ -0,0 +1,7 @@
+mod builder;
+mod config;
+mod plugin;
+
+pub use builder::RouteExplanationOutputPluginBuilder;
+pub use config::RouteExplanationConfig;
+pub use plugin::RouteExplanationOutputPlugin;
@@ -0,0 +1,337 @@
+use super::RouteExplanationConfig;
+use crate::app::{compass::CompassAppError, search::SearchAppResult};
+use crate::plugin::output::output_plugin::OutputPlugin;
+use crate::plugin::output::output_plugin_ops::route_total;
+use crate::plugin::output::OutputPluginError;
+use routee_compass_core::algorithm::search::{
+    a_star, Direction, EdgeTraversal, SearchInstance, SearchTreeStorage,
+};
+use routee_compass_core::model::{network::VertexId, state::StateVariableConfig};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// explains why a route was chosen by comparing it against baseline routes between the
+/// same origin and destination that each minimize a single state variable, such as the
+/// fastest and the shortest route.
+///
+/// each response gets a `route_explanation` object with the totals of the compared state
+/// variables over the `route` and, for each baseline, the baseline route totals, the
+/// `deltas` of the route relative to the baseline route and a `summary` sentence such as
+/// "compared to the trip_time-optimal route, this route is 4.0% slower and uses 12.0% less
+/// trip_energy".
+pub struct RouteExplanationOutputPlugin {
+    pub baselines: Vec<String>,
+    pub features: Option<Vec<String>>,
+}
+
+/// the totals of the compared state variables over a route, by name, along with their
+/// serialized values
+type RouteTotals = Vec<(String, Option<f64>, Value)>;
+
+impl RouteExplanationOutputPlugin {
+    pub fn new(
+        conf: RouteExplanationConfig,
+    ) -> Result<RouteExplanationOutputPlugin, OutputPluginError> {
+        let baselines = conf
+            .baselines
+            .unwrap_or_else(|| vec![String::from("trip_time"), String::from("trip_distance")]);
+        if baselines.is_empty() {
+            return Err(OutputPluginError::BuildFailed(String::from(
+                "route explanation requires at least one baseline",
+            )));
+        }
+        Ok(RouteExplanationOutputPlugin {
+            baselines,
+            features: conf.features,
+        })
+    }
+
+    /// the compared state variables, as (state index, name, feature)
+    fn compared_features<'a>(
+        &self,
+        si: &'a SearchInstance,
+    ) -> Result<Vec<(usize, &'a String, &'a StateVariableConfig)>, OutputPluginError> {
+        if let Some(names) = &self.features {
+            if let Some(missing) = names.iter().find(|n| !si.state_model.contains_key(n)) {
+                return Err(OutputPluginError::OutputPluginFailed(format!(
+                    "route explanation feature '{missing}' is not a state variable"
+                )));
+            }
+        }
+        Ok(si
+            .state_model
+            .indexed_iter()
+            .filter(|(_, (name, feature))| match &self.features {
+                Some(names) => names.contains(name),
+                None => feature.is_accumulator(),
+            })
+            .map(|(idx, (name, feature))| (idx, name, feature))
+            .collect())
+    }
+
+    /// finds the route from the source to the target vertex that minimizes a single state
+    /// variable, using the rates of the cost model of the query
+    fn baseline_route(
+        &self,
+        objective: &str,
+        source: VertexId,
+        target: VertexId,
+        si: &SearchInstance,
+    ) -> Result<Vec<EdgeTraversal>, OutputPluginError> {
+        let weights = HashMap::from([(objective.to_string(), 1.0)]);
+        let cost_model = si
+            .cost_model
+            .reweighted(weights, si.state_model.clone())
+            .map_err(|e| {
+                OutputPluginError::OutputPluginFailed(format!(
+                    "failure building cost model of the {objective}-optimal route: {e}"
+                ))
+            })?;
+        let baseline_si = SearchInstance {
+            cost_model: Arc::new(cost_model),
+            max_cost: None,
+            corridor: None,
+            record_settled: false,
+            ..si.clone()
+        };
+        // the cost estimates of the query may not suit the baseline objective, so the
+        // baseline routes are found with Dijkstra's algorithm
+        let result = a_star::run_vertex_oriented(
+            source,
+            Some(target),
+            &Direction::Forward,
+            false,
+            None,
+            SearchTreeStorage::Nodes,
+            &baseline_si,
+        )
+        .map_err(|e| {
+            OutputPluginError::OutputPluginFailed(format!(
+                "failure finding the {objective}-optimal route: {e}"
+            ))
+        })?;
+        result.tree.backtrack(target).map_err(|e| {
+            OutputPluginError::OutputPluginFailed(format!(
+                "failure finding the {objective}-optimal route: {e}"
+            ))
+        })
+    }
+
+    /// explains a route against the baseline routes between its first and last vertex
+    fn explain(
+        &self,
+        route: &[EdgeTraversal],
+        si: &SearchInstance,
+    ) -> Result<Value, OutputPluginError> {
+        let (Some(first), Some(last)) = (route.first(), route.last()) else {
+            return Ok(Value::Null);
+        };
+        let get_edge = |e: &EdgeTraversal| {
+            si.graph
+                .get_edge(&e.edge_list_id, &e.edge_id)
+                .map_err(|e| OutputPluginError::OutputPluginFailed(e.to_string()))
+        };
+        let source = get_edge(first)?.src_vertex_id;
+        let target = get_edge(last)?.dst_vertex_id;
+        let features = self.compared_features(si)?;
+        let route_totals = totals(route, &features)?;
+
+        let mut baselines = serde_json::Map::new();
+        for objective in self.baselines.iter() {
+            if !si.state_model.contains_key(objective) {
+                return Err(OutputPluginError::OutputPluginFailed(format!(
+                    "route explanation baseline '{objective}' is not a state variable"
+                )));
+            }
+            let baseline = self.baseline_route(objective, source, target, si)?;
+            let baseline_totals = totals(&baseline, &features)?;
+            let same_route = baseline.len() == route.len()
+                && baseline
+                    .iter()
+                    .zip(route.iter())
+                    .all(|(a, b)| a.edge_list_id == b.edge_list_id && a.edge_id == b.edge_id);
+            let deltas = route_totals
+                .iter()
+                .zip(baseline_totals.iter())
+                .map(|((name, value, _), (_, baseline_value, _))| {
+                    let difference = value.zip(*baseline_value).map(|(v, b)| v - b);
+                    let percent = value
+                        .zip(*baseline_value)
+                        .filter(|(_, b)| *b != 0.0)
+                        .map(|(v, b)| 100.0 * (v - b) / b);
+                    (name.clone(), difference, percent)
+                })
+                .collect::<Vec<_>>();
+            let summary = summarize(objective, same_route, &deltas);
+            baselines.insert(
+                objective.clone(),
+                json!({
+                    "route": totals_json(&baseline_totals),
+                    "same_route": same_route,
+                    "deltas": deltas
+                        .iter()
+                        .map(|(name, difference, percent)| {
+                            (name.clone(), json!({ "difference": difference, "percent": percent }))
+                        })
+                        .collect::<serde_json::Map<_, _>>(),
+                    "summary": summary,
+                }),
+            );
+        }
+        Ok(json!({
+            "route": totals_json(&route_totals),
+            "baselines": baselines,
+        }))
+    }
+}
+
+impl OutputPlugin for RouteExplanationOutputPlugin {
+    fn process(
+        &self,
+        output: &mut Value,
+        search_result: &Result<(SearchAppResult, SearchInstance), CompassAppError>,
+    ) -> Result<(), OutputPluginError> {
+        let (result, si) = match search_result {
+            Err(_) => return Ok(()),
+            Ok((result, si)) => (result, si),
+        };
+        let explanations = result
+            .routes
+            .iter()
+            .map(|route| self.explain(route, si))
+            .collect::<Result<Vec<_>, OutputPluginError>>()?;
+        output["route_explanation"] = match explanations.as_slice() {
+            [] => Value::Null,
+            [explanation] => explanation.to_owned(),
+            _ => json![explanations],
+        };
+        Ok(())
+    }
+}
+
+/// the totals of the compared state variables over a route
+fn totals(
+    route: &[EdgeTraversal],
+    features: &[(usize, &String, &StateVariableConfig)],
+) -> Result<RouteTotals, OutputPluginError> {
+    features
+        .iter()
+        .map(|(state_idx, name, feature)| {
+            let value = match route_total(route, *state_idx, feature) {
+                Some(total) => feature.serialize_variable(&total).map_err(|e| {
+                    OutputPluginError::OutputPluginFailed(format!(
+                        "failure serializing route explanation of '{name}': {e}"
+                    ))
+                })?,
+                None => Value::Null,
+            };
+            let unit = feature.get_unit_name();
+            Ok((
+                name.to_string(),
+                value.as_f64(),
+                json!({ "value": value, "unit": unit }),
+            ))
+        })
+        .collect()
+}
+
+fn totals_json(totals: &RouteTotals) -> Value {
+    totals
+        .iter()
+        .map(|(name, _, value)| (name.clone(), value.clone()))
+        .collect::<serde_json::Map<_, _>>()
+        .into()
+}
+
+/// describes the percent differences of a route from a baseline route in a sentence.
+/// differences below 0.05% are left out.
+fn summarize(
+    objective: &str,
+    same_route: bool,
+    deltas: &[(String, Option<f64>, Option<f64>)],
+) -> String {
+    if same_route {
+        return format!("this route is the {objective}-optimal route");
+    }
+    // each part is a verb and a phrase, where the verb is left out if it repeats
+    let mut parts: Vec<String> = vec![];
+    let mut previous_verb = "";
+    for (name, _, percent) in deltas.iter() {
+        let Some(p) = percent.filter(|p| p.abs() >= 0.05) else {
+            continue;
+        };
+        let (verb, phrase) = match (name.as_str(), p > 0.0) {
+            ("trip_time", true) => ("is", format!("{p:.1}% slower")),
+            ("trip_time", false) => ("is", format!("{:.1}% faster", -p)),
+            ("trip_distance", true) => ("is", format!("{p:.1}% longer")),
+            ("trip_distance", false) => ("is", format!("{:.1}% shorter", -p)),
+            (_, true) => ("uses", format!("{p:.1}% more {name}")),
+            (_, false) => ("uses", format!("{:.1}% less {name}", -p)),
+        };
+        match verb == previous_verb {
+            true => parts.push(phrase),
+            false => parts.push(format!("{verb} {phrase}")),
+        }
+        previous_verb = verb;
+    }
+    let description = match parts.as_slice() {
+        [] => String::from("has the same totals"),
+        [only] => only.clone(),
+        [init @ .., last] => format!("{} and {last}", init.join(", ")),
+    };
+    format!("compared to the {objective}-optimal route, this route {description}")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::compass::compass_app_test_ops::speeds_test_app;
+
+    #[test]
+    fn test_summarize() {
+        let deltas = vec![
+            (String::from("trip_time"), Some(2.0), Some(4.0)),
+            (String::from("trip_distance"), Some(0.0), Some(0.01)),
+            (String::from("trip_energy"), Some(-1.0), Some(-12.0)),
+        ];
+        assert_eq!(
+            summarize("trip_time", false, &deltas),
+            "compared to the trip_time-optimal route, this route is 4.0% slower and uses 12.0% less trip_energy"
+        );
+        assert_eq!(
+            summarize("trip_distance", true, &deltas),
+            "this route is the trip_distance-optimal route"
+        );
+    }
+
+    #[test]
+    fn test_route_explanation() {
+        let app = speeds_test_app("[[plugin.output_plugins]]\ntype = \"route_explanation\"");
+        let mut queries = vec![serde_json::json!({"origin_vertex": 0, "destination_vertex": 2})];
+        let responses = app.run(&mut queries, None).expect("run failed");
+        let explanation = &responses[0]["route_explanation"];
+        // the route minimizes trip time through vertex 1, while the shortest route is edge 1
+        let fastest = &explanation["baselines"]["trip_time"];
+        assert_eq!(fastest["same_route"], true, "{explanation}");
+        assert_eq!(fastest["deltas"]["trip_time"]["difference"], 0.0);
+        let shortest = &explanation["baselines"]["trip_distance"];
+        assert_eq!(shortest["same_route"], false, "{explanation}");
+        assert!(
+            shortest["deltas"]["trip_distance"]["percent"]
+                .as_f64()
+                .unwrap()
+                > 0.0
+        );
+        assert!(shortest["deltas"]["trip_time"]["percent"].as_f64().unwrap() < 0.0);
+        let summary = shortest["summary"].as_str().unwrap();
+        assert!(
+            summary.starts_with("compared to the trip_distance-optimal route, this route is"),
+            "{summary}"
+        );
+        assert!(
+            summary.contains("faster") && summary.contains("longer"),
+            "{summary}"
+        );
+    }
+}
@@ -0,0 +1,43 @@
+use super::plugin::RouteFacilitiesOutputPlugin;
+use crate::{
+    app::compass::CompassComponentError,
+    plugin::{
+        output::{
+            default::route_facilities::RouteFacilitiesConfig, OutputPlugin, OutputPluginBuilder,
+        },
+        PluginError,
+    },
+};
+use std::sync::Arc;
+
+/// Builds a plugin that finds the facilities within a buffer of each route, ordered by
+/// their distance along the route.
+///
+/// # Example Configuration
+///
+/// ```toml
+/// [[plugin.output_plugins]]
+/// type = "route_facilities"
+/// name = "chargers"
+/// facilities_input_file = "charging-stations.csv"
+/// buffer = 1.0
+/// distance_unit = "miles"
+/// ```
+pub struct RouteFacilitiesOutputPluginBuilder {}
+
+impl OutputPluginBuilder for RouteFacilitiesOutputPluginBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn OutputPlugin>, CompassComponentError> {
+        let conf: RouteFacilitiesConfig =
+            serde_json::from_value(parameters.clone()).map_err(|e| {
+                PluginError::BuildFailed(format!(
+                    "failure reading route facilities output plugin config: {e}"
+                ))
+            })?;
+        let plugin = RouteFacilitiesOutputPlugin::new(conf)
+            .map_err(|e| PluginError::OutputPluginFailed { source: e })?;
+        Ok(Arc::new(plugin))
+    }
+}
@@ -0,0 +1,17 @@
+use routee_compass_core::model::unit::DistanceUnit;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RouteFacilitiesConfig {
+    /// name of this kind of facility, used as the output key, such as `chargers`
+    pub name: String,
+    /// CSV file of facilities with WGS84 `x` and `y` columns and optional `name` and
+    /// `category` columns
+    pub facilities_input_file: String,
+    /// if provided, only facilities with one of these categories are found
+    pub categories: Option<Vec<String>>,
+    /// facilities within this distance of the route are found
+    pub buffer: f64,
+    /// unit of the buffer and the distances in the output. defaults to miles.
+    pub distance_unit: Option<DistanceUnit>,
+}
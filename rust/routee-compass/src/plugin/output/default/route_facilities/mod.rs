@@ -0,0 +1,7 @@
+mod builder;
+mod config;
+mod plugin;
+
+pub use builder::RouteFacilitiesOutputPluginBuilder;
+pub use config::RouteFacilitiesConfig;
+pub use plugin::RouteFacilitiesOutputPlugin;
@@ -0,0 +1,203 @@
+use crate::app::{compass::CompassAppError, search::SearchAppResult};
+use crate::plugin::output::default::{facility::Facility, route_facilities::RouteFacilitiesConfig};
+use crate::plugin::output::output_plugin::OutputPlugin;
+use crate::plugin::output::OutputPluginError;
+use geo::Point;
+use routee_compass_core::algorithm::search::{EdgeTraversal, SearchInstance};
+use routee_compass_core::model::{
+    network::{EdgeId, EdgeListId},
+    unit::DistanceUnit,
+};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use uom::si::f64::Length;
+use uom::ConstZero;
+
+/// finds the facilities from a file of points of interest within a buffer of each
+/// route, such as chargers within a mile of the route. each facility is snapped to the
+/// nearest route edge within the buffer using the map model spatial index, and the
+/// facilities are listed under `route_facilities.<name>` in order of their distance
+/// along the route.
+pub struct RouteFacilitiesOutputPlugin {
+    pub name: String,
+    pub facilities: Vec<Facility>,
+    pub buffer: Length,
+    pub distance_unit: DistanceUnit,
+}
+
+/// a facility snapped to a route
+struct RouteFacility<'a> {
+    facility: &'a Facility,
+    edge_index: usize,
+    along_route_distance: Length,
+    distance_from_route: Length,
+}
+
+impl RouteFacilitiesOutputPlugin {
+    pub fn new(
+        conf: RouteFacilitiesConfig,
+    ) -> Result<RouteFacilitiesOutputPlugin, OutputPluginError> {
+        if !(conf.buffer.is_finite() && conf.buffer > 0.0) {
+            return Err(OutputPluginError::BuildFailed(format!(
+                "route facilities buffer must be positive, found {}",
+                conf.buffer
+            )));
+        }
+        let distance_unit = conf.distance_unit.unwrap_or(DistanceUnit::Miles);
+        let facilities =
+            Facility::read_file(&conf.facilities_input_file, conf.categories.as_deref())?;
+        Ok(RouteFacilitiesOutputPlugin {
+            name: conf.name,
+            facilities,
+            buffer: distance_unit.to_uom(conf.buffer),
+            distance_unit,
+        })
+    }
+
+    /// snaps each facility to the nearest route edge within the buffer, ordered by
+    /// distance along the route
+    fn find_along_route(
+        &self,
+        route: &[EdgeTraversal],
+        si: &SearchInstance,
+    ) -> Result<Vec<RouteFacility<'_>>, OutputPluginError> {
+        // the route index and distance along the route at the start of each edge. edges
+        // visited more than once keep their first visit.
+        let mut route_edges: HashMap<(EdgeListId, EdgeId), (usize, Length, Length)> =
+            HashMap::new();
+        let mut along_route_distance = Length::ZERO;
+        for (idx, edge) in route.iter().enumerate() {
+            let edge_length = si
+                .graph
+                .get_edge(&edge.edge_list_id, &edge.edge_id)
+                .map_err(|e| OutputPluginError::OutputPluginFailed(e.to_string()))?
+                .distance;
+            route_edges
+                .entry((edge.edge_list_id, edge.edge_id))
+                .or_insert((idx, along_route_distance, edge_length));
+            along_route_distance += edge_length;
+        }
+
+        let mut found = vec![];
+        for facility in self.facilities.iter() {
+            let point = Point::new(facility.x, facility.y);
+            let candidates = si
+                .map_model
+                .edges_within(&point, self.buffer)
+                .map_err(|e| OutputPluginError::OutputPluginFailed(e.to_string()))?;
+            let on_route = candidates.into_iter().find_map(|c| {
+                route_edges
+                    .get(&(c.edge_list_id, c.edge_id))
+                    .map(|edge| (c, edge))
+            });
+            if let Some((candidate, (edge_index, start, edge_length))) = on_route {
+                found.push(RouteFacility {
+                    facility,
+                    edge_index: *edge_index,
+                    along_route_distance: *start + *edge_length * candidate.offset_fraction,
+                    distance_from_route: candidate.distance,
+                });
+            }
+        }
+        found.sort_by(|a, b| {
+            a.along_route_distance
+                .value
+                .total_cmp(&b.along_route_distance.value)
+        });
+        Ok(found)
+    }
+
+    fn describe(&self, found: &[RouteFacility]) -> Value {
+        let facilities = found
+            .iter()
+            .map(|f| {
+                json!({
+                    "name": f.facility.name,
+                    "category": f.facility.category,
+                    "x": f.facility.x,
+                    "y": f.facility.y,
+                    "edge_index": f.edge_index,
+                    "along_route_distance": self.distance_unit.from_uom(f.along_route_distance),
+                    "distance_from_route": self.distance_unit.from_uom(f.distance_from_route),
+                })
+            })
+            .collect::<Vec<_>>();
+        json!({
+            "distance_unit": self.distance_unit,
+            "facilities": facilities
+        })
+    }
+}
+
+impl OutputPlugin for RouteFacilitiesOutputPlugin {
+    fn name(&self) -> &str {
+        "route_facilities"
+    }
+
+    fn process(
+        &self,
+        output: &mut Value,
+        search_result: &Result<(SearchAppResult, SearchInstance), CompassAppError>,
+    ) -> Result<(), OutputPluginError> {
+        let (result, si) = match search_result {
+            Err(_) => return Ok(()),
+            Ok((result, si)) => (result, si),
+        };
+        let descriptions = result
+            .routes
+            .iter()
+            .map(|route| Ok(self.describe(&self.find_along_route(route, si)?)))
+            .collect::<Result<Vec<_>, OutputPluginError>>()?;
+        let description = match descriptions.as_slice() {
+            [] => Value::Null,
+            [description] => description.to_owned(),
+            _ => json![descriptions],
+        };
+        if !output["route_facilities"].is_object() {
+            output["route_facilities"] = json!({});
+        }
+        output["route_facilities"][&self.name] = description;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::compass::compass_app_test_ops::speeds_test_app;
+
+    #[test]
+    fn test_route_facilities() {
+        let dir = tempfile::tempdir().unwrap();
+        let facilities_file = dir.path().join("facilities.csv");
+        // the route from vertex 0 to 2 uses edge 0 from vertex 0 to 1, then edge 2 from
+        // vertex 1 to 2. edge 1 from vertex 0 to 2 is not on the route.
+        std::fs::write(
+            &facilities_file,
+            "name,x,y\nmiddle of edge 2,-108.36,40.96\nnear vertex 1,-104.82,41.15\nmiddle of edge 1,-108.54,40.25\nfar away,-100.0,35.0\n",
+        )
+        .unwrap();
+        let app = speeds_test_app(&format!(
+            "[[plugin.output_plugins]]\ntype = \"route_facilities\"\nname = \"chargers\"\nfacilities_input_file = {:?}\nbuffer = 1.0",
+            facilities_file.to_str().unwrap(),
+        ));
+        let mut queries = vec![serde_json::json!({"origin_vertex": 0, "destination_vertex": 2})];
+        let responses = app.run(&mut queries, None).expect("run failed");
+        let chargers = &responses[0]["route_facilities"]["chargers"];
+        let facilities = chargers["facilities"]
+            .as_array()
+            .expect("missing facilities");
+        let names = facilities
+            .iter()
+            .map(|f| f["name"].as_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec!["near vertex 1", "middle of edge 2"],
+            "{chargers}"
+        );
+        assert_eq!(facilities[1]["edge_index"], 1);
+        let along = |f: &serde_json::Value| f["along_route_distance"].as_f64().unwrap();
+        assert!(along(&facilities[0]) < along(&facilities[1]));
+        assert!(facilities[1]["distance_from_route"].as_f64().unwrap() < 1.0);
+    }
+}
@@ -0,0 +1,42 @@
+use super::plugin::SegmentSummaryOutputPlugin;
+use crate::{
+    app::compass::CompassComponentError,
+    plugin::{
+        output::{
+            default::segment_summary::SegmentSummaryConfig, OutputPlugin, OutputPluginBuilder,
+        },
+        PluginError,
+    },
+};
+use std::sync::Arc;
+
+/// Builds a plugin that groups the edges of each route by an edge attribute, such as
+/// road class or county, and appends subtotals of state variables for each group.
+///
+/// # Example Configuration
+///
+/// ```toml
+/// [[plugin.output_plugins]]
+/// type = "segment_summary"
+/// name = "road_class"
+/// attribute_input_file = "edges-road-class-enumerated.txt.gz"
+/// features = ["edge_distance", "edge_time"]
+/// ```
+pub struct SegmentSummaryOutputPluginBuilder {}
+
+impl OutputPluginBuilder for SegmentSummaryOutputPluginBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn OutputPlugin>, CompassComponentError> {
+        let conf: SegmentSummaryConfig =
+            serde_json::from_value(parameters.clone()).map_err(|e| {
+                PluginError::BuildFailed(format!(
+                    "failure reading segment summary output plugin config: {e}"
+                ))
+            })?;
+        let plugin = SegmentSummaryOutputPlugin::new(conf)
+            .map_err(|e| PluginError::OutputPluginFailed { source: e })?;
+        Ok(Arc::new(plugin))
+    }
+}
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SegmentSummaryConfig {
+    /// name of the grouping attribute, used as the output key, such as `road_class`
+    pub name: String,
+    /// file with one label per edge of the edge list, such as a road class or county
+    pub attribute_input_file: String,
+    /// edge list labeled by the attribute file. defaults to 0.
+    pub edge_list_id: Option<usize>,
+    /// state variables to subtotal for each group. defaults to all state variables
+    /// that are not accumulators.
+    pub features: Option<Vec<String>>,
+    /// label for edges that are not found in the attribute file. defaults to "unknown".
+    pub unknown_label: Option<String>,
+}
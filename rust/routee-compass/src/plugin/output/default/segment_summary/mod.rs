@@ -0,0 +1,7 @@
+mod builder;
+mod config;
+mod plugin;
+
+pub use builder::SegmentSummaryOutputPluginBuilder;
+pub use config::SegmentSummaryConfig;
+pub use plugin::SegmentSummaryOutputPlugin;
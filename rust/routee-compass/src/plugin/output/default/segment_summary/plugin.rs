@@ -0,0 +1,264 @@
+use crate::app::{compass::CompassAppError, search::SearchAppResult};
+use crate::plugin::output::default::segment_summary::SegmentSummaryConfig;
+use crate::plugin::output::output_plugin::OutputPlugin;
+use crate::plugin::output::OutputPluginError;
+use kdam::Bar;
+use routee_compass_core::algorithm::search::{EdgeTraversal, SearchInstance};
+use routee_compass_core::model::state::{StateModel, StateVariable};
+use routee_compass_core::util::fs::{read_decoders, read_utils};
+use serde_json::json;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// groups the edges of each route by a label read from an edge attribute file and
+/// appends the number of edges and the sum of each state variable for each group,
+/// such as the distance and time spent on interstates vs. arterials. groups are written
+/// in the order they are first visited by the route under `segment_summary.<name>`.
+pub struct SegmentSummaryOutputPlugin {
+    pub name: String,
+    pub edge_list_id: usize,
+    pub features: Option<Vec<String>>,
+    /// encoded label of each edge
+    pub label_by_edge: Box<[u32]>,
+    /// labels by encoded value, with the unknown label last
+    pub labels: Vec<String>,
+}
+
+impl SegmentSummaryOutputPlugin {
+    pub fn new(
+        conf: SegmentSummaryConfig,
+    ) -> Result<SegmentSummaryOutputPlugin, OutputPluginError> {
+        let file = Path::new(&conf.attribute_input_file);
+        let raw_labels: Box<[String]> = read_utils::read_raw_file(
+            file,
+            read_decoders::string,
+            Some(Bar::builder().desc(format!("{} segments", conf.name))),
+            None,
+        )
+        .map_err(|e| {
+            OutputPluginError::BuildFailed(format!(
+                "failure reading segment summary attribute file {}: {e}",
+                file.to_string_lossy()
+            ))
+        })?;
+        let mut labels: Vec<String> = vec![];
+        let mut encoding: HashMap<String, u32> = HashMap::new();
+        let label_by_edge = raw_labels
+            .into_vec()
+            .into_iter()
+            .map(|label| {
+                *encoding.entry(label).or_insert_with_key(|label| {
+                    labels.push(label.clone());
+                    (labels.len() - 1) as u32
+                })
+            })
+            .collect::<Box<[u32]>>();
+        labels.push(
+            conf.unknown_label
+                .unwrap_or_else(|| String::from("unknown")),
+        );
+        Ok(SegmentSummaryOutputPlugin {
+            name: conf.name,
+            edge_list_id: conf.edge_list_id.unwrap_or_default(),
+            features: conf.features,
+            label_by_edge,
+            labels,
+        })
+    }
+
+    /// label of an edge, or the unknown label if the edge is not in the attribute file
+    fn label(&self, edge: &EdgeTraversal) -> &str {
+        let code = if edge.edge_list_id.0 == self.edge_list_id {
+            self.label_by_edge.get(edge.edge_id.0).copied()
+        } else {
+            None
+        };
+        let unknown = self.labels.len() - 1;
+        &self.labels[code.map(|c| c as usize).unwrap_or(unknown)]
+    }
+
+    /// computes the number of edges and the subtotal of each state variable for
+    /// each group of edges in a route.
+    pub fn summarize(
+        &self,
+        route: &[EdgeTraversal],
+        state_model: &StateModel,
+    ) -> Result<serde_json::Value, OutputPluginError> {
+        let features = state_model
+            .indexed_iter()
+            .filter(|(_, (name, feature))| match &self.features {
+                Some(names) => names.contains(name),
+                None => !feature.is_accumulator(),
+            })
+            .collect::<Vec<_>>();
+        if let Some(names) = &self.features {
+            if let Some(missing) = names.iter().find(|n| !state_model.contains_key(n)) {
+                return Err(OutputPluginError::OutputPluginFailed(format!(
+                    "segment summary feature '{missing}' is not a state variable"
+                )));
+            }
+        }
+
+        let mut groups: Vec<(&str, usize, Vec<StateVariable>)> = vec![];
+        for edge in route.iter() {
+            let label = self.label(edge);
+            let group_idx = match groups.iter().position(|(l, _, _)| *l == label) {
+                Some(idx) => idx,
+                None => {
+                    groups.push((label, 0, vec![StateVariable::ZERO; features.len()]));
+                    groups.len() - 1
+                }
+            };
+            let (_, n_edges, totals) = &mut groups[group_idx];
+            *n_edges += 1;
+            for (total, (state_idx, _)) in totals.iter_mut().zip(features.iter()) {
+                *total = *total + edge.result_state[*state_idx];
+            }
+        }
+
+        let mut result = serde_json::Map::new();
+        for (label, n_edges, totals) in groups.into_iter() {
+            let mut group = serde_json::Map::new();
+            group.insert(String::from("n_edges"), json![n_edges]);
+            for (total, (_, (name, feature))) in totals.iter().zip(features.iter()) {
+                let value = feature.serialize_variable(total).map_err(|e| {
+                    OutputPluginError::OutputPluginFailed(format!(
+                        "failure serializing segment summary of '{name}': {e}"
+                    ))
+                })?;
+                group.insert(
+                    name.to_string(),
+                    json!({ "value": value, "unit": feature.get_unit_name() }),
+                );
+            }
+            result.insert(label.to_string(), json![group]);
+        }
+        Ok(json![result])
+    }
+}
+
+impl OutputPlugin for SegmentSummaryOutputPlugin {
+    fn name(&self) -> &str {
+        "segment_summary"
+    }
+
+    fn process(
+        &self,
+        output: &mut serde_json::Value,
+        search_result: &Result<(SearchAppResult, SearchInstance), CompassAppError>,
+    ) -> Result<(), OutputPluginError> {
+        let (result, si) = match search_result {
+            Err(_) => return Ok(()),
+            Ok((result, si)) => (result, si),
+        };
+        let summaries = result
+            .routes
+            .iter()
+            .map(|route| self.summarize(route, &si.state_model))
+            .collect::<Result<Vec<_>, OutputPluginError>>()?;
+        let summary = match summaries.as_slice() {
+            [] => serde_json::Value::Null,
+            [summary] => summary.to_owned(),
+            _ => json![summaries],
+        };
+        if !output["segment_summary"].is_object() {
+            output["segment_summary"] = json!({});
+        }
+        output["segment_summary"][&self.name] = summary;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use routee_compass_core::model::{
+        cost::TraversalCost,
+        network::{EdgeId, EdgeListId},
+        state::StateVariableConfig,
+        traversal::default::fieldname,
+        unit::DistanceUnit,
+    };
+    use uom::si::f64::Length;
+    use uom::ConstZero;
+
+    fn mock_edge(
+        state_model: &StateModel,
+        edge_list_id: usize,
+        edge_id: usize,
+        meters: f64,
+    ) -> EdgeTraversal {
+        let mut state = state_model
+            .initial_state(None)
+            .expect("test invariant failed");
+        let distance = DistanceUnit::Meters.to_uom(meters);
+        state_model
+            .set_distance(&mut state, fieldname::EDGE_DISTANCE, &distance)
+            .expect("test invariant failed");
+        EdgeTraversal {
+            edge_list_id: EdgeListId(edge_list_id),
+            edge_id: EdgeId(edge_id),
+            cost: TraversalCost::default(),
+            result_state: state,
+        }
+    }
+
+    #[test]
+    fn test_summarize() {
+        let state_model = StateModel::new(vec![
+            (
+                String::from(fieldname::EDGE_DISTANCE),
+                StateVariableConfig::Distance {
+                    initial: Length::ZERO,
+                    accumulator: false,
+                    output_unit: Some(DistanceUnit::Meters),
+                },
+            ),
+            (
+                String::from(fieldname::TRIP_DISTANCE),
+                StateVariableConfig::Distance {
+                    initial: Length::ZERO,
+                    accumulator: true,
+                    output_unit: Some(DistanceUnit::Meters),
+                },
+            ),
+        ]);
+        let plugin = SegmentSummaryOutputPlugin {
+            name: String::from("road_class"),
+            edge_list_id: 0,
+            features: None,
+            label_by_edge: Box::new([0, 1, 0]),
+            labels: vec![
+                String::from("interstate"),
+                String::from("arterial"),
+                String::from("unknown"),
+            ],
+        };
+        let route = vec![
+            mock_edge(&state_model, 0, 0, 100.0),
+            mock_edge(&state_model, 0, 1, 50.0),
+            mock_edge(&state_model, 0, 2, 25.0),
+            mock_edge(&state_model, 1, 0, 10.0),
+        ];
+        let summary = plugin
+            .summarize(&route, &state_model)
+            .expect("summarize failed");
+        let labels = summary
+            .as_object()
+            .expect("summary should be an object")
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>();
+        assert_eq!(labels, vec!["interstate", "arterial", "unknown"]);
+        assert_eq!(summary["interstate"]["n_edges"], json!(2));
+        assert_eq!(
+            summary["interstate"]["edge_distance"]["value"],
+            json!(125.0)
+        );
+        assert_eq!(summary["arterial"]["edge_distance"]["value"], json!(50.0));
+        assert_eq!(summary["unknown"]["edge_distance"]["value"], json!(10.0));
+        assert!(summary["interstate"]
+            .get(fieldname::TRIP_DISTANCE)
+            .is_none());
+    }
+}
@@ -12,6 +12,10 @@ pub struct SummaryOutputPlugin {
 }
 
 impl OutputPlugin for SummaryOutputPlugin {
+    fn name(&self) -> &str {
+        "summary"
+    }
+
     /// append "Cost" value to the output JSON
     fn process(
         &self,
@@ -32,6 +36,7 @@ impl OutputPlugin for SummaryOutputPlugin {
                 output["search_runtime"] = json![result.search_runtime.hhmmss()];
                 output["route_edges"] = json![route_edges];
                 output["tree_size_count"] = json![tree_edges];
+                output["peak_tree_size"] = json![result.peak_tree_size];
                 output["iterations"] = json![result.iterations];
                 output["terminated"] = json![terminated];
 
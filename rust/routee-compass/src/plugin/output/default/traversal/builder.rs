@@ -1,5 +1,8 @@
+use super::geojson_properties::GeoJsonProperties;
+use super::geometry_output_options::GeometryOutputOptions;
 use super::plugin::TraversalPlugin;
 use super::traversal_output_format::TraversalOutputFormat;
+use super::tree_output_limits::TreeOutputLimits;
 use crate::app::search::SummaryOp;
 use crate::{
     app::compass::CompassComponentError,
@@ -9,6 +12,7 @@ use crate::{
     },
 };
 use routee_compass_core::config::ConfigJsonExtensions;
+use routee_compass_core::model::state::DerivedVariable;
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -20,10 +24,35 @@ use std::sync::Arc;
 /// * `geometry_file` - the filename providing edge geometries
 /// * `route` (optional) - traversal output format for the route result
 /// * `tree` (optional) - traversal output format for the search tree result
+/// * `summary_ops` (optional) - the operation used to summarize each state variable over
+///   the route. can be extended by the query `summary_ops` key.
+/// * `derived_variables` (optional) - named arithmetic expressions over state variables,
+///   such as `cost_per_mile = "toll / trip_distance"`, added to the route traversal summary.
+///   can be extended by the query `derived_variables` key. see [DerivedVariable].
+/// * `cost_breakdown` (optional) - if true, adds the cost of each cost feature for each edge
+///   in the route. can be overridden by the query `cost_breakdown` key. defaults to false.
+/// * `tree_limits` (optional) - limits on the tree output by `max_depth`, `max_cost`,
+///   `sample_every` and `max_size`. can be overridden by the query `tree_limits` key.
+///   see [TreeOutputLimits] for details.
+/// * `geojson_properties` (optional) - selects the properties written to each `geo_json`
+///   feature. can be overridden by the query `geojson_properties` key. see [GeoJsonProperties].
+/// * `simplify_tolerance` (optional) - simplifies route and tree geometries with the
+///   Douglas-Peucker algorithm, keeping them within this many meters of the original
+///   linestrings. applies to the `wkt`, `wkb`, `geo_json` and encoded polyline formats.
+///   can be overridden by the query `simplify_tolerance` key. geometries are not simplified
+///   by default.
+/// * `output_srid` (optional) - EPSG SRID of the coordinate reference system that route and
+///   tree geometries are written in, such as `32613` for UTM zone 13N. can be overridden by
+///   the query `output_srid` key. defaults to WGS84 (`4326`). see [Crs] for the supported
+///   systems. encoded polylines can only be written in WGS84.
 ///
 /// See [TraversalOutputFormat] for information on the output formats supported.
 ///
 /// [TraversalOutputFormat]: super::traversal_output_format::TraversalOutputFormat
+/// [TreeOutputLimits]: super::tree_output_limits::TreeOutputLimits
+/// [GeoJsonProperties]: super::geojson_properties::GeoJsonProperties
+/// [DerivedVariable]: routee_compass_core::model::state::DerivedVariable
+/// [Crs]: routee_compass_core::util::geo::crs::Crs
 ///
 /// # Example Configuration
 ///
@@ -54,9 +83,39 @@ impl OutputPluginBuilder for TraversalPluginBuilder {
         let summary_ops: HashMap<String, SummaryOp> = parameters
             .get_config_serde_optional(&"summary_ops", &parent_key)?
             .unwrap_or_default();
+        let derived_variables: HashMap<String, DerivedVariable> = parameters
+            .get_config_serde_optional(&"derived_variables", &parent_key)?
+            .unwrap_or_default();
+
+        let cost_breakdown: bool = parameters
+            .get_config_serde_optional(&"cost_breakdown", &parent_key)?
+            .unwrap_or_default();
+
+        let tree_limits: TreeOutputLimits = parameters
+            .get_config_serde_optional(&"tree_limits", &parent_key)?
+            .unwrap_or_default();
+
+        let geojson_properties: GeoJsonProperties = parameters
+            .get_config_serde_optional(&"geojson_properties", &parent_key)?
+            .unwrap_or_default();
+
+        let geometry_options = GeometryOutputOptions {
+            simplify_tolerance: parameters
+                .get_config_serde_optional(&"simplify_tolerance", &parent_key)?,
+            output_srid: parameters.get_config_serde_optional(&"output_srid", &parent_key)?,
+        };
 
-        let geom_plugin = TraversalPlugin::new(route, tree, summary_ops)
-            .map_err(|e| PluginError::OutputPluginFailed { source: e })?;
+        let geom_plugin = TraversalPlugin::new(
+            route,
+            tree,
+            summary_ops,
+            derived_variables,
+            cost_breakdown,
+            tree_limits,
+            geojson_properties,
+            geometry_options,
+        )
+        .map_err(|e| PluginError::OutputPluginFailed { source: e })?;
         Ok(Arc::new(geom_plugin))
     }
 }
@@ -0,0 +1,176 @@
+use crate::plugin::output::OutputPluginError;
+use routee_compass_core::{algorithm::search::EdgeTraversal, model::state::StateModel};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+/// the edge attributes written to each GeoJSON feature.
+pub const EDGE_ATTRIBUTES: [&str; 4] = ["edge_id", "edge_list_id", "cost", "state"];
+
+/// selects the properties written to each feature of a GeoJSON traversal output.
+/// names may refer to an edge attribute (`edge_id`, `edge_list_id`, `cost`, `state`)
+/// or to a state variable in the state model.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct GeoJsonProperties {
+    /// properties to include. if omitted, all edge attributes and state variables are
+    /// included. listing a state variable includes only the listed state variables,
+    /// unless `state` is also listed.
+    pub include: Option<Vec<String>>,
+    /// properties to drop after applying `include`.
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    /// if true, state variables are written as top-level feature properties instead
+    /// of within a nested `state` object, for tools that do not support nested properties.
+    #[serde(default)]
+    pub flatten_state: bool,
+}
+
+impl GeoJsonProperties {
+    /// confirms that all named properties exist as edge attributes or state variables.
+    pub fn validate(&self, state_model: &StateModel) -> Result<(), OutputPluginError> {
+        let names = self.include.iter().flatten().chain(self.exclude.iter());
+        for name in names {
+            if !EDGE_ATTRIBUTES.contains(&name.as_str()) && !state_model.contains_key(name) {
+                let valid = EDGE_ATTRIBUTES
+                    .iter()
+                    .map(|s| s.to_string())
+                    .chain(state_model.keys().cloned())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                return Err(OutputPluginError::OutputPluginFailed(format!(
+                    "unknown GeoJSON property '{name}', must be one of: {valid}"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// builds the feature properties for an edge traversal from its serialized state.
+    pub fn create_properties(&self, t: &EdgeTraversal, state: Value) -> Map<String, Value> {
+        let mut properties = Map::new();
+        for (name, value) in [
+            ("edge_id", json![t.edge_id]),
+            ("edge_list_id", json![t.edge_list_id]),
+            ("cost", json![t.cost]),
+        ] {
+            if self.includes_attribute(name) {
+                properties.insert(String::from(name), value);
+            }
+        }
+
+        if !self.includes_attribute("state") && !self.includes_any_state_variable() {
+            return properties;
+        }
+        let state_map = match state {
+            Value::Object(map) => map
+                .into_iter()
+                .filter(|(name, _)| self.includes_state_variable(name))
+                .collect::<Map<_, _>>(),
+            other => {
+                properties.insert(String::from("state"), other);
+                return properties;
+            }
+        };
+        if self.flatten_state {
+            properties.extend(state_map);
+        } else {
+            properties.insert(String::from("state"), Value::Object(state_map));
+        }
+        properties
+    }
+
+    fn is_excluded(&self, name: &str) -> bool {
+        self.exclude.iter().any(|e| e == name)
+    }
+
+    fn is_listed(&self, name: &str) -> bool {
+        self.include
+            .as_ref()
+            .map(|i| i.iter().any(|n| n == name))
+            .unwrap_or(true)
+    }
+
+    fn includes_attribute(&self, name: &str) -> bool {
+        self.is_listed(name) && !self.is_excluded(name)
+    }
+
+    /// true if the include list names any state variable directly
+    fn includes_any_state_variable(&self) -> bool {
+        self.include
+            .iter()
+            .flatten()
+            .any(|n| !EDGE_ATTRIBUTES.contains(&n.as_str()) && !self.is_excluded(n))
+    }
+
+    fn includes_state_variable(&self, name: &str) -> bool {
+        if self.is_excluded(name) {
+            return false;
+        }
+        self.includes_attribute("state") || self.is_listed(name)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use routee_compass_core::model::{
+        cost::TraversalCost,
+        network::{EdgeId, EdgeListId},
+    };
+
+    fn mock_properties(properties: GeoJsonProperties) -> Map<String, Value> {
+        let t = EdgeTraversal {
+            edge_list_id: EdgeListId(0),
+            edge_id: EdgeId(7),
+            cost: TraversalCost::default(),
+            result_state: vec![],
+        };
+        let state = json!({"distance": 1.5, "time": 2.0});
+        properties.create_properties(&t, state)
+    }
+
+    #[test]
+    fn test_default_includes_all() {
+        let result = mock_properties(GeoJsonProperties::default());
+        let keys = result.keys().cloned().collect::<Vec<_>>();
+        assert_eq!(keys.len(), 4);
+        for attribute in EDGE_ATTRIBUTES {
+            assert!(result.contains_key(attribute));
+        }
+        assert_eq!(result["state"]["distance"], json![1.5]);
+    }
+
+    #[test]
+    fn test_include_state_variable() {
+        let result = mock_properties(GeoJsonProperties {
+            include: Some(vec![String::from("edge_id"), String::from("time")]),
+            ..Default::default()
+        });
+        assert_eq!(result["edge_id"], json![7]);
+        assert!(!result.contains_key("cost"));
+        assert_eq!(result["state"], json!({"time": 2.0}));
+    }
+
+    #[test]
+    fn test_exclude_and_flatten() {
+        let result = mock_properties(GeoJsonProperties {
+            include: None,
+            exclude: vec![String::from("cost"), String::from("time")],
+            flatten_state: true,
+        });
+        assert!(!result.contains_key("cost"));
+        assert!(!result.contains_key("state"));
+        assert!(!result.contains_key("time"));
+        assert_eq!(result["distance"], json![1.5]);
+    }
+
+    #[test]
+    fn test_exclude_state() {
+        let result = mock_properties(GeoJsonProperties {
+            exclude: vec![String::from("state")],
+            ..Default::default()
+        });
+        assert!(!result.contains_key("state"));
+        assert!(result.contains_key("edge_id"));
+    }
+}
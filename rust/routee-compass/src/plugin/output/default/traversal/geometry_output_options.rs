@@ -0,0 +1,183 @@
+use super::traversal_ops as ops;
+use crate::plugin::output::OutputPluginError;
+use geo::{Geometry, LineString, MultiLineString};
+use geojson::JsonObject;
+use routee_compass_core::util::geo::crs::Crs;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// options applied to the geometries written by the traversal output formats.
+/// configured on the traversal plugin and overridden by the query keys of the same name.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+pub struct GeometryOutputOptions {
+    /// if provided, geometries are simplified with the Douglas-Peucker algorithm,
+    /// keeping them within this many meters of the original linestrings
+    pub simplify_tolerance: Option<f64>,
+    /// if provided, geometries are transformed from WGS84 to this coordinate reference system
+    pub output_srid: Option<Crs>,
+}
+
+impl GeometryOutputOptions {
+    /// reads any geometry options set on the query, which take precedence over these options
+    pub fn with_query_overrides(
+        &self,
+        request: Option<&serde_json::Value>,
+    ) -> Result<GeometryOutputOptions, OutputPluginError> {
+        let options = GeometryOutputOptions {
+            simplify_tolerance: read_query_option(request, "simplify_tolerance")?
+                .or(self.simplify_tolerance),
+            output_srid: read_query_option(request, "output_srid")?.or(self.output_srid),
+        };
+        options.validate()?;
+        Ok(options)
+    }
+
+    pub fn validate(&self) -> Result<(), OutputPluginError> {
+        match self.simplify_tolerance {
+            Some(t) if !(t.is_finite() && t >= 0.0) => Err(OutputPluginError::OutputPluginFailed(
+                format!("simplify_tolerance must be a non-negative number of meters, found {t}"),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// the output coordinate reference system, if it differs from WGS84
+    pub fn projected_crs(&self) -> Option<&Crs> {
+        self.output_srid.as_ref().filter(|crs| !crs.is_wgs84())
+    }
+
+    /// simplifies a WGS84 linestring and transforms it to the output coordinate reference system
+    pub fn apply(&self, linestring: LineString<f32>) -> Result<OutputGeometry, OutputPluginError> {
+        let simplified = ops::simplify_linestring(linestring, self.simplify_tolerance);
+        match self.projected_crs() {
+            None => Ok(OutputGeometry::Wgs84(Geometry::LineString(simplified))),
+            Some(crs) => {
+                let projected = project(&simplified, crs)?;
+                Ok(OutputGeometry::Projected(Geometry::LineString(projected)))
+            }
+        }
+    }
+
+    /// simplifies each linestring and transforms it to the output coordinate reference system
+    pub fn apply_multi(
+        &self,
+        multilinestring: MultiLineString<f32>,
+    ) -> Result<OutputGeometry, OutputPluginError> {
+        let simplified = ops::simplify_multilinestring(multilinestring, self.simplify_tolerance);
+        match self.projected_crs() {
+            None => Ok(OutputGeometry::Wgs84(Geometry::MultiLineString(simplified))),
+            Some(crs) => {
+                let projected = simplified
+                    .iter()
+                    .map(|l| project(l, crs))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(OutputGeometry::Projected(Geometry::MultiLineString(
+                    MultiLineString::new(projected),
+                )))
+            }
+        }
+    }
+
+    /// a GeoJSON `crs` member naming the output coordinate reference system, if it
+    /// differs from WGS84, which is the GeoJSON default
+    pub fn geojson_crs_member(&self) -> Option<JsonObject> {
+        self.projected_crs().map(|crs| {
+            let name = format!("urn:ogc:def:crs:EPSG::{}", crs.srid());
+            let member = serde_json::json!({"type": "name", "properties": {"name": name}});
+            JsonObject::from_iter([(String::from("crs"), member)])
+        })
+    }
+}
+
+/// a geometry ready to be written, in WGS84 at the precision it was stored in, or
+/// transformed to another coordinate reference system at double precision.
+pub enum OutputGeometry {
+    Wgs84(Geometry<f32>),
+    Projected(Geometry<f64>),
+}
+
+impl OutputGeometry {
+    pub fn wkt_string(&self) -> String {
+        use wkt::ToWkt;
+        match self {
+            OutputGeometry::Wgs84(g) => g.wkt_string(),
+            OutputGeometry::Projected(g) => g.wkt_string(),
+        }
+    }
+
+    pub fn to_geojson(&self) -> geojson::Geometry {
+        match self {
+            OutputGeometry::Wgs84(g) => geojson::Geometry::from(g),
+            OutputGeometry::Projected(g) => geojson::Geometry::from(g),
+        }
+    }
+}
+
+fn read_query_option<T: DeserializeOwned>(
+    request: Option<&serde_json::Value>,
+    key: &str,
+) -> Result<Option<T>, OutputPluginError> {
+    request
+        .and_then(|r| r.get(key))
+        .map(|value| serde_json::from_value(value.clone()))
+        .transpose()
+        .map_err(|e| {
+            OutputPluginError::OutputPluginFailed(format!("failure reading query {key}: {e}"))
+        })
+}
+
+fn project(linestring: &LineString<f32>, crs: &Crs) -> Result<LineString<f64>, OutputPluginError> {
+    crs.linestring_from_wgs84(linestring).map_err(|e| {
+        OutputPluginError::OutputPluginFailed(format!("failure transforming to {crs}: {e}"))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_query_overrides() {
+        let configured = GeometryOutputOptions {
+            simplify_tolerance: Some(10.0),
+            output_srid: None,
+        };
+        let request = serde_json::json!({"output_srid": "EPSG:32613"});
+        let options = configured.with_query_overrides(Some(&request)).unwrap();
+        assert_eq!(options.simplify_tolerance, Some(10.0));
+        assert_eq!(options.projected_crs().map(|c| c.srid()), Some(32613));
+
+        let invalid = serde_json::json!({"simplify_tolerance": -1.0});
+        assert!(configured.with_query_overrides(Some(&invalid)).is_err());
+        let unsupported = serde_json::json!({"output_srid": 2232});
+        assert!(configured.with_query_overrides(Some(&unsupported)).is_err());
+    }
+
+    #[test]
+    fn test_apply_output_srid() {
+        let linestring = LineString::from(vec![(-105.0f32, 40.0f32), (-104.99, 40.0)]);
+        let wgs84 = GeometryOutputOptions::default();
+        assert_eq!(
+            wgs84.apply(linestring.clone()).unwrap().wkt_string(),
+            "LINESTRING(-105 40,-104.99 40)"
+        );
+        assert!(wgs84.geojson_crs_member().is_none());
+
+        let utm = GeometryOutputOptions {
+            simplify_tolerance: None,
+            output_srid: Some(Crs::from_srid(32613).unwrap()),
+        };
+        match utm.apply(linestring).unwrap() {
+            OutputGeometry::Projected(Geometry::LineString(projected)) => {
+                let start = projected.0[0];
+                assert!((start.x - 500_000.0).abs() < 0.01);
+                assert!((start.y - 4_427_757.22).abs() < 1.0);
+            }
+            _ => panic!("expected a projected linestring"),
+        }
+        let member = utm.geojson_crs_member().unwrap();
+        assert_eq!(
+            member["crs"]["properties"]["name"],
+            serde_json::json!("urn:ogc:def:crs:EPSG::32613")
+        );
+    }
+}
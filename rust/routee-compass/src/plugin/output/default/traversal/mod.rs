@@ -1,10 +1,18 @@
 mod builder;
+mod geojson_properties;
+mod geometry_output_options;
 mod json_extensions;
+mod parent_table;
 mod plugin;
+pub mod polyline;
 pub mod traversal_ops;
 mod traversal_output_format;
+mod tree_output_limits;
 
 pub use builder::TraversalPluginBuilder;
+pub use geojson_properties::GeoJsonProperties;
+pub use geometry_output_options::{GeometryOutputOptions, OutputGeometry};
 pub use json_extensions::TraversalJsonExtensions;
 pub use plugin::TraversalPlugin;
 pub use traversal_output_format::TraversalOutputFormat;
+pub use tree_output_limits::{TreeOutputBranch, TreeOutputLimits};
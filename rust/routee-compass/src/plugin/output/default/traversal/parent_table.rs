@@ -0,0 +1,220 @@
+use super::tree_output_limits::TreeOutputBranch;
+use crate::plugin::output::OutputPluginError;
+use arrow::array::{ArrayRef, Float64Array, StringArray, UInt64Array};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::ipc::writer::StreamWriter;
+use arrow::record_batch::RecordBatch;
+use base64::Engine;
+use routee_compass_core::{algorithm::search::SearchTree, model::label::Label, model::unit::AsF64};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// a row of the parent-pointer table of a search tree. each label of the tree is
+/// identified by its row index, so that a tree can be rebuilt by following the parent
+/// label ids back to a root.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParentTableRow {
+    pub label_id: u64,
+    /// id of the parent label, or None for a root or a label whose parent was left out
+    /// by the tree output limits
+    pub parent_label_id: Option<u64>,
+    pub vertex_id: u64,
+    pub label: String,
+    /// the edge from the parent label to this label, or None for a root
+    pub edge_list_id: Option<u64>,
+    pub edge_id: Option<u64>,
+    pub depth: u64,
+    /// cumulative objective cost from the root
+    pub cost: f64,
+}
+
+/// builds the parent-pointer table of the roots of a search tree and the branches
+/// selected for output. roots come first, followed by the branches in order of their
+/// cumulative cost.
+pub fn create_parent_table(
+    tree: &SearchTree,
+    branches: &[TreeOutputBranch],
+) -> Vec<ParentTableRow> {
+    let roots = tree
+        .iter()
+        .filter(|(_, node)| node.is_root())
+        .map(|(label, _)| label)
+        .collect::<Vec<_>>();
+    let label_ids: HashMap<&Label, u64> = roots
+        .iter()
+        .copied()
+        .chain(branches.iter().map(|b| b.label))
+        .enumerate()
+        .map(|(idx, label)| (label, idx as u64))
+        .collect();
+    let root_rows = roots.iter().map(|label| ParentTableRow {
+        label_id: label_ids[label],
+        parent_label_id: None,
+        vertex_id: label.vertex_id().0 as u64,
+        label: label.to_string(),
+        edge_list_id: None,
+        edge_id: None,
+        depth: 0,
+        cost: 0.0,
+    });
+    let branch_rows = branches.iter().map(|b| ParentTableRow {
+        label_id: label_ids[b.label],
+        parent_label_id: b
+            .node
+            .parent_label()
+            .and_then(|parent| label_ids.get(parent).copied()),
+        vertex_id: b.label.vertex_id().0 as u64,
+        label: b.label.to_string(),
+        edge_list_id: Some(b.edge_traversal.edge_list_id.0 as u64),
+        edge_id: Some(b.edge_traversal.edge_id.0 as u64),
+        depth: b.depth as u64,
+        cost: b.cost.as_f64(),
+    });
+    root_rows.chain(branch_rows).collect()
+}
+
+/// writes a parent-pointer table as CSV, leaving missing values empty
+pub fn parent_table_csv(rows: &[ParentTableRow]) -> String {
+    let optional = |v: Option<u64>| v.map(|v| v.to_string()).unwrap_or_default();
+    let mut csv =
+        String::from("label_id,parent_label_id,vertex_id,label,edge_list_id,edge_id,depth,cost\n");
+    for row in rows.iter() {
+        csv.push_str(&format!(
+            "{},{},{},\"{}\",{},{},{},{}\n",
+            row.label_id,
+            optional(row.parent_label_id),
+            row.vertex_id,
+            row.label,
+            optional(row.edge_list_id),
+            optional(row.edge_id),
+            row.depth,
+            row.cost
+        ));
+    }
+    csv
+}
+
+/// writes a parent-pointer table as an Arrow IPC stream, encoded as base64 so that it can
+/// be carried in a JSON response
+pub fn parent_table_arrow(rows: &[ParentTableRow]) -> Result<String, OutputPluginError> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("label_id", DataType::UInt64, false),
+        Field::new("parent_label_id", DataType::UInt64, true),
+        Field::new("vertex_id", DataType::UInt64, false),
+        Field::new("label", DataType::Utf8, false),
+        Field::new("edge_list_id", DataType::UInt64, true),
+        Field::new("edge_id", DataType::UInt64, true),
+        Field::new("depth", DataType::UInt64, false),
+        Field::new("cost", DataType::Float64, false),
+    ]));
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(UInt64Array::from_iter_values(
+            rows.iter().map(|r| r.label_id),
+        )),
+        Arc::new(UInt64Array::from_iter(
+            rows.iter().map(|r| r.parent_label_id),
+        )),
+        Arc::new(UInt64Array::from_iter_values(
+            rows.iter().map(|r| r.vertex_id),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            rows.iter().map(|r| r.label.as_str()),
+        )),
+        Arc::new(UInt64Array::from_iter(rows.iter().map(|r| r.edge_list_id))),
+        Arc::new(UInt64Array::from_iter(rows.iter().map(|r| r.edge_id))),
+        Arc::new(UInt64Array::from_iter_values(rows.iter().map(|r| r.depth))),
+        Arc::new(Float64Array::from_iter_values(rows.iter().map(|r| r.cost))),
+    ];
+    let failed = |e: arrow::error::ArrowError| {
+        OutputPluginError::OutputPluginFailed(format!("failure writing tree parent table: {e}"))
+    };
+    let batch = RecordBatch::try_new(schema.clone(), columns).map_err(failed)?;
+    let mut bytes = vec![];
+    let mut writer = StreamWriter::try_new(&mut bytes, &schema).map_err(failed)?;
+    writer.write(&batch).map_err(failed)?;
+    writer.finish().map_err(failed)?;
+    drop(writer);
+    Ok(base64::engine::general_purpose::STANDARD.encode(bytes))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::plugin::output::default::traversal::TreeOutputLimits;
+    use arrow::array::{Array, AsArray};
+    use arrow::datatypes::UInt64Type;
+    use arrow::ipc::reader::StreamReader;
+    use routee_compass_core::{
+        algorithm::search::{Direction, EdgeTraversal},
+        model::{
+            cost::TraversalCost,
+            label::default::vertex_label_model::VertexLabelModel,
+            network::{EdgeId, EdgeListId, VertexId},
+            unit::Cost,
+        },
+    };
+
+    /// a tree 0 -> 1 -> 2 and 0 -> 3 where each edge has an objective cost of 1
+    // the struct update fills cost_component when built with detailed_costs
+    #[allow(clippy::needless_update)]
+    fn mock_tree() -> SearchTree {
+        let mut tree = SearchTree::with_root(Label::Vertex(VertexId(0)), Direction::Forward);
+        for (edge_id, src, dst) in [(0, 0, 1), (1, 1, 2), (2, 0, 3)] {
+            let edge_traversal = EdgeTraversal {
+                edge_list_id: EdgeListId(0),
+                edge_id: EdgeId(edge_id),
+                cost: TraversalCost {
+                    objective_cost: Cost::ONE,
+                    total_cost: Cost::ONE,
+                    ..Default::default()
+                },
+                result_state: vec![],
+            };
+            tree.insert(
+                Label::Vertex(VertexId(src)),
+                edge_traversal,
+                Label::Vertex(VertexId(dst)),
+                Arc::new(VertexLabelModel {}),
+            )
+            .expect("test invariant failed");
+        }
+        tree
+    }
+
+    #[test]
+    fn test_parent_table() {
+        let tree = mock_tree();
+        let branches = TreeOutputLimits::default().select(&tree).unwrap();
+        let rows = create_parent_table(&tree, &branches);
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[0].parent_label_id, None);
+        assert_eq!(rows[0].vertex_id, 0);
+        // each parent precedes its children and the tree can be walked back to the root
+        let vertex_2 = rows.iter().find(|r| r.vertex_id == 2).unwrap();
+        assert_eq!(vertex_2.depth, 2);
+        assert_eq!(vertex_2.cost, 2.0);
+        let parent = &rows[vertex_2.parent_label_id.unwrap() as usize];
+        assert_eq!(parent.vertex_id, 1);
+        assert_eq!(parent.parent_label_id, Some(0));
+
+        let csv = parent_table_csv(&rows);
+        assert_eq!(csv.lines().count(), 5);
+        assert!(csv.contains("0,,0,\"Vertex(0)\",,,0,0\n"), "{csv}");
+
+        let encoded = parent_table_arrow(&rows).unwrap();
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .unwrap();
+        let batches = StreamReader::try_new(bytes.as_slice(), None)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].num_rows(), 4);
+        let parents = batches[0]
+            .column_by_name("parent_label_id")
+            .unwrap()
+            .as_primitive::<UInt64Type>();
+        assert_eq!(parents.null_count(), 1);
+    }
+}
@@ -1,10 +1,14 @@
+use super::geojson_properties::GeoJsonProperties;
+use super::geometry_output_options::GeometryOutputOptions;
 use super::json_extensions::TraversalJsonField;
 use super::traversal_output_format::TraversalOutputFormat;
+use super::tree_output_limits::TreeOutputLimits;
 use crate::app::compass::CompassAppError;
 use crate::app::search::{generate_route_output, RouteOutputError, SearchAppResult, SummaryOp};
 use crate::plugin::output::output_plugin::OutputPlugin;
 use crate::plugin::output::OutputPluginError;
 use routee_compass_core::algorithm::search::SearchInstance;
+use routee_compass_core::model::state::DerivedVariable;
 use serde_json::json;
 use std::collections::HashMap;
 
@@ -12,22 +16,47 @@ pub struct TraversalPlugin {
     route: Option<TraversalOutputFormat>,
     tree: Option<TraversalOutputFormat>,
     summary_ops: HashMap<String, SummaryOp>,
+    derived_variables: HashMap<String, DerivedVariable>,
+    cost_breakdown: bool,
+    tree_limits: TreeOutputLimits,
+    geojson_properties: GeoJsonProperties,
+    geometry_options: GeometryOutputOptions,
     route_key: String,
     tree_key: String,
 }
 
 impl TraversalPlugin {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         route: Option<TraversalOutputFormat>,
         tree: Option<TraversalOutputFormat>,
         summary_ops: HashMap<String, SummaryOp>,
+        derived_variables: HashMap<String, DerivedVariable>,
+        cost_breakdown: bool,
+        tree_limits: TreeOutputLimits,
+        geojson_properties: GeoJsonProperties,
+        geometry_options: GeometryOutputOptions,
     ) -> Result<TraversalPlugin, OutputPluginError> {
+        geometry_options.validate()?;
+        if cost_breakdown && !cfg!(feature = "detailed_costs") {
+            return Err(OutputPluginError::BuildFailed(String::from(
+                "traversal plugin 'cost_breakdown' requires routee-compass to be built with the detailed_costs feature",
+            )));
+        }
+        if let Some(route) = &route {
+            route.validate_route_format()?;
+        }
         let route_key = TraversalJsonField::RouteOutput.to_string();
         let tree_key = TraversalJsonField::TreeOutput.to_string();
         Ok(TraversalPlugin {
             route,
             tree,
             summary_ops,
+            derived_variables,
+            cost_breakdown,
+            tree_limits,
+            geojson_properties,
+            geometry_options,
             route_key,
             tree_key,
         })
@@ -35,6 +64,10 @@ impl TraversalPlugin {
 }
 
 impl OutputPlugin for TraversalPlugin {
+    fn name(&self) -> &str {
+        "traversal"
+    }
+
     fn process(
         &self,
         output: &mut serde_json::Value,
@@ -45,6 +78,24 @@ impl OutputPlugin for TraversalPlugin {
             Ok((result, si)) => (result, si),
         };
 
+        let query_geojson_properties: Option<GeoJsonProperties> = output
+            .get("request")
+            .and_then(|r| r.get("geojson_properties"))
+            .map(|p| serde_json::from_value(p.clone()))
+            .transpose()
+            .map_err(|e| {
+                OutputPluginError::OutputPluginFailed(format!(
+                    "failure reading query geojson_properties: {e}"
+                ))
+            })?;
+        let geojson_properties = query_geojson_properties
+            .as_ref()
+            .unwrap_or(&self.geojson_properties);
+
+        let geometry_options = self
+            .geometry_options
+            .with_query_overrides(output.get("request"))?;
+
         // output route if configured
         if let Some(route_args) = self.route {
             let mut summary_ops = self.summary_ops.clone();
@@ -57,10 +108,42 @@ impl OutputPlugin for TraversalPlugin {
                 summary_ops.extend(query_ops);
             }
 
+            let mut derived_variables = self.derived_variables.clone();
+            let query_derived_variables: Option<HashMap<String, DerivedVariable>> = output
+                .get("request")
+                .and_then(|r| r.get("derived_variables"))
+                .map(|d| serde_json::from_value(d.clone()))
+                .transpose()
+                .map_err(|e| {
+                    OutputPluginError::OutputPluginFailed(format!(
+                        "failure reading query derived_variables: {e}"
+                    ))
+                })?;
+            if let Some(query_derived) = query_derived_variables {
+                derived_variables.extend(query_derived);
+            }
+
+            let cost_breakdown = output
+                .get("request")
+                .and_then(|r| r.get("cost_breakdown"))
+                .and_then(|b| b.as_bool())
+                .unwrap_or(self.cost_breakdown);
+
             let routes_serialized = result
                 .routes
                 .iter()
-                .map(|route| generate_route_output(route, si, &route_args, &summary_ops))
+                .map(|route| {
+                    generate_route_output(
+                        route,
+                        si,
+                        &route_args,
+                        &summary_ops,
+                        &derived_variables,
+                        geojson_properties,
+                        &geometry_options,
+                        cost_breakdown,
+                    )
+                })
                 .collect::<Result<Vec<_>, RouteOutputError>>()
                 .map_err(|e| {
                     OutputPluginError::OutputPluginFailed(format!(
@@ -82,15 +165,28 @@ impl OutputPlugin for TraversalPlugin {
 
         // output tree(s) if configured
         if let Some(tree_args) = self.tree {
+            let query_tree_limits: Option<TreeOutputLimits> = output
+                .get("request")
+                .and_then(|r| r.get("tree_limits"))
+                .map(|l| serde_json::from_value(l.clone()))
+                .transpose()
+                .map_err(|e| {
+                    OutputPluginError::OutputPluginFailed(format!(
+                        "failure reading query tree_limits: {e}"
+                    ))
+                })?;
+            let tree_limits = query_tree_limits.as_ref().unwrap_or(&self.tree_limits);
             let trees_serialized = result
                 .trees
                 .iter()
                 .map(|tree| {
-                    // tree_args.generate_tree_output(tree, &self.geoms)
                     tree_args.generate_tree_output(
                         tree,
+                        tree_limits,
                         si.map_model.clone(),
                         si.state_model.clone(),
+                        geojson_properties,
+                        &geometry_options,
                     )
                 })
                 .collect::<Result<Vec<_>, _>>()?;
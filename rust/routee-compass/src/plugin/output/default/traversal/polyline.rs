@@ -0,0 +1,71 @@
+use crate::plugin::output::OutputPluginError;
+use geo::LineString;
+
+/// encodes a linestring using the Google encoded polyline algorithm at the given
+/// precision (number of decimal places). coordinates are written in (lat, lon) order.
+/// precision 5 matches Google Maps, precision 6 matches OSRM and Valhalla.
+pub fn encode_polyline(
+    linestring: &LineString<f32>,
+    precision: u32,
+) -> Result<String, OutputPluginError> {
+    let factor = 10_f64.powi(precision as i32);
+    let mut encoded = String::new();
+    let (mut prev_lat, mut prev_lon) = (0_i64, 0_i64);
+    for coord in linestring.coords() {
+        let (x, y) = (coord.x as f64, coord.y as f64);
+        if !(-180.0..=180.0).contains(&x) || !(-90.0..=90.0).contains(&y) {
+            return Err(OutputPluginError::OutputPluginFailed(format!(
+                "coordinate ({x}, {y}) is not a valid WGS84 position for polyline encoding"
+            )));
+        }
+        let lat = (y * factor).round() as i64;
+        let lon = (x * factor).round() as i64;
+        encode_value(lat - prev_lat, &mut encoded);
+        encode_value(lon - prev_lon, &mut encoded);
+        prev_lat = lat;
+        prev_lon = lon;
+    }
+    Ok(encoded)
+}
+
+/// appends a single signed delta value to the encoded polyline string.
+fn encode_value(delta: i64, out: &mut String) {
+    let mut value = if delta < 0 { !(delta << 1) } else { delta << 1 };
+    while value >= 0x20 {
+        let chunk = ((value & 0x1f) | 0x20) as u8 + 63;
+        out.push(chunk as char);
+        value >>= 5;
+    }
+    out.push((value as u8 + 63) as char);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo::line_string;
+
+    #[test]
+    fn test_encode_google_example() {
+        // example from the Google polyline algorithm documentation
+        let linestring = line_string![
+            (x: -120.2, y: 38.5),
+            (x: -120.95, y: 40.7),
+            (x: -126.453, y: 43.252),
+        ];
+        let result = encode_polyline(&linestring, 5).expect("encoding failed");
+        assert_eq!(result, "_p~iF~ps|U_ulLnnqC_mqNvxq`@");
+    }
+
+    #[test]
+    fn test_encode_precision_6() {
+        let linestring = line_string![(x: -105.5, y: 40.25), (x: -105.25, y: 40.5)];
+        let result = encode_polyline(&linestring, 6).expect("encoding failed");
+        assert_eq!(result, "_htwkA~tefhE_hgN_hgN");
+    }
+
+    #[test]
+    fn test_encode_invalid_coordinate() {
+        let linestring = line_string![(x: 500.0, y: 40.0)];
+        assert!(encode_polyline(&linestring, 5).is_err());
+    }
+}
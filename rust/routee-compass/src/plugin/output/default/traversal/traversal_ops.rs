@@ -1,28 +1,65 @@
+use super::geojson_properties::GeoJsonProperties;
+use super::geometry_output_options::GeometryOutputOptions;
+use super::tree_output_limits::TreeOutputBranch;
 use crate::plugin::output::OutputPluginError;
-use geo::{LineString, MultiLineString, Point};
+use geo::{LineString, MultiLineString, Point, Simplify};
 use geo_types::MultiPoint;
 use geojson::{Feature, FeatureCollection};
 use routee_compass_core::algorithm::search::EdgeTraversal;
-use routee_compass_core::algorithm::search::SearchTree;
 use routee_compass_core::model::map::MapModel;
 use routee_compass_core::model::state::StateModel;
 use routee_compass_core::util::geo::geo_io_utils;
-use serde_json::{json, Map};
 use std::sync::Arc;
 
+/// approximate length of one degree of latitude, used to convert simplification
+/// tolerances from meters to WGS84 degrees
+const METERS_PER_DEGREE: f64 = 111_320.0;
+
+/// simplifies a linestring with the Douglas-Peucker algorithm, removing vertices that are
+/// within `simplify_tolerance` meters of the simplified line. the tolerance is converted to
+/// degrees of latitude, so east-west deviations away from the equator are slightly
+/// overestimated. the first and last coordinates are always kept.
+pub fn simplify_linestring(
+    linestring: LineString<f32>,
+    simplify_tolerance: Option<f64>,
+) -> LineString<f32> {
+    match simplify_tolerance {
+        Some(tolerance) if tolerance > 0.0 => {
+            linestring.simplify((tolerance / METERS_PER_DEGREE) as f32)
+        }
+        _ => linestring,
+    }
+}
+
+/// simplifies each linestring of a multilinestring. see [`simplify_linestring`].
+pub fn simplify_multilinestring(
+    multilinestring: MultiLineString<f32>,
+    simplify_tolerance: Option<f64>,
+) -> MultiLineString<f32> {
+    match simplify_tolerance {
+        Some(tolerance) if tolerance > 0.0 => MultiLineString::new(
+            multilinestring
+                .into_iter()
+                .map(|l| simplify_linestring(l, simplify_tolerance))
+                .collect(),
+        ),
+        _ => multilinestring,
+    }
+}
+
 pub fn create_tree_geojson(
-    tree: &SearchTree,
+    branches: &[TreeOutputBranch],
     map_model: Arc<MapModel>,
     state_model: Arc<StateModel>,
+    properties: &GeoJsonProperties,
+    geometry_options: &GeometryOutputOptions,
 ) -> Result<serde_json::Value, OutputPluginError> {
-    let features = tree
-        .values()
-        .filter_map(|t| {
-            let et = match t.incoming_edge() {
-                None => return None,
-                Some(e) => e,
-            };
-            let row_result = map_model
+    properties.validate(&state_model)?;
+    let features = branches
+        .iter()
+        .map(|b| {
+            let et: &EdgeTraversal = &b.edge_traversal;
+            map_model
                 .get_linestring(&et.edge_list_id, &et.edge_id)
                 .cloned()
                 .map_err(|e| {
@@ -30,16 +67,17 @@ pub fn create_tree_geojson(
                         "failure creating tree GeoJSON: {e}"
                     ))
                 })
-                .and_then(|g| create_geojson_feature(et, g, state_model.clone()));
-
-            Some(row_result)
+                .and_then(|g| {
+                    let geometry = geometry_options.apply(g)?.to_geojson();
+                    create_geojson_feature(et, geometry, state_model.clone(), properties)
+                })
         })
         .collect::<Result<Vec<_>, OutputPluginError>>()?;
     // let result_json = serde_json::to_value(features)?;/
     let feature_collection = FeatureCollection {
         bbox: None,
         features,
-        foreign_members: None,
+        foreign_members: geometry_options.geojson_crs_member(),
     };
     let result = serde_json::to_value(feature_collection)?;
     Ok(result)
@@ -49,7 +87,10 @@ pub fn create_route_geojson(
     route: &[EdgeTraversal],
     map_model: Arc<MapModel>,
     state_model: Arc<StateModel>,
+    properties: &GeoJsonProperties,
+    geometry_options: &GeometryOutputOptions,
 ) -> Result<serde_json::Value, OutputPluginError> {
+    properties.validate(&state_model)?;
     let features = route
         .iter()
         .map(|t| {
@@ -61,7 +102,9 @@ pub fn create_route_geojson(
                         "failure building route geojson: {e}"
                     ))
                 })?;
-            let geojson_feature = create_geojson_feature(t, g, state_model.clone())?;
+            let geometry = geometry_options.apply(g)?.to_geojson();
+            let geojson_feature =
+                create_geojson_feature(t, geometry, state_model.clone(), properties)?;
             Ok(geojson_feature)
         })
         .collect::<Result<Vec<_>, OutputPluginError>>()?;
@@ -69,7 +112,7 @@ pub fn create_route_geojson(
     let feature_collection = FeatureCollection {
         bbox: None,
         features,
-        foreign_members: None,
+        foreign_members: geometry_options.geojson_crs_member(),
     };
     let result = serde_json::to_value(feature_collection)?;
     Ok(result)
@@ -77,8 +120,9 @@ pub fn create_route_geojson(
 
 pub fn create_geojson_feature(
     t: &EdgeTraversal,
-    g: LineString<f32>,
+    geometry: geojson::Geometry,
     state_model: Arc<StateModel>,
+    properties: &GeoJsonProperties,
 ) -> Result<Feature, OutputPluginError> {
     let serialized_state = state_model
         .serialize_state(&t.result_state, false)
@@ -87,16 +131,9 @@ pub fn create_geojson_feature(
                 "failure serializing final trip state while constructing geojson output: {e}"
             ))
         })?;
-    let serialized_cost = json![t.cost];
-
-    let mut properties = Map::new();
-    properties.insert(String::from("edge_id"), json![t.edge_id]);
-    properties.insert(String::from("edge_list_id"), json![t.edge_list_id]);
-    properties.insert(String::from("state"), serialized_state);
-    properties.insert(String::from("cost"), serialized_cost);
+    let properties = properties.create_properties(t, serialized_state);
 
     // let id = Id::Number(serde_json::Number::from(t.edge_id.0));
-    let geometry = geojson::Geometry::from(&g);
     let feature = Feature {
         bbox: None,
         geometry: Some(geometry),
@@ -144,13 +181,12 @@ pub fn create_route_linestring(
 }
 
 pub fn create_tree_multilinestring(
-    tree: &SearchTree,
-    // geoms: &[LineString<f32>],
+    branches: &[TreeOutputBranch],
     map_model: Arc<MapModel>,
 ) -> Result<MultiLineString<f32>, OutputPluginError> {
-    let edges = tree
-        .values()
-        .flat_map(|node| node.incoming_edge().map(|et| (et.edge_list_id, et.edge_id)))
+    let edges = branches
+        .iter()
+        .map(|b| (b.edge_traversal.edge_list_id, b.edge_traversal.edge_id))
         .collect::<Vec<_>>();
 
     let tree_linestrings = edges
@@ -167,12 +203,12 @@ pub fn create_tree_multilinestring(
 }
 
 pub fn create_tree_multipoint(
-    tree: &SearchTree,
+    branches: &[TreeOutputBranch],
     map_model: Arc<MapModel>,
 ) -> Result<MultiPoint<f32>, OutputPluginError> {
-    let edges = tree
-        .values()
-        .filter_map(|node| node.incoming_edge().map(|et| (et.edge_list_id, et.edge_id)))
+    let edges = branches
+        .iter()
+        .map(|b| (b.edge_traversal.edge_list_id, b.edge_traversal.edge_id))
         .collect::<Vec<_>>();
 
     let tree_destinations = edges
@@ -194,3 +230,38 @@ pub fn create_tree_multipoint(
     let geometry = MultiPoint::new(tree_destinations);
     Ok(geometry)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_simplify_linestring() {
+        // a nearly straight line with a 1 meter kink at (0.001, 0.00001)
+        let linestring = LineString::from(vec![
+            (0.0f32, 0.0f32),
+            (0.001, 0.000009),
+            (0.002, 0.0),
+            (0.003, 0.001),
+        ]);
+        let simplified = simplify_linestring(linestring.clone(), Some(5.0));
+        assert_eq!(simplified.0.len(), 3);
+        assert_eq!(simplified.0.first(), linestring.0.first());
+        assert_eq!(simplified.0.last(), linestring.0.last());
+
+        // a tolerance below the kink keeps every vertex
+        assert_eq!(
+            simplify_linestring(linestring.clone(), Some(0.5)),
+            linestring
+        );
+        assert_eq!(simplify_linestring(linestring.clone(), None), linestring);
+        assert_eq!(
+            simplify_linestring(linestring.clone(), Some(0.0)),
+            linestring
+        );
+
+        let multilinestring = MultiLineString::new(vec![linestring.clone(), linestring]);
+        let simplified = simplify_multilinestring(multilinestring, Some(5.0));
+        assert!(simplified.iter().all(|l| l.0.len() == 3));
+    }
+}
@@ -1,6 +1,11 @@
 use std::sync::Arc;
 
+use super::geojson_properties::GeoJsonProperties;
+use super::geometry_output_options::{GeometryOutputOptions, OutputGeometry};
+use super::parent_table;
+use super::polyline;
 use super::traversal_ops as ops;
+use super::tree_output_limits::{TreeOutputBranch, TreeOutputLimits};
 use crate::plugin::output::OutputPluginError;
 use geo::{CoordFloat, Geometry, TryConvert};
 use routee_compass_core::{
@@ -9,7 +14,6 @@ use routee_compass_core::{
 };
 use serde::{Deserialize, Serialize};
 use wkb::writer::WriteOptions;
-use wkt::ToWkt;
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -23,26 +27,43 @@ pub enum TraversalOutputFormat {
     // returns the geometries and properties as GeoJSON
     GeoJson,
     EdgeId,
+    // returns a compact CSV table with one row per edge traversal. for trees, each row
+    // is a settled vertex with its depth and cumulative objective cost from the root.
+    Csv,
+    // concatenates all LINESTRINGS and returns the geometry as a Google encoded polyline
+    // with precision 5. trees return an array with one encoded polyline per edge.
+    EncodedPolyline,
+    // same as EncodedPolyline with precision 6, as used by OSRM and Valhalla
+    EncodedPolyline6,
+    // returns a search tree as a CSV parent-pointer table with one row per label: its id,
+    // the id of its parent label, the incoming edge and the cumulative objective cost from
+    // the root. not supported for routes.
+    ParentTable,
+    // same as ParentTable, written as a base64-encoded Arrow IPC stream
+    ParentTableArrow,
 }
 
 impl TraversalOutputFormat {
-    /// generates output for a route based on the configured TraversalOutputFormat
+    /// generates output for a route based on the configured TraversalOutputFormat.
+    /// GeoJSON feature properties are selected by the provided [GeoJsonProperties],
+    /// and geometries are simplified and transformed by the [GeometryOutputOptions].
     pub fn generate_route_output(
         &self,
         route: &Vec<EdgeTraversal>,
         map_model: Arc<MapModel>,
         state_model: Arc<StateModel>,
+        geojson_properties: &GeoJsonProperties,
+        geometry_options: &GeometryOutputOptions,
     ) -> Result<serde_json::Value, OutputPluginError> {
         match self {
             TraversalOutputFormat::Wkt => {
                 let route_geometry = ops::create_route_linestring(route, map_model.clone())?;
-                let route_wkt = route_geometry.wkt_string();
+                let route_wkt = geometry_options.apply(route_geometry)?.wkt_string();
                 Ok(serde_json::Value::String(route_wkt))
             }
             TraversalOutputFormat::Wkb => {
                 let linestring = ops::create_route_linestring(route, map_model.clone())?;
-                let geometry = geo::Geometry::LineString(linestring);
-                let wkb_str = geometry_to_wkb_string(&geometry)?;
+                let wkb_str = output_geometry_to_wkb_string(&geometry_options.apply(linestring)?)?;
                 Ok(serde_json::Value::String(wkb_str))
             }
             TraversalOutputFormat::Json => {
@@ -50,7 +71,13 @@ impl TraversalOutputFormat {
                 Ok(result)
             }
             TraversalOutputFormat::GeoJson => {
-                let result = ops::create_route_geojson(route, map_model, state_model)?;
+                let result = ops::create_route_geojson(
+                    route,
+                    map_model,
+                    state_model,
+                    geojson_properties,
+                    geometry_options,
+                )?;
                 Ok(result)
             }
             TraversalOutputFormat::EdgeId => {
@@ -58,48 +85,176 @@ impl TraversalOutputFormat {
                 let json = serde_json::json![route_ids];
                 Ok(json)
             }
+            TraversalOutputFormat::Csv => {
+                let csv = create_route_csv(route);
+                Ok(serde_json::Value::String(csv))
+            }
+            TraversalOutputFormat::EncodedPolyline | TraversalOutputFormat::EncodedPolyline6 => {
+                self.validate_polyline_options(geometry_options)?;
+                let linestring = ops::create_route_linestring(route, map_model)?;
+                let linestring =
+                    ops::simplify_linestring(linestring, geometry_options.simplify_tolerance);
+                let encoded = polyline::encode_polyline(&linestring, self.polyline_precision())?;
+                Ok(serde_json::Value::String(encoded))
+            }
+            TraversalOutputFormat::ParentTable | TraversalOutputFormat::ParentTableArrow => {
+                Err(self.unsupported_route_format())
+            }
         }
     }
 
-    /// generates output for a tree based on the configured TraversalOutputFormat
+    /// generates output for a tree based on the configured TraversalOutputFormat,
+    /// including only the branches of the tree which satisfy the provided limits.
+    /// geometries are simplified and transformed by the [GeometryOutputOptions].
     pub fn generate_tree_output(
         &self,
         tree: &SearchTree,
+        limits: &TreeOutputLimits,
         map_model: Arc<MapModel>,
         state_model: Arc<StateModel>,
+        geojson_properties: &GeoJsonProperties,
+        geometry_options: &GeometryOutputOptions,
     ) -> Result<serde_json::Value, OutputPluginError> {
+        let branches = limits.select(tree)?;
         match self {
             TraversalOutputFormat::Wkt => {
-                let route_geometry = ops::create_tree_multilinestring(tree, map_model)?;
-                let route_wkt = route_geometry.wkt_string();
+                let route_geometry = ops::create_tree_multilinestring(&branches, map_model)?;
+                let route_wkt = geometry_options.apply_multi(route_geometry)?.wkt_string();
                 Ok(serde_json::Value::String(route_wkt))
             }
             TraversalOutputFormat::Wkb => {
-                let route_geometry = ops::create_tree_multilinestring(tree, map_model)?;
-                let geometry = geo::Geometry::MultiLineString(route_geometry);
-                let wkb_str = geometry_to_wkb_string(&geometry)?;
+                let route_geometry = ops::create_tree_multilinestring(&branches, map_model)?;
+                let geometry = geometry_options.apply_multi(route_geometry)?;
+                let wkb_str = output_geometry_to_wkb_string(&geometry)?;
                 Ok(serde_json::Value::String(wkb_str))
             }
             TraversalOutputFormat::Json => {
-                let result = serde_json::to_value(tree.values().collect::<Vec<_>>())?;
+                let nodes = branches
+                    .iter()
+                    .filter_map(|b| tree.get_with_state(b.label))
+                    .collect::<Vec<_>>();
+                let result = serde_json::to_value(nodes)?;
                 Ok(result)
             }
             TraversalOutputFormat::GeoJson => {
-                let result = ops::create_tree_geojson(tree, map_model, state_model)?;
+                let result = ops::create_tree_geojson(
+                    &branches,
+                    map_model,
+                    state_model,
+                    geojson_properties,
+                    geometry_options,
+                )?;
                 Ok(result)
             }
             TraversalOutputFormat::EdgeId => {
-                let tree_ids = tree
-                    .values()
-                    .filter_map(|b| b.incoming_edge().map(|e| (e.edge_list_id, e.edge_id)))
+                let tree_ids = branches
+                    .iter()
+                    .map(|b| (b.edge_traversal.edge_list_id, b.edge_traversal.edge_id))
                     .collect::<Vec<_>>();
                 let json = serde_json::json![tree_ids];
                 Ok(json)
             }
+            TraversalOutputFormat::Csv => {
+                let csv = create_tree_csv(&branches);
+                Ok(serde_json::Value::String(csv))
+            }
+            TraversalOutputFormat::EncodedPolyline | TraversalOutputFormat::EncodedPolyline6 => {
+                self.validate_polyline_options(geometry_options)?;
+                let geometry = ops::create_tree_multilinestring(&branches, map_model)?;
+                let geometry =
+                    ops::simplify_multilinestring(geometry, geometry_options.simplify_tolerance);
+                let encoded = geometry
+                    .iter()
+                    .map(|l| polyline::encode_polyline(l, self.polyline_precision()))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(serde_json::json![encoded])
+            }
+            TraversalOutputFormat::ParentTable => {
+                let rows = parent_table::create_parent_table(tree, &branches);
+                Ok(serde_json::Value::String(parent_table::parent_table_csv(
+                    &rows,
+                )))
+            }
+            TraversalOutputFormat::ParentTableArrow => {
+                let rows = parent_table::create_parent_table(tree, &branches);
+                Ok(serde_json::Value::String(parent_table::parent_table_arrow(
+                    &rows,
+                )?))
+            }
+        }
+    }
+
+    /// checks that this format can be used for route outputs
+    pub fn validate_route_format(&self) -> Result<(), OutputPluginError> {
+        match self {
+            TraversalOutputFormat::ParentTable | TraversalOutputFormat::ParentTableArrow => {
+                Err(self.unsupported_route_format())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn unsupported_route_format(&self) -> OutputPluginError {
+        OutputPluginError::BuildFailed(format!(
+            "traversal output format {self:?} is only supported for search trees"
+        ))
+    }
+
+    /// encoded polylines are defined over WGS84 longitude and latitude only
+    fn validate_polyline_options(
+        &self,
+        geometry_options: &GeometryOutputOptions,
+    ) -> Result<(), OutputPluginError> {
+        match geometry_options.projected_crs() {
+            Some(crs) => Err(OutputPluginError::OutputPluginFailed(format!(
+                "encoded polyline output requires WGS84 coordinates, cannot write {crs}"
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// the number of decimal places used by the encoded polyline formats
+    fn polyline_precision(&self) -> u32 {
+        match self {
+            TraversalOutputFormat::EncodedPolyline6 => 6,
+            _ => 5,
         }
     }
 }
 
+fn create_route_csv(route: &[EdgeTraversal]) -> String {
+    let mut csv = String::from("edge_list_id,edge_id,objective_cost,total_cost\n");
+    for et in route.iter() {
+        csv.push_str(&format!(
+            "{},{},{},{}\n",
+            et.edge_list_id, et.edge_id, et.cost.objective_cost, et.cost.total_cost
+        ));
+    }
+    csv
+}
+
+fn create_tree_csv(branches: &[TreeOutputBranch]) -> String {
+    let mut csv = String::from("vertex_id,edge_list_id,edge_id,depth,cost\n");
+    for b in branches.iter() {
+        csv.push_str(&format!(
+            "{},{},{},{},{}\n",
+            b.label.vertex_id(),
+            b.edge_traversal.edge_list_id,
+            b.edge_traversal.edge_id,
+            b.depth,
+            b.cost
+        ));
+    }
+    csv
+}
+
+fn output_geometry_to_wkb_string(geometry: &OutputGeometry) -> Result<String, OutputPluginError> {
+    match geometry {
+        OutputGeometry::Wgs84(g) => geometry_to_wkb_string(g),
+        OutputGeometry::Projected(g) => geometry_to_wkb_string(g),
+    }
+}
+
 fn geometry_to_wkb_string<T: CoordFloat + Into<f64>>(
     geometry: &Geometry<T>,
 ) -> Result<String, OutputPluginError> {
@@ -0,0 +1,217 @@
+use crate::plugin::output::OutputPluginError;
+use routee_compass_core::{
+    algorithm::search::{EdgeTraversal, SearchTree, SearchTreeNode},
+    model::{label::Label, unit::Cost},
+};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+/// options for limiting the size of search tree outputs. branches are ordered by
+/// their cumulative objective cost from the tree root before sampling and truncation,
+/// so that the retained branches stay connected to the root where possible.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct TreeOutputLimits {
+    /// only include branches within this many edges of the tree root
+    pub max_depth: Option<usize>,
+    /// only include branches with a cumulative objective cost at or below this value
+    pub max_cost: Option<f64>,
+    /// only include every Nth branch
+    pub sample_every: Option<usize>,
+    /// include at most this many branches
+    pub max_size: Option<usize>,
+}
+
+/// a branch of a search tree selected for output, along with its depth and cumulative
+/// objective cost from the tree root. the edge traversal holds its result state, which
+/// is copied out of the tree if the tree uses arena storage.
+pub struct TreeOutputBranch<'a> {
+    pub label: &'a Label,
+    pub node: &'a SearchTreeNode,
+    pub edge_traversal: Cow<'a, EdgeTraversal>,
+    pub depth: usize,
+    pub cost: Cost,
+}
+
+impl TreeOutputLimits {
+    /// selects the branches of the search tree that satisfy these limits, ordered
+    /// by cumulative cost from the root.
+    pub fn select<'a>(
+        &self,
+        tree: &'a SearchTree,
+    ) -> Result<Vec<TreeOutputBranch<'a>>, OutputPluginError> {
+        if let Some(0) = self.sample_every {
+            return Err(OutputPluginError::OutputPluginFailed(String::from(
+                "tree output sample_every must be greater than zero",
+            )));
+        }
+        let mut memo: HashMap<&'a Label, (usize, Cost)> = HashMap::with_capacity(tree.len());
+        let mut branches = vec![];
+        for (label, node) in tree.iter() {
+            if node.is_root() {
+                continue;
+            }
+            let (depth, cost) = depth_and_cost(tree, label, &mut memo)?;
+            let within_depth = self.max_depth.map(|d| depth <= d).unwrap_or(true);
+            let within_cost = self.max_cost.map(|c| cost <= Cost::new(c)).unwrap_or(true);
+            if within_depth && within_cost {
+                branches.push((label, node, depth, cost));
+            }
+        }
+        branches.sort_by(|a, b| a.3.cmp(&b.3).then(a.2.cmp(&b.2)));
+
+        // edge traversals are only read from the tree for the selected branches, as
+        // they are copied when the tree uses arena storage
+        let step = self.sample_every.unwrap_or(1);
+        let limit = self.max_size.unwrap_or(usize::MAX);
+        branches
+            .into_iter()
+            .step_by(step)
+            .take(limit)
+            .map(|(label, node, depth, cost)| {
+                let edge_traversal = tree.get_edge_traversal(label).ok_or_else(|| {
+                    OutputPluginError::OutputPluginFailed(format!(
+                        "search tree is missing the edge traversal of label {label}"
+                    ))
+                })?;
+                Ok(TreeOutputBranch {
+                    label,
+                    node,
+                    edge_traversal,
+                    depth,
+                    cost,
+                })
+            })
+            .collect()
+    }
+}
+
+/// finds the number of edges and the cumulative objective cost between the tree root
+/// and the given label, memoizing results for each label visited along the way.
+fn depth_and_cost<'a>(
+    tree: &'a SearchTree,
+    label: &'a Label,
+    memo: &mut HashMap<&'a Label, (usize, Cost)>,
+) -> Result<(usize, Cost), OutputPluginError> {
+    // walk toward the root until reaching a label with a known result or the root itself
+    let mut unresolved: Vec<(&'a Label, Cost)> = vec![];
+    let mut current = label;
+    let (mut depth, mut cost) = loop {
+        if let Some(known) = memo.get(current) {
+            break *known;
+        }
+        let node = tree.get(current).ok_or_else(|| {
+            OutputPluginError::OutputPluginFailed(format!(
+                "search tree is missing label {current} while computing tree output limits"
+            ))
+        })?;
+        match (node.parent_label(), node.incoming_edge()) {
+            (Some(parent), Some(et)) => {
+                unresolved.push((current, et.cost.objective_cost));
+                current = parent;
+            }
+            _ => break (0, Cost::ZERO),
+        }
+        if unresolved.len() > tree.len() {
+            return Err(OutputPluginError::OutputPluginFailed(String::from(
+                "cycle detected in search tree while computing tree output limits",
+            )));
+        }
+    };
+    // resolve from the root back down to the requested label
+    for (label, edge_cost) in unresolved.into_iter().rev() {
+        depth += 1;
+        cost += edge_cost;
+        memo.insert(label, (depth, cost));
+    }
+    Ok((depth, cost))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use routee_compass_core::{
+        algorithm::search::Direction,
+        model::{
+            cost::TraversalCost,
+            label::default::vertex_label_model::VertexLabelModel,
+            network::{EdgeId, EdgeListId, VertexId},
+        },
+    };
+    use std::sync::Arc;
+
+    /// a line tree 0 -> 1 -> 2 -> 3 where each edge has an objective cost of 1
+    // the struct update fills cost_component when built with detailed_costs
+    #[allow(clippy::needless_update)]
+    fn mock_tree() -> SearchTree {
+        let mut tree = SearchTree::with_root(Label::Vertex(VertexId(0)), Direction::Forward);
+        for i in 0..3 {
+            let edge_traversal = EdgeTraversal {
+                edge_list_id: EdgeListId(0),
+                edge_id: EdgeId(i),
+                cost: TraversalCost {
+                    objective_cost: Cost::ONE,
+                    total_cost: Cost::ONE,
+                    ..Default::default()
+                },
+                result_state: vec![],
+            };
+            tree.insert(
+                Label::Vertex(VertexId(i)),
+                edge_traversal,
+                Label::Vertex(VertexId(i + 1)),
+                Arc::new(VertexLabelModel {}),
+            )
+            .expect("test invariant failed");
+        }
+        tree
+    }
+
+    fn selected_edges(limits: TreeOutputLimits) -> Vec<usize> {
+        let tree = mock_tree();
+        limits
+            .select(&tree)
+            .expect("select failed")
+            .iter()
+            .map(|b| b.edge_traversal.edge_id.0)
+            .collect()
+    }
+
+    #[test]
+    fn test_no_limits() {
+        assert_eq!(selected_edges(TreeOutputLimits::default()), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_max_depth() {
+        let limits = TreeOutputLimits {
+            max_depth: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(selected_edges(limits), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_max_cost() {
+        let limits = TreeOutputLimits {
+            max_cost: Some(1.5),
+            ..Default::default()
+        };
+        assert_eq!(selected_edges(limits), vec![0]);
+    }
+
+    #[test]
+    fn test_sample_and_max_size() {
+        let limits = TreeOutputLimits {
+            sample_every: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(selected_edges(limits), vec![0, 2]);
+        let limits = TreeOutputLimits {
+            max_size: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(selected_edges(limits), vec![0]);
+    }
+}
@@ -42,6 +42,10 @@ impl UUIDOutputPlugin {
 }
 
 impl OutputPlugin for UUIDOutputPlugin {
+    fn name(&self) -> &str {
+        "uuid"
+    }
+
     fn process(
         &self,
         output: &mut serde_json::Value,
@@ -0,0 +1,52 @@
+use super::{config::VectorTileConfig, plugin::VectorTileOutputPlugin};
+use crate::{
+    app::compass::CompassComponentError,
+    plugin::{
+        output::{OutputPlugin, OutputPluginBuilder},
+        PluginError,
+    },
+};
+use std::sync::Arc;
+
+/// Builds a plugin that writes route and search tree geometries as Mapbox Vector Tiles.
+///
+/// # Configuration
+///
+/// This plugin expects the following keys:
+/// * `output_directory` - directory where tiles are written as `{query_id}/{z}/{x}/{y}.mvt`
+/// * `min_zoom` - lowest zoom level to write
+/// * `max_zoom` - highest zoom level to write, at most 24
+/// * `route` (optional) - write routes to a "route" layer. defaults to true.
+/// * `tree` (optional) - write search trees to a "tree" layer. defaults to false.
+/// * `extent` (optional) - tile extent. defaults to 4096.
+/// * `id_field` (optional) - query field used to name each query's tile directory.
+///   defaults to "request_id".
+///
+/// # Example Configuration
+///
+/// ```toml
+/// [[plugin.output_plugins]]
+/// type = "vector_tile"
+/// output_directory = "tiles"
+/// min_zoom = 8
+/// max_zoom = 14
+/// tree = true
+/// ```
+///
+pub struct VectorTileOutputPluginBuilder {}
+
+impl OutputPluginBuilder for VectorTileOutputPluginBuilder {
+    fn build(
+        &self,
+        parameters: &serde_json::Value,
+    ) -> Result<Arc<dyn OutputPlugin>, CompassComponentError> {
+        let conf: VectorTileConfig = serde_json::from_value(parameters.clone()).map_err(|e| {
+            PluginError::BuildFailed(format!(
+                "failure reading vector tile output plugin config: {e}"
+            ))
+        })?;
+        let plugin = VectorTileOutputPlugin::new(conf)
+            .map_err(|e| PluginError::OutputPluginFailed { source: e })?;
+        Ok(Arc::new(plugin))
+    }
+}
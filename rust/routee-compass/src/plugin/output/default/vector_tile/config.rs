@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct VectorTileConfig {
+    /// directory where tiles are written, with one sub-directory per query
+    pub output_directory: String,
+    /// lowest zoom level to write
+    pub min_zoom: u8,
+    /// highest zoom level to write
+    pub max_zoom: u8,
+    /// write route geometries to a "route" layer. defaults to true.
+    pub route: Option<bool>,
+    /// write search tree geometries to a "tree" layer. defaults to false.
+    pub tree: Option<bool>,
+    /// tile extent in tile-local coordinates. defaults to 4096.
+    pub extent: Option<u32>,
+    /// query field used to name each query's tile directory. defaults to "request_id".
+    /// queries without this field are named by the order they are processed.
+    pub id_field: Option<String>,
+}
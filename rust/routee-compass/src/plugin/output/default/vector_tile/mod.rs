@@ -0,0 +1,8 @@
+mod builder;
+mod config;
+pub mod mvt;
+mod plugin;
+
+pub use builder::VectorTileOutputPluginBuilder;
+pub use config::VectorTileConfig;
+pub use plugin::VectorTileOutputPlugin;
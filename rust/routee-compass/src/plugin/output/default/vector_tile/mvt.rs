@@ -0,0 +1,324 @@
+use geo::LineString;
+use std::collections::{BTreeMap, HashMap};
+use std::f64::consts::PI;
+
+/// maximum latitude of the web mercator projection
+const MAX_LATITUDE: f64 = 85.051_128_779_806_59;
+
+/// protobuf wire types used by the vector tile specification
+const WIRE_VARINT: u32 = 0;
+const WIRE_FIXED64: u32 = 1;
+const WIRE_LENGTH_DELIMITED: u32 = 2;
+
+/// vector tile geometry commands and feature type
+const COMMAND_MOVE_TO: u32 = 1;
+const COMMAND_LINE_TO: u32 = 2;
+const GEOM_TYPE_LINESTRING: u64 = 2;
+
+/// the address of a tile in the XYZ (slippy map) tiling scheme
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TileId {
+    pub z: u8,
+    pub x: u32,
+    pub y: u32,
+}
+
+/// a feature property value written to a vector tile layer
+#[derive(Clone, Copy, Debug)]
+pub enum TileValue {
+    Uint(u64),
+    Double(f64),
+}
+
+/// a linestring feature with named properties, in WGS84 coordinates
+pub struct TileFeature {
+    pub geometry: LineString<f32>,
+    pub properties: Vec<(&'static str, TileValue)>,
+}
+
+/// projects a WGS84 coordinate to fractional web mercator tile coordinates at a zoom level.
+pub fn project(lon: f64, lat: f64, z: u8) -> (f64, f64) {
+    let n = f64::from(1_u32 << z);
+    let lat = lat.clamp(-MAX_LATITUDE, MAX_LATITUDE).to_radians();
+    let x = (lon + 180.0) / 360.0 * n;
+    let y = (1.0 - (lat.tan() + 1.0 / lat.cos()).ln() / PI) / 2.0 * n;
+    (x, y)
+}
+
+/// finds the tile containing a fractional tile coordinate, clamped to the tile grid.
+fn tile_index(value: f64, z: u8) -> u32 {
+    let max = (1_u32 << z) - 1;
+    (value.floor().max(0.0) as u32).min(max)
+}
+
+/// builds vector tiles at each zoom level in the range for the given layers of features.
+/// features are not clipped to the tile bounds, they are included in every tile their
+/// bounding box intersects.
+pub fn build_tiles(
+    layers: &[(&str, Vec<TileFeature>)],
+    min_zoom: u8,
+    max_zoom: u8,
+    extent: u32,
+) -> BTreeMap<TileId, Vec<u8>> {
+    let mut tiles = BTreeMap::new();
+    for z in min_zoom..=max_zoom {
+        // assign features to each tile intersecting their bounding box
+        let mut assignments: BTreeMap<TileId, Vec<(usize, usize)>> = BTreeMap::new();
+        for (layer_idx, (_, features)) in layers.iter().enumerate() {
+            for (feature_idx, feature) in features.iter().enumerate() {
+                let projected = feature
+                    .geometry
+                    .coords()
+                    .map(|c| project(c.x as f64, c.y as f64, z))
+                    .collect::<Vec<_>>();
+                if projected.is_empty() {
+                    continue;
+                }
+                let (min_x, max_x, min_y, max_y) = projected.iter().fold(
+                    (f64::MAX, f64::MIN, f64::MAX, f64::MIN),
+                    |(x0, x1, y0, y1), (x, y)| (x0.min(*x), x1.max(*x), y0.min(*y), y1.max(*y)),
+                );
+                for x in tile_index(min_x, z)..=tile_index(max_x, z) {
+                    for y in tile_index(min_y, z)..=tile_index(max_y, z) {
+                        let tile_id = TileId { z, x, y };
+                        assignments
+                            .entry(tile_id)
+                            .or_default()
+                            .push((layer_idx, feature_idx));
+                    }
+                }
+            }
+        }
+        for (tile_id, members) in assignments.into_iter() {
+            let encoded = encode_tile(layers, &members, tile_id, extent);
+            if !encoded.is_empty() {
+                tiles.insert(tile_id, encoded);
+            }
+        }
+    }
+    tiles
+}
+
+/// encodes a single tile from the assigned (layer, feature) pairs as a protobuf message.
+fn encode_tile(
+    layers: &[(&str, Vec<TileFeature>)],
+    members: &[(usize, usize)],
+    tile_id: TileId,
+    extent: u32,
+) -> Vec<u8> {
+    let mut tile = vec![];
+    for (layer_idx, (name, features)) in layers.iter().enumerate() {
+        let layer_features = members
+            .iter()
+            .filter(|(l, _)| *l == layer_idx)
+            .map(|(_, f)| &features[*f])
+            .collect::<Vec<_>>();
+        if let Some(layer) = encode_layer(name, &layer_features, tile_id, extent) {
+            write_bytes_field(&mut tile, 3, &layer);
+        }
+    }
+    tile
+}
+
+/// encodes a layer message, returning None if no feature has a valid geometry in this tile.
+fn encode_layer(
+    name: &str,
+    features: &[&TileFeature],
+    tile_id: TileId,
+    extent: u32,
+) -> Option<Vec<u8>> {
+    let mut keys: Vec<&str> = vec![];
+    let mut key_lookup: HashMap<&str, u32> = HashMap::new();
+    let mut values: Vec<TileValue> = vec![];
+    let mut value_lookup: HashMap<(u8, u64), u32> = HashMap::new();
+    let mut encoded_features = vec![];
+
+    for (id, feature) in features.iter().enumerate() {
+        let geometry = match encode_geometry(&feature.geometry, tile_id, extent) {
+            Some(g) => g,
+            None => continue,
+        };
+        let mut tags = vec![];
+        for (key, value) in feature.properties.iter() {
+            let next_key = keys.len() as u32;
+            let key_idx = *key_lookup.entry(key).or_insert_with(|| {
+                keys.push(key);
+                next_key
+            });
+            let value_hash = match value {
+                TileValue::Uint(v) => (0, *v),
+                TileValue::Double(v) => (1, v.to_bits()),
+            };
+            let next_value = values.len() as u32;
+            let value_idx = *value_lookup.entry(value_hash).or_insert_with(|| {
+                values.push(*value);
+                next_value
+            });
+            tags.push(key_idx);
+            tags.push(value_idx);
+        }
+
+        let mut encoded = vec![];
+        write_varint_field(&mut encoded, 1, id as u64);
+        write_packed_field(&mut encoded, 2, &tags);
+        write_varint_field(&mut encoded, 3, GEOM_TYPE_LINESTRING);
+        write_packed_field(&mut encoded, 4, &geometry);
+        encoded_features.push(encoded);
+    }
+    if encoded_features.is_empty() {
+        return None;
+    }
+
+    let mut layer = vec![];
+    write_varint_field(&mut layer, 15, 2);
+    write_bytes_field(&mut layer, 1, name.as_bytes());
+    for feature in encoded_features.iter() {
+        write_bytes_field(&mut layer, 2, feature);
+    }
+    for key in keys.iter() {
+        write_bytes_field(&mut layer, 3, key.as_bytes());
+    }
+    for value in values.iter() {
+        let mut encoded = vec![];
+        match value {
+            TileValue::Uint(v) => write_varint_field(&mut encoded, 5, *v),
+            TileValue::Double(v) => {
+                write_key(&mut encoded, 3, WIRE_FIXED64);
+                encoded.extend_from_slice(&v.to_le_bytes());
+            }
+        }
+        write_bytes_field(&mut layer, 4, &encoded);
+    }
+    write_varint_field(&mut layer, 5, extent as u64);
+    Some(layer)
+}
+
+/// encodes a linestring as vector tile geometry commands in tile-local coordinates.
+/// returns None if the linestring has fewer than two distinct points at this zoom level.
+pub fn encode_geometry(
+    linestring: &LineString<f32>,
+    tile_id: TileId,
+    extent: u32,
+) -> Option<Vec<u32>> {
+    let mut points: Vec<(i64, i64)> = vec![];
+    for coord in linestring.coords() {
+        let (x, y) = project(coord.x as f64, coord.y as f64, tile_id.z);
+        let local_x = ((x - tile_id.x as f64) * extent as f64).round() as i64;
+        let local_y = ((y - tile_id.y as f64) * extent as f64).round() as i64;
+        if points.last() != Some(&(local_x, local_y)) {
+            points.push((local_x, local_y));
+        }
+    }
+    if points.len() < 2 {
+        return None;
+    }
+
+    let mut commands = Vec::with_capacity(points.len() * 2 + 2);
+    let (mut cursor_x, mut cursor_y) = (0_i64, 0_i64);
+    for (idx, (x, y)) in points.iter().enumerate() {
+        if idx == 0 {
+            commands.push(command(COMMAND_MOVE_TO, 1));
+        } else if idx == 1 {
+            commands.push(command(COMMAND_LINE_TO, points.len() as u32 - 1));
+        }
+        commands.push(zigzag(x - cursor_x));
+        commands.push(zigzag(y - cursor_y));
+        cursor_x = *x;
+        cursor_y = *y;
+    }
+    Some(commands)
+}
+
+fn command(id: u32, count: u32) -> u32 {
+    (id & 0x7) | (count << 3)
+}
+
+fn zigzag(value: i64) -> u32 {
+    ((value << 1) ^ (value >> 63)) as u32
+}
+
+fn write_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+fn write_key(buf: &mut Vec<u8>, field: u32, wire_type: u32) {
+    write_varint(buf, ((field << 3) | wire_type) as u64);
+}
+
+fn write_varint_field(buf: &mut Vec<u8>, field: u32, value: u64) {
+    write_key(buf, field, WIRE_VARINT);
+    write_varint(buf, value);
+}
+
+fn write_bytes_field(buf: &mut Vec<u8>, field: u32, bytes: &[u8]) {
+    write_key(buf, field, WIRE_LENGTH_DELIMITED);
+    write_varint(buf, bytes.len() as u64);
+    buf.extend_from_slice(bytes);
+}
+
+fn write_packed_field(buf: &mut Vec<u8>, field: u32, values: &[u32]) {
+    let mut packed = vec![];
+    for value in values.iter() {
+        write_varint(&mut packed, *value as u64);
+    }
+    write_bytes_field(buf, field, &packed);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo::line_string;
+
+    #[test]
+    fn test_project() {
+        let (x, y) = project(0.0, 0.0, 1);
+        assert!((x - 1.0).abs() < 1e-9);
+        assert!((y - 1.0).abs() < 1e-9);
+        let (x, y) = project(-105.0, 40.0, 10);
+        assert_eq!((tile_index(x, 10), tile_index(y, 10)), (213, 387));
+    }
+
+    #[test]
+    fn test_zigzag_and_varint() {
+        assert_eq!(zigzag(0), 0);
+        assert_eq!(zigzag(-1), 1);
+        assert_eq!(zigzag(1), 2);
+        assert_eq!(zigzag(-2), 3);
+        let mut buf = vec![];
+        write_varint(&mut buf, 300);
+        assert_eq!(buf, vec![0xac, 0x02]);
+    }
+
+    #[test]
+    fn test_encode_geometry() {
+        // a line across the center of the single tile at zoom 0
+        let linestring = line_string![(x: -90.0, y: 0.0), (x: 0.0, y: 0.0), (x: 90.0, y: 0.0)];
+        let tile_id = TileId { z: 0, x: 0, y: 0 };
+        let result = encode_geometry(&linestring, tile_id, 4096).expect("should encode");
+        // MoveTo(1) to (1024, 2048), LineTo(2) by (+1024, 0) twice
+        assert_eq!(result, vec![9, 2048, 4096, 18, 2048, 0, 2048, 0]);
+    }
+
+    #[test]
+    fn test_encode_geometry_collapsed() {
+        let linestring = line_string![(x: -105.0, y: 40.0), (x: -105.000001, y: 40.000001)];
+        let tile_id = TileId { z: 0, x: 0, y: 0 };
+        assert!(encode_geometry(&linestring, tile_id, 4096).is_none());
+    }
+
+    #[test]
+    fn test_build_tiles() {
+        let feature = TileFeature {
+            geometry: line_string![(x: -105.0, y: 40.0), (x: -104.0, y: 40.0)],
+            properties: vec![("edge_id", TileValue::Uint(1))],
+        };
+        let layers = vec![("route", vec![feature])];
+        let tiles = build_tiles(&layers, 0, 2, 4096);
+        assert_eq!(tiles.len(), 3);
+        assert!(tiles.keys().all(|t| t.z <= 2));
+    }
+}
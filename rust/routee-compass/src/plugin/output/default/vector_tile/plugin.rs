@@ -0,0 +1,155 @@
+use super::config::VectorTileConfig;
+use super::mvt::{self, TileFeature, TileValue};
+use crate::app::{compass::CompassAppError, search::SearchAppResult};
+use crate::plugin::output::output_plugin::OutputPlugin;
+use crate::plugin::output::OutputPluginError;
+use routee_compass_core::algorithm::search::{EdgeTraversal, SearchInstance};
+use routee_compass_core::model::map::MapModel;
+use serde_json::json;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// the highest zoom level supported by the plugin
+const MAX_ZOOM: u8 = 24;
+
+/// writes route and search tree geometries of each query as Mapbox Vector Tiles
+/// to a directory on disk, for review of search results in a web map.
+pub struct VectorTileOutputPlugin {
+    output_directory: PathBuf,
+    min_zoom: u8,
+    max_zoom: u8,
+    route: bool,
+    tree: bool,
+    extent: u32,
+    id_field: String,
+    query_count: AtomicU64,
+}
+
+impl VectorTileOutputPlugin {
+    pub fn new(conf: VectorTileConfig) -> Result<VectorTileOutputPlugin, OutputPluginError> {
+        if conf.min_zoom > conf.max_zoom {
+            return Err(OutputPluginError::BuildFailed(format!(
+                "vector tile min_zoom {} is greater than max_zoom {}",
+                conf.min_zoom, conf.max_zoom
+            )));
+        }
+        if conf.max_zoom > MAX_ZOOM {
+            return Err(OutputPluginError::BuildFailed(format!(
+                "vector tile max_zoom {} exceeds the maximum zoom level {MAX_ZOOM}",
+                conf.max_zoom
+            )));
+        }
+        Ok(VectorTileOutputPlugin {
+            output_directory: PathBuf::from(conf.output_directory),
+            min_zoom: conf.min_zoom,
+            max_zoom: conf.max_zoom,
+            route: conf.route.unwrap_or(true),
+            tree: conf.tree.unwrap_or_default(),
+            extent: conf.extent.unwrap_or(4096),
+            id_field: conf.id_field.unwrap_or_else(|| String::from("request_id")),
+            query_count: AtomicU64::new(0),
+        })
+    }
+
+    /// names the tile directory for a query by its id field, or else by processing order
+    fn query_id(&self, output: &serde_json::Value) -> String {
+        let query_idx = self.query_count.fetch_add(1, Ordering::SeqCst);
+        match output.get("request").and_then(|r| r.get(&self.id_field)) {
+            Some(serde_json::Value::String(s)) => s.clone(),
+            Some(serde_json::Value::Number(n)) => n.to_string(),
+            _ => query_idx.to_string(),
+        }
+    }
+}
+
+impl OutputPlugin for VectorTileOutputPlugin {
+    fn name(&self) -> &str {
+        "vector_tile"
+    }
+
+    fn process(
+        &self,
+        output: &mut serde_json::Value,
+        search_result: &Result<(SearchAppResult, SearchInstance), CompassAppError>,
+    ) -> Result<(), OutputPluginError> {
+        let (result, si) = match search_result {
+            Err(_) => return Ok(()),
+            Ok((result, si)) => (result, si),
+        };
+
+        let mut layers = vec![];
+        if self.route {
+            let edges = result.routes.iter().flatten();
+            layers.push(("route", create_features(edges, &si.map_model)?));
+        }
+        if self.tree {
+            let edges = result
+                .trees
+                .iter()
+                .flat_map(|t| t.values().filter_map(|n| n.incoming_edge()));
+            layers.push(("tree", create_features(edges, &si.map_model)?));
+        }
+
+        let tiles = mvt::build_tiles(&layers, self.min_zoom, self.max_zoom, self.extent);
+        let query_directory = self.output_directory.join(self.query_id(output));
+        for (tile_id, bytes) in tiles.iter() {
+            let tile_directory = query_directory
+                .join(tile_id.z.to_string())
+                .join(tile_id.x.to_string());
+            std::fs::create_dir_all(&tile_directory).map_err(|e| {
+                OutputPluginError::OutputPluginFailed(format!(
+                    "failure creating vector tile directory {}: {e}",
+                    tile_directory.to_string_lossy()
+                ))
+            })?;
+            let tile_file = tile_directory.join(format!("{}.mvt", tile_id.y));
+            std::fs::write(&tile_file, bytes).map_err(|e| {
+                OutputPluginError::OutputPluginFailed(format!(
+                    "failure writing vector tile {}: {e}",
+                    tile_file.to_string_lossy()
+                ))
+            })?;
+        }
+
+        output["vector_tiles"] = json!({
+            "directory": query_directory.to_string_lossy(),
+            "tile_count": tiles.len(),
+        });
+        Ok(())
+    }
+}
+
+/// creates a tile feature for each edge traversal with its ids and costs as properties
+fn create_features<'a>(
+    edges: impl Iterator<Item = &'a EdgeTraversal>,
+    map_model: &MapModel,
+) -> Result<Vec<TileFeature>, OutputPluginError> {
+    edges
+        .map(|et| {
+            let geometry = map_model
+                .get_linestring(&et.edge_list_id, &et.edge_id)
+                .map_err(|e| {
+                    OutputPluginError::OutputPluginFailed(format!(
+                        "failure building vector tile feature: {e}"
+                    ))
+                })?
+                .clone();
+            let properties = vec![
+                ("edge_list_id", TileValue::Uint(et.edge_list_id.0 as u64)),
+                ("edge_id", TileValue::Uint(et.edge_id.0 as u64)),
+                (
+                    "objective_cost",
+                    TileValue::Double(f64::from(et.cost.objective_cost)),
+                ),
+                (
+                    "total_cost",
+                    TileValue::Double(f64::from(et.cost.total_cost)),
+                ),
+            ];
+            Ok(TileFeature {
+                geometry,
+                properties,
+            })
+        })
+        .collect()
+}
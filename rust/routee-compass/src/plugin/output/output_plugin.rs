@@ -1,6 +1,7 @@
 use super::output_plugin_error::OutputPluginError;
 use crate::app::compass::CompassAppError;
 use crate::app::search::SearchAppResult;
+use crate::plugin::short_type_name;
 use routee_compass_core::algorithm::search::SearchInstance;
 
 /// Performs some kind of post-processing on a search result. The result JSON is available
@@ -13,14 +14,24 @@ use routee_compass_core::algorithm::search::SearchInstance;
 ///
 /// The following default set of output plugin builders are found in the [`super::default`] module:
 ///
+/// * [elevation_profile] - appends the distance vs. elevation profile of each route
 /// * [summary] - simple plugin appends cost and distance to result
 /// * [traversal] - fully-featured plugin for traversal outputs in different formats
 /// * [uuid] - attach the original graph ids to a result
+/// * [vector_tile] - write route and tree geometries as Mapbox Vector Tiles
 ///
+/// [elevation_profile]: super::default::elevation_profile::ElevationProfileOutputPluginBuilder
 /// [summary]: super::default::summary::builder::SummaryOutputPluginBuilder
 /// [traversal]: super::default::traversal::builder::TraversalPluginBuilder
 /// [uuid]: super::default::uuid::builder::UUIDOutputPluginBuilder
+/// [vector_tile]: super::default::vector_tile::VectorTileOutputPluginBuilder
 pub trait OutputPlugin: Send + Sync {
+    /// a name for this plugin used in run metrics. by default, the name of the
+    /// type implementing [`OutputPlugin`].
+    fn name(&self) -> &str {
+        short_type_name(std::any::type_name::<Self>())
+    }
+
     /// Applies this [`OutputPlugin`] to a search result, passing along a JSON
     /// that will replace the `output` JSON argument.
     ///
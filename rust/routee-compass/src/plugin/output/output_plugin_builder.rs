@@ -9,7 +9,7 @@ use std::sync::Arc;
 /// this trait.
 ///
 /// [OutputPlugin]: compass_app::plugin::input::output_plugin::OutputPlugin
-pub trait OutputPluginBuilder {
+pub trait OutputPluginBuilder: Send + Sync {
     /// Builds a [OutputPlugin] from JSON configuration.
     ///
     /// # Arguments
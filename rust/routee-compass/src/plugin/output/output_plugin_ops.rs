@@ -1,9 +1,18 @@
 use crate::app::{
     compass::CompassAppError,
-    search::{SearchApp, SearchAppResult},
+    search::{SearchApp, SearchAppResult, TradeoffQuery},
+};
+use routee_compass_core::{
+    algorithm::search::{a_star::a_star_ops, EdgeTraversal, SearchError, SearchInstance},
+    model::{
+        map::MapJsonKey,
+        network::{EdgeId, EdgeListId},
+        state::{StateVariable, StateVariableConfig},
+        unit::Cost,
+    },
 };
-use routee_compass_core::algorithm::search::SearchInstance;
 use serde_json::{json, Value};
+use std::collections::HashMap;
 
 /// creates the initial output with summary information from the search app,
 /// which happens regardless of the output plugin setup.
@@ -12,12 +21,36 @@ pub fn create_initial_output(
     res: &Result<(SearchAppResult, SearchInstance), CompassAppError>,
     _app: &SearchApp,
 ) -> Result<Value, Value> {
+    let (req, snapping) = split_snap_reports(req);
     match &res {
-        Err(e) => Err(package_error(req, e)),
-        Ok((_, _)) => {
+        Err(e) => {
+            let mut error_output = package_error(&req, e);
+            if let CompassAppError::SearchFailure(SearchError::NoPathExists(diagnostics)) = e {
+                error_output["no_path_diagnostics"] = json!(diagnostics);
+            }
+            if let Some(snapping) = snapping {
+                error_output["snapping"] = snapping;
+            }
+            Err(error_output)
+        }
+        Ok((result, si)) => {
             let mut init_output = serde_json::json!({
                 "request": req,
             });
+            if let Some(snapping) = snapping {
+                init_output["snapping"] = snapping;
+            }
+            if si.heuristic_weight > 1.0 {
+                init_output["weighted_search"] = weighted_search_report(result, si);
+            }
+            let tradeoff = TradeoffQuery::from_query(&req).ok().flatten();
+            if let Some(tradeoff) = &tradeoff {
+                init_output["tradeoff"] = tradeoff_report(tradeoff, result, si);
+            } else if result.routes.len() > 1 {
+                if let Some(report) = alternatives_report(&result.routes, si) {
+                    init_output["alternatives"] = report;
+                }
+            }
 
             let output_plugin_executed_time = chrono::Local::now();
             init_output["output_plugin_executed_time"] =
@@ -28,6 +61,151 @@ pub fn create_initial_output(
     }
 }
 
+/// moves the snap reports written to the request during map matching into a separate
+/// `snapping` object with `origin` and `destination` entries, along with any endpoint
+/// candidates that were retained.
+fn split_snap_reports(req: &Value) -> (Value, Option<Value>) {
+    let mut request = req.clone();
+    let mut snapping = serde_json::Map::new();
+    if let Some(map) = request.as_object_mut() {
+        let keys = [
+            ("origin", MapJsonKey::OriginSnap),
+            ("destination", MapJsonKey::DestinationSnap),
+            ("origin_candidates", MapJsonKey::OriginCandidates),
+            ("destination_candidates", MapJsonKey::DestinationCandidates),
+        ];
+        for (name, key) in keys {
+            if let Some(report) = map.remove(key.as_str()) {
+                snapping.insert(String::from(name), report);
+            }
+        }
+    }
+    let snapping = (!snapping.is_empty()).then_some(Value::Object(snapping));
+    (request, snapping)
+}
+
+/// reports the weight of a weighted A* search along with the largest amount by which the
+/// cost of its (first) route may exceed the optimal route cost.
+fn weighted_search_report(result: &SearchAppResult, si: &SearchInstance) -> Value {
+    let mut report = json!({ "heuristic_weight": si.heuristic_weight });
+    if let Some(route) = result.routes.first() {
+        let route_cost: Cost = route.iter().map(|e| e.cost.objective_cost).sum();
+        let gap = a_star_ops::optimality_gap_bound(route_cost, si.heuristic_weight);
+        report["route_cost"] = json!(route_cost);
+        report["optimality_gap_bound"] = json!(gap);
+    }
+    report
+}
+
+/// reports how much the routes of a multi-route search overlap. the overlap of a route
+/// with another route is the share of its distance on edges that both routes traverse.
+/// each route reports its overlap with the first (shortest) route and its largest
+/// overlap with any other route. returns None if an edge of a route is not in the graph.
+fn alternatives_report(routes: &[Vec<EdgeTraversal>], si: &SearchInstance) -> Option<Value> {
+    let edge_distances = routes
+        .iter()
+        .map(|route| {
+            route
+                .iter()
+                .map(|et| {
+                    let edge = si.graph.get_edge(&et.edge_list_id, &et.edge_id).ok()?;
+                    Some(((et.edge_list_id, et.edge_id), edge.distance.value))
+                })
+                .collect::<Option<HashMap<_, _>>>()
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let overlap = |a: &HashMap<(EdgeListId, EdgeId), f64>,
+                   b: &HashMap<(EdgeListId, EdgeId), f64>| {
+        let total: f64 = a.values().sum();
+        if total <= 0.0 {
+            return 0.0;
+        }
+        let shared: f64 = a
+            .iter()
+            .filter(|(edge, _)| b.contains_key(*edge))
+            .map(|(_, distance)| distance)
+            .sum();
+        shared / total
+    };
+    let route_reports = edge_distances
+        .iter()
+        .enumerate()
+        .map(|(idx, route)| {
+            let max_overlap = edge_distances
+                .iter()
+                .enumerate()
+                .filter(|(other_idx, _)| *other_idx != idx)
+                .map(|(_, other)| overlap(route, other))
+                .fold(0.0, f64::max);
+            json!({
+                "route_index": idx,
+                "overlap_with_shortest": overlap(route, &edge_distances[0]),
+                "max_overlap": max_overlap
+            })
+        })
+        .collect::<Vec<_>>();
+    Some(json!({
+        "route_count": routes.len(),
+        "routes": route_reports
+    }))
+}
+
+/// reports the tradeoff curve of a tradeoff query. each weighting lists the weights of
+/// the two objectives, the cost of its route under that weighting and the route total of
+/// each objective that is a state variable.
+fn tradeoff_report(
+    tradeoff: &TradeoffQuery,
+    result: &SearchAppResult,
+    si: &SearchInstance,
+) -> Value {
+    let [first, second] = &tradeoff.objectives;
+    let features = si
+        .state_model
+        .indexed_iter()
+        .filter(|(_, (name, _))| *name == first || *name == second)
+        .collect::<Vec<_>>();
+    let points = tradeoff
+        .second_objective_weights()
+        .into_iter()
+        .zip(result.routes.iter())
+        .enumerate()
+        .map(|(idx, (weight, route))| {
+            let route_cost: Cost = route.iter().map(|e| e.cost.objective_cost).sum();
+            let mut point = json!({
+                "route_index": idx,
+                "weights": { first: 1.0 - weight, second: weight },
+                "route_cost": route_cost,
+            });
+            for (state_idx, (name, feature)) in features.iter() {
+                let total = route_total(route, *state_idx, feature);
+                let value = total.and_then(|t| feature.serialize_variable(&t).ok());
+                point[name] = json!({ "value": value, "unit": feature.get_unit_name() });
+            }
+            point
+        })
+        .collect::<Vec<_>>();
+    json!({
+        "objectives": tradeoff.objectives,
+        "points": points
+    })
+}
+
+/// the total of a state variable over a route. accumulators hold the route total at the
+/// end of the route, while other state variables are summed over its edges. None for an
+/// empty route with an accumulator.
+pub fn route_total(
+    route: &[EdgeTraversal],
+    state_idx: usize,
+    feature: &StateVariableConfig,
+) -> Option<StateVariable> {
+    match feature.is_accumulator() {
+        true => route.last().map(|e| e.result_state[state_idx]),
+        false => Some(route.iter().fold(StateVariable::ZERO, |acc, e| {
+            acc + e.result_state[state_idx]
+        })),
+    }
+}
+
 /// helper to return errors as JSON response objects which include the
 /// original request along with the error message
 pub fn package_error<E: ToString>(req: &Value, error: E) -> Value {